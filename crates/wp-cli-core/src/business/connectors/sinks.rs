@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
 use orion_conf::ToStructError;
@@ -193,6 +193,77 @@ pub fn list_connectors_usage(
     Ok((conn_map, usage))
 }
 
+/// Sink connector defined in `connectors/sink.d` but not used by any route.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct UnusedConnector {
+    pub id: String,
+    /// File defining the connector
+    pub file: String,
+}
+
+/// Route sink whose `connect` id has no connector definition.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DanglingConnectorRef {
+    pub id: String,
+    /// Route file referencing the connector
+    pub file: String,
+    pub group: String,
+}
+
+/// Cross reference between sink connector definitions and route usage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ConnectorXref {
+    pub unused: Vec<UnusedConnector>,
+    pub dangling: Vec<DanglingConnectorRef>,
+}
+
+impl ConnectorXref {
+    pub fn is_clean(&self) -> bool {
+        self.unused.is_empty() && self.dangling.is_empty()
+    }
+}
+
+/// Find sink connectors never referenced by a route and route references to
+/// undefined connectors. Works on raw route files, so a dangling reference is
+/// reported instead of aborting the analysis like `list_connectors_usage` does.
+pub fn connector_xref(work_root: &str, env_dict: &EnvDict) -> OrionConfResult<ConnectorXref> {
+    let wr = PathBuf::from(work_root);
+    let conn_map = load_sink_connectors(&wr, env_dict)?;
+    let routes = load_routes(&wr, env_dict)?;
+    let mut used: BTreeSet<String> = BTreeSet::new();
+    let mut xref = ConnectorXref::default();
+    for rf in &routes {
+        let sink_root = rf
+            .path
+            .parent()
+            .and_then(|p| p.parent())
+            .unwrap_or_else(|| rf.path.parent().unwrap_or(&wr))
+            .to_path_buf();
+        let conn_map_local = load_sink_connectors(&sink_root, env_dict)?;
+        for s in rf.sink_group.sinks.iter() {
+            let id = s.use_id();
+            if conn_map_local.contains_key(id) || conn_map.contains_key(id) {
+                used.insert(id.to_string());
+            } else {
+                xref.dangling.push(DanglingConnectorRef {
+                    id: id.to_string(),
+                    file: rf.path_str(),
+                    group: rf.sink_group.name.clone(),
+                });
+            }
+        }
+    }
+    for (id, def) in &conn_map {
+        if !used.contains(id) {
+            xref.unused.push(UnusedConnector {
+                id: id.clone(),
+                file: def.origin.clone().unwrap_or_else(|| "-".to_string()),
+            });
+        }
+    }
+    Ok(xref)
+}
+
 #[derive(Debug, Clone)]
 pub struct RouteRow {
     pub scope: String,
@@ -415,6 +486,48 @@ tags = ["sink:json"]
         );
     }
 
+    #[test]
+    fn connector_xref_reports_unused_and_dangling() {
+        let root = tmp_dir("wpcore_sink_xref");
+        write_demo_connectors(&root);
+        let cdir = root.join("connectors").join("sink.d");
+        fs::write(
+            cdir.join("kafka.toml"),
+            r#"[[connectors]]
+id = "kafka_sink"
+type = "kafka"
+"#,
+        )
+        .unwrap();
+        let sink_root = root.join("models").join("sinks");
+        write_demo_route_business(&sink_root);
+        let infra = sink_root.join("infra.d");
+        fs::create_dir_all(&infra).unwrap();
+        fs::write(
+            infra.join("monitor.toml"),
+            r#"version = "2.0"
+
+[sink_group]
+name = "monitor"
+
+[[sink_group.sinks]]
+name = "prom"
+connect = "prometheus_sink"
+"#,
+        )
+        .unwrap();
+
+        let xref = connector_xref(root.to_string_lossy().as_ref(), &EnvDict::test_default())
+            .expect("xref");
+        assert_eq!(xref.unused.len(), 1);
+        assert_eq!(xref.unused[0].id, "kafka_sink");
+        assert!(xref.unused[0].file.ends_with("kafka.toml"));
+        assert_eq!(xref.dangling.len(), 1);
+        assert_eq!(xref.dangling[0].id, "prometheus_sink");
+        assert_eq!(xref.dangling[0].group, "monitor");
+        assert!(xref.dangling[0].file.ends_with("monitor.toml"));
+    }
+
     #[test]
     fn test_validate_flexgroup_oml_rule_mutually_exclusive() {
         // 测试 FlexGroup 中 OML 和 RULE 的互斥验证
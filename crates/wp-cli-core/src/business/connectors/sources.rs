@@ -1,14 +1,15 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-use orion_conf::EnvTomlLoad;
 use orion_conf::error::{ConfIOReason, OrionConfResult};
 use orion_error::UvsValidationFrom;
 use orion_variate::{EnvDict, EnvEvalable};
 use wp_conf::connectors::{
-    ConnectorScope, ParamMap, load_connector_defs_from_dir, merge_params, param_map_to_table,
+    ConnectorScope, ParamMap, load_connector_defs_from_dir, load_resolved_connectors_from_dir,
+    merge_params, param_map_to_table,
 };
 use wp_conf::engine::EngineConfig;
+use wp_conf::loader::env_load_project_toml;
 use wp_conf::sources::{SourceConnector, WpSourcesConfig, find_connectors_dir};
 
 /// A flattened row for listing source connectors and their usages.
@@ -17,7 +18,10 @@ pub struct ConnectorListRow {
     pub id: String,
     pub kind: String,
     pub allow_override: Vec<String>,
+    /// Effective params after `extends` resolution, as one-line TOML
     pub detail: String,
+    /// Inheritance chain, nearest base first
+    pub extends: Vec<String>,
     pub refs: usize,
 }
 
@@ -69,8 +73,8 @@ pub fn list_connectors(
             wpsrc_path.display()
         ))
     })?;
-    let conn_map = load_connectors_map(&conn_base, dict)?;
-    let wp_sources = WpSourcesConfig::env_load_toml(&wpsrc_path, dict)?;
+    let resolved = load_resolved_connectors_from_dir(&conn_base, ConnectorScope::Source, dict)?;
+    let wp_sources: WpSourcesConfig = env_load_project_toml(&wpsrc_path, dict)?;
 
     // Count how many times each connector id is referenced.
     let mut refs: BTreeMap<String, usize> = BTreeMap::new();
@@ -78,14 +82,15 @@ pub fn list_connectors(
         *refs.entry(s.connect.clone()).or_insert(0) += 1;
     }
 
-    let mut rows: Vec<ConnectorListRow> = conn_map
-        .iter()
-        .map(|(id, c)| ConnectorListRow {
-            id: id.clone(),
-            kind: c.kind.clone(),
-            allow_override: c.allow_override.clone(),
-            detail: detail_of(&c.kind, &c.default_params),
-            refs: *refs.get(id).unwrap_or(&0),
+    let mut rows: Vec<ConnectorListRow> = resolved
+        .into_iter()
+        .map(|c| ConnectorListRow {
+            refs: *refs.get(&c.def.id).unwrap_or(&0),
+            detail: detail_of(&c.def.kind, &c.def.default_params),
+            id: c.def.id,
+            kind: c.def.kind,
+            allow_override: c.def.allow_override,
+            extends: c.extends,
         })
         .collect();
     rows.sort_by(|a, b| a.kind.cmp(&b.kind).then(a.id.cmp(&b.id)));
@@ -107,7 +112,8 @@ pub fn route_table(
         ))
     })?;
     let conn_map = load_connectors_map(&conn_base, dict)?;
-    let wrapper = WpSourcesConfig::env_load_toml(&wpsrc_path, dict)?.env_eval(dict);
+    let wrapper: WpSourcesConfig = env_load_project_toml(&wpsrc_path, dict)?;
+    let wrapper = wrapper.env_eval(dict);
     let mut rows: Vec<RouteRow> = Vec::new();
     for src in wrapper.sources.into_iter() {
         let conn = conn_map.get(&src.connect).ok_or_else(|| {
@@ -197,6 +203,45 @@ params_override = { path = "/x" }
         assert_eq!(rows[0].refs, 2);
     }
 
+    #[test]
+    fn list_connectors_shows_resolved_extends() {
+        let root = tmp_dir("wpcore_src_extends");
+        let cdir = root.join("connectors").join("source.d");
+        fs::create_dir_all(&cdir).unwrap();
+        fs::write(
+            cdir.join("c1.toml"),
+            r#"[[connectors]]
+id = "base_src"
+type = "file"
+allow_override = ["path"]
+params = { path = "/data/in.dat", encode = "text" }
+
+[[connectors]]
+id = "c1_src"
+extends = "base_src"
+params = { path = "/data/c1.dat" }
+"#,
+        )
+        .unwrap();
+        let wpsrc = root.join("topology").join("sources");
+        fs::create_dir_all(&wpsrc).unwrap();
+        fs::write(
+            wpsrc.join("wpsrc.toml"),
+            "[[sources]]\nkey = \"s1\"\nconnect = \"c1_src\"\n",
+        )
+        .unwrap();
+
+        let eng = EngineConfig::init(root.to_string_lossy().as_ref());
+        let rows =
+            list_connectors(root.to_string_lossy().as_ref(), &eng, &EnvDict::new()).expect("list");
+        let row = rows.iter().find(|r| r.id == "c1_src").expect("c1_src");
+        assert_eq!(row.extends, vec!["base_src".to_string()]);
+        assert_eq!(row.kind, "file");
+        assert_eq!(row.refs, 1);
+        assert!(row.detail.contains("/data/c1.dat"), "{}", row.detail);
+        assert!(row.detail.contains("encode"), "{}", row.detail);
+    }
+
     #[test]
     fn route_table_merges_overrides_and_renders_params() {
         let root = tmp_dir("wpcore_src_route");
@@ -0,0 +1,339 @@
+//! Rule/OML coverage report
+//!
+//! Joins the project inventory (every WPL rule and OML model, built by
+//! `wp-proj`) with accumulated [`StatsFile`] counters to answer which rules
+//! never matched within the stats window and which models can never produce
+//! output. Several stats files (e.g. one per node or per day) are merged first:
+//! hits are summed, the latest `last_seen` and the earliest `window_start` win.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Serialize;
+
+use crate::utils::stats::StatsFile;
+
+/// One WPL rule of the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleEntry {
+    /// `<package>/<rule>` path, as reported in stats
+    pub rule: String,
+    /// File that defines the rule
+    pub file: String,
+    /// Modification time of `file`; rules changed after the window began are "new"
+    pub modified: Option<SystemTime>,
+}
+
+/// One OML model of the project, including disabled ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelEntry {
+    pub name: String,
+    pub file: String,
+    pub enabled: bool,
+    /// Rule paths matched by the model's `rule` patterns
+    pub rules: Vec<String>,
+    /// Sink groups whose `oml` patterns match the model
+    pub routed_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageInventory {
+    pub rules: Vec<RuleEntry>,
+    pub models: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CoverageStatus {
+    Ok,
+    /// Rule without hits in the window
+    ZeroHit,
+    /// No hits, but the rule (or every rule of the model) changed after the window began
+    New,
+    /// Enabled model whose rules never matched (or that matches no rule at all)
+    Dead,
+    Disabled,
+    /// Disabled model that sink groups still route by name
+    DisabledReferenced,
+}
+
+impl CoverageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoverageStatus::Ok => "ok",
+            CoverageStatus::ZeroHit => "zero-hit",
+            CoverageStatus::New => "new",
+            CoverageStatus::Dead => "dead",
+            CoverageStatus::Disabled => "disabled",
+            CoverageStatus::DisabledReferenced => "disabled-referenced",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleCoverage {
+    pub rule: String,
+    pub file: String,
+    pub hits: u64,
+    pub last_seen: Option<String>,
+    pub status: CoverageStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModelCoverage {
+    pub model: String,
+    pub file: String,
+    pub enabled: bool,
+    pub rules: Vec<String>,
+    pub routed_by: Vec<String>,
+    /// Sum of hits over the matched rules
+    pub hits: u64,
+    pub status: CoverageStatus,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageSummary {
+    pub rules: usize,
+    pub zero_hit: usize,
+    pub new: usize,
+    pub models: usize,
+    pub dead: usize,
+    pub disabled_referenced: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    /// Earliest `window_start` over the stats files
+    pub window_start: Option<String>,
+    pub rules: Vec<RuleCoverage>,
+    pub models: Vec<ModelCoverage>,
+    /// Rules present in stats but no longer in the project (renamed or removed)
+    pub unknown_rules: Vec<String>,
+    pub summary: CoverageSummary,
+}
+
+#[derive(Default)]
+struct RuleHits {
+    hits: u64,
+    last_seen: Option<(DateTime<FixedOffset>, String)>,
+}
+
+/// Build the coverage report; rules and models keep inventory order
+pub fn coverage(inventory: &CoverageInventory, stats: &[StatsFile]) -> CoverageReport {
+    let mut merged: HashMap<&str, RuleHits> = HashMap::new();
+    let mut window: Option<(DateTime<FixedOffset>, &str)> = None;
+    for file in stats {
+        if let Some(start) = file.window_start.as_deref()
+            && let Some(at) = parse_time(start)
+            && window.is_none_or(|(w, _)| at < w)
+        {
+            window = Some((at, start));
+        }
+        for rs in &file.rules {
+            let entry = merged.entry(rs.name.as_str()).or_default();
+            entry.hits += rs.hits;
+            if let Some(seen) = rs.last_seen.as_deref()
+                && let Some(at) = parse_time(seen)
+                && entry.last_seen.as_ref().is_none_or(|(l, _)| at > *l)
+            {
+                entry.last_seen = Some((at, seen.to_string()));
+            }
+        }
+    }
+
+    let mut report = CoverageReport {
+        window_start: window.map(|(_, s)| s.to_string()),
+        ..Default::default()
+    };
+    for entry in &inventory.rules {
+        let (hits, last_seen) = match merged.get(entry.rule.as_str()) {
+            Some(h) => (h.hits, h.last_seen.as_ref().map(|(_, s)| s.clone())),
+            None => (0, None),
+        };
+        let status = if hits > 0 {
+            CoverageStatus::Ok
+        } else if window
+            .is_some_and(|(w, _)| entry.modified.is_some_and(|m| DateTime::<Utc>::from(m) > w))
+        {
+            CoverageStatus::New
+        } else {
+            CoverageStatus::ZeroHit
+        };
+        report.rules.push(RuleCoverage {
+            rule: entry.rule.clone(),
+            file: entry.file.clone(),
+            hits,
+            last_seen,
+            status,
+        });
+    }
+
+    for model in &inventory.models {
+        let matched: Vec<&RuleCoverage> = report
+            .rules
+            .iter()
+            .filter(|r| model.rules.contains(&r.rule))
+            .collect();
+        let hits = matched.iter().map(|r| r.hits).sum();
+        let status = if !model.enabled {
+            if model.routed_by.is_empty() {
+                CoverageStatus::Disabled
+            } else {
+                CoverageStatus::DisabledReferenced
+            }
+        } else if hits > 0 {
+            CoverageStatus::Ok
+        } else if !matched.is_empty() && matched.iter().all(|r| r.status == CoverageStatus::New) {
+            CoverageStatus::New
+        } else {
+            CoverageStatus::Dead
+        };
+        report.models.push(ModelCoverage {
+            model: model.name.clone(),
+            file: model.file.clone(),
+            enabled: model.enabled,
+            rules: model.rules.clone(),
+            routed_by: model.routed_by.clone(),
+            hits,
+            status,
+        });
+    }
+
+    let known: Vec<&str> = inventory.rules.iter().map(|r| r.rule.as_str()).collect();
+    report.unknown_rules = merged
+        .keys()
+        .filter(|name| !known.contains(*name))
+        .map(|name| name.to_string())
+        .collect();
+    report.unknown_rules.sort();
+
+    let count = |st: CoverageStatus| report.rules.iter().filter(|r| r.status == st).count();
+    let count_m = |st: CoverageStatus| report.models.iter().filter(|m| m.status == st).count();
+    report.summary = CoverageSummary {
+        rules: report.rules.len(),
+        zero_hit: count(CoverageStatus::ZeroHit),
+        new: count(CoverageStatus::New),
+        models: report.models.len(),
+        dead: count_m(CoverageStatus::Dead),
+        disabled_referenced: count_m(CoverageStatus::DisabledReferenced),
+    };
+    report
+}
+
+fn parse_time(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(s).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::stats::RuleStat;
+
+    fn rule(name: &str, modified: Option<&str>) -> RuleEntry {
+        RuleEntry {
+            rule: name.to_string(),
+            file: "models/wpl/parse.wpl".to_string(),
+            modified: modified.map(|m| DateTime::parse_from_rfc3339(m).unwrap().into()),
+        }
+    }
+
+    fn model(name: &str, enabled: bool, rules: &[&str], routed_by: &[&str]) -> ModelEntry {
+        ModelEntry {
+            name: name.to_string(),
+            file: format!("models/oml/{}.oml", name),
+            enabled,
+            rules: rules.iter().map(|s| s.to_string()).collect(),
+            routed_by: routed_by.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn stats(window_start: &str, rules: &[(&str, u64, &str)]) -> StatsFile {
+        StatsFile {
+            window_start: Some(window_start.to_string()),
+            rules: rules
+                .iter()
+                .map(|(name, hits, seen)| RuleStat {
+                    name: name.to_string(),
+                    hits: *hits,
+                    last_seen: Some(seen.to_string()),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merges_stats_and_flags_zero_hit_new_and_dead() {
+        let inventory = CoverageInventory {
+            rules: vec![
+                rule("/a/x", None),
+                rule("/a/y", Some("2026-01-01T00:00:00Z")),
+                rule("/a/z", Some("2026-01-09T00:00:00Z")),
+            ],
+            models: vec![
+                model("mx", true, &["/a/x"], &[]),
+                model("my", true, &["/a/y"], &[]),
+                model("mz", true, &["/a/z"], &[]),
+                model("off", false, &["/a/x"], &["biz"]),
+                model("gone", false, &[], &[]),
+            ],
+        };
+        let files = vec![
+            stats(
+                "2026-01-08T00:00:00Z",
+                &[
+                    ("/a/x", 3, "2026-01-10T08:00:00Z"),
+                    ("/old/r", 1, "2026-01-08T01:00:00Z"),
+                ],
+            ),
+            stats(
+                "2026-01-05T00:00:00Z",
+                &[("/a/x", 2, "2026-01-12T08:00:00Z")],
+            ),
+        ];
+        let report = coverage(&inventory, &files);
+
+        assert_eq!(report.window_start.as_deref(), Some("2026-01-05T00:00:00Z"));
+        let x = &report.rules[0];
+        assert_eq!((x.hits, x.status), (5, CoverageStatus::Ok));
+        assert_eq!(x.last_seen.as_deref(), Some("2026-01-12T08:00:00Z"));
+        assert_eq!(report.rules[1].status, CoverageStatus::ZeroHit);
+        assert_eq!(report.rules[2].status, CoverageStatus::New);
+
+        let status: Vec<CoverageStatus> = report.models.iter().map(|m| m.status).collect();
+        assert_eq!(
+            status,
+            vec![
+                CoverageStatus::Ok,
+                CoverageStatus::Dead,
+                CoverageStatus::New,
+                CoverageStatus::DisabledReferenced,
+                CoverageStatus::Disabled,
+            ]
+        );
+        assert_eq!(report.unknown_rules, vec!["/old/r".to_string()]);
+        assert_eq!(
+            report.summary,
+            CoverageSummary {
+                rules: 3,
+                zero_hit: 1,
+                new: 1,
+                models: 5,
+                dead: 1,
+                disabled_referenced: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn without_window_unmatched_rules_are_zero_hit() {
+        let inventory = CoverageInventory {
+            rules: vec![rule("/a/x", Some("2026-01-09T00:00:00Z"))],
+            models: vec![model("empty", true, &[], &[])],
+        };
+        let report = coverage(&inventory, &[]);
+        assert_eq!(report.rules[0].status, CoverageStatus::ZeroHit);
+        assert_eq!(report.models[0].status, CoverageStatus::Dead);
+    }
+}
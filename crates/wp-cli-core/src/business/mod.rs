@@ -4,4 +4,6 @@
 //! configuration loading, data processing, and result aggregation.
 
 pub mod connectors;
+pub mod coverage;
 pub mod observability;
+pub mod trial;
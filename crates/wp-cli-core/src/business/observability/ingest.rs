@@ -0,0 +1,275 @@
+//! Cumulative ingest statistics persisted across engine restarts
+//!
+//! The engine periodically writes `<work_root>/.run/ingest_stats.json` with
+//! lifetime counters (previous runs + current run) and the counters of the
+//! current run alone. The file starts with a checksum header line so that a
+//! truncated or hand-edited file is detected and ignored instead of skewing
+//! the totals.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Runtime directory (relative to work root) holding the checkpoint file
+pub const INGEST_STATS_DIR: &str = ".run";
+/// Checkpoint file name
+pub const INGEST_STATS_FILE: &str = "ingest_stats.json";
+
+const HEADER_PREFIX: &str = "# wparse-ingest-stats v1 fnv1a64=";
+
+/// Per-source counters (keyed by source key)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceTotals {
+    pub received: u64,
+    pub parsed: u64,
+    pub missed: u64,
+}
+
+/// Per-sink counters (keyed by sink name)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SinkTotals {
+    pub sent: u64,
+    pub failed: u64,
+    pub rescued: u64,
+    /// Records dropped by the route schema (`on_conflict = "drop"`)
+    #[serde(default)]
+    pub dropped: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestCounters {
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceTotals>,
+    #[serde(default)]
+    pub sinks: BTreeMap<String, SinkTotals>,
+    /// WPL rule hits (keyed by full rule path)
+    #[serde(default)]
+    pub rules: BTreeMap<String, u64>,
+}
+
+impl IngestCounters {
+    /// Element-wise sum of two counter sets
+    pub fn merged(&self, other: &IngestCounters) -> IngestCounters {
+        let mut out = self.clone();
+        for (key, v) in &other.sources {
+            let e = out.sources.entry(key.clone()).or_default();
+            e.received += v.received;
+            e.parsed += v.parsed;
+            e.missed += v.missed;
+        }
+        for (key, v) in &other.sinks {
+            let e = out.sinks.entry(key.clone()).or_default();
+            e.sent += v.sent;
+            e.failed += v.failed;
+            e.rescued += v.rescued;
+            e.dropped += v.dropped;
+        }
+        for (rule, hits) in &other.rules {
+            *out.rules.entry(rule.clone()).or_default() += hits;
+        }
+        out
+    }
+
+    pub fn total_received(&self) -> u64 {
+        self.sources.values().map(|s| s.received).sum()
+    }
+
+    pub fn total_sent(&self) -> u64 {
+        self.sinks.values().map(|s| s.sent).sum()
+    }
+}
+
+/// Content of the checkpoint file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestCheckpoint {
+    /// RFC 3339 time of the last write
+    #[serde(default)]
+    pub updated_at: String,
+    /// Totals over all runs, including the current one
+    pub lifetime: IngestCounters,
+    /// Totals of the run that wrote the file
+    pub since_start: IngestCounters,
+    /// RFC 3339 time the lifetime totals started accumulating
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_start: Option<String>,
+    /// RFC 3339 time of the checkpoint that first saw each rule's latest hits
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rule_last_seen: BTreeMap<String, String>,
+}
+
+pub fn ingest_stats_path(work_root: &Path) -> PathBuf {
+    work_root.join(INGEST_STATS_DIR).join(INGEST_STATS_FILE)
+}
+
+impl IngestCheckpoint {
+    /// Read a checkpoint; `Ok(None)` when the file does not exist,
+    /// `Err` when it is partial, corrupt or fails the checksum
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("read {}", path.display())),
+        };
+        Self::decode(&text)
+            .with_context(|| format!("ingest stats {}", path.display()))
+            .map(Some)
+    }
+
+    /// Write the checkpoint via a temp file + rename so readers never see a partial file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+        }
+        let mut tmp = path.to_path_buf();
+        tmp.set_extension("json.tmp");
+        std::fs::write(&tmp, self.encode()?).with_context(|| format!("write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("replace {}", path.display()))?;
+        Ok(())
+    }
+
+    fn encode(&self) -> Result<String> {
+        let body = serde_json::to_string_pretty(self)?;
+        Ok(format!(
+            "{}{:016x}\n{}\n",
+            HEADER_PREFIX,
+            fnv1a64(body.as_bytes()),
+            body
+        ))
+    }
+
+    /// Whether `text` looks like a checkpoint file (starts with the checksum header)
+    pub fn is_checkpoint_text(text: &str) -> bool {
+        text.starts_with(HEADER_PREFIX)
+    }
+
+    pub(crate) fn decode(text: &str) -> Result<Self> {
+        let Some((header, body)) = text.split_once('\n') else {
+            bail!("missing checksum header");
+        };
+        let Some(sum) = header.strip_prefix(HEADER_PREFIX) else {
+            bail!("unrecognized header");
+        };
+        let body = body.strip_suffix('\n').unwrap_or(body);
+        let expect = u64::from_str_radix(sum.trim(), 16).context("bad checksum")?;
+        if fnv1a64(body.as_bytes()) != expect {
+            bail!("checksum mismatch");
+        }
+        Ok(serde_json::from_str(body)?)
+    }
+}
+
+/// Lifetime and since-start totals as last recorded by the engine
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestTotalsReport {
+    pub path: String,
+    pub updated_at: String,
+    pub lifetime: IngestCounters,
+    pub since_start: IngestCounters,
+}
+
+/// Load the engine's ingest checkpoint under `work_root`;
+/// returns `None` when absent or unusable (corrupt files are ignored)
+pub fn ingest_totals(work_root: &Path) -> Option<IngestTotalsReport> {
+    let path = ingest_stats_path(work_root);
+    let ckpt = IngestCheckpoint::load(&path).ok().flatten()?;
+    Some(IngestTotalsReport {
+        path: path.display().to_string(),
+        updated_at: ckpt.updated_at,
+        lifetime: ckpt.lifetime,
+        since_start: ckpt.since_start,
+    })
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes
+        .iter()
+        .fold(OFFSET, |h, b| (h ^ u64::from(*b)).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn counters(received: u64, sent: u64) -> IngestCounters {
+        let mut c = IngestCounters::default();
+        c.sources.insert(
+            "file_src".into(),
+            SourceTotals {
+                received,
+                parsed: received,
+                missed: 0,
+            },
+        );
+        c.sinks.insert(
+            "all_sink".into(),
+            SinkTotals {
+                sent,
+                ..Default::default()
+            },
+        );
+        c
+    }
+
+    #[test]
+    fn save_load_roundtrip_and_report() {
+        let dir = TempDir::new().unwrap();
+        let path = ingest_stats_path(dir.path());
+        assert!(IngestCheckpoint::load(&path).unwrap().is_none());
+
+        let ckpt = IngestCheckpoint {
+            updated_at: "2026-01-01T00:00:00Z".into(),
+            lifetime: counters(30, 28),
+            since_start: counters(10, 9),
+            ..Default::default()
+        };
+        ckpt.save(&path).unwrap();
+        assert_eq!(IngestCheckpoint::load(&path).unwrap(), Some(ckpt));
+
+        let report = ingest_totals(dir.path()).unwrap();
+        assert_eq!(report.lifetime.total_received(), 30);
+        assert_eq!(report.since_start.total_sent(), 9);
+    }
+
+    #[test]
+    fn corrupt_or_truncated_file_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = ingest_stats_path(dir.path());
+        IngestCheckpoint {
+            lifetime: counters(5, 5),
+            ..Default::default()
+        }
+        .save(&path)
+        .unwrap();
+        let good = std::fs::read_to_string(&path).unwrap();
+
+        std::fs::write(&path, &good[..good.len() / 2]).unwrap();
+        assert!(IngestCheckpoint::load(&path).is_err());
+        assert!(ingest_totals(dir.path()).is_none());
+
+        std::fs::write(&path, good.replace("\"received\": 5", "\"received\": 500")).unwrap();
+        assert!(IngestCheckpoint::load(&path).is_err());
+
+        std::fs::write(&path, "{}").unwrap();
+        assert!(IngestCheckpoint::load(&path).is_err());
+    }
+
+    #[test]
+    fn merged_sums_per_key() {
+        let mut a = counters(3, 2);
+        a.rules.insert("/nginx/access".into(), 3);
+        let mut b = counters(4, 4);
+        b.sinks.insert("other_sink".into(), SinkTotals::default());
+        b.rules.insert("/nginx/access".into(), 4);
+        b.rules.insert("/nginx/error".into(), 1);
+        let m = a.merged(&b);
+        assert_eq!(m.sources["file_src"].received, 7);
+        assert_eq!(m.sinks["all_sink"].sent, 6);
+        assert_eq!(m.sinks.len(), 2);
+        assert_eq!(m.rules["/nginx/access"], 7);
+        assert_eq!(m.rules["/nginx/error"], 1);
+    }
+}
@@ -3,10 +3,15 @@
 //! This module provides high-level business functions for collecting
 //! observability data about sources and sinks.
 
+mod ingest;
 mod sinks;
 mod sources;
 mod validate;
 
+pub use ingest::{
+    INGEST_STATS_DIR, INGEST_STATS_FILE, IngestCheckpoint, IngestCounters, IngestTotalsReport,
+    SinkTotals, SourceTotals, ingest_stats_path, ingest_totals,
+};
 pub use sinks::{ResolvedSinkLite, collect_sink_statistics, process_group, process_group_v2};
 pub use sources::{
     SrcLineItem, SrcLineReport, list_file_sources_with_lines, total_input_from_wpsrc,
@@ -145,7 +145,11 @@ tol   = 0.0
         assert!(!groups.is_empty() && total > 0);
 
         // denom uses TotalInput (from defaults); we pass override as total from rows
-        let rep = crate::utils::validate::validate_groups(&groups, Some(total));
+        let rep = crate::utils::validate::validate_groups(
+            &groups,
+            Some(total),
+            &crate::utils::ValidateOptions::default(),
+        );
         assert!(!rep.has_error_fail());
     }
 }
@@ -0,0 +1,172 @@
+//! Trial (dry-run) report model
+//!
+//! `wproj prj trial` parses a sample file against a project without any sinks.
+//! The engine side produces one [`TrialLine`] per parsed event; this module
+//! aggregates them into a [`TrialReport`] with per-rule hit counts and the most
+//! frequent errors, shared by the text and JSON renderers.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+/// Default number of distinct errors kept in the summary
+pub const TRIAL_TOP_ERRORS: usize = 5;
+
+/// Outcome of one input event (one line, or several lines joined by multiline preprocessing)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TrialLine {
+    /// 1-based line number of the (first) input line
+    pub line: usize,
+    /// Payload after source preprocessing
+    pub input: String,
+    /// Matched WPL rule; `None` on miss
+    pub rule: Option<String>,
+    /// OML model that transformed the record; `None` when no model matches the rule
+    pub model: Option<String>,
+    /// Final record rendered as JSON
+    pub record: Option<String>,
+    /// Unparsed tail when the rule matched only a prefix of the input
+    pub residue: Option<String>,
+    /// Closest rule on miss (deepest partial match)
+    pub closest_rule: Option<String>,
+    /// Parse error on miss, or OML transform error on hit
+    pub error: Option<String>,
+}
+
+impl TrialLine {
+    pub fn is_hit(&self) -> bool {
+        self.rule.is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrialErrorCount {
+    pub error: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TrialSummary {
+    pub total: usize,
+    pub hit: usize,
+    pub miss: usize,
+    /// Hits with residue (rule matched a prefix only)
+    pub partial: usize,
+    /// Hits per WPL rule
+    pub rules: BTreeMap<String, usize>,
+    /// Most frequent errors (first line of message), by count then message
+    pub top_errors: Vec<TrialErrorCount>,
+}
+
+impl TrialSummary {
+    pub fn from_lines(lines: &[TrialLine], top_n: usize) -> Self {
+        let mut summary = Self {
+            total: lines.len(),
+            ..Default::default()
+        };
+        let mut errors: HashMap<&str, usize> = HashMap::new();
+        for line in lines {
+            match &line.rule {
+                Some(rule) => {
+                    summary.hit += 1;
+                    if line.residue.is_some() {
+                        summary.partial += 1;
+                    }
+                    *summary.rules.entry(rule.clone()).or_default() += 1;
+                }
+                None => summary.miss += 1,
+            }
+            // Errors on hit lines come from OML; misses without one count as "no matching rule"
+            let error = match line.error.as_deref() {
+                Some(e) => e.lines().next().unwrap_or(e),
+                None if line.is_hit() => continue,
+                None => "no matching rule",
+            };
+            *errors.entry(error).or_default() += 1;
+        }
+        let mut top: Vec<TrialErrorCount> = errors
+            .into_iter()
+            .map(|(error, count)| TrialErrorCount {
+                error: error.to_string(),
+                count,
+            })
+            .collect();
+        top.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.error.cmp(&b.error)));
+        top.truncate(top_n);
+        summary.top_errors = top;
+        summary
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TrialReport {
+    pub lines: Vec<TrialLine>,
+    pub summary: TrialSummary,
+}
+
+impl TrialReport {
+    pub fn new(lines: Vec<TrialLine>) -> Self {
+        let summary = TrialSummary::from_lines(&lines, TRIAL_TOP_ERRORS);
+        Self { lines, summary }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(line: usize, rule: &str) -> TrialLine {
+        TrialLine {
+            line,
+            rule: Some(rule.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn miss(line: usize, error: &str) -> TrialLine {
+        TrialLine {
+            line,
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn summary_counts_hits_per_rule_and_top_errors() {
+        let mut partial = hit(3, "/a//x");
+        partial.residue = Some("tail".into());
+        let report = TrialReport::new(vec![
+            hit(1, "/a//x"),
+            hit(2, "/b//y"),
+            partial,
+            miss(4, "not complete\nat depth 3"),
+            miss(5, "not complete"),
+            miss(6, "bad ip"),
+        ]);
+        let s = &report.summary;
+        assert_eq!((s.total, s.hit, s.miss, s.partial), (6, 3, 3, 1));
+        assert_eq!(s.rules.get("/a//x"), Some(&2));
+        assert_eq!(s.rules.get("/b//y"), Some(&1));
+        assert_eq!(
+            s.top_errors,
+            vec![
+                TrialErrorCount {
+                    error: "not complete".into(),
+                    count: 2
+                },
+                TrialErrorCount {
+                    error: "bad ip".into(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn top_errors_are_truncated() {
+        let lines: Vec<TrialLine> = (0..4).map(|i| miss(i, &format!("e{}", i))).collect();
+        let summary = TrialSummary::from_lines(&lines, 2);
+        assert_eq!(summary.top_errors.len(), 2);
+        assert_eq!(summary.top_errors[0].error, "e0");
+    }
+}
@@ -0,0 +1,477 @@
+//! CSV/TSV bulk import into the knowdb layout.
+//!
+//! Column types are inferred from a sample of the source file; the table
+//! definition (`create.sql`/`insert.sql`), a normalized `data.csv` and a
+//! `[[tables]]` entry in `knowdb.toml` are written under `models/knowledge`.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const DEFAULT_SAMPLE_ROWS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Digit,
+    Float,
+    Ip,
+    Chars,
+}
+
+impl ColumnType {
+    fn sql_type(&self) -> &'static str {
+        match self {
+            ColumnType::Digit => "INTEGER",
+            ColumnType::Float => "REAL",
+            ColumnType::Ip | ColumnType::Chars => "TEXT",
+        }
+    }
+
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            ColumnType::Digit => value.parse::<i64>().is_ok(),
+            ColumnType::Float => value.parse::<f64>().is_ok_and(f64::is_finite),
+            ColumnType::Ip => value.parse::<IpAddr>().is_ok(),
+            ColumnType::Chars => true,
+        }
+    }
+
+    /// Like `accepts`, but numbers with leading zeros (`007`, `-01.5`) stay chars:
+    /// storing them as numbers would drop the zeros. Used for inferred columns only.
+    fn fits(&self, value: &str) -> bool {
+        match self {
+            ColumnType::Digit | ColumnType::Float => {
+                self.accepts(value) && !has_leading_zero(value)
+            }
+            _ => self.accepts(value),
+        }
+    }
+
+    /// Narrowest type fitting every non-empty sample value; all-empty columns are chars.
+    fn infer<'a>(values: impl Iterator<Item = &'a str>) -> Self {
+        let mut non_empty = values.filter(|v| !v.is_empty()).peekable();
+        if non_empty.peek().is_none() {
+            return ColumnType::Chars;
+        }
+        let sample: Vec<&str> = non_empty.collect();
+        [ColumnType::Digit, ColumnType::Float, ColumnType::Ip]
+            .into_iter()
+            .find(|ty| sample.iter().all(|v| ty.fits(v)))
+            .unwrap_or(ColumnType::Chars)
+    }
+}
+
+impl Display for ColumnType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ColumnType::Digit => "digit",
+            ColumnType::Float => "float",
+            ColumnType::Ip => "ip",
+            ColumnType::Chars => "chars",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for ColumnType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "digit" => Ok(ColumnType::Digit),
+            "float" => Ok(ColumnType::Float),
+            "ip" => Ok(ColumnType::Ip),
+            "chars" => Ok(ColumnType::Chars),
+            other => bail!("unknown column type '{}' (digit/float/ip/chars)", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// CSV (`,`) or TSV (`.tsv`, tab separated) file with a header row
+    pub source: PathBuf,
+    /// Table name; defaults to the file stem
+    pub table: Option<String>,
+    /// Key column whose values must be unique (`--key`)
+    pub key: Option<String>,
+    /// Type overrides (`--type col=ip`)
+    pub types: Vec<(String, ColumnType)>,
+    /// Only infer and report the schema (`--dry-run`)
+    pub dry_run: bool,
+    /// Rows used for type inference
+    pub sample_rows: usize,
+}
+
+impl ImportOptions {
+    pub fn new(source: impl Into<PathBuf>) -> Self {
+        Self {
+            source: source.into(),
+            table: None,
+            key: None,
+            types: Vec::new(),
+            dry_run: false,
+            sample_rows: DEFAULT_SAMPLE_ROWS,
+        }
+    }
+
+    /// Parse a `col=type` override as given on the command line.
+    pub fn parse_type_override(spec: &str) -> Result<(String, ColumnType)> {
+        let Some((col, ty)) = spec.split_once('=') else {
+            bail!("invalid type override '{}', expected col=type", spec);
+        };
+        Ok((col.trim().to_string(), ty.parse()?))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InferredColumn {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: ColumnType,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub table: String,
+    pub columns: Vec<InferredColumn>,
+    pub key: Option<String>,
+    pub rows: usize,
+    /// Directory the table files were written to; None for a dry run
+    pub table_dir: Option<String>,
+}
+
+impl ImportReport {
+    /// `create.sql` content for the inferred schema.
+    pub fn create_sql(&self) -> String {
+        let cols: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| {
+                let key = if self.key.as_deref() == Some(c.name.as_str()) {
+                    " PRIMARY KEY"
+                } else {
+                    ""
+                };
+                format!("  {} {}{}", c.name, c.ty.sql_type(), key)
+            })
+            .collect();
+        format!(
+            "CREATE TABLE IF NOT EXISTS {{table}} (\n{}\n);\n",
+            cols.join(",\n")
+        )
+    }
+
+    fn insert_sql(&self) -> String {
+        let names: Vec<&str> = self.columns.iter().map(|c| c.name.as_str()).collect();
+        let params: Vec<String> = (1..=names.len()).map(|i| format!("?{}", i)).collect();
+        format!(
+            "INSERT INTO {{table}} ({}) VALUES ({});\n",
+            names.join(", "),
+            params.join(", ")
+        )
+    }
+}
+
+/// Import a CSV/TSV file as a knowdb table under `<work_root>/models/knowledge`.
+pub fn import(work_root: &str, opts: &ImportOptions) -> Result<ImportReport> {
+    let delimiter = if opts
+        .source
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv"))
+    {
+        b'\t'
+    } else {
+        b','
+    };
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .trim(csv::Trim::All)
+        .from_path(&opts.source)
+        .with_context(|| format!("open {}", opts.source.display()))?;
+    let headers: Vec<String> = rdr.headers()?.iter().map(str::to_string).collect();
+    for h in &headers {
+        check_ident("column", h)?;
+    }
+    let mut rows = Vec::new();
+    for rec in rdr.records() {
+        let rec = rec.with_context(|| format!("read {}", opts.source.display()))?;
+        rows.push(rec.iter().map(str::to_string).collect::<Vec<_>>());
+    }
+
+    let sample = &rows[..rows.len().min(opts.sample_rows.max(1))];
+    let mut columns: Vec<InferredColumn> = headers
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| InferredColumn {
+            name: name.clone(),
+            ty: ColumnType::infer(sample.iter().map(move |r| r[idx].as_str())),
+        })
+        .collect();
+    for (col, ty) in &opts.types {
+        match columns.iter_mut().find(|c| &c.name == col) {
+            Some(c) => c.ty = *ty,
+            None => bail!("type override for unknown column '{}'", col),
+        }
+    }
+    for c in columns.iter_mut() {
+        let idx = column_index(&headers, &c.name);
+        let overridden = opts.types.iter().any(|(col, _)| col == &c.name);
+        let valid = |v: &str| {
+            if overridden {
+                c.ty.accepts(v)
+            } else {
+                c.ty.fits(v)
+            }
+        };
+        let Some((line, v)) = rows
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (i + 2, r[idx].as_str()))
+            .find(|(_, v)| !v.is_empty() && !valid(v))
+        else {
+            continue;
+        };
+        // Inferred types fall back to chars beyond the sample; overrides must hold for every row
+        if overridden {
+            bail!(
+                "column '{}' declared {} but line {} has '{}'",
+                c.name,
+                c.ty,
+                line,
+                v
+            );
+        }
+        c.ty = ColumnType::Chars;
+    }
+    if let Some(key) = &opts.key {
+        check_unique_key(&headers, &rows, key)?;
+    }
+
+    let table = match &opts.table {
+        Some(t) => t.clone(),
+        None => opts
+            .source
+            .file_stem()
+            .map(|s| s.to_string_lossy().replace(['-', '.', ' '], "_"))
+            .unwrap_or_default(),
+    };
+    check_ident("table", &table)?;
+    let mut report = ImportReport {
+        table,
+        columns,
+        key: opts.key.clone(),
+        rows: rows.len(),
+        table_dir: None,
+    };
+    if opts.dry_run {
+        return Ok(report);
+    }
+
+    let models_dir = PathBuf::from(work_root).join("models").join("knowledge");
+    fs::create_dir_all(&models_dir)?;
+    let conf_path = models_dir.join("knowdb.toml");
+    let conf_txt = if conf_path.exists() {
+        fs::read_to_string(&conf_path)?
+    } else {
+        "version = 2\n".to_string()
+    };
+    let conf: toml::Table = conf_txt
+        .parse()
+        .with_context(|| format!("parse {}", conf_path.display()))?;
+    if declared_tables(&conf).any(|n| n == report.table) {
+        bail!("table '{}' already declared in knowdb.toml", report.table);
+    }
+    let base_dir = models_dir.join(conf.get("base_dir").and_then(|v| v.as_str()).unwrap_or("."));
+    let csv_conf = conf.get("csv").and_then(|v| v.as_table());
+    let out_delim = csv_conf
+        .and_then(|c| c.get("delimiter"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.bytes().next())
+        .unwrap_or(b',');
+    let has_header = csv_conf
+        .and_then(|c| c.get("has_header"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    let table_dir = base_dir.join(&report.table);
+    if table_dir.exists() {
+        bail!("table directory already exists: {}", table_dir.display());
+    }
+    fs::create_dir_all(&table_dir)?;
+    fs::write(table_dir.join("create.sql"), report.create_sql())?;
+    fs::write(table_dir.join("insert.sql"), report.insert_sql())?;
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(out_delim)
+        .from_path(table_dir.join("data.csv"))?;
+    if has_header {
+        wtr.write_record(&headers)?;
+    }
+    for r in &rows {
+        wtr.write_record(r)?;
+    }
+    wtr.flush()?;
+
+    // The table directory is named after the table, so no `dir` key is needed
+    let columns = if has_header {
+        format!("columns.by_header = {:?}", headers)
+    } else {
+        format!(
+            "columns.by_index = {:?}",
+            (0..headers.len()).collect::<Vec<_>>()
+        )
+    };
+    let mut body = conf_txt;
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    body.push_str(&format!(
+        "\n[[tables]]\nname = \"{}\"\n{}\n",
+        report.table, columns
+    ));
+    fs::write(&conf_path, body)?;
+
+    report.table_dir = Some(table_dir.display().to_string());
+    Ok(report)
+}
+
+fn has_leading_zero(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value).as_bytes();
+    digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit()
+}
+
+fn column_index(headers: &[String], name: &str) -> usize {
+    headers.iter().position(|h| h == name).unwrap_or_default()
+}
+
+fn check_unique_key(headers: &[String], rows: &[Vec<String>], key: &str) -> Result<()> {
+    let Some(idx) = headers.iter().position(|h| h == key) else {
+        bail!("key column '{}' not found in header", key);
+    };
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (i, r) in rows.iter().enumerate() {
+        let line = i + 2;
+        let v = r[idx].as_str();
+        if v.is_empty() {
+            bail!("key column '{}' is empty at line {}", key, line);
+        }
+        if let Some(first) = seen.insert(v, line) {
+            bail!(
+                "duplicate key '{}' in column '{}' at lines {} and {}",
+                v,
+                key,
+                first,
+                line
+            );
+        }
+    }
+    Ok(())
+}
+
+fn declared_tables(conf: &toml::Table) -> impl Iterator<Item = &str> {
+    conf.get("tables")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.get("name").and_then(|n| n.as_str()))
+}
+
+fn check_ident(kind: &str, name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        bail!("invalid {} name '{}' (letters, digits and '_')", kind, name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_prefers_narrowest_type() {
+        assert_eq!(
+            ColumnType::infer(["1", "", "-3"].into_iter()),
+            ColumnType::Digit
+        );
+        assert_eq!(
+            ColumnType::infer(["1", "2.5"].into_iter()),
+            ColumnType::Float
+        );
+        assert_eq!(
+            ColumnType::infer(["10.0.0.1", "::1"].into_iter()),
+            ColumnType::Ip
+        );
+        assert_eq!(
+            ColumnType::infer(["10.0.0.1", "x"].into_iter()),
+            ColumnType::Chars
+        );
+        assert_eq!(ColumnType::infer(["", ""].into_iter()), ColumnType::Chars);
+    }
+
+    #[test]
+    fn infer_keeps_leading_zeros_as_chars() {
+        assert_eq!(
+            ColumnType::infer(["007", "12"].into_iter()),
+            ColumnType::Chars
+        );
+        assert_eq!(
+            ColumnType::infer(["-01.5", "2.0"].into_iter()),
+            ColumnType::Chars
+        );
+        assert_eq!(
+            ColumnType::infer(["0", "-0.5", "10"].into_iter()),
+            ColumnType::Float
+        );
+        assert_eq!(
+            ColumnType::infer(["0", "100"].into_iter()),
+            ColumnType::Digit
+        );
+    }
+
+    #[test]
+    fn dry_run_reports_schema_and_rejects_duplicate_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("hosts.tsv");
+        fs::write(
+            &src,
+            "ip\tname\tweight\n10.0.0.1\tweb\t1.5\n10.0.0.2\tdb\t2\n",
+        )
+        .unwrap();
+        let mut opts = ImportOptions::new(&src);
+        opts.dry_run = true;
+        opts.key = Some("ip".into());
+        opts.types
+            .push(ImportOptions::parse_type_override("name=chars").unwrap());
+        let root = dir.path().to_string_lossy().to_string();
+        let report = import(&root, &opts).unwrap();
+        assert_eq!(report.table, "hosts");
+        let types: Vec<ColumnType> = report.columns.iter().map(|c| c.ty).collect();
+        assert_eq!(
+            types,
+            vec![ColumnType::Ip, ColumnType::Chars, ColumnType::Float]
+        );
+        assert!(report.create_sql().contains("ip TEXT PRIMARY KEY"));
+        assert!(report.table_dir.is_none());
+        assert!(!dir.path().join("models").exists());
+
+        fs::write(&src, "ip\tname\n10.0.0.1\tweb\n10.0.0.1\tdb\n").unwrap();
+        let err = import(&root, &opts).unwrap_err().to_string();
+        assert!(err.contains("lines 2 and 3"), "{}", err);
+
+        opts.key = None;
+        opts.types = vec![("ip".into(), ColumnType::Digit)];
+        let err = import(&root, &opts).unwrap_err().to_string();
+        assert!(err.contains("declared digit"), "{}", err);
+    }
+}
@@ -5,6 +5,9 @@ use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod import;
+pub use import::{ColumnType, ImportOptions, ImportReport, InferredColumn, import};
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TableCheck {
     pub name: String,
@@ -2,4 +2,7 @@
 
 mod stat;
 
-pub use stat::{RescueFileStat, RescueStatSummary, SinkRescueStat, scan_rescue_stat};
+pub use stat::{
+    DEFAULT_RESCUE_AGE_THRESHOLD, RescueFileStat, RescueStatSummary, SinkRescueStat,
+    scan_rescue_stat,
+};
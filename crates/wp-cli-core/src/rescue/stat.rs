@@ -4,10 +4,22 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+/// rescue 积压告警的默认阈值：最旧未投递数据超过 1 小时
+pub const DEFAULT_RESCUE_AGE_THRESHOLD: Duration = Duration::from_secs(3600);
+
+/// rescue 文件名中的时间戳格式（UTC），见 `<sink>-YYYY-MM-DD_HH:MM:SS[-seq].dat`
+const RESCUE_TIME_FMT: &str = "%Y-%m-%d_%H:%M:%S";
+const RESCUE_TIME_LEN: usize = 19;
+
+/// 条目头检查读取的字节数
+const ENTRY_HEADER_LEN: usize = 64;
+
 /// 单个 rescue 文件的统计信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RescueFileStat {
@@ -19,6 +31,9 @@ pub struct RescueFileStat {
     pub size_bytes: u64,
     /// 记录条数
     pub line_count: usize,
+    /// 无法回放的损坏条目数
+    #[serde(default)]
+    pub corrupt_entries: usize,
     /// 文件创建/修改时间
     pub modified_time: Option<String>,
 }
@@ -34,10 +49,19 @@ pub struct RescueStatSummary {
     pub total_lines: usize,
     /// 总字节数
     pub total_bytes: u64,
+    /// 损坏条目总数
+    #[serde(default)]
+    pub total_corrupt: usize,
+    /// 含损坏条目的文件
+    #[serde(default)]
+    pub corrupt_files: Vec<String>,
     /// 按 sink 分组的统计
     pub by_sink: HashMap<String, SinkRescueStat>,
     /// 各文件详情（可选）
     pub files: Vec<RescueFileStat>,
+    /// 所有 sink 中最旧未投递数据的积压时长
+    #[serde(default, with = "opt_duration_secs")]
+    pub max_age: Option<Duration>,
 }
 
 /// 按 sink 分组的统计
@@ -51,9 +75,67 @@ pub struct SinkRescueStat {
     pub line_count: usize,
     /// 字节数
     pub size_bytes: u64,
+    /// 损坏条目数（截断或非 JSON 的行，不会被删除）
+    #[serde(default)]
+    pub corrupt_entries: usize,
+    /// 最旧未投递数据的积压时长（按文件名时间戳，缺失时取修改时间）
+    #[serde(default, with = "opt_duration_secs")]
+    pub oldest_entry_age: Option<Duration>,
 }
 
 impl RescueStatSummary {
+    /// 积压时长超过阈值时返回告警信息
+    pub fn age_warning(&self, threshold: Duration) -> Option<String> {
+        let max_age = self.max_age.filter(|age| *age > threshold)?;
+        let mut stale: Vec<&SinkRescueStat> = self
+            .by_sink
+            .values()
+            .filter(|s| s.oldest_entry_age.is_some_and(|age| age > threshold))
+            .collect();
+        stale.sort_by(|a, b| b.oldest_entry_age.cmp(&a.oldest_entry_age));
+        let sinks: Vec<&str> = stale.iter().map(|s| s.sink_name.as_str()).collect();
+        Some(format!(
+            "rescue 数据积压 {} 超过阈值 {}，涉及 sink: {}",
+            format_age(max_age),
+            format_age(threshold),
+            sinks.join(", ")
+        ))
+    }
+
+    /// 积压时长超过阈值时向 stderr 打印告警，返回是否告警
+    pub fn print_age_warning(&self, threshold: Duration) -> bool {
+        match self.age_warning(threshold) {
+            Some(msg) => {
+                eprintln!("WARN: {}", msg);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 存在损坏条目时返回告警信息
+    pub fn corrupt_warning(&self) -> Option<String> {
+        if self.total_corrupt == 0 {
+            return None;
+        }
+        Some(format!(
+            "rescue 数据含 {} 条损坏条目，回放时将失败，涉及文件: {}",
+            self.total_corrupt,
+            self.corrupt_files.join(", ")
+        ))
+    }
+
+    /// 存在损坏条目时向 stderr 打印告警，返回是否告警
+    pub fn print_corrupt_warning(&self) -> bool {
+        match self.corrupt_warning() {
+            Some(msg) => {
+                eprintln!("WARN: {}", msg);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// 以表格形式打印统计信息
     pub fn print_table(&self, detail: bool) {
         println!("Rescue 数据统计");
@@ -66,25 +148,32 @@ impl RescueStatSummary {
             format_bytes(self.total_bytes),
             self.total_bytes
         );
+        if let Some(age) = self.max_age {
+            println!("最长积压: {}", format_age(age));
+        }
+        if self.total_corrupt > 0 {
+            println!("损坏条目: {}", self.total_corrupt);
+        }
         println!();
 
         if !self.by_sink.is_empty() {
             println!("按 Sink 分组:");
             println!(
-                "{:<30} {:>10} {:>12} {:>15}",
-                "Sink", "Files", "Lines", "Size"
+                "{:<30} {:>10} {:>12} {:>15} {:>12}",
+                "Sink", "Files", "Lines", "Size", "Oldest"
             );
-            println!("{}", "-".repeat(70));
+            println!("{}", "-".repeat(83));
             for stat in self.by_sink.values() {
                 println!(
-                    "{:<30} {:>10} {:>12} {:>15}",
+                    "{:<30} {:>10} {:>12} {:>15} {:>12}",
                     stat.sink_name,
                     stat.file_count,
                     stat.line_count,
-                    format_bytes(stat.size_bytes)
+                    format_bytes(stat.size_bytes),
+                    stat.oldest_entry_age.map(format_age).unwrap_or_default()
                 );
             }
-            println!("{}", "-".repeat(70));
+            println!("{}", "-".repeat(83));
         }
 
         if detail && !self.files.is_empty() {
@@ -127,11 +216,17 @@ impl RescueStatSummary {
                 );
             }
         } else {
-            println!("sink_name,file_count,line_count,size_bytes");
+            println!("sink_name,file_count,line_count,size_bytes,oldest_entry_age_secs");
             for stat in self.by_sink.values() {
                 println!(
-                    "{},{},{},{}",
-                    stat.sink_name, stat.file_count, stat.line_count, stat.size_bytes
+                    "{},{},{},{},{}",
+                    stat.sink_name,
+                    stat.file_count,
+                    stat.line_count,
+                    stat.size_bytes,
+                    stat.oldest_entry_age
+                        .map(|age| age.as_secs().to_string())
+                        .unwrap_or_default()
                 );
             }
         }
@@ -155,6 +250,35 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// 格式化积压时长，如 `2h05m`、`3m20s`
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs >= 86400 {
+        format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600)
+    } else if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// 从文件名解析写入时间：`<sink>-YYYY-MM-DD_HH:MM:SS[-seq].dat`，时间为 UTC
+fn parse_entry_time(path: &Path) -> Option<SystemTime> {
+    let stem = path.file_stem()?.to_str()?;
+    // 去掉可选的序号后缀
+    let stem = match stem.rsplit_once('-') {
+        Some((head, seq)) if !seq.is_empty() && seq.bytes().all(|b| b.is_ascii_digit()) => head,
+        _ => stem,
+    };
+    let ts = stem.get(stem.len().checked_sub(RESCUE_TIME_LEN)?..)?;
+    let naive = NaiveDateTime::parse_from_str(ts, RESCUE_TIME_FMT).ok()?;
+    Some(SystemTime::from(
+        DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc),
+    ))
+}
+
 /// 从文件名解析 sink 名称
 fn parse_sink_name(path: &Path, rescue_root: &Path) -> String {
     let rel_path = path.strip_prefix(rescue_root).unwrap_or(path);
@@ -175,12 +299,33 @@ fn parse_sink_name(path: &Path, rescue_root: &Path) -> String {
     sink_id.to_string()
 }
 
-/// 统计单个文件的行数
-fn count_lines(path: &Path) -> usize {
-    match File::open(path) {
-        Ok(file) => BufReader::new(file).lines().count(),
-        Err(_) => 0,
+/// 检查条目头：前 64 字节须为 JSON 对象起始并含 `version`/`kind` 键，且行以 `}` 结束（排除截断写入）
+fn entry_is_intact(line: &[u8]) -> bool {
+    let line = line.trim_ascii();
+    let header = &line[..line.len().min(ENTRY_HEADER_LEN)];
+    let has_key = |key: &[u8]| header.windows(key.len()).any(|w| w == key);
+    header.starts_with(b"{\"")
+        && (has_key(b"\"version\"") || has_key(b"\"kind\""))
+        && line.ends_with(b"}")
+}
+
+/// 统计单个文件的行数与损坏条目数（空行不计为损坏）
+fn inspect_entries(path: &Path) -> (usize, usize) {
+    let Ok(file) = File::open(path) else {
+        return (0, 0);
+    };
+    let mut lines = 0;
+    let mut corrupt = 0;
+    for line in BufReader::new(file).split(b'\n') {
+        let Ok(line) = line else {
+            break;
+        };
+        lines += 1;
+        if !line.trim_ascii().is_empty() && !entry_is_intact(&line) {
+            corrupt += 1;
+        }
     }
+    (lines, corrupt)
 }
 
 /// 扫描 rescue 目录并统计数据
@@ -190,6 +335,7 @@ pub fn scan_rescue_stat(rescue_path: &str, include_detail: bool) -> RescueStatSu
         ..Default::default()
     };
 
+    let now = SystemTime::now();
     let rescue_dir = Path::new(rescue_path);
     if !rescue_dir.exists() {
         return summary;
@@ -213,8 +359,11 @@ pub fn scan_rescue_stat(rescue_path: &str, include_detail: bool) -> RescueStatSu
         };
 
         let size = metadata.len();
-        let line_count = count_lines(path);
+        let (line_count, corrupt_entries) = inspect_entries(path);
         let sink_name = parse_sink_name(path, rescue_dir);
+        let entry_age = parse_entry_time(path)
+            .or_else(|| metadata.modified().ok())
+            .map(|t| now.duration_since(t).unwrap_or_default());
         let modified_time = metadata.modified().ok().map(|t| {
             chrono::DateTime::<chrono::Local>::from(t)
                 .format("%Y-%m-%d %H:%M:%S")
@@ -225,6 +374,10 @@ pub fn scan_rescue_stat(rescue_path: &str, include_detail: bool) -> RescueStatSu
         summary.total_files += 1;
         summary.total_lines += line_count;
         summary.total_bytes += size;
+        summary.total_corrupt += corrupt_entries;
+        if corrupt_entries > 0 {
+            summary.corrupt_files.push(path.display().to_string());
+        }
 
         // 按 sink 分组
         let sink_stat =
@@ -238,6 +391,11 @@ pub fn scan_rescue_stat(rescue_path: &str, include_detail: bool) -> RescueStatSu
         sink_stat.file_count += 1;
         sink_stat.line_count += line_count;
         sink_stat.size_bytes += size;
+        sink_stat.corrupt_entries += corrupt_entries;
+        if let Some(age) = entry_age {
+            sink_stat.oldest_entry_age = sink_stat.oldest_entry_age.max(Some(age));
+            summary.max_age = summary.max_age.max(Some(age));
+        }
 
         // 文件详情
         if include_detail {
@@ -246,6 +404,7 @@ pub fn scan_rescue_stat(rescue_path: &str, include_detail: bool) -> RescueStatSu
                 sink_name,
                 size_bytes: size,
                 line_count,
+                corrupt_entries,
                 modified_time,
             });
         }
@@ -253,10 +412,29 @@ pub fn scan_rescue_stat(rescue_path: &str, include_detail: bool) -> RescueStatSu
 
     // 按修改时间排序文件
     summary.files.sort_by(|a, b| a.path.cmp(&b.path));
+    summary.corrupt_files.sort();
 
     summary
 }
 
+/// `Option<Duration>` 以秒数序列化，便于 JSON 输出与脚本处理
+mod opt_duration_secs {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Some(d) => s.serialize_some(&d.as_secs()),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<u64>::deserialize(d)?.map(Duration::from_secs))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,6 +471,77 @@ mod tests {
         assert_eq!(parse_sink_name(path2, root), "group/kafka_sink");
     }
 
+    #[test]
+    fn test_parse_entry_time() {
+        let expect =
+            SystemTime::from(DateTime::parse_from_rfc3339("2024-01-15T10:30:00Z").unwrap());
+        let path = Path::new("./rescue/http_sink-2024-01-15_10:30:00.dat");
+        assert_eq!(parse_entry_time(path), Some(expect));
+        let with_seq = Path::new("./rescue/http_sink-2024-01-15_10:30:00-17.dat");
+        assert_eq!(parse_entry_time(with_seq), Some(expect));
+        assert_eq!(parse_entry_time(Path::new("./rescue/manual.dat")), None);
+    }
+
+    #[test]
+    fn test_age_warning_threshold() {
+        let dir = setup_test_rescue_dir("age_warning");
+        let recent = Utc::now().format(RESCUE_TIME_FMT).to_string();
+        fs::write(format!("{}/sink_a-2024-01-15_10:30:00-1.dat", dir), "l\n").unwrap();
+        fs::write(format!("{}/sink_a-{}-2.dat", dir, recent), "l\n").unwrap();
+        fs::write(format!("{}/sink_b-{}-3.dat", dir, recent), "l\n").unwrap();
+
+        let summary = scan_rescue_stat(&dir, false);
+        let age_a = summary.by_sink["sink_a"].oldest_entry_age.unwrap();
+        let age_b = summary.by_sink["sink_b"].oldest_entry_age.unwrap();
+        assert!(age_a > DEFAULT_RESCUE_AGE_THRESHOLD);
+        assert!(age_b < DEFAULT_RESCUE_AGE_THRESHOLD);
+        assert_eq!(summary.max_age, Some(age_a));
+
+        let msg = summary
+            .age_warning(DEFAULT_RESCUE_AGE_THRESHOLD)
+            .expect("stale rescue data");
+        assert!(msg.contains("sink_a") && !msg.contains("sink_b"));
+        assert!(
+            summary
+                .age_warning(age_a + Duration::from_secs(60))
+                .is_none()
+        );
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["max_age"].as_u64(), Some(age_a.as_secs()));
+
+        cleanup_test_dir(&dir);
+    }
+
+    #[test]
+    fn test_corrupt_entries() {
+        let dir = setup_test_rescue_dir("corrupt");
+        let good = r#"{"version":1,"kind":"raw","raw":"a"}"#;
+        let truncated = r#"{"version":1,"kind":"raw","raw":"#;
+        fs::write(
+            format!("{}/sink_a-2024-01-15_10:30:00.dat", dir),
+            format!("{good}\n\n{good}\n"),
+        )
+        .unwrap();
+        let bad_file = format!("{}/sink_b-2024-01-15_10:30:00.dat", dir);
+        fs::write(&bad_file, format!("{good}\n{truncated}\nplain text\n")).unwrap();
+
+        let summary = scan_rescue_stat(&dir, true);
+        assert_eq!(summary.total_lines, 6);
+        assert_eq!(summary.total_corrupt, 2);
+        assert_eq!(summary.by_sink["sink_a"].corrupt_entries, 0);
+        assert_eq!(summary.by_sink["sink_b"].corrupt_entries, 2);
+        assert_eq!(summary.corrupt_files, vec![bad_file.clone()]);
+        // 损坏条目只统计不删除
+        assert!(Path::new(&bad_file).exists());
+
+        let msg = summary.corrupt_warning().expect("corrupt warning");
+        assert!(msg.contains(&bad_file));
+        assert!(RescueStatSummary::default().corrupt_warning().is_none());
+
+        cleanup_test_dir(&dir);
+    }
+
     #[test]
     fn test_scan_empty_directory() {
         let dir = setup_test_rescue_dir("empty");
@@ -397,5 +646,7 @@ mod tests {
         assert_eq!(summary.total_lines, 0);
         assert_eq!(summary.total_bytes, 0);
         assert!(summary.rescue_path.is_empty());
+        assert!(summary.max_age.is_none());
+        assert!(summary.age_warning(Duration::ZERO).is_none());
     }
 }
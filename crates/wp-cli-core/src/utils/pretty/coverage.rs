@@ -0,0 +1,81 @@
+use super::helpers::{color_err, color_warn};
+use crate::business::coverage::{CoverageReport, CoverageStatus};
+use comfy_table::{
+    Cell, CellAlignment, ContentArrangement, Row as CRow, Table, presets::ASCII_MARKDOWN,
+};
+
+fn status_cell(st: CoverageStatus) -> String {
+    match st {
+        CoverageStatus::Ok | CoverageStatus::Disabled => st.as_str().to_string(),
+        CoverageStatus::New => color_warn(st.as_str()),
+        CoverageStatus::ZeroHit | CoverageStatus::Dead | CoverageStatus::DisabledReferenced => {
+            color_err(st.as_str())
+        }
+    }
+}
+
+/// Print rule and model coverage in table form.
+/// Rule columns: Rule | Hits | Last Seen | Status; model columns: Model | Enabled | Rules | Hits | Routed By | Status
+pub fn print_coverage(rep: &CoverageReport) {
+    let mut rules = Table::new();
+    rules.load_preset(ASCII_MARKDOWN);
+    rules.set_content_arrangement(ContentArrangement::Dynamic);
+    rules.set_header(vec!["Rule", "Hits", "Last Seen", "Status"]);
+    for it in &rep.rules {
+        let mut row = CRow::new();
+        row.add_cell(Cell::new(&it.rule).set_alignment(CellAlignment::Left));
+        row.add_cell(Cell::new(it.hits.to_string()).set_alignment(CellAlignment::Right));
+        row.add_cell(
+            Cell::new(it.last_seen.as_deref().unwrap_or("-")).set_alignment(CellAlignment::Left),
+        );
+        row.add_cell(Cell::new(status_cell(it.status)).set_alignment(CellAlignment::Left));
+        rules.add_row(row);
+    }
+    println!("{}", rules);
+
+    let mut models = Table::new();
+    models.load_preset(ASCII_MARKDOWN);
+    models.set_content_arrangement(ContentArrangement::Dynamic);
+    models.set_header(vec![
+        "Model",
+        "Enabled",
+        "Rules",
+        "Hits",
+        "Routed By",
+        "Status",
+    ]);
+    for it in &rep.models {
+        let mut row = CRow::new();
+        row.add_cell(Cell::new(&it.model).set_alignment(CellAlignment::Left));
+        row.add_cell(
+            Cell::new(if it.enabled { "Y" } else { "N" }).set_alignment(CellAlignment::Center),
+        );
+        row.add_cell(Cell::new(it.rules.len().to_string()).set_alignment(CellAlignment::Right));
+        row.add_cell(Cell::new(it.hits.to_string()).set_alignment(CellAlignment::Right));
+        let routed = if it.routed_by.is_empty() {
+            "-".to_string()
+        } else {
+            it.routed_by.join(",")
+        };
+        row.add_cell(Cell::new(routed).set_alignment(CellAlignment::Left));
+        row.add_cell(Cell::new(status_cell(it.status)).set_alignment(CellAlignment::Left));
+        models.add_row(row);
+    }
+    println!("\n{}", models);
+
+    let s = &rep.summary;
+    println!(
+        "\nWindow start: {}",
+        rep.window_start.as_deref().unwrap_or("-")
+    );
+    println!(
+        "Rules: {} (zero-hit {}, new {}); Models: {} (dead {}, disabled-referenced {})",
+        s.rules, s.zero_hit, s.new, s.models, s.dead, s.disabled_referenced
+    );
+    if !rep.unknown_rules.is_empty() {
+        println!(
+            "Rules in stats but not in project: {}",
+            rep.unknown_rules.join(", ")
+        );
+    }
+}
@@ -1,8 +1,10 @@
+pub mod coverage;
 pub mod helpers;
 pub mod sinks;
 pub mod sources;
 pub mod validate;
 
+pub use coverage::print_coverage;
 pub use sinks::print_rows;
 pub use sources::print_src_files_table;
 pub use validate::{
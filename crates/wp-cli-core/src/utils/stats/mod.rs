@@ -6,4 +6,4 @@
 #[allow(clippy::module_inception)]
 pub mod stats;
 
-pub use stats::{StatsFile, group_input, load_stats_file};
+pub use stats::{RuleStat, StatsFile, group_input, load_stats_file};
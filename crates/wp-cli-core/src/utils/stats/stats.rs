@@ -1,6 +1,9 @@
+use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::path::Path;
 
+use crate::business::observability::IngestCheckpoint;
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct SinkStat {
     pub name: String,
@@ -17,17 +20,63 @@ pub struct GroupStat {
     pub sinks: Vec<SinkStat>,
 }
 
+/// Accumulated hits of one WPL rule (`<package>/<rule>` path)
 #[derive(Debug, Deserialize, Clone)]
+pub struct RuleStat {
+    pub name: String,
+    #[serde(default)]
+    pub hits: u64,
+    /// RFC 3339 time of the last hit
+    #[serde(default)]
+    pub last_seen: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct StatsFile {
     #[serde(default)]
     pub total_input: u64,
     #[serde(default)]
     pub groups: Vec<GroupStat>,
+    /// RFC 3339 start of the window the counters cover
+    #[serde(default)]
+    pub window_start: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<RuleStat>,
 }
 
-pub fn load_stats_file(path: &Path) -> Option<StatsFile> {
-    let content = std::fs::read_to_string(path).ok()?;
-    serde_json::from_str::<StatsFile>(&content).ok()
+impl From<&IngestCheckpoint> for StatsFile {
+    /// Lifetime totals of the engine's ingest checkpoint (`.run/ingest_stats.json`)
+    fn from(ckpt: &IngestCheckpoint) -> Self {
+        StatsFile {
+            total_input: ckpt.lifetime.total_received(),
+            groups: Vec::new(),
+            window_start: ckpt.window_start.clone(),
+            rules: ckpt
+                .lifetime
+                .rules
+                .iter()
+                .map(|(name, hits)| RuleStat {
+                    name: name.clone(),
+                    hits: *hits,
+                    last_seen: ckpt.rule_last_seen.get(name).cloned(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Load a stats file: either a plain `StatsFile` JSON document or an engine
+/// ingest checkpoint (detected by its checksum header)
+pub fn load_stats_file(path: &Path) -> Result<StatsFile> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    if IngestCheckpoint::is_checkpoint_text(&content) {
+        let ckpt = IngestCheckpoint::decode(&content)
+            .with_context(|| format!("ingest checkpoint {}", path.display()))?;
+        return Ok(StatsFile::from(&ckpt));
+    }
+    serde_json::from_str::<StatsFile>(&content)
+        .with_context(|| format!("parse stats file {}", path.display()))
 }
 
 pub fn group_input(stats: &StatsFile, group: &str) -> Option<u64> {
@@ -37,3 +86,61 @@ pub fn group_input(stats: &StatsFile, group: &str) -> Option<u64> {
         .find(|g| g.name == group)
         .map(|g| g.input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::observability::{IngestCounters, SourceTotals};
+
+    #[test]
+    fn load_reports_parse_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        std::fs::write(&path, r#"{"total_input": "many"}"#).unwrap();
+        let err = format!("{:#}", load_stats_file(&path).unwrap_err());
+        assert!(err.contains("stats.json"), "{}", err);
+        assert!(err.contains("total_input"), "{}", err);
+
+        let missing = dir.path().join("absent.json");
+        assert!(load_stats_file(&missing).is_err());
+    }
+
+    #[test]
+    fn load_reads_engine_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ingest_stats.json");
+        let mut lifetime = IngestCounters::default();
+        lifetime.sources.insert(
+            "file_src".into(),
+            SourceTotals {
+                received: 12,
+                parsed: 10,
+                missed: 2,
+            },
+        );
+        lifetime.rules.insert("/nginx/access".into(), 10);
+        IngestCheckpoint {
+            lifetime,
+            window_start: Some("2026-01-05T00:00:00+00:00".into()),
+            rule_last_seen: [("/nginx/access".into(), "2026-01-06T00:00:00+00:00".into())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }
+        .save(&path)
+        .unwrap();
+
+        let stats = load_stats_file(&path).unwrap();
+        assert_eq!(stats.total_input, 12);
+        assert_eq!(
+            stats.window_start.as_deref(),
+            Some("2026-01-05T00:00:00+00:00")
+        );
+        assert_eq!(stats.rules.len(), 1);
+        assert_eq!(stats.rules[0].hits, 10);
+        assert_eq!(
+            stats.rules[0].last_seen.as_deref(),
+            Some("2026-01-06T00:00:00+00:00")
+        );
+    }
+}
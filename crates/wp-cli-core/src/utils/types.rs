@@ -115,6 +115,29 @@ impl ValidateReport {
     }
 }
 
+/// Extra thresholds for `validate_groups` / `validate_with_stats`.
+///
+/// The defaults disable every check, so callers that pass `ValidateOptions::default()`
+/// get the same report as before. Can be flattened into a clap command.
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct ValidateOptions {
+    /// Minimum parse rate (1 - miss / total input), 0 disables the check
+    #[arg(long = "min-parse-rate", default_value_t = 0.0)]
+    pub min_parse_rate: f64,
+    /// Minimum match rate (business lines / parsed lines), 0 disables the check
+    #[arg(long = "min-match-rate", default_value_t = 0.0)]
+    pub min_match_rate: f64,
+    /// Group to skip entirely; repeatable
+    #[arg(long = "ignore-group", value_name = "NAME")]
+    pub ignore_groups: Vec<String>,
+}
+
+impl ValidateOptions {
+    pub fn is_ignored(&self, group: &str) -> bool {
+        self.ignore_groups.iter().any(|g| g == group)
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum Severity {
     Warn,
@@ -1,23 +1,65 @@
 use super::super::stats::stats::StatsFile;
-use super::super::types::{GroupAccum, Severity, ValidateItem, ValidateReport};
+use super::super::types::{GroupAccum, Severity, ValidateItem, ValidateOptions, ValidateReport};
+use wp_conf::sinks::{GROUP_MISS, is_infra_group_name};
 use wp_conf::structure::{Basis, ExpectMode};
 
-pub fn validate_groups(groups: &[GroupAccum], total_override: Option<u64>) -> ValidateReport {
-    let mut rep = ValidateReport::default();
+pub fn validate_groups(
+    groups: &[GroupAccum],
+    total_override: Option<u64>,
+    opts: &ValidateOptions,
+) -> ValidateReport {
+    let total_input = total_override.unwrap_or_else(|| sum_lines(groups));
+    validate_impl(groups, total_input, |_| None, opts)
+}
+
+pub fn validate_with_stats(
+    groups: &[GroupAccum],
+    stats: Option<&StatsFile>,
+    total_override: Option<u64>,
+    opts: &ValidateOptions,
+) -> ValidateReport {
+    let stats_total = stats.map(|s| s.total_input);
+    let total_input = total_override
+        .or(stats_total)
+        .unwrap_or_else(|| sum_lines(groups));
+    let group_input = |g: &GroupAccum| {
+        stats.and_then(|st| {
+            st.groups
+                .iter()
+                .find(|gg| gg.name == g.name)
+                .map(|gg| gg.input)
+        })
+    };
+    validate_impl(groups, total_input, group_input, opts)
+}
 
-    let total_sum: u64 = groups
-        .iter()
-        .map(|g| g.sinks.iter().map(|s| s.lines).sum::<u64>())
-        .sum();
-    let total_input: u64 = total_override.unwrap_or(total_sum);
+fn sum_lines(groups: &[GroupAccum]) -> u64 {
+    groups.iter().map(group_lines).sum()
+}
+
+fn group_lines(g: &GroupAccum) -> u64 {
+    g.sinks.iter().map(|s| s.lines).sum()
+}
+
+/// Shared by both entry points; `group_input` supplies the stats-based input of a group (if any).
+fn validate_impl(
+    groups: &[GroupAccum],
+    total_input: u64,
+    group_input: impl Fn(&GroupAccum) -> Option<u64>,
+    opts: &ValidateOptions,
+) -> ValidateReport {
+    let mut rep = ValidateReport::default();
 
     for g in groups {
+        if opts.is_ignored(&g.name) {
+            continue;
+        }
         let Some(ge) = &g.expect else { continue };
         let denom = |basis: &Basis| -> u64 {
             match basis {
-                Basis::GroupInput => g.sinks.iter().map(|s| s.lines).sum(),
+                Basis::GroupInput => group_input(g).unwrap_or_else(|| group_lines(g)),
                 Basis::TotalInput => total_input,
-                Basis::Model { .. } => g.sinks.iter().map(|s| s.lines).sum(),
+                Basis::Model { .. } => group_lines(g),
             }
         };
         let d = denom(&ge.basis);
@@ -114,134 +156,58 @@ pub fn validate_groups(groups: &[GroupAccum], total_override: Option<u64>) -> Va
         }
     }
 
+    validate_rates(groups, total_input, opts, &mut rep);
     rep
 }
 
-pub fn validate_with_stats(
+/// Global thresholds from `ValidateOptions`:
+/// - parse rate = 1 - miss / total_input
+/// - match rate = business lines / parsed lines (parsed = total_input - miss)
+fn validate_rates(
     groups: &[GroupAccum],
-    stats: Option<&StatsFile>,
-    total_override: Option<u64>,
-) -> ValidateReport {
-    let mut rep = ValidateReport::default();
-
-    let total_sum: u64 = groups
-        .iter()
-        .map(|g| g.sinks.iter().map(|s| s.lines).sum::<u64>())
-        .sum();
-    let stats_total = stats.map(|s| s.total_input);
-    let total_input: u64 = total_override.or(stats_total).unwrap_or(total_sum);
-
-    for g in groups {
-        let Some(ge) = &g.expect else { continue };
-        let group_stats_input = stats.and_then(|st| {
-            st.groups
-                .iter()
-                .find(|gg| gg.name == g.name)
-                .map(|gg| gg.input)
-        });
-        let denom = |basis: &Basis| -> u64 {
-            match basis {
-                Basis::GroupInput => {
-                    group_stats_input.unwrap_or_else(|| g.sinks.iter().map(|s| s.lines).sum())
-                }
-                Basis::TotalInput => total_input,
-                Basis::Model { .. } => g.sinks.iter().map(|s| s.lines).sum(),
-            }
-        };
-        let d = denom(&ge.basis);
-        if d == 0 {
-            rep.items.push(ValidateItem {
-                group: g.name.clone(),
-                sink: None,
-                msg: "ignored: denominator is zero (no samples)".to_string(),
-                severity: Severity::Warn,
-            });
-            continue;
+    total_input: u64,
+    opts: &ValidateOptions,
+    rep: &mut ValidateReport,
+) {
+    if total_input == 0 || (opts.min_parse_rate <= 0.0 && opts.min_match_rate <= 0.0) {
+        return;
+    }
+    let counted = groups.iter().filter(|g| !opts.is_ignored(&g.name));
+    let (mut miss, mut business) = (0u64, 0u64);
+    for g in counted {
+        if g.name == GROUP_MISS {
+            miss += group_lines(g);
+        } else if !is_infra_group_name(&g.name) {
+            business += group_lines(g);
         }
-        if let Some(ms) = ge.min_samples
-            && (d as usize) < ms
-        {
+    }
+    let parse_rate = 1.0 - miss.min(total_input) as f64 / total_input as f64;
+    if parse_rate + 1e-12 < opts.min_parse_rate {
+        rep.items.push(ValidateItem {
+            group: GROUP_MISS.to_string(),
+            sink: None,
+            msg: format!(
+                "parse rate below threshold: actual={:.6} < min={} (miss={}, total={})",
+                parse_rate, opts.min_parse_rate, miss, total_input
+            ),
+            severity: Severity::Error,
+        });
+    }
+    let parsed = total_input.saturating_sub(miss);
+    if parsed > 0 && opts.min_match_rate > 0.0 {
+        let match_rate = business as f64 / parsed as f64;
+        if match_rate + 1e-12 < opts.min_match_rate {
             rep.items.push(ValidateItem {
-                group: g.name.clone(),
+                group: "*".to_string(),
                 sink: None,
-                msg: format!("ignored: samples {} < min_samples {}", d, ms),
-                severity: Severity::Warn,
+                msg: format!(
+                    "match rate below threshold: actual={:.6} < min={} (business={}, parsed={})",
+                    match_rate, opts.min_match_rate, business, parsed
+                ),
+                severity: Severity::Error,
             });
-            continue;
-        }
-
-        for s in &g.sinks {
-            let Some(se) = &s.expect else { continue };
-            let r = s.lines as f64 / d as f64;
-            if let Some(target) = se.ratio {
-                let tol = se.tol.unwrap_or(0.0);
-                if (r - target).abs() > tol + 1e-12 {
-                    rep.items.push(ValidateItem {
-                        group: g.name.clone(),
-                        sink: Some(s.name.clone()),
-                        msg: format!(
-                            "ratio out of range: actual={:.6}, expect={}±{} (lines={}, denom={})",
-                            r, target, tol, s.lines, d
-                        ),
-                        severity: to_sev(&ge.mode),
-                    });
-                }
-            }
-            let lo = se.min.unwrap_or(f64::NEG_INFINITY);
-            let hi = se.max.unwrap_or(f64::INFINITY);
-            if !(lo..=hi).contains(&r) {
-                rep.items.push(ValidateItem {
-                    group: g.name.clone(),
-                    sink: Some(s.name.clone()),
-                    msg: format!(
-                        "ratio not in [{},{}]: actual={:.6} (lines={}, denom={})",
-                        se.min.unwrap_or(0.0),
-                        se.max.unwrap_or(1.0),
-                        r,
-                        s.lines,
-                        d
-                    ),
-                    severity: to_sev(&ge.mode),
-                });
-            }
-        }
-
-        if let Some(om) = ge.others_max {
-            let sum_other: u64 = g
-                .sinks
-                .iter()
-                .filter(|s| s.expect.is_none())
-                .map(|s| s.lines)
-                .sum();
-            let r = sum_other as f64 / d as f64;
-            if r > om + 1e-12 {
-                rep.items.push(ValidateItem {
-                    group: g.name.clone(),
-                    sink: None,
-                    msg: format!("others ratio exceeded: actual={:.6} > max={}", r, om),
-                    severity: to_sev(&ge.mode),
-                });
-            }
-        }
-
-        if let Some(st) = ge.sum_tol {
-            let sum_r: f64 = g
-                .sinks
-                .iter()
-                .filter_map(|s| s.expect.as_ref()?.ratio.map(|_| s.lines as f64 / d as f64))
-                .sum();
-            if (sum_r - 1.0).abs() > st + 1e-12 {
-                rep.items.push(ValidateItem {
-                    group: g.name.clone(),
-                    sink: None,
-                    msg: format!("sum(ratio) not within 1±{}: actual={:.6}", st, sum_r),
-                    severity: to_sev(&ge.mode),
-                });
-            }
         }
     }
-
-    rep
 }
 
 fn to_sev(mode: &ExpectMode) -> Severity {
@@ -256,6 +222,7 @@ fn to_sev(mode: &ExpectMode) -> Severity {
 mod tests {
     use super::super::super::types::{GroupAccum, SinkAccum};
     use super::*;
+    use wp_conf::sinks::{GROUP_DEFAULT, GROUP_MISS};
     use wp_conf::structure::SinkExpectOverride;
     use wp_conf::structure::{Basis, GroupExpectSpec};
 
@@ -292,7 +259,11 @@ mod tests {
             lines: 1000,
             expect: se_ratio(0.0, 0.02),
         }];
-        let rep = validate_groups(&[mk_group("default", ge, sinks)], Some(1000));
+        let rep = validate_groups(
+            &[mk_group("default", ge, sinks)],
+            Some(1000),
+            &ValidateOptions::default(),
+        );
         assert!(!rep.has_error_fail());
         assert!(
             rep.items
@@ -317,7 +288,11 @@ mod tests {
             lines: 1000,
             expect: se_ratio(0.0, 0.02),
         }];
-        let rep = validate_groups(&[mk_group("default", ge, sinks)], Some(1000));
+        let rep = validate_groups(
+            &[mk_group("default", ge, sinks)],
+            Some(1000),
+            &ValidateOptions::default(),
+        );
         assert!(rep.has_error_fail());
         assert!(
             rep.items
@@ -342,7 +317,11 @@ mod tests {
             lines: 1000,
             expect: se_ratio(0.0, 0.0),
         }];
-        let rep = validate_groups(&[mk_group("default", ge, sinks)], Some(1000));
+        let rep = validate_groups(
+            &[mk_group("default", ge, sinks)],
+            Some(1000),
+            &ValidateOptions::default(),
+        );
         assert!(!rep.has_error_fail());
         assert!(rep.items.iter().any(|it| it.msg.contains("ignored")));
     }
@@ -370,7 +349,11 @@ mod tests {
                 expect: None,
             },
         ];
-        let rep = validate_groups(&[mk_group("default", ge, sinks)], Some(100));
+        let rep = validate_groups(
+            &[mk_group("default", ge, sinks)],
+            Some(100),
+            &ValidateOptions::default(),
+        );
         assert!(rep.has_error_fail());
         assert!(
             rep.items
@@ -402,7 +385,11 @@ mod tests {
                 expect: se_ratio(0.5, 0.0),
             },
         ];
-        let rep = validate_groups(&[mk_group("default", ge, sinks)], Some(1000));
+        let rep = validate_groups(
+            &[mk_group("default", ge, sinks)],
+            Some(1000),
+            &ValidateOptions::default(),
+        );
         assert!(rep.has_error_fail());
         assert!(rep.items.iter().any(|it| it.msg.contains("sum(ratio)")));
     }
@@ -431,9 +418,100 @@ mod tests {
                 input: 100,
                 sinks: vec![],
             }],
+            ..Default::default()
         };
-        let rep = validate_with_stats(&[g], Some(&stats), None);
+        let rep = validate_with_stats(&[g], Some(&stats), None, &ValidateOptions::default());
         // ratio=50/100=0.5 meets target => PASS
         assert!(!rep.has_error_fail());
     }
+
+    fn plain_group(name: &str, lines: u64) -> GroupAccum {
+        let mut g = GroupAccum::new(name.to_string(), None);
+        g.add_sink(SinkAccum {
+            name: format!("{name}_sink"),
+            lines,
+            expect: None,
+        });
+        g
+    }
+
+    #[test]
+    fn ignore_groups_skips_expect_checks() {
+        let ge = GroupExpectSpec {
+            basis: Basis::TotalInput,
+            min_samples: Some(1),
+            mode: ExpectMode::Error,
+            window: None,
+            sum_tol: None,
+            others_max: None,
+        };
+        let sinks = vec![SinkAccum {
+            name: "default".into(),
+            lines: 1000,
+            expect: se_ratio(0.0, 0.02),
+        }];
+        let groups = [mk_group("noisy", ge, sinks)];
+        let rep = validate_groups(&groups, Some(1000), &ValidateOptions::default());
+        assert!(rep.has_error_fail());
+
+        let opts = ValidateOptions {
+            ignore_groups: vec!["noisy".into()],
+            ..Default::default()
+        };
+        let rep = validate_groups(&groups, Some(1000), &opts);
+        assert!(rep.items.is_empty());
+    }
+
+    #[test]
+    fn min_parse_rate_flags_excessive_miss() {
+        let groups = [plain_group("biz", 900), plain_group(GROUP_MISS, 100)];
+        // defaults keep the old behaviour: no global thresholds
+        let rep = validate_groups(&groups, Some(1000), &ValidateOptions::default());
+        assert!(rep.items.is_empty());
+
+        let opts = ValidateOptions {
+            min_parse_rate: 0.95,
+            ..Default::default()
+        };
+        let rep = validate_groups(&groups, Some(1000), &opts);
+        assert!(rep.has_error_fail());
+        assert!(rep.items[0].msg.contains("parse rate"));
+
+        let opts = ValidateOptions {
+            min_parse_rate: 0.9,
+            ..Default::default()
+        };
+        assert!(!validate_groups(&groups, Some(1000), &opts).has_error_fail());
+
+        // ignoring miss removes it from the parse-rate numerator
+        let opts = ValidateOptions {
+            min_parse_rate: 0.95,
+            ignore_groups: vec![GROUP_MISS.into()],
+            ..Default::default()
+        };
+        assert!(!validate_groups(&groups, Some(1000), &opts).has_error_fail());
+    }
+
+    #[test]
+    fn min_match_rate_counts_business_groups_only() {
+        let groups = [
+            plain_group("biz", 600),
+            plain_group(GROUP_DEFAULT, 300),
+            plain_group(GROUP_MISS, 100),
+        ];
+        // parsed = 900, business = 600 => 0.667
+        let opts = ValidateOptions {
+            min_match_rate: 0.8,
+            ..Default::default()
+        };
+        let rep = validate_groups(&groups, Some(1000), &opts);
+        assert!(rep.has_error_fail());
+        assert!(rep.items[0].msg.contains("match rate"));
+
+        let opts = ValidateOptions {
+            min_match_rate: 0.6,
+            ..Default::default()
+        };
+        assert!(!validate_groups(&groups, Some(1000), &opts).has_error_fail());
+    }
 }
@@ -4,4 +4,5 @@
 //! checking thresholds, and other validation operations.
 
 mod core;
+pub use super::types::ValidateOptions;
 pub use core::{validate_groups, validate_with_stats};
@@ -0,0 +1,72 @@
+// Round-trip test for knowdb CSV import
+//
+// Imports a CSV file into `models/knowledge`, loads the resulting knowdb
+// and resolves rows through an OML `select` in a transform.
+
+use std::fs;
+
+use oml::core::DataTransformer;
+use oml::parser::oml_parse_raw;
+use orion_error::TestAssert;
+use orion_variate::EnvDict;
+use wp_cli_core::knowdb::{ColumnType, ImportOptions, import};
+use wp_conf::test_support::ForTest;
+use wp_data_model::cache::FieldQueryCache;
+use wp_knowledge::facade as kdb;
+use wp_model_core::model::{DataField, DataRecord, FieldStorage};
+
+#[test]
+fn imported_table_is_queryable_from_oml() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path();
+    let src = root.join("hosts.csv");
+    fs::write(
+        &src,
+        "ip,host,score\n10.0.0.1,web,3\n10.0.0.2,db,5\n10.0.0.3,cache,\n",
+    )
+    .unwrap();
+
+    let mut opts = ImportOptions::new(&src);
+    opts.key = Some("ip".into());
+    let report = import(&root.to_string_lossy(), &opts).expect("import csv");
+    assert_eq!(report.table, "hosts");
+    assert_eq!(report.rows, 3);
+    let types: Vec<ColumnType> = report.columns.iter().map(|c| c.ty).collect();
+    assert_eq!(
+        types,
+        vec![ColumnType::Ip, ColumnType::Chars, ColumnType::Digit]
+    );
+
+    // A second import of the same table must not clobber the first one
+    assert!(import(&root.to_string_lossy(), &opts).is_err());
+
+    let models = root.join("models").join("knowledge");
+    let authority = models.join("authority.sqlite");
+    kdb::init_thread_cloned_from_knowdb(
+        &models,
+        &models.join("knowdb.toml"),
+        &format!("file:{}?mode=rwc&uri=true", authority.display()),
+        &EnvDict::test_default(),
+    )
+    .expect("load imported knowdb");
+
+    let mut conf = r#"
+        name : test
+        ---
+        host, score = select host, score from hosts where ip = read(src_ip) ;
+    "#;
+    let model = oml_parse_raw(&mut conf).assert();
+    let cache = &mut FieldQueryCache::default();
+    let src = DataRecord::from(vec![FieldStorage::from_owned(DataField::from_chars(
+        "src_ip", "10.0.0.2",
+    ))]);
+    let target = model.transform(src, cache);
+    assert_eq!(
+        target.field("host").map(|s| s.as_field()),
+        Some(&DataField::from_chars("host", "db"))
+    );
+    assert_eq!(
+        target.field("score").map(|s| s.as_field()),
+        Some(&DataField::from_digit("score", 5))
+    );
+}
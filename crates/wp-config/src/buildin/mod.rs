@@ -6,4 +6,4 @@
 pub mod file;
 pub mod syslog;
 pub use file::FileSinkConf;
-pub use syslog::{SyslogSinkConf, SyslogSourceConf};
+pub use syslog::{SyslogFormat, SyslogFraming, SyslogSinkConf, SyslogSourceConf};
@@ -15,6 +15,59 @@ pub struct SyslogSinkConf {
     pub protocol: Protocol,
     #[serde(default)]
     pub app_name: Option<String>,
+    /// 报文格式：rfc3164（默认）或 rfc5424
+    #[serde(default)]
+    pub format: SyslogFormat,
+    /// TCP 分帧方式：lf（默认，换行结尾）或 octet（RFC6587 octet-counting）
+    #[serde(default)]
+    pub framing: SyslogFraming,
+    /// RFC5424 STRUCTURED-DATA 的 SD-ID，缺省为 `wp@32473`
+    #[serde(default)]
+    pub sd_id: Option<String>,
+    /// 写入 STRUCTURED-DATA 的记录字段名；为空时输出 `-`
+    #[serde(default)]
+    pub sd_fields: Vec<String>,
+    /// 启用 TLS（仅 TCP）
+    #[serde(default)]
+    pub tls: bool,
+    /// 服务端 CA 证书（PEM）；未配置时使用内置的 webpki 根证书
+    #[serde(default)]
+    pub tls_ca_file: Option<String>,
+    /// 客户端证书（PEM），需与 `tls_key_file` 同时配置
+    #[serde(default)]
+    pub tls_cert_file: Option<String>,
+    /// 客户端私钥（PEM）
+    #[serde(default)]
+    pub tls_key_file: Option<String>,
+    /// TLS SNI/证书校验使用的服务名，缺省取 `addr`
+    #[serde(default)]
+    pub tls_server_name: Option<String>,
+    /// 断线重连的最大尝试次数（仅 TLS）
+    #[educe(Default = 5)]
+    #[serde(default = "SyslogSinkConf::reconnect_attempts_default")]
+    pub reconnect_attempts: usize,
+    /// 断线重连的初始退避（毫秒），每次失败翻倍
+    #[educe(Default = 200)]
+    #[serde(default = "SyslogSinkConf::reconnect_backoff_ms_default")]
+    pub reconnect_backoff_ms: u64,
+}
+
+/// Syslog 报文格式
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogFormat {
+    #[default]
+    Rfc3164,
+    Rfc5424,
+}
+
+/// Syslog over TCP 的分帧方式（RFC6587）
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogFraming {
+    #[default]
+    Lf,
+    Octet,
 }
 
 impl SyslogSinkConf {
@@ -30,6 +83,18 @@ impl SyslogSinkConf {
             .map(|s| s.to_string())
             .unwrap_or_else(|| fallback.to_string())
     }
+
+    pub fn host(&self) -> &str {
+        self.addr.as_str()
+    }
+
+    fn reconnect_attempts_default() -> usize {
+        5
+    }
+
+    fn reconnect_backoff_ms_default() -> u64 {
+        200
+    }
 }
 
 // ---------------- Syslog Source Config ----------------
@@ -180,6 +245,29 @@ app_name = "test_app"
         assert_eq!(conf.app_name, Some("test_app".to_string()));
     }
 
+    #[test]
+    fn config_loader_syslog_sink_tls_rfc5424() {
+        let toml = r#"
+addr = "collector.local"
+port = 6514
+protocol = "tcp"
+format = "rfc5424"
+framing = "octet"
+sd_fields = ["src_ip", "action"]
+tls = true
+tls_ca_file = "/etc/wp/ca.pem"
+"#;
+        let conf = SyslogSinkConf::load_from_str(toml, Path::new("/"), &EnvDict::test_default())
+            .expect("parse tls sink");
+        assert_eq!(conf.format, SyslogFormat::Rfc5424);
+        assert_eq!(conf.framing, SyslogFraming::Octet);
+        assert_eq!(conf.sd_fields, vec!["src_ip", "action"]);
+        assert!(conf.tls);
+        assert_eq!(conf.tls_ca_file.as_deref(), Some("/etc/wp/ca.pem"));
+        assert_eq!(conf.reconnect_attempts, 5);
+        assert_eq!(conf.reconnect_backoff_ms, 200);
+    }
+
     #[test]
     fn config_loader_syslog_source_from_str() {
         let toml = r#"
@@ -1,12 +1,17 @@
 pub mod defs;
 mod params;
+mod schema;
 mod toml;
 
 pub use defs::{
     ConnectorTomlFile, param_map_from_table_ref, param_map_to_table, param_value_from_toml,
 };
 pub use params::merge_params;
-pub use toml::load_connector_defs_from_dir;
+pub use schema::{ConnectorSchema, JSON_SCHEMA_DRAFT7, SCHEMA_DOC_KEY, load_schema_docs};
+pub use toml::{
+    ConnectorDirLoad, ResolvedConnector, collect_connector_files, load_connector_defs_from_dir,
+    load_connectors_collecting, load_resolved_connectors_from_dir,
+};
 pub use wp_connector_api::{
     ConnectorDef, ConnectorScope, ParamMap, SinkDefProvider, SourceDefProvider,
     parammap_from_toml_table as param_map_from_table,
@@ -0,0 +1,234 @@
+//! 连接器定义的 JSON Schema 导出（Draft 7），供 IDE 校验 `connectors/*.d/*.toml`。
+//!
+//! Schema 完全由 `ConnectorDef` 推导：`params` 的键与默认值决定属性及类型，
+//! `allow_override` 决定可覆盖键的枚举；说明文字取自连接器 TOML 中的 `schema_doc`。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use orion_conf::error::OrionConfResult;
+use orion_error::{ErrorOwe, ErrorWith};
+use serde_json::{Map, Value as JsonValue, json};
+use wp_connector_api::{ConnectorDef, ConnectorScope};
+
+pub const JSON_SCHEMA_DRAFT7: &str = "http://json-schema.org/draft-07/schema#";
+
+/// 连接器 TOML 条目中的说明字段
+pub const SCHEMA_DOC_KEY: &str = "schema_doc";
+
+pub trait ConnectorSchema {
+    /// 生成描述该连接器文件的 JSON Schema（无说明文字）
+    fn json_schema(&self) -> JsonValue {
+        self.json_schema_with_doc(None)
+    }
+
+    /// 生成 JSON Schema，`schema_doc` 作为顶层及连接器条目的 `description`
+    fn json_schema_with_doc(&self, schema_doc: Option<&str>) -> JsonValue;
+}
+
+impl ConnectorSchema for ConnectorDef {
+    fn json_schema_with_doc(&self, schema_doc: Option<&str>) -> JsonValue {
+        let mut params = Map::new();
+        let mut required = Vec::new();
+        for (key, default) in self.default_params.iter() {
+            let mut prop = Map::new();
+            if let Some(ty) = json_type_of(default) {
+                prop.insert("type".into(), json!(ty));
+            }
+            if let JsonValue::Array(items) = default
+                && let Some(item_ty) = items.first().and_then(json_type_of)
+            {
+                prop.insert("items".into(), json!({ "type": item_ty }));
+            }
+            if default.is_null() {
+                // 没有可用默认值的参数必须在连接器中给出
+                required.push(key.clone());
+            } else {
+                prop.insert("default".into(), default.clone());
+            }
+            prop.insert(
+                "x-allow-override".into(),
+                json!(self.allow_override.iter().any(|k| k == key)),
+            );
+            params.insert(key.clone(), JsonValue::Object(prop));
+        }
+        let mut params_schema = Map::new();
+        params_schema.insert("type".into(), json!("object"));
+        params_schema.insert("properties".into(), JsonValue::Object(params));
+        if !required.is_empty() {
+            params_schema.insert("required".into(), json!(required));
+        }
+
+        let mut entry = Map::new();
+        entry.insert("type".into(), json!("object"));
+        if let Some(doc) = schema_doc {
+            entry.insert("description".into(), json!(doc));
+        }
+        entry.insert(
+            "properties".into(),
+            json!({
+                "id": { "type": "string", "const": self.id },
+                "type": { "type": "string", "const": self.kind },
+                "allow_override": {
+                    "type": "array",
+                    "uniqueItems": true,
+                    "items": { "type": "string", "enum": self.allow_override },
+                    "default": self.allow_override,
+                },
+                "params": JsonValue::Object(params_schema),
+                SCHEMA_DOC_KEY: { "type": "string" },
+            }),
+        );
+        entry.insert("required".into(), json!(["id", "type"]));
+
+        let mut schema = Map::new();
+        schema.insert("$schema".into(), json!(JSON_SCHEMA_DRAFT7));
+        schema.insert(
+            "$id".into(),
+            json!(format!(
+                "wp-connector:{}:{}",
+                scope_label(self.scope),
+                self.id
+            )),
+        );
+        schema.insert("title".into(), json!(self.id));
+        if let Some(doc) = schema_doc {
+            schema.insert("description".into(), json!(doc));
+        }
+        schema.insert("type".into(), json!("object"));
+        schema.insert(
+            "properties".into(),
+            json!({
+                "connectors": { "type": "array", "items": JsonValue::Object(entry) }
+            }),
+        );
+        schema.insert("required".into(), json!(["connectors"]));
+        JsonValue::Object(schema)
+    }
+}
+
+fn scope_label(scope: ConnectorScope) -> &'static str {
+    match scope {
+        ConnectorScope::Source => "source",
+        ConnectorScope::Sink => "sink",
+    }
+}
+
+fn json_type_of(value: &JsonValue) -> Option<&'static str> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::Bool(_) => Some("boolean"),
+        JsonValue::Number(n) if n.is_f64() => Some("number"),
+        JsonValue::Number(_) => Some("integer"),
+        JsonValue::String(_) => Some("string"),
+        JsonValue::Array(_) => Some("array"),
+        JsonValue::Object(_) => Some("object"),
+    }
+}
+
+/// 读取连接器 TOML 文件中各条目的 `schema_doc`（id -> 说明）
+pub fn load_schema_docs(path: &Path) -> OrionConfResult<BTreeMap<String, String>> {
+    let raw = fs::read_to_string(path).owe_conf().with(path)?;
+    let value: toml::Value = toml::from_str(&raw).owe_conf().with(path)?;
+    let mut docs = BTreeMap::new();
+    let entries = value
+        .get("connectors")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for entry in entries {
+        if let (Some(id), Some(doc)) = (
+            entry.get("id").and_then(|v| v.as_str()),
+            entry.get(SCHEMA_DOC_KEY).and_then(|v| v.as_str()),
+        ) {
+            docs.insert(id.to_string(), doc.trim().to_string());
+        }
+    }
+    Ok(docs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_connector_api::ParamMap;
+
+    fn kafka_def() -> ConnectorDef {
+        let mut params = ParamMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("num_partitions".into(), json!(1));
+        params.insert("config".into(), json!(["acks=all"]));
+        params.insert("group_id".into(), JsonValue::Null);
+        ConnectorDef {
+            id: "kafka_sink".into(),
+            kind: "kafka".into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["brokers".into(), "config".into()],
+            default_params: params,
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn schema_describes_params_and_overrides() {
+        let schema = kafka_def().json_schema_with_doc(Some("Kafka output"));
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DRAFT7);
+        assert_eq!(schema["description"], "Kafka output");
+        let entry = &schema["properties"]["connectors"]["items"];
+        assert_eq!(entry["properties"]["type"]["const"], "kafka");
+        assert_eq!(
+            entry["properties"]["allow_override"]["items"]["enum"],
+            json!(["brokers", "config"])
+        );
+        let params = &entry["properties"]["params"];
+        assert_eq!(params["properties"]["brokers"]["type"], "string");
+        assert_eq!(params["properties"]["brokers"]["x-allow-override"], true);
+        assert_eq!(params["properties"]["num_partitions"]["type"], "integer");
+        assert_eq!(params["properties"]["num_partitions"]["default"], 1);
+        assert_eq!(
+            params["properties"]["num_partitions"]["x-allow-override"],
+            false
+        );
+        assert_eq!(params["properties"]["config"]["items"]["type"], "string");
+        assert_eq!(params["required"], json!(["group_id"]));
+    }
+
+    #[test]
+    fn schema_without_doc_has_no_description() {
+        let schema = kafka_def().json_schema();
+        assert!(schema.get("description").is_none());
+        assert_eq!(schema["$id"], "wp-connector:sink:kafka_sink");
+    }
+
+    #[test]
+    fn load_docs_from_toml() {
+        let dir = std::env::temp_dir().join(format!("conn_schema_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let fp = dir.join("30-kafka.toml");
+        fs::write(
+            &fp,
+            r#"
+[[connectors]]
+id = "kafka_sink"
+type = "kafka"
+schema_doc = """
+Kafka output
+"""
+[connectors.params]
+brokers = "localhost:9092"
+
+[[connectors]]
+id = "kafka_raw_sink"
+type = "kafka"
+"#,
+        )
+        .unwrap();
+        let docs = load_schema_docs(&fp).unwrap();
+        assert_eq!(
+            docs.get("kafka_sink").map(String::as_str),
+            Some("Kafka output")
+        );
+        assert!(!docs.contains_key("kafka_raw_sink"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
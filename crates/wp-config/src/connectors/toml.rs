@@ -1,14 +1,16 @@
-use super::defs::ConnectorTomlFile;
-use orion_conf::EnvTomlLoad;
+use super::params::merge_params;
+use crate::loader::env_vars::env_load_project_toml;
 use orion_conf::error::{ConfIOReason, OrionConfResult};
-use orion_error::{ErrorOwe, ErrorWith, ToStructError, UvsValidationFrom};
+use orion_error::{ErrorOwe, ErrorWith, StructError, ToStructError, UvsValidationFrom};
 use orion_variate::EnvDict;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use wp_connector_api::{ConnectorDef, ConnectorScope};
+use wp_connector_api::{ConnectorDef, ConnectorScope, ParamMap};
 
-fn collect_connector_files(dir: &Path) -> OrionConfResult<Vec<PathBuf>> {
+/// 列出连接器目录下的 `*.toml` 文件（按文件名排序，目录不存在时为空）
+pub fn collect_connector_files(dir: &Path) -> OrionConfResult<Vec<PathBuf>> {
     if !dir.exists() {
         return Ok(Vec::new());
     }
@@ -23,30 +25,233 @@ fn collect_connector_files(dir: &Path) -> OrionConfResult<Vec<PathBuf>> {
     Ok(files)
 }
 
+/// 解析 `extends` 继承后的连接器定义
+#[derive(Debug, Clone)]
+pub struct ResolvedConnector {
+    /// 生效定义：参数已与基类合并（子类优先），`allow_override` 为并集
+    pub def: ConnectorDef,
+    /// 继承链，由近及远（`extends` 的直接基类在前）
+    pub extends: Vec<String>,
+}
+
+/// 连接器文件的原始条目，`extends` 在反序列化为 [`ConnectorDef`] 之前解析
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct RawConnectorFile {
+    #[serde(default)]
+    connectors: Vec<toml::Table>,
+}
+
 pub fn load_connector_defs_from_dir(
     dir: &Path,
     scope: ConnectorScope,
     dict: &EnvDict,
 ) -> OrionConfResult<Vec<ConnectorDef>> {
-    let mut map: BTreeMap<String, ConnectorDef> = BTreeMap::new();
+    Ok(load_resolved_connectors_from_dir(dir, scope, dict)?
+        .into_iter()
+        .map(|c| c.def)
+        .collect())
+}
+
+/// 加载目录下的连接器定义并解析 `extends = "<base id>"` 继承
+///
+/// 基类须位于同一目录；缺失的基类与循环继承均报错并指出所在文件。
+pub fn load_resolved_connectors_from_dir(
+    dir: &Path,
+    scope: ConnectorScope,
+    dict: &EnvDict,
+) -> OrionConfResult<Vec<ResolvedConnector>> {
+    let loaded = load_connectors_collecting(dir, scope, dict)?;
+    match loaded.errors.into_iter().next() {
+        Some((_, e)) => Err(e),
+        None => Ok(loaded.resolved),
+    }
+}
+
+/// 连接器目录的加载结果：出错的文件或条目不中断其余定义的加载
+#[derive(Debug)]
+pub struct ConnectorDirLoad {
+    /// 解析成功的定义（按 id 排序）
+    pub resolved: Vec<ResolvedConnector>,
+    /// 逐条错误及其所在文件，按发现顺序
+    pub errors: Vec<(PathBuf, StructError<ConfIOReason>)>,
+}
+
+/// 同 [`load_resolved_connectors_from_dir`]，但收集全部错误而不是在首个错误处返回，供校验报告使用
+///
+/// 仅目录无法读取时返回 Err。
+pub fn load_connectors_collecting(
+    dir: &Path,
+    scope: ConnectorScope,
+    dict: &EnvDict,
+) -> OrionConfResult<ConnectorDirLoad> {
+    let mut errors = Vec::new();
+    let mut raw: BTreeMap<String, (PathBuf, toml::Table)> = BTreeMap::new();
     for fp in collect_connector_files(dir)? {
-        let file: ConnectorTomlFile = ConnectorTomlFile::env_load_toml(&fp, dict)?;
-        for mut def in file.connectors {
-            let origin = Some(fp.display().to_string());
-            if map.contains_key(&def.id) {
-                return ConfIOReason::from_validation(format!(
-                    "duplicate connector id '{}' (file {})",
-                    def.id,
-                    fp.display()
-                ))
-                .err_result();
+        let file: RawConnectorFile = match env_load_project_toml(&fp, dict) {
+            Ok(file) => file,
+            Err(e) => {
+                errors.push((fp, e));
+                continue;
+            }
+        };
+        for entry in file.connectors {
+            let Some(id) = entry.get("id").and_then(toml::Value::as_str) else {
+                let msg = format!("connector without id (file {})", fp.display());
+                errors.push((fp.clone(), ConfIOReason::from_validation(msg).to_err()));
+                continue;
+            };
+            if raw.contains_key(id) {
+                let msg = format!("duplicate connector id '{}' (file {})", id, fp.display());
+                errors.push((fp.clone(), ConfIOReason::from_validation(msg).to_err()));
+                continue;
+            }
+            raw.insert(id.to_string(), (fp.clone(), entry));
+        }
+    }
+    let mut resolved: BTreeMap<String, ResolvedConnector> = BTreeMap::new();
+    for (id, (fp, _)) in &raw {
+        if let Err(e) = resolve_connector(id, &raw, scope, &mut resolved, &mut Vec::new()) {
+            // 基类出错时其子类会得到同一错误，只保留一次
+            let text = e.to_string();
+            if !errors.iter().any(|(_, prev)| prev.to_string() == text) {
+                errors.push((fp.clone(), e));
             }
-            def.scope = scope;
-            def.origin = origin;
-            map.insert(def.id.clone(), def);
         }
     }
-    Ok(map.into_values().collect())
+    Ok(ConnectorDirLoad {
+        resolved: resolved.into_values().collect(),
+        errors,
+    })
+}
+
+fn resolve_connector(
+    id: &str,
+    raw: &BTreeMap<String, (PathBuf, toml::Table)>,
+    scope: ConnectorScope,
+    resolved: &mut BTreeMap<String, ResolvedConnector>,
+    visiting: &mut Vec<String>,
+) -> OrionConfResult<()> {
+    if resolved.contains_key(id) {
+        return Ok(());
+    }
+    let (fp, entry) = &raw[id];
+    if visiting.iter().any(|v| v == id) {
+        visiting.push(id.to_string());
+        return ConfIOReason::from_validation(format!(
+            "connector inheritance cycle: {} (file {})",
+            visiting.join(" -> "),
+            fp.display()
+        ))
+        .err_result();
+    }
+    let mut table = entry.clone();
+    let base_id = match table.remove("extends") {
+        None => None,
+        Some(toml::Value::String(base)) => Some(base),
+        Some(_) => {
+            return ConfIOReason::from_validation(format!(
+                "connector '{}': 'extends' must be a connector id (file {})",
+                id,
+                fp.display()
+            ))
+            .err_result();
+        }
+    };
+    let Some(base_id) = base_id else {
+        let def = connector_def_from(table, fp, scope)?;
+        resolved.insert(
+            id.to_string(),
+            ResolvedConnector {
+                def,
+                extends: Vec::new(),
+            },
+        );
+        return Ok(());
+    };
+    if !raw.contains_key(&base_id) {
+        return ConfIOReason::from_validation(format!(
+            "connector '{}' extends unknown connector '{}' (file {})",
+            id,
+            base_id,
+            fp.display()
+        ))
+        .err_result();
+    }
+
+    visiting.push(id.to_string());
+    resolve_connector(&base_id, raw, scope, resolved, visiting)?;
+    visiting.pop();
+    let base = resolved[&base_id].clone();
+
+    // 子类未声明 type 时沿用基类
+    table
+        .entry("type")
+        .or_insert_with(|| toml::Value::String(base.def.kind.clone()));
+    let mut def = connector_def_from(table, fp, scope)?;
+    if !def.kind.eq_ignore_ascii_case(&base.def.kind) {
+        return ConfIOReason::from_validation(format!(
+            "connector '{}' type '{}' differs from base '{}' type '{}' (file {})",
+            id,
+            def.kind,
+            base_id,
+            base.def.kind,
+            fp.display()
+        ))
+        .err_result();
+    }
+    def.default_params = inherit_params(&base.def, &def.default_params).map_err(|e| {
+        ConfIOReason::from_validation(format!(
+            "connector '{}' extends '{}': {} (file {})",
+            id,
+            base_id,
+            e,
+            fp.display()
+        ))
+        .to_err()
+    })?;
+    for key in base.def.allow_override.iter().rev() {
+        if !def.allow_override.contains(key) {
+            def.allow_override.insert(0, key.clone());
+        }
+    }
+    let mut extends = vec![base_id];
+    extends.extend(base.extends);
+    resolved.insert(id.to_string(), ResolvedConnector { def, extends });
+    Ok(())
+}
+
+/// 子类参数覆盖基类同名参数时受基类 `allow_override` 约束，新增参数直接并入
+fn inherit_params(base: &ConnectorDef, params: &ParamMap) -> OrionConfResult<ParamMap> {
+    let mut overrides = ParamMap::new();
+    let mut added = ParamMap::new();
+    for (k, v) in params.iter() {
+        if base.default_params.contains_key(k) {
+            overrides.insert(k.clone(), v.clone());
+        } else {
+            added.insert(k.clone(), v.clone());
+        }
+    }
+    let mut merged = merge_params(&base.default_params, &overrides, &base.allow_override)?;
+    merged.extend(added);
+    Ok(merged)
+}
+
+fn connector_def_from(
+    table: toml::Table,
+    fp: &Path,
+    scope: ConnectorScope,
+) -> OrionConfResult<ConnectorDef> {
+    let mut def: ConnectorDef = toml::Value::Table(table).try_into().map_err(|e| {
+        ConfIOReason::from_validation(format!(
+            "invalid connector definition (file {}): {}",
+            fp.display(),
+            e
+        ))
+        .to_err()
+    })?;
+    def.scope = scope;
+    def.origin = Some(fp.display().to_string());
+    Ok(def)
 }
 
 #[cfg(test)]
@@ -169,4 +374,181 @@ base = "${UNDEFINED_VAR}/data"
             base_val
         );
     }
+
+    #[test]
+    fn load_connector_expands_env_defaults() {
+        let base = tmp_dir("conn_env_default");
+        let cdir = base.join("connectors").join("sink.d");
+        fs::create_dir_all(&cdir).unwrap();
+        let connector_toml = r#"
+[[connectors]]
+id = "file_sink"
+type = "file"
+[connectors.params]
+base = "${WORK_ROOT:-/var/wp}/data"
+file = "${OUTPUT_FILE:-out.dat}"
+"#;
+        fs::write(cdir.join("default.toml"), connector_toml).unwrap();
+
+        let mut dict = EnvDict::new();
+        dict.insert("OUTPUT_FILE", ValueType::from("custom.dat"));
+        let defs = load_connector_defs_from_dir(&cdir, ConnectorScope::Sink, &dict)
+            .expect("load connectors");
+        let param = |k: &str| defs[0].default_params.get(k).and_then(|v| v.as_str());
+        assert_eq!(param("base"), Some("/var/wp/data"));
+        assert_eq!(param("file"), Some("custom.dat"));
+    }
+
+    #[test]
+    fn load_connector_resolves_two_level_extends() {
+        let base = tmp_dir("conn_extends");
+        let cdir = base.join("connectors").join("sink.d");
+        fs::create_dir_all(&cdir).unwrap();
+        let connector_toml = r#"
+[[connectors]]
+id = "kafka_base"
+type = "kafka"
+allow_override = ["topic"]
+[connectors.params]
+brokers = "k1:9092"
+sasl = "plain"
+topic = "default"
+
+[[connectors]]
+id = "kafka_tls"
+extends = "kafka_base"
+allow_override = ["tls_ca"]
+[connectors.params]
+tls_ca = "/etc/ca.pem"
+
+[[connectors]]
+id = "kafka_audit_sink"
+extends = "kafka_tls"
+[connectors.params]
+topic = "audit"
+"#;
+        fs::write(cdir.join("10-kafka.toml"), connector_toml).unwrap();
+
+        let resolved =
+            load_resolved_connectors_from_dir(&cdir, ConnectorScope::Sink, &EnvDict::new())
+                .expect("load connectors");
+        let audit = resolved
+            .iter()
+            .find(|c| c.def.id == "kafka_audit_sink")
+            .unwrap();
+        assert_eq!(audit.extends, vec!["kafka_tls", "kafka_base"]);
+        assert_eq!(audit.def.kind, "kafka");
+        assert_eq!(audit.def.allow_override, vec!["topic", "tls_ca"]);
+        let param = |k: &str| audit.def.default_params.get(k).and_then(|v| v.as_str());
+        assert_eq!(param("topic"), Some("audit"));
+        assert_eq!(param("brokers"), Some("k1:9092"));
+        assert_eq!(param("tls_ca"), Some("/etc/ca.pem"));
+        assert!(
+            audit
+                .def
+                .origin
+                .as_deref()
+                .is_some_and(|o| o.ends_with("10-kafka.toml"))
+        );
+    }
+
+    #[test]
+    fn load_connector_extends_rejects_disallowed_cycle_and_missing() {
+        let base = tmp_dir("conn_extends_err");
+        let cases = [
+            (
+                "override",
+                r#"
+[[connectors]]
+id = "kafka_base"
+type = "kafka"
+allow_override = ["topic"]
+[connectors.params]
+brokers = "k1:9092"
+
+[[connectors]]
+id = "kafka_mid"
+extends = "kafka_base"
+allow_override = ["tls_ca"]
+
+[[connectors]]
+id = "kafka_bad_sink"
+extends = "kafka_mid"
+[connectors.params]
+brokers = "k2:9092"
+"#,
+                "brokers",
+            ),
+            (
+                "cycle",
+                r#"
+[[connectors]]
+id = "a_sink"
+type = "kafka"
+extends = "b_sink"
+
+[[connectors]]
+id = "b_sink"
+extends = "a_sink"
+"#,
+                "cycle",
+            ),
+            (
+                "missing",
+                r#"
+[[connectors]]
+id = "a_sink"
+extends = "nope"
+"#,
+                "nope",
+            ),
+        ];
+        for (name, body, needle) in cases {
+            let cdir = base.join(name);
+            fs::create_dir_all(&cdir).unwrap();
+            let file = cdir.join("00-bad.toml");
+            fs::write(&file, body).unwrap();
+            let err = load_connector_defs_from_dir(&cdir, ConnectorScope::Sink, &EnvDict::new())
+                .expect_err(name)
+                .to_string();
+            assert!(err.contains(needle), "{}: {}", name, err);
+            assert!(
+                err.contains(&file.display().to_string()),
+                "{}: {}",
+                name,
+                err
+            );
+        }
+    }
+    #[test]
+    fn load_connectors_collecting_reports_every_bad_file() {
+        let cdir = tmp_dir("conn_collect").join("sink.d");
+        fs::create_dir_all(&cdir).unwrap();
+        fs::write(
+            cdir.join("00-ok.toml"),
+            "[[connectors]]\nid = \"ok_sink\"\ntype = \"file\"\n",
+        )
+        .unwrap();
+        fs::write(cdir.join("10-syntax.toml"), "[[connectors]\nid = ").unwrap();
+        fs::write(
+            cdir.join("20-noid.toml"),
+            "[[connectors]]\ntype = \"file\"\n",
+        )
+        .unwrap();
+
+        let loaded =
+            load_connectors_collecting(&cdir, ConnectorScope::Sink, &EnvDict::new()).unwrap();
+        assert_eq!(loaded.resolved.len(), 1);
+        let files: Vec<_> = loaded.errors.iter().map(|(fp, _)| fp.clone()).collect();
+        assert_eq!(
+            files,
+            vec![cdir.join("10-syntax.toml"), cdir.join("20-noid.toml")]
+        );
+        assert!(loaded.errors[1].1.to_string().contains("without id"));
+
+        // 严格加载仍在首个错误处失败
+        assert!(
+            load_connector_defs_from_dir(&cdir, ConnectorScope::Sink, &EnvDict::new()).is_err()
+        );
+    }
 }
@@ -1,13 +1,17 @@
-use orion_conf::{EnvTomlLoad, ErrorOwe, ErrorWith, TomlIO, error::OrionConfResult};
+use orion_conf::{ErrorOwe, ErrorWith, TomlIO, error::OrionConfResult};
 use orion_variate::{EnvDict, EnvEvaluable};
 use serde_derive::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs::create_dir_all,
     path::{Path, PathBuf},
 };
+use wp_data_model::limits::{LimitPolicy, RecordLimits};
 use wp_error::error_handling::RobustnessMode;
 use wp_log::conf::LogConf;
 
+use crate::loader::env_vars::{VariableManifest, load_variable_manifest};
+use crate::loader::include::env_load_toml_included;
 use crate::stat::StatConf;
 
 impl EngineConfig {}
@@ -87,6 +91,123 @@ pub struct SemanticConf {
     pub enabled: bool,
 }
 
+/// Prometheus 指标导出；默认关闭
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+pub struct MetricsConf {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 监听地址，如 `127.0.0.1:9464`；抓取路径固定为 `/metrics`
+    #[serde(default = "default_metrics_listen")]
+    pub listen: String,
+}
+
+impl Default for MetricsConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_metrics_listen(),
+        }
+    }
+}
+
+impl EnvEvaluable<MetricsConf> for MetricsConf {
+    fn env_eval(mut self, dict: &orion_variate::EnvDict) -> MetricsConf {
+        self.listen = self.listen.env_eval(dict);
+        self
+    }
+}
+
+/// 记录级追踪；默认关闭
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+pub struct TraceConf {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 采样率，取值 `[0, 1]`；按 `wp_trace_id` 哈希决定是否采样
+    #[serde(default = "default_trace_sample_rate", alias = "trace_sample_rate")]
+    pub sample_rate: f64,
+    /// 环形缓冲保留的追踪记录数，超出后淘汰最早的记录
+    #[serde(default = "default_trace_capacity")]
+    pub capacity: usize,
+}
+
+impl Default for TraceConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_rate: default_trace_sample_rate(),
+            capacity: default_trace_capacity(),
+        }
+    }
+}
+
+/// 单条记录体量限制；各项为 0 表示不限制（默认均不限制）
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone, Default)]
+pub struct RecordLimitConf {
+    /// 每条记录最多字段数
+    #[serde(default)]
+    pub max_fields_per_record: usize,
+    /// 单个字段值最大字节数（数组/对象按展开后的总量估算）
+    #[serde(default)]
+    pub max_field_value_bytes: usize,
+    /// 单条记录最大字节数（字段名与值累加）
+    #[serde(default)]
+    pub max_record_bytes: usize,
+    /// 超限处理：`truncate` 截断并标记 `__truncated = true`，`drop` 转入 miss
+    #[serde(default)]
+    pub policy: LimitPolicy,
+}
+
+impl RecordLimitConf {
+    pub fn to_limits(&self) -> RecordLimits {
+        RecordLimits {
+            max_fields: self.max_fields_per_record,
+            max_field_value_bytes: self.max_field_value_bytes,
+            max_record_bytes: self.max_record_bytes,
+            policy: self.policy,
+        }
+    }
+}
+
+/// OML 运行期选项
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+pub struct OmlConf {
+    /// 是否执行 `assert` 语句；生产环境可关闭以跳过全部断言
+    #[serde(default = "default_compile_assert")]
+    pub compile_assert: bool,
+    /// 断言失败时按转换失败处理（默认仅 WARN 日志）
+    #[serde(default)]
+    pub assert_strict: bool,
+    /// 转换线程查询缓存条目的有效期（毫秒）；未设置时条目只受容量约束
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lookup_cache_ttl_ms: Option<u64>,
+}
+
+impl Default for OmlConf {
+    fn default() -> Self {
+        Self {
+            compile_assert: default_compile_assert(),
+            assert_strict: false,
+            lookup_cache_ttl_ms: None,
+        }
+    }
+}
+
+/// 优雅退出选项
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+pub struct ShutdownConf {
+    /// 进入 drain 后 sink 下发残留批次的时限（毫秒）；超时未写出的批次写入 rescue
+    #[serde(default = "default_drain_timeout_ms")]
+    pub drain_timeout_ms: u64,
+}
+
+impl Default for ShutdownConf {
+    fn default() -> Self {
+        Self {
+            drain_timeout_ms: default_drain_timeout_ms(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 pub struct EngineConfig {
     #[serde(default = "default_version")]
@@ -115,6 +236,19 @@ pub struct EngineConfig {
     /// 语义分析功能开关（默认关闭，启用后加载 jieba 分词器和语义词典）
     #[serde(default)]
     semantic: SemanticConf,
+    #[serde(default)]
+    oml: OmlConf,
+    #[serde(default)]
+    metrics: MetricsConf,
+    #[serde(default)]
+    shutdown: ShutdownConf,
+    #[serde(default)]
+    trace: TraceConf,
+    #[serde(default)]
+    record_limits: RecordLimitConf,
+    /// 配置中引用的变量声明（必需性、说明、默认值）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    variables: VariableManifest,
 }
 
 impl EnvEvaluable<EngineConfig> for EngineConfig {
@@ -122,6 +256,7 @@ impl EnvEvaluable<EngineConfig> for EngineConfig {
         self.models = self.models.env_eval(dict);
         self.topology = self.topology.env_eval(dict);
         self.rescue = self.rescue.env_eval(dict);
+        self.metrics = self.metrics.env_eval(dict);
         self
     }
 }
@@ -159,6 +294,26 @@ pub fn default_speed_limit() -> usize {
     10000
 }
 
+pub fn default_compile_assert() -> bool {
+    true
+}
+
+pub fn default_metrics_listen() -> String {
+    "127.0.0.1:9464".to_string()
+}
+
+pub fn default_drain_timeout_ms() -> u64 {
+    5000
+}
+
+pub fn default_trace_sample_rate() -> f64 {
+    0.001
+}
+
+pub fn default_trace_capacity() -> usize {
+    4096
+}
+
 pub fn default_topology_conf() -> TopologyConf {
     TopologyConf {
         sources: default_sources_root(),
@@ -187,6 +342,12 @@ impl Default for EngineConfig {
             skip_parse: false,
             skip_sink: false,
             semantic: SemanticConf::default(),
+            oml: OmlConf::default(),
+            metrics: MetricsConf::default(),
+            shutdown: ShutdownConf::default(),
+            trace: TraceConf::default(),
+            record_limits: RecordLimitConf::default(),
+            variables: VariableManifest::new(),
         }
     }
 }
@@ -217,6 +378,12 @@ impl EngineConfig {
             skip_parse: false,
             skip_sink: false,
             semantic: SemanticConf::default(),
+            oml: OmlConf::default(),
+            metrics: MetricsConf::default(),
+            shutdown: ShutdownConf::default(),
+            trace: TraceConf::default(),
+            record_limits: RecordLimitConf::default(),
+            variables: VariableManifest::new(),
         }
     }
 
@@ -282,6 +449,30 @@ impl EngineConfig {
         &self.semantic
     }
 
+    pub fn oml(&self) -> &OmlConf {
+        &self.oml
+    }
+
+    pub fn metrics(&self) -> &MetricsConf {
+        &self.metrics
+    }
+
+    pub fn shutdown(&self) -> &ShutdownConf {
+        &self.shutdown
+    }
+
+    pub fn trace(&self) -> &TraceConf {
+        &self.trace
+    }
+
+    pub fn record_limits(&self) -> &RecordLimitConf {
+        &self.record_limits
+    }
+
+    pub fn variables(&self) -> &VariableManifest {
+        &self.variables
+    }
+
     pub fn src_conf_of(&self, file_name: &str) -> String {
         format!("{}/{}", self.src_root(), file_name)
     }
@@ -300,7 +491,7 @@ impl EngineConfig {
         use crate::constants::ENGINE_CONF_FILE;
         let engine_conf_path = work_root.as_ref().join("conf").join(ENGINE_CONF_FILE);
         if engine_conf_path.exists() {
-            EngineConfig::env_load_toml(&engine_conf_path, dict)
+            Self::load_expanded(&engine_conf_path, dict)
         } else {
             if let Some(parent) = engine_conf_path.parent() {
                 create_dir_all(parent)
@@ -316,11 +507,16 @@ impl EngineConfig {
     pub fn load<P: AsRef<Path>>(work_root: P, dict: &EnvDict) -> OrionConfResult<Self> {
         use crate::constants::ENGINE_CONF_FILE;
         let engine_conf_path = work_root.as_ref().join("conf").join(ENGINE_CONF_FILE);
-        EngineConfig::env_load_toml(&engine_conf_path, dict)
+        Self::load_expanded(&engine_conf_path, dict)
             .want("load engine config")
             .with(ENGINE_CONF_FILE)
     }
 
+    /// 先合并 `include` 的文件，`${VAR:-default}` 与 `[variables]` 中的默认值先于 EnvDict 替换展开
+    pub fn load_expanded(path: &Path, dict: &EnvDict) -> OrionConfResult<Self> {
+        env_load_toml_included(path, dict, &load_variable_manifest(path))
+    }
+
     // Add a gen_default method for StatConf compatibility
     pub fn gen_default(&self) -> StatConf {
         StatConf::default()
@@ -383,6 +579,98 @@ fn normalize_path(path: &Path) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_oml_conf_defaults() {
+        let conf: EngineConfig = toml::from_str("").unwrap();
+        assert!(conf.oml().compile_assert);
+        assert!(!conf.oml().assert_strict);
+
+        assert_eq!(conf.oml().lookup_cache_ttl_ms, None);
+
+        let conf: EngineConfig = toml::from_str("[oml]\ncompile_assert = false\n").unwrap();
+        assert!(!conf.oml().compile_assert);
+
+        let conf: EngineConfig = toml::from_str("[oml]\nlookup_cache_ttl_ms = 30000\n").unwrap();
+        assert_eq!(conf.oml().lookup_cache_ttl_ms, Some(30000));
+    }
+
+    #[test]
+    fn test_load_merges_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_dir = dir.path().join("conf");
+        std::fs::create_dir_all(&conf_dir).unwrap();
+        std::fs::write(
+            conf_dir.join("base.toml"),
+            "[performance]\nparse_workers = 4\nrate_limit_rps = 500\n\n[metrics]\nenabled = true\n",
+        )
+        .unwrap();
+        std::fs::write(
+            conf_dir.join("site.toml"),
+            "[metrics]\nlisten = \"10.0.0.1:9464\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            conf_dir.join(crate::constants::ENGINE_CONF_FILE),
+            "include = [\"base.toml\", \"site.toml\"]\n\n[performance]\nparse_workers = 8\n",
+        )
+        .unwrap();
+
+        let conf = EngineConfig::load(dir.path(), &EnvDict::new()).expect("load");
+        assert_eq!(conf.performance.parse_workers, 8);
+        assert_eq!(conf.performance.rate_limit_rps, 500);
+        assert!(conf.metrics().enabled);
+        assert_eq!(conf.metrics().listen, "10.0.0.1:9464");
+    }
+
+    #[test]
+    fn test_metrics_conf_defaults() {
+        let conf: EngineConfig = toml::from_str("").unwrap();
+        assert!(!conf.metrics().enabled);
+        assert_eq!(conf.metrics().listen, "127.0.0.1:9464");
+
+        let conf: EngineConfig =
+            toml::from_str("[metrics]\nenabled = true\nlisten = \"0.0.0.0:9100\"\n").unwrap();
+        assert!(conf.metrics().enabled);
+        assert_eq!(conf.metrics().listen, "0.0.0.0:9100");
+    }
+
+    #[test]
+    fn test_shutdown_conf_defaults() {
+        let conf: EngineConfig = toml::from_str("").unwrap();
+        assert_eq!(conf.shutdown().drain_timeout_ms, 5000);
+
+        let conf: EngineConfig = toml::from_str("[shutdown]\ndrain_timeout_ms = 800\n").unwrap();
+        assert_eq!(conf.shutdown().drain_timeout_ms, 800);
+    }
+
+    #[test]
+    fn test_trace_conf_defaults() {
+        let conf: EngineConfig = toml::from_str("").unwrap();
+        assert!(!conf.trace().enabled);
+        assert_eq!(conf.trace().capacity, 4096);
+
+        let conf: EngineConfig =
+            toml::from_str("[trace]\nenabled = true\ntrace_sample_rate = 0.5\n").unwrap();
+        assert!(conf.trace().enabled);
+        assert_eq!(conf.trace().sample_rate, 0.5);
+    }
+
+    #[test]
+    fn test_record_limit_conf() {
+        let conf: EngineConfig = toml::from_str("").unwrap();
+        assert!(conf.record_limits().to_limits().is_unlimited());
+
+        let conf: EngineConfig = toml::from_str(
+            "[record_limits]\nmax_fields_per_record = 1000\nmax_record_bytes = 1048576\npolicy = \"drop\"\n",
+        )
+        .unwrap();
+        let limits = conf.record_limits().to_limits();
+        assert_eq!(limits.max_fields, 1000);
+        assert_eq!(limits.max_field_value_bytes, 0);
+        assert_eq!(limits.max_record_bytes, 1_048_576);
+        assert_eq!(limits.policy, LimitPolicy::Drop);
+    }
+
     #[test]
     fn test_normalize_path_removes_current_dir() {
         let path = Path::new("/foo/./bar/./baz");
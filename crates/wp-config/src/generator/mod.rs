@@ -2,4 +2,4 @@
 pub mod speed_profile;
 pub mod wpgen;
 
-pub use speed_profile::{CombineModeConfig, SpeedProfileConfig};
+pub use speed_profile::{CombineModeConfig, InterpolationConfig, SpeedProfileConfig};
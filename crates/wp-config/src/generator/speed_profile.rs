@@ -110,6 +110,26 @@ pub enum SpeedProfileConfig {
         variance: f64,
     },
 
+    /// 实测流量直方图（回放一天的流量曲线）
+    ///
+    /// ```toml
+    /// [generator.speed_profile]
+    /// type = "histogram"
+    /// buckets = [800, 600, 500, ...]   # 24 或 96 个桶，单位 行/秒
+    /// interpolation = "linear"
+    /// period_secs = 86400.0
+    /// ```
+    Histogram {
+        /// 各时段速率 (行/秒)，均分 `period_secs`
+        buckets: Vec<usize>,
+        /// 桶间插值方式
+        #[serde(default)]
+        interpolation: InterpolationConfig,
+        /// 整条曲线的时长 (秒)，默认一天，结束后循环
+        #[serde(default = "default_histogram_period")]
+        period_secs: f64,
+    },
+
     /// 复合模式
     ///
     /// ```toml
@@ -152,6 +172,21 @@ pub enum CombineModeConfig {
     Sum,
 }
 
+/// 直方图桶间插值方式
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InterpolationConfig {
+    /// 桶内保持该桶速率
+    #[default]
+    Step,
+    /// 相邻桶中点之间线性过渡
+    Linear,
+}
+
+fn default_histogram_period() -> f64 {
+    86_400.0
+}
+
 impl Default for SpeedProfileConfig {
     fn default() -> Self {
         SpeedProfileConfig::Constant { rate: 1000 }
@@ -170,6 +205,9 @@ impl SpeedProfileConfig {
             SpeedProfileConfig::Burst { base, .. } => *base,
             SpeedProfileConfig::Ramp { start, .. } => *start,
             SpeedProfileConfig::RandomWalk { base, .. } => *base,
+            SpeedProfileConfig::Histogram { buckets, .. } => {
+                buckets.iter().sum::<usize>() / buckets.len().max(1)
+            }
             SpeedProfileConfig::Composite { profiles, .. } => {
                 let sum: usize = profiles.iter().map(|p| p.base_rate()).sum();
                 sum / profiles.len().max(1)
@@ -177,6 +215,140 @@ impl SpeedProfileConfig {
         }
     }
 
+    /// 速率可能的取值范围 `(min, max)`（行/秒），与运行期控制器的计算口径一致
+    pub fn rate_bounds(&self) -> (usize, usize) {
+        match self {
+            SpeedProfileConfig::Constant { rate } => (*rate, *rate),
+            SpeedProfileConfig::Sinusoidal {
+                base,
+                amplitude,
+                period_secs,
+            } => {
+                if *period_secs <= 0.0 {
+                    (*base, *base)
+                } else {
+                    (base.saturating_sub(*amplitude).max(1), base + amplitude)
+                }
+            }
+            SpeedProfileConfig::Stepped { steps, .. } => {
+                let rates = steps.iter().map(|(_, r)| *r);
+                match (rates.clone().min(), rates.max()) {
+                    (Some(min), Some(max)) => (min, max),
+                    _ => (1000, 1000),
+                }
+            }
+            SpeedProfileConfig::Burst {
+                base,
+                burst_rate,
+                burst_probability,
+                ..
+            } => {
+                if *burst_probability <= 0.0 {
+                    (*base, *base)
+                } else {
+                    (*base.min(burst_rate), *base.max(burst_rate))
+                }
+            }
+            SpeedProfileConfig::Ramp {
+                start,
+                end,
+                duration_secs,
+            } => {
+                if *duration_secs <= 0.0 {
+                    (*end, *end)
+                } else {
+                    (*start.min(end).max(&1), *start.max(end).max(&1))
+                }
+            }
+            SpeedProfileConfig::RandomWalk { base, variance } => {
+                let v = variance.clamp(0.0, 1.0);
+                (
+                    ((*base as f64) * (1.0 - v)).max(1.0) as usize,
+                    ((*base as f64) * (1.0 + v)).max(1.0) as usize,
+                )
+            }
+            SpeedProfileConfig::Histogram { buckets, .. } => {
+                match (buckets.iter().min(), buckets.iter().max()) {
+                    (Some(min), Some(max)) => (*min, *max),
+                    _ => (1000, 1000),
+                }
+            }
+            SpeedProfileConfig::Composite {
+                profiles,
+                combine_mode,
+            } => {
+                if profiles.is_empty() {
+                    return (1000, 1000);
+                }
+                let bounds: Vec<(usize, usize)> =
+                    profiles.iter().map(|p| p.rate_bounds()).collect();
+                let mins = bounds.iter().map(|b| b.0);
+                let maxs = bounds.iter().map(|b| b.1);
+                match combine_mode {
+                    CombineModeConfig::Average => (
+                        mins.sum::<usize>() / bounds.len(),
+                        maxs.sum::<usize>() / bounds.len(),
+                    ),
+                    CombineModeConfig::Max => (mins.max().unwrap_or(0), maxs.max().unwrap_or(0)),
+                    CombineModeConfig::Min => (mins.min().unwrap_or(0), maxs.min().unwrap_or(0)),
+                    CombineModeConfig::Sum => (mins.sum(), maxs.sum()),
+                }
+            }
+        }
+    }
+
+    /// 校验模型参数，返回有效速率范围 `(min, max)`
+    ///
+    /// 速率为 0 在限速器中表示不限速：单独配置 `rate = 0` 是合法的"全速"，
+    /// 但复合模型组合出 0 通常是配置错误（如 `min` 组合两个基准为 0 的突发模型）；
+    /// 直方图中任一桶为 0 会让该时段变成全速，同样拒绝。
+    pub fn validate(&self) -> Result<(usize, usize), String> {
+        match self {
+            SpeedProfileConfig::Histogram {
+                buckets,
+                period_secs,
+                ..
+            } => {
+                if buckets.is_empty() {
+                    return Err("histogram profile needs at least one bucket".to_string());
+                }
+                if !(period_secs.is_finite() && *period_secs > 0.0) {
+                    return Err(format!(
+                        "histogram period_secs must be > 0, got {}",
+                        period_secs
+                    ));
+                }
+                if let Some(idx) = buckets.iter().position(|b| *b == 0) {
+                    return Err(format!(
+                        "histogram bucket #{} is 0; 0 means unlimited to the rate limiter",
+                        idx
+                    ));
+                }
+            }
+            SpeedProfileConfig::Composite { profiles, .. } => {
+                if profiles.is_empty() {
+                    return Err("composite profile needs at least one sub-profile".to_string());
+                }
+                for p in profiles {
+                    p.validate()?;
+                }
+                let (min, max) = self.rate_bounds();
+                if max == 0 {
+                    return Err("composite profile always yields 0 EPS".to_string());
+                }
+                if min == 0 {
+                    return Err(format!(
+                        "composite profile may drop to 0 EPS (effective range 0..={}); \
+                         0 means unlimited to the rate limiter",
+                        max
+                    ));
+                }
+            }
+            _ => {}
+        }
+        Ok(self.rate_bounds())
+    }
+
     /// 是否为恒定速率
     pub fn is_constant(&self) -> bool {
         matches!(self, SpeedProfileConfig::Constant { .. })
@@ -363,6 +535,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_histogram_config() {
+        let toml_str = r#"
+            type = "histogram"
+            buckets = [100, 300, 200]
+            interpolation = "linear"
+        "#;
+        let config: SpeedProfileConfig = toml::from_str(toml_str).unwrap();
+        if let SpeedProfileConfig::Histogram {
+            buckets,
+            interpolation,
+            period_secs,
+        } = &config
+        {
+            assert_eq!(buckets, &vec![100, 300, 200]);
+            assert_eq!(*interpolation, InterpolationConfig::Linear);
+            assert!((period_secs - 86_400.0).abs() < 0.001);
+        } else {
+            panic!("Expected Histogram");
+        }
+        assert_eq!(config.base_rate(), 200);
+        assert_eq!(config.validate(), Ok((100, 300)));
+    }
+
+    #[test]
+    fn test_composite_validation_reports_bounds() {
+        let toml_str = r#"
+            type = "composite"
+            combine_mode = "sum"
+
+            [[profiles]]
+            type = "sinusoidal"
+            base = 5000
+            amplitude = 2000
+            period_secs = 60.0
+
+            [[profiles]]
+            type = "random_walk"
+            base = 1000
+            variance = 0.5
+        "#;
+        let config: SpeedProfileConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.validate(), Ok((3500, 8500)));
+
+        // 两个基准为 0 的突发模型取 min：几乎始终为 0（即不限速）
+        let toml_str = r#"
+            type = "composite"
+            combine_mode = "min"
+
+            [[profiles]]
+            type = "burst"
+            base = 0
+            burst_rate = 10000
+            burst_duration_ms = 500
+            burst_probability = 0.05
+
+            [[profiles]]
+            type = "burst"
+            base = 0
+            burst_rate = 8000
+            burst_duration_ms = 500
+            burst_probability = 0.05
+        "#;
+        let config: SpeedProfileConfig = toml::from_str(toml_str).unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("0..=8000"), "{}", err);
+
+        let empty = SpeedProfileConfig::Composite {
+            profiles: vec![],
+            combine_mode: CombineModeConfig::Average,
+        };
+        assert!(empty.validate().is_err());
+        let nested_bad = SpeedProfileConfig::Composite {
+            profiles: vec![SpeedProfileConfig::Histogram {
+                buckets: vec![],
+                interpolation: InterpolationConfig::Step,
+                period_secs: 60.0,
+            }],
+            combine_mode: CombineModeConfig::Max,
+        };
+        assert!(nested_bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_histogram_rejects_zero_bucket() {
+        let config = SpeedProfileConfig::Histogram {
+            buckets: vec![100, 0, 200],
+            interpolation: InterpolationConfig::Linear,
+            period_secs: 60.0,
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("bucket #1"), "{}", err);
+    }
+
     #[test]
     fn test_serialize_roundtrip() {
         let config = SpeedProfileConfig::Sinusoidal {
@@ -1,14 +1,17 @@
 //! New wpgen configuration structure (generalized)
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::net::Ipv4Addr;
 use std::path::{Path, PathBuf};
 
 use crate::structure::ConfStdOperation;
 use crate::structure::SinkInstanceConf;
 use crate::utils::{backup_clean, save_conf};
-use orion_conf::error::OrionConfResult;
+use orion_conf::error::{ConfIOReason, OrionConfResult};
+use orion_error::{ToStructError, UvsValidationFrom};
 use orion_variate::EnvDict;
 use serde_derive::{Deserialize, Serialize};
 use toml;
+use wp_log::info_ctrl;
 
 use super::speed_profile::SpeedProfileConfig;
 // no external IO traits for resolved wpgen; handled in loader
@@ -18,7 +21,12 @@ use super::speed_profile::SpeedProfileConfig;
 pub struct WpGenConfig {
     pub version: String,
     pub generator: GeneratorConfig,
+    /// 单一输出（向后兼容）；配置了 `[[outputs]]` 时忽略
+    #[serde(default)]
     pub output: OutputConfig,
+    /// 多路命名输出（`[[outputs]]`），按 `ratio` 分流或按 `filter` 复制
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<NamedOutputConfig>,
     pub logging: LoggingConfig,
     #[serde(default)]
     pub presets: HashMap<String, String>,
@@ -38,6 +46,13 @@ pub struct GeneratorConfig {
     pub parallel: usize,
     pub rule_root: Option<String>,
     pub sample_pattern: Option<String>,
+    /// `mode = "replay"` 的回放参数
+    pub replay: Option<ReplayConfig>,
+    /// 字段值池（`[generator.fields]`）：规则输出中的 `{name}` 占位符逐条按池取值替换
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldPoolConfig>,
+    /// 字段取值的随机种子；固定后输出可复现
+    pub seed: Option<u64>,
 }
 
 impl Default for GeneratorConfig {
@@ -51,6 +66,9 @@ impl Default for GeneratorConfig {
             parallel: 1,
             rule_root: None,
             sample_pattern: None,
+            replay: None,
+            fields: BTreeMap::new(),
+            seed: None,
         }
     }
 }
@@ -84,6 +102,93 @@ impl GeneratorConfig {
     }
 }
 
+/// 单个字段的取值来源，`values`/`ip_range`/`int_range`/`seq` 四选一
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FieldPoolConfig {
+    /// 候选值列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<String>>,
+    /// 与 `values` 一一对应的权重；缺省时等概率
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<u32>>,
+    /// IPv4 网段，如 `10.0.0.0/16`，在网段内均匀取地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_range: Option<String>,
+    /// 闭区间整数 `[min, max]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub int_range: Option<[i64; 2]>,
+    /// 自增序号的起始值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+}
+
+impl FieldPoolConfig {
+    /// 解析 `ip_range`，返回网段首地址与地址数
+    pub fn ipv4_range(cidr: &str) -> Result<(u32, u64), String> {
+        let (addr, prefix) = cidr
+            .split_once('/')
+            .ok_or_else(|| format!("'{}' is not a CIDR (expect a.b.c.d/len)", cidr))?;
+        let addr: Ipv4Addr = addr
+            .trim()
+            .parse()
+            .map_err(|_| format!("'{}' has an invalid IPv4 address", cidr))?;
+        let prefix: u32 = match prefix.trim().parse() {
+            Ok(p) if p <= 32 => p,
+            _ => return Err(format!("'{}' has an invalid prefix length", cidr)),
+        };
+        let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+        Ok((u32::from(addr) & mask, 1u64 << (32 - prefix)))
+    }
+
+    fn validate(&self, name: &str) -> Result<(), String> {
+        let kinds = [
+            self.values.is_some(),
+            self.ip_range.is_some(),
+            self.int_range.is_some(),
+            self.seq.is_some(),
+        ];
+        if kinds.iter().filter(|k| **k).count() != 1 {
+            return Err(format!(
+                "field '{}' needs exactly one of values | ip_range | int_range | seq",
+                name
+            ));
+        }
+        if let Some(values) = &self.values {
+            if values.is_empty() {
+                return Err(format!("field '{}': values is empty", name));
+            }
+            if let Some(weights) = &self.weights {
+                if weights.len() != values.len() {
+                    return Err(format!(
+                        "field '{}': {} weights for {} values",
+                        name,
+                        weights.len(),
+                        values.len()
+                    ));
+                }
+                if weights.iter().all(|w| *w == 0) {
+                    return Err(format!("field '{}': all weights are zero", name));
+                }
+            }
+        } else if self.weights.is_some() {
+            return Err(format!("field '{}': weights requires values", name));
+        }
+        if let Some(cidr) = &self.ip_range {
+            Self::ipv4_range(cidr).map_err(|e| format!("field '{}': {}", name, e))?;
+        }
+        if let Some([min, max]) = self.int_range
+            && min > max
+        {
+            return Err(format!(
+                "field '{}': int_range [{}, {}] is empty",
+                name, min, max
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum GenMode {
     #[serde(rename = "rule")]
@@ -91,6 +196,33 @@ pub enum GenMode {
     Rule,
     #[serde(rename = "sample")]
     Sample,
+    /// 按事件内嵌时间戳的原始间隔回放数据文件
+    #[serde(rename = "replay")]
+    Replay,
+}
+
+/// 回放模式参数（`[generator.replay]`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplayConfig {
+    /// 待回放的数据文件，每行一条事件
+    pub file: String,
+    /// 时间戳所在字段：JSON 行取同名键，其余按 `field=value` 查找；缺省时取行首时间戳
+    #[serde(default)]
+    pub timestamp_field: Option<String>,
+    /// `rfc3339`、`epoch`（秒，可带小数）、`epoch_ms` 或 chrono 格式串（如 `%Y-%m-%d %H:%M:%S%.f`）
+    #[serde(default = "default_timestamp_format")]
+    pub timestamp_format: String,
+    /// 时间倍率：2.0 以两倍速回放（间隔减半），0.5 放慢一倍
+    #[serde(default = "default_speed_factor")]
+    pub speed_factor: f64,
+}
+
+fn default_timestamp_format() -> String {
+    "rfc3339".to_string()
+}
+
+fn default_speed_factor() -> f64 {
+    1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -113,6 +245,24 @@ impl Default for OutputConfig {
     }
 }
 
+/// 命名输出（`[[outputs]]` 数组项）
+///
+/// - `ratio`：按比例分流，各输出互斥取行，所有 ratio 之和不得超过 1.0，余量丢弃；
+/// - `filter`：正则表达式，匹配的行复制到该输出（与 ratio 互斥）；
+/// - 二者均缺省时接收全部行。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NamedOutputConfig {
+    pub name: String,
+    pub connect: String,
+    #[serde(default)]
+    pub params: toml::value::Table,
+    #[serde(default)]
+    pub ratio: Option<f64>,
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
 // Removed OutputType/DataFormat/ErrorHandling: 由 connectors 决定输出类型与格式
 
 // 兼容类型移除：File/Kafka/Syslog/Stdout 等旧式输出定义已废弃
@@ -130,6 +280,43 @@ pub struct LoggingConfig {
 
 impl WpGenConfig {
     pub fn validate(&self) -> OrionConfResult<()> {
+        if self.generator.mode == GenMode::Replay {
+            let Some(replay) = &self.generator.replay else {
+                return ConfIOReason::from_validation(
+                    "wpgen.generator.mode = \"replay\" requires [generator.replay]",
+                )
+                .err_result();
+            };
+            if !(replay.speed_factor.is_finite() && replay.speed_factor > 0.0) {
+                return ConfIOReason::from_validation(format!(
+                    "wpgen.generator.replay.speed_factor must be > 0, got {}",
+                    replay.speed_factor
+                ))
+                .err_result();
+            }
+        }
+        if let Some(profile) = &self.generator.speed_profile {
+            match profile.validate() {
+                Ok((min, max)) => info_ctrl!(
+                    "wpgen speed_profile effective rate: {}..={} lines/s",
+                    min,
+                    max
+                ),
+                Err(msg) => {
+                    return ConfIOReason::from_validation(format!(
+                        "wpgen.generator.speed_profile: {}",
+                        msg
+                    ))
+                    .err_result();
+                }
+            }
+        }
+        for (name, pool) in &self.generator.fields {
+            if let Err(msg) = pool.validate(name) {
+                return ConfIOReason::from_validation(format!("wpgen.generator.fields: {}", msg))
+                    .err_result();
+            }
+        }
         Ok(())
     }
 }
@@ -138,7 +325,51 @@ impl WpGenConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WpGenResolved {
     pub conf: WpGenConfig,
+    /// 主输出：`[output]`，或 `[[outputs]]` 的第一项
     pub out_sink: SinkInstanceConf,
+    /// 全部输出及其分流规则；单一 `[output]` 时仅含一项 `OutputRoute::All`
+    #[serde(default)]
+    pub outputs: Vec<WpGenOutput>,
+}
+
+/// 已解析的命名输出
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WpGenOutput {
+    pub name: String,
+    pub sink: SinkInstanceConf,
+    pub route: OutputRoute,
+}
+
+/// 输出的取行规则
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum OutputRoute {
+    /// 接收全部行
+    All,
+    /// 按比例分流（0, 1]
+    Ratio(f64),
+    /// 正则匹配的行
+    Filter(String),
+}
+
+impl WpGenResolved {
+    /// 全部输出；`outputs` 未经装载器填充时以 `out_sink` 作为唯一的全量输出
+    pub fn all_outputs(&self) -> Vec<WpGenOutput> {
+        if self.outputs.is_empty() {
+            vec![WpGenOutput::all(self.out_sink.clone())]
+        } else {
+            self.outputs.clone()
+        }
+    }
+}
+
+impl WpGenOutput {
+    pub fn all(sink: SinkInstanceConf) -> Self {
+        Self {
+            name: sink.name().clone(),
+            sink,
+            route: OutputRoute::All,
+        }
+    }
 }
 
 // WpGenResolved is assembled by loader; no direct disk IO here
@@ -249,6 +480,92 @@ mod tests {
         p
     }
 
+    #[test]
+    fn replay_mode_requires_section_and_positive_speed_factor() {
+        let base = r#"
+version = "1.0"
+
+[generator]
+mode = "replay"
+count = 10
+
+[output]
+connect = "file_json_sink"
+
+[logging]
+level = "info"
+output = "stdout"
+"#;
+        let conf: WpGenConfig = toml::from_str(base).unwrap();
+        assert_eq!(conf.generator.mode, GenMode::Replay);
+        assert!(conf.validate().is_err());
+
+        let text = base.replace(
+            "[output]",
+            "[generator.replay]\nfile = \"./data/capture.dat\"\ntimestamp_field = \"ts\"\n\n[output]",
+        );
+        let conf: WpGenConfig = toml::from_str(&text).unwrap();
+        conf.validate().expect("valid replay");
+        let replay = conf.generator.replay.as_ref().unwrap();
+        assert_eq!(replay.timestamp_format, "rfc3339");
+        assert_eq!(replay.speed_factor, 1.0);
+
+        let text = text.replace("timestamp_field", "speed_factor = 0.0\ntimestamp_field");
+        let conf: WpGenConfig = toml::from_str(&text).unwrap();
+        assert!(conf.validate().is_err());
+    }
+
+    #[test]
+    fn field_pools_parse_and_validate() {
+        let base = r#"
+version = "1.0"
+
+[generator]
+mode = "rule"
+seed = 42
+
+[generator.fields]
+user = { values = ["alice", "bob"], weights = [3, 1] }
+src_ip = { ip_range = "10.0.0.0/16" }
+status = { int_range = [200, 599] }
+req_id = { seq = 1000 }
+
+[output]
+connect = "file_json_sink"
+
+[logging]
+level = "info"
+output = "stdout"
+"#;
+        let conf: WpGenConfig = toml::from_str(base).unwrap();
+        conf.validate().expect("valid fields");
+        assert_eq!(conf.generator.seed, Some(42));
+        assert_eq!(conf.generator.fields.len(), 4);
+        assert_eq!(conf.generator.fields["req_id"].seq, Some(1000));
+        assert_eq!(
+            FieldPoolConfig::ipv4_range("10.0.3.7/16"),
+            Ok((u32::from(Ipv4Addr::new(10, 0, 0, 0)), 65536))
+        );
+
+        for bad in [
+            "weights = [3, 1, 1]",
+            "weights = [0, 0]",
+            "weights = [3, 1], seq = 1",
+        ] {
+            let text = base.replace("weights = [3, 1]", bad);
+            let conf: WpGenConfig = toml::from_str(&text).unwrap();
+            assert!(conf.validate().is_err(), "{}", bad);
+        }
+        for bad in ["10.0.0.0/33", "10.0.0/16", "10.0.0.0"] {
+            let text = base.replace("10.0.0.0/16", bad);
+            let conf: WpGenConfig = toml::from_str(&text).unwrap();
+            assert!(conf.validate().is_err(), "{}", bad);
+        }
+        let text = base.replace("[200, 599]", "[599, 200]");
+        let conf: WpGenConfig = toml::from_str(&text).unwrap();
+        assert!(conf.validate().is_err());
+    }
+
     #[test]
     fn to_log_conf_uses_plain_level() {
         let lg = LoggingConfig {
@@ -61,4 +61,4 @@ pub use types::SafeDefault;
 pub use wp_specs::WildArray;
 
 // 生成器速度模型配置
-pub use generator::{CombineModeConfig, SpeedProfileConfig};
+pub use generator::{CombineModeConfig, InterpolationConfig, SpeedProfileConfig};
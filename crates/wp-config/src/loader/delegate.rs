@@ -2,10 +2,17 @@ use crate::structure::ConfStdOperation;
 use orion_conf::error::OrionConfResult;
 use orion_variate::EnvDict;
 use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+use super::watch::{ConfWatcher, ConfigChange};
 
 pub struct ConfDelegate<T: ConfStdOperation> {
     pub(super) path: String,
     pub(super) _x: PhantomData<T>,
+    /// 首次订阅时创建，之后所有订阅者共享
+    pub(super) watcher: Mutex<Option<ConfWatcher>>,
 }
 
 impl<T: ConfStdOperation> ConfDelegate<T> {
@@ -13,6 +20,7 @@ impl<T: ConfStdOperation> ConfDelegate<T> {
         Self {
             path: path.to_string(),
             _x: PhantomData,
+            watcher: Mutex::new(None),
         }
     }
     pub fn init(&self) -> OrionConfResult<T> {
@@ -25,4 +33,47 @@ impl<T: ConfStdOperation> ConfDelegate<T> {
     pub fn load(&self, dict: &EnvDict) -> OrionConfResult<T> {
         T::load(self.path.as_str(), dict)
     }
+    /// 订阅配置文件变更；收到通知后调用 [`Self::load`] 重新加载
+    ///
+    /// 监听在 delegate 存活期间持续有效。
+    pub fn subscribe_changes(&self) -> OrionConfResult<broadcast::Receiver<ConfigChange>> {
+        let mut guard = self.watcher.lock().unwrap_or_else(|e| e.into_inner());
+        let watcher = match &mut *guard {
+            Some(watcher) => watcher,
+            slot => slot.insert(ConfWatcher::start(Path::new(&self.path))?),
+        };
+        Ok(watcher.subscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::wpgen::WpGenConfig;
+    use crate::loader::ChangeKind;
+    use std::io::Write;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn subscribers_are_notified_of_writes() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let delegate = ConfDelegate::<WpGenConfig>::new(file.path().to_str().unwrap());
+        let mut first = delegate.subscribe_changes().expect("subscribe");
+        let mut second = delegate.subscribe_changes().expect("subscribe");
+
+        file.write_all(b"version = \"1.0\"\n").unwrap();
+        file.flush().unwrap();
+
+        for rx in [&mut first, &mut second] {
+            let change = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+                .await
+                .expect("notified within 1s")
+                .expect("channel open");
+            assert_eq!(change.path, file.path());
+            assert!(matches!(
+                change.kind,
+                ChangeKind::Modified | ChangeKind::Created
+            ));
+        }
+    }
 }
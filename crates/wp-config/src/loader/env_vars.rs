@@ -0,0 +1,402 @@
+//! 配置变量：`${VAR:-default}` 默认值展开、`[variables]` 清单与变量来源报告
+//!
+//! 展开发生在 TOML 解析之前：带默认值的占位符取 EnvDict / 进程环境中的值，缺失时取默认值；
+//! 不带默认值的 `${VAR}` 原样保留，交由 EnvDict 替换（仍可由清单中的 `default` 兜底）。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use orion_conf::EnvTomlLoad;
+use orion_conf::error::OrionConfResult;
+use orion_error::{ErrorOwe, ErrorWith};
+use orion_variate::EnvDict;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::ENGINE_CONF_FILE;
+
+/// 参与变量扫描的工程配置目录
+const CONFIG_DIRS: [&str; 4] = ["conf", "connectors", "topology", "models/sinks"];
+/// 名称包含这些片段（不区分大小写）的变量值在输出中打码
+const SECRET_MARKERS: [&str; 3] = ["PASSWORD", "TOKEN", "SECRET"];
+const MASK: &str = "******";
+
+/// 引擎配置 `[variables]` 中的变量声明
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VariableSpec {
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 环境中缺失时使用的默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+pub type VariableManifest = BTreeMap<String, VariableSpec>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VarSource {
+    /// EnvDict 或进程环境
+    Env,
+    /// 占位符自带的 `:-default`
+    Default,
+    /// `[variables]` 中声明的默认值
+    Manifest,
+    Missing,
+}
+
+impl VarSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VarSource::Env => "env",
+            VarSource::Default => "default",
+            VarSource::Manifest => "manifest",
+            VarSource::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VariableUsage {
+    pub name: String,
+    pub source: VarSource,
+    /// 生效值；敏感变量已打码
+    pub value: Option<String>,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 引用该变量的文件（仅在清单中声明时为空）
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VariableReport {
+    pub variables: Vec<VariableUsage>,
+}
+
+impl VariableReport {
+    /// 无任何取值来源的变量：被引用的或声明为必需的
+    pub fn missing(&self) -> Vec<&VariableUsage> {
+        self.variables
+            .iter()
+            .filter(|v| v.source == VarSource::Missing)
+            .collect()
+    }
+
+    /// 按引用文件分组列出缺失变量，如 `conf/wparse.toml: A (desc), B; ...`
+    pub fn missing_text(&self) -> String {
+        let mut groups: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for var in self.missing() {
+            let label = match &var.description {
+                Some(desc) => format!("{} ({})", var.name, desc),
+                None => var.name.clone(),
+            };
+            if var.files.is_empty() {
+                groups.entry("[variables]").or_default().push(label);
+            }
+            for file in &var.files {
+                groups.entry(file.as_str()).or_default().push(label.clone());
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(file, vars)| format!("{}: {}", file, vars.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// 展开文本中的 `${VAR:-default}`，不带默认值的占位符保持不变
+pub fn expand_env_defaults(text: &str, dict: &EnvDict) -> String {
+    expand_placeholders(text, dict, &VariableManifest::new())
+}
+
+/// 读取 TOML 文件，先展开变量默认值（含清单默认值），再交由 EnvDict 替换并解析
+pub fn env_load_toml_expanded<T: EnvTomlLoad>(
+    path: &Path,
+    dict: &EnvDict,
+    manifest: &VariableManifest,
+) -> OrionConfResult<T> {
+    let text = std::fs::read_to_string(path).owe_conf().with(path)?;
+    T::env_parse_toml(&expand_placeholders(&text, dict, manifest), dict).with(path)
+}
+
+/// 从引擎配置原文读取 `[variables]` 清单；文件缺失或无法解析时为空
+pub fn load_variable_manifest(engine_conf: &Path) -> VariableManifest {
+    #[derive(Deserialize)]
+    struct ManifestOnly {
+        #[serde(default)]
+        variables: VariableManifest,
+    }
+    std::fs::read_to_string(engine_conf)
+        .ok()
+        .and_then(|text| toml::from_str::<ManifestOnly>(&text).ok())
+        .map(|m| m.variables)
+        .unwrap_or_default()
+}
+
+/// 自配置文件或目录向上查找所属工程的 `conf/wparse.toml`（最多 32 层），读取其 `[variables]` 清单
+///
+/// 未找到工程配置时为空，与 `load_variable_manifest` 一致。
+pub fn project_variable_manifest(start: &Path) -> VariableManifest {
+    let base = if start.is_absolute() {
+        start.to_path_buf()
+    } else {
+        match std::env::current_dir() {
+            Ok(cwd) => cwd.join(start),
+            Err(_) => return VariableManifest::new(),
+        }
+    };
+    let mut cur = if base.is_dir() {
+        base
+    } else {
+        match base.parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => return VariableManifest::new(),
+        }
+    };
+    for _ in 0..32 {
+        let candidate = cur.join("conf").join(ENGINE_CONF_FILE);
+        if candidate.is_file() {
+            return load_variable_manifest(&candidate);
+        }
+        if !cur.pop() {
+            break;
+        }
+    }
+    VariableManifest::new()
+}
+
+/// 读取工程内的 TOML 文件，变量默认值取自所属工程 `[variables]` 清单
+pub fn env_load_project_toml<T: EnvTomlLoad>(path: &Path, dict: &EnvDict) -> OrionConfResult<T> {
+    env_load_toml_expanded(path, dict, &project_variable_manifest(path))
+}
+
+/// 汇总工程配置引用的全部变量及其来源
+pub fn variable_report(work_root: &Path, dict: &EnvDict) -> VariableReport {
+    let manifest = load_variable_manifest(&work_root.join("conf").join(ENGINE_CONF_FILE));
+    // name -> (inline default, files)
+    let mut refs: BTreeMap<String, (Option<String>, Vec<String>)> = BTreeMap::new();
+    for dir in CONFIG_DIRS {
+        for file in toml_files(&work_root.join(dir)) {
+            let Ok(text) = std::fs::read_to_string(&file) else {
+                continue;
+            };
+            let rel = file
+                .strip_prefix(work_root)
+                .unwrap_or(&file)
+                .display()
+                .to_string();
+            for (name, default) in placeholders(&text) {
+                let entry = refs.entry(name.to_string()).or_default();
+                if entry.0.is_none() {
+                    entry.0 = default.map(str::to_string);
+                }
+                if !entry.1.contains(&rel) {
+                    entry.1.push(rel.clone());
+                }
+            }
+        }
+    }
+    for name in manifest.keys() {
+        refs.entry(name.clone()).or_default();
+    }
+
+    let variables = refs
+        .into_iter()
+        .map(|(name, (inline, files))| {
+            let spec = manifest.get(&name).cloned().unwrap_or_default();
+            let (source, value) = if let Some(v) = lookup_var(&name, dict) {
+                (VarSource::Env, Some(v))
+            } else if let Some(v) = inline {
+                (VarSource::Default, Some(v))
+            } else if let Some(v) = spec.default.clone() {
+                (VarSource::Manifest, Some(v))
+            } else {
+                (VarSource::Missing, None)
+            };
+            VariableUsage {
+                value: value.map(|v| mask_secret(&name, v)),
+                source,
+                required: spec.required,
+                description: spec.description,
+                files,
+                name,
+            }
+        })
+        // 仅声明、非必需且未被引用的变量不参与报告
+        .filter(|v| v.source != VarSource::Missing || v.required || !v.files.is_empty())
+        .collect();
+    VariableReport { variables }
+}
+
+/// 名称看起来是密钥时返回打码值
+pub fn mask_secret(name: &str, value: String) -> String {
+    let upper = name.to_ascii_uppercase();
+    if SECRET_MARKERS.iter().any(|m| upper.contains(m)) {
+        MASK.to_string()
+    } else {
+        value
+    }
+}
+
+fn lookup_var(name: &str, dict: &EnvDict) -> Option<String> {
+    dict.get(name)
+        .map(|v| v.to_string())
+        .or_else(|| std::env::var(name).ok())
+}
+
+pub(crate) fn expand_placeholders(
+    text: &str,
+    dict: &EnvDict,
+    manifest: &VariableManifest,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((start, end, name, default)) = next_placeholder(rest) {
+        out.push_str(&rest[..start]);
+        let value = match default {
+            Some(default) => Some(lookup_var(name, dict).unwrap_or_else(|| default.to_string())),
+            None if lookup_var(name, dict).is_none() => {
+                manifest.get(name).and_then(|spec| spec.default.clone())
+            }
+            None => None,
+        };
+        match value {
+            Some(v) => out.push_str(&v),
+            None => out.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn placeholders(text: &str) -> Vec<(&str, Option<&str>)> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some((_, end, name, default)) = next_placeholder(rest) {
+        found.push((name, default));
+        rest = &rest[end..];
+    }
+    found
+}
+
+/// 查找下一个 `${NAME}` / `${NAME:-default}`，返回（起始、结束偏移，名称，默认值）
+fn next_placeholder(text: &str) -> Option<(usize, usize, &str, Option<&str>)> {
+    let mut offset = 0;
+    while let Some(pos) = text[offset..].find("${") {
+        let start = offset + pos;
+        let close = text[start..].find('}')? + start;
+        let inner = &text[start + 2..close];
+        let (name, default) = match inner.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (inner, None),
+        };
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Some((start, close + 1, name, default));
+        }
+        offset = start + 2;
+    }
+    None
+}
+
+fn toml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(toml_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "toml") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orion_variate::ValueType;
+
+    #[test]
+    fn expands_inline_and_manifest_defaults() {
+        let mut dict = EnvDict::new();
+        dict.insert("HOST", ValueType::from("10.0.0.1"));
+        let text = r#"a = "${HOST:-localhost}" b = "${PORT:-514}" c = "${KEEP}" d = "${NAME}""#;
+        assert_eq!(
+            expand_env_defaults(text, &dict),
+            r#"a = "10.0.0.1" b = "514" c = "${KEEP}" d = "${NAME}""#
+        );
+
+        let manifest = VariableManifest::from([(
+            "NAME".to_string(),
+            VariableSpec {
+                default: Some("wp".into()),
+                ..Default::default()
+            },
+        )]);
+        assert_eq!(
+            expand_placeholders(text, &dict, &manifest),
+            r#"a = "10.0.0.1" b = "514" c = "${KEEP}" d = "wp""#
+        );
+        // 空默认值与非变量的 `${` 保持语义
+        assert_eq!(expand_env_defaults("${WP_NONE_X:-}", &dict), "");
+        assert_eq!(expand_env_defaults("${not a var}", &dict), "${not a var}");
+    }
+
+    #[test]
+    fn report_lists_sources_masks_secrets_and_groups_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("conf")).unwrap();
+        std::fs::create_dir_all(root.join("connectors/sink.d")).unwrap();
+        std::fs::write(
+            root.join("conf").join(ENGINE_CONF_FILE),
+            r#"
+[rescue]
+path = "${WP_T_RESCUE:-./data/rescue}"
+
+[variables]
+WP_T_KAFKA_TOKEN = { required = true, description = "kafka sasl token" }
+WP_T_REGION = { default = "cn" }
+WP_T_ADMIN = { required = true, description = "admin contact" }
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("connectors/sink.d/kafka.toml"),
+            "[[connectors]]\nid = \"${WP_T_REGION}_sink\"\nparams = { token = \"${WP_T_KAFKA_TOKEN}\", user = \"${WP_T_USER}\" }\n",
+        )
+        .unwrap();
+
+        let mut dict = EnvDict::new();
+        dict.insert("WP_T_KAFKA_TOKEN", ValueType::from("s3cr3t"));
+        let report = variable_report(root, &dict);
+        let var = |name: &str| report.variables.iter().find(|v| v.name == name).unwrap();
+
+        assert_eq!(var("WP_T_KAFKA_TOKEN").source, VarSource::Env);
+        assert_eq!(var("WP_T_KAFKA_TOKEN").value.as_deref(), Some(MASK));
+        assert_eq!(var("WP_T_RESCUE").source, VarSource::Default);
+        assert_eq!(var("WP_T_REGION").source, VarSource::Manifest);
+        assert_eq!(var("WP_T_REGION").value.as_deref(), Some("cn"));
+        assert_eq!(
+            var("WP_T_REGION").files,
+            vec!["connectors/sink.d/kafka.toml".to_string()]
+        );
+
+        let missing: Vec<&str> = report.missing().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(missing, vec!["WP_T_ADMIN", "WP_T_USER"]);
+        assert_eq!(
+            report.missing_text(),
+            "[variables]: WP_T_ADMIN (admin contact); connectors/sink.d/kafka.toml: WP_T_USER"
+        );
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains("s3cr3t"));
+    }
+}
@@ -0,0 +1,263 @@
+//! 配置文件的 include / 覆盖：`include = ["base.toml", "site.toml"]`
+//!
+//! 被 include 的文件按列出顺序深度优先合并，本文件的内容最后覆盖其上：
+//! - 表逐层合并，同名标量以后出现者为准；
+//! - 数组整体替换；键名带 `+` 后缀（如 `"sources+" = [...]`）时追加到已有数组之后；
+//! - include 路径相对于声明它的文件，循环引用与缺失文件报错并给出完整 include 链。
+
+use std::path::{Path, PathBuf};
+
+use orion_conf::EnvTomlLoad;
+use orion_conf::error::{ConfIOReason, OrionConfResult};
+use orion_error::{ErrorOwe, ErrorWith, ToStructError, UvsValidationFrom};
+use orion_variate::EnvDict;
+
+use super::env_vars::{VariableManifest, expand_placeholders};
+
+const INCLUDE_KEY: &str = "include";
+const APPEND_SUFFIX: char = '+';
+
+/// 合并后的配置表及参与合并的文件（按优先级由低到高）
+#[derive(Debug, Clone, Default)]
+pub struct IncludedToml {
+    pub table: toml::Table,
+    pub files: Vec<PathBuf>,
+}
+
+/// 读取 `path` 并递归展开其 include，返回合并结果
+pub fn load_toml_with_includes(
+    path: &Path,
+    dict: &EnvDict,
+    manifest: &VariableManifest,
+) -> OrionConfResult<IncludedToml> {
+    let mut merged = IncludedToml::default();
+    let mut chain = Vec::new();
+    load_layer(path, dict, manifest, &mut chain, &mut merged)?;
+    Ok(merged)
+}
+
+/// 与 [`super::env_load_toml_expanded`] 相同，但先展开 include；无 include 时直接解析原文
+pub fn env_load_toml_included<T: EnvTomlLoad>(
+    path: &Path,
+    dict: &EnvDict,
+    manifest: &VariableManifest,
+) -> OrionConfResult<T> {
+    let merged = load_toml_with_includes(path, dict, manifest)?;
+    if merged.files.len() == 1 {
+        return super::env_load_toml_expanded(path, dict, manifest);
+    }
+    let text = toml::to_string(&merged.table).owe_conf().with(path)?;
+    T::env_parse_toml(&text, dict).with(path)
+}
+
+/// 参与合并的全部配置文件（按优先级由低到高）
+pub fn included_files(path: &Path, dict: &EnvDict) -> OrionConfResult<Vec<PathBuf>> {
+    Ok(load_toml_with_includes(path, dict, &VariableManifest::new())?.files)
+}
+
+fn load_layer(
+    path: &Path,
+    dict: &EnvDict,
+    manifest: &VariableManifest,
+    chain: &mut Vec<PathBuf>,
+    merged: &mut IncludedToml,
+) -> OrionConfResult<()> {
+    let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if chain
+        .iter()
+        .any(|p| p.canonicalize().ok().as_ref() == Some(&key))
+    {
+        chain.push(path.to_path_buf());
+        return ConfIOReason::from_validation(format!("include cycle: {}", chain_text(chain)))
+            .err_result();
+    }
+    chain.push(path.to_path_buf());
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if chain.len() > 1 => {
+            return ConfIOReason::from_validation(format!(
+                "include file {} unreadable ({}): {}",
+                path.display(),
+                e,
+                chain_text(chain)
+            ))
+            .err_result();
+        }
+        Err(e) => return Err(e).owe_conf().with(path),
+    };
+    let mut table: toml::Table = toml::from_str(&expand_placeholders(&text, dict, manifest))
+        .owe_conf()
+        .with(path)?;
+
+    let includes = match table.remove(INCLUDE_KEY) {
+        None => Vec::new(),
+        Some(toml::Value::Array(items)) => items
+            .into_iter()
+            .map(|item| match item {
+                toml::Value::String(s) => Ok(s),
+                other => Err(other),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .or_else(|other| {
+                ConfIOReason::from_validation(format!(
+                    "'include' entries must be file paths, got {} ({})",
+                    other,
+                    chain_text(chain)
+                ))
+                .err_result()
+            })?,
+        Some(_) => {
+            return ConfIOReason::from_validation(format!(
+                "'include' must be an array of file paths ({})",
+                chain_text(chain)
+            ))
+            .err_result();
+        }
+    };
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    for include in includes {
+        load_layer(&base_dir.join(include), dict, manifest, chain, merged)?;
+    }
+    merge_tables(&mut merged.table, table);
+    merged.files.push(chain.pop().unwrap_or_default());
+    Ok(())
+}
+
+/// 将 `overlay` 合并进 `base`：表递归合并，其余值（含数组）替换，`key+` 数组追加
+pub fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        if let Some(name) = key.strip_suffix(APPEND_SUFFIX)
+            && let toml::Value::Array(items) = value
+        {
+            match base.get_mut(name) {
+                Some(toml::Value::Array(existing)) => existing.extend(items),
+                _ => {
+                    base.insert(name.to_string(), toml::Value::Array(items));
+                }
+            }
+            continue;
+        }
+        match value {
+            toml::Value::Table(sub) => {
+                let entry = base
+                    .entry(key)
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if !entry.is_table() {
+                    *entry = toml::Value::Table(toml::Table::new());
+                }
+                if let toml::Value::Table(target) = entry {
+                    merge_tables(target, sub);
+                }
+            }
+            other => {
+                base.insert(key, other);
+            }
+        }
+    }
+}
+
+fn chain_text(chain: &[PathBuf]) -> String {
+    let files: Vec<String> = chain.iter().map(|p| p.display().to_string()).collect();
+    format!("include chain: {}", files.join(" -> "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, body).unwrap();
+        path
+    }
+
+    #[test]
+    fn nested_tables_follow_include_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "shared/base.toml",
+            r#"
+[performance]
+parse_workers = 2
+rate_limit_rps = 1000
+
+[metrics]
+enabled = true
+listen = "0.0.0.0:9464"
+
+[tags]
+list = ["a", "b"]
+more = ["x"]
+"#,
+        );
+        write(
+            root,
+            "site.toml",
+            r#"
+[performance]
+parse_workers = 8
+
+[tags]
+list = ["c"]
+"more+" = ["y"]
+"#,
+        );
+        let main = write(
+            root,
+            "wparse.toml",
+            r#"
+include = ["shared/base.toml", "site.toml"]
+
+[metrics]
+listen = "10.0.0.1:9464"
+"#,
+        );
+
+        let merged = load_toml_with_includes(&main, &EnvDict::new(), &VariableManifest::new())
+            .expect("merge");
+        let names: Vec<String> = merged
+            .files
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, vec!["shared/base.toml", "site.toml", "wparse.toml"]);
+
+        let t = &merged.table;
+        assert_eq!(t["performance"]["parse_workers"].as_integer(), Some(8));
+        assert_eq!(t["performance"]["rate_limit_rps"].as_integer(), Some(1000));
+        assert_eq!(t["metrics"]["enabled"].as_bool(), Some(true));
+        assert_eq!(t["metrics"]["listen"].as_str(), Some("10.0.0.1:9464"));
+        assert_eq!(t["tags"]["list"], toml::Value::from(vec!["c"]));
+        assert_eq!(t["tags"]["more"], toml::Value::from(vec!["x", "y"]));
+        assert!(t.get("include").is_none());
+    }
+
+    #[test]
+    fn include_cycle_and_missing_file_report_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "a.toml", "include = [\"b.toml\"]\n");
+        write(root, "b.toml", "include = [\"a.toml\"]\n");
+        let main = write(root, "main.toml", "include = [\"a.toml\"]\n");
+        let err = load_toml_with_includes(&main, &EnvDict::new(), &VariableManifest::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("include cycle"), "{}", err);
+        assert!(
+            err.contains("main.toml -> ") && err.contains("b.toml -> "),
+            "{}",
+            err
+        );
+
+        let main = write(root, "main.toml", "include = [\"a2.toml\"]\n");
+        write(root, "a2.toml", "include = [\"gone.toml\"]\n");
+        let err = load_toml_with_includes(&main, &EnvDict::new(), &VariableManifest::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("gone.toml"), "{}", err);
+        assert!(err.contains("a2.toml -> "), "{}", err);
+    }
+}
@@ -20,9 +20,19 @@
 
 pub mod batch;
 pub mod delegate;
+pub mod env_vars;
+pub mod include;
 pub mod traits;
 pub mod validate_vars;
+pub mod watch;
 
 pub use delegate::ConfDelegate;
+pub use env_vars::{
+    VarSource, VariableManifest, VariableReport, VariableSpec, VariableUsage,
+    env_load_project_toml, env_load_toml_expanded, expand_env_defaults, project_variable_manifest,
+    variable_report,
+};
+pub use include::{IncludedToml, env_load_toml_included, included_files, load_toml_with_includes};
 pub use traits::ConfigLoader;
 pub use validate_vars::check_unresolved_variables;
+pub use watch::{ChangeKind, ConfWatcher, ConfigChange};
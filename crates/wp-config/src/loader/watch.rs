@@ -0,0 +1,86 @@
+//! 配置文件变更订阅：监听文件所在目录（兼容编辑器"写临时文件再改名"的保存方式），
+//! 过滤出目标文件的事件后广播给所有订阅者。
+
+use std::path::{Path, PathBuf};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use orion_conf::error::{ConfIOReason, OrionConfResult};
+use orion_error::{ErrorOwe, ErrorWith, ToStructError, UvsValidationFrom};
+use tokio::sync::broadcast;
+
+/// 未被订阅者及时消费的事件上限；超出后慢订阅者收到 `Lagged`，重新 load 即可
+const CHANGE_CHANNEL_CAP: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+}
+
+/// 单个配置文件的监听器，持有期间持续广播变更
+pub struct ConfWatcher {
+    _watcher: RecommendedWatcher,
+    sender: broadcast::Sender<ConfigChange>,
+}
+
+impl ConfWatcher {
+    pub fn start(path: &Path) -> OrionConfResult<Self> {
+        let Some(file_name) = path.file_name().map(|n| n.to_os_string()) else {
+            return ConfIOReason::from_validation(format!(
+                "cannot watch '{}': not a file path",
+                path.display()
+            ))
+            .err_result();
+        };
+        let dir = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let (sender, _) = broadcast::channel(CHANGE_CHANNEL_CAP);
+        let tx = sender.clone();
+        let conf_path = path.to_path_buf();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            let kind = match event.kind {
+                EventKind::Create(_) => ChangeKind::Created,
+                EventKind::Modify(_) => ChangeKind::Modified,
+                EventKind::Remove(_) => ChangeKind::Removed,
+                _ => return,
+            };
+            if event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == Some(file_name.as_os_str()))
+            {
+                // 无订阅者时发送失败，忽略即可
+                let _ = tx.send(ConfigChange {
+                    path: conf_path.clone(),
+                    kind,
+                });
+            }
+        })
+        .owe_conf()
+        .with(path)?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .owe_conf()
+            .with(path)?;
+        Ok(Self {
+            _watcher: watcher,
+            sender,
+        })
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ConfigChange> {
+        self.sender.subscribe()
+    }
+}
@@ -1,6 +1,7 @@
 use derive_more::Display;
 
 use crate::types::AnyResult;
+use wp_data_model::limits::RecordLimits;
 
 /// 运行模式：批处理/常驻
 #[derive(Clone, Debug, PartialEq, Eq, Display)]
@@ -28,6 +29,23 @@ pub struct RuntimeArgs {
     pub skip_sink: bool,
     // 语义分析开关：从 EngineConfig [semantic].enabled 派生
     pub semantic_enabled: bool,
+    // OML 断言开关：从 EngineConfig [oml] 派生
+    pub oml_compile_assert: bool,
+    pub oml_assert_strict: bool,
+    // OML 查询缓存有效期（毫秒）：从 EngineConfig [oml].lookup_cache_ttl_ms 派生
+    pub oml_lookup_cache_ttl_ms: Option<u64>,
+    // Prometheus 指标监听地址：从 EngineConfig [metrics] 派生，未启用时为 None
+    pub metrics_listen: Option<String>,
+    // 累计计数落盘周期（秒）：从 EngineConfig [stat].checkpoint_sec 派生，0 表示关闭
+    pub stats_checkpoint_sec: u64,
+    // 退出时 sink drain 时限（毫秒）：从 EngineConfig [shutdown].drain_timeout_ms 派生
+    pub drain_timeout_ms: u64,
+    // 记录级追踪采样率：从 EngineConfig [trace] 派生，未启用时为 None
+    pub trace_sample_rate: Option<f64>,
+    // 追踪环形缓冲容量：从 EngineConfig [trace].capacity 派生
+    pub trace_capacity: usize,
+    // 单条记录体量限制：从 EngineConfig [record_limits] 派生
+    pub record_limits: RecordLimits,
 }
 
 impl Default for RuntimeArgs {
@@ -46,6 +64,15 @@ impl Default for RuntimeArgs {
             skip_parse: false,
             skip_sink: false,
             semantic_enabled: false,
+            oml_compile_assert: true,
+            oml_assert_strict: false,
+            oml_lookup_cache_ttl_ms: None,
+            metrics_listen: None,
+            stats_checkpoint_sec: 0,
+            drain_timeout_ms: 5000,
+            trace_sample_rate: None,
+            trace_capacity: 4096,
+            record_limits: RecordLimits::default(),
         }
     }
 }
@@ -2,7 +2,9 @@ use super::types::RouteSink;
 use super::types::{ConnectorRec, DefaultsBody, RouteFile, StringOrArray};
 use crate::sinks::io::business_dir;
 use crate::sinks::{load_connectors_for, load_route_files_from, load_sink_defaults};
-use crate::structure::{SinkInstanceConf, SinkRouteConf, Validate as ConfValidate};
+use crate::structure::{
+    RouteSchemaConf, SinkInstanceConf, SinkRouteConf, Validate as ConfValidate,
+};
 use orion_conf::error::{ConfIOReason, OrionConfResult};
 use orion_error::{ToStructError, UvsValidationFrom};
 use orion_variate::EnvDict;
@@ -12,7 +14,7 @@ use std::sync::Arc;
 use wp_connector_api::ParamMap;
 use wp_model_core::model::fmt_def::TextFmt;
 
-use crate::structure::{FlexGroup, extend_matches};
+use crate::structure::{BatchMode, FlexGroup, extend_matches};
 
 const CONNECTOR_TYPE_FILE: &str = "file";
 const CONNECTOR_TYPE_TEST_RESCUE: &str = "test_rescue";
@@ -30,8 +32,17 @@ fn build_sink_instance(
         .inner_name()
         .map(|s| s.to_string())
         .unwrap_or_else(|| format!("[{}]", index));
-    let merged_params = merge_sink_params(group_name, index, origin, conn, r)?;
-    let fmt = decide_fmt(conn, &merged_params);
+    let mut merged_params = merge_sink_params(group_name, index, origin, conn, r)?;
+    // format.kind 优先于 connector 的 fmt；文件类同时写回参数表，供工厂按 fmt 构建
+    let format_kind = r.format().and_then(|f| f.kind.as_deref());
+    if let Some(kind) = format_kind
+        && is_file_kind(conn)
+    {
+        merged_params.insert(FIELD_FMT.to_string(), serde_json::Value::from(kind));
+    }
+    let fmt = format_kind
+        .map(TextFmt::from)
+        .unwrap_or_else(|| decide_fmt(conn, &merged_params));
     let mut sink = crate::structure::SinkInstanceConf::new_type(
         sink_name.clone(),
         fmt,
@@ -44,6 +55,8 @@ fn build_sink_instance(
     sink.connector_id = Some(conn.id.clone());
     sink.group_name = Some(group_name.to_string());
     sink.expect = r.expect().cloned();
+    sink.breaker = r.breaker().cloned();
+    sink.format = r.format().cloned();
     sink.set_tags(r.tags().cloned().unwrap_or_default());
     Ok(sink)
 }
@@ -80,7 +93,7 @@ fn pick_string(m: &ParamMap, key: &str) -> Option<String> {
 /// - 文件类（file/test_rescue）：从合并后的参数表读取 `fmt`（允许覆写），若缺省则默认 `json`
 /// - 其它类型：固定为 `json`
 fn decide_fmt(conn: &ConnectorRec, params: &ParamMap) -> TextFmt {
-    if conn.kind == CONNECTOR_TYPE_FILE || conn.kind == CONNECTOR_TYPE_TEST_RESCUE {
+    if is_file_kind(conn) {
         let s = pick_string(params, FIELD_FMT).unwrap_or_else(|| DEFAULT_OUTPUT_FORMAT.to_string());
         TextFmt::from(s.as_str())
     } else {
@@ -88,6 +101,10 @@ fn decide_fmt(conn: &ConnectorRec, params: &ParamMap) -> TextFmt {
     }
 }
 
+fn is_file_kind(conn: &ConnectorRec) -> bool {
+    conn.kind == CONNECTOR_TYPE_FILE || conn.kind == CONNECTOR_TYPE_TEST_RESCUE
+}
+
 fn merge_sink_params(
     group_name: &str,
     index: usize,
@@ -235,6 +252,34 @@ fn apply_group_metadata(
     if let Some(size) = rf.sink_group.batch_size {
         g.batch_size = size;
     }
+    if let Some(mode) = rf.sink_group.batch_mode {
+        g.batch_mode = mode;
+    }
+    if let Some(adaptive) = rf.sink_group.adaptive.as_ref() {
+        g.adaptive = adaptive.clone();
+    }
+    if let Some(workers) = rf.sink_group.workers {
+        g.workers = workers;
+    }
+    if let Some(preserve) = rf.sink_group.preserve_order {
+        g.preserve_order = preserve;
+    }
+}
+
+/// 读取路由的字段类型约束：并入 `file` 后校验类型名
+fn load_group_schema(rf: &RouteFile) -> OrionConfResult<Option<RouteSchemaConf>> {
+    let Some(mut schema) = rf.sink_group.schema.clone() else {
+        return Ok(None);
+    };
+    let base = rf.origin.as_deref().and_then(Path::parent);
+    if let Err(e) = schema.load_file(base).and_then(|_| schema.validate()) {
+        return ConfIOReason::from_validation(format!(
+            "group '{}' schema: {:#}",
+            rf.sink_group.name, e
+        ))
+        .err_result();
+    }
+    Ok(Some(schema))
 }
 
 /// 从单个 RouteFile 构建标准输出 SinkRouteConf（统一事实源）
@@ -290,6 +335,28 @@ pub fn build_route_conf_from_with(
     group.oml = extend_matches(oml_vec);
     group.rule = extend_matches(rule_vec);
     apply_group_metadata(&mut group, rf, defaults);
+    if group.workers > 32 {
+        return ConfIOReason::from_validation(format!(
+            "group '{}' workers must be <= 32",
+            rf.sink_group.name
+        ))
+        .err_result();
+    }
+    if group.batch_mode == BatchMode::Adaptive
+        && let Err(e) = group.adaptive.validate()
+    {
+        return ConfIOReason::from_validation(format!(
+            "group '{}' adaptive batch: {}",
+            rf.sink_group.name, e
+        ))
+        .err_result();
+    }
+    if let Some(schema) = load_group_schema(rf)? {
+        for sink in group.sinks.iter_mut() {
+            sink.schema = Some(schema.clone());
+        }
+        group.schema = Some(schema);
+    }
 
     Ok(SinkRouteConf {
         version: "2.0".into(),
@@ -456,6 +523,11 @@ mod tests {
                 parallel: None,
                 batch_timeout_ms: None,
                 batch_size: None,
+                batch_mode: None,
+                adaptive: None,
+                workers: None,
+                preserve_order: None,
+                schema: None,
             },
             origin: None,
         };
@@ -483,6 +555,11 @@ mod tests {
                 parallel: None,
                 batch_timeout_ms: None,
                 batch_size: None,
+                batch_mode: None,
+                adaptive: None,
+                workers: None,
+                preserve_order: None,
+                schema: None,
             },
             origin: None,
         };
@@ -512,6 +589,11 @@ mod tests {
                 parallel: None,
                 batch_timeout_ms: None,
                 batch_size: None,
+                batch_mode: None,
+                adaptive: None,
+                workers: None,
+                preserve_order: None,
+                schema: None,
             },
             origin: None,
         };
@@ -540,6 +622,11 @@ mod tests {
                 parallel: None,
                 batch_timeout_ms: None,
                 batch_size: None,
+                batch_mode: None,
+                adaptive: None,
+                workers: None,
+                preserve_order: None,
+                schema: None,
             },
             origin: None,
         };
@@ -1,6 +1,6 @@
 use super::types::*;
 use crate::connectors::load_connector_defs_from_dir;
-use orion_conf::EnvTomlLoad;
+use crate::loader::env_vars::env_load_project_toml;
 use orion_conf::error::OrionConfResult;
 use orion_error::ErrorWith;
 use orion_variate::{EnvDict, EnvEvalable};
@@ -62,9 +62,7 @@ pub fn load_route_files_from(dir: &Path, dict: &EnvDict) -> OrionConfResult<Vec<
 
     for fstr in uniq.into_iter() {
         let fp = Path::new(&fstr).to_path_buf();
-        let mut rf: RouteFile = RouteFile::env_load_toml(&fp, dict)
-            .with(&fp)?
-            .env_eval(dict);
+        let mut rf: RouteFile = env_load_project_toml(&fp, dict).with(&fp)?.env_eval(dict);
         rf.origin = Some(fp.clone());
         out.push(rf);
     }
@@ -73,13 +71,13 @@ pub fn load_route_files_from(dir: &Path, dict: &EnvDict) -> OrionConfResult<Vec<
 
 pub fn load_sink_defaults<P: AsRef<Path>>(
     sink_root: P,
-    _dict: &EnvDict,
+    dict: &EnvDict,
 ) -> OrionConfResult<Option<DefaultsBody>> {
     let p = sink_root.as_ref().join(PATH_DEFAULTS_FILE);
     if !p.exists() {
         return Ok(None);
     }
-    let f: super::types::DefaultsFile = DefaultsFile::env_load_toml(&p, _dict)?;
+    let f: DefaultsFile = env_load_project_toml(&p, dict)?;
     Ok(Some(f.defaults))
 }
 
@@ -89,3 +87,100 @@ pub fn business_dir<P: AsRef<Path>>(root: P) -> PathBuf {
 pub fn infra_dir<P: AsRef<Path>>(root: P) -> PathBuf {
     root.as_ref().join(PATH_INFRA_SUBDIR)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orion_variate::ValueType;
+    use std::fs;
+
+    #[test]
+    fn route_files_and_defaults_expand_env_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink_root = dir.path();
+        let biz = business_dir(sink_root);
+        fs::create_dir_all(&biz).unwrap();
+        fs::write(
+            biz.join("audit.toml"),
+            r#"
+[sink_group]
+name = "${GROUP_NAME:-audit}"
+oml = "${GROUP_OML:-audit_*}"
+[[sink_group.sinks]]
+use = "file_sink"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            sink_root.join(PATH_DEFAULTS_FILE),
+            r#"
+[defaults]
+tags = ["zone:${ZONE:-cn}"]
+[defaults.expect]
+window = "${EXPECT_WINDOW:-5m}"
+"#,
+        )
+        .unwrap();
+
+        let mut dict = EnvDict::new();
+        dict.insert("GROUP_OML", ValueType::from("audit_v2"));
+        let routes = load_route_files_from(&biz, &dict).expect("load routes");
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].sink_group.name, "audit");
+        let oml = routes[0].sink_group.oml.as_ref().map(StringOrArray::as_vec);
+        assert_eq!(oml, Some(vec!["audit_v2".to_string()]));
+
+        let defaults = load_sink_defaults(sink_root, &dict)
+            .expect("load defaults")
+            .expect("defaults present");
+        assert_eq!(defaults.tags, Some(vec!["zone:cn".to_string()]));
+        assert_eq!(defaults.expect.window.as_deref(), Some("5m"));
+    }
+
+    #[test]
+    fn route_files_and_defaults_use_project_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let work_root = dir.path();
+        fs::create_dir_all(work_root.join("conf")).unwrap();
+        fs::write(
+            work_root.join("conf").join("wparse.toml"),
+            r#"
+[variables.GROUP_NAME]
+default = "audit"
+[variables.ZONE]
+default = "eu"
+"#,
+        )
+        .unwrap();
+        let sink_root = work_root.join("topology").join("sinks");
+        let biz = business_dir(&sink_root);
+        fs::create_dir_all(&biz).unwrap();
+        fs::write(
+            biz.join("audit.toml"),
+            r#"
+[sink_group]
+name = "${GROUP_NAME}"
+oml = "audit_*"
+[[sink_group.sinks]]
+use = "file_sink"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            sink_root.join(PATH_DEFAULTS_FILE),
+            r#"
+[defaults]
+tags = ["zone:${ZONE}"]
+"#,
+        )
+        .unwrap();
+
+        let dict = EnvDict::new();
+        let routes = load_route_files_from(&biz, &dict).expect("load routes");
+        assert_eq!(routes[0].sink_group.name, "audit");
+        let defaults = load_sink_defaults(&sink_root, &dict)
+            .expect("load defaults")
+            .expect("defaults present");
+        assert_eq!(defaults.tags, Some(vec!["zone:eu".to_string()]));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::connectors::ConnectorTomlFile;
 use crate::structure::GroupExpectSpec;
-use crate::structure::SinkExpectOverride;
+use crate::structure::{AdaptiveBatchConf, BatchMode};
+use crate::structure::{RouteSchemaConf, SinkBreakerConf, SinkExpectOverride, SinkFormatConf};
 use crate::utils::env_eval_params;
 use crate::utils::env_eval_vec;
 use orion_variate::EnvEvaluable;
@@ -48,6 +49,21 @@ pub struct RouteGroup {
     /// 批量缓冲大小，默认 1024 条记录
     #[serde(default)]
     pub batch_size: Option<usize>,
+    /// 批量模式：`fixed`（默认）或 `adaptive`
+    #[serde(default)]
+    pub batch_mode: Option<BatchMode>,
+    /// 自适应批量参数（`[sink_group.adaptive]`）
+    #[serde(default)]
+    pub adaptive: Option<AdaptiveBatchConf>,
+    /// 组内 OML 转换线程数
+    #[serde(default)]
+    pub workers: Option<usize>,
+    /// 多线程转换时保持输入顺序
+    #[serde(default)]
+    pub preserve_order: Option<bool>,
+    /// 字段类型约束（`[sink_group.schema]`）
+    #[serde(default)]
+    pub schema: Option<RouteSchemaConf>,
     #[serde(default)]
     pub sinks: Vec<RouteSink>,
 }
@@ -77,6 +93,12 @@ pub struct RouteSink {
     tags: Option<Vec<String>>,
     #[serde(default)]
     expect: Option<SinkExpectOverride>,
+    /// sink 级熔断配置
+    #[serde(default)]
+    breaker: Option<SinkBreakerConf>,
+    /// sink 级输出形态
+    #[serde(default)]
+    format: Option<SinkFormatConf>,
     #[serde(default)]
     filter: Option<String>,
     /// 当 cond 结果等于该值时投递；默认为 true
@@ -110,6 +132,12 @@ impl RouteSink {
     pub fn expect(&self) -> Option<&SinkExpectOverride> {
         self.expect.as_ref()
     }
+    pub fn breaker(&self) -> Option<&SinkBreakerConf> {
+        self.breaker.as_ref()
+    }
+    pub fn format(&self) -> Option<&SinkFormatConf> {
+        self.format.as_ref()
+    }
     pub fn filter_path(&self) -> Option<&str> {
         self.filter.as_deref()
     }
@@ -1,4 +1,5 @@
 use super::types::WpSourcesConfig;
+use crate::loader::env_vars::{expand_placeholders, project_variable_manifest};
 use crate::loader::traits::ConfigLoader;
 use crate::sources::load_connectors_for;
 use crate::sources::types::SourceConnector;
@@ -71,7 +72,9 @@ pub fn load_source_instances_from_str(
     start: &Path,
     dict: &EnvDict,
 ) -> OrionConfResult<Vec<SourceInstanceConf>> {
-    let src_conf: WpSourcesConfig = WpSourcesConfig::env_parse_toml(config_str, dict)
+    // `${VAR:-default}` 与工程 `[variables]` 默认值先于 EnvDict 替换展开，与连接器、引擎配置一致
+    let expanded = expand_placeholders(config_str, dict, &project_variable_manifest(start));
+    let src_conf: WpSourcesConfig = WpSourcesConfig::env_parse_toml(&expanded, dict)
         .owe_conf()
         .want("parse sources")?
         .env_eval(dict);
@@ -116,8 +119,13 @@ pub fn build_source_instances(
             .to_err()
         })?;
         let merged = merge_source_params(&conn.default_params, &s.params, &conn.allow_override)?;
+        let rate_limit = s.rate_limit_conf().map_err(|e| {
+            ConfIOReason::from_validation(format!("source '{}': {}", s.key, e)).to_err()
+        })?;
         let mut inst = SourceInstanceConf::new_type(s.key, conn.kind.clone(), merged, s.tags);
         inst.connector_id = Some(conn.id.clone());
+        inst.rate_limit = rate_limit;
+        inst.preproc = s.preproc;
         srcins_confs.push(inst);
     }
     Ok(srcins_confs)
@@ -283,6 +291,10 @@ connect = "conn1"
                     connect: "c1".into(),
                     tags: vec![],
                     params: ParamMap::new(),
+                    rate_limit: None,
+                    burst: None,
+                    rate_limit_policy: None,
+                    preproc: Vec::new(),
                 },
                 types::WpSource {
                     key: "s2".into(),
@@ -290,6 +302,10 @@ connect = "conn1"
                     connect: "c1".into(),
                     tags: vec![],
                     params: ParamMap::new(),
+                    rate_limit: None,
+                    burst: None,
+                    rate_limit_policy: None,
+                    preproc: Vec::new(),
                 },
             ],
         };
@@ -328,6 +344,84 @@ type = "dummy"
         assert!(e.contains("duplicate connector id"));
     }
 
+    #[test]
+    fn source_instances_from_file_expand_env_defaults() {
+        let base = tmp_dir("src_env_default");
+        let cdir = base.join("connectors").join("source.d");
+        fs::create_dir_all(&cdir).unwrap();
+        fs::write(
+            cdir.join("syslog.toml"),
+            r#"[[connectors]]
+id = "syslog_src"
+type = "syslog"
+allow_override = ["port"]
+[connectors.params]
+port = 514
+"#,
+        )
+        .unwrap();
+        let topo = base.join("topology").join("sources");
+        fs::create_dir_all(&topo).unwrap();
+        let wpsrc = topo.join("wpsrc.toml");
+        fs::write(
+            &wpsrc,
+            r#"[[sources]]
+key = "${SRC_KEY:-syslog_main}"
+connect = "syslog_src"
+tags = ["zone:${ZONE:-cn}"]
+params = { port = "${SYSLOG_PORT:-1514}" }
+"#,
+        )
+        .unwrap();
+
+        let mut dict = EnvDict::new();
+        dict.insert("ZONE", orion_variate::ValueType::from("eu"));
+        let insts = load_source_instances_from_file(&wpsrc, &dict).expect("load sources");
+        assert_eq!(insts.len(), 1);
+        assert_eq!(insts[0].name(), &"syslog_main".to_string());
+        assert_eq!(insts[0].core.tags, vec!["zone:eu".to_string()]);
+        assert_eq!(insts[0].core.params.get("port"), Some(&json!("1514")));
+    }
+
+    #[test]
+    fn source_instances_use_project_manifest_defaults() {
+        let base = tmp_dir("src_manifest_default");
+        fs::create_dir_all(base.join("conf")).unwrap();
+        fs::write(
+            base.join("conf").join("wparse.toml"),
+            "[variables.SRC_PORT]\ndefault = \"1515\"\n",
+        )
+        .unwrap();
+        let cdir = base.join("connectors").join("source.d");
+        fs::create_dir_all(&cdir).unwrap();
+        fs::write(
+            cdir.join("syslog.toml"),
+            r#"[[connectors]]
+id = "syslog_src"
+type = "syslog"
+allow_override = ["port"]
+[connectors.params]
+port = 514
+"#,
+        )
+        .unwrap();
+        let topo = base.join("topology").join("sources");
+        fs::create_dir_all(&topo).unwrap();
+        let wpsrc = topo.join("wpsrc.toml");
+        fs::write(
+            &wpsrc,
+            r#"[[sources]]
+key = "syslog_main"
+connect = "syslog_src"
+params = { port = "${SRC_PORT}" }
+"#,
+        )
+        .unwrap();
+
+        let insts = load_source_instances_from_file(&wpsrc, &EnvDict::new()).expect("load sources");
+        assert_eq!(insts[0].core.params.get("port"), Some(&json!("1515")));
+    }
+
     use crate::connectors::ConnectorDef;
     use crate::connectors::ParamMap;
     use wp_connector_api::SourceFactory;
@@ -7,6 +7,8 @@ use orion_variate::EnvEvaluable;
 use serde::{Deserialize, Serialize};
 use wp_connector_api::{ConnectorDef, ParamMap};
 
+use crate::structure::{RateLimitPolicy, SourceRateLimitConf};
+
 pub type SrcConnectorFileRec = ConnectorTomlFile;
 pub type SourceConnector = ConnectorDef;
 
@@ -21,6 +23,18 @@ pub struct WpSource {
     pub tags: Vec<String>,
     #[serde(default, rename = "params", alias = "params_override")]
     pub params: ParamMap,
+    /// 实例级限速（事件/秒）；缺省不限速
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<usize>,
+    /// 令牌桶突发容量（事件数），需配合 `rate_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<usize>,
+    /// 超速处理策略：`wait`（默认）或 `drop`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_policy: Option<RateLimitPolicy>,
+    /// 解析前按序执行的预处理钩子（如 `strip/bom`、`syslog/header`）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preproc: Vec<String>,
 }
 
 impl EnvEvaluable<WpSource> for WpSource {
@@ -29,10 +43,32 @@ impl EnvEvaluable<WpSource> for WpSource {
         self.connect = self.connect.env_eval(dict);
         self.tags = env_eval_vec(self.tags, dict);
         self.params = env_eval_params(self.params, dict);
+        self.preproc = env_eval_vec(self.preproc, dict);
         self
     }
 }
 
+impl WpSource {
+    /// 汇总实例级限速配置；仅配置 `burst`/`rate_limit_policy` 而缺少 `rate_limit` 视为错误
+    pub fn rate_limit_conf(&self) -> Result<Option<SourceRateLimitConf>, String> {
+        match self.rate_limit {
+            Some(rate) => {
+                let conf = SourceRateLimitConf::new(
+                    rate,
+                    self.burst,
+                    self.rate_limit_policy.unwrap_or_default(),
+                );
+                conf.validate().map_err(|e| e.to_string())?;
+                Ok(Some(conf))
+            }
+            None if self.burst.is_some() || self.rate_limit_policy.is_some() => {
+                Err("burst/rate_limit_policy requires rate_limit".to_string())
+            }
+            None => Ok(None),
+        }
+    }
+}
+
 /// Deprecated alias: maintained for crates that still refer to `SourceItem`
 pub type SourceItem = WpSource;
 
@@ -69,6 +105,10 @@ mod tests {
             connect: "${CONNECTOR}".into(),
             tags: vec!["env-${TAG}".into()],
             params,
+            rate_limit: None,
+            burst: None,
+            rate_limit_policy: None,
+            preproc: Vec::new(),
         };
         let mut dict = EnvDict::new();
         dict.insert("SRC_KEY", ValueType::from("file_src"));
@@ -102,6 +142,10 @@ mod tests {
                 connect: "${CONNECT}".into(),
                 tags: vec![],
                 params,
+                rate_limit: None,
+                burst: None,
+                rate_limit_policy: None,
+                preproc: Vec::new(),
             }],
         };
         let mut dict = EnvDict::new();
@@ -118,4 +162,34 @@ mod tests {
             Some("/tmp/a.dat")
         );
     }
+
+    #[test]
+    fn rate_limit_fields_parse_and_validate() {
+        let cfg: WpSourcesConfig = toml::from_str(
+            r#"
+[[sources]]
+key = "tcp_in"
+connect = "tcp_src"
+rate_limit = 5000
+burst = 500
+rate_limit_policy = "drop"
+
+[[sources]]
+key = "file_in"
+connect = "file_src"
+"#,
+        )
+        .unwrap();
+        let limit = cfg.sources[0].rate_limit_conf().unwrap().unwrap();
+        assert_eq!(limit.rate, 5000);
+        assert_eq!(limit.burst, Some(500));
+        assert_eq!(limit.policy, RateLimitPolicy::Drop);
+        assert_eq!(cfg.sources[1].rate_limit_conf().unwrap(), None);
+
+        let mut bad = cfg.sources[1].clone();
+        bad.burst = Some(10);
+        assert!(bad.rate_limit_conf().is_err());
+        bad.rate_limit = Some(0);
+        assert!(bad.rate_limit_conf().is_err());
+    }
 }
@@ -10,6 +10,9 @@ pub struct StatConf {
     /// 统计窗口（单位秒）；None 表示使用默认（60）
     #[serde(default)]
     pub window_sec: Option<u64>,
+    /// 累计 source/sink 计数落盘周期（单位秒）；None 表示默认（30），0 表示关闭
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_sec: Option<u64>,
     /// 采集阶段（Pick）统计项
     #[serde(default)]
     pub pick: Vec<StatItem>,
@@ -39,6 +42,7 @@ impl Default for StatConf {
     fn default() -> Self {
         Self {
             window_sec: Default::default(),
+            checkpoint_sec: None,
             pick: [StatItem {
                 key: "pick_stat".into(),
                 target: "*".into(),
@@ -136,8 +140,9 @@ impl StatConf {
         // 提供默认的生成器统计配置
         Self {
             window_sec: Some(60),
-            pick: Vec::new(),  // 生成器没有 pick 阶段
-            parse: Vec::new(), // 生成器没有 parse 阶段
+            checkpoint_sec: Some(0), // 生成器不做累计落盘
+            pick: Vec::new(),        // 生成器没有 pick 阶段
+            parse: Vec::new(),       // 生成器没有 parse 阶段
             sink: vec![StatItem {
                 key: "gen_direct".to_string(),
                 target: "*".to_string(),
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// sink 组批量模式：固定条数或按下发耗时自适应
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// 达到 `batch_size` 或超时即 flush（默认）
+    #[default]
+    Fixed,
+    /// 以 `target_latency_ms` 为目标，在 `[min_batch, max_batch]` 内按 AIMD 调整批量
+    Adaptive,
+}
+
+/// 自适应批量参数（仅 `batch_mode = "adaptive"` 时生效）
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, derive_getters::Getters)]
+#[serde(deny_unknown_fields)]
+pub struct AdaptiveBatchConf {
+    /// 单批下发的目标耗时（毫秒）
+    #[serde(default = "default_target_latency_ms")]
+    pub target_latency_ms: u64,
+    /// 批量下限
+    #[serde(default = "default_min_batch")]
+    pub min_batch: usize,
+    /// 批量上限
+    #[serde(default = "default_max_batch")]
+    pub max_batch: usize,
+}
+
+fn default_target_latency_ms() -> u64 {
+    200
+}
+
+fn default_min_batch() -> usize {
+    16
+}
+
+fn default_max_batch() -> usize {
+    8192
+}
+
+impl Default for AdaptiveBatchConf {
+    fn default() -> Self {
+        Self {
+            target_latency_ms: default_target_latency_ms(),
+            min_batch: default_min_batch(),
+            max_batch: default_max_batch(),
+        }
+    }
+}
+
+impl AdaptiveBatchConf {
+    pub fn validate(&self) -> crate::types::AnyResult<()> {
+        use anyhow::bail;
+        if self.target_latency_ms == 0 {
+            bail!("target_latency_ms must be >= 1");
+        }
+        if self.min_batch == 0 {
+            bail!("min_batch must be >= 1");
+        }
+        if self.max_batch < self.min_batch {
+            bail!(
+                "max_batch ({}) must be >= min_batch ({})",
+                self.max_batch,
+                self.min_batch
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_and_validate() {
+        let conf: AdaptiveBatchConf = toml::from_str("target_latency_ms = 100").unwrap();
+        assert_eq!(conf.target_latency_ms, 100);
+        assert_eq!(conf.min_batch, 16);
+        assert_eq!(conf.max_batch, 8192);
+        assert!(conf.validate().is_ok());
+
+        let bad = AdaptiveBatchConf {
+            min_batch: 64,
+            max_batch: 32,
+            ..AdaptiveBatchConf::default()
+        };
+        assert!(bad.validate().is_err());
+        assert!(toml::from_str::<AdaptiveBatchConf>("target = 3").is_err());
+    }
+}
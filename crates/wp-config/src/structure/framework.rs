@@ -1,7 +1,9 @@
 use crate::common::paths::OUT_FILE_PATH;
 use crate::structure::SinkInstanceConf;
 use crate::structure::group::default_batch_timeout_ms;
-use crate::structure::{Basis, ExpectMode, FixedGroup, FlexGroup, GroupExpectSpec};
+use crate::structure::{
+    AdaptiveBatchConf, Basis, BatchMode, ExpectMode, FixedGroup, FlexGroup, GroupExpectSpec,
+};
 use wp_model_core::model::fmt_def::TextFmt;
 use wp_specs::WildArray;
 
@@ -91,6 +93,11 @@ impl FlexGroup {
             }),
             batch_timeout_ms: default_batch_timeout_ms(),
             batch_size: 1,
+            batch_mode: BatchMode::Fixed,
+            adaptive: AdaptiveBatchConf::default(),
+            workers: 0,
+            preserve_order: false,
+            schema: None,
             sinks: vec![SinkInstanceConf::file_new(
                 "monitor_sink".to_string(),
                 TextFmt::ProtoText,
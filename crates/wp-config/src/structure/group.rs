@@ -7,8 +7,10 @@ use orion_error::UvsValidationFrom;
 use orion_variate::EnvEvaluable;
 use wp_conf_base::ConfParser;
 use wp_connector_api::Tags;
+use wp_model_core::model::DataType;
 use wp_model_core::model::fmt_def::TextFmt;
 
+use crate::structure::{AdaptiveBatchConf, BatchMode, RouteSchemaConf, SchemaMismatch};
 use crate::types::AnyResult;
 use crate::{structure::SinkInstanceConf, utils::env_eval_vec};
 use anyhow::bail;
@@ -36,9 +38,24 @@ pub struct FlexGroup {
     /// 批量超时时间，单位：毫秒，默认 300ms
     #[serde(default = "default_batch_timeout_ms")]
     pub batch_timeout_ms: u64,
-    /// 批量缓冲大小，默认 1024 条记录
+    /// 批量缓冲大小，默认 1024 条记录；自适应模式下作为初始批量
     #[serde(default = "default_batch_size")]
     pub batch_size: usize,
+    /// 批量模式：`fixed`（默认）或 `adaptive`
+    #[serde(default)]
+    pub batch_mode: BatchMode,
+    /// 自适应批量参数
+    #[serde(default)]
+    pub adaptive: AdaptiveBatchConf,
+    /// 组内 OML 转换线程数，默认 0/1 表示在组任务内串行转换；最大 32
+    #[serde(default)]
+    pub workers: usize,
+    /// 多线程转换时按输入顺序重排后再下发
+    #[serde(default)]
+    pub preserve_order: bool,
+    /// 字段类型约束；`file` 已在装载时并入 `fields`
+    #[serde(default)]
+    pub schema: Option<RouteSchemaConf>,
     pub sinks: Vec<SinkInstanceConf>,
 }
 
@@ -201,6 +218,13 @@ impl SinkGroupConf {
             SinkGroupConf::Fixed(x) => x.batch_size,
         }
     }
+    /// 自适应批量参数；Fixed（基础组）始终使用固定批量
+    pub fn adaptive_batch(&self) -> Option<&AdaptiveBatchConf> {
+        match self {
+            SinkGroupConf::Flexi(x) if x.batch_mode == BatchMode::Adaptive => Some(&x.adaptive),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default, Getters)]
@@ -247,6 +271,23 @@ impl FlexGroup {
     pub fn set_parallel(&mut self, p: usize) {
         self.parallel = p;
     }
+
+    /// 路由配置了 schema 且模型按 `oml` 路由到本组时，对照模型显式声明的字段类型；
+    /// 引擎装载与 `wproj check` 共用此判定
+    pub fn schema_mismatches<'a>(
+        &self,
+        model: &str,
+        declared: impl IntoIterator<Item = (&'a str, &'a DataType)>,
+    ) -> Vec<SchemaMismatch> {
+        let Some(schema) = self.schema.as_ref() else {
+            return Vec::new();
+        };
+        if !self.oml.as_ref().iter().any(|m| m.matches(model)) {
+            return Vec::new();
+        }
+        schema.mismatches(declared)
+    }
+
     pub fn test_new(name: &str, rule: &str) -> Self {
         Self {
             name: name.to_string(),
@@ -258,6 +299,11 @@ impl FlexGroup {
             expect: None,
             batch_timeout_ms: default_batch_timeout_ms(),
             batch_size: default_batch_size(),
+            batch_mode: BatchMode::Fixed,
+            adaptive: AdaptiveBatchConf::default(),
+            workers: 0,
+            preserve_order: false,
+            schema: None,
             sinks: vec![SinkInstanceConf::null_new(
                 "test_sink".to_string(),
                 TextFmt::Raw,
@@ -277,6 +323,11 @@ impl FlexGroup {
             expect: None,
             batch_timeout_ms: default_batch_timeout_ms(),
             batch_size: default_batch_size(),
+            batch_mode: BatchMode::Fixed,
+            adaptive: AdaptiveBatchConf::default(),
+            workers: 0,
+            preserve_order: false,
+            schema: None,
             sinks,
         }
     }
@@ -288,6 +339,11 @@ impl FlexGroup {
             _ => 10,
         }
     }
+
+    /// OML 转换线程数：0 视为 1（串行），上限 32
+    pub fn worker_cnt(&self) -> usize {
+        self.workers.clamp(1, 32)
+    }
 }
 
 impl crate::structure::Validate for FlexGroup {
@@ -298,6 +354,9 @@ impl crate::structure::Validate for FlexGroup {
         if self.parallel > 10 {
             return ConfIOReason::from_validation("group.parallel must be <= 10").err_result();
         }
+        if self.workers > 32 {
+            return ConfIOReason::from_validation("group.workers must be <= 32").err_result();
+        }
         // tags 校验：统一使用 wp_model_core::tags::validate_tags
         if let Err(e) = Tags::validate(&self.tags) {
             return ConfIOReason::from_validation(e).err_result();
@@ -307,6 +366,11 @@ impl crate::structure::Validate for FlexGroup {
         {
             return ConfIOReason::from_validation(e.to_string()).err_result();
         }
+        if self.batch_mode == BatchMode::Adaptive
+            && let Err(e) = self.adaptive.validate()
+        {
+            return ConfIOReason::from_validation(format!("group.adaptive: {}", e)).err_result();
+        }
         if self.sinks.is_empty() {
             return ConfIOReason::from_validation("group.sinks must not be empty").err_result();
         }
@@ -368,6 +432,11 @@ impl FlexGroup {
             expect: None,
             batch_timeout_ms: default_batch_timeout_ms(),
             batch_size: default_batch_size(),
+            batch_mode: BatchMode::Fixed,
+            adaptive: AdaptiveBatchConf::default(),
+            workers: 0,
+            preserve_order: false,
+            schema: None,
             sinks: vec![],
         }
     }
@@ -390,6 +459,11 @@ impl FlexGroup {
             rule: rule_matches,
             batch_timeout_ms: default_batch_timeout_ms(),
             batch_size: default_batch_size(),
+            batch_mode: BatchMode::Fixed,
+            adaptive: AdaptiveBatchConf::default(),
+            workers: 0,
+            preserve_order: false,
+            schema: None,
             expect: None,
             sinks: vec![sink_conf],
         }
@@ -437,6 +511,11 @@ mod tests {
             expect: None,
             batch_timeout_ms: default_batch_timeout_ms(),
             batch_size: default_batch_size(),
+            batch_mode: BatchMode::Fixed,
+            adaptive: AdaptiveBatchConf::default(),
+            workers: 0,
+            preserve_order: false,
+            schema: None,
             sinks: vec![sink],
         };
 
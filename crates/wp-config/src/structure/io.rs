@@ -1,2 +1,2 @@
 pub use crate::buildin::FileSinkConf;
-pub use crate::buildin::{SyslogSinkConf, SyslogSourceConf};
+pub use crate::buildin::{SyslogFormat, SyslogFraming, SyslogSinkConf, SyslogSourceConf};
@@ -2,19 +2,24 @@
 // Keep it dependency-light and implementation-free.
 // V1 MDBConf removed; KnowDB v2 uses directory layout + SQL files
 
+mod batch;
 mod framework;
 mod group;
 mod io;
 mod sink;
 mod source;
 
+pub use batch::{AdaptiveBatchConf, BatchMode};
 pub use group::{
     Basis, ExpectMode, FixedGroup, FlexGroup, GroupExpectSpec, SinkGroupConf, default_batch_size,
     default_batch_timeout_ms, extend_matches,
 };
-pub use io::{FileSinkConf, SyslogSinkConf, SyslogSourceConf};
-pub use sink::{SinkExpectOverride, SinkInstanceConf, SinkRouteConf};
-pub use source::SourceInstanceConf;
+pub use io::{FileSinkConf, SyslogFormat, SyslogFraming, SyslogSinkConf, SyslogSourceConf};
+pub use sink::{
+    RouteSchemaConf, SchemaConflict, SchemaMismatch, SinkBreakerConf, SinkExpectOverride,
+    SinkFormatConf, SinkInstanceConf, SinkRouteConf,
+};
+pub use source::{RateLimitPolicy, SourceInstanceConf, SourceRateLimitConf};
 
 pub use bool_de::de_opt_bool_onoff;
 /// 稳定别名：对外重导出基础接口与工具。
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// sink 级熔断配置：连续失败达到阈值后熔断，冷却期内向上游施加背压
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, derive_getters::Getters)]
+#[serde(deny_unknown_fields)]
+pub struct SinkBreakerConf {
+    /// 连续失败的批次数阈值
+    #[serde(default = "default_error_threshold")]
+    pub error_threshold: u32,
+    /// 熔断冷却时间（秒），到期后进入半开探测
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+    /// 半开探测批次的最大记录数
+    #[serde(default = "default_probe_size")]
+    pub probe_size: usize,
+}
+
+fn default_error_threshold() -> u32 {
+    5
+}
+
+fn default_cooldown_secs() -> u64 {
+    30
+}
+
+fn default_probe_size() -> usize {
+    16
+}
+
+impl Default for SinkBreakerConf {
+    fn default() -> Self {
+        Self {
+            error_threshold: default_error_threshold(),
+            cooldown_secs: default_cooldown_secs(),
+            probe_size: default_probe_size(),
+        }
+    }
+}
+
+impl SinkBreakerConf {
+    pub fn validate(&self) -> crate::types::AnyResult<()> {
+        use anyhow::bail;
+        if self.error_threshold == 0 {
+            bail!("error_threshold must be >= 1");
+        }
+        if self.cooldown_secs == 0 {
+            bail!("cooldown_secs must be >= 1");
+        }
+        if self.probe_size == 0 {
+            bail!("probe_size must be >= 1");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_and_validate() {
+        let conf: SinkBreakerConf = toml::from_str("error_threshold = 3").unwrap();
+        assert_eq!(conf.error_threshold, 3);
+        assert_eq!(conf.cooldown_secs, 30);
+        assert_eq!(conf.probe_size, 16);
+        assert!(conf.validate().is_ok());
+
+        let bad = SinkBreakerConf {
+            cooldown_secs: 0,
+            ..SinkBreakerConf::default()
+        };
+        assert!(bad.validate().is_err());
+        assert!(toml::from_str::<SinkBreakerConf>("threshold = 3").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// sink 级输出形态：格式选择、字段顺序、重命名与空值省略；
+/// 在 sink 写出路径上生效，同一路由可向多个 sink 输出不同形态
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default, derive_getters::Getters)]
+#[serde(deny_unknown_fields)]
+pub struct SinkFormatConf {
+    /// 输出格式（json/kv/csv/raw…），覆盖 connector 的 `fmt`
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// 字段输出顺序（按原始字段名）；为空时保持记录原有顺序
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// 未列入 `fields` 的字段是否按原顺序追加在末尾；false 时丢弃
+    #[serde(default = "default_true")]
+    pub append_rest: bool,
+    /// 字段重命名：原名 -> 输出名
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+    /// 省略空字符串与 null 字段
+    #[serde(default)]
+    pub omit_empty: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl SinkFormatConf {
+    pub fn validate(&self) -> crate::types::AnyResult<()> {
+        use anyhow::bail;
+        let mut seen = BTreeSet::new();
+        for f in &self.fields {
+            if f.is_empty() {
+                bail!("fields contains empty name");
+            }
+            if !seen.insert(f.as_str()) {
+                bail!("duplicate field '{}' in fields", f);
+            }
+        }
+        let mut targets = BTreeSet::new();
+        for (from, to) in &self.rename {
+            if to.is_empty() {
+                bail!("rename target of '{}' is empty", from);
+            }
+            if !targets.insert(to.as_str()) {
+                bail!("duplicate rename target '{}'", to);
+            }
+        }
+        if self.fields.is_empty() && !self.append_rest {
+            bail!("append_rest = false requires a non-empty fields list");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_and_validate() {
+        let conf: SinkFormatConf = toml::from_str(
+            r#"
+            kind = "json"
+            fields = ["ts", "msg"]
+            rename = { msg = "message" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(conf.kind.as_deref(), Some("json"));
+        assert!(conf.append_rest);
+        assert!(!conf.omit_empty);
+        assert_eq!(conf.rename.get("msg").map(String::as_str), Some("message"));
+        assert!(conf.validate().is_ok());
+
+        let dup = SinkFormatConf {
+            fields: vec!["a".into(), "a".into()],
+            ..SinkFormatConf::default()
+        };
+        assert!(dup.validate().is_err());
+        let drop_all = SinkFormatConf {
+            append_rest: false,
+            ..SinkFormatConf::default()
+        };
+        assert!(drop_all.validate().is_err());
+        assert!(toml::from_str::<SinkFormatConf>("order = []").is_err());
+    }
+}
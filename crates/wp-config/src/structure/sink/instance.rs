@@ -1,4 +1,7 @@
+use super::breaker::SinkBreakerConf;
 use super::expect::SinkExpectOverride;
+use super::format::SinkFormatConf;
+use super::schema::RouteSchemaConf;
 use crate::types::AnyResult;
 use crate::utils::{env_eval_params, env_eval_vec};
 use crate::{cond::WarpConditionParser, structure::Validate};
@@ -24,6 +27,15 @@ pub struct SinkInstanceConf {
     pub fmt: TextFmt,
     #[serde(default)]
     pub expect: Option<SinkExpectOverride>,
+    /// 熔断配置；未配置时不启用熔断
+    #[serde(default)]
+    pub breaker: Option<SinkBreakerConf>,
+    /// 输出形态（字段顺序/重命名/空值省略）；未配置时按记录原样输出
+    #[serde(default)]
+    pub format: Option<SinkFormatConf>,
+    /// 所属路由的字段类型约束（路由装配阶段注入；不参与序列化）
+    #[serde(skip, default)]
+    pub schema: Option<RouteSchemaConf>,
     /// 当 cond 结果等于该值时投递；默认为 true
     #[serde(default = "default_true")]
     filter_expect: bool,
@@ -117,6 +129,9 @@ impl SinkInstanceConf {
             },
             fmt,
             expect: None,
+            breaker: None,
+            format: None,
+            schema: None,
             connector_id: None,
             group_name: None,
             filter_expect: true,
@@ -254,6 +269,15 @@ impl Validate for SinkInstanceConf {
         if let Some(exp) = &self.expect {
             exp.validate().owe_conf().want("sink.expect validate")?;
         }
+        if let Some(breaker) = &self.breaker {
+            breaker
+                .validate()
+                .owe_conf()
+                .want("sink.breaker validate")?;
+        }
+        if let Some(format) = &self.format {
+            format.validate().owe_conf().want("sink.format validate")?;
+        }
         Tags::validate(&self.core.tags)
             .owe_conf()
             .want("tags validate")?;
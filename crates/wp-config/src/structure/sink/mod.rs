@@ -1,8 +1,14 @@
+pub mod breaker;
 pub mod expect;
+pub mod format;
 pub mod instance;
 pub mod route;
+pub mod schema;
 // tags 校验改为统一使用 wp_model_core::tags::validate_tags；移除本地 utils
 
+pub use breaker::SinkBreakerConf;
 pub use expect::SinkExpectOverride;
+pub use format::SinkFormatConf;
 pub use instance::SinkInstanceConf;
 pub use route::SinkRouteConf;
+pub use schema::{RouteSchemaConf, SchemaConflict, SchemaMismatch};
@@ -0,0 +1,176 @@
+use anyhow::{Context, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use wp_model_core::model::DataType;
+
+use crate::types::AnyResult;
+
+/// schema 中可声明的字段类型
+pub const SCHEMA_TYPES: [&str; 5] = ["chars", "digit", "float", "bool", "ip"];
+
+/// 记录字段类型与 schema 不一致时的处理方式
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaConflict {
+    /// 仅告警，记录原样下发
+    #[default]
+    Warn,
+    /// 可安全转换的按 schema 转换；无法转换的字段从记录中移除
+    Coerce,
+    /// 可安全转换的按 schema 转换；存在无法转换的字段时整条记录丢弃
+    Drop,
+}
+
+/// 路由级字段类型约束（`[sink_group.schema]`）：同一路由下所有模型输出的同名字段类型一致
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default, derive_getters::Getters)]
+#[serde(deny_unknown_fields)]
+pub struct RouteSchemaConf {
+    /// 字段类型文件（TOML：`字段名 = "类型"`）；相对路径按路由文件所在目录解析
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub on_conflict: SchemaConflict,
+    /// 字段名 -> 类型；装载时并入 `file` 的内容，同名时内联项优先
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// 模型声明的字段类型与 schema 不一致
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    pub field: String,
+    pub expected: String,
+    pub declared: String,
+}
+
+impl RouteSchemaConf {
+    /// 读取 `file` 并入 `fields`；`base` 为路由文件所在目录
+    pub fn load_file(&mut self, base: Option<&Path>) -> AnyResult<()> {
+        let Some(file) = self.file.as_deref() else {
+            return Ok(());
+        };
+        let path = match base {
+            Some(dir) if Path::new(file).is_relative() => dir.join(file),
+            _ => PathBuf::from(file),
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("read schema file '{}'", path.display()))?;
+        let loaded: BTreeMap<String, String> = toml::from_str(&content)
+            .with_context(|| format!("parse schema file '{}'", path.display()))?;
+        for (name, ty) in loaded {
+            self.fields.entry(name).or_insert(ty);
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> AnyResult<()> {
+        if self.fields.is_empty() {
+            bail!("schema declares no fields");
+        }
+        for (name, ty) in &self.fields {
+            if name.trim().is_empty() {
+                bail!("schema contains empty field name");
+            }
+            parse_type(ty).with_context(|| format!("schema field '{}'", name))?;
+        }
+        Ok(())
+    }
+
+    /// 字段名 -> 类型
+    pub fn field_types(&self) -> AnyResult<BTreeMap<String, DataType>> {
+        self.fields
+            .iter()
+            .map(|(name, ty)| Ok((name.clone(), parse_type(ty)?)))
+            .collect()
+    }
+
+    /// 对照模型显式声明的字段类型；`auto` 与 schema 未列出的字段不参与比较
+    pub fn mismatches<'a>(
+        &self,
+        declared: impl IntoIterator<Item = (&'a str, &'a DataType)>,
+    ) -> Vec<SchemaMismatch> {
+        let Ok(types) = self.field_types() else {
+            return Vec::new();
+        };
+        declared
+            .into_iter()
+            .filter(|(_, ty)| **ty != DataType::Auto)
+            .filter_map(|(name, ty)| {
+                let expected = types.get(name)?;
+                (expected != ty).then(|| SchemaMismatch {
+                    field: name.to_string(),
+                    expected: expected.to_string(),
+                    declared: ty.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_type(name: &str) -> AnyResult<DataType> {
+    let lower = name.trim().to_ascii_lowercase();
+    if !SCHEMA_TYPES.contains(&lower.as_str()) {
+        bail!(
+            "unsupported type '{}', expected one of {}",
+            name,
+            SCHEMA_TYPES.join("/")
+        );
+    }
+    DataType::from(lower.as_str()).map_err(|_| anyhow!("unsupported type '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_file_and_compare_declared_types() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("es.toml"),
+            "port = \"digit\"\nsrc_ip = \"ip\"\nmsg = \"chars\"\n",
+        )
+        .unwrap();
+        let mut conf: RouteSchemaConf = toml::from_str(
+            r#"
+file = "es.toml"
+on_conflict = "coerce"
+fields = { msg = "float" }
+"#,
+        )
+        .unwrap();
+        conf.load_file(Some(dir.path())).unwrap();
+        conf.validate().unwrap();
+        assert_eq!(conf.on_conflict, SchemaConflict::Coerce);
+        // 内联项优先
+        assert_eq!(conf.fields["msg"], "float");
+        assert_eq!(conf.fields["port"], "digit");
+
+        let declared = [
+            ("port", DataType::Chars),
+            ("src_ip", DataType::IP),
+            ("msg", DataType::Auto),
+            ("other", DataType::Digit),
+        ];
+        let found = conf.mismatches(declared.iter().map(|(n, t)| (*n, t)));
+        assert_eq!(
+            found,
+            vec![SchemaMismatch {
+                field: "port".into(),
+                expected: DataType::Digit.to_string(),
+                declared: DataType::Chars.to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unsupported_type_is_rejected() {
+        let conf = RouteSchemaConf {
+            fields: [("ts".to_string(), "time".to_string())].into(),
+            ..RouteSchemaConf::default()
+        };
+        assert!(conf.validate().is_err());
+        assert!(RouteSchemaConf::default().validate().is_err());
+    }
+}
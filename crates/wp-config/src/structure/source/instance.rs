@@ -1,23 +1,44 @@
 use crate::connectors::{ParamMap, param_map_from_table_ref};
 use crate::structure::Validate;
+use crate::utils::{env_eval_params, env_eval_vec};
 use orion_conf::{
     ToStructError,
     error::{ConfIOReason, OrionConfResult},
 };
 use orion_error::UvsValidationFrom;
+use orion_variate::EnvEvaluable;
 use serde::{Deserialize, Serialize};
 use wp_conf_base::ConfParser;
 use wp_connector_api::Tags;
 
+use super::SourceRateLimitConf;
+
 /// Source 实例级配置（最小实现）：
 /// - 扁平合入 CoreSourceSpec（name/type/params/tags）作为“单一事实来源”
 /// - 预留 connector_id（运行期展示/诊断用）
+/// - rate_limit 来自 `[[sources]]` 的 `rate_limit`/`burst`/`rate_limit_policy`
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, derive_getters::Getters)]
 pub struct SourceInstanceConf {
     #[serde(flatten)]
     pub core: wp_specs::CoreSourceSpec,
     #[serde(skip, default)]
     pub connector_id: Option<String>,
+    #[serde(skip, default)]
+    pub rate_limit: Option<SourceRateLimitConf>,
+    /// 预处理钩子名（按序执行）；名称是否已注册由引擎侧校验
+    #[serde(skip, default)]
+    pub preproc: Vec<String>,
+}
+
+impl EnvEvaluable<SourceInstanceConf> for SourceInstanceConf {
+    fn env_eval(mut self, dict: &orion_variate::EnvDict) -> SourceInstanceConf {
+        self.core.name = self.core.name.env_eval(dict);
+        self.core.kind = self.core.kind.env_eval(dict);
+        self.core.params = env_eval_params(self.core.params, dict);
+        self.core.tags = env_eval_vec(self.core.tags, dict);
+        self.connector_id = self.connector_id.env_eval(dict);
+        self
+    }
 }
 
 impl SourceInstanceConf {
@@ -62,6 +83,8 @@ impl SourceInstanceConf {
                 tags,
             },
             connector_id: None,
+            rate_limit: None,
+            preproc: Vec::new(),
         }
     }
 }
@@ -77,6 +100,19 @@ impl Validate for SourceInstanceConf {
         if self.core.name.trim().is_empty() {
             return ConfIOReason::from_validation("source.name must not be empty").err_result();
         }
+        if let Some(limit) = &self.rate_limit
+            && let Err(e) = limit.validate()
+        {
+            return ConfIOReason::from_validation(format!("source '{}': {}", self.core.name, e))
+                .err_result();
+        }
+        if self.preproc.iter().any(|h| h.trim().is_empty()) {
+            return ConfIOReason::from_validation(format!(
+                "source '{}': preproc hook name must not be empty",
+                self.core.name
+            ))
+            .err_result();
+        }
         if let Err(e) = Tags::validate(&self.core.tags) {
             return ConfIOReason::from_validation(e).err_result();
         }
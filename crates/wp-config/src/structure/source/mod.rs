@@ -1,3 +1,5 @@
 pub mod instance;
+pub mod rate_limit;
 
 pub use instance::SourceInstanceConf;
+pub use rate_limit::{RateLimitPolicy, SourceRateLimitConf};
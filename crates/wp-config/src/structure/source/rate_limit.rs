@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// 超出速率时的处理策略
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitPolicy {
+    /// 暂停拉取直到令牌补齐（背压传导到上游）
+    #[default]
+    Wait,
+    /// 直接丢弃超出部分并计入统计
+    Drop,
+}
+
+impl std::fmt::Display for RateLimitPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateLimitPolicy::Wait => write!(f, "wait"),
+            RateLimitPolicy::Drop => write!(f, "drop"),
+        }
+    }
+}
+
+/// source 实例级限速：令牌桶，`rate` 为每秒事件数，`burst` 为桶容量
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, derive_getters::Getters)]
+pub struct SourceRateLimitConf {
+    pub rate: usize,
+    /// 突发容量（事件数）；缺省为 0.2 秒的量（至少 10）
+    #[serde(default)]
+    pub burst: Option<usize>,
+    #[serde(default)]
+    pub policy: RateLimitPolicy,
+}
+
+impl SourceRateLimitConf {
+    pub fn new(rate: usize, burst: Option<usize>, policy: RateLimitPolicy) -> Self {
+        Self {
+            rate,
+            burst,
+            policy,
+        }
+    }
+
+    pub fn validate(&self) -> crate::types::AnyResult<()> {
+        use anyhow::bail;
+        if self.rate == 0 {
+            bail!("rate_limit must be >= 1");
+        }
+        if self.burst == Some(0) {
+            bail!("burst must be >= 1");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_and_validate() {
+        let conf: SourceRateLimitConf = toml::from_str("rate = 500\npolicy = \"drop\"").unwrap();
+        assert_eq!(conf.policy, RateLimitPolicy::Drop);
+        assert_eq!(conf.burst, None);
+        assert!(conf.validate().is_ok());
+
+        assert!(
+            SourceRateLimitConf::new(0, None, RateLimitPolicy::Wait)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            SourceRateLimitConf::new(10, Some(0), RateLimitPolicy::Wait)
+                .validate()
+                .is_err()
+        );
+        assert!(toml::from_str::<SourceRateLimitConf>("rate = 1\npolicy = \"queue\"").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use wp_data_utils::intern::NameInterner;
+use wp_model_core::model::FNameStr;
+
+const KEYS: [&str; 5] = [
+    "src_ip",
+    "dst_ip",
+    "timestamp",
+    "action",
+    "a_rather_long_field_name_for_heap",
+];
+
+// ── 驻留命中 vs 直接构造 ─────────────────────────────────────────────
+
+fn bench_hit(c: &mut Criterion) {
+    let interner = NameInterner::with_capacity(1024);
+    c.bench_function("intern/hit", |b| {
+        b.iter(|| {
+            for k in KEYS {
+                black_box(interner.intern(black_box(k)));
+            }
+        })
+    });
+    c.bench_function("intern/direct", |b| {
+        b.iter(|| {
+            for k in KEYS {
+                black_box(FNameStr::from(black_box(k)));
+            }
+        })
+    });
+}
+
+// ── 表满后的高基数键：只走读锁 ───────────────────────────────────────
+
+fn bench_full_table(c: &mut Criterion) {
+    let interner = NameInterner::with_capacity(16);
+    for i in 0..64 {
+        interner.intern(&format!("warmup_{i}"));
+    }
+    let names: Vec<String> = (0..256).map(|i| format!("uuid_key_{i:08x}")).collect();
+    c.bench_function("intern/full_table_miss", |b| {
+        b.iter(|| {
+            for n in &names {
+                black_box(interner.intern(black_box(n)));
+            }
+        })
+    });
+}
+
+// ── 多线程并发驻留 ───────────────────────────────────────────────────
+
+fn bench_contended(c: &mut Criterion) {
+    let interner = NameInterner::with_capacity(1024);
+    let names: Vec<String> = (0..512).map(|i| format!("field_{i}")).collect();
+    c.bench_function("intern/contended_4_threads", |b| {
+        b.iter(|| {
+            std::thread::scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(|| {
+                        for n in &names {
+                            black_box(interner.intern(n));
+                        }
+                    });
+                }
+            });
+        })
+    });
+}
+
+criterion_group!(benches, bench_hit, bench_full_table, bench_contended);
+criterion_main!(benches);
@@ -1,4 +1,10 @@
-use std::{collections::HashMap, net::IpAddr, num::NonZeroUsize};
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    net::IpAddr,
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
 
 use derive_builder::Builder;
 
@@ -9,18 +15,65 @@ pub struct FieldQueryCache {
     str_idx: HashMap<FValueStr, usize>,
     i64_idx: HashMap<i64, usize>,
     ip_idx: HashMap<IpAddr, usize>,
-    cache_data: lru::LruCache<EnumSizeIndex, Vec<DataField>>,
+    cache_data: lru::LruCache<EnumSizeIndex, Timed<Vec<DataField>>>,
     idx_num: usize,
+    /// 缓存内容对应的数据源代数；数据源重载后由查询方通过 `sync_generation` 整体失效
+    #[builder(default)]
+    generation: u64,
+    /// 条目有效期；None 表示仅受 LRU 容量约束
+    #[builder(default)]
+    ttl: Option<Duration>,
+    /// `fetch` 只持有共享引用，命中统计放在 Cell 中
+    #[builder(default)]
+    stats: Cell<CacheStats>,
 }
+
+/// 带写入时刻的缓存值；字段查询缓存与知识库查询结果缓存共用同一过期判断
+#[derive(Debug, Clone)]
+pub struct Timed<T> {
+    at: Instant,
+    value: T,
+}
+
+impl<T> Timed<T> {
+    pub fn new(value: T, at: Instant) -> Self {
+        Self { at, value }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// 写入后未超过 `ttl`；`ttl` 为 None 时永不过期
+    pub fn is_fresh(&self, ttl: Option<Duration>, now: Instant) -> bool {
+        ttl.is_none_or(|ttl| now.duration_since(self.at) < ttl)
+    }
+}
+
+/// 查询缓存的命中统计；未命中即一次实际查询
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// 自 `earlier` 以来的增量
+    pub fn since(&self, earlier: CacheStats) -> CacheStats {
+        CacheStats {
+            hits: self.hits.saturating_sub(earlier.hits),
+            misses: self.misses.saturating_sub(earlier.misses),
+        }
+    }
+}
+
 impl Default for FieldQueryCache {
     fn default() -> Self {
-        Self {
-            str_idx: HashMap::new(),
-            i64_idx: HashMap::new(),
-            ip_idx: HashMap::new(),
-            idx_num: 0,
-            cache_data: lru::LruCache::new(NonZeroUsize::new(100).unwrap()),
-        }
+        Self::with_capacity(100)
     }
 }
 impl FieldQueryCache {
@@ -32,6 +85,77 @@ impl FieldQueryCache {
             ip_idx: HashMap::new(),
             idx_num: 0,
             cache_data: lru::LruCache::new(NonZeroUsize::new(size).unwrap()),
+            generation: 0,
+            ttl: None,
+            stats: Cell::new(CacheStats::default()),
+        }
+    }
+
+    /// 带有效期的缓存：超过 `ttl` 的条目视为未命中，由下一次查询结果覆盖
+    pub fn with_ttl(size: usize, ttl: Duration) -> Self {
+        let mut cache = Self::with_capacity(size);
+        cache.ttl = Some(ttl);
+        cache
+    }
+
+    /// 容量与有效期相同的空缓存
+    pub fn empty_like(&self) -> Self {
+        let mut cache = Self::with_capacity(self.capacity());
+        cache.ttl = self.ttl;
+        cache
+    }
+
+    /// 缓存可容纳的查询结果条数（LRU 容量）
+    pub fn capacity(&self) -> usize {
+        self.cache_data.cap().get()
+    }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// 累计命中统计（代数切换不清零）
+    pub fn stats(&self) -> CacheStats {
+        self.stats.get()
+    }
+
+    fn lookup(&self, idxs: &EnumSizeIndex) -> Option<&Vec<DataField>> {
+        let mut stats = self.stats.get();
+        let found = self
+            .cache_data
+            .peek(idxs)
+            .filter(|entry| entry.is_fresh(self.ttl, Instant::now()))
+            .map(Timed::value);
+        match found {
+            Some(_) => stats.hits += 1,
+            None => stats.misses += 1,
+        }
+        self.stats.set(stats);
+        found
+    }
+
+    /// 参数值从未入缓存：直接计为未命中
+    fn miss(&self) -> Option<&Vec<DataField>> {
+        let mut stats = self.stats.get();
+        stats.misses += 1;
+        self.stats.set(stats);
+        None
+    }
+
+    fn store(&mut self, idxs: EnumSizeIndex, result: Vec<DataField>) {
+        self.cache_data
+            .put(idxs, Timed::new(result, Instant::now()));
+    }
+
+    /// 数据源代数变化时清空全部条目（容量不变）
+    pub fn sync_generation(&mut self, generation: u64) {
+        if self.generation != generation {
+            self.str_idx.clear();
+            self.i64_idx.clear();
+            self.ip_idx.clear();
+            self.cache_data.clear();
+            self.idx_num = 0;
+            self.generation = generation;
         }
     }
 }
@@ -53,15 +177,15 @@ impl CacheAble<DataField, Vec<DataField>, 1> for FieldQueryCache {
     fn save(&mut self, params: &[DataField; 1], result: Vec<DataField>) {
         if let Some(i0) = self.try_up_idx(&params[0]) {
             let idxs = EnumSizeIndex::Idx1(i0);
-            self.cache_data.put(idxs, result);
+            self.store(idxs, result);
         }
     }
     fn fetch(&self, params: &[DataField; 1]) -> Option<&Vec<DataField>> {
         if let Some(idx1) = self.get_idx(&params[0]) {
             let idxs = EnumSizeIndex::Idx1(idx1);
-            return self.cache_data.peek(&idxs);
+            return self.lookup(&idxs);
         }
-        None
+        self.miss()
     }
 }
 
@@ -69,15 +193,15 @@ impl CacheAble<DataField, Vec<DataField>, 2> for FieldQueryCache {
     fn save(&mut self, params: &[DataField; 2], result: Vec<DataField>) {
         if let (Some(i0), Some(i1)) = (self.try_up_idx(&params[0]), self.try_up_idx(&params[1])) {
             let idxs = EnumSizeIndex::Idx2(i0, i1);
-            self.cache_data.put(idxs, result);
+            self.store(idxs, result);
         }
     }
     fn fetch(&self, params: &[DataField; 2]) -> Option<&Vec<DataField>> {
         if let (Some(idx1), Some(idx2)) = (self.get_idx(&params[0]), self.get_idx(&params[1])) {
             let idxs = EnumSizeIndex::Idx2(idx1, idx2);
-            return self.cache_data.peek(&idxs);
+            return self.lookup(&idxs);
         }
-        None
+        self.miss()
     }
 }
 
@@ -89,7 +213,7 @@ impl CacheAble<DataField, Vec<DataField>, 3> for FieldQueryCache {
             self.try_up_idx(&params[2]),
         ) {
             let idxs = EnumSizeIndex::Idx3(i0, i1, i2);
-            self.cache_data.put(idxs, result);
+            self.store(idxs, result);
         }
     }
     fn fetch(&self, params: &[DataField; 3]) -> Option<&Vec<DataField>> {
@@ -99,9 +223,9 @@ impl CacheAble<DataField, Vec<DataField>, 3> for FieldQueryCache {
             self.get_idx(&params[2]),
         ) {
             let idxs = EnumSizeIndex::Idx3(idx1, idx2, idx3);
-            return self.cache_data.peek(&idxs);
+            return self.lookup(&idxs);
         }
-        None
+        self.miss()
     }
 }
 
@@ -114,7 +238,7 @@ impl CacheAble<DataField, Vec<DataField>, 4> for FieldQueryCache {
             self.try_up_idx(&params[3]),
         ) {
             let idxs = EnumSizeIndex::Idx4(i0, i1, i2, i3);
-            self.cache_data.put(idxs, result);
+            self.store(idxs, result);
         }
     }
     fn fetch(&self, params: &[DataField; 4]) -> Option<&Vec<DataField>> {
@@ -125,9 +249,9 @@ impl CacheAble<DataField, Vec<DataField>, 4> for FieldQueryCache {
             self.get_idx(&params[3]),
         ) {
             let idxs = EnumSizeIndex::Idx4(idx1, idx2, idx3, idx4);
-            return self.cache_data.peek(&idxs);
+            return self.lookup(&idxs);
         }
-        None
+        self.miss()
     }
 }
 
@@ -141,7 +265,7 @@ impl CacheAble<DataField, Vec<DataField>, 5> for FieldQueryCache {
             self.try_up_idx(&params[4]),
         ) {
             let idxs = EnumSizeIndex::Idx5(i0, i1, i2, i3, i4);
-            self.cache_data.put(idxs, result);
+            self.store(idxs, result);
         }
     }
     fn fetch(&self, params: &[DataField; 5]) -> Option<&Vec<DataField>> {
@@ -153,9 +277,9 @@ impl CacheAble<DataField, Vec<DataField>, 5> for FieldQueryCache {
             self.get_idx(&params[4]),
         ) {
             let idxs = EnumSizeIndex::Idx5(idx1, idx2, idx3, idx4, idx5);
-            return self.cache_data.peek(&idxs);
+            return self.lookup(&idxs);
         }
-        None
+        self.miss()
     }
 }
 
@@ -170,7 +294,7 @@ impl CacheAble<DataField, Vec<DataField>, 6> for FieldQueryCache {
             self.try_up_idx(&params[5]),
         ) {
             let idxs = EnumSizeIndex::Idx6(i0, i1, i2, i3, i4, i5);
-            self.cache_data.put(idxs, result);
+            self.store(idxs, result);
         }
     }
     fn fetch(&self, params: &[DataField; 6]) -> Option<&Vec<DataField>> {
@@ -183,9 +307,9 @@ impl CacheAble<DataField, Vec<DataField>, 6> for FieldQueryCache {
             self.get_idx(&params[5]),
         ) {
             let idxs = EnumSizeIndex::Idx6(idx1, idx2, idx3, idx4, idx5, idx6);
-            return self.cache_data.peek(&idxs);
+            return self.lookup(&idxs);
         }
-        None
+        self.miss()
     }
 }
 
@@ -238,10 +362,13 @@ impl FieldQueryCache {
 mod test {
 
     use std::collections::HashMap;
+    use std::time::Duration;
 
     use wp_model_core::model::DataField;
 
-    use crate::cache::{CacheAble, EnumSizeIndex, FieldQueryCache};
+    use std::time::Instant;
+
+    use crate::cache::{CacheAble, CacheStats, EnumSizeIndex, FieldQueryCache, Timed};
 
     #[test]
     fn test_idx() {
@@ -267,6 +394,7 @@ mod test {
         ];
 
         let mut cache = FieldQueryCache::with_capacity(3);
+        assert_eq!(cache.capacity(), 3);
         let cache_ret = cache.fetch(&data);
         assert!(cache_ret.is_none());
         cache.save(&data, out.clone());
@@ -288,5 +416,40 @@ mod test {
         cache.save(&data2, out.clone());
         let cache_ret = cache.fetch(&data2);
         assert_eq!(cache_ret, Some(&out));
+
+        cache.sync_generation(0);
+        assert_eq!(cache.fetch(&data2), Some(&out));
+        cache.sync_generation(1);
+        assert!(cache.fetch(&data2).is_none());
+        assert_eq!(cache.capacity(), 3);
+    }
+
+    #[test]
+    fn test_ttl_and_stats() {
+        let key = [DataField::from_digit("id", 1)];
+        let out = vec![DataField::from_chars("name", "a")];
+        let mut cache = FieldQueryCache::with_ttl(4, Duration::from_millis(20));
+        assert!(cache.fetch(&key).is_none());
+        cache.save(&key, out.clone());
+        assert_eq!(cache.fetch(&key), Some(&out));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.fetch(&key).is_none());
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 2 });
+
+        let fresh = cache.empty_like();
+        assert_eq!(fresh.capacity(), 4);
+        assert_eq!(fresh.ttl(), Some(Duration::from_millis(20)));
+        assert_eq!(fresh.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn timed_entry_expiry() {
+        let at = Instant::now();
+        let entry = Timed::new(1, at);
+        let later = at + Duration::from_millis(50);
+        assert!(entry.is_fresh(None, later));
+        assert!(entry.is_fresh(Some(Duration::from_millis(60)), later));
+        assert!(!entry.is_fresh(Some(Duration::from_millis(50)), later));
+        assert_eq!(entry.value(), &1);
     }
 }
@@ -0,0 +1,163 @@
+//! 字段名驻留：重复出现的字段名（`src_ip`、`timestamp` …）共享同一个 `FNameStr` 实例。
+//!
+//! - 解析器产出的键先经 [`intern_name`] 取共享实例，再传给 `DataField` 构造函数；
+//!   名称的相等性与 Display 行为不变，只减少逐记录的名称分配。
+//! - 表容量有上限（[`MAX_INTERNED`]），超出后退化为直接构造，避免高基数键（如 UUID 作键）撑大常驻内存；
+//!   表满后只在读锁下判断，不再争用写锁。
+//! - 表按名称哈希分片，各分片独立加锁，多个解析线程同时驻留新名称时互不阻塞。
+//! - 全局实例供协议解析器使用；需要按路由隔离时可自行持有 [`NameInterner`]。
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{LazyLock, RwLock, RwLockReadGuard};
+
+use wp_model_core::model::FNameStr;
+
+/// 全局驻留表的默认容量
+pub const MAX_INTERNED: usize = 8192;
+
+/// 分片数上限；容量小于该值时每个分片只容纳一个名称
+const MAX_SHARDS: usize = 16;
+
+static GLOBAL: LazyLock<NameInterner> = LazyLock::new(|| NameInterner::with_capacity(MAX_INTERNED));
+
+/// 从全局驻留表取字段名
+pub fn intern_name(name: &str) -> FNameStr {
+    GLOBAL.intern(name)
+}
+
+/// 全局驻留表
+pub fn global() -> &'static NameInterner {
+    &GLOBAL
+}
+
+type Shard = RwLock<HashMap<Box<str>, FNameStr>>;
+
+/// 有容量上限的字段名驻留表
+pub struct NameInterner {
+    shards: Box<[Shard]>,
+    // 每个分片的容量，各分片之和不超过总容量
+    shard_capacity: usize,
+    hasher: RandomState,
+}
+
+impl NameInterner {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let shard_count = capacity.clamp(1, MAX_SHARDS);
+        Self {
+            shards: (0..shard_count).map(|_| Shard::default()).collect(),
+            shard_capacity: capacity / shard_count,
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub fn intern(&self, name: &str) -> FNameStr {
+        let shard = self.shard(name);
+        {
+            let names = read_shard(shard);
+            if let Some(hit) = names.get(name) {
+                return hit.clone();
+            }
+            if names.len() >= self.shard_capacity {
+                return FNameStr::from(name);
+            }
+        }
+        let mut names = shard.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(hit) = names.get(name) {
+            return hit.clone();
+        }
+        let fresh = FNameStr::from(name);
+        if names.len() < self.shard_capacity {
+            names.insert(name.into(), fresh.clone());
+        }
+        fresh
+    }
+
+    /// 已驻留的名称数
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| read_shard(s).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn shard(&self, name: &str) -> &Shard {
+        let idx = self.hasher.hash_one(name) as usize % self.shards.len();
+        &self.shards[idx]
+    }
+}
+
+fn read_shard(shard: &Shard) -> RwLockReadGuard<'_, HashMap<Box<str>, FNameStr>> {
+    shard.read().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_model_core::model::DataField;
+
+    const KEYS: [&str; 5] = [
+        "src_ip",
+        "dst_ip",
+        "timestamp",
+        "action",
+        "a_rather_long_field_name_for_heap",
+    ];
+
+    #[test]
+    fn records_share_interned_names() {
+        let interner = NameInterner::with_capacity(MAX_INTERNED);
+        let mut records = Vec::with_capacity(100_000);
+        for i in 0..100_000 {
+            let fields: Vec<DataField> = KEYS
+                .iter()
+                .map(|k| DataField::from_chars(interner.intern(k), i.to_string()))
+                .collect();
+            records.push(fields);
+        }
+        assert_eq!(interner.len(), KEYS.len());
+        assert_eq!(records[99_999][2].get_name(), "timestamp");
+        // 超出内联长度的名称在堆上只分配一次，所有记录指向同一份字节
+        let first = records[0][4].get_name();
+        let last = records[99_999][4].get_name();
+        assert_eq!(first.as_ptr(), last.as_ptr());
+        let direct = DataField::from_chars(KEYS[4], String::new());
+        assert_ne!(direct.get_name().as_ptr(), first.as_ptr());
+        // 与直接构造的字段相等
+        assert_eq!(
+            records[0][0],
+            DataField::from_chars("src_ip", "0".to_string())
+        );
+    }
+
+    #[test]
+    fn capacity_caps_table_growth() {
+        let interner = NameInterner::with_capacity(2);
+        let first = interner.intern("a_rather_long_field_name_k0");
+        for i in 1..10 {
+            let name = format!("a_rather_long_field_name_k{i}");
+            assert_eq!(interner.intern(&name).to_string(), name);
+        }
+        assert!(interner.len() <= 2);
+        // 首个名称落入空分片，已驻留并继续命中
+        let again = interner.intern("a_rather_long_field_name_k0");
+        assert_eq!(first.as_ptr(), again.as_ptr());
+    }
+
+    #[test]
+    fn concurrent_interning_converges() {
+        let interner = NameInterner::with_capacity(64);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for i in 0..1000 {
+                        let name = format!("field_{}", i % 32);
+                        assert_eq!(interner.intern(&name).to_string(), name);
+                    }
+                });
+            }
+        });
+        assert!(interner.len() <= 64);
+    }
+}
@@ -2,6 +2,9 @@ extern crate serde_derive;
 pub mod cache;
 pub mod compare;
 pub mod conditions;
+pub mod intern;
+pub mod limits;
+pub mod record;
 // format 模块已迁移至独立 crate `wp-data-fmt`
 //pub mod model;
 //pub mod tags;
@@ -0,0 +1,411 @@
+//! 单条记录的体量保护：字段数、单字段值大小、记录总大小。
+//!
+//! - 限制为进程级配置（0 表示不限制），由主 crate 启动时经 [`configure`] 设置；[`scoped`] 可为当前线程单独指定。
+//!   未启用时写入路径只多一次线程局部读取。
+//! - 在字段写入处生效（WPL 协议解析器的 `out.push`、OML 写入目标记录），而不是事后扫描整条记录：
+//!   超限后的字段直接丢弃，记录不会先膨胀再裁剪。
+//! - 每条记录的累计状态放在线程局部，由 [`begin_record`] / [`finish_record`] 界定，无需改动解析/求值接口。
+//! - 策略 `truncate` 保留已写入部分并追加 `__truncated = true`；`drop` 由调用方将整条记录转入 miss。
+
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+use serde_derive::{Deserialize, Serialize};
+use wp_model_core::model::{DataField, FieldStorage, Value};
+
+/// 截断标记字段名
+pub const TRUNCATED_FIELD: &str = "__truncated";
+
+// 非字符串标量的估算大小
+const SCALAR_BYTES: usize = 16;
+
+/// 超限处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitPolicy {
+    /// 丢弃超限字段（超长字符串截到上限），记录追加 `__truncated = true`
+    #[default]
+    Truncate,
+    /// 整条记录转入 miss
+    Drop,
+}
+
+/// 记录体量限制；各项为 0 表示不限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RecordLimits {
+    pub max_fields: usize,
+    pub max_field_value_bytes: usize,
+    pub max_record_bytes: usize,
+    pub policy: LimitPolicy,
+}
+
+impl RecordLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_fields == 0 && self.max_field_value_bytes == 0 && self.max_record_bytes == 0
+    }
+}
+
+/// 触发的限制类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    Fields,
+    FieldValueBytes,
+    RecordBytes,
+}
+
+impl LimitKind {
+    pub const ALL: [LimitKind; 3] = [
+        LimitKind::Fields,
+        LimitKind::FieldValueBytes,
+        LimitKind::RecordBytes,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitKind::Fields => "fields",
+            LimitKind::FieldValueBytes => "field_value_bytes",
+            LimitKind::RecordBytes => "record_bytes",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            LimitKind::Fields => 0,
+            LimitKind::FieldValueBytes => 1,
+            LimitKind::RecordBytes => 2,
+        }
+    }
+}
+
+impl Display for LimitKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 一条记录结束时的结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordVerdict {
+    Intact,
+    Truncated(LimitKind),
+    Dropped(LimitKind),
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static MAX_FIELDS: AtomicUsize = AtomicUsize::new(0);
+static MAX_VALUE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static MAX_RECORD_BYTES: AtomicUsize = AtomicUsize::new(0);
+static POLICY_DROP: AtomicBool = AtomicBool::new(false);
+static HITS: [AtomicU64; 3] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+
+const UNLIMITED: RecordLimits = RecordLimits {
+    max_fields: 0,
+    max_field_value_bytes: 0,
+    max_record_bytes: 0,
+    policy: LimitPolicy::Truncate,
+};
+
+/// 当前记录的累计状态；限制在 [`begin_record`] 时取快照，记录处理中途修改配置不影响本条
+#[derive(Clone, Copy)]
+struct GuardState {
+    active: bool,
+    limits: RecordLimits,
+    bytes: usize,
+    // 字段数或总大小已到上限，后续字段一律拒绝
+    exhausted: bool,
+    tripped: Option<LimitKind>,
+}
+
+impl GuardState {
+    const IDLE: GuardState = GuardState {
+        active: false,
+        limits: UNLIMITED,
+        bytes: 0,
+        exhausted: false,
+        tripped: None,
+    };
+
+    fn trip(&mut self, kind: LimitKind, exhaust: bool) {
+        self.tripped.get_or_insert(kind);
+        // drop 策略下记录终将丢弃，无需继续接收
+        self.exhausted |= exhaust || self.limits.policy == LimitPolicy::Drop;
+    }
+}
+
+thread_local! {
+    static STATE: Cell<GuardState> = const { Cell::new(GuardState::IDLE) };
+    // 仅对当前线程生效的限制，优先于全局配置
+    static SCOPED: Cell<Option<RecordLimits>> = const { Cell::new(None) };
+}
+
+pub fn configure(limits: RecordLimits) {
+    MAX_FIELDS.store(limits.max_fields, Ordering::Relaxed);
+    MAX_VALUE_BYTES.store(limits.max_field_value_bytes, Ordering::Relaxed);
+    MAX_RECORD_BYTES.store(limits.max_record_bytes, Ordering::Relaxed);
+    POLICY_DROP.store(limits.policy == LimitPolicy::Drop, Ordering::Relaxed);
+    ACTIVE.store(!limits.is_unlimited(), Ordering::Relaxed);
+}
+
+/// 全局配置的限制
+pub fn limits() -> RecordLimits {
+    if !ACTIVE.load(Ordering::Relaxed) {
+        return UNLIMITED;
+    }
+    RecordLimits {
+        max_fields: MAX_FIELDS.load(Ordering::Relaxed),
+        max_field_value_bytes: MAX_VALUE_BYTES.load(Ordering::Relaxed),
+        max_record_bytes: MAX_RECORD_BYTES.load(Ordering::Relaxed),
+        policy: if POLICY_DROP.load(Ordering::Relaxed) {
+            LimitPolicy::Drop
+        } else {
+            LimitPolicy::Truncate
+        },
+    }
+}
+
+/// 在当前线程内以 `limits` 替代全局配置执行 `f`（测试、试运行等场景）
+pub fn scoped<T>(limits: RecordLimits, f: impl FnOnce() -> T) -> T {
+    let prev = SCOPED.with(|s| s.replace(Some(limits)));
+    let out = f();
+    SCOPED.with(|s| s.set(prev));
+    out
+}
+
+/// 累计触发次数（每条被截断/丢弃的记录按首个触发的限制计一次）
+pub fn hits(kind: LimitKind) -> u64 {
+    HITS[kind.index()].load(Ordering::Relaxed)
+}
+
+/// 开始一条记录（WPL 解析 / OML 转换入口调用）
+#[inline]
+pub fn begin_record() {
+    let conf = SCOPED.with(|s| s.get()).unwrap_or_else(limits);
+    let state = if conf.is_unlimited() {
+        GuardState::IDLE
+    } else {
+        GuardState {
+            active: true,
+            limits: conf,
+            ..GuardState::IDLE
+        }
+    };
+    STATE.with(|s| s.set(state));
+}
+
+/// 当前记录是否已停止接收字段；解析大对象时可据此提前结束遍历
+#[inline]
+pub fn exhausted() -> bool {
+    STATE.with(|s| s.get().exhausted)
+}
+
+/// 结束一条记录并返回结论；截断时调用方追加 [`truncated_marker`]，丢弃时转入 miss
+pub fn finish_record() -> RecordVerdict {
+    let state = STATE.with(|s| s.replace(GuardState::IDLE));
+    match state.tripped {
+        None => RecordVerdict::Intact,
+        Some(kind) => {
+            HITS[kind.index()].fetch_add(1, Ordering::Relaxed);
+            match state.limits.policy {
+                LimitPolicy::Truncate => RecordVerdict::Truncated(kind),
+                LimitPolicy::Drop => RecordVerdict::Dropped(kind),
+            }
+        }
+    }
+}
+
+pub fn truncated_marker() -> DataField {
+    DataField::from_bool(TRUNCATED_FIELD, true)
+}
+
+/// 按限制写入字段；`out` 为当前记录的字段序列。不在 `begin_record`/`finish_record` 之间时不做限制
+#[inline]
+pub fn push_field(out: &mut Vec<DataField>, field: DataField) {
+    let mut st = STATE.with(|s| s.get());
+    if !st.active {
+        out.push(field);
+        return;
+    }
+    if let Some(field) = admit(&mut st, out.len(), field) {
+        out.push(field);
+    }
+    STATE.with(|s| s.set(st));
+}
+
+/// [`push_field`] 的 `FieldStorage` 版本；共享字段仅在需要截断时转为独占
+#[inline]
+pub fn push_storage(out: &mut Vec<FieldStorage>, field: FieldStorage) {
+    let mut st = STATE.with(|s| s.get());
+    if !st.active {
+        out.push(field);
+        return;
+    }
+    let max_value = st.limits.max_field_value_bytes;
+    let over_value = max_value > 0 && value_bytes(field.get_value()) > max_value;
+    if field.is_shared() && !over_value {
+        if reserve(&mut st, out.len(), field.get_name(), field.get_value()) {
+            out.push(field);
+        }
+    } else if let Some(field) = admit(&mut st, out.len(), field.into_owned()) {
+        out.push(FieldStorage::from_owned(field));
+    }
+    STATE.with(|s| s.set(st));
+}
+
+fn admit(st: &mut GuardState, count: usize, mut field: DataField) -> Option<DataField> {
+    let max_value = st.limits.max_field_value_bytes;
+    if max_value > 0 && value_bytes(field.get_value()) > max_value {
+        st.trip(LimitKind::FieldValueBytes, false);
+        if st.limits.policy == LimitPolicy::Drop {
+            return None;
+        }
+        // 仅字符串可截断，其它超大值（数组/对象）整体丢弃
+        let cut = match field.get_value() {
+            Value::Chars(s) => {
+                let cut = floor_char_boundary(s.as_str(), max_value);
+                Value::Chars(s.as_str()[..cut].into())
+            }
+            _ => return None,
+        };
+        field.value = cut;
+    }
+    reserve(st, count, field.get_name(), field.get_value()).then_some(field)
+}
+
+/// 检查字段数与记录总大小，通过时计入该字段的大小
+fn reserve(st: &mut GuardState, count: usize, name: &str, value: &Value) -> bool {
+    if st.exhausted {
+        return false;
+    }
+    let max_fields = st.limits.max_fields;
+    if max_fields > 0 && count >= max_fields {
+        st.trip(LimitKind::Fields, true);
+        return false;
+    }
+    let bytes = name.len() + value_bytes(value);
+    let max_record = st.limits.max_record_bytes;
+    if max_record > 0 && st.bytes + bytes > max_record {
+        st.trip(LimitKind::RecordBytes, true);
+        return false;
+    }
+    st.bytes += bytes;
+    true
+}
+
+/// 字段值的估算大小：字符串按字节数，数组/对象递归累加，其余标量按固定值
+pub fn value_bytes(value: &Value) -> usize {
+    match value {
+        Value::Chars(s) => s.len(),
+        Value::Array(arr) => arr
+            .iter()
+            .map(|f| f.get_name().len() + value_bytes(f.get_value()))
+            .sum(),
+        Value::Obj(obj) => obj
+            .iter()
+            .map(|(k, f)| k.len() + value_bytes(f.get_value()))
+            .sum(),
+        _ => SCALAR_BYTES,
+    }
+}
+
+fn floor_char_boundary(s: &str, max: usize) -> usize {
+    let mut cut = max.min(s.len());
+    while !s.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_passes_through() {
+        scoped(RecordLimits::default(), || {
+            begin_record();
+            let mut out = Vec::new();
+            for i in 0..100 {
+                push_field(&mut out, DataField::from_digit(format!("f{}", i), i));
+            }
+            assert_eq!(out.len(), 100);
+            assert_eq!(finish_record(), RecordVerdict::Intact);
+        });
+    }
+
+    #[test]
+    fn field_count_truncates_and_counts() {
+        let limits = RecordLimits {
+            max_fields: 3,
+            ..Default::default()
+        };
+        scoped(limits, || {
+            let before = hits(LimitKind::Fields);
+            begin_record();
+            let mut out = Vec::new();
+            for i in 0..10 {
+                push_field(&mut out, DataField::from_digit(format!("f{}", i), i));
+            }
+            assert!(exhausted());
+            assert_eq!(out.len(), 3);
+            assert_eq!(finish_record(), RecordVerdict::Truncated(LimitKind::Fields));
+            assert_eq!(hits(LimitKind::Fields), before + 1);
+            // 下一条记录重新计数
+            begin_record();
+            assert!(!exhausted());
+            finish_record();
+        });
+        // 未在记录边界内时不做限制
+        let mut out = Vec::new();
+        for i in 0..10 {
+            push_field(&mut out, DataField::from_digit(format!("f{}", i), i));
+        }
+        assert_eq!(out.len(), 10);
+    }
+
+    #[test]
+    fn value_bytes_cut_at_char_boundary() {
+        let limits = RecordLimits {
+            max_field_value_bytes: 4,
+            ..Default::default()
+        };
+        scoped(limits, || {
+            begin_record();
+            let mut out = Vec::new();
+            push_field(&mut out, DataField::from_chars("msg", "日志内容"));
+            push_field(&mut out, DataField::from_chars("ok", "abc"));
+            assert_eq!(out[0].get_value(), &Value::Chars("日".into()));
+            assert_eq!(out[1].get_value(), &Value::Chars("abc".into()));
+            assert_eq!(
+                finish_record(),
+                RecordVerdict::Truncated(LimitKind::FieldValueBytes)
+            );
+        });
+    }
+
+    #[test]
+    fn record_bytes_drop_policy() {
+        let limits = RecordLimits {
+            max_record_bytes: 16,
+            policy: LimitPolicy::Drop,
+            ..Default::default()
+        };
+        scoped(limits, || {
+            begin_record();
+            let mut out = Vec::new();
+            push_storage(
+                &mut out,
+                FieldStorage::from_owned(DataField::from_chars("a", "0123456789")),
+            );
+            push_storage(
+                &mut out,
+                FieldStorage::from_owned(DataField::from_chars("b", "0123456789")),
+            );
+            assert_eq!(out.len(), 1);
+            assert_eq!(
+                finish_record(),
+                RecordVerdict::Dropped(LimitKind::RecordBytes)
+            );
+        });
+    }
+}
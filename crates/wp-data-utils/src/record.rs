@@ -0,0 +1,421 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::net::IpAddr;
+
+use chrono::NaiveDateTime;
+use serde::de::{Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use wp_model_core::model::types::value::ObjectValue;
+use wp_model_core::model::{DataField, DataRecord, DataType, FieldStorage, Value};
+
+/// 带类型标记的 JSON 值：`{"@type": "<类型>", "@value": "<文本>"}`
+pub const JSON_TYPE_TAG: &str = "@type";
+pub const JSON_VALUE_TAG: &str = "@value";
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// DataRecord 字段原地改名
+pub trait RecordRename {
+    /// 将首个名为 `from` 的字段改名为 `to`，值不复制（共享字段只改其当前名）；
+    /// 未找到返回 false。`to` 已存在时不做去重，由调用方决定是否先移除。
+    fn rename_field(&mut self, from: &str, to: &str) -> bool;
+}
+
+impl RecordRename for DataRecord {
+    fn rename_field(&mut self, from: &str, to: &str) -> bool {
+        match self.items.iter_mut().find(|f| f.get_name() == from) {
+            Some(field) => {
+                field.set_name(to.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// DataRecord 与 `serde_json::Value` / JSON 文本互转
+///
+/// - chars/digit/bool、数组、对象映射为 JSON 原生类型；
+/// - `serde_json::Map` 按键名排序，需要保留字段顺序时使用 `to_json_string` / `from_json_str`，
+///   二者按记录（文本）中的顺序读写，不依赖 serde_json 的 `preserve_order`；
+/// - 其余类型（float、ip、time、hex 等）写成带类型标记的对象，反向时按标记还原，
+///   float 以文本保存以保留 NaN/inf；带类型的 null 写成 `{"@type": "<类型>", "@value": null}`，
+///   无类型（auto）的 null 写成 JSON null；
+/// - 同名字段只输出第一个（与按名取字段一致），其后的同名字段丢弃；
+/// - `ignore` 字段不输出；数组元素不带字段名，反向时按 `<数组名>/[下标]` 命名（与 WPL 数组解析一致）。
+pub trait RecordJson: Sized {
+    fn into_json_value(self) -> JsonValue;
+    /// 按记录中的字段顺序输出 JSON 文本
+    fn to_json_string(&self) -> String;
+    /// 顶层须为对象，否则返回空记录；字段类型按 JSON 值类型推断
+    fn from_json_value(val: &JsonValue) -> Self;
+    /// 同 `from_json_value`，字段保持文本中的顺序；文本不是合法 JSON 时返回错误
+    fn from_json_str(text: &str) -> serde_json::Result<Self>;
+}
+
+impl RecordJson for DataRecord {
+    fn into_json_value(self) -> JsonValue {
+        JsonValue::Object(
+            json_entries(&self)
+                .into_iter()
+                .map(|(name, field)| (name.to_string(), field_to_json(field)))
+                .collect(),
+        )
+    }
+
+    fn to_json_string(&self) -> String {
+        // 键为字符串、值均可序列化，不会失败
+        serde_json::to_string(&OrderedRecord(json_entries(self))).unwrap_or_default()
+    }
+
+    fn from_json_value(val: &JsonValue) -> Self {
+        let fields: Vec<DataField> = match val {
+            JsonValue::Object(map) => map.iter().map(|(k, v)| field_from_json(k, v)).collect(),
+            _ => Vec::new(),
+        };
+        DataRecord::from(fields)
+    }
+
+    fn from_json_str(text: &str) -> serde_json::Result<Self> {
+        let fields: Vec<DataField> = match serde_json::from_str::<JsonTop>(text)? {
+            JsonTop::Object(entries) => entries
+                .0
+                .iter()
+                .map(|(k, v)| field_from_json(k, v))
+                .collect(),
+            JsonTop::Other(_) => Vec::new(),
+        };
+        Ok(DataRecord::from(fields))
+    }
+}
+
+/// 参与输出的字段：跳过 `ignore`，同名只取第一个
+fn json_entries(record: &DataRecord) -> Vec<(&str, &DataField)> {
+    let mut seen = HashSet::with_capacity(record.items.len());
+    record
+        .items
+        .iter()
+        .map(|item| item.as_field())
+        .filter(|field| !matches!(field.get_value(), Value::Ignore(_)))
+        .filter(|field| seen.insert(field.get_name()))
+        .map(|field| (field.get_name(), field))
+        .collect()
+}
+
+struct OrderedRecord<'a>(Vec<(&'a str, &'a DataField)>);
+
+impl Serialize for OrderedRecord<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, field) in &self.0 {
+            map.serialize_entry(name, &OrderedField(field))?;
+        }
+        map.end()
+    }
+}
+
+/// 逐层按字段顺序序列化数组与对象，其余值与 `field_to_json` 一致
+struct OrderedField<'a>(&'a DataField);
+
+impl Serialize for OrderedField<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.get_value() {
+            Value::Array(items) => {
+                serializer.collect_seq(items.iter().map(|f| OrderedField(f.as_field())))
+            }
+            Value::Obj(obj) => serializer.collect_map(
+                obj.iter()
+                    .map(|(k, f)| (k.to_string(), OrderedField(f.as_field()))),
+            ),
+            _ => field_to_json(self.0).serialize(serializer),
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize)]
+#[serde(untagged)]
+enum JsonTop {
+    Object(OrderedEntries),
+    Other(IgnoredAny),
+}
+
+/// 保持文本顺序的顶层对象
+struct OrderedEntries(Vec<(String, JsonValue)>);
+
+impl<'de> Deserialize<'de> for OrderedEntries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = OrderedEntries;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry::<String, JsonValue>()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedEntries(entries))
+            }
+        }
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+fn tagged(meta: &DataType, text: String) -> JsonValue {
+    let mut obj = Map::with_capacity(2);
+    obj.insert(
+        JSON_TYPE_TAG.to_string(),
+        JsonValue::String(meta.to_string()),
+    );
+    obj.insert(JSON_VALUE_TAG.to_string(), JsonValue::String(text));
+    JsonValue::Object(obj)
+}
+
+fn field_to_json(field: &DataField) -> JsonValue {
+    match field.get_value() {
+        Value::Null if *field.get_meta() != DataType::Auto => {
+            let mut obj = Map::with_capacity(2);
+            obj.insert(
+                JSON_TYPE_TAG.to_string(),
+                JsonValue::String(field.get_meta().to_string()),
+            );
+            obj.insert(JSON_VALUE_TAG.to_string(), JsonValue::Null);
+            JsonValue::Object(obj)
+        }
+        Value::Null | Value::Ignore(_) => JsonValue::Null,
+        Value::Bool(v) => JsonValue::Bool(*v),
+        Value::Digit(v) => JsonValue::from(*v),
+        Value::Chars(v) => JsonValue::String(v.to_string()),
+        Value::Float(v) => tagged(&DataType::Float, v.to_string()),
+        Value::IpAddr(v) => tagged(&DataType::IP, v.to_string()),
+        Value::Time(v) => tagged(&DataType::Time, v.format(TIME_FORMAT).to_string()),
+        Value::Array(items) => {
+            JsonValue::Array(items.iter().map(|f| field_to_json(f.as_field())).collect())
+        }
+        Value::Obj(obj) => JsonValue::Object(
+            obj.iter()
+                .map(|(k, f)| (k.to_string(), field_to_json(f.as_field())))
+                .collect(),
+        ),
+        other => tagged(field.get_meta(), other.to_string()),
+    }
+}
+
+/// 识别 `{"@type", "@value"}` 标记对象，`@value` 为 null 时返回 None 文本；
+/// 类型名无法识别时按普通对象处理
+fn tagged_parts(map: &Map<String, JsonValue>) -> Option<(DataType, Option<&str>)> {
+    if map.len() != 2 {
+        return None;
+    }
+    let tag = map.get(JSON_TYPE_TAG)?.as_str()?;
+    let text = match map.get(JSON_VALUE_TAG)? {
+        JsonValue::Null => None,
+        JsonValue::String(s) => Some(s.as_str()),
+        _ => return None,
+    };
+    DataType::from(tag).ok().map(|meta| (meta, text))
+}
+
+fn field_from_tagged(name: &str, meta: DataType, text: Option<&str>) -> DataField {
+    let Some(text) = text else {
+        return DataField::new_opt(meta, Some(name.into()), Value::Null);
+    };
+    let parsed = match meta {
+        DataType::Float => text
+            .parse::<f64>()
+            .ok()
+            .map(|v| DataField::from_float(name, v)),
+        DataType::IP => text
+            .parse::<IpAddr>()
+            .ok()
+            .map(|v| DataField::from_ip(name, v)),
+        DataType::Time => NaiveDateTime::parse_from_str(text, TIME_FORMAT)
+            .ok()
+            .map(|v| DataField::from_time(name, v)),
+        meta => DataField::from_str(meta, name, text).ok(),
+    };
+    parsed.unwrap_or_else(|| DataField::from_chars(name, text))
+}
+
+fn field_from_json(name: &str, val: &JsonValue) -> DataField {
+    match val {
+        JsonValue::Null => DataField::new_opt(DataType::Auto, Some(name.into()), Value::Null),
+        JsonValue::Bool(v) => DataField::from_bool(name, *v),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(v) => DataField::from_digit(name, v),
+            None => DataField::from_float(name, n.as_f64().unwrap_or(f64::NAN)),
+        },
+        JsonValue::String(s) => DataField::from_chars(name, s.as_str()),
+        JsonValue::Array(items) => DataField::from_arr(
+            name,
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| field_from_json(&format!("{}/[{}]", name, i), v))
+                .collect(),
+        ),
+        JsonValue::Object(map) => match tagged_parts(map) {
+            Some((meta, text)) => field_from_tagged(name, meta, text),
+            None => {
+                let mut obj = ObjectValue::default();
+                for (k, v) in map {
+                    obj.insert(k.clone(), FieldStorage::from_owned(field_from_json(k, v)));
+                }
+                DataField::from_obj(name, obj)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecordJson, RecordRename};
+    use serde_json::json;
+    use std::sync::Arc;
+    use wp_model_core::model::types::value::ObjectValue;
+    use wp_model_core::model::{DataField, DataRecord, DataType, FieldStorage, Value};
+
+    #[test]
+    fn rename_owned_and_shared_fields() {
+        let shared = Arc::new(DataField::from_chars("const", "v"));
+        let mut record = DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_digit("src_port", 443)),
+            FieldStorage::from_shared(shared.clone()),
+        ]);
+
+        assert!(record.rename_field("src_port", "sport"));
+        assert!(record.get_field_owned("src_port").is_none());
+        assert_eq!(
+            record.get_field_owned("sport").map(|f| f.value),
+            Some(Value::Digit(443))
+        );
+
+        assert!(record.rename_field("const", "tag"));
+        assert!(record.get_field_owned("tag").is_some());
+        // 共享字段本身不被修改
+        assert_eq!(shared.get_name(), "const");
+
+        assert!(!record.rename_field("missing", "x"));
+        assert_eq!(record.items.len(), 2);
+    }
+
+    fn fields(rec: &DataRecord) -> Vec<DataField> {
+        rec.items.iter().map(|f| f.as_field().clone()).collect()
+    }
+
+    #[test]
+    fn json_round_trip_keeps_types() {
+        let mut detail = ObjectValue::default();
+        detail.insert(
+            "port".to_string(),
+            FieldStorage::from_owned(DataField::from_digit("port", 443)),
+        );
+        let record = DataRecord::from(vec![
+            DataField::from_chars("msg", "hello"),
+            DataField::from_digit("count", -7),
+            DataField::from_bool("ok", true),
+            DataField::from_float("ratio", 3.0),
+            DataField::from_float("nan", f64::INFINITY),
+            DataField::from_ip("src_ip", "10.0.0.1".parse().unwrap()),
+            DataField::from_time(
+                "ts",
+                chrono::NaiveDate::from_ymd_opt(2026, 10, 15)
+                    .unwrap()
+                    .and_hms_milli_opt(8, 30, 0, 250)
+                    .unwrap(),
+            ),
+            DataField::from_obj("detail", detail),
+            DataField::from_ignore("skipped"),
+        ]);
+
+        let json = record.clone().into_json_value();
+        assert_eq!(json["msg"], json!("hello"));
+        assert_eq!(json["count"], json!(-7));
+        assert_eq!(
+            json["ratio"],
+            json!({"@type": DataType::Float.to_string(), "@value": "3"})
+        );
+        assert_eq!(json["src_ip"]["@value"], json!("10.0.0.1"));
+        assert_eq!(json["detail"], json!({"port": 443}));
+        assert!(json.get("skipped").is_none());
+
+        let mut expect = fields(&record);
+        expect.pop();
+        let mut sorted = expect.clone();
+        sorted.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+        let back = DataRecord::from_json_value(&json);
+        assert_eq!(fields(&back), sorted);
+
+        // 文本形式按记录顺序输出，而非按名排序
+        let text = record.to_json_string();
+        assert!(text.starts_with(r#"{"msg":"hello","count":-7,"ok":true,"ratio":"#));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+            json
+        );
+        let back = DataRecord::from_json_str(&text).unwrap();
+        assert_eq!(fields(&back), expect);
+    }
+
+    #[test]
+    fn json_round_trip_typed_null_and_array_names() {
+        let record = DataRecord::from(vec![
+            DataField::new_opt(DataType::Digit, Some("port".into()), Value::Null),
+            DataField::new_opt(DataType::Auto, Some("any".into()), Value::Null),
+            DataField::from_arr(
+                "ids",
+                vec![
+                    DataField::from_digit("ids/[0]", 1),
+                    DataField::from_digit("ids/[1]", 2),
+                ],
+            ),
+        ]);
+        let json = record.clone().into_json_value();
+        assert_eq!(
+            json["port"],
+            json!({"@type": DataType::Digit.to_string(), "@value": null})
+        );
+        assert_eq!(json["any"], json!(null));
+        assert_eq!(json["ids"], json!([1, 2]));
+
+        let back = DataRecord::from_json_value(&json);
+        assert_eq!(fields(&back), fields(&record));
+    }
+
+    #[test]
+    fn json_duplicate_names_keep_first() {
+        let record = DataRecord::from(vec![
+            DataField::from_chars("b", "first"),
+            DataField::from_digit("a", 1),
+            DataField::from_chars("b", "second"),
+        ]);
+        assert_eq!(record.to_json_string(), r#"{"b":"first","a":1}"#);
+        let json = record.into_json_value();
+        assert_eq!(json["b"], json!("first"));
+    }
+
+    #[test]
+    fn json_types_inferred() {
+        let back = DataRecord::from_json_value(&json!({
+            "a": 1.5,
+            "b": [1, "x"],
+            "c": null,
+            "d": {"@type": "no_such_type", "@value": "v"},
+        }));
+        let get = |name: &str| back.get_field_owned(name).map(|f| f.value).unwrap();
+        assert_eq!(get("a"), Value::Float(1.5));
+        assert!(matches!(get("b"), Value::Array(ref items) if items.len() == 2));
+        assert_eq!(get("c"), Value::Null);
+        assert!(matches!(get("d"), Value::Obj(_)));
+        assert!(DataRecord::from_json_value(&json!([1, 2])).items.is_empty());
+        assert!(
+            DataRecord::from_json_str("[1, 2]")
+                .unwrap()
+                .items
+                .is_empty()
+        );
+        assert!(DataRecord::from_json_str("{").is_err());
+    }
+}
@@ -1,8 +1,10 @@
+use std::borrow::Cow;
 use std::path::Path;
 use std::sync::Arc;
 
 use std::collections::HashSet;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
 use wp_data_model::cache::CacheAble;
 use wp_error::{KnowledgeReason, KnowledgeResult};
 use wp_log::info_ctrl;
@@ -12,11 +14,17 @@ use crate::DBQuery;
 use crate::mem::RowData;
 use crate::mem::memdb::MemDB;
 use crate::mem::thread_clone::ThreadClonedMDB;
+use crate::query_cache::{CacheKey, Cacheable, SqlQuery};
 //use anyhow::{anyhow, Result};
 use orion_error::{ErrorWith, ToStructError, UvsLogicFrom};
 use rusqlite::ToSql;
 use rusqlite::{Connection, OpenFlags};
 
+pub use crate::query_cache::{
+    QueryCacheSpec, QueryCacheStats, QueryResultCache, configure_query_cache, flush_query_cache,
+    query_cache_spec, query_cache_stats,
+};
+
 /// 对外统一查询门面，隐藏底层 MemDB/线程副本等实现选择。
 /// 仅提供对象安全的两种查询接口：无参和命名参数。
 pub trait QueryFacade: Send + Sync {
@@ -28,6 +36,8 @@ pub trait QueryFacade: Send + Sync {
         params: &'a [(&'a str, &'a dyn ToSql)],
     ) -> KnowledgeResult<RowData>;
     fn query_cipher(&self, table: &str) -> KnowledgeResult<Vec<String>>;
+    /// 查询结果缓存键，由后端的 [`Cacheable`] 实现决定
+    fn cache_key(&self, query: &SqlQuery) -> CacheKey;
 }
 
 impl QueryFacade for ThreadClonedMDB {
@@ -47,6 +57,9 @@ impl QueryFacade for ThreadClonedMDB {
     fn query_cipher(&self, table: &str) -> KnowledgeResult<Vec<String>> {
         DBQuery::query_cipher(self, table)
     }
+    fn cache_key(&self, query: &SqlQuery) -> CacheKey {
+        <ThreadClonedMDB as Cacheable>::cache_key(query)
+    }
 }
 
 struct MemProvider(MemDB);
@@ -67,9 +80,14 @@ impl QueryFacade for MemProvider {
     fn query_cipher(&self, table: &str) -> KnowledgeResult<Vec<String>> {
         DBQuery::query_cipher(&self.0, table)
     }
+    fn cache_key(&self, query: &SqlQuery) -> CacheKey {
+        <MemDB as Cacheable>::cache_key(query)
+    }
 }
 
-static PROVIDER: OnceLock<Arc<dyn QueryFacade>> = OnceLock::new();
+static PROVIDER: RwLock<Option<Arc<dyn QueryFacade>>> = RwLock::new(None);
+/// provider 代数：每次热重载替换 provider 时递增
+static PROVIDER_GEN: AtomicU64 = AtomicU64::new(0);
 static TABLE_WHITELIST: OnceLock<HashSet<String>> = OnceLock::new();
 
 /// 直接使用已有的权威库 URI 初始化线程副本 provider。
@@ -89,25 +107,67 @@ pub fn init_mem_provider(memdb: MemDB) -> KnowledgeResult<()> {
     res
 }
 
+/// 热重载：以更新后的内存库替换当前 provider（调用方已就地修改表数据时使用）
+pub fn reload_mem_provider(memdb: MemDB) {
+    replace_provider(Arc::new(MemProvider(memdb)));
+}
+
 fn set_provider(p: Arc<dyn QueryFacade>) -> KnowledgeResult<()> {
-    PROVIDER
-        .set(p)
-        .map_err(|_| KnowledgeReason::from_logic("knowledge provider already initialized").to_err())
+    let mut slot = PROVIDER.write().unwrap_or_else(|e| e.into_inner());
+    if slot.is_some() {
+        return KnowledgeReason::from_logic("knowledge provider already initialized").err_result();
+    }
+    *slot = Some(p);
+    Ok(())
+}
+
+/// 原子替换 provider 并递增代数，已缓存的查询结果随之失效；
+/// 本线程暂存的区间索引在同一写锁内随新代数发布
+pub(crate) fn replace_provider(p: Arc<dyn QueryFacade>) {
+    {
+        let mut slot = PROVIDER.write().unwrap_or_else(|e| e.into_inner());
+        *slot = Some(p);
+        let generation = PROVIDER_GEN.fetch_add(1, Ordering::SeqCst) + 1;
+        crate::mem::range_index::publish_staged(generation);
+    }
+    flush_query_cache();
+}
+
+/// 当前 provider 代数；调用方据此判断自身持有的查询缓存是否过期
+pub fn provider_generation() -> u64 {
+    PROVIDER_GEN.load(Ordering::SeqCst)
+}
+
+fn get_provider() -> KnowledgeResult<Arc<dyn QueryFacade>> {
+    get_provider_gen().map(|(p, _)| p)
 }
 
-fn get_provider() -> KnowledgeResult<&'static Arc<dyn QueryFacade>> {
-    PROVIDER
-        .get()
-        .ok_or_else(|| KnowledgeReason::from_logic("knowledge provider not initialized").to_err())
+/// 在同一读锁下取 provider 与其代数，保证改写后的 SQL 与所查的库属于同一代
+fn get_provider_gen() -> KnowledgeResult<(Arc<dyn QueryFacade>, u64)> {
+    let slot = PROVIDER.read().unwrap_or_else(|e| e.into_inner());
+    let provider = slot.clone().ok_or_else(|| {
+        KnowledgeReason::from_logic("knowledge provider not initialized").to_err()
+    })?;
+    Ok((provider, PROVIDER_GEN.load(Ordering::SeqCst)))
 }
 
 pub fn query(sql: &str) -> KnowledgeResult<Vec<RowData>> {
-    get_provider()?.query(sql)
+    let (provider, generation) = get_provider_gen()?;
+    provider.query(&range_sql(sql, generation))
+}
+
+/// 区间表（`key_type = "iprange"`）上的等值条件改写为区间索引定位，其余 SQL 原样返回
+fn range_sql(sql: &str, generation: u64) -> Cow<'_, str> {
+    match crate::mem::range_index::rewrite_range_sql(sql, generation) {
+        Some(rewritten) => Cow::Owned(rewritten),
+        None => Cow::Borrowed(sql),
+    }
 }
 
 /// 门面查询：无参
 pub fn query_row(sql: &str) -> KnowledgeResult<RowData> {
-    get_provider()?.query_row(sql)
+    let (provider, generation) = get_provider_gen()?;
+    provider.query_row(&range_sql(sql, generation))
 }
 
 /// 门面查询：命名参数
@@ -115,7 +175,8 @@ pub fn query_named<'a>(
     sql: &str,
     params: &'a [(&'a str, &'a dyn ToSql)],
 ) -> KnowledgeResult<RowData> {
-    get_provider()?.query_named(sql, params)
+    let (provider, generation) = get_provider_gen()?;
+    provider.query_named(&range_sql(sql, generation), params)
 }
 
 /// 读取密文字典表（单列表 `value`），用于隐私脱敏加载词表
@@ -134,19 +195,29 @@ pub fn query_cipher(table: &str) -> KnowledgeResult<Vec<String>> {
 /// - `c_params` 用于上层缓存键（通常为 `[md5, :k1, :k2, ...]`）
 /// - `named_params` 为 SQLite 命名参数切片（可由 `SqlNamedParam` 数组通过 `to_params()` 生成）
 ///   命中缓存直接返回；未命中则通过全局 provider 查询并回填缓存。
+/// - 开启查询结果缓存（[`configure_query_cache`]）时改用线程内带 TTL 的缓存，`cache` 不再参与。
 pub fn cache_query<const N: usize>(
     sql: &str,
     c_params: &[DataField; N],
     named_params: &[(&str, &dyn ToSql)],
     cache: &mut impl CacheAble<DataField, RowData, N>,
 ) -> RowData {
-    crate::cache_util::cache_query_impl(c_params, cache, || {
+    let run = || {
+        let (provider, generation) = get_provider_gen()?;
+        let exec_sql = range_sql(sql, generation);
         if named_params.is_empty() {
-            get_provider().and_then(|p| p.query_row(sql))
+            provider.query_row(&exec_sql)
         } else {
-            get_provider().and_then(|p| p.query_named(sql, named_params))
+            provider.query_named(&exec_sql, named_params)
         }
-    })
+    };
+    if query_cache_spec().is_some()
+        && let Ok(provider) = get_provider()
+    {
+        let key = provider.cache_key(&SqlQuery::new(sql, c_params));
+        return crate::query_cache::cached_query(key, run);
+    }
+    crate::cache_util::cache_query_impl(c_params, cache, run)
 }
 
 fn ensure_wal(authority_uri: &str) -> KnowledgeResult<()> {
@@ -174,7 +245,8 @@ pub fn init_wal_pool_from_authority(authority_uri: &str, pool_size: u32) -> Know
 
 /// 设置基于 V2 KnowDB 的全局 Provider：
 /// - 构建权威库文件（CSV → SQLite），并启用线程克隆 Provider；
-/// - 同时设置表名白名单（仅允许访问配置中声明的表）。
+/// - 同时设置表名白名单（仅允许访问配置中声明的表）；
+/// - 配置含 `[cache]` 时开启查询结果缓存。
 pub fn init_thread_cloned_from_knowdb(
     root: &Path,
     knowdb_conf: &Path,
@@ -183,14 +255,12 @@ pub fn init_thread_cloned_from_knowdb(
 ) -> KnowledgeResult<()> {
     let tables =
         crate::loader::build_authority_from_knowdb(root, knowdb_conf, authority_uri, dict)?;
+    let (conf, _, _) = crate::loader::parse_knowdb_conf(root, knowdb_conf, dict)?;
+    if conf.cache.is_some() {
+        configure_query_cache(conf.cache);
+    }
     // 使用只读 URI 暴露给线程克隆
-    let ro_uri = if let Some(rest) = authority_uri.strip_prefix("file:") {
-        let path_part = rest.split('?').next().unwrap_or(rest);
-        format!("file:{}?mode=ro&uri=true", path_part)
-    } else {
-        authority_uri.to_string()
-    };
-    let tc = ThreadClonedMDB::from_authority(&ro_uri);
+    let tc = ThreadClonedMDB::from_authority(&read_only_uri(authority_uri));
 
     // Pre-load the database into memory to avoid file deletion issues
     // This ensures the database is copied immediately rather than lazily
@@ -204,3 +274,14 @@ pub fn init_thread_cloned_from_knowdb(
     info_ctrl!("init authority knowdb success({}) ", knowdb_conf.display(),);
     set_provider(Arc::new(tc))
 }
+
+/// 权威库的只读 URI（供线程克隆读取）；非 `file:` URI 原样返回
+pub(crate) fn read_only_uri(authority_uri: &str) -> String {
+    match authority_uri.strip_prefix("file:") {
+        Some(rest) => {
+            let path_part = rest.split('?').next().unwrap_or(rest);
+            format!("file:{}?mode=ro&uri=true", path_part)
+        }
+        None => authority_uri.to_string(),
+    }
+}
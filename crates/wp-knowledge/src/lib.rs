@@ -4,4 +4,6 @@ pub use crate::mem::memdb::MDBEnum;
 pub mod cache_util;
 pub mod facade;
 pub mod loader;
+pub mod query_cache;
+pub mod reload;
 pub mod sqlite_ext;
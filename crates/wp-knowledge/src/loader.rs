@@ -7,7 +7,11 @@ use serde::Deserialize;
 use wp_log::info_ctrl;
 
 use crate::mem::memdb::MemDB;
-use orion_error::{ContextRecord, ErrorOwe, OperationContext, ToStructError, UvsConfFrom};
+use crate::mem::range_index::{IpRangeSpec, KeyType};
+use crate::query_cache::QueryCacheSpec;
+use orion_error::{
+    ContextRecord, ErrorOwe, ErrorWith, OperationContext, ToStructError, UvsConfFrom,
+};
 use orion_variate::EnvDict;
 use rusqlite::OpenFlags;
 use wp_error::{KnowledgeReason, KnowledgeResult};
@@ -23,6 +27,12 @@ pub struct KnowDbConf {
     pub default: OptLoadSpec,
     #[serde(default)]
     pub csv: CsvSpec,
+    /// 查询结果缓存；缺省不开启
+    #[serde(default)]
+    pub cache: Option<QueryCacheSpec>,
+    /// 热重载轮询间隔（秒）；缺省不监听表文件变更
+    #[serde(default)]
+    pub reload_interval_secs: Option<u64>,
     pub tables: Vec<TableSpec>,
 }
 
@@ -87,6 +97,12 @@ pub struct TableSpec {
     pub expected_rows: RowExpect,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// 键类型：`iprange` 时加载后构建 IP 区间索引，`where <key> = <ip>` 按区间包含匹配
+    #[serde(default)]
+    pub key_type: KeyType,
+    /// 区间表的列声明（`key_type = "iprange"` 时必填）
+    #[serde(default)]
+    pub range: Option<IpRangeSpec>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -170,7 +186,7 @@ pub fn build_authority_from_knowdb(
     Ok(loaded_names)
 }
 
-fn parse_knowdb_conf(
+pub(crate) fn parse_knowdb_conf(
     root: &Path,
     conf_path: &Path,
     dict: &EnvDict,
@@ -191,7 +207,22 @@ fn parse_knowdb_conf(
     Ok((conf, conf_abs, base_dir))
 }
 
-fn open_authority(authority_uri: &str) -> KnowledgeResult<MemDB> {
+/// 表的全部源文件（建表/导入/清理 SQL 与数据文件），用于热重载检测变更
+pub(crate) fn table_source_files(base_dir: &Path, t: &TableSpec) -> Vec<PathBuf> {
+    let table_dir = base_dir.join(t.dir.as_deref().unwrap_or(&t.name));
+    let data_path = match &t.data_file {
+        Some(rel) => join_rel(&table_dir, rel),
+        None => table_dir.join("data.csv"),
+    };
+    vec![
+        table_dir.join("create.sql"),
+        table_dir.join("insert.sql"),
+        table_dir.join("clean.sql"),
+        data_path,
+    ]
+}
+
+pub(crate) fn open_authority(authority_uri: &str) -> KnowledgeResult<MemDB> {
     ensure_parent_dir_for_file_uri(authority_uri);
     let flags = OpenFlags::SQLITE_OPEN_READ_WRITE
         | OpenFlags::SQLITE_OPEN_CREATE
@@ -217,7 +248,7 @@ fn ensure_parent_dir_for_file_uri(uri: &str) {
     }
 }
 
-fn load_one_table(
+pub(crate) fn load_one_table(
     db: &MemDB,
     base_dir: &Path,
     t: &TableSpec,
@@ -339,10 +370,25 @@ fn load_one_table(
     if bad > 0 {
         wp_log::warn_kdb!("table {} skipped {} bad rows (on_error=skip)", &t.name, bad);
     }
+    index_range_table(db, t)?;
     opx.mark_suc();
     Ok(())
 }
 
+/// 区间表：在导入完成后构建 IP 区间索引
+pub(crate) fn index_range_table(db: &MemDB, t: &TableSpec) -> KnowledgeResult<()> {
+    if t.key_type != KeyType::IpRange {
+        return Ok(());
+    }
+    let Some(spec) = &t.range else {
+        return KnowledgeReason::from_conf("iprange table requires [tables.range]")
+            .err_result()
+            .with(("table", t.name.as_str()));
+    };
+    db.index_ip_ranges(&t.name, spec).owe_conf()?;
+    Ok(())
+}
+
 fn build_csv_reader(
     csvd: &CsvSpec,
     data_path: &Path,
@@ -26,6 +26,7 @@ use wp_model_core::model::DataField;
 
 use super::AnyResult;
 use super::SqlNamedParam;
+use super::range_index::{self, IpRangeIndex, IpRangeSpec};
 
 lazy_static! {
     // Important: Use a single SQLite in-memory connection so schema/data persist across calls.
@@ -272,6 +273,38 @@ impl MemDB {
         Ok(load_cnt)
     }
 
+    /// 为区间表构建 IP 区间索引并登记，返回参与索引的区间数；
+    /// 任一行的区间无法解析时报错（不替换已登记的索引）
+    pub fn index_ip_ranges(&self, table: &str, spec: &IpRangeSpec) -> AnyResult<usize> {
+        let sql = spec.select_sql(table)?;
+        let ranges = self.with_conn(|conn| {
+            let _ = crate::sqlite_ext::register_builtin(conn);
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query([])?;
+            let mut ranges = Vec::new();
+            while let Some(row) = rows.next()? {
+                let rowid: i64 = row.get(0)?;
+                let bounds = if spec.cidr.is_some() {
+                    let text: String = row.get(1)?;
+                    range_index::cidr_bounds(&text)
+                } else {
+                    let start: Value = row.get(1)?;
+                    let end: Value = row.get(2)?;
+                    range_index::bound_key(&start).zip(range_index::bound_key(&end))
+                };
+                match bounds {
+                    Some((start, end)) if start <= end => ranges.push((start, end, rowid)),
+                    _ => anyhow::bail!("table {} row {}: invalid ip range", table, rowid),
+                }
+            }
+            Ok(ranges)
+        })?;
+        let cnt = ranges.len();
+        range_index::register_range_table(table, &spec.key, IpRangeIndex::build(ranges));
+        info_kdb!("table {} ip range index built: {} ranges", table, cnt);
+        Ok(cnt)
+    }
+
     pub fn check_data(&self, table: &str, scope: (usize, usize)) -> KnowledgeResult<usize> {
         let conn = self.conn.get().owe_res()?;
         let count_sql = format!("select count(*) from {}", table);
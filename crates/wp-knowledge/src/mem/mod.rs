@@ -1,6 +1,7 @@
 pub mod memdb;
 pub mod params;
 pub mod query_util;
+pub mod range_index;
 mod stub;
 pub mod thread_clone;
 
@@ -0,0 +1,477 @@
+//! IP 区间表：为声明 `key_type = "iprange"` 的表在加载时构建区间索引，
+//! 将 `where <key> = <ip>` 的等值条件改写为按索引定位行（O(log n)）。
+//!
+//! - 区间来源：CIDR 列（如 `10.0.0.0/8`、`2001:db8::/32`）或起止列（点分/冒号文本，或 IPv4 整数）
+//! - IPv4 统一映射为 `::ffff:a.b.c.d` 后与 IPv6 共用 u128 键空间
+//! - 区间重叠时取最具体（跨度最小）的区间；跨度相同取 rowid 较小者
+//! - 索引按表名登记在进程级注册表中，SQL 通过 `ip_range_rowid(table, ip, generation)` 查询，
+//!   线程克隆库经 backup 复制，rowid 与权威库一致
+//! - 注册表按 provider 代数分组：热重载期间新建的索引先暂存在重载线程（[`StagingScope`]），
+//!   与 provider 一同切换；切换前改写的查询带旧代数，仍按旧索引定位旧库的行
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+
+use ipnet::IpNet;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use super::AnyResult;
+
+/// 表的键类型：缺省为普通等值匹配
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyType {
+    #[default]
+    Exact,
+    IpRange,
+}
+
+/// 区间表的列声明：`cidr` 与 `start`/`end` 二选一
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IpRangeSpec {
+    /// SQL 条件中使用的键名（`where ip = ...` 中的 `ip`）
+    #[serde(default = "default_range_key")]
+    pub key: String,
+    #[serde(default)]
+    pub cidr: Option<String>,
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
+}
+
+fn default_range_key() -> String {
+    "ip".to_string()
+}
+
+impl IpRangeSpec {
+    pub fn with_cidr(key: &str, cidr: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            cidr: Some(cidr.to_string()),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn with_bounds(key: &str, start: &str, end: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            cidr: None,
+            start: Some(start.to_string()),
+            end: Some(end.to_string()),
+        }
+    }
+
+    /// 生成读取区间的 SQL（rowid + 区间列）
+    pub(crate) fn select_sql(&self, table: &str) -> AnyResult<String> {
+        match (&self.cidr, &self.start, &self.end) {
+            (Some(cidr), None, None) => Ok(format!("SELECT rowid, {} FROM {}", cidr, table)),
+            (None, Some(start), Some(end)) => {
+                Ok(format!("SELECT rowid, {}, {} FROM {}", start, end, table))
+            }
+            _ => anyhow::bail!(
+                "table {}: iprange requires either `cidr` or both `start` and `end`",
+                table
+            ),
+        }
+    }
+}
+
+/// 不相交的区间段（按起点排序），每段记录覆盖它的最具体区间所在行
+#[derive(Debug, Clone, Default)]
+pub struct IpRangeIndex {
+    segments: Vec<(u128, u128, i64)>,
+}
+
+impl IpRangeIndex {
+    /// 由 `(起点, 终点(含), rowid)` 构建；重叠区间在此展开为不相交段
+    pub fn build(ranges: Vec<(u128, u128, i64)>) -> Self {
+        // 边界点 -> (此处开始的区间, 此处结束的区间)
+        let mut events: BTreeMap<u128, (Vec<usize>, Vec<usize>)> = BTreeMap::new();
+        for (i, (start, end, _)) in ranges.iter().enumerate() {
+            events.entry(*start).or_default().0.push(i);
+            if let Some(after) = end.checked_add(1) {
+                events.entry(after).or_default().1.push(i);
+            }
+        }
+        let mut active: BTreeSet<(u128, i64, usize)> = BTreeSet::new();
+        let mut segments: Vec<(u128, u128, i64)> = Vec::new();
+        let points: Vec<u128> = events.keys().copied().collect();
+        for (pos, point) in points.iter().enumerate() {
+            let (adds, removes) = &events[point];
+            for &i in removes {
+                let (s, e, row) = ranges[i];
+                active.remove(&(e - s, row, i));
+            }
+            for &i in adds {
+                let (s, e, row) = ranges[i];
+                active.insert((e - s, row, i));
+            }
+            let Some(&(_, row, _)) = active.first() else {
+                continue;
+            };
+            let seg_end = points.get(pos + 1).map(|p| p - 1).unwrap_or(u128::MAX);
+            match segments.last_mut() {
+                Some(last) if last.2 == row && last.1.checked_add(1) == Some(*point) => {
+                    last.1 = seg_end
+                }
+                _ => segments.push((*point, seg_end, row)),
+            }
+        }
+        Self { segments }
+    }
+
+    pub fn lookup(&self, ip: IpAddr) -> Option<i64> {
+        let key = ip_key(ip);
+        let idx = self.segments.partition_point(|s| s.0 <= key);
+        let (_, end, row) = *self.segments.get(idx.checked_sub(1)?)?;
+        (key <= end).then_some(row)
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+/// IP 映射到统一的 u128 键空间（IPv4 -> IPv4-mapped IPv6）
+pub fn ip_key(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(v4) => u128::from(v4.to_ipv6_mapped()),
+        IpAddr::V6(v6) => u128::from(v6),
+    }
+}
+
+/// 解析 CIDR 为闭区间
+pub fn cidr_bounds(s: &str) -> Option<(u128, u128)> {
+    let net: IpNet = s.trim().trim_matches('"').parse().ok()?;
+    Some((ip_key(net.network()), ip_key(net.broadcast())))
+}
+
+/// 解析区间端点：IP 文本或 IPv4 整数
+pub fn bound_key(v: &rusqlite::types::Value) -> Option<u128> {
+    match v {
+        rusqlite::types::Value::Integer(i) => {
+            let v4 = u32::try_from(*i).ok()?;
+            Some(ip_key(IpAddr::from(std::net::Ipv4Addr::from(v4))))
+        }
+        rusqlite::types::Value::Text(s) => {
+            let ip: IpAddr = s.trim().trim_matches('"').parse().ok()?;
+            Some(ip_key(ip))
+        }
+        _ => None,
+    }
+}
+
+struct RangeTable {
+    key: String,
+    index: IpRangeIndex,
+}
+
+type TableMap = HashMap<String, Arc<RangeTable>>;
+
+/// 某一代 provider 对应的区间索引
+#[derive(Default)]
+struct RangeTables {
+    generation: u64,
+    tables: TableMap,
+}
+
+/// 当前代与上一代：切换瞬间仍在执行的旧查询按上一代定位
+#[derive(Default)]
+struct RangeRegistry {
+    current: Arc<RangeTables>,
+    previous: Option<Arc<RangeTables>>,
+}
+
+impl RangeRegistry {
+    fn tables(&self, generation: u64) -> &TableMap {
+        match &self.previous {
+            Some(prev)
+                if prev.generation == generation && self.current.generation != generation =>
+            {
+                &prev.tables
+            }
+            _ => &self.current.tables,
+        }
+    }
+}
+
+static RANGE_TABLES: Lazy<RwLock<RangeRegistry>> =
+    Lazy::new(|| RwLock::new(RangeRegistry::default()));
+
+thread_local! {
+    // Some：本线程正在为下一代 provider 构建索引
+    static STAGED: RefCell<Option<TableMap>> = const { RefCell::new(None) };
+}
+
+/// 暂存作用域：作用域内登记的区间索引不立即生效，由 [`publish_staged`] 随 provider 切换；
+/// 离开作用域时丢弃未发布的索引（如重载没有表生效）
+pub struct StagingScope {
+    _private: (),
+}
+
+impl StagingScope {
+    pub fn enter() -> Self {
+        STAGED.with(|s| *s.borrow_mut() = Some(HashMap::new()));
+        Self { _private: () }
+    }
+}
+
+impl Drop for StagingScope {
+    fn drop(&mut self) {
+        STAGED.with(|s| *s.borrow_mut() = None);
+    }
+}
+
+/// 登记（或替换）表的区间索引；表名不区分大小写。暂存作用域内只暂存，否则立即生效
+pub fn register_range_table(table: &str, key: &str, index: IpRangeIndex) {
+    let name = table.to_ascii_lowercase();
+    let table = Arc::new(RangeTable {
+        key: key.to_string(),
+        index,
+    });
+    let staged = STAGED.with(|s| match s.borrow_mut().as_mut() {
+        Some(staged) => {
+            staged.insert(name.clone(), table.clone());
+            true
+        }
+        None => false,
+    });
+    if staged {
+        return;
+    }
+    let mut reg = RANGE_TABLES.write().unwrap_or_else(|e| e.into_inner());
+    let mut tables = reg.current.tables.clone();
+    tables.insert(name, table);
+    reg.current = Arc::new(RangeTables {
+        generation: reg.current.generation,
+        tables,
+    });
+}
+
+/// 切换到第 `generation` 代 provider：本线程暂存的索引覆盖当前索引，原当前代保留为上一代。
+/// 由 provider 替换在持有 provider 写锁时调用
+pub(crate) fn publish_staged(generation: u64) {
+    let staged = STAGED.with(|s| s.borrow_mut().as_mut().map(std::mem::take));
+    let mut reg = RANGE_TABLES.write().unwrap_or_else(|e| e.into_inner());
+    let mut tables = reg.current.tables.clone();
+    tables.extend(staged.unwrap_or_default());
+    let next = Arc::new(RangeTables { generation, tables });
+    reg.previous = Some(std::mem::replace(&mut reg.current, next));
+}
+
+/// 按第 `generation` 代的区间索引定位 IP 所在行；表未登记或 IP 非法时为 None
+pub fn range_rowid(table: &str, ip: &str, generation: u64) -> Option<i64> {
+    let ip: IpAddr = ip.trim().parse().ok()?;
+    let table = RANGE_TABLES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .tables(generation)
+        .get(&table.to_ascii_lowercase())
+        .cloned()?;
+    table.index.lookup(ip)
+}
+
+/// 大小写不敏感地查找关键字（含两侧空格），不复制整条 SQL
+fn find_keyword(sql: &str, keyword: &str) -> Option<usize> {
+    sql.as_bytes()
+        .windows(keyword.len())
+        .position(|w| w.eq_ignore_ascii_case(keyword.as_bytes()))
+}
+
+/// 将 `select <cols> from <range_table> where <key> = <value>` 改写为按第 `generation` 代
+/// 区间索引定位；其它形式（非区间表、多条件、键名不符）返回 None 原样执行。
+/// 只有表名与键名按小写比较，其余 SQL（含参数与字面量）保持原样
+pub fn rewrite_range_sql(sql: &str, generation: u64) -> Option<String> {
+    let reg = RANGE_TABLES.read().unwrap_or_else(|e| e.into_inner());
+    let tables = reg.tables(generation);
+    if tables.is_empty() {
+        return None;
+    }
+    let from = find_keyword(sql, " from ")? + " from ".len();
+    let where_at = find_keyword(&sql[from..], " where ")? + from;
+    let table = sql[from..where_at].trim().to_ascii_lowercase();
+    let range = tables.get(&table)?;
+
+    let cond = sql[where_at + " where ".len()..]
+        .trim()
+        .trim_end_matches(';');
+    let cond = cond.trim();
+    let cond = cond
+        .strip_prefix('(')
+        .and_then(|c| c.strip_suffix(')'))
+        .unwrap_or(cond)
+        .trim();
+    let (lhs, rhs) = cond.split_once('=')?;
+    let (lhs, rhs) = (lhs.trim(), rhs.trim());
+    if !lhs.eq_ignore_ascii_case(&range.key) || rhs.is_empty() || rhs.contains(char::is_whitespace)
+    {
+        return None;
+    }
+    Some(format!(
+        "{} where rowid = ip_range_rowid('{}', {}, {})",
+        sql[..where_at].trim_end(),
+        table,
+        rhs,
+        generation
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mem::DBQuery;
+    use crate::mem::memdb::MemDB;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn cidr(s: &str, row: i64) -> (u128, u128, i64) {
+        let (a, b) = cidr_bounds(s).unwrap();
+        (a, b, row)
+    }
+
+    #[test]
+    fn overlapping_ranges_pick_most_specific() {
+        let index = IpRangeIndex::build(vec![
+            cidr("10.0.0.0/8", 1),
+            cidr("10.1.0.0/16", 2),
+            cidr("10.1.2.0/24", 3),
+            cidr("192.168.0.0/16", 4),
+        ]);
+        assert_eq!(index.lookup(ip("10.9.9.9")), Some(1));
+        assert_eq!(index.lookup(ip("10.1.9.9")), Some(2));
+        assert_eq!(index.lookup(ip("10.1.2.200")), Some(3));
+        // 离开最内层后回到外层区间
+        assert_eq!(index.lookup(ip("10.1.3.0")), Some(2));
+        assert_eq!(index.lookup(ip("10.2.0.0")), Some(1));
+        assert_eq!(index.lookup(ip("192.168.255.255")), Some(4));
+        assert_eq!(index.lookup(ip("11.0.0.0")), None);
+        assert_eq!(index.lookup(ip("9.255.255.255")), None);
+        assert_eq!(index.len(), 6);
+    }
+
+    #[test]
+    fn partial_overlap_and_ipv6() {
+        let v4 = |s: &str| ip_key(ip(s));
+        let index = IpRangeIndex::build(vec![
+            (v4("1.0.0.0"), v4("1.0.0.100"), 1),
+            (v4("1.0.0.50"), v4("1.0.0.150"), 2),
+            cidr("2001:db8::/32", 3),
+            cidr("2001:db8:abcd::/48", 4),
+            cidr("::/0", 5),
+        ]);
+        assert_eq!(index.lookup(ip("1.0.0.10")), Some(1));
+        // 两区间跨度相同：取 rowid 较小者
+        assert_eq!(index.lookup(ip("1.0.0.60")), Some(1));
+        assert_eq!(index.lookup(ip("1.0.0.120")), Some(2));
+        assert_eq!(index.lookup(ip("2001:db8:1::1")), Some(3));
+        assert_eq!(index.lookup(ip("2001:db8:abcd:1::1")), Some(4));
+        assert_eq!(index.lookup(ip("fe80::1")), Some(5));
+        assert_eq!(
+            index.lookup(ip("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff")),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn rewrite_only_matches_range_key_equality() {
+        register_range_table("rw_geo", "ip", IpRangeIndex::default());
+        let g = RANGE_TABLES.read().unwrap().current.generation;
+        let expect =
+            format!("select city from rw_geo where rowid = ip_range_rowid('rw_geo', :src_ip, {g})");
+        assert_eq!(
+            rewrite_range_sql("select city from rw_geo where ip = :src_ip", g),
+            Some(expect.clone())
+        );
+        assert_eq!(
+            rewrite_range_sql("select city from rw_geo where (ip = :src_ip)", g),
+            Some(expect)
+        );
+        // 关键字与表名、键名不区分大小写，参数与字面量原样保留
+        assert_eq!(
+            rewrite_range_sql("SELECT City FROM RW_Geo WHERE IP = 'AbC'", g),
+            Some(format!(
+                "SELECT City FROM RW_Geo where rowid = ip_range_rowid('rw_geo', 'AbC', {g})"
+            ))
+        );
+        assert!(rewrite_range_sql("select city from rw_geo where city = :c", g).is_none());
+        assert!(rewrite_range_sql("select city from rw_geo where ip = :a and x = 1", g).is_none());
+        assert!(rewrite_range_sql("select city from other where ip = :src_ip", g).is_none());
+    }
+
+    #[test]
+    fn staged_index_switches_with_generation() {
+        let old = IpRangeIndex::build(vec![cidr("10.0.0.0/8", 1)]);
+        register_range_table("stage_geo", "ip", old);
+        let before = RANGE_TABLES.read().unwrap().current.generation;
+        {
+            let _staging = StagingScope::enter();
+            register_range_table(
+                "stage_geo",
+                "ip",
+                IpRangeIndex::build(vec![cidr("10.0.0.0/8", 7)]),
+            );
+            // 切换前仍是旧索引
+            assert_eq!(range_rowid("stage_geo", "10.1.1.1", before), Some(1));
+            publish_staged(before + 1000);
+        }
+        // 切换前改写的查询带旧代数，按上一代索引定位
+        assert_eq!(range_rowid("stage_geo", "10.1.1.1", before), Some(1));
+        assert_eq!(range_rowid("stage_geo", "10.1.1.1", before + 1000), Some(7));
+        assert_eq!(range_rowid("STAGE_GEO", "10.1.1.1", before + 1000), Some(7));
+
+        // 未发布的暂存索引随作用域丢弃
+        {
+            let _staging = StagingScope::enter();
+            register_range_table(
+                "stage_geo",
+                "ip",
+                IpRangeIndex::build(vec![cidr("10.0.0.0/8", 9)]),
+            );
+        }
+        assert_eq!(range_rowid("stage_geo", "10.1.1.1", before + 1000), Some(7));
+    }
+
+    #[test]
+    fn memdb_cidr_table_lookup() -> AnyResult<()> {
+        let db = MemDB::instance();
+        db.table_create("CREATE TABLE geo_cidr (network TEXT NOT NULL, city TEXT NOT NULL)")?;
+        for (net, city) in [
+            ("10.0.0.0/8", "intranet"),
+            ("10.20.0.0/16", "beijing"),
+            ("2001:db8::/32", "v6lab"),
+        ] {
+            db.execute(&format!(
+                "INSERT INTO geo_cidr (network, city) VALUES ('{}', '{}')",
+                net, city
+            ))?;
+        }
+        let cnt = db.index_ip_ranges("geo_cidr", &IpRangeSpec::with_cidr("ip", "network"))?;
+        assert_eq!(cnt, 3);
+
+        let generation = crate::facade::provider_generation();
+        let sql =
+            rewrite_range_sql("select city from geo_cidr where ip = :ip", generation).unwrap();
+        for (addr, city) in [
+            ("10.20.1.1", "chars(beijing)"),
+            ("10.30.1.1", "chars(intranet)"),
+            ("2001:db8::8", "chars(v6lab)"),
+        ] {
+            let row = db.query_row_params(&sql, rusqlite::named_params! {":ip": addr})?;
+            assert_eq!(row[0].to_string(), city, "{}", addr);
+        }
+        let row = db.query_row_params(&sql, rusqlite::named_params! {":ip": "8.8.8.8"})?;
+        assert!(row.is_empty());
+        Ok(())
+    }
+}
@@ -11,7 +11,8 @@ use wp_model_core::model::DataField;
 
 thread_local! {
     // clippy: use const init for thread_local value
-    static TLS_DB: RefCell<Option<Connection>> = const { RefCell::new(None) };
+    // 记录副本来源的权威库路径：热重载切换到新权威库后，各线程在下一次查询时重新复制
+    static TLS_DB: RefCell<Option<(String, Connection)>> = const { RefCell::new(None) };
 }
 
 /// Thread-cloned read-only in-memory DB built from an authority file DB via SQLite backup API.
@@ -34,8 +35,12 @@ impl ThreadClonedMDB {
     ) -> KnowledgeResult<T> {
         let path = self.authority_path.clone();
         TLS_DB.with(|cell| {
-            // make sure a thread-local in-memory db exists
-            if cell.borrow().is_none() {
+            // make sure a thread-local in-memory db cloned from the current authority exists
+            let stale = cell
+                .borrow()
+                .as_ref()
+                .is_none_or(|(src_path, _)| *src_path != path);
+            if stale {
                 // source: authority file; dest: in-memory
                 let src = Connection::open_with_flags(
                     &path,
@@ -54,11 +59,11 @@ impl ThreadClonedMDB {
                 }
                 // 为查询连接注册内置 UDF（只读场景也可用在 SQL/OML 查询中）
                 let _ = crate::sqlite_ext::register_builtin(&dst);
-                *cell.borrow_mut() = Some(dst);
+                *cell.borrow_mut() = Some((path, dst));
             }
             // safe to unwrap since ensured above
             let conn = cell.borrow();
-            f(conn.as_ref().unwrap())
+            f(&conn.as_ref().unwrap().1)
         })
     }
 }
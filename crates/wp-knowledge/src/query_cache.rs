@@ -0,0 +1,318 @@
+//! 查询结果缓存：按线程持有（无锁），带 TTL 与容量上限（LRU 淘汰）。
+//!
+//! - 通过 [`configure_query_cache`] 或 knowdb.toml 的 `[cache]` 段开启；默认关闭；
+//! - 每个线程在首次查询时按当前配置创建自己的 [`QueryResultCache`]；
+//! - [`flush_query_cache`] 递增全局代数，各线程在下一次查询时整体清空（知识库重载时调用）。
+
+use std::cell::RefCell;
+use std::num::NonZeroUsize;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+use serde::Deserialize;
+use wp_data_model::cache::Timed;
+use wp_error::KnowledgeResult;
+use wp_log::warn_kdb;
+use wp_model_core::model::DataField;
+
+use crate::mem::RowData;
+use crate::mem::memdb::MemDB;
+use crate::mem::thread_clone::ThreadClonedMDB;
+
+/// 一次查询：SQL 文本与决定结果的参数值
+#[derive(Debug, Clone, Copy)]
+pub struct SqlQuery<'a> {
+    pub sql: &'a str,
+    pub params: &'a [DataField],
+}
+
+impl<'a> SqlQuery<'a> {
+    pub fn new(sql: &'a str, params: &'a [DataField]) -> Self {
+        Self { sql, params }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    /// SQL 与各参数（含类型）按分隔符拼接
+    pub fn of(query: &SqlQuery) -> Self {
+        let mut key = String::from(query.sql);
+        for p in query.params {
+            key.push('\u{1f}');
+            key.push_str(p.get_name());
+            key.push('=');
+            key.push_str(&p.to_string());
+        }
+        Self(key)
+    }
+}
+
+/// 可缓存查询结果的后端；后端语义相同的查询必须返回相同的键
+pub trait Cacheable {
+    fn cache_key(query: &SqlQuery) -> CacheKey {
+        CacheKey::of(query)
+    }
+}
+
+impl Cacheable for MemDB {}
+impl Cacheable for ThreadClonedMDB {}
+
+/// knowdb.toml `[cache]` 段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct QueryCacheSpec {
+    #[serde(default = "default_ttl_ms")]
+    pub ttl_ms: u64,
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for QueryCacheSpec {
+    fn default() -> Self {
+        Self {
+            ttl_ms: default_ttl_ms(),
+            max_entries: default_max_entries(),
+        }
+    }
+}
+
+impl QueryCacheSpec {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_millis(self.ttl_ms)
+    }
+}
+
+const fn default_ttl_ms() -> u64 {
+    60_000
+}
+const fn default_max_entries() -> usize {
+    10_000
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// 因容量或过期而移除的条目数
+    pub evictions: u64,
+}
+
+pub struct QueryResultCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: LruCache<CacheKey, Timed<RowData>>,
+    stats: QueryCacheStats,
+}
+
+impl QueryResultCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        let max_entries = max_entries.max(1);
+        Self {
+            ttl,
+            max_entries,
+            entries: LruCache::new(NonZeroUsize::new(max_entries).unwrap()),
+            stats: QueryCacheStats::default(),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> QueryCacheStats {
+        self.stats
+    }
+
+    pub fn get(&mut self, key: &CacheKey, now: Instant) -> Option<RowData> {
+        let fresh = match self.entries.get(key) {
+            Some(entry) if entry.is_fresh(Some(self.ttl), now) => Some(entry.value().clone()),
+            Some(_) => None,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+        match fresh {
+            Some(rows) => {
+                self.stats.hits += 1;
+                Some(rows)
+            }
+            None => {
+                self.entries.pop(key);
+                self.stats.evictions += 1;
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn put(&mut self, key: CacheKey, rows: RowData, now: Instant) {
+        if let Some((old, _)) = self.entries.push(key.clone(), Timed::new(rows, now))
+            && old != key
+        {
+            self.stats.evictions += 1;
+        }
+    }
+
+    /// 清空条目，统计保留
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+static QUERY_CACHE_SPEC: RwLock<Option<QueryCacheSpec>> = RwLock::new(None);
+static QUERY_CACHE_GEN: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static TLS_CACHE: RefCell<Option<(u64, QueryResultCache)>> = const { RefCell::new(None) };
+}
+
+/// 设置（`Some`）或关闭（`None`）查询结果缓存；已有的线程缓存在下一次查询时按新配置重建
+pub fn configure_query_cache(spec: Option<QueryCacheSpec>) {
+    *QUERY_CACHE_SPEC.write().unwrap_or_else(|e| e.into_inner()) = spec;
+    flush_query_cache();
+}
+
+pub fn query_cache_spec() -> Option<QueryCacheSpec> {
+    *QUERY_CACHE_SPEC.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 使所有线程的缓存失效（知识库重载后调用）
+pub fn flush_query_cache() {
+    QUERY_CACHE_GEN.fetch_add(1, Ordering::SeqCst);
+}
+
+/// 当前线程缓存的统计；缓存未开启或本线程尚未查询时为 None
+pub fn query_cache_stats() -> Option<QueryCacheStats> {
+    TLS_CACHE.with(|cell| cell.borrow().as_ref().map(|(_, c)| c.stats()))
+}
+
+/// 经当前线程缓存执行查询；缓存未开启时直接查询。查询失败返回空行且不缓存。
+pub(crate) fn cached_query(
+    key: CacheKey,
+    query_fn: impl FnOnce() -> KnowledgeResult<RowData>,
+) -> RowData {
+    let now = Instant::now();
+    if let Some(Some(rows)) = with_thread_cache(|qc| qc.get(&key, now)) {
+        return rows;
+    }
+    match query_fn() {
+        Ok(rows) => {
+            with_thread_cache(|qc| qc.put(key, rows.clone(), now));
+            rows
+        }
+        Err(e) => {
+            warn_kdb!("[kdb] query error: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 在当前线程的缓存上执行 `f`；缓存未开启时返回 None
+fn with_thread_cache<T>(f: impl FnOnce(&mut QueryResultCache) -> T) -> Option<T> {
+    let spec = query_cache_spec()?;
+    let generation = QUERY_CACHE_GEN.load(Ordering::SeqCst);
+    TLS_CACHE.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        let stale = !matches!(slot.as_ref(), Some((g, _)) if *g == generation);
+        if stale {
+            // 配置未变时仅清空条目，保留统计
+            match slot.as_mut() {
+                Some((g, cache))
+                    if cache.ttl() == spec.ttl()
+                        && cache.max_entries() == spec.max_entries.max(1) =>
+                {
+                    cache.clear();
+                    *g = generation;
+                }
+                _ => {
+                    *slot = Some((
+                        generation,
+                        QueryResultCache::new(spec.ttl(), spec.max_entries),
+                    ))
+                }
+            }
+        }
+        slot.as_mut().map(|(_, cache)| f(cache))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(v: i64) -> CacheKey {
+        CacheKey::of(&SqlQuery::new(
+            "select name from t where id=:id",
+            &[DataField::from_digit(":id", v)],
+        ))
+    }
+
+    fn rows(s: &str) -> RowData {
+        vec![DataField::from_chars("name", s)]
+    }
+
+    #[test]
+    fn hits_expire_after_ttl() {
+        let mut cache = QueryResultCache::new(Duration::from_secs(10), 8);
+        let t0 = Instant::now();
+        assert!(cache.get(&key(1), t0).is_none());
+        cache.put(key(1), rows("a"), t0);
+        assert_eq!(
+            cache.get(&key(1), t0 + Duration::from_secs(5)),
+            Some(rows("a"))
+        );
+        assert!(cache.get(&key(1), t0 + Duration::from_secs(10)).is_none());
+        assert!(cache.is_empty());
+        assert_eq!(
+            cache.stats(),
+            QueryCacheStats {
+                hits: 1,
+                misses: 2,
+                evictions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn lru_evicts_beyond_max_entries() {
+        let mut cache = QueryResultCache::new(Duration::from_secs(60), 2);
+        let now = Instant::now();
+        cache.put(key(1), rows("a"), now);
+        cache.put(key(2), rows("b"), now);
+        assert!(cache.get(&key(1), now).is_some());
+        // 覆盖同一键不计淘汰
+        cache.put(key(2), rows("b2"), now);
+        cache.put(key(3), rows("c"), now);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(1), now).is_none());
+        assert_eq!(cache.get(&key(2), now), Some(rows("b2")));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn keys_differ_by_param_type_and_value() {
+        let sql = "select x from t where k=:k";
+        let a = CacheKey::of(&SqlQuery::new(sql, &[DataField::from_digit(":k", 1)]));
+        let b = CacheKey::of(&SqlQuery::new(sql, &[DataField::from_chars(":k", "1")]));
+        let c =
+            <MemDB as Cacheable>::cache_key(&SqlQuery::new(sql, &[DataField::from_digit(":k", 1)]));
+        assert_ne!(a, b);
+        assert_eq!(a, c);
+    }
+}
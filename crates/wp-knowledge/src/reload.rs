@@ -0,0 +1,236 @@
+//! 知识库热重载：轮询各表源文件的修改时间，有变更时在权威库的新副本中重建受影响的表，
+//! 随后原子替换全局 provider 并递增代数（查询缓存随之失效）。
+//!
+//! 单表重建失败时该表回填旧数据，记录表名与错误，其余表照常生效；
+//! 区间表索引在重建期间暂存，与 provider 同步切换；
+//! 上一代权威库文件保留，供切换瞬间仍在复制旧库的线程读取，更早的副本随即删除。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use orion_error::{ErrorOwe, ToStructError, UvsConfFrom};
+use orion_variate::EnvDict;
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+use wp_error::{KnowledgeReason, KnowledgeResult};
+use wp_log::{info_kdb, warn_kdb};
+
+use crate::facade::{read_only_uri, replace_provider};
+use crate::loader::{
+    index_range_table, load_one_table, open_authority, parse_knowdb_conf, table_source_files,
+};
+use crate::mem::memdb::MemDB;
+use crate::mem::range_index::StagingScope;
+use crate::mem::thread_clone::ThreadClonedMDB;
+
+type Stamps = Vec<Option<SystemTime>>;
+
+/// 一次轮询的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReloadReport {
+    /// 已重建并生效的表
+    pub reloaded: Vec<String>,
+    /// 重建失败、保留旧数据的表及错误信息
+    pub failed: Vec<(String, String)>,
+}
+
+impl ReloadReport {
+    pub fn is_empty(&self) -> bool {
+        self.reloaded.is_empty() && self.failed.is_empty()
+    }
+}
+
+pub struct KnowdbReloader {
+    root: PathBuf,
+    conf: PathBuf,
+    dict: EnvDict,
+    /// 初始权威库文件，各代副本以其为前缀命名
+    base_file: PathBuf,
+    /// 当前生效的权威库文件
+    current: PathBuf,
+    previous: Option<PathBuf>,
+    generation: u64,
+    interval: Option<Duration>,
+    stamps: HashMap<String, Stamps>,
+}
+
+impl KnowdbReloader {
+    /// 以已构建的权威库（`file:` URI）为起点，记录各表源文件的当前修改时间
+    pub fn new(
+        root: &Path,
+        conf: &Path,
+        authority_uri: &str,
+        dict: &EnvDict,
+    ) -> KnowledgeResult<Self> {
+        let Some(base_file) = authority_file(authority_uri) else {
+            return KnowledgeReason::from_conf("knowdb reload requires a file: authority uri")
+                .err_result();
+        };
+        let (kconf, _, base_dir) = parse_knowdb_conf(root, conf, dict)?;
+        let stamps = kconf
+            .tables
+            .iter()
+            .filter(|t| t.enabled)
+            .map(|t| (t.name.clone(), file_stamps(&base_dir, t)))
+            .collect();
+        Ok(Self {
+            root: root.to_path_buf(),
+            conf: conf.to_path_buf(),
+            dict: dict.clone(),
+            current: base_file.clone(),
+            base_file,
+            previous: None,
+            generation: 0,
+            interval: kconf
+                .reload_interval_secs
+                .filter(|s| *s > 0)
+                .map(Duration::from_secs),
+            stamps,
+        })
+    }
+
+    /// 配置了 `reload_interval_secs` 时构建热重载器；未开启热重载时返回 `Ok(None)`
+    pub fn when_enabled(
+        root: &Path,
+        conf: &Path,
+        authority_uri: &str,
+        dict: &EnvDict,
+    ) -> KnowledgeResult<Option<Self>> {
+        let (kconf, _, _) = parse_knowdb_conf(root, conf, dict)?;
+        if !kconf.reload_interval_secs.is_some_and(|s| s > 0) {
+            return Ok(None);
+        }
+        Self::new(root, conf, authority_uri, dict).map(Some)
+    }
+
+    /// 配置的轮询间隔（`reload_interval_secs`）；未配置时为 None
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// 检查表文件变更并重建；无变更时返回空报告
+    pub fn poll(&mut self) -> KnowledgeResult<ReloadReport> {
+        let (kconf, _, base_dir) = parse_knowdb_conf(&self.root, &self.conf, &self.dict)?;
+        let mut changed = Vec::new();
+        for t in kconf.tables.iter().filter(|t| t.enabled) {
+            let now = file_stamps(&base_dir, t);
+            if self.stamps.get(&t.name) != Some(&now) {
+                changed.push((t, now));
+            }
+        }
+        let mut report = ReloadReport::default();
+        if changed.is_empty() {
+            return Ok(report);
+        }
+
+        // 新建的区间索引暂存至 provider 切换时一并生效，切换前的查询仍按旧索引定位旧库
+        let _staging = StagingScope::enter();
+        let next = generation_file(&self.base_file, self.generation + 1);
+        let db = self.clone_current(&next)?;
+        for (t, _) in &changed {
+            match load_one_table(&db, &base_dir, t, &kconf.csv, &kconf.default) {
+                Ok(()) => report.reloaded.push(t.name.clone()),
+                Err(e) => {
+                    warn_kdb!(
+                        "knowdb reload table {} failed, keep old data: {}",
+                        t.name,
+                        e
+                    );
+                    restore_table(&db, &self.current, &t.name)?;
+                    // 回填后 rowid 可能变化，区间表据旧数据重建索引
+                    index_range_table(&db, t)?;
+                    report.failed.push((t.name.clone(), e.to_string()));
+                }
+            }
+        }
+        drop(db);
+        // 失败的表同样记录新时间戳：待文件再次修改后重试，避免每轮重复报错
+        for (t, now) in changed {
+            self.stamps.insert(t.name.clone(), now);
+        }
+        if report.reloaded.is_empty() {
+            let _ = std::fs::remove_file(&next);
+            return Ok(report);
+        }
+
+        let uri = format!("file:{}", next.display());
+        replace_provider(Arc::new(ThreadClonedMDB::from_authority(&read_only_uri(
+            &uri,
+        ))));
+        self.generation += 1;
+        if let Some(stale) = self
+            .previous
+            .replace(std::mem::replace(&mut self.current, next))
+        {
+            let _ = std::fs::remove_file(stale);
+        }
+        info_kdb!(
+            "knowdb reloaded tables {:?} (generation {})",
+            report.reloaded,
+            self.generation
+        );
+        Ok(report)
+    }
+
+    /// 将当前权威库完整复制到 `next`，返回其可写连接
+    fn clone_current(&self, next: &Path) -> KnowledgeResult<MemDB> {
+        let _ = std::fs::remove_file(next);
+        let src = Connection::open_with_flags(&self.current, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .owe_res()?;
+        {
+            let mut dst = Connection::open(next).owe_res()?;
+            let bk = Backup::new(&src, &mut dst).owe_res()?;
+            bk.run_to_completion(50, Duration::from_millis(0), None)
+                .owe_res()?;
+        }
+        open_authority(&format!("file:{}?mode=rwc&uri=true", next.display()))
+    }
+}
+
+/// 用上一代权威库中的数据回填重建失败的表；新增表在旧库中不存在时直接移除
+fn restore_table(db: &MemDB, previous: &Path, table: &str) -> KnowledgeResult<()> {
+    let prev = previous.display().to_string();
+    db.with_conn(|conn| {
+        conn.execute("ATTACH DATABASE ?1 AS prev", [&prev])?;
+        let res = (|| {
+            let existed: i64 = conn.query_row(
+                "SELECT count(*) FROM prev.sqlite_master WHERE type = 'table' AND name = ?1",
+                [table],
+                |r| r.get(0),
+            )?;
+            if existed > 0 {
+                conn.execute_batch(&format!(
+                    "DELETE FROM main.{t}; INSERT INTO main.{t} SELECT * FROM prev.{t};",
+                    t = table
+                ))
+            } else {
+                conn.execute_batch(&format!("DROP TABLE IF EXISTS main.{}", table))
+            }
+        })();
+        conn.execute_batch("DETACH DATABASE prev")?;
+        res?;
+        Ok::<(), anyhow::Error>(())
+    })
+    .owe_res()
+}
+
+fn file_stamps(base_dir: &Path, t: &crate::loader::TableSpec) -> Stamps {
+    table_source_files(base_dir, t)
+        .iter()
+        .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+fn authority_file(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file:")?;
+    let path = rest.split('?').next().unwrap_or(rest);
+    (!path.is_empty() && !path.starts_with(':')).then(|| PathBuf::from(path))
+}
+
+fn generation_file(base: &Path, generation: u64) -> PathBuf {
+    let mut name = base.as_os_str().to_os_string();
+    name.push(format!(".g{}", generation));
+    PathBuf::from(name)
+}
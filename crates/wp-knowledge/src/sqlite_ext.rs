@@ -117,6 +117,26 @@ pub fn register_builtin(conn: &rusqlite::Connection) -> SqlResult<()> {
         },
     )?;
 
+    // ip_range_rowid(table, ip[, generation]) -> integer|null：按区间表索引定位行
+    // （非确定性：随热重载变化；门面改写时带上 provider 代数，缺省按当前代）
+    for n_arg in [2, 3] {
+        conn.create_scalar_function(
+            "ip_range_rowid",
+            n_arg,
+            FunctionFlags::SQLITE_UTF8,
+            |ctx: &Context| {
+                let table: String = ctx.get(0)?;
+                let ip: Option<String> = ctx.get(1).ok();
+                let generation = if ctx.len() > 2 {
+                    ctx.get::<i64>(2)? as u64
+                } else {
+                    crate::facade::provider_generation()
+                };
+                Ok(ip.and_then(|ip| crate::mem::range_index::range_rowid(&table, &ip, generation)))
+            },
+        )?;
+    }
+
     // trim_quotes(text) -> text：去除两端成对引号（支持 ' 或 "），容忍前后空白
     conn.create_scalar_function(
         "trim_quotes",
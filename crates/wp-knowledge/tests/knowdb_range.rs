@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use orion_variate::EnvDict;
+use wp_config::test_support::ForTest;
+use wp_knowledge::facade as kdb;
+
+const KNOWDB_TOML: &str = r#"
+version = 2
+base_dir = "."
+
+[csv]
+has_header = true
+
+[[tables]]
+name = "geoip"
+columns.by_header = ["network", "city"]
+key_type = "iprange"
+[tables.range]
+key = "ip"
+cidr = "network"
+
+[[tables]]
+name = "asn"
+columns.by_header = ["start_ip", "end_ip", "org"]
+key_type = "iprange"
+[tables.range]
+start = "start_ip"
+end = "end_ip"
+"#;
+
+fn write_table(root: &Path, name: &str, create: &str, insert: &str, csv: &str) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("create.sql"), create).unwrap();
+    fs::write(dir.join("insert.sql"), insert).unwrap();
+    fs::write(dir.join("data.csv"), csv).unwrap();
+}
+
+fn lookup(sql: &str, ip: &str) -> String {
+    let rows = kdb::query_named(sql, &[(":ip", &ip as &dyn rusqlite::ToSql)]).expect("lookup");
+    rows.first().map(|f| f.to_string()).unwrap_or_default()
+}
+
+#[test]
+fn cidr_and_bounds_tables_resolve_by_containment() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".run/range_case");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("knowdb.toml"), KNOWDB_TOML).unwrap();
+    write_table(
+        &root,
+        "geoip",
+        "CREATE TABLE IF NOT EXISTS {table} (network TEXT NOT NULL, city TEXT NOT NULL);",
+        "INSERT INTO {table} (network, city) VALUES (?1, ?2);",
+        "network,city\n10.0.0.0/8,intranet\n10.20.0.0/16,beijing\n10.20.30.0/24,chaoyang\n2001:db8::/32,v6lab\n2001:db8:ff::/48,v6edge\n",
+    );
+    write_table(
+        &root,
+        "asn",
+        "CREATE TABLE IF NOT EXISTS {table} (start_ip TEXT, end_ip TEXT, org TEXT);",
+        "INSERT INTO {table} (start_ip, end_ip, org) VALUES (?1, ?2, ?3);",
+        "start_ip,end_ip,org\n1.0.0.0,1.0.0.255,apnic\n8.8.8.0,8.8.8.255,google\n",
+    );
+
+    let authority = root.join("authority.sqlite");
+    let uri = format!("file:{}?mode=rwc&uri=true", authority.display());
+    kdb::init_thread_cloned_from_knowdb(
+        &root,
+        &root.join("knowdb.toml"),
+        &uri,
+        &EnvDict::test_default(),
+    )
+    .expect("init knowdb");
+
+    let geo = "select city from geoip where ip = :ip";
+    assert_eq!(lookup(geo, "10.1.1.1"), "chars(intranet)");
+    assert_eq!(lookup(geo, "10.20.1.1"), "chars(beijing)");
+    assert_eq!(lookup(geo, "10.20.30.40"), "chars(chaoyang)");
+    assert_eq!(lookup(geo, "2001:db8::1"), "chars(v6lab)");
+    assert_eq!(lookup(geo, "2001:db8:ff::1"), "chars(v6edge)");
+    assert_eq!(lookup(geo, "172.16.0.1"), "");
+
+    let asn = "select org from asn where ip = :ip";
+    assert_eq!(lookup(asn, "8.8.8.8"), "chars(google)");
+    assert_eq!(lookup(asn, "1.0.0.7"), "chars(apnic)");
+    assert_eq!(lookup(asn, "9.9.9.9"), "");
+
+    // 非区间键的条件原样执行
+    let rows = kdb::query_named(
+        "select network from geoip where city = :c",
+        &[(":c", &"beijing" as &dyn rusqlite::ToSql)],
+    )
+    .expect("exact query");
+    assert_eq!(rows[0].to_string(), "chars(10.20.0.0/16)");
+}
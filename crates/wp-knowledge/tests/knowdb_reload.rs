@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use orion_variate::EnvDict;
+use wp_config::test_support::ForTest;
+use wp_knowledge::facade as kdb;
+use wp_knowledge::reload::KnowdbReloader;
+
+const KNOWDB_TOML: &str = r#"
+version = 2
+base_dir = "."
+reload_interval_secs = 5
+
+[csv]
+has_header = true
+
+[[tables]]
+name = "color"
+columns.by_header = ["code", "label"]
+
+[[tables]]
+name = "level"
+columns.by_header = ["code", "label"]
+"#;
+
+const CREATE_SQL: &str =
+    "CREATE TABLE IF NOT EXISTS {table} (code INTEGER PRIMARY KEY, label TEXT NOT NULL);";
+const INSERT_SQL: &str = "INSERT INTO {table} (code, label) VALUES (?1, ?2);";
+
+fn write_table(root: &Path, name: &str, csv: &str) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("create.sql"), CREATE_SQL).unwrap();
+    fs::write(dir.join("insert.sql"), INSERT_SQL).unwrap();
+    fs::write(dir.join("data.csv"), csv).unwrap();
+}
+
+/// 改写数据文件并推后修改时间，避免同一秒内写入导致 mtime 不变
+fn rewrite_csv(root: &Path, name: &str, csv: &str, bump: u64) {
+    let path = root.join(name).join("data.csv");
+    fs::write(&path, csv).unwrap();
+    let file = fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(bump))
+        .unwrap();
+}
+
+fn label_of(table: &str, code: i64) -> String {
+    let sql = format!("SELECT label FROM {} WHERE code=:code", table);
+    let rows =
+        kdb::query_named(&sql, &[(":code", &code as &dyn rusqlite::ToSql)]).expect("query label");
+    rows[0].to_string()
+}
+
+#[test]
+fn reload_changed_table_and_keep_old_data_on_failure() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".run/reload_case");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("knowdb.toml"), KNOWDB_TOML).unwrap();
+    write_table(&root, "color", "code,label\n1,red\n2,green\n");
+    write_table(&root, "level", "code,label\n1,low\n");
+
+    let conf = root.join("knowdb.toml");
+    let authority = root.join("authority.sqlite");
+    let uri = format!("file:{}?mode=rwc&uri=true", authority.display());
+    let dict = EnvDict::test_default();
+    kdb::init_thread_cloned_from_knowdb(&root, &conf, &uri, &dict).expect("init knowdb");
+    let mut reloader = KnowdbReloader::new(&root, &conf, &uri, &dict).expect("reloader");
+    assert_eq!(reloader.interval(), Some(Duration::from_secs(5)));
+    assert!(reloader.poll().expect("poll").is_empty());
+    assert_eq!(label_of("color", 1), "chars(red)");
+
+    // 1) 修改 color：仅该表重建，查询看到新数据，代数递增
+    let gen_before = kdb::provider_generation();
+    rewrite_csv(&root, "color", "code,label\n1,crimson\n3,blue\n", 10);
+    let report = reloader.poll().expect("poll");
+    assert_eq!(report.reloaded, vec!["color".to_string()]);
+    assert!(report.failed.is_empty());
+    assert_eq!(kdb::provider_generation(), gen_before + 1);
+    assert_eq!(label_of("color", 1), "chars(crimson)");
+    assert_eq!(label_of("color", 3), "chars(blue)");
+    assert_eq!(label_of("level", 1), "chars(low)");
+
+    // 2) level 表头损坏、color 正常更新：level 保留旧数据并报告失败，color 照常生效
+    rewrite_csv(&root, "level", "bad_header\nx\n", 20);
+    rewrite_csv(&root, "color", "code,label\n1,scarlet\n", 20);
+    let report = reloader.poll().expect("poll");
+    assert_eq!(report.reloaded, vec!["color".to_string()]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "level");
+    assert_eq!(label_of("color", 1), "chars(scarlet)");
+    assert_eq!(label_of("level", 1), "chars(low)");
+
+    // 3) 仅有失败表时不切换 provider
+    let gen_before = kdb::provider_generation();
+    rewrite_csv(&root, "level", "still_bad\n", 30);
+    let report = reloader.poll().expect("poll");
+    assert!(report.reloaded.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(kdb::provider_generation(), gen_before);
+    assert_eq!(label_of("level", 1), "chars(low)");
+}
+
+#[test]
+fn when_enabled_skips_reloader_without_interval() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".run/reload_off_case");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    let conf = root.join("knowdb.toml");
+    fs::write(&conf, KNOWDB_TOML.replace("reload_interval_secs = 5\n", "")).unwrap();
+    write_table(&root, "color", "code,label\n1,red\n");
+    write_table(&root, "level", "code,label\n1,low\n");
+    let dict = EnvDict::test_default();
+
+    // 未开启热重载：即便权威库不是 file: URI 也不报错
+    let off = KnowdbReloader::when_enabled(&root, &conf, "memory", &dict).expect("reload off");
+    assert!(off.is_none());
+
+    fs::write(&conf, KNOWDB_TOML).unwrap();
+    assert!(KnowdbReloader::when_enabled(&root, &conf, "memory", &dict).is_err());
+}
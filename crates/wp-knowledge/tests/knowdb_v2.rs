@@ -80,3 +80,36 @@ fn query_zone_table_segments() {
     assert_eq!(count.len(), 1);
     assert_eq!(count[0].to_string(), "digit(4)");
 }
+
+#[test]
+fn cache_query_uses_thread_result_cache_with_ttl() {
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::DataField;
+
+    let _root = ensure_packaged_knowdb_initialized();
+    kdb::configure_query_cache(Some(kdb::QueryCacheSpec {
+        ttl_ms: 60_000,
+        max_entries: 16,
+    }));
+
+    let sql = "SELECT pinying FROM example WHERE name=:name";
+    let name = "令狐冲";
+    let c_params = [DataField::from_chars(":name", name)];
+    let named = [(":name", &name as &dyn rusqlite::ToSql)];
+    let mut field_cache = FieldQueryCache::default();
+    for _ in 0..3 {
+        let rows = kdb::cache_query(sql, &c_params, &named, &mut field_cache);
+        assert_eq!(rows[0].to_string(), "chars(linghuchong)");
+    }
+    let stats = kdb::query_cache_stats().expect("thread cache");
+    assert_eq!((stats.hits, stats.misses), (2, 1));
+
+    // 重载后整体失效，下一次查询重新访问 provider
+    kdb::flush_query_cache();
+    let rows = kdb::cache_query(sql, &c_params, &named, &mut field_cache);
+    assert_eq!(rows.len(), 1);
+    let stats = kdb::query_cache_stats().expect("thread cache");
+    assert_eq!((stats.hits, stats.misses), (2, 2));
+
+    kdb::configure_query_cache(None);
+}
@@ -130,6 +130,7 @@ mod tests {
         let ann = AnnFun {
             tags: BTreeMap::from([("tag_1".into(), "x".into())]),
             copy_raw: None,
+            dup: None,
         };
         let tag = AnnotationType::convert(&Some(ann));
         let mut data = DataRecord::test_value();
@@ -149,6 +150,7 @@ mod tests {
         let ann = AnnFun {
             tags: Default::default(),
             copy_raw: Some(("name".into(), "raw".into())),
+            dup: None,
         };
         let tag = AnnotationType::convert(&Some(ann));
         let mut data = DataRecord::test_value();
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use crate::ast::WplPackage;
 
-use crate::parser::error::{WplCodeError, WplCodeReason, WplCodeResult, error_detail};
+use crate::parser::error::{WPLCodeErrorTrait, WplCodeError, WplCodeReason, WplCodeResult};
 use crate::parser::wpl_pkg::wpl_package;
 use crate::parser::wpl_rule::wpl_rule;
 use crate::winnow::Parser;
@@ -40,14 +40,10 @@ impl TryFrom<&str> for WplCode {
 
 impl WplCode {
     pub fn build(path: PathBuf, code: &str) -> WplCodeResult<Self> {
-        let mut in_code = code;
-        let pure_code = CommentParser::ignore_comment(&mut in_code).map_err(|e| {
-            WplCodeError::from(WplCodeReason::Syntax(format!("comment proc error {} ", e)))
-        })?;
-
+        // 注释替换为空白而非删除，保证错误位置与原文件行列一致
         Ok(Self {
             path,
-            code: pure_code.to_string(),
+            code: CommentParser::blank_comment(code),
         })
     }
     pub fn get_code(&self) -> &String {
@@ -56,13 +52,13 @@ impl WplCode {
     pub fn parse_pkg(&self) -> WplCodeResult<WplPackage> {
         let package = wpl_package
             .parse(self.code.as_str())
-            .map_err(|err| WplCodeError::from(WplCodeReason::Syntax(error_detail(err))))?;
+            .map_err(|err| WplCodeError::from_parse_err(err, &self.path_str()))?;
         Ok(package)
     }
     pub fn parse_rule(&self) -> WplCodeResult<WplPackage> {
         let rule = wpl_rule
             .parse(self.code.as_str())
-            .map_err(|err| WplCodeError::from(WplCodeReason::Syntax(error_detail(err))))?;
+            .map_err(|err| WplCodeError::from_parse_err(err, &self.path_str()))?;
         let mut target = WplPackage::default();
         target.rules.push_back(rule);
         Ok(target)
@@ -71,7 +67,10 @@ impl WplCode {
         WplCode::try_from((PathBuf::new(), ""))
     }
     pub fn is_empty(&self) -> bool {
-        self.code.is_empty()
+        self.code.trim().is_empty()
+    }
+    fn path_str(&self) -> String {
+        self.path.display().to_string()
     }
     pub fn load<P: AsRef<Path> + Clone>(wpl_file: P) -> WplCodeResult<Self> {
         let mut buffer = Vec::with_capacity(10240);
@@ -100,3 +99,35 @@ impl WplCode {
         WplCodeReason::from_not_found("miss wpl file").err_result()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pkg_error_points_at_offending_char() {
+        // 注释替换为空白，不影响其后的行列号
+        let code = "package /demo/ {\n    // 注释\n    rule nginx {\n        (ip:sip, digit:port)\n    }\n    oops\n}\n";
+        let wpl = WplCode::try_from((PathBuf::from("rules/demo.wpl"), code)).expect("build");
+        let err = wpl.parse_pkg().expect_err("stray token");
+        let text = err.to_string();
+        assert!(text.contains("[6:5] error:"), "line:col in: {}", text);
+        assert!(
+            text.contains("rules/demo.wpl:6:5"),
+            "path location in: {}",
+            text
+        );
+        assert_eq!(err.position().as_deref(), Some("6:5"));
+
+        let lines: Vec<&str> = text.lines().collect();
+        let src = lines
+            .iter()
+            .position(|l| l.starts_with("6 | "))
+            .unwrap_or_else(|| panic!("source line in: {}", text));
+        assert_eq!(lines[src], "6 |     oops");
+        let caret = lines[src + 1];
+        assert_eq!(caret, "  |     ^");
+        let col = caret.find('^').expect("caret");
+        assert_eq!(&lines[src][col..], "oops");
+    }
+}
@@ -15,6 +15,19 @@ impl DebugFormat for WplFieldSet {
     where
         W: ?Sized + Write + DepIndent,
     {
+        if let Some(rest) = self.rest() {
+            self.write_open_parenthesis(w)?;
+            for (k, conf) in self.conf_items().exact_iter() {
+                FieldConfSetWrap(k.clone(), conf.clone()).write(w)?;
+                write!(w, ",")?;
+            }
+            for (k, _, conf) in self.conf_items().wild_iter() {
+                FieldConfSetWrap(k.clone(), conf.clone()).write(w)?;
+                write!(w, ",")?;
+            }
+            write!(w, "rest@{}", rest)?;
+            return self.write_close_parenthesis(w);
+        }
         let item_count = self.conf_items().len();
 
         for (index, x) in self.conf_items().exact_iter().enumerate() {
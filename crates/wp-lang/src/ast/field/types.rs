@@ -7,6 +7,7 @@ use crate::types::WildMap;
 use derive_getters::Getters;
 use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter};
+use wp_data_model::intern::intern_name;
 use wp_model_core::model::FNameStr;
 use wp_model_core::model::{DataType, MetaErr};
 use wp_parser::Parser;
@@ -45,10 +46,10 @@ impl WplField {
         self.fmt_conf.scope_beg.is_some() && self.fmt_conf.scope_end.is_some()
     }
     pub fn run_key(&self, key: &str) -> Option<FNameStr> {
-        self.name().clone().or(Some(FNameStr::from(key)))
+        self.name().clone().or_else(|| Some(intern_name(key)))
     }
     pub fn run_key_str(&self, key: &str) -> FNameStr {
-        self.name().clone().unwrap_or_else(|| FNameStr::from(key))
+        self.name().clone().unwrap_or_else(|| intern_name(key))
     }
     /*
     pub fn use_sep(&mut self, sep: PrioSep) {
@@ -194,10 +195,15 @@ impl WplField {
 #[derive(Debug, Clone, PartialEq, Default, Getters)]
 pub struct WplFieldSet {
     conf_items: WildMap<WplField>,
+    // `rest@<name>`：未声明的键收集到 `<name>` 对象字段（`rest@_` 丢弃）
+    rest: Option<String>,
 }
 impl From<WildMap<WplField>> for WplFieldSet {
     fn from(value: WildMap<WplField>) -> Self {
-        Self { conf_items: value }
+        Self {
+            conf_items: value,
+            rest: None,
+        }
     }
 }
 
@@ -208,6 +214,10 @@ impl WplFieldSet {
     pub fn add<S: Into<String>>(&mut self, path: S, conf: WplField) {
         self.conf_items.insert(path.into(), conf);
     }
+    pub fn with_rest(mut self, rest: Option<String>) -> Self {
+        self.rest = rest;
+        self
+    }
 }
 
 impl Display for WplFieldSet {
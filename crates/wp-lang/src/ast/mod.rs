@@ -23,7 +23,8 @@ pub use processor::WplFun;
 pub use processor::WplPipe;
 pub use rule::meta::WplRuleMeta;
 pub use rule::meta::WplTag;
+pub use rule::output::WplRuleOutput;
 pub use rule::types::{WplExpress, WplRule, WplStatementType};
 pub use syntax::sep_pattern::{SepPattern, build_pattern};
-pub use syntax::tag::{AnnEnum, AnnFun, TagKvs};
+pub use syntax::tag::{AnnEnum, AnnFun, DupPolicy, TagKvs};
 pub use syntax::wpl_sep::{DefaultSep, WplSep, WplSepT};
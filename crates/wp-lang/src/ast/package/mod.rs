@@ -107,9 +107,10 @@ impl WplPackage {
     }
 
     pub fn parse(data: &mut &str, path: &str) -> Result<Self, WplCodeError> {
+        let code = *data;
         let package = wpl_package
             .parse_next(data)
-            .map_err(|e| WplCodeError::from_syntax(e, data, path))?;
+            .map_err(|e| WplCodeError::from_syntax_at(e, code, data, path))?;
         Ok(package)
     }
 
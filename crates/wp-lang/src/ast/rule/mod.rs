@@ -1,2 +1,3 @@
 pub mod meta;
+pub mod output;
 pub mod types;
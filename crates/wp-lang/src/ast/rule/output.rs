@@ -0,0 +1,133 @@
+//! 规则输出字段的静态推导（`prj check` 中 OML/WPL 字段映射检查使用）
+
+use std::collections::BTreeSet;
+
+use wp_model_core::model::DataType;
+
+use crate::ast::{WplField, WplPipe, WplRule, WplStatementType, group::WplGroup};
+
+/// 规则可能输出的字段名；`dynamic` 为真表示存在运行期才能确定名称的字段
+/// （json/kv 等按数据内容展开），此时 `fields` 不完整
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WplRuleOutput {
+    pub fields: BTreeSet<String>,
+    pub dynamic: bool,
+}
+
+impl WplRuleOutput {
+    pub fn contains(&self, name: &str) -> bool {
+        self.fields.contains(name)
+    }
+}
+
+impl WplRule {
+    /// 按规则 AST 推导输出字段名（字段名/元类型名、子字段、字段内分组以及 tag 注解）
+    pub fn output_fields(&self) -> WplRuleOutput {
+        let mut out = WplRuleOutput::default();
+        match &self.statement {
+            WplStatementType::Express(express) => {
+                for group in &express.group {
+                    collect_group(group, &mut out);
+                }
+                if let Some(ann) = &express.tags {
+                    out.fields.extend(ann.tags.keys().map(|k| k.to_string()));
+                    if let Some((k, raw_key)) = &ann.copy_raw
+                        && k == "name"
+                    {
+                        out.fields.insert(raw_key.to_string());
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+fn collect_group(group: &WplGroup, out: &mut WplRuleOutput) {
+    for field in &group.fields {
+        collect_field(field, out);
+    }
+}
+
+fn collect_field(field: &WplField, out: &mut WplRuleOutput) {
+    if is_dynamic_type(&field.meta_type) {
+        out.dynamic = true;
+    }
+    if !field.enriches.is_empty() {
+        out.dynamic = true;
+    }
+    out.fields.insert(field.safe_name().to_string());
+    if let Some(subs) = &field.sub_fields {
+        for (key, sub) in subs.conf_items().exact_iter() {
+            out.fields.insert(sub.run_key_str(key).to_string());
+        }
+        if subs.conf_items().wild_iter().next().is_some() {
+            out.dynamic = true;
+        }
+    }
+    for pipe in &field.pipe {
+        if let WplPipe::Group(group) = pipe {
+            collect_group(group, out);
+        }
+    }
+}
+
+/// 按数据内容决定输出字段名的解析类型
+fn is_dynamic_type(meta: &DataType) -> bool {
+    matches!(
+        meta,
+        DataType::Json
+            | DataType::ExactJson
+            | DataType::KV
+            | DataType::KvArr
+            | DataType::Auto
+            | DataType::ProtoText
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::WplPackage;
+
+    fn parse_rules(code: &str) -> WplPackage {
+        let mut code = code;
+        WplPackage::parse(&mut code, "from_test").expect("parse wpl")
+    }
+
+    #[test]
+    fn test_static_rule_output() {
+        let pkg = parse_rules(
+            r#"package /nginx/ {
+   rule example {
+        (ip:sip,2*_,time/clf:recv_time<[,]>,http/request",http/status,digit,chars",http/agent",_")
+   }
+}"#,
+        );
+        let out = pkg.rules[0].output_fields();
+        assert!(!out.dynamic);
+        for name in ["sip", "recv_time", "digit"] {
+            assert!(out.contains(name), "missing {}", name);
+        }
+        assert!(!out.contains("src_ip"));
+    }
+
+    #[test]
+    fn test_dynamic_rule_output() {
+        let pkg = parse_rules(
+            r#"package /demo/ {
+   rule js {
+        (json(chars@name:user))
+   }
+   rule plain {
+        (ip:sip, digit:port)
+   }
+}"#,
+        );
+        let js = pkg.rules[0].output_fields();
+        assert!(js.dynamic);
+        assert!(js.contains("user"));
+        let plain = pkg.rules[1].output_fields();
+        assert!(!plain.dynamic);
+        assert_eq!(plain.fields.len(), 2);
+    }
+}
@@ -372,6 +372,33 @@ impl SepPattern {
         }
     }
 
+    /// Find all non-overlapping matches in `haystack`, in order. Each offset is
+    /// absolute within `haystack`; the next search starts right after the
+    /// previous match (`offset + matched`).
+    pub fn find_all(&self, haystack: &str) -> Vec<(usize, SepMatch)> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        while start <= haystack.len() {
+            let Some((off, m)) = self.find(&haystack[start..]) else {
+                break;
+            };
+            let abs = start + off;
+            start = abs + m.matched;
+            if m.matched == 0 {
+                // Zero-width match: step over one char to guarantee progress.
+                match haystack[start..].chars().next() {
+                    Some(c) => start += c.len_utf8(),
+                    None => {
+                        out.push((abs, m));
+                        break;
+                    }
+                }
+            }
+            out.push((abs, m));
+        }
+        out
+    }
+
     /// Match only at the start of `haystack` (for `consume_sep`).
     pub fn match_at_start(&self, haystack: &str) -> Option<SepMatch> {
         match &self.compiled {
@@ -1212,6 +1239,133 @@ mod tests {
         assert_eq!(m.matched, 4); // "cat="
     }
 
+    #[test]
+    fn test_preserve_only_eq() {
+        // `{(=)}` — the separator is the `=` itself, kept for the next field
+        let p = build_pattern("(=)").unwrap();
+        match &p.compiled {
+            SepMatcher::Glob(g) => {
+                assert!(g.segments.is_empty());
+                assert_eq!(g.preserve, Some(vec![GlobSegment::Literal("=".into())]));
+            }
+            _ => panic!("expected Glob"),
+        }
+        let (off, m) = p.find("key=value").unwrap();
+        assert_eq!(off, 3);
+        assert_eq!(
+            m,
+            SepMatch {
+                consumed: 0,
+                matched: 1
+            }
+        );
+        assert_eq!(
+            p.match_at_start("=value"),
+            Some(SepMatch {
+                consumed: 0,
+                matched: 1
+            })
+        );
+        assert_eq!(p.match_at_start("value"), None);
+        assert!(p.find("no separator").is_none());
+    }
+
+    #[test]
+    fn test_preserve_only_newline() {
+        // `{(\n)}` — split before each newline, the newline starts the next field
+        let p = build_pattern("(\\n)").unwrap();
+        match &p.compiled {
+            SepMatcher::Glob(g) => {
+                assert!(g.segments.is_empty());
+                assert_eq!(g.preserve, Some(vec![GlobSegment::Literal("\n".into())]));
+            }
+            _ => panic!("expected Glob"),
+        }
+        let (off, m) = p.find("line1\nline2").unwrap();
+        assert_eq!(off, 5);
+        assert_eq!(
+            m,
+            SepMatch {
+                consumed: 0,
+                matched: 1
+            }
+        );
+        assert_eq!(p.match_at_start("\nline2").unwrap().consumed, 0);
+
+        // Empty main and empty preserve is still rejected
+        assert!(build_pattern("()").is_err());
+    }
+
+    // ── find_all ─────────────────────────────────────────────────────
+
+    fn offsets(p: &SepPattern, s: &str) -> Vec<usize> {
+        p.find_all(s).into_iter().map(|(off, _)| off).collect()
+    }
+
+    #[test]
+    fn test_find_all_literal() {
+        let p = build_pattern("&").unwrap();
+        assert_eq!(offsets(&p, "key1=val1&key2=val2&k3=v3"), vec![9, 19]);
+        assert!(p.find_all("key1=val1").is_empty());
+    }
+
+    #[test]
+    fn test_find_all_adjacent() {
+        let p = build_pattern(",").unwrap();
+        assert_eq!(offsets(&p, "a,,b,"), vec![1, 2, 4]);
+        let p = build_pattern("\\s").unwrap();
+        // Whitespace runs are matched as a whole
+        let all = p.find_all("a  b\t\tc");
+        assert_eq!(all.len(), 2);
+        assert_eq!(
+            all[0],
+            (
+                1,
+                SepMatch {
+                    consumed: 2,
+                    matched: 2
+                }
+            )
+        );
+        assert_eq!(
+            all[1],
+            (
+                4,
+                SepMatch {
+                    consumed: 2,
+                    matched: 2
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_find_all_overlapping_pattern() {
+        // "aa" in "aaaaa" yields 0 and 2; the trailing "a" is left over
+        let p = build_pattern("aa").unwrap();
+        assert_eq!(offsets(&p, "aaaaa"), vec![0, 2]);
+        let p = build_pattern("aba").unwrap();
+        assert_eq!(offsets(&p, "ababa_aba"), vec![0, 6]);
+    }
+
+    #[test]
+    fn test_find_all_glob() {
+        // Star content is field data: offsets point at each `=`
+        let p = build_pattern("*=").unwrap();
+        assert_eq!(offsets(&p, "a=bb=ccc="), vec![1, 4, 8]);
+        let p = build_pattern("(key=)").unwrap();
+        let all = p.find_all("x key=1 key=2");
+        assert_eq!(all.iter().map(|(o, _)| *o).collect::<Vec<_>>(), vec![2, 8]);
+        assert!(all.iter().all(|(_, m)| m.consumed == 0 && m.matched == 4));
+    }
+
+    #[test]
+    fn test_find_all_empty_haystack() {
+        assert!(build_pattern(",").unwrap().find_all("").is_empty());
+        assert!(build_pattern("\\s=").unwrap().find_all("").is_empty());
+        assert!(build_pattern("(key=)").unwrap().find_all("").is_empty());
+    }
+
     // ── Serde round-trip ─────────────────────────────────────────────
 
     #[test]
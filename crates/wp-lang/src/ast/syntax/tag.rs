@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 use std::io::Write;
 
 use smol_str::SmolStr;
@@ -11,15 +12,45 @@ use crate::parser::MergeTags;
 pub type TagKvs = BTreeMap<SmolStr, SmolStr>;
 pub type CopyRaw = (SmolStr, SmolStr);
 
+/// 同名字段重复出现时的处理策略：`#[dup(first|last|concat(',')|index)]`
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub enum DupPolicy {
+    /// 全部保留（默认，兼容原行为）
+    #[default]
+    Keep,
+    /// 只保留第一次出现的字段
+    First,
+    /// 只保留最后一次出现的字段
+    Last,
+    /// 以分隔符拼接各次取值，合并为一个字符串字段
+    Concat(SmolStr),
+    /// 重命名为 `name[0]`、`name[1]` …
+    Index,
+}
+
+impl Display for DupPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DupPolicy::Keep => write!(f, "keep"),
+            DupPolicy::First => write!(f, "first"),
+            DupPolicy::Last => write!(f, "last"),
+            DupPolicy::Concat(sep) => write!(f, "concat(\"{}\")", sep),
+            DupPolicy::Index => write!(f, "index"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum AnnEnum {
     Tags(TagKvs),
     Copy(CopyRaw),
+    Dup(DupPolicy),
 }
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct AnnFun {
     pub tags: TagKvs,
     pub copy_raw: Option<CopyRaw>,
+    pub dup: Option<DupPolicy>,
 }
 
 impl MergeTags for AnnFun {
@@ -34,6 +65,9 @@ impl MergeTags for AnnFun {
             if self.copy_raw.is_none() {
                 self.copy_raw = atags.copy_raw.clone()
             }
+            if self.dup.is_none() {
+                self.dup = atags.dup.clone()
+            }
         }
     }
 }
@@ -84,6 +118,12 @@ impl DebugFormat for AnnFun {
             write!(w, "{}:\"{}\"", ck, cv)?;
             self.write_close_parenthesis(w)?;
         }
+        if let Some(dup) = &self.dup {
+            write!(w, ", dup")?;
+            self.write_open_parenthesis(w)?;
+            write!(w, "{}", dup)?;
+            self.write_close_parenthesis(w)?;
+        }
         write!(w, "]")?;
         self.write_new_line(w)?;
         Ok(())
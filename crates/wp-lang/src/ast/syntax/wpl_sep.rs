@@ -543,6 +543,23 @@ mod tests {
         assert_eq!(data, "value");
     }
 
+    #[test]
+    fn test_pattern_preserve_only_keeps_sep() {
+        use crate::ast::syntax::sep_pattern::build_pattern;
+        let sep = WplSep::field_sep_pattern(build_pattern("(=)").unwrap());
+        let mut data = "key=value";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "key");
+        // zero-width: the preserved `=` is left for the next field
+        sep.consume_sep(&mut data).unwrap();
+        assert_eq!(data, "=value");
+
+        let sep = WplSep::field_sep_pattern(build_pattern("(\\n)").unwrap());
+        let mut data = "first\nsecond";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "first");
+        sep.consume_sep(&mut data).unwrap();
+        assert_eq!(data, "\nsecond");
+    }
+
     #[test]
     fn test_pattern_try_consume_sep() {
         use crate::ast::syntax::sep_pattern::build_pattern;
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use wp_parse_api::{PipeProcessor, RawData, WparseResult};
+
+/// 控制字符清除处理器
+///
+/// - `strip/control`：移除 ASCII 控制字符 0x00–0x1F，保留 `\t`、`\n`、`\r`
+/// - `strip/nonprintable`：在此基础上再移除 DEL（0x7F）与 C1 控制字符（0x80–0x9F）
+///
+/// # 行为
+/// - 直接在字节上扫描，不先转换为字符串；无可移除字符时返回原始数据
+/// - C1 控制字符：输入为合法 UTF-8 时按其编码 `0xC2 0x80..=0x9F` 移除，
+///   避免误删多字节字符的续字节；非 UTF-8 输入按单字节 0x80–0x9F 移除
+/// - 保持输入容器类型不变
+#[derive(Debug)]
+pub struct ControlCharStripProc {
+    nonprintable: bool,
+}
+
+impl ControlCharStripProc {
+    /// `strip/control`
+    pub const CONTROL: Self = Self {
+        nonprintable: false,
+    };
+    /// `strip/nonprintable`
+    pub const NONPRINTABLE: Self = Self { nonprintable: true };
+
+    /// 移除控制字符；无需移除时返回 `None`
+    fn strip(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let is_ctrl = |b: u8| b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r');
+        if !self.nonprintable {
+            if !data.iter().any(|&b| is_ctrl(b)) {
+                return None;
+            }
+            return Some(data.iter().copied().filter(|&b| !is_ctrl(b)).collect());
+        }
+
+        let utf8 = std::str::from_utf8(data).is_ok();
+        let mut result: Option<Vec<u8>> = None;
+        let mut pos = 0;
+        while pos < data.len() {
+            let b = data[pos];
+            let skip = if is_ctrl(b) || b == 0x7F {
+                1
+            } else if utf8 {
+                match data.get(pos + 1) {
+                    Some(0x80..=0x9F) if b == 0xC2 => 2,
+                    _ => 0,
+                }
+            } else if (0x80..=0x9F).contains(&b) {
+                1
+            } else {
+                0
+            };
+            if skip > 0 {
+                // 首次命中时才分配，并补齐之前的字节
+                result.get_or_insert_with(|| data[..pos].to_vec());
+                pos += skip;
+            } else {
+                if let Some(out) = result.as_mut() {
+                    out.push(b);
+                }
+                pos += 1;
+            }
+        }
+        result
+    }
+}
+
+impl PipeProcessor for ControlCharStripProc {
+    /// 移除数据中的控制字符
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        match data {
+            RawData::String(s) => {
+                if let Some(cleaned) = self.strip(s.as_bytes()) {
+                    let result = String::from_utf8_lossy(&cleaned).into_owned();
+                    Ok(RawData::from_string(result))
+                } else {
+                    Ok(RawData::from_string(s))
+                }
+            }
+            RawData::Bytes(b) => {
+                if let Some(cleaned) = self.strip(&b) {
+                    Ok(RawData::Bytes(cleaned.into()))
+                } else {
+                    Ok(RawData::Bytes(b))
+                }
+            }
+            RawData::ArcBytes(b) => {
+                if let Some(cleaned) = self.strip(&b) {
+                    Ok(RawData::ArcBytes(Arc::new(cleaned)))
+                } else {
+                    Ok(RawData::ArcBytes(b))
+                }
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        if self.nonprintable {
+            "strip/nonprintable"
+        } else {
+            "strip/control"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::eval::builtins::raw_to_utf8_string;
+    use crate::types::AnyResult;
+
+    #[test]
+    fn test_strip_control_keeps_tab_newline_cr() -> AnyResult<()> {
+        let data = RawData::Bytes(Bytes::from_static(b"a\x00b\x01\tc\x1b[0m\r\n\x1fd"));
+        let result = ControlCharStripProc::CONTROL.process(data)?;
+        assert!(matches!(result, RawData::Bytes(_)));
+        assert_eq!(raw_to_utf8_string(&result), "ab\tc[0m\r\nd");
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_control_keeps_del_and_c1() -> AnyResult<()> {
+        let data = RawData::from_string("x\u{7f}y\u{85}z\u{0}".to_string());
+        let result = ControlCharStripProc::CONTROL.process(data)?;
+        assert!(matches!(result, RawData::String(_)));
+        assert_eq!(raw_to_utf8_string(&result), "x\u{7f}y\u{85}z");
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_control_untouched_input() -> AnyResult<()> {
+        let data = RawData::ArcBytes(Arc::new(b"plain text".to_vec()));
+        let result = ControlCharStripProc::CONTROL.process(data)?;
+        assert!(matches!(result, RawData::ArcBytes(_)));
+        assert_eq!(raw_to_utf8_string(&result), "plain text");
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_nonprintable_utf8() -> AnyResult<()> {
+        // 中文的续字节落在 0x80–0x9F 区间，不能被误删
+        let data = RawData::from_string("中\u{7f}文\u{85}\u{9f}\x07ok\t".to_string());
+        let result = ControlCharStripProc::NONPRINTABLE.process(data)?;
+        assert_eq!(raw_to_utf8_string(&result), "中文ok\t");
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_nonprintable_raw_bytes() -> AnyResult<()> {
+        // 非 UTF-8 输入按单字节处理
+        let data = RawData::Bytes(Bytes::from_static(&[b'a', 0x80, 0x9F, 0x7F, b'b', 0xA0]));
+        let result = ControlCharStripProc::NONPRINTABLE.process(data)?;
+        match result {
+            RawData::Bytes(b) => assert_eq!(b.as_ref(), &[b'a', b'b', 0xA0]),
+            other => panic!("unexpected container: {:?}", raw_to_utf8_string(&other)),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(ControlCharStripProc::CONTROL.name(), "strip/control");
+        assert_eq!(
+            ControlCharStripProc::NONPRINTABLE.name(),
+            "strip/nonprintable"
+        );
+    }
+}
@@ -5,6 +5,7 @@ use wp_parse_api::{PipeHold, RawData};
 
 pub mod base64;
 pub mod bom;
+pub mod control;
 pub mod hex;
 mod pipe_fun;
 pub mod quotation;
@@ -12,6 +13,7 @@ pub mod registry;
 
 use base64::Base64Proc;
 use bom::BomClearProc;
+use control::ControlCharStripProc;
 use hex::HexProc;
 use quotation::EscQuotaProc;
 
@@ -47,6 +49,14 @@ fn bom_strip_stage() -> PipeHold {
     Arc::new(BomClearProc)
 }
 
+fn control_strip_stage() -> PipeHold {
+    Arc::new(ControlCharStripProc::CONTROL)
+}
+
+fn nonprintable_strip_stage() -> PipeHold {
+    Arc::new(ControlCharStripProc::NONPRINTABLE)
+}
+
 /// Ensure core decode/unquote pipe units are registered in the plg_pipe registry.
 pub fn ensure_builtin_pipe_units() {
     BUILTIN_PIPE_INIT.call_once(|| {
@@ -54,6 +64,8 @@ pub fn ensure_builtin_pipe_units() {
         registry::register_pipe_unit("decode/hex", decode_hex_stage);
         registry::register_pipe_unit("unquote/unescape", unquote_unescape_stage);
         registry::register_pipe_unit("strip/bom", bom_strip_stage);
+        registry::register_pipe_unit("strip/control", control_strip_stage);
+        registry::register_pipe_unit("strip/nonprintable", nonprintable_strip_stage);
     });
 }
 
@@ -80,6 +92,16 @@ mod tests {
                 .any(|name| name.to_uppercase() == "UNQUOTE/UNESCAPE")
         );
         assert!(units.iter().any(|name| name.to_uppercase() == "STRIP/BOM"));
+        assert!(
+            units
+                .iter()
+                .any(|name| name.to_uppercase() == "STRIP/CONTROL")
+        );
+        assert!(
+            units
+                .iter()
+                .any(|name| name.to_uppercase() == "STRIP/NONPRINTABLE")
+        );
     }
 
     #[test]
@@ -95,4 +117,17 @@ mod tests {
             assert_eq!(proc.name(), "strip/bom");
         }
     }
+
+    #[test]
+    fn test_strip_control_can_be_created() {
+        ensure_builtin_pipe_units();
+
+        for name in ["strip/control", "strip/nonprintable"] {
+            let processor = registry::create_pipe_unit(name);
+            assert!(processor.is_some());
+            if let Some(proc) = processor {
+                assert_eq!(proc.name(), name);
+            }
+        }
+    }
 }
@@ -1,3 +1,10 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, RwLock};
+
+use winnow::error::{ContextError, ErrMode, StrContext};
+
+use crate::ast::WplField;
+
 pub fn group_idx_desc(idx: usize) -> &'static str {
     match idx {
         0 => "",
@@ -30,3 +37,52 @@ pub fn fpu_idx_desc(idx: usize) -> &'static str {
         _ => "field[.]",
     }
 }
+
+// 字段单元标签：winnow 上下文只接受 &'static str，按描述驻留，规则重载时不重复分配。
+// 仅在构建字段单元时写入，解析失败路径直接使用预先取得的标签
+static UNIT_LABELS: LazyLock<RwLock<HashSet<&'static str>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+/// 字段单元在错误上下文中的标签，如 `digit:port`；在规则构建时调用
+pub fn unit_label(conf: &WplField) -> &'static str {
+    let desc = if conf.desc.is_empty() {
+        conf.meta_name.as_str()
+    } else {
+        conf.desc.as_str()
+    };
+    if let Some(hit) = UNIT_LABELS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(desc)
+    {
+        return hit;
+    }
+    let mut labels = UNIT_LABELS.write().unwrap_or_else(|e| e.into_inner());
+    if let Some(hit) = labels.get(desc) {
+        return hit;
+    }
+    let label: &'static str = Box::leak(desc.to_string().into_boxed_str());
+    labels.insert(label);
+    label
+}
+
+/// 解析失败时经过的字段单元链，由外到内，如 `json:a > digit@x`
+pub fn unit_chain(e: &ErrMode<ContextError>) -> Option<String> {
+    let ctx = match e {
+        ErrMode::Backtrack(c) | ErrMode::Cut(c) => c,
+        ErrMode::Incomplete(_) => return None,
+    };
+    let labels = UNIT_LABELS.read().unwrap_or_else(|e| e.into_inner());
+    let mut chain: Vec<&str> = ctx
+        .context()
+        .filter_map(|c| match c {
+            StrContext::Label(l) if labels.contains(l) => Some(*l),
+            _ => None,
+        })
+        .collect();
+    if chain.is_empty() {
+        return None;
+    }
+    chain.reverse();
+    Some(chain.join(" > "))
+}
@@ -0,0 +1,150 @@
+//! 同名字段去重：按规则注解 `#[dup(...)]` 处理字段单元产出中的重复字段名。
+
+use std::collections::{HashMap, HashSet};
+
+use wp_model_core::model::{DataField, Value};
+
+use crate::ast::DupPolicy;
+
+/// 对一条记录的字段列表应用重复策略；无重复时不做任何改动
+pub fn apply_dup_policy(policy: &DupPolicy, fields: &mut Vec<DataField>) {
+    if matches!(policy, DupPolicy::Keep) || fields.len() < 2 {
+        return;
+    }
+    // 名称 -> 出现次数
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for f in fields.iter() {
+        *counts.entry(f.get_name().to_string()).or_default() += 1;
+    }
+    if counts.values().all(|c| *c < 2) {
+        return;
+    }
+
+    match policy {
+        DupPolicy::Keep => {}
+        DupPolicy::First => {
+            let mut seen: HashSet<String> = HashSet::new();
+            fields.retain(|f| seen.insert(f.get_name().to_string()));
+        }
+        DupPolicy::Last => {
+            fields.retain(|f| {
+                let left = counts.get_mut(f.get_name()).expect("counted");
+                *left -= 1;
+                *left == 0
+            });
+        }
+        DupPolicy::Concat(sep) => {
+            // 合并到首次出现的位置
+            let mut joined: HashMap<String, (usize, String)> = HashMap::new();
+            let mut keep = Vec::with_capacity(fields.len());
+            for (idx, f) in fields.iter().enumerate() {
+                let name = f.get_name();
+                if counts[name] < 2 {
+                    keep.push(true);
+                    continue;
+                }
+                let text = value_text(f.get_value());
+                match joined.get_mut(name) {
+                    Some((_, acc)) => {
+                        acc.push_str(sep);
+                        acc.push_str(&text);
+                        keep.push(false);
+                    }
+                    None => {
+                        joined.insert(name.to_string(), (idx, text));
+                        keep.push(true);
+                    }
+                }
+            }
+            for (name, (idx, text)) in joined {
+                fields[idx] = DataField::from_chars(name, text);
+            }
+            let mut it = keep.into_iter();
+            fields.retain(|_| it.next().unwrap_or(true));
+        }
+        DupPolicy::Index => {
+            let mut next: HashMap<String, usize> = HashMap::new();
+            for f in fields.iter_mut() {
+                if counts[f.get_name()] < 2 {
+                    continue;
+                }
+                let idx = next.entry(f.get_name().to_string()).or_default();
+                let new_name = format!("{}[{}]", f.get_name(), idx);
+                *idx += 1;
+                f.set_name(new_name);
+            }
+        }
+    }
+}
+
+fn value_text(v: &Value) -> String {
+    match v {
+        Value::Chars(s) => s.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users() -> Vec<DataField> {
+        vec![
+            DataField::from_chars("user", "alice"),
+            DataField::from_chars("ip", "10.0.0.1"),
+            DataField::from_chars("user", "bob"),
+            DataField::from_chars("user", "carol"),
+        ]
+    }
+
+    fn chars_of(fields: &[DataField], name: &str) -> Vec<String> {
+        fields
+            .iter()
+            .filter(|f| f.get_name() == name)
+            .map(|f| value_text(f.get_value()))
+            .collect()
+    }
+
+    #[test]
+    fn keep_is_noop() {
+        let mut fields = users();
+        apply_dup_policy(&DupPolicy::Keep, &mut fields);
+        assert_eq!(fields, users());
+    }
+
+    #[test]
+    fn first_keeps_first_occurrence() {
+        let mut fields = users();
+        apply_dup_policy(&DupPolicy::First, &mut fields);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(chars_of(&fields, "user"), vec!["alice"]);
+        assert_eq!(fields[1].get_name(), "ip");
+    }
+
+    #[test]
+    fn last_keeps_last_occurrence() {
+        let mut fields = users();
+        apply_dup_policy(&DupPolicy::Last, &mut fields);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(chars_of(&fields, "user"), vec!["carol"]);
+        assert_eq!(fields[0].get_name(), "ip");
+    }
+
+    #[test]
+    fn concat_joins_values() {
+        let mut fields = users();
+        apply_dup_policy(&DupPolicy::Concat(",".into()), &mut fields);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0], DataField::from_chars("user", "alice,bob,carol"));
+        assert_eq!(fields[1].get_name(), "ip");
+    }
+
+    #[test]
+    fn index_renames_duplicates() {
+        let mut fields = users();
+        apply_dup_policy(&DupPolicy::Index, &mut fields);
+        let names: Vec<&str> = fields.iter().map(|f| f.get_name()).collect();
+        assert_eq!(names, vec!["user[0]", "ip", "user[1]", "user[2]"]);
+        assert_eq!(chars_of(&fields, "user[2]"), vec!["carol"]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ast::group::{GroupSeq, WplGroupType};
 use crate::ast::{WplField, WplSep};
+use crate::eval::desc::unit_label;
 use crate::eval::runtime::field_pipe::PipeEnum;
 use crate::eval::runtime::vm_unit::WplEvaluator;
 use crate::eval::value::parse_def::{FieldParser, Hold, ParserHold};
@@ -14,6 +15,7 @@ use wp_model_core::model::FNameStr;
 // Use centralized parser result alias for consistency across crates
 use wp_model_core::model::{DataField, DataType};
 use wp_parser::WResult as ModalResult;
+use wp_parser::symbol::ctx_label;
 
 use super::pipe_exec::PipeExecutor;
 use super::subunit::SubUnitManager;
@@ -27,12 +29,16 @@ pub struct FieldEvalUnit {
     sub_units: SubUnitManager,
     next: Option<Box<FieldEvalUnit>>,
     pub group_enum: WplGroupType,
+    // 解析失败时压入错误上下文的标签，构建时驻留
+    #[getter(skip)]
+    label: &'static str,
 }
 
 impl FieldEvalUnit {
     pub fn new(index: usize, conf: WplField, parser: ParserHold, group_enum: WplGroupType) -> Self {
         Self {
             index,
+            label: unit_label(&conf),
             conf,
             parser,
             pipe_exec: PipeExecutor::new(),
@@ -70,6 +76,7 @@ impl FieldEvalUnit {
         cur_conf.meta_type = meta;
         let ins = Self {
             index,
+            label: unit_label(&cur_conf),
             conf: cur_conf,
             parser,
             pipe_exec: PipeExecutor::new(),
@@ -136,7 +143,10 @@ impl FieldEvalUnit {
                 if self.conf.is_opt {
                     Ok(())
                 } else {
-                    Err(e)
+                    Err(e.map(|mut c| {
+                        c.push(ctx_label(self.label));
+                        c
+                    }))
                 }
             }
         }
@@ -1,3 +1,4 @@
+pub mod dup;
 pub mod field;
 pub mod field_pipe;
 pub mod group;
@@ -1,8 +1,11 @@
+use crate::ast::DupPolicy;
 use crate::ast::WplPipe;
 use crate::ast::group::{WplGroup, WplGroupType};
 use crate::ast::{WplExpress, WplStatementType};
 use crate::ast::{WplField, WplSep};
 use crate::eval::builtins::{self, PipeLineResult, raw_to_utf8_string};
+use crate::eval::desc::unit_chain;
+use crate::eval::runtime::dup::apply_dup_policy;
 use crate::eval::runtime::field::FieldEvalUnit;
 use crate::eval::runtime::field_pipe::PipeEnum;
 use crate::eval::runtime::group::WplEvalGroup;
@@ -14,10 +17,13 @@ use crate::parser::wpl_rule::wpl_rule;
 use anyhow::Result;
 use orion_error::{ErrorWith, ToStructError, UvsDataFrom};
 use orion_overload::new::New3;
+use winnow::combinator::fail;
+use wp_data_model::limits::{self, RecordVerdict};
 use wp_log::debug_edata;
 use wp_model_core::model::DataRecord;
 use wp_parser::Parser;
 use wp_parser::WResult as ModalResult;
+use wp_parser::symbol::ctx_desc;
 
 // Internal DataResult for wp-lang usage
 // Plugin developers should use wp_parse_api::DataResult instead
@@ -56,6 +62,8 @@ impl IntoRawData for &str {
 pub struct WplEvaluator {
     preorder: Vec<PipeHold>,
     group_units: Vec<WplEvalGroup>,
+    // 同名字段处理策略，来自规则注解 `#[dup(...)]`
+    dup_policy: DupPolicy,
 }
 unsafe impl Send for WplEvaluator {}
 
@@ -115,9 +123,13 @@ impl WplEvaluator {
                 let cur_pos = input.len();
                 let pos = ori_len - cur_pos;
                 if pos >= oth_suc_len {
+                    let detail = match unit_chain(&e) {
+                        Some(chain) => format!("field: {}\n{}", chain, e),
+                        None => e.to_string(),
+                    };
                     Err(WparseReason::from_data(input.to_string(), Some(pos))
                         .to_err()
-                        .with_detail(e.to_string()))
+                        .with_detail(detail))
                 } else {
                     Err(WparseError::from(WparseReason::NotMatch))
                 }
@@ -144,6 +156,9 @@ impl WplEvaluator {
             Self::assemble_ins(inject, &mut target_dpl)?;
         }
         Self::assemble_ins(dy_lang, &mut target_dpl)?;
+        if let Some(dup) = dy_lang.tags.as_ref().and_then(|t| t.dup.clone()) {
+            target_dpl.dup_policy = dup;
+        }
         Ok(target_dpl)
     }
 
@@ -231,14 +246,28 @@ impl WplEvaluator {
         let mut result = Vec::with_capacity(100);
 
         let sep = WplSep::default();
+        limits::begin_record();
         for group_unit in self.group_units.iter() {
             match group_unit.proc(e_id, &sep, data, &mut result) {
                 Ok(_) => {}
                 Err(e) => {
+                    // 解析失败也要结束本条记录的计数，避免遗留状态
+                    let _ = limits::finish_record();
                     return Err(e);
                 }
             }
         }
+        apply_dup_policy(&self.dup_policy, &mut result);
+        match limits::finish_record() {
+            RecordVerdict::Intact => {}
+            RecordVerdict::Truncated(_) => result.push(limits::truncated_marker()),
+            RecordVerdict::Dropped(_) => {
+                // 整条记录按未命中处理，进入 miss
+                return fail
+                    .context(ctx_desc("record limit exceeded"))
+                    .parse_next(&mut "");
+            }
+        }
         // Convert Vec<DataField> to Vec<FieldStorage>
         let storage_items: Vec<_> = result
             .into_iter()
@@ -627,4 +656,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_err_carries_field_chain() -> AnyResult<()> {
+        let pipe = WplEvaluator::from_code(r#"rule test { (ip:sip, digit:port)\s }"#)?;
+        let mut data = "10.0.0.1 abc";
+        let err = pipe.parse_groups(0, &mut data).expect_err("digit mismatch");
+        let chain = crate::eval::desc::unit_chain(&err).expect("field chain");
+        assert!(chain.contains("digit:port"), "chain: {}", chain);
+        assert!(!chain.contains("sip"), "chain: {}", chain);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dup_policy_from_rule_annotation() -> AnyResult<()> {
+        let data = "alice bob carol 10.0.0.1";
+        let body = r#"rule test { (chars:user, chars:user, chars:user, chars:ip)\s }"#;
+        let users = |anno: &str| -> AnyResult<Vec<(String, String)>> {
+            let pipe = WplEvaluator::from_code(&format!("{} {}", anno, body))?;
+            let (record, _) = pipe.proc(0, data, 0)?;
+            Ok(record
+                .items
+                .iter()
+                .filter(|f| f.get_name().starts_with("user"))
+                .map(|f| (f.get_name().to_string(), f.get_value().to_string()))
+                .collect())
+        };
+        let pair = |n: &str, v: &str| (n.to_string(), v.to_string());
+
+        // 无注解：保持原行为，三个同名字段全部保留
+        assert_eq!(users("")?.len(), 3);
+        assert_eq!(users("#[dup(first)]")?, vec![pair("user", "alice")]);
+        assert_eq!(users("#[dup(last)]")?, vec![pair("user", "carol")]);
+        assert_eq!(
+            users("#[dup(concat(','))]")?,
+            vec![pair("user", "alice,bob,carol")]
+        );
+        assert_eq!(
+            users("#[dup(index)]")?,
+            vec![
+                pair("user[0]", "alice"),
+                pair("user[1]", "bob"),
+                pair("user[2]", "carol")
+            ]
+        );
+        Ok(())
+    }
 }
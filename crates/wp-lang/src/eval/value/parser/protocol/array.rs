@@ -7,6 +7,7 @@ use crate::parser::utils::quot_str;
 
 use winnow::ascii::{digit1, multispace0};
 use winnow::combinator::{fail, preceded, separated};
+use wp_data_model::limits;
 use wp_model_core::model::DigitValue;
 use wp_parser::symbol::{ctx_desc, symbol_brackets_beg, symbol_brackets_end};
 
@@ -95,7 +96,7 @@ impl PatternParser for ArrayP {
             // 可选的尾随逗号，在收尾 ']' 前允许一次（含空白）
             let _ = preceded(multispace0, opt(literal(","))).parse_next(data)?;
             symbol_brackets_end.parse_next(data)?;
-            out.push(DataField::from_arr(name, tdo_arr));
+            limits::push_field(out, DataField::from_arr(name, tdo_arr));
             return Ok(());
         }
         fail.parse_next(data)
@@ -11,6 +11,7 @@ use crate::ast::WplField;
 use crate::derive_base_prs;
 use crate::eval::runtime::field::FieldEvalUnit;
 use crate::eval::value::parse_def::*;
+use wp_data_model::limits;
 
 derive_base_prs!(Base64P);
 
@@ -30,11 +31,14 @@ impl FieldParser for Base64P {
         match general_purpose::STANDARD.decode(take) {
             Ok(output) => {
                 let value = String::from_utf8_lossy(&output).to_string();
-                out.push(DataField::new_opt(
-                    DataType::Base64,
-                    f_name,
-                    Value::Chars(SmolStr::from(value)),
-                ));
+                limits::push_field(
+                    out,
+                    DataField::new_opt(
+                        DataType::Base64,
+                        f_name,
+                        Value::Chars(SmolStr::from(value)),
+                    ),
+                );
                 Ok(())
             }
             Err(_e) => fail
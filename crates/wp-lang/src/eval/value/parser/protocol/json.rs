@@ -882,6 +882,46 @@ mod tests {
 }
 "#;
 
+    #[test]
+    fn test_json_record_limits_bound_fields() -> AnyResult<()> {
+        use std::fmt::Write;
+        use wp_data_model::limits::{self, LimitPolicy, RecordLimits, TRUNCATED_FIELD};
+
+        // 100 万个 key 的合成 JSON
+        let mut raw = String::with_capacity(16 << 20);
+        raw.push('{');
+        for i in 0..1_000_000 {
+            if i > 0 {
+                raw.push(',');
+            }
+            write!(raw, "\"k{}\":{}", i, i)?;
+        }
+        raw.push('}');
+        let ppl = WplEvaluator::from_code("rule test {(json)}").assert();
+
+        let limit = RecordLimits {
+            max_fields: 1000,
+            ..Default::default()
+        };
+        let truncated = limits::scoped(limit, || ppl.parse_groups(0, &mut raw.as_str()));
+        let drop = RecordLimits {
+            policy: LimitPolicy::Drop,
+            ..limit
+        };
+        let dropped = limits::scoped(drop, || ppl.parse_groups(0, &mut raw.as_str()));
+
+        let record = truncated.assert();
+        assert_eq!(record.items.len(), 1001);
+        assert!(record.items.capacity() < 4096);
+        assert!(record.field("k0").is_some());
+        assert_eq!(
+            record.items.last().map(|f| f.get_name().to_string()),
+            Some(TRUNCATED_FIELD.to_string())
+        );
+        assert!(dropped.is_err());
+        Ok(())
+    }
+
     const JSON_DATA1: &str = r#"
 {"action":{"text":"父进程 /bin/bash（pid：105123）创建进程 /usr/bin/curl（pid：105129）启动参数：-fsL http://localhost:8080/api/health/ 。来源：进程创建监控"} }
 "#;
@@ -2,9 +2,10 @@ use super::super::prelude::*;
 use crate::ast::WplSep;
 use crate::eval::runtime::field::FieldEvalUnit;
 use smol_str::SmolStr;
-use wp_model_core::model::FNameStr;
 // 使用 String 动态拼接路径，避免固定容量 ArrayString 在深层或长 key 时 panic
 use serde_json::{Map, Value};
+use wp_data_model::intern::intern_name;
+use wp_data_model::limits;
 use wp_model_core::model::types::value::ObjectValue;
 use wp_model_core::model::{DataField, DataType, FieldStorage};
 
@@ -60,6 +61,10 @@ impl JsonProc {
     ) -> ModalResult<()> {
         //let mut sub_fields = Vec::with_capacity(20);
         for (k, v) in v_map {
+            // 记录已达字段数/总大小上限：后续字段不会再写入，提前结束遍历
+            if limits::exhausted() {
+                break;
+            }
             Self::proc_value_inner(
                 e_id,
                 fpu,
@@ -192,7 +197,7 @@ impl JsonProc {
         let run_key = if let Some(sub_conf) = sub_conf_opt {
             sub_conf.run_key_str(j_path.as_str())
         } else {
-            FNameStr::from(j_path.clone())
+            intern_name(&j_path)
         };
         match v {
             Value::Null => {
@@ -211,7 +216,7 @@ impl JsonProc {
                     }
                 }
                 let field = DataField::from_bool(run_key, *b_v);
-                out.push(field);
+                limits::push_field(out, field);
             }
             Value::Number(num) => {
                 if exact {
@@ -227,29 +232,29 @@ impl JsonProc {
                 }
                 // 统一数值处理：优先 f64；其次 i64；u64 超出 i64 上限时降级为字符串，避免静默丢弃
                 if let (true, Some(f)) = (num.is_f64(), num.as_f64()) {
-                    out.push(DataField::from_float(run_key, f));
+                    limits::push_field(out, DataField::from_float(run_key, f));
                 } else if num.is_i64() {
                     if let Some(i_n) = num.as_i64() {
-                        out.push(DataField::from_digit(run_key, i_n));
+                        limits::push_field(out, DataField::from_digit(run_key, i_n));
                     }
                 } else if num.is_u64() {
                     if let Some(u) = num.as_u64() {
                         if u <= i64::MAX as u64 {
-                            out.push(DataField::from_digit(run_key, u as i64));
+                            limits::push_field(out, DataField::from_digit(run_key, u as i64));
                         } else {
                             // 超范围：保留精确性，降级为字符串
-                            out.push(DataField::from_chars(
-                                run_key,
-                                SmolStr::from(num.to_string()),
-                            ));
+                            limits::push_field(
+                                out,
+                                DataField::from_chars(run_key, SmolStr::from(num.to_string())),
+                            );
                         }
                     }
                 } else {
                     // 兜底：未知数值类型，按字符串保留
-                    out.push(DataField::from_chars(
-                        run_key,
-                        SmolStr::from(num.to_string()),
-                    ));
+                    limits::push_field(
+                        out,
+                        DataField::from_chars(run_key, SmolStr::from(num.to_string())),
+                    );
                 }
             }
             Value::String(_) => {
@@ -270,14 +275,14 @@ impl JsonProc {
                     fpu.parse(e_id, &ups_sep, &mut raw_ref, Some(run_key), out)?;
                     return Ok(());
                 }
-                out.push(DataField::from_chars(run_key, raw));
+                limits::push_field(out, DataField::from_chars(run_key, raw));
                 return Ok(());
             }
             Value::Array(arr) => {
                 if exact {
                     Self::exact_check(fpu, true, sub_conf_opt.is_some(), j_path.as_str())?;
                 }
-                let mut arr_name = FNameStr::from(name);
+                let mut arr_name = intern_name(name);
                 if let Some(cnf) = sub_conf_opt {
                     arr_name = cnf.name.clone().unwrap_or(arr_name);
                     if let DataType::Array(_) = *cnf.meta_type() {
@@ -294,7 +299,7 @@ impl JsonProc {
                                     dat.push(value);
                                 }
                             }
-                            out.push(DataField::from_arr(run_key, dat));
+                            limits::push_field(out, DataField::from_arr(run_key, dat));
                             return Ok(());
                         }
                     }
@@ -302,6 +307,9 @@ impl JsonProc {
 
                 let mut item_name = String::with_capacity(arr_name.len() + 2 + 10);
                 for (i, v) in arr.iter().enumerate() {
+                    if limits::exhausted() {
+                        break;
+                    }
                     // 复用 item_name，减少分配
                     item_name.clear();
                     item_name.push_str(arr_name.as_str());
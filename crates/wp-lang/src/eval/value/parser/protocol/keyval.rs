@@ -6,6 +6,8 @@ use crate::eval::value::parse_def::PatternParser;
 use crate::eval::value::parser::physical::foundation::gen_chars;
 use crate::eval::value::parser::{ParserFactory, protocol};
 use crate::parser::utils::{quot_r_str, quot_str, take_kv_key, window_path};
+use wp_data_model::intern::intern_name;
+use wp_data_model::limits;
 use wp_model_core::model::FNameStr;
 derive_base_prs!(KeyValP);
 
@@ -93,14 +95,14 @@ fn value_take(
         // 无子配置快路径：直接产出原始值为 chars，避免子解析检索与额外分支
         match str_val_r {
             Ok(str_val) => {
-                out.push(DataField::from_chars(key, str_val));
+                limits::push_field(out, DataField::from_chars(intern_name(key), str_val));
                 return Ok(());
             }
             Err(_) => {
                 let sep = p_sep; // 已设置到行尾
                 let val = sep.read_until_sep(data)?;
                 let trim_val = val.trim();
-                out.push(DataField::from_chars(key, trim_val));
+                limits::push_field(out, DataField::from_chars(intern_name(key), trim_val));
                 return Ok(());
             }
         }
@@ -4,9 +4,12 @@ use crate::eval::runtime::field::FieldEvalUnit;
 use crate::eval::value::parse_def::PatternParser;
 use crate::parser::utils::{decode_escapes, interval_data, quot_str, take_kv_key, take_to_end};
 use serde_json::{Number, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use winnow::token::{rest, take_until};
-use wp_model_core::model::FNameStr;
+use wp_data_model::intern::intern_name;
+use wp_data_model::limits;
+use wp_model_core::model::types::value::ObjectValue;
+use wp_model_core::model::{FNameStr, FieldStorage};
 
 #[derive(Default, Clone)]
 pub struct KvArrP {}
@@ -35,6 +38,15 @@ impl PatternParser for KvArrP {
         let mut parsed = 0usize;
         let mut emitted_ranges: Vec<(String, usize, usize)> = Vec::new();
         let cur_sep = WplSepT::<Self>::from(ups_sep);
+        // rest 模式：未声明键进入桶，声明但缺失的键补空字段
+        let rest = fpu
+            .conf()
+            .sub_fields
+            .as_ref()
+            .and_then(|subs| subs.rest().clone());
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut extra: Vec<DataField> = Vec::new();
+        let mut extra_ranges: Vec<(String, usize, usize)> = Vec::new();
         loop {
             Self::consume_delimiter(data)?;
             if data.is_empty() {
@@ -44,6 +56,25 @@ impl PatternParser for KvArrP {
             match Self::take_pair(data, &cur_sep) {
                 Ok((key, value)) => {
                     parsed += 1;
+                    if let Some(bucket) = &rest {
+                        if fpu.get_sub_fpu(key.as_str()).is_none() {
+                            if bucket != "_" {
+                                let start_idx = extra.len();
+                                Self::emit_value(
+                                    e_id,
+                                    fpu,
+                                    ups_sep,
+                                    key.as_str(),
+                                    value,
+                                    &mut extra,
+                                )?;
+                                extra_ranges.push((key, start_idx, extra.len()));
+                            }
+                            Self::consume_trailing(data, &cur_sep)?;
+                            continue;
+                        }
+                        seen.insert(key.clone());
+                    }
                     let start_idx = out.len();
                     Self::emit_value(e_id, fpu, ups_sep, key.as_str(), value, out)?;
                     let end_idx = out.len();
@@ -78,6 +109,23 @@ impl PatternParser for KvArrP {
         }
 
         Self::rename_duplicates(out, emitted_ranges);
+        if let Some(bucket) = rest {
+            Self::fill_missing(fpu, &seen, out);
+            if bucket != "_" && !extra.is_empty() {
+                Self::rename_duplicates(&mut extra, extra_ranges);
+                let mut obj = ObjectValue::default();
+                for field in extra {
+                    obj.insert(
+                        field.get_name().to_string(),
+                        FieldStorage::from_owned(field),
+                    );
+                }
+                limits::push_field(
+                    out,
+                    DataField::new_opt(DataType::Obj, Some(intern_name(&bucket)), obj.into()),
+                );
+            }
+        }
         Ok(())
     }
 
@@ -201,27 +249,33 @@ impl KvArrP {
         match value {
             Value::Null => Ok(()),
             Value::Bool(b) => {
-                out.push(DataField::from_bool(key, b));
+                limits::push_field(out, DataField::from_bool(intern_name(key), b));
                 Ok(())
             }
             Value::Number(num) => {
                 if let (true, Some(f)) = (num.is_f64(), num.as_f64()) {
-                    out.push(DataField::from_float(key, f));
+                    limits::push_field(out, DataField::from_float(intern_name(key), f));
                 } else if let Some(i) = num.as_i64() {
-                    out.push(DataField::from_digit(key, i));
+                    limits::push_field(out, DataField::from_digit(intern_name(key), i));
                 } else if let Some(u) = num.as_u64() {
                     if u <= i64::MAX as u64 {
-                        out.push(DataField::from_digit(key, u as i64));
+                        limits::push_field(out, DataField::from_digit(intern_name(key), u as i64));
                     } else {
-                        out.push(DataField::from_chars(key, num.to_string()));
+                        limits::push_field(
+                            out,
+                            DataField::from_chars(intern_name(key), num.to_string()),
+                        );
                     }
                 } else {
-                    out.push(DataField::from_chars(key, num.to_string()));
+                    limits::push_field(
+                        out,
+                        DataField::from_chars(intern_name(key), num.to_string()),
+                    );
                 }
                 Ok(())
             }
             Value::String(s) => {
-                out.push(DataField::from_chars(key, s));
+                limits::push_field(out, DataField::from_chars(intern_name(key), s));
                 Ok(())
             }
             Value::Array(vals) => {
@@ -251,6 +305,18 @@ impl KvArrP {
         }
     }
 
+    /// 声明但未出现的键补空字段
+    fn fill_missing(fpu: &FieldEvalUnit, seen: &HashSet<String>, out: &mut Vec<DataField>) {
+        let Some(subs) = fpu.conf().sub_fields.as_ref() else {
+            return;
+        };
+        for (key, conf) in subs.conf_items().exact_iter() {
+            if !seen.contains(key) {
+                limits::push_field(out, DataField::from_chars(conf.run_key_str(key), ""));
+            }
+        }
+    }
+
     fn rename_duplicates(out: &mut [DataField], emitted: Vec<(String, usize, usize)>) {
         let mut dup: HashMap<String, Vec<usize>> = HashMap::new();
         for (key, start, end) in emitted {
@@ -284,7 +350,7 @@ mod tests {
     use crate::types::AnyResult;
     use crate::{WplEvaluator, ast::WplField};
     use orion_error::TestAssert;
-    use wp_model_core::model::{DataField, DataRecord, data::Field};
+    use wp_model_core::model::{DataField, DataRecord, Value as FValue, data::Field};
 
     #[test]
     fn test_kvarr_with_commas() -> AnyResult<()> {
@@ -534,4 +600,76 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_kvarr_rest_collects_unknown_keys() -> AnyResult<()> {
+        let conf = WplField::try_parse("kvarr(chars@id, digit@version, chars@time, rest@extra)\\s")
+            .assert();
+        let mut data = "id=7 fw_new=x1 version=3 fw_flag=true time=\"10:00:00\"";
+        let parser = ParserTUnit::new(KvArrP::default(), conf);
+        let fields = parser.verify_parse_suc(&mut data).assert();
+        let record = DataRecord::from(fields);
+        assert_eq!(
+            record.field("version").map(|s| s.as_field()),
+            Some(&DataField::from_digit("version", 3))
+        );
+        assert_eq!(
+            record.field("time").map(|s| s.as_field()),
+            Some(&DataField::from_chars("time", "10:00:00"))
+        );
+        // 未声明键不再作为顶层字段出现
+        assert!(record.field("fw_new").is_none());
+        assert!(record.field("fw_flag").is_none());
+        let Some(FValue::Obj(extra)) = record.field("extra").map(|f| f.get_value().clone()) else {
+            panic!("extra bucket missing");
+        };
+        assert_eq!(
+            extra.get("fw_new").map(|f| f.get_value().clone()),
+            Some(FValue::Chars("x1".into()))
+        );
+        assert_eq!(
+            extra.get("fw_flag").map(|f| f.get_value().clone()),
+            Some(FValue::Bool(true))
+        );
+        assert!(extra.get("version").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_kvarr_rest_skip_and_missing_keys() -> AnyResult<()> {
+        let conf =
+            WplField::try_parse("kvarr(chars@id, digit@version, chars@time, rest@_)\\s").assert();
+        let mut data = "id=7 fw_new=x1 time=now fw_flag=true";
+        let parser = ParserTUnit::new(KvArrP::default(), conf);
+        let fields = parser.verify_parse_suc(&mut data).assert();
+        let record = DataRecord::from(fields);
+        assert!(record.field("fw_new").is_none());
+        assert!(record.field("fw_flag").is_none());
+        assert!(record.field("_").is_none());
+        // 声明但缺失的键补空字段
+        assert_eq!(
+            record.field("version").map(|s| s.as_field()),
+            Some(&DataField::from_chars("version", ""))
+        );
+        assert_eq!(
+            record.field("id").map(|s| s.as_field()),
+            Some(&DataField::from_chars("id", "7"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_kvarr_without_rest_keeps_unknown_keys() -> AnyResult<()> {
+        let conf = WplField::try_parse("kvarr(chars@id, digit@version)\\s").assert();
+        let mut data = "id=7 fw_new=x1 version=3";
+        let parser = ParserTUnit::new(KvArrP::default(), conf);
+        let fields = parser.verify_parse_suc(&mut data).assert();
+        let record = DataRecord::from(fields);
+        assert_eq!(
+            record.field("fw_new").map(|s| s.as_field()),
+            Some(&DataField::from_chars("fw_new", "x1"))
+        );
+        assert!(record.field("extra").is_none());
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@ use crate::eval::runtime::field::FieldEvalUnit;
 use crate::parser::utils::{quot_r_str, quot_str, window_path};
 use winnow::ascii::multispace0;
 use winnow::combinator::alt;
+use wp_data_model::limits;
 use wp_model_core::model::DataField;
 use wp_parser::Parser;
 use wp_parser::WResult as ModalResult;
@@ -34,7 +35,7 @@ pub fn take_sub_tdo(
                 let run_key = sub_fpu.conf().run_key(key);
                 Ok(sub_fpu.parse(e_id, &prefer_sep, &mut str_val, run_key, out)?)
             } else {
-                out.push(DataField::from_chars(key, str_val));
+                limits::push_field(out, DataField::from_chars(key, str_val));
                 Ok(())
             }
         }
@@ -48,7 +49,7 @@ pub fn take_sub_tdo(
                 let sep = fpu.conf().resolve_sep(upper_sep);
                 let val = sep.read_until_sep(data)?;
                 let trim_val = val.trim();
-                out.push(DataField::from_chars(key, trim_val));
+                limits::push_field(out, DataField::from_chars(key, trim_val));
                 Ok(())
             }
         }
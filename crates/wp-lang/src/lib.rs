@@ -26,7 +26,7 @@ pub use ast::WplSep;
 pub use ast::WplStatementType;
 pub use ast::ann_func::{AnnotationFunc, AnnotationType};
 pub use ast::{SepPattern, build_pattern};
-pub use ast::{WplExpress, WplPackage, WplPkgMeta};
+pub use ast::{WplExpress, WplPackage, WplPkgMeta, WplRuleOutput};
 pub use eval::DataTypeParser;
 pub use eval::OPTIMIZE_TIMES;
 pub use eval::PipeLineResult;
@@ -5,6 +5,7 @@ use orion_error::{ErrorCode, UvsReason};
 use thiserror::Error;
 use winnow::error::{ErrMode, Needed};
 use wp_error::util::split_string;
+use wp_parser::location::CodePos;
 // use wp_error::DataErrKind; // kept for potential future conversions
 
 fn translate_position(input: &[u8], index: usize) -> (usize, usize) {
@@ -112,6 +113,30 @@ pub type WplCodeResult<T> = Result<T, WplCodeError>;
 
 pub trait WPLCodeErrorTrait {
     fn from_syntax(e: ErrMode<ContextError>, code: &str, path: &str) -> Self;
+
+    /// 带行列号的语法错误：`code` 为完整输入，`rest` 为解析失败时剩余的输入
+    fn from_syntax_at(e: ErrMode<ContextError>, code: &str, rest: &str, path: &str) -> Self;
+
+    /// 由 `Parser::parse` 的错误生成带行列号的语法错误
+    fn from_parse_err(e: ParseError<&str, ContextError>, path: &str) -> Self;
+}
+
+/// 规则文件中的错误位置：`[行:列] error: ...`，附路径与带 `^` 指示的源码片段
+fn located_syntax(code: &str, pos: CodePos, msg: String, path: &str) -> WplCodeError {
+    let msg = if msg.is_empty() {
+        "wpl code parse fail".to_string()
+    } else {
+        msg
+    };
+    StructError::from(WplCodeReason::Syntax(format!(
+        "[{}] error: {}\n --> {}:{}\n{}",
+        pos,
+        msg,
+        path,
+        pos,
+        pos.snippet(code)
+    )))
+    .with_position(pos.to_string())
 }
 impl WPLCodeErrorTrait for StructError<WplCodeReason> {
     fn from_syntax(e: ErrMode<ContextError>, code: &str, path: &str) -> Self {
@@ -138,6 +163,24 @@ impl WPLCodeErrorTrait for StructError<WplCodeReason> {
             }
         }
     }
+
+    fn from_syntax_at(e: ErrMode<ContextError>, code: &str, rest: &str, path: &str) -> Self {
+        let e = match e {
+            ErrMode::Backtrack(e) | ErrMode::Cut(e) => e,
+            incomplete => return Self::from_syntax(incomplete, rest, path),
+        };
+        located_syntax(code, CodePos::locate(code, rest), e.to_string(), path)
+    }
+
+    fn from_parse_err(e: ParseError<&str, ContextError>, path: &str) -> Self {
+        let code = *e.input();
+        located_syntax(
+            code,
+            CodePos::at_offset(code, e.offset()),
+            e.inner().to_string(),
+            path,
+        )
+    }
 }
 
 /*
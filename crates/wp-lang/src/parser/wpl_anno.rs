@@ -1,5 +1,6 @@
 use crate::ast::AnnEnum;
 use crate::ast::AnnFun;
+use crate::ast::DupPolicy;
 use crate::ast::TagKvs;
 use crate::parser::utils;
 use smol_str::SmolStr;
@@ -35,13 +36,39 @@ fn copy_raw(input: &mut &str) -> WResult<AnnEnum> {
     Ok(AnnEnum::Copy(obj))
 }
 
+fn dup_policy(input: &mut &str) -> WResult<DupPolicy> {
+    alt((
+        literal("first").value(DupPolicy::First),
+        literal("last").value(DupPolicy::Last),
+        literal("index").value(DupPolicy::Index),
+        delimited(
+            (literal("concat"), multispace0, literal('(')),
+            delimited(multispace0, utils::quot_str, multispace0),
+            literal(')'),
+        )
+        .map(|sep: &str| DupPolicy::Concat(SmolStr::from(utils::decode_escapes(sep)))),
+    ))
+    .parse_next(input)
+}
+
+fn dup(input: &mut &str) -> WResult<AnnEnum> {
+    let policy = delimited(
+        (multispace0, literal("dup"), multispace0, literal('(')),
+        cut_err(delimited(multispace0, dup_policy, multispace0))
+            .context(ctx_desc("dup(first|last|concat(\",\")|index)")),
+        literal(')'),
+    )
+    .parse_next(input)?;
+    Ok(AnnEnum::Dup(policy))
+}
+
 pub fn ann_fun(input: &mut &str) -> WResult<AnnFun> {
     multispace0.parse_next(input)?;
     literal("#[")
         .context(ctx_desc("annotation start"))
         .parse_next(input)?;
     let x: Vec<AnnEnum> =
-        separated(0.., alt((wpl_tags, copy_raw)), literal(",")).parse_next(input)?;
+        separated(0.., alt((wpl_tags, copy_raw, dup)), literal(",")).parse_next(input)?;
     multispace0.parse_next(input)?;
     literal("]")
         .context(ctx_desc("annotation end"))
@@ -56,6 +83,9 @@ pub fn ann_fun(input: &mut &str) -> WResult<AnnFun> {
             AnnEnum::Tags(v) => {
                 af.tags = v;
             }
+            AnnEnum::Dup(v) => {
+                af.dup = Some(v);
+            }
         }
     }
     Ok(af)
@@ -65,7 +95,7 @@ pub fn ann_fun(input: &mut &str) -> WResult<AnnFun> {
 mod tests {
     use std::collections::BTreeMap;
 
-    use crate::ast::{AnnEnum, AnnFun};
+    use crate::ast::{AnnEnum, AnnFun, DupPolicy};
     use crate::parser::utils::take_tag_kv;
     use crate::parser::wpl_anno::{ann_fun, wpl_tags};
     use orion_error::TestAssert;
@@ -144,6 +174,7 @@ mod tests {
                     ("cc_y".into(), "qw_/e".into())
                 ]),
                 copy_raw: Some(("name".into(), "tq".into())),
+                dup: None,
             }
         );
 
@@ -161,6 +192,7 @@ mod tests {
                     ("cc_y".into(), "qw_/e".into())
                 ]),
                 copy_raw: None,
+                dup: None,
             }
         );
 
@@ -175,7 +207,30 @@ mod tests {
             AnnFun {
                 tags: Default::default(),
                 copy_raw: Some(("name".into(), "tq".into())),
+                dup: None,
             }
         );
     }
+
+    #[test]
+    fn test_dup_annotation() {
+        let cases = [
+            ("#[dup(first)]", DupPolicy::First),
+            ("#[dup( last )]", DupPolicy::Last),
+            ("#[dup(index)]", DupPolicy::Index),
+            ("#[dup(concat(','))]", DupPolicy::Concat(",".into())),
+            (r#"#[dup(concat(" | "))]"#, DupPolicy::Concat(" | ".into())),
+        ];
+        for (code, expect) in cases {
+            assert_eq!(ann_fun.parse(code).assert().dup, Some(expect));
+        }
+
+        let af = ann_fun
+            .parse(r#"#[tag(t:"x"), dup(last), copy_raw(name:"raw")]"#)
+            .assert();
+        assert_eq!(af.dup, Some(DupPolicy::Last));
+        assert_eq!(af.copy_raw, Some(("name".into(), "raw".into())));
+
+        assert!(ann_fun.parse("#[dup(random)]").is_err());
+    }
 }
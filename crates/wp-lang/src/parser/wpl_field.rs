@@ -196,20 +196,38 @@ fn wpl_id_field(input: &mut &str) -> ModalResult<(String, WplField)> {
     Ok((k, conf))
 }
 
+/// `rest@extra`：收集未声明键的桶
+fn wpl_rest_bucket(input: &mut &str) -> ModalResult<String> {
+    (
+        multispace0,
+        literal("rest"),
+        multispace0,
+        literal('@'),
+        take_ref_path_or_quoted,
+    )
+        .map(|x| x.4)
+        .parse_next(input)
+}
+
 fn wpl_field_subs(input: &mut &str) -> ModalResult<WplFieldSet> {
     let mut set: WildMap<WplField> = WildMap::new();
+    let mut rest = None;
     (multispace0, literal('('))
         .context(ctx_desc("sub field(...)"))
         .parse_next(input)?;
     while peek_next((multispace0, literal(')')), input).is_err() {
-        let (key, field) = wpl_id_field.parse_next(input)?;
-        set.insert(key, field);
+        if let Some(bucket) = opt(wpl_rest_bucket).parse_next(input)? {
+            rest = Some(bucket);
+        } else {
+            let (key, field) = wpl_id_field.parse_next(input)?;
+            set.insert(key, field);
+        }
         opt(literal(',')).parse_next(input)?;
     }
     (multispace0, literal(')'))
         .context(ctx_desc(") "))
         .parse_next(input)?;
-    Ok(WplFieldSet::from(set))
+    Ok(WplFieldSet::from(set).with_rest(rest))
 
     /*
     let opt_tag = (multispace0, opt(literal(',')), multispace0);
@@ -254,12 +272,16 @@ fn wpl_field_impl(input: &mut &str) -> ModalResult<WplField> {
 
     multispace0.parse_next(input)?;
     if peek_str("(", input).is_ok() {
-        conf.sub_fields = Option::from(
-            wpl_field_subs
-                .context(ctx_literal(conf.meta_type.static_name()))
-                .context(ctx_desc("sub define"))
-                .parse_next(input)?,
-        );
+        let subs = wpl_field_subs
+            .context(ctx_literal(conf.meta_type.static_name()))
+            .context(ctx_desc("sub define"))
+            .parse_next(input)?;
+        // 目前只有 kvarr 会收集未声明的键，其他解析器上的 rest@ 不会生效
+        if subs.rest().is_some() && conf.meta_type != DataType::KvArr {
+            fail.context(ctx_desc("rest@ is only supported by kvarr"))
+                .parse_next(input)?;
+        }
+        conf.sub_fields = Some(subs);
     }
 
     if peek_str(":", input).is_ok() {
@@ -561,6 +583,28 @@ mod tests {
         assert_eq!(conf.name, Some("dst-ip".into()));
     }
 
+    #[test]
+    fn test_rest_bucket() {
+        let set = wpl_field_subs
+            .parse("(@id, digit@version, rest@extra, time@time)")
+            .assert();
+        assert_eq!(set.rest(), &Some("extra".to_string()));
+        assert!(set.get("version").is_some());
+        assert!(set.get("time").is_some());
+        assert!(set.to_string().contains("rest@extra"));
+
+        let set = wpl_field_subs.parse("(@id)").assert();
+        assert_eq!(set.rest(), &None);
+
+        let field = wpl_field.parse("kvarr(@id, rest@extra)").assert();
+        assert_eq!(
+            field.sub_fields.as_ref().and_then(|s| s.rest().clone()),
+            Some("extra".to_string())
+        );
+        assert!(wpl_field.parse("json(@id, rest@extra)").is_err());
+        assert!(wpl_field.parse("kv(@id, rest@extra)").is_err());
+    }
+
     #[test]
     fn test_parse_field_conf() {
         let fmt = WplFieldFmt::default();
@@ -0,0 +1,68 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use oml::core::DataTransformer;
+use oml::language::{CompiledRegex, MatchAble, MatchCond, MatchFun};
+use oml::parser::oml_parse_raw;
+use wp_data_model::cache::FieldQueryCache;
+use wp_model_core::model::{DataField, DataRecord};
+
+const PATTERNS: &[&str] = &[
+    r"^\[ERROR\]",
+    r"^\[WARN\]",
+    r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}",
+    r"(?i)timeout|refused|unreachable",
+];
+
+fn bench_regex_cond(c: &mut Criterion) {
+    let value = DataField::from_chars("msg", "2024-05-01T10:20:30 connection refused by peer");
+    let compiled: Vec<MatchCond> = PATTERNS
+        .iter()
+        .map(|p| MatchCond::Regex(CompiledRegex::new(p).expect("valid bench regex")))
+        .collect();
+    let by_fun: Vec<MatchCond> = PATTERNS
+        .iter()
+        .map(|p| MatchCond::Fun(MatchFun::new("regex_match", Some(*p))))
+        .collect();
+
+    let mut group = c.benchmark_group("oml_regex_cond");
+    group.bench_function("compiled", |b| {
+        b.iter(|| compiled.iter().filter(|c| c.is_match(&value)).count())
+    });
+    group.bench_function("match_fun", |b| {
+        b.iter(|| by_fun.iter().filter(|c| c.is_match(&value)).count())
+    });
+    group.finish();
+}
+
+fn bench_regex_model(c: &mut Criterion) {
+    let mut code = r#"
+name : bench_regex
+---
+level = match read(msg) {
+    regex_match('^\[ERROR\]') => chars(error),
+    regex_match('^\[WARN\]') => chars(warn),
+    regex_match('^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}') => chars(dated),
+    _ => chars(other),
+};
+net = match read(msg) {
+    regex_match('(?i)timeout|refused|unreachable') => chars(network),
+    _ => chars(none),
+};
+"#;
+    let model = oml_parse_raw(&mut code).expect("parse OML model for regex bench");
+    let input = DataRecord::from(vec![DataField::from_chars(
+        "msg",
+        "2024-05-01T10:20:30 connection refused by peer",
+    )]);
+
+    c.bench_function("oml_regex_model_transform", |b| {
+        let mut cache = FieldQueryCache::default();
+        b.iter_batched(
+            || input.clone(),
+            |data| model.transform(data, &mut cache),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_regex_cond, bench_regex_model);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+//! OML `assert` 语句的运行期开关与失败记录。
+//! - `compile_assert = false`（生产模式）时断言节点整体跳过，零开销。
+//! - 默认断言失败只打 WARN 日志；严格模式下记录失败，由 `ObjModel::try_transform_ref` 转为 `OMLRunError`。
+//! - 使用线程局部缓冲，与 [`super::diagnostics`] 一致，避免改动求值接口。
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use orion_error::ToStructError;
+
+use super::{OMLRunError, OMLRunReason};
+
+/// 是否执行断言（默认开启，对应引擎配置 `[oml].compile_assert`）
+static ASSERT_ENABLED: AtomicBool = AtomicBool::new(true);
+/// 断言失败是否视为转换错误（默认关闭，对应引擎配置 `[oml].assert_strict`）
+static ASSERT_STRICT: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static FAILURE: RefCell<Option<OMLRunReason>> = const { RefCell::new(None) };
+}
+
+/// 设置断言开关（由主 crate 在启动时调用）
+pub fn set_compile_assert(v: bool) {
+    ASSERT_ENABLED.store(v, Ordering::Relaxed);
+}
+
+pub fn is_assert_enabled() -> bool {
+    ASSERT_ENABLED.load(Ordering::Relaxed)
+}
+
+/// 设置严格模式（由主 crate 在启动时调用）
+pub fn set_assert_strict(v: bool) {
+    ASSERT_STRICT.store(v, Ordering::Relaxed);
+}
+
+pub fn is_assert_strict() -> bool {
+    ASSERT_STRICT.load(Ordering::Relaxed)
+}
+
+pub fn reset() {
+    FAILURE.with(|f| f.borrow_mut().take());
+}
+
+/// 记录本条记录的首个断言失败
+pub fn record_failure(detail: String) {
+    record_reason(OMLRunReason::AssertFailed(detail));
+}
+
+/// 记录本条记录的首个转换失败（断言失败、记录超限等）
+pub(crate) fn record_reason(reason: OMLRunReason) {
+    FAILURE.with(|f| {
+        let mut slot = f.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(reason);
+        }
+    });
+}
+
+pub fn take_failure() -> Option<OMLRunError> {
+    take_reason().map(|reason| reason.to_err())
+}
+
+/// 取出失败原因（跨线程转交时使用，由接收线程重新 [`record_reason`]）
+pub(crate) fn take_reason() -> Option<OMLRunReason> {
+    FAILURE.with(|f| f.borrow_mut().take())
+}
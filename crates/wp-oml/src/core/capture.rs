@@ -0,0 +1,100 @@
+//! `match` 分支的命名捕获：`regex_match('(?P<name>...)')` 命中时记录命名分组，
+//! 供该分支结果表达式中的 `read_capture("name")` 读取。
+//! - 条件求值时写入暂存区；分支命中后由 [`CaptureScope`] 转为当前帧，结果求值结束即弹出。
+//! - 每个分支求值前清空暂存区，未命中分支（含多源条件中部分命中）的捕获不会带入后续分支。
+//! - 帧按栈组织，结果表达式中嵌套的 `match` 不会覆盖外层分支的捕获。
+//! - 使用线程局部缓冲，与 [`super::assertion`] 一致，避免改动求值接口。
+
+use std::cell::RefCell;
+
+type Captures = Vec<(String, String)>;
+
+#[derive(Default)]
+struct CaptureContext {
+    staged: Captures,
+    frames: Vec<Captures>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<CaptureContext> = RefCell::new(CaptureContext::default());
+}
+
+/// 条件求值前清空暂存区
+pub fn reset_staged() {
+    CONTEXT.with(|c| c.borrow_mut().staged.clear());
+}
+
+/// 记录一次正则命中的命名分组（未参与匹配的分组不记录）
+pub fn stage(re: &regex::Regex, text: &str) -> bool {
+    let Some(caps) = re.captures(text) else {
+        return false;
+    };
+    CONTEXT.with(|c| {
+        let mut ctx = c.borrow_mut();
+        for name in re.capture_names().flatten() {
+            if let Some(m) = caps.name(name) {
+                ctx.staged.push((name.to_string(), m.as_str().to_string()));
+            }
+        }
+    });
+    true
+}
+
+/// 读取当前分支的命名捕获
+pub fn get(name: &str) -> Option<String> {
+    CONTEXT.with(|c| {
+        c.borrow()
+            .frames
+            .last()
+            .and_then(|frame| frame.iter().find(|(k, _)| k == name))
+            .map(|(_, v)| v.clone())
+    })
+}
+
+/// 命中分支的捕获作用域：创建时接管暂存区，析构时弹出
+pub struct CaptureScope(());
+
+impl CaptureScope {
+    pub fn enter() -> Self {
+        CONTEXT.with(|c| {
+            let mut ctx = c.borrow_mut();
+            let frame = std::mem::take(&mut ctx.staged);
+            ctx.frames.push(frame);
+        });
+        Self(())
+    }
+}
+
+impl Drop for CaptureScope {
+    fn drop(&mut self) {
+        CONTEXT.with(|c| {
+            c.borrow_mut().frames.pop();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_isolates_cases() {
+        let re = regex::Regex::new(r"level=(?P<l>\w+)(?: user=(?P<u>\w+))?").unwrap();
+        reset_staged();
+        assert!(stage(&re, "level=warn"));
+        {
+            let _scope = CaptureScope::enter();
+            assert_eq!(get("l").as_deref(), Some("warn"));
+            // 未参与匹配的分组不记录
+            assert_eq!(get("u"), None);
+            {
+                reset_staged();
+                let _inner = CaptureScope::enter();
+                assert_eq!(get("l"), None);
+            }
+            assert_eq!(get("l").as_deref(), Some("warn"));
+        }
+        assert_eq!(get("l"), None);
+        assert!(!stage(&re, "nothing"));
+    }
+}
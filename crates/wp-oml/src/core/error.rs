@@ -5,6 +5,10 @@ use thiserror::Error;
 pub enum OMLRunReason {
     #[error("format conv fail{0}")]
     FmtConv(String),
+    #[error("assert failed: {0}")]
+    AssertFailed(String),
+    #[error("record limit exceeded: {0}")]
+    LimitExceeded(String),
 }
 impl DomainReason for OMLRunReason {}
 
@@ -0,0 +1,321 @@
+//! `group_by` 有状态聚合：与逐条转换的 `ObjModel` 分离，由宿主（sink 组）持有状态。
+//!
+//! 宿主把模型转换后的记录 [`AggregateEvaluator::feed`] 进来，定时调用
+//! [`AggregateEvaluator::poll`]；到达 `emit_every` 时按组输出汇总记录并清空状态。
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::Instant;
+
+use wp_model_core::model::{DataField, DataRecord, Value};
+
+use crate::language::{AggregateExpr, AggregateOp};
+
+/// 分组键：各 key 字段值的字符串形式，缺失字段记为空串
+pub type GroupKey = Vec<String>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Num {
+    Int(i64),
+    Float(f64),
+}
+
+impl Num {
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Digit(i) => Some(Num::Int(*i)),
+            Value::Float(f) => Some(Num::Float(*f)),
+            Value::Chars(s) => {
+                let s = s.to_string();
+                s.parse::<i64>()
+                    .map(Num::Int)
+                    .or_else(|_| s.parse::<f64>().map(Num::Float))
+                    .ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Num::Int(i) => i as f64,
+            Num::Float(f) => f,
+        }
+    }
+
+    fn to_field(self, name: &str) -> DataField {
+        match self {
+            Num::Int(i) => DataField::from_digit(name, i),
+            Num::Float(f) => DataField::from_float(name, f),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Accumulator {
+    Count(u64),
+    Sum(Option<Num>),
+    Min(Option<Num>),
+    Max(Option<Num>),
+    Distinct {
+        seen: HashSet<String>,
+        values: Vec<DataField>,
+    },
+}
+
+impl Accumulator {
+    fn new(expr: &AggregateExpr) -> Self {
+        match expr {
+            AggregateExpr::Count => Accumulator::Count(0),
+            AggregateExpr::Sum(_) => Accumulator::Sum(None),
+            AggregateExpr::Min(_) => Accumulator::Min(None),
+            AggregateExpr::Max(_) => Accumulator::Max(None),
+            AggregateExpr::CollectDistinct(_) => Accumulator::Distinct {
+                seen: HashSet::new(),
+                values: Vec::new(),
+            },
+        }
+    }
+
+    fn update(&mut self, field: Option<&DataField>) {
+        match self {
+            Accumulator::Count(n) => *n += 1,
+            Accumulator::Sum(acc) => {
+                if let Some(v) = field.and_then(|f| Num::of(f.get_value())) {
+                    *acc = Some(match (*acc, v) {
+                        (None, v) => v,
+                        (Some(Num::Int(a)), Num::Int(b)) => Num::Int(a.wrapping_add(b)),
+                        (Some(a), b) => Num::Float(a.as_f64() + b.as_f64()),
+                    });
+                }
+            }
+            Accumulator::Min(acc) => {
+                if let Some(v) = field.and_then(|f| Num::of(f.get_value()))
+                    && acc.is_none_or(|cur| v.as_f64() < cur.as_f64())
+                {
+                    *acc = Some(v);
+                }
+            }
+            Accumulator::Max(acc) => {
+                if let Some(v) = field.and_then(|f| Num::of(f.get_value()))
+                    && acc.is_none_or(|cur| v.as_f64() > cur.as_f64())
+                {
+                    *acc = Some(v);
+                }
+            }
+            Accumulator::Distinct { seen, values } => {
+                if let Some(f) = field
+                    && seen.insert(f.get_value().to_string())
+                {
+                    values.push(f.clone());
+                }
+            }
+        }
+    }
+
+    fn finish(self, name: &str) -> DataField {
+        match self {
+            Accumulator::Count(n) => DataField::from_digit(name, n as i64),
+            Accumulator::Sum(acc) => acc.unwrap_or(Num::Int(0)).to_field(name),
+            Accumulator::Min(acc) | Accumulator::Max(acc) => match acc {
+                Some(v) => v.to_field(name),
+                None => DataField::from_chars(name, ""),
+            },
+            Accumulator::Distinct { values, .. } => DataField::from_arr(name, values),
+        }
+    }
+}
+
+/// 单个分组的累积状态
+#[derive(Debug, Clone)]
+struct AccumulatorState {
+    keys: Vec<DataField>,
+    accs: Vec<Accumulator>,
+}
+
+/// `group_by` 聚合器；按 [`GroupKey`] 排序输出，便于下游比对
+#[derive(Debug, Clone)]
+pub struct AggregateEvaluator {
+    op: AggregateOp,
+    groups: BTreeMap<GroupKey, AccumulatorState>,
+    last_emit: Instant,
+}
+
+impl AggregateEvaluator {
+    pub fn new(op: AggregateOp) -> Self {
+        Self::start_at(op, Instant::now())
+    }
+
+    pub fn start_at(op: AggregateOp, now: Instant) -> Self {
+        Self {
+            op,
+            groups: BTreeMap::new(),
+            last_emit: now,
+        }
+    }
+
+    pub fn op(&self) -> &AggregateOp {
+        &self.op
+    }
+
+    /// 当前窗口内的分组数
+    pub fn group_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// 累积一条（已转换的）记录
+    pub fn feed(&mut self, record: &DataRecord) {
+        let key_fields = self.op.key_fields();
+        let mut key = Vec::with_capacity(key_fields.len());
+        for name in key_fields {
+            let value = record
+                .field(name)
+                .map(|f| f.as_field().get_value().to_string())
+                .unwrap_or_default();
+            key.push(value);
+        }
+        let aggs = self.op.aggs();
+        let state = self.groups.entry(key).or_insert_with(|| AccumulatorState {
+            keys: key_fields
+                .iter()
+                .map(|name| match record.field(name) {
+                    Some(f) => f.as_field().clone(),
+                    None => DataField::from_chars(name.as_str(), ""),
+                })
+                .collect(),
+            accs: aggs
+                .iter()
+                .map(|item| Accumulator::new(item.expr()))
+                .collect(),
+        });
+        for (acc, item) in state.accs.iter_mut().zip(aggs) {
+            let field = item
+                .expr()
+                .field()
+                .and_then(|name| record.field(name))
+                .map(|f| f.as_field());
+            acc.update(field);
+        }
+    }
+
+    /// 到达输出周期时返回汇总记录并开始新窗口，否则返回空
+    pub fn poll(&mut self, now: Instant) -> Vec<DataRecord> {
+        if now.saturating_duration_since(self.last_emit) < self.op.emit_every() {
+            return Vec::new();
+        }
+        self.drain(now)
+    }
+
+    /// 立即输出全部分组（如退出、模型切换时）
+    pub fn drain(&mut self, now: Instant) -> Vec<DataRecord> {
+        self.last_emit = now;
+        let groups = std::mem::take(&mut self.groups);
+        let aggs = self.op.aggs();
+        groups
+            .into_values()
+            .map(|state| {
+                let mut record = DataRecord::default();
+                for key in state.keys {
+                    record.append(key);
+                }
+                for (acc, item) in state.accs.into_iter().zip(aggs) {
+                    record.append(acc.finish(item.target()));
+                }
+                record
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::AggregateItem;
+    use std::time::Duration;
+    use wp_model_core::model::FieldStorage;
+
+    fn rec(src: &str, bytes: i64, user: &str) -> DataRecord {
+        DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_chars("src", src)),
+            FieldStorage::from_owned(DataField::from_digit("bytes", bytes)),
+            FieldStorage::from_owned(DataField::from_chars("user", user)),
+        ])
+    }
+
+    fn op() -> AggregateOp {
+        AggregateOp::GroupBy {
+            key_fields: vec!["src".to_string()],
+            aggs: vec![
+                AggregateItem::new("hits", AggregateExpr::Count),
+                AggregateItem::new("total", AggregateExpr::Sum("bytes".into())),
+                AggregateItem::new("lo", AggregateExpr::Min("bytes".into())),
+                AggregateItem::new("hi", AggregateExpr::Max("bytes".into())),
+                AggregateItem::new("users", AggregateExpr::CollectDistinct("user".into())),
+            ],
+            emit_every: Duration::from_secs(60),
+        }
+    }
+
+    fn value_of(record: &DataRecord, name: &str) -> Value {
+        record
+            .field(name)
+            .map(|f| f.as_field().get_value().clone())
+            .unwrap_or_else(|| panic!("missing {name}"))
+    }
+
+    #[test]
+    fn group_by_accumulates_and_emits_per_window() {
+        let t0 = Instant::now();
+        let mut agg = AggregateEvaluator::start_at(op(), t0);
+        agg.feed(&rec("10.0.0.2", 5, "bob"));
+        agg.feed(&rec("10.0.0.1", 10, "alice"));
+        agg.feed(&rec("10.0.0.1", 30, "bob"));
+        agg.feed(&rec("10.0.0.1", 20, "alice"));
+        assert_eq!(agg.group_count(), 2);
+
+        assert!(agg.poll(t0 + Duration::from_secs(59)).is_empty());
+        let out = agg.poll(t0 + Duration::from_secs(60));
+        assert_eq!(out.len(), 2);
+        assert_eq!(agg.group_count(), 0);
+
+        let first = &out[0];
+        assert_eq!(value_of(first, "src").to_string(), "10.0.0.1");
+        assert_eq!(value_of(first, "hits"), Value::Digit(3));
+        assert_eq!(value_of(first, "total"), Value::Digit(60));
+        assert_eq!(value_of(first, "lo"), Value::Digit(10));
+        assert_eq!(value_of(first, "hi"), Value::Digit(30));
+        match value_of(first, "users") {
+            Value::Array(users) => {
+                let names: Vec<String> = users
+                    .iter()
+                    .map(|u| u.as_field().get_value().to_string())
+                    .collect();
+                assert_eq!(names, vec!["alice", "bob"]);
+            }
+            other => panic!("users expected array, got {:?}", other),
+        }
+        assert_eq!(value_of(&out[1], "hits"), Value::Digit(1));
+
+        // 新窗口从上次输出时刻计时
+        agg.feed(&rec("10.0.0.3", 1, "eve"));
+        assert!(agg.poll(t0 + Duration::from_secs(90)).is_empty());
+        assert_eq!(agg.poll(t0 + Duration::from_secs(120)).len(), 1);
+    }
+
+    #[test]
+    fn sum_promotes_to_float_and_skips_non_numeric() {
+        let mut agg = AggregateEvaluator::new(op());
+        agg.feed(&rec("a", 1, "x"));
+        agg.feed(&DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_chars("src", "a")),
+            FieldStorage::from_owned(DataField::from_float("bytes", 0.5)),
+        ]));
+        agg.feed(&DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_chars("src", "a")),
+            FieldStorage::from_owned(DataField::from_chars("bytes", "n/a")),
+        ]));
+        let out = agg.drain(Instant::now());
+        assert_eq!(value_of(&out[0], "hits"), Value::Digit(3));
+        assert_eq!(value_of(&out[0], "total"), Value::Float(1.5));
+        assert_eq!(value_of(&out[0], "lo"), Value::Float(0.5));
+    }
+}
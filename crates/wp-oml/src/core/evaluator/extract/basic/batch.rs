@@ -3,6 +3,7 @@ use crate::core::prelude::*;
 use crate::language::BatchEvalTarget;
 use crate::language::{BatchEvalExp, BatchEvaluation, RecordOperation};
 use wp_data_model::cache::FieldQueryCache;
+use wp_data_model::limits;
 use wp_model_core::model::{DataField, DataRecord, FieldStorage};
 
 impl ExpEvaluator for BatchEvalExp {
@@ -23,9 +24,9 @@ impl ExpEvaluator for BatchEvalExp {
                 .unwrap_or_else(|| "_".to_string());
             diagnostics::push(OmlIssue::new(OmlIssueKind::BatchNoMatch, pat));
         }
-        let mut wrapped_needs: Vec<FieldStorage> =
-            needs.into_iter().map(FieldStorage::from_owned).collect();
-        dst.items.append(&mut wrapped_needs);
+        for field in needs {
+            limits::push_storage(&mut dst.items, FieldStorage::from_owned(field));
+        }
     }
 }
 
@@ -68,7 +69,10 @@ impl BatchFetcher for RecordOperation {
 mod tests {
     use crate::core::DataRecordRef;
     use crate::core::evaluator::traits::ExpEvaluator;
-    use crate::language::BatchEvalExp;
+    use crate::language::{
+        BatchEvalExp, BatchEvalExpBuilder, BatchEvalTarget, BatchEvaluation,
+        EvaluationTargetBuilder, RecordOperation,
+    };
     use wp_data_model::cache::FieldQueryCache;
     use wp_model_core::model::{DataField, DataRecord, DataType, FieldStorage};
 
@@ -124,4 +128,37 @@ mod tests {
         assert_eq!(expect, needs);
         assert_eq!(src_ref.len(), 3);
     }
+
+    #[test]
+    fn test_value_arr_except() {
+        let cache = &mut FieldQueryCache::default();
+
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("A1/path", "a1")),
+            FieldStorage::from_owned(DataField::from_chars("A2/path", "a2")),
+            FieldStorage::from_owned(DataField::from_chars("B1/path", "b1")),
+            FieldStorage::from_owned(DataField::from_chars("B2/path", "b2")),
+        ];
+        let src = DataRecord::from(data.clone());
+        let expect = DataRecord::from(vec![FieldStorage::from_owned(DataField::from_chars(
+            "B2/path", "b2",
+        ))]);
+
+        let origin = EvaluationTargetBuilder::default()
+            .name(Some("*/path".to_string()))
+            .data_type(DataType::Auto)
+            .exclude(vec!["A*/path".to_string(), "B1/path".to_string()])
+            .build()
+            .unwrap();
+        let target = BatchEvalExpBuilder::default()
+            .target(BatchEvalTarget::new(origin))
+            .eval_way(BatchEvaluation::Get(RecordOperation::default()))
+            .build()
+            .unwrap();
+        let mut needs = DataRecord::default();
+        let mut src_ref = DataRecordRef::from(&src);
+        target.eval_proc(&mut src_ref, &mut needs, cache);
+        assert_eq!(expect, needs);
+        assert_eq!(src_ref.len(), 3);
+    }
 }
@@ -0,0 +1,25 @@
+use crate::core::capture;
+use crate::core::prelude::*;
+use crate::language::FieldCapture;
+use wp_model_core::model::FieldStorage;
+
+impl FieldExtractor for FieldCapture {
+    fn extract_one(
+        &self,
+        target: &EvaluationTarget,
+        _src: &mut DataRecordRef<'_>,
+        _dst: &DataRecord,
+    ) -> Option<DataField> {
+        capture::get(self.capture_name()).map(|v| DataField::from_chars(target.safe_name(), v))
+    }
+
+    fn extract_storage(
+        &self,
+        target: &EvaluationTarget,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+    ) -> Option<FieldStorage> {
+        self.extract_one(target, src, dst)
+            .map(FieldStorage::from_owned)
+    }
+}
@@ -2,6 +2,7 @@ use crate::core::prelude::*;
 use crate::{core::FieldCollector, language::DirectAccessor};
 use wp_model_core::model::FieldStorage;
 mod batch;
+mod capture;
 mod read;
 mod take;
 
@@ -15,6 +16,7 @@ impl FieldCollector for DirectAccessor {
         match self {
             DirectAccessor::Take(o) => o.collect_item(name, src, dst),
             DirectAccessor::Read(o) => o.collect_item(name, src, dst),
+            DirectAccessor::Capture(_) => Vec::new(),
         }
     }
 }
@@ -29,6 +31,7 @@ impl FieldExtractor for DirectAccessor {
         match self {
             DirectAccessor::Take(o) => o.extract_one(target, src, dst),
             DirectAccessor::Read(o) => o.extract_one(target, src, dst),
+            DirectAccessor::Capture(o) => o.extract_one(target, src, dst),
         }
     }
 
@@ -51,6 +54,7 @@ impl FieldExtractor for DirectAccessor {
         match self {
             DirectAccessor::Take(o) => o.extract_more(src, dst, cache),
             DirectAccessor::Read(o) => o.extract_more(src, dst, cache),
+            DirectAccessor::Capture(o) => o.extract_more(src, dst, cache),
         }
     }
 
@@ -58,6 +62,7 @@ impl FieldExtractor for DirectAccessor {
         match self {
             DirectAccessor::Take(o) => o.support_batch(),
             DirectAccessor::Read(o) => o.support_batch(),
+            DirectAccessor::Capture(o) => o.support_batch(),
         }
     }
 }
@@ -1,7 +1,9 @@
 use crate::core::prelude::*;
-use crate::language::ArrOperation;
+use crate::language::{ArrOperation, SortOrder};
 
-use wp_model_core::model::{DataField, DataRecord, FieldStorage};
+use std::cmp::Ordering;
+use std::net::IpAddr;
+use wp_model_core::model::{DataField, DataRecord, FieldStorage, Value};
 
 use crate::core::FieldExtractor;
 
@@ -13,12 +15,18 @@ impl FieldExtractor for ArrOperation {
         dst: &DataRecord,
     ) -> Option<DataField> {
         let target_name = target.name().clone().unwrap_or("_".to_string());
-        let arr = self.dat_crate.collect_item(target_name.as_str(), src, dst);
-        if !arr.is_empty() {
-            Some(DataField::from_arr(target_name, arr))
-        } else {
-            None
+        let mut arr = self.dat_crate.collect_item(target_name.as_str(), src, dst);
+        if arr.is_empty() {
+            return None;
         }
+        if let Some((key, order)) = self.order_by() {
+            sort_fields(&mut arr, key, *order);
+        }
+        let mut field = DataField::from_arr(target_name, arr);
+        for pipe in self.pipes() {
+            field = pipe.value_cacu(field);
+        }
+        Some(field)
     }
 
     fn extract_storage(
@@ -31,3 +39,185 @@ impl FieldExtractor for ArrOperation {
             .map(FieldStorage::from_owned)
     }
 }
+
+/// 稳定排序：对象元素取 `key` 子字段，其它元素取自身值。
+/// 类型次序固定为 数值 < 字符串 < IP < 其它 < 缺失，方向只作用于同类值之间。
+fn sort_fields(arr: &mut [DataField], key: &str, order: SortOrder) {
+    arr.sort_by(|a, b| {
+        let (a, b) = (sort_value(a, key), sort_value(b, key));
+        match sort_rank(a).cmp(&sort_rank(b)) {
+            Ordering::Equal => {
+                let ord = compare_same_rank(a, b);
+                match order {
+                    SortOrder::Asc => ord,
+                    SortOrder::Desc => ord.reverse(),
+                }
+            }
+            ord => ord,
+        }
+    });
+}
+
+fn sort_value<'a>(field: &'a DataField, key: &str) -> Option<&'a Value> {
+    match field.get_value() {
+        Value::Obj(obj) => obj.get(key).map(|f| f.get_value()),
+        other => Some(other),
+    }
+}
+
+fn sort_rank(value: Option<&Value>) -> u8 {
+    match value {
+        Some(Value::Digit(_)) | Some(Value::Float(_)) => 0,
+        Some(Value::Chars(_)) => 1,
+        Some(Value::IpAddr(_)) => 2,
+        Some(_) => 3,
+        None => 4,
+    }
+}
+
+fn compare_same_rank(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Digit(x)), Some(Value::Digit(y))) => x.cmp(y),
+        (Some(Value::Chars(x)), Some(Value::Chars(y))) => x.as_str().cmp(y.as_str()),
+        (Some(Value::IpAddr(x)), Some(Value::IpAddr(y))) => ip_key(x).cmp(&ip_key(y)),
+        (Some(x), Some(y)) => match (as_f64(x), as_f64(y)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            _ => Ordering::Equal,
+        },
+        _ => Ordering::Equal,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Digit(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// IPv4 排在 IPv6 之前，各自按数值比较
+fn ip_key(ip: &IpAddr) -> (u8, u128) {
+    match ip {
+        IpAddr::V4(v4) => (4, u32::from(*v4) as u128),
+        IpAddr::V6(v6) => (6, u128::from(*v6)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use std::net::{IpAddr, Ipv4Addr};
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord, Value};
+
+    fn collect_sorted(conf: &str, src: DataRecord) -> Vec<Value> {
+        let mut conf = conf;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, &mut FieldQueryCache::default());
+        match target.get_field_owned("out").map(|f| f.value) {
+            Some(Value::Array(arr)) => arr.into_iter().map(|f| f.value).collect(),
+            other => panic!("expected array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn order_by_digit_and_chars() {
+        let src = DataRecord::from(vec![
+            DataField::from_digit("p1", 514),
+            DataField::from_digit("p2", 22),
+            DataField::from_digit("p3", 8080),
+        ]);
+        let values = collect_sorted(
+            r#"
+            name : test
+            ---
+            out = collect read(keys:[p1,p2,p3]) order_by(_, desc) ;
+            "#,
+            src,
+        );
+        assert_eq!(
+            values,
+            vec![Value::Digit(8080), Value::Digit(514), Value::Digit(22)]
+        );
+
+        let src = DataRecord::from(vec![
+            DataField::from_chars("h1", "web"),
+            DataField::from_chars("h2", "cache"),
+            DataField::from_chars("h3", "db"),
+        ]);
+        let values = collect_sorted(
+            r#"
+            name : test
+            ---
+            out = collect read(keys:[h1,h2,h3]) order_by(_) ;
+            "#,
+            src,
+        );
+        assert_eq!(
+            values,
+            vec![
+                Value::Chars("cache".into()),
+                Value::Chars("db".into()),
+                Value::Chars("web".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn order_by_ip_and_mixed_types() {
+        let ip = |s: &str| IpAddr::V4(s.parse::<Ipv4Addr>().unwrap());
+        let src = DataRecord::from(vec![
+            DataField::from_ip("a", ip("10.0.0.10")),
+            DataField::from_chars("b", "zeta"),
+            DataField::from_ip("c", ip("10.0.0.9")),
+            DataField::from_digit("d", 7),
+            DataField::from_chars("e", "alpha"),
+        ]);
+        let values = collect_sorted(
+            r#"
+            name : test
+            ---
+            out = collect read(keys:[a,b,c,d,e]) order_by(_, asc) ;
+            "#,
+            src,
+        );
+        // 数值 < 字符串 < IP；IP 按数值而非字典序
+        assert_eq!(
+            values,
+            vec![
+                Value::Digit(7),
+                Value::Chars("alpha".into()),
+                Value::Chars("zeta".into()),
+                Value::IpAddr(ip("10.0.0.9")),
+                Value::IpAddr(ip("10.0.0.10")),
+            ]
+        );
+    }
+
+    #[test]
+    fn order_by_runs_before_pipes_and_round_trips() {
+        let mut conf = r#"
+            name : test
+            ---
+            out = collect read(keys:[p1,p2]) order_by(_, desc) | nth(0) ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let printed = model.to_string();
+        assert!(printed.contains("order_by(_, desc)"));
+        let mut reparse = printed.as_str();
+        assert_eq!(oml_parse_raw(&mut reparse).assert().to_string(), printed);
+
+        let src = DataRecord::from(vec![
+            DataField::from_digit("p1", 1),
+            DataField::from_digit("p2", 2),
+        ]);
+        let target = model.transform(src, &mut FieldQueryCache::default());
+        assert_eq!(
+            target.get_field_owned("out").map(|f| f.value),
+            Some(Value::Digit(2))
+        );
+    }
+}
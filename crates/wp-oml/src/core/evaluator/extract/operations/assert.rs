@@ -0,0 +1,99 @@
+use crate::core::assertion;
+use crate::core::prelude::*;
+use crate::language::AssertExpr;
+use wp_data_model::compare::compare_datafield;
+
+impl ExpEvaluator for AssertExpr {
+    fn eval_proc(
+        &self,
+        src: &mut DataRecordRef<'_>,
+        dst: &mut DataRecord,
+        _cache: &mut FieldQueryCache,
+    ) {
+        if !assertion::is_assert_enabled() {
+            return;
+        }
+        let cond = self.cond();
+        let target = EvaluationTarget::auto_default();
+        let left = cond.left().extract_one(&target, src, dst);
+        let right = cond.right().extract_one(&target, src, dst);
+        let passed = match (&left, &right) {
+            (Some(l), Some(r)) => compare_datafield(l, r, *cond.op()),
+            _ => false,
+        };
+        if passed {
+            return;
+        }
+        let show = |v: &Option<DataField>| {
+            v.as_ref()
+                .map(|f| f.get_value().to_string())
+                .unwrap_or_else(|| "<missing>".to_string())
+        };
+        let detail = format!(
+            "{}{} (left={}, right={})",
+            self.message()
+                .as_ref()
+                .map(|m| format!("{}: ", m))
+                .unwrap_or_default(),
+            cond,
+            show(&left),
+            show(&right)
+        );
+        if assertion::is_assert_strict() {
+            assertion::record_failure(detail);
+        } else {
+            warn_data!("oml assert failed: {}", detail);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::assertion;
+    use crate::parser::oml_parse_raw;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord, FieldStorage};
+
+    fn run(code: &str, strict: bool) -> (DataRecord, bool) {
+        let mut code = code;
+        let model = oml_parse_raw(&mut code).expect("parse oml");
+        let src = DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_chars("status", "active")),
+            FieldStorage::from_owned(DataField::from_chars("items", "a,b")),
+        ]);
+        assertion::set_assert_strict(strict);
+        let res = model.try_transform_ref(&src, &mut FieldQueryCache::default());
+        assertion::set_assert_strict(false);
+        match res {
+            Ok(out) => (out, false),
+            Err(_) => (DataRecord::default(), true),
+        }
+    }
+
+    #[test]
+    fn assert_pass_and_fail() {
+        let code = r#"
+name : test
+---
+status = read(status) ;
+assert read(status) == chars(active) ;
+assert read(items) | length > digit(0), "items must not be empty" ;
+"#;
+        let (out, failed) = run(code, true);
+        assert!(!failed);
+        assert_eq!(out.items.len(), 1);
+
+        let code = r#"
+name : test
+---
+assert read(status) == chars(closed), "status drift" ;
+status = read(status) ;
+"#;
+        // 非严格模式只告警，不影响输出
+        let (out, failed) = run(code, false);
+        assert!(!failed);
+        assert_eq!(out.items.len(), 1);
+        let (_, failed) = run(code, true);
+        assert!(failed);
+    }
+}
@@ -5,6 +5,7 @@ use crate::language::MatchSource;
 use wp_model_core::model::{DataField, DataRecord, DataType, FieldStorage};
 
 use crate::core::FieldExtractor;
+use crate::core::capture::{self, CaptureScope};
 
 impl FieldExtractor for MatchOperation {
     fn extract_one(
@@ -19,7 +20,9 @@ impl FieldExtractor for MatchOperation {
                 let cur = EvaluationTarget::new(key, DataType::Auto);
                 if let Some(x) = dat.extract_one(&cur, src, dst) {
                     for i in self.items() {
+                        capture::reset_staged();
                         if i.is_match(&x) {
+                            let _scope = CaptureScope::enter();
                             return i.result().extract_one(target, src, dst);
                         }
                     }
@@ -35,6 +38,8 @@ impl FieldExtractor for MatchOperation {
                     } else {
                         // If any source fails to extract, skip matching
                         if let Some(default) = self.default() {
+                            capture::reset_staged();
+                            let _scope = CaptureScope::enter();
                             return default.result().extract_one(target, src, dst);
                         }
                         return None;
@@ -42,13 +47,17 @@ impl FieldExtractor for MatchOperation {
                 }
                 let refs: Vec<&DataField> = vals.iter().collect();
                 for i in self.items() {
+                    capture::reset_staged();
                     if i.is_match(refs.as_slice()) {
+                        let _scope = CaptureScope::enter();
                         return i.result().extract_one(target, src, dst);
                     }
                 }
             }
         }
         if let Some(default) = self.default() {
+            capture::reset_staged();
+            let _scope = CaptureScope::enter();
             return default.result().extract_one(target, src, dst);
         }
         None
@@ -67,7 +76,9 @@ impl FieldExtractor for MatchOperation {
                 let cur = EvaluationTarget::new(key, DataType::Auto);
                 if let Some(x) = dat.extract_one(&cur, src, dst) {
                     for i in self.items() {
+                        capture::reset_staged();
                         if i.is_match(&x) {
+                            let _scope = CaptureScope::enter();
                             // Call extract_storage to enable zero-copy for FieldArc/ObjArc
                             return i.result().extract_storage(target, src, dst);
                         }
@@ -83,6 +94,8 @@ impl FieldExtractor for MatchOperation {
                         vals.push(v);
                     } else {
                         if let Some(default) = self.default() {
+                            capture::reset_staged();
+                            let _scope = CaptureScope::enter();
                             return default.result().extract_storage(target, src, dst);
                         }
                         return None;
@@ -90,7 +103,9 @@ impl FieldExtractor for MatchOperation {
                 }
                 let refs: Vec<&DataField> = vals.iter().collect();
                 for i in self.items() {
+                    capture::reset_staged();
                     if i.is_match(refs.as_slice()) {
+                        let _scope = CaptureScope::enter();
                         // Call extract_storage to enable zero-copy for FieldArc/ObjArc
                         return i.result().extract_storage(target, src, dst);
                     }
@@ -98,6 +113,8 @@ impl FieldExtractor for MatchOperation {
             }
         }
         if let Some(default) = self.default() {
+            capture::reset_staged();
+            let _scope = CaptureScope::enter();
             // Call extract_storage to enable zero-copy for FieldArc/ObjArc
             return default.result().extract_storage(target, src, dst);
         }
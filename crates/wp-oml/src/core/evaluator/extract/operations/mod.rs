@@ -1,4 +1,5 @@
 mod array;
+mod assert;
 mod map;
 mod matchs;
 mod other;
@@ -3,6 +3,7 @@ use crate::core::prelude::*;
 use crate::language::GenericAccessor;
 use crate::language::{GenericBinding, NestedBinding, SingleEvalExp};
 use wp_data_model::cache::FieldQueryCache;
+use wp_data_model::limits;
 use wp_model_core::model::{DataField, DataRecord, DataType, FieldStorage};
 
 use crate::core::FieldExtractor;
@@ -16,13 +17,22 @@ impl ExpEvaluator for SingleEvalExp {
     ) {
         if self.eval_way().support_batch() {
             let obj: Vec<DataField> = self.eval_way().extract_more(src, dst, cache);
+            if self.target().len() == 1 && self.eval_way().keeps_field_names() {
+                // 展平输出：单目标时字段沿用生成的名称
+                for field in obj {
+                    limits::push_storage(&mut dst.items, FieldStorage::from_owned(field));
+                }
+                return;
+            }
             for i in 0..self.target().len() {
                 if let (Some(target), Some(mut v)) = (self.target().get(i), obj.get(i).cloned()) {
                     if let Some(name) = target.name() {
                         v.set_name(name.clone());
                     }
-                    dst.items
-                        .push(FieldStorage::from_owned(omlobj_meta_conv(v, target)));
+                    limits::push_storage(
+                        &mut dst.items,
+                        FieldStorage::from_owned(omlobj_meta_conv(v, target)),
+                    );
                 }
             }
         } else if let Some(target) = self.target().first()
@@ -38,7 +48,7 @@ impl ExpEvaluator for SingleEvalExp {
                 // ✅ Shared + no conversion: Zero-copy optimization
                 // set_name() only modifies cur_name, doesn't clone Arc
                 storage.set_name(target.safe_name());
-                dst.items.push(storage);
+                limits::push_storage(&mut dst.items, storage);
             } else {
                 // Owned or needs conversion: Apply name to underlying field
                 let mut field = storage.into_owned();
@@ -48,7 +58,7 @@ impl ExpEvaluator for SingleEvalExp {
                     field = omlobj_meta_conv(field, target);
                 }
 
-                dst.items.push(FieldStorage::from_owned(field));
+                limits::push_storage(&mut dst.items, FieldStorage::from_owned(field));
             }
         }
     }
@@ -1,5 +1,6 @@
 use crate::core::prelude::*;
 use crate::language::RecordOperation;
+use wp_data_model::limits;
 use wp_model_core::model::{DataField, DataRecord, FieldStorage};
 
 use crate::core::FieldExtractor;
@@ -11,6 +12,10 @@ impl FieldExtractor for RecordOperation {
         src: &mut DataRecordRef<'_>,
         dst: &DataRecord,
     ) -> Option<DataField> {
+        // 目标记录已达上限：结果不会写入，省去取值
+        if limits::exhausted() {
+            return None;
+        }
         match self.dat_get.extract_one(target, src, dst) {
             Some(x) => Some(x),
             None => {
@@ -35,6 +40,9 @@ impl FieldExtractor for RecordOperation {
         src: &mut DataRecordRef<'_>,
         dst: &DataRecord,
     ) -> Option<FieldStorage> {
+        if limits::exhausted() {
+            return None;
+        }
         // Try primary extraction first
         if let Some(storage) = self.dat_get.extract_storage(target, src, dst) {
             return Some(storage);
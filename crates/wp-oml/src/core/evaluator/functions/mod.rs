@@ -1,2 +1,3 @@
 mod string;
+mod sys;
 mod time;
@@ -0,0 +1,99 @@
+use std::sync::OnceLock;
+
+use crate::core::FieldExtractor;
+use crate::core::prelude::*;
+use crate::language::{Hostname, ProcessId};
+use wp_model_core::model::FieldStorage;
+
+/// 进程内只解析一次：优先 `HOSTNAME` 环境变量（容器内常用），否则取系统主机名
+fn local_hostname() -> &'static str {
+    static HOSTNAME: OnceLock<String> = OnceLock::new();
+    HOSTNAME.get_or_init(|| {
+        std::env::var("HOSTNAME")
+            .ok()
+            .filter(|h| !h.trim().is_empty())
+            .unwrap_or_else(|| {
+                hostname::get()
+                    .map(|h| h.to_string_lossy().into_owned())
+                    .unwrap_or_else(|_| "unknown".to_string())
+            })
+    })
+}
+
+impl FieldExtractor for Hostname {
+    fn extract_one(
+        &self,
+        target: &EvaluationTarget,
+        _src: &mut DataRecordRef<'_>,
+        _dst: &DataRecord,
+    ) -> Option<DataField> {
+        Some(DataField::from_chars(target.safe_name(), local_hostname()))
+    }
+
+    fn extract_storage(
+        &self,
+        target: &EvaluationTarget,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+    ) -> Option<FieldStorage> {
+        self.extract_one(target, src, dst)
+            .map(FieldStorage::from_owned)
+    }
+}
+
+impl FieldExtractor for ProcessId {
+    fn extract_one(
+        &self,
+        target: &EvaluationTarget,
+        _src: &mut DataRecordRef<'_>,
+        _dst: &DataRecord,
+    ) -> Option<DataField> {
+        Some(DataField::from_digit(
+            target.safe_name(),
+            std::process::id() as i64,
+        ))
+    }
+
+    fn extract_storage(
+        &self,
+        target: &EvaluationTarget,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+    ) -> Option<FieldStorage> {
+        self.extract_one(target, src, dst)
+            .map(FieldStorage::from_owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssertWithMsg;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataRecord, Value};
+
+    #[test]
+    fn test_hostname_and_process_id() {
+        let cache = &mut FieldQueryCache::default();
+        let mut conf = r#"
+        name : test
+        ---
+        node = Hostname() ;
+        pid = ProcessId() ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert("oml_conf");
+        let target = model.transform(DataRecord::default(), cache);
+
+        match target.field("node").map(|f| f.as_field().get_value()) {
+            Some(Value::Chars(name)) => assert!(!name.is_empty()),
+            other => panic!("node: {:?}", other),
+        }
+        assert_eq!(
+            target
+                .field("pid")
+                .map(|f| f.as_field().get_value().clone()),
+            Some(Value::Digit(std::process::id() as i64))
+        );
+    }
+}
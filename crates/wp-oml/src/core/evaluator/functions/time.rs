@@ -58,6 +58,8 @@ impl FieldExtractor for BuiltinFunction {
             BuiltinFunction::NowTime(x) => x.extract_one(target, src, dst),
             BuiltinFunction::NowDate(x) => x.extract_one(target, src, dst),
             BuiltinFunction::NowHour(x) => x.extract_one(target, src, dst),
+            BuiltinFunction::Hostname(x) => x.extract_one(target, src, dst),
+            BuiltinFunction::ProcessId(x) => x.extract_one(target, src, dst),
         }
     }
 
@@ -2,6 +2,7 @@
 pub mod traits;
 pub use traits::*;
 
+pub mod aggregate;
 mod extract;
 mod functions;
 mod query;
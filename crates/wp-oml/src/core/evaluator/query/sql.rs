@@ -33,6 +33,8 @@ impl FieldExtractor for SqlQuery {
         dst: &DataRecord,
         cache: &mut FieldQueryCache,
     ) -> Vec<DataField> {
+        // 知识库热重载后 provider 代数变化，旧缓存整体作废
+        cache.sync_generation(kdb::provider_generation());
         let mut params = Vec::with_capacity(5);
         let target = EvaluationTarget::auto_default();
         for (v, acq) in self.vars() {
@@ -331,4 +333,130 @@ mod tests {
 
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn test_reload_invalidates_cache() {
+        ensure_provider();
+        let db = MemDB::global();
+        db.table_create(
+            "CREATE TABLE IF NOT EXISTS reload_demo (id INTEGER PRIMARY KEY, tag TEXT)",
+        )
+        .assert();
+        db.execute("INSERT OR REPLACE INTO reload_demo (id, tag) VALUES (1, 'old')")
+            .assert();
+        let cache = &mut FieldQueryCache::default();
+        let query = create_test_query(
+            "SELECT tag FROM reload_demo WHERE id = :id",
+            vec![("id", DataField::from_digit("id".to_string(), 1))],
+        );
+        let run = |cache: &mut FieldQueryCache| {
+            query.extract_more(
+                &mut DataRecordRef::from(&DataRecord::default()),
+                &DataRecord::default(),
+                cache,
+            )
+        };
+        assert_eq!(run(cache)[0].get_value(), &Value::Chars("old".into()));
+
+        db.execute("UPDATE reload_demo SET tag = 'new' WHERE id = 1")
+            .assert();
+        // 未重载：命中缓存，仍为旧值
+        assert_eq!(run(cache)[0].get_value(), &Value::Chars("old".into()));
+
+        kdb::reload_mem_provider(MemDB::global());
+        assert_eq!(run(cache)[0].get_value(), &Value::Chars("new".into()));
+    }
+
+    #[test]
+    fn test_ip_range_table_query() {
+        use wp_know::mem::range_index::IpRangeSpec;
+        ensure_provider();
+        let db = MemDB::global();
+        db.table_create("CREATE TABLE IF NOT EXISTS oml_geo (network TEXT, city TEXT)")
+            .assert();
+        db.execute("DELETE FROM oml_geo").assert();
+        db.execute(
+            "INSERT INTO oml_geo (network, city) VALUES ('10.0.0.0/8', 'intranet'), ('10.1.0.0/16', 'lab'), ('2001:db8::/32', 'v6')",
+        )
+        .assert();
+        db.index_ip_ranges("oml_geo", &IpRangeSpec::with_cidr("ip", "network"))
+            .assert();
+
+        for (ip, city) in [
+            ("10.1.2.3", "lab"),
+            ("10.9.9.9", "intranet"),
+            ("2001:db8::1", "v6"),
+        ] {
+            let query = create_test_query(
+                "select city from oml_geo where ip = :src_ip",
+                vec![("src_ip", DataField::from_chars("src_ip", ip))],
+            );
+            let result = query.extract_more(
+                &mut DataRecordRef::from(&DataRecord::default()),
+                &DataRecord::default(),
+                &mut FieldQueryCache::default(),
+            );
+            assert_eq!(result[0].get_value(), &Value::Chars(city.into()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_skewed_keys_hit_cache() {
+        use crate::core::DataTransformer;
+        use crate::parser::oml_parse_raw;
+        ensure_provider();
+        let mut code = r#"
+name : lookup
+---
+name = select name from test where id = read(uid) ;
+"#;
+        let model = oml_parse_raw(&mut code).assert();
+        let cache = &mut FieldQueryCache::with_capacity(100);
+        // 热点键占九成，其余分散在 50 个冷门键上
+        let records = 5000;
+        for i in 0..records {
+            let uid = if i % 10 == 0 { 100 + (i / 10) % 50 } else { 1 };
+            let src = DataRecord::from(vec![FieldStorage::from_owned(DataField::from_digit(
+                "uid", uid,
+            ))]);
+            let out = model.transform(src, cache);
+            if uid == 1 {
+                assert_eq!(
+                    out.field("name").map(|f| f.get_value().clone()),
+                    Some(Value::Chars("test1".into()))
+                );
+            }
+        }
+        let stats = model.lookup_stats();
+        assert_eq!(stats.lookups(), records as u64);
+        assert!(
+            stats.misses * 10 <= stats.lookups(),
+            "provider calls not reduced 10x: {:?}",
+            stats
+        );
+        assert_eq!(cache.stats(), stats);
+    }
+
+    #[test]
+    fn test_expired_entries_requery() {
+        ensure_provider();
+        let cache = &mut FieldQueryCache::with_ttl(10, std::time::Duration::from_millis(20));
+        let query = create_test_query(
+            "SELECT name FROM test WHERE id = :id",
+            vec![("id", DataField::from_digit("id".to_string(), 2))],
+        );
+        let mut run = || {
+            query.extract_more(
+                &mut DataRecordRef::from(&DataRecord::default()),
+                &DataRecord::default(),
+                cache,
+            )
+        };
+        assert_eq!(run()[0].get_value(), &Value::Chars("test2".into()));
+        assert_eq!(run()[0].get_value(), &Value::Chars("test2".into()));
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        assert_eq!(run()[0].get_value(), &Value::Chars("test2".into()));
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().misses, 2);
+    }
 }
@@ -54,6 +54,9 @@ impl ExpEvaluator for EvalExp {
             EvalExp::Batch(x) => {
                 x.eval_proc(src, dst, cache);
             }
+            EvalExp::Assert(x) => {
+                x.eval_proc(src, dst, cache);
+            }
         }
     }
 }
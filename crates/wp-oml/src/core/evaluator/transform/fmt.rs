@@ -11,6 +11,11 @@ impl FieldExtractor for FmtOperation {
         src: &mut DataRecordRef<'_>,
         dst: &DataRecord,
     ) -> Option<DataField> {
+        // 无参数：按模板中的字段名直接从源记录取值
+        if self.subs().is_empty() {
+            let data = render_named(self.fmt_str(), src);
+            return Some(DataField::from_chars(target.safe_name(), data));
+        }
         let mut args = HashMap::new();
         let mut not_find_items = Vec::new();
         for item in self.subs() {
@@ -65,6 +70,64 @@ impl FieldExtractor for FmtOperation {
     }
 }
 
+/// 命名模板渲染：`{name}` 从源记录取值，`{obj.key}` 访问嵌套对象，`{{`/`}}` 转义为 `{`/`}`。
+/// 未命中的字段输出为空串并记录诊断；未闭合的 `{` 按字面输出。
+fn render_named(tpl: &str, src: &DataRecordRef<'_>) -> String {
+    let mut out = String::with_capacity(tpl.len());
+    let mut rest = tpl;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            out.push('}');
+            rest = &tail[1..];
+            continue;
+        }
+        let Some(end) = tail.find('}') else {
+            out.push_str(tail);
+            return out;
+        };
+        let name = tail[1..end].trim();
+        match lookup_path(src, name) {
+            Some(storage) => out.push_str(&Raw.fmt_field(&storage).to_string()),
+            None => diagnostics::push(OmlIssue::new(OmlIssueKind::FmtVarMissing, name)),
+        }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 按字段名取值；字段名本身含 `.` 时优先整体匹配，否则按 `.` 逐层进入对象
+fn lookup_path(src: &DataRecordRef<'_>, path: &str) -> Option<FieldStorage> {
+    if let Some(field) = src.get(path) {
+        return Some(FieldStorage::from_owned(field.clone()));
+    }
+    let mut keys = path.split('.');
+    let head = src.get(keys.next()?)?;
+    let Value::Obj(root) = head.get_value() else {
+        return None;
+    };
+    let mut obj = root;
+    let mut keys = keys.peekable();
+    while let Some(key) = keys.next() {
+        let val = obj.get(key)?;
+        if keys.peek().is_none() {
+            return Some(val.clone());
+        }
+        match val.get_value() {
+            Value::Obj(nested) => obj = nested,
+            _ => return None,
+        }
+    }
+    None
+}
+
 #[derive(Debug)]
 pub struct FmtVal(pub FieldStorage);
 impl DisplayStr for FmtVal
@@ -85,6 +148,7 @@ mod tests {
     use crate::parser::oml_parse_raw;
     use orion_error::TestAssertWithMsg;
     use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::types::value::ObjectValue;
     use wp_model_core::model::{DataField, DataRecord, FieldStorage};
 
     #[test]
@@ -110,4 +174,57 @@ mod tests {
         let expect = DataField::from_chars("X".to_string(), "wplab:h1-h2_h3".to_string());
         assert_eq!(target.field("X").map(|s| s.as_field()), Some(&expect));
     }
+
+    fn named_src() -> DataRecord {
+        let mut geo = ObjectValue::default();
+        geo.insert(
+            "city".to_string(),
+            DataField::from_chars("city", "Hangzhou"),
+        );
+        DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_chars("ip", "10.0.0.1")),
+            FieldStorage::from_owned(DataField::from_digit("port", 8080)),
+            FieldStorage::from_owned(DataField::from_obj("geo", geo)),
+        ])
+    }
+
+    fn named_fmt(rule: &str) -> Option<DataField> {
+        let conf = format!("name : test\n---\nX : chars = {rule} ;\n");
+        let model = oml_parse_raw(&mut conf.as_str()).assert("oml_conf");
+        let mut cache = FieldQueryCache::default();
+        let target = model.transform(named_src(), &mut cache);
+        target.field("X").map(|s| s.as_field().clone())
+    }
+
+    #[test]
+    fn test_fmt_named_fields() {
+        assert_eq!(
+            named_fmt(r#"fmt("{ip}:{port}")"#),
+            Some(DataField::from_chars("X", "10.0.0.1:8080"))
+        );
+    }
+
+    #[test]
+    fn test_fmt_named_missing_is_empty() {
+        assert_eq!(
+            named_fmt(r#"fmt("{ip}-{nope}|")"#),
+            Some(DataField::from_chars("X", "10.0.0.1-|"))
+        );
+    }
+
+    #[test]
+    fn test_fmt_named_nested_obj() {
+        assert_eq!(
+            named_fmt(r#"fmt("{ip}@{geo.city}")"#),
+            Some(DataField::from_chars("X", "10.0.0.1@Hangzhou"))
+        );
+    }
+
+    #[test]
+    fn test_fmt_named_escape() {
+        assert_eq!(
+            named_fmt(r#"fmt("{{ip}}={ip} }}")"#),
+            Some(DataField::from_chars("X", "{ip}=10.0.0.1 }"))
+        );
+    }
 }
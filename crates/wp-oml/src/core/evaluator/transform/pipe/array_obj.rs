@@ -1,5 +1,5 @@
 use crate::core::prelude::*;
-use crate::language::{Get, Nth, SkipEmpty};
+use crate::language::{Get, MapKeys, MapValues, Nth, SkipEmpty};
 use std::collections::VecDeque;
 use wp_model_core::model::types::value::ObjectValue;
 use wp_model_core::model::{DataField, FieldStorage, Value};
@@ -93,6 +93,43 @@ impl ValueProcessor for Get {
     }
 }
 
+/// 对象键名转换 - map_keys(camel_case)
+impl ValueProcessor for MapKeys {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Obj(obj) => {
+                let mut out = ObjectValue::default();
+                for (key, val) in obj.iter() {
+                    let name = self.transform.apply(key);
+                    let mut field = val.as_field().clone();
+                    field.set_name(name.clone());
+                    out.insert(name, field);
+                }
+                DataField::from_obj(in_val.get_name().to_string(), out)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+/// 对象值转换 - map_values(to_str)
+impl ValueProcessor for MapValues {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Obj(obj) => {
+                let mut out = ObjectValue::default();
+                for (key, val) in obj.iter() {
+                    let mut field = self.inner.value_cacu(val.as_field().clone());
+                    field.set_name(key.to_string());
+                    out.insert(key.to_string(), field);
+                }
+                DataField::from_obj(in_val.get_name().to_string(), out)
+            }
+            _ => in_val,
+        }
+    }
+}
+
 // Helper function to navigate nested objects
 fn get_from_obj<'a>(mut obj: &'a ObjectValue, keys: &[&str]) -> Option<&'a FieldStorage> {
     for (i, key) in keys.iter().enumerate() {
@@ -149,6 +186,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pipe_map_keys_and_values() {
+        use wp_model_core::model::types::value::ObjectValue;
+
+        let cache = &mut FieldQueryCache::default();
+        let mut obj = ObjectValue::default();
+        obj.insert(
+            "src_ip".to_string(),
+            DataField::from_chars("src_ip", "10.0.0.1"),
+        );
+        obj.insert("dstPort".to_string(), DataField::from_digit("dstPort", 443));
+        obj.insert(
+            "HTTPStatus".to_string(),
+            DataField::from_digit("HTTPStatus", 200),
+        );
+        let src = DataRecord::from(vec![
+            DataField::from_obj("event", obj),
+            DataField::from_chars("plain", "keep"),
+        ]);
+
+        let mut conf = r#"
+        name : test
+        ---
+        camel = pipe read(event) | map_keys(camel_case) ;
+        snake = pipe read(event) | map_keys(snake_case) ;
+        upper = pipe read(event) | map_keys(uppercase) ;
+        text  = pipe read(event) | map_values(to_str) | get(dstPort) ;
+        plain = pipe read(plain) | map_keys(camel_case) | map_values(to_str) ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        let keys = |name: &str| -> Vec<String> {
+            match target.field(name).map(|f| f.as_field().get_value().clone()) {
+                Some(wp_model_core::model::Value::Obj(obj)) => {
+                    obj.iter().map(|(k, _)| k.to_string()).collect()
+                }
+                other => panic!("{} not an object: {:?}", name, other),
+            }
+        };
+        let mut camel = keys("camel");
+        camel.sort();
+        assert_eq!(camel, vec!["dstPort", "httpStatus", "srcIp"]);
+        let mut snake = keys("snake");
+        snake.sort();
+        assert_eq!(snake, vec!["dst_port", "http_status", "src_ip"]);
+        let mut upper = keys("upper");
+        upper.sort();
+        assert_eq!(upper, vec!["DSTPORT", "HTTPSTATUS", "SRC_IP"]);
+
+        assert_eq!(
+            target.field("text").map(|s| s.as_field()),
+            Some(&DataField::from_chars("text", "443"))
+        );
+        assert_eq!(
+            target.field("plain").map(|s| s.as_field()),
+            Some(&DataField::from_chars("plain", "keep"))
+        );
+    }
+
     #[test]
     fn test_pipe_obj_get() {
         let val = r#"{"id":0,"items":[{"meta":{"array":"obj"},"name":"current_process","value":{"Array":[{"meta":"obj","name":"obj","value":{"Obj":{"ctime":{"meta":"digit","name":"ctime","value":{"Digit":1676340214}},"desc":{"meta":"chars","name":"desc","value":{"Chars":""}},"md5":{"meta":"chars","name":"md5","value":{"Chars":"d4ed19a8acd9df02123f655fa1e8a8e7"}},"path":{"meta":"chars","name":"path","value":{"Chars":"c:\\\\users\\\\administrator\\\\desktop\\\\domaintool\\\\x64\\\\childproc\\\\test_le9mwv.exe"}},"sign":{"meta":"chars","name":"sign","value":{"Chars":""}},"size":{"meta":"digit","name":"size","value":{"Digit":189446}},"state":{"meta":"digit","name":"state","value":{"Digit":0}},"type":{"meta":"digit","name":"type","value":{"Digit":1}}}}}]}}]}"#;
@@ -1,5 +1,5 @@
 use crate::core::prelude::*;
-use crate::language::{Base64Decode, Base64Encode, EncodeType};
+use crate::language::{Ascii85Decode, Ascii85Encode, Base64Decode, Base64Encode, EncodeType};
 use base64::Engine;
 use base64::engine::general_purpose;
 use encoding_rs::{
@@ -204,6 +204,94 @@ impl ValueProcessor for Base64Decode {
     }
 }
 
+impl ValueProcessor for Ascii85Encode {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                let encode = ascii85_encode(x.as_bytes());
+                DataField::from_chars(in_val.get_name().to_string(), encode)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Ascii85Decode {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                let code = ascii85_decode(x);
+                DataField::from_chars(
+                    in_val.get_name().to_string(),
+                    String::from_utf8_lossy(&code).to_string(),
+                )
+            }
+            _ => in_val,
+        }
+    }
+}
+
+/// btoa 风格编码：每 4 字节转为 5 个 `!`..`u` 字符，全零整组写作 `z`，不加 `<~ ~>` 定界符
+fn ascii85_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(4) * 5);
+    for chunk in data.chunks(4) {
+        let mut buf = [0u8; 4];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let mut word = u32::from_be_bytes(buf);
+        if word == 0 && chunk.len() == 4 {
+            out.push('z');
+            continue;
+        }
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = (word % 85) as u8 + b'!';
+            word /= 85;
+        }
+        // 末尾不足 4 字节时只输出 n + 1 个字符
+        for d in &digits[..chunk.len() + 1] {
+            out.push(*d as char);
+        }
+    }
+    out
+}
+
+/// 容错解码：忽略 `<~ ~>` 定界符、空白及其他非法字符；组内出现的 `z` 同样跳过
+fn ascii85_decode(text: &str) -> Vec<u8> {
+    let body = text.trim();
+    let body = body.strip_prefix("<~").unwrap_or(body);
+    let body = body.strip_suffix("~>").unwrap_or(body);
+    let mut out = Vec::with_capacity(body.len() / 5 * 4 + 4);
+    let mut group = [0u8; 5];
+    let mut len = 0;
+    for b in body.bytes() {
+        match b {
+            b'z' if len == 0 => out.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                group[len] = b - b'!';
+                len += 1;
+                if len == 5 {
+                    push_ascii85_group(&mut out, &group, 4);
+                    len = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+    // 末尾残组按 `u` 补齐；单个字符无法还原任何字节，直接丢弃
+    if len > 1 {
+        group[len..].fill(84);
+        push_ascii85_group(&mut out, &group, len - 1);
+    }
+    out
+}
+
+fn push_ascii85_group(out: &mut Vec<u8>, group: &[u8; 5], take: usize) {
+    let word = group.iter().fold(0u64, |acc, d| acc * 85 + u64::from(*d));
+    // 越界组（> u32::MAX）属于非法输入，按低 32 位截断而不是报错
+    let bytes = (word as u32).to_be_bytes();
+    out.extend_from_slice(&bytes[..take]);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::DataTransformer;
@@ -211,6 +299,52 @@ mod tests {
     use wp_data_model::cache::FieldQueryCache;
     use wp_model_core::model::{DataField, DataRecord, FieldStorage};
 
+    use super::{ascii85_decode, ascii85_encode};
+
+    #[test]
+    fn test_ascii85_codec() {
+        assert_eq!(ascii85_encode(b"Man "), "9jqo^");
+        assert_eq!(ascii85_encode(b"sure."), "F*2M7/c");
+        assert_eq!(ascii85_encode(&[0, 0, 0, 0, 1]), "z!<");
+        assert_eq!(ascii85_encode(b""), "");
+        assert_eq!(ascii85_decode("9jqo^F*2M7/c"), b"Man sure.");
+        assert_eq!(ascii85_decode("z!<"), [0, 0, 0, 0, 1]);
+        // 定界符、空白与非法字符均被忽略
+        assert_eq!(ascii85_decode("<~9jq o^\nF*2M7/c~>"), b"Man sure.");
+        assert_eq!(ascii85_decode("9jq{o^|"), b"Man ");
+        for len in 0..9 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37 + 5) as u8).collect();
+            assert_eq!(ascii85_decode(&ascii85_encode(&data)), data);
+        }
+    }
+
+    #[test]
+    fn test_pipe_ascii85() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("A1", "hello world")),
+            FieldStorage::from_owned(DataField::from_chars("B2", "<~87cURD]i,\"Ebo80~>")),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        X : chars =  pipe read(A1) | ascii85_encode ;
+        Y : chars =  pipe read(A1) | ascii85_encode | ascii85_decode ;
+        Z : chars =  pipe read(B2) | ascii85_decode ;
+         "#;
+        let model = oml_parse_raw(&mut conf).unwrap();
+        let target = model.transform(src, cache);
+
+        let expect = DataField::from_chars("X".to_string(), "BOu!rD]j7BEbo7".to_string());
+        assert_eq!(target.field("X").map(|s| s.as_field()), Some(&expect));
+        let expect = DataField::from_chars("Y".to_string(), "hello world".to_string());
+        assert_eq!(target.field("Y").map(|s| s.as_field()), Some(&expect));
+        let expect = DataField::from_chars("Z".to_string(), "Hello World!".to_string());
+        assert_eq!(target.field("Z").map(|s| s.as_field()), Some(&expect));
+    }
+
     #[test]
     fn test_pipe_base64() {
         let cache = &mut FieldQueryCache::default();
@@ -1,7 +1,9 @@
 use crate::core::prelude::*;
 use crate::language::{
-    HtmlEscape, HtmlUnescape, JsonEscape, JsonUnescape, StrEscape, ToJson, ToStr,
+    HtmlEscape, HtmlUnescape, JsonEscape, JsonPath, JsonUnescape, StrEscape, ToBoolean, ToJson,
+    ToStr,
 };
+use jsonpath_rust::JsonPathValue;
 
 use wp_data_fmt::{Json, ValueFormatter};
 use wp_model_core::model::{DataField, DataType, FNameStr, Value};
@@ -89,6 +91,62 @@ impl ValueProcessor for ToJson {
     }
 }
 
+impl ValueProcessor for ToBoolean {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let flag = match in_val.get_value() {
+            Value::Chars(x) => match x.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" | "on" => true,
+                "false" | "no" | "0" | "off" => false,
+                _ => return in_val,
+            },
+            Value::Digit(n) => *n != 0,
+            _ => return in_val,
+        };
+        DataField::from_bool(in_val.clone_name(), flag)
+    }
+}
+
+/// JSON 命中值转为文本：字符串取原文，其余按 JSON 序列化
+fn json_text(val: &serde_json::Value) -> String {
+    match val {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl ValueProcessor for JsonPath {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let name = in_val.get_name().to_string();
+        let Value::Chars(raw) = in_val.get_value() else {
+            return DataField::from_chars(name, "");
+        };
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(raw) else {
+            return DataField::from_chars(name, "");
+        };
+        let mut hits: Vec<String> = self
+            .compiled
+            .find_slice(&json)
+            .into_iter()
+            .filter_map(|hit| match hit {
+                JsonPathValue::Slice(v, _) => Some(json_text(v)),
+                JsonPathValue::NewValue(v) => Some(json_text(&v)),
+                JsonPathValue::NoValue => None,
+            })
+            .collect();
+        match hits.len() {
+            0 => DataField::from_chars(name, ""),
+            1 => DataField::from_chars(name, hits.remove(0)),
+            _ => {
+                let items = hits
+                    .into_iter()
+                    .map(|v| DataField::from_chars(name.clone(), v))
+                    .collect();
+                DataField::from_arr(name, items)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::DataTransformer;
@@ -160,4 +218,90 @@ mod tests {
         let expect = DataField::from_chars("X".to_string(), "This is a crab: 🦀".to_string());
         assert_eq!(target.field("X").map(|s| s.as_field()), Some(&expect));
     }
+
+    #[test]
+    fn test_json_path() {
+        let cache = &mut FieldQueryCache::default();
+        let payload =
+            r#"{"events":[{"id":"e1","n":1},{"id":"e2","n":2}],"meta":{"host":"h1","port":80}}"#;
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("payload", payload)),
+            FieldStorage::from_owned(DataField::from_chars("bad", "{not json")),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        ids  =  pipe read(payload) | json_path('$.events[*].id') ;
+        host =  pipe read(payload) | json_path('$.meta.host') ;
+        port =  pipe read(payload) | json_path('$.meta.port') ;
+        miss =  pipe read(payload) | json_path('$.meta.none') ;
+        bad  =  pipe read(bad) | json_path('$.meta.host') ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        assert_eq!(
+            target.field("ids").map(|s| s.as_field()),
+            Some(&DataField::from_arr(
+                "ids",
+                vec![
+                    DataField::from_chars("payload", "e1"),
+                    DataField::from_chars("payload", "e2"),
+                ],
+            ))
+        );
+        assert_eq!(
+            target.field("host").map(|s| s.as_field()),
+            Some(&DataField::from_chars("host", "h1"))
+        );
+        assert_eq!(
+            target.field("port").map(|s| s.as_field()),
+            Some(&DataField::from_chars("port", "80"))
+        );
+        assert_eq!(
+            target.field("miss").map(|s| s.as_field()),
+            Some(&DataField::from_chars("miss", ""))
+        );
+        assert_eq!(
+            target.field("bad").map(|s| s.as_field()),
+            Some(&DataField::from_chars("bad", ""))
+        );
+    }
+
+    #[test]
+    fn test_to_bool() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("a", "Yes")),
+            FieldStorage::from_owned(DataField::from_chars("b", "OFF")),
+            FieldStorage::from_owned(DataField::from_chars("c", "1")),
+            FieldStorage::from_owned(DataField::from_chars("d", "maybe")),
+            FieldStorage::from_owned(DataField::from_digit("e", 0)),
+            FieldStorage::from_owned(DataField::from_digit("f", 42)),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        A = pipe read(a) | to_bool ;
+        B = pipe read(b) | to_bool ;
+        C = read(c) | to_bool ;
+        D = pipe read(d) | to_bool ;
+        E = pipe read(e) | to_bool ;
+        F = pipe read(f) | to_bool ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        let field = |name: &str| target.field(name).map(|s| s.as_field().clone());
+        assert_eq!(field("A"), Some(DataField::from_bool("A", true)));
+        assert_eq!(field("B"), Some(DataField::from_bool("B", false)));
+        assert_eq!(field("C"), Some(DataField::from_bool("C", true)));
+        assert_eq!(field("D"), Some(DataField::from_chars("D", "maybe")));
+        assert_eq!(field("E"), Some(DataField::from_bool("E", false)));
+        assert_eq!(field("F"), Some(DataField::from_bool("F", true)));
+    }
 }
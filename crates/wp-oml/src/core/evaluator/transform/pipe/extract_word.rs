@@ -1,6 +1,5 @@
 use crate::core::prelude::*;
 use crate::language::{ExtractMainWord, ExtractSubjectObject};
-use jieba_rs::Jieba;
 use lazy_static::lazy_static;
 use serde_json::json;
 use std::collections::HashMap;
@@ -8,12 +7,9 @@ use wp_model_core::model::types::value::ObjectValue;
 use wp_model_core::model::{DataField, Value};
 
 // 导入语义词典
-use super::semantic_dict_loader::{SEMANTIC_DICT, is_semantic_enabled};
+use super::semantic_dict_loader::{SemanticDict, is_semantic_enabled, jieba, semantic_dict};
 
 lazy_static! {
-    // Jieba 中文分词器实例（全局单例）
-    static ref JIEBA: Jieba = Jieba::new();
-
     // 中英文字段映射表（保留用于其他功能扩展）
     #[allow(dead_code)]
     static ref FIELD_MAPPING: HashMap<&'static str, &'static str> = {
@@ -105,19 +101,19 @@ impl DebugInfo {
 }
 
 /// 英文词角色判断
-fn classify_eng(word: &str) -> WordRole {
+fn classify_eng(dict: &SemanticDict, word: &str) -> WordRole {
     let lower = word.to_lowercase();
 
     // 优先级1：领域词典明确匹配
-    if SEMANTIC_DICT.status_words.contains(lower.as_str()) {
+    if dict.status_words.contains(lower.as_str()) {
         return WordRole::Status;
     }
-    if SEMANTIC_DICT.action_verbs.contains(lower.as_str()) {
+    if dict.action_verbs.contains(lower.as_str()) {
         return WordRole::Action;
     }
 
     // 优先级2：实体名词白名单（覆盖词缀规则）
-    if SEMANTIC_DICT.entity_nouns.contains(lower.as_str()) {
+    if dict.entity_nouns.contains(lower.as_str()) {
         return WordRole::Entity;
     }
 
@@ -140,19 +136,19 @@ fn classify_eng(word: &str) -> WordRole {
 }
 
 /// 中文词角色判断（根据词性）
-fn classify_cn(pos: &str, word: &str) -> Option<WordRole> {
+fn classify_cn(dict: &SemanticDict, pos: &str, word: &str) -> Option<WordRole> {
     let lower = word.to_lowercase();
-    if SEMANTIC_DICT.status_words.contains(lower.as_str()) {
+    if dict.status_words.contains(lower.as_str()) {
         return Some(WordRole::Status);
     }
-    if SEMANTIC_DICT.action_verbs.contains(lower.as_str()) {
+    if dict.action_verbs.contains(lower.as_str()) {
         return Some(WordRole::Action);
     }
     match pos {
         "v" | "vn" | "vd" => Some(WordRole::Action),
         "n" | "nr" | "ns" | "nt" | "nz" | "ng" => Some(WordRole::Entity),
         _ => {
-            if SEMANTIC_DICT.domain_words.contains(lower.as_str()) {
+            if dict.domain_words.contains(lower.as_str()) {
                 Some(WordRole::Entity)
             } else {
                 None // 停用词/虚词等，不参与分配
@@ -172,7 +168,10 @@ fn analyze_subject_object_with_debug(
     text: &str,
     enable_debug: bool,
 ) -> (String, String, String, String, Option<DebugInfo>) {
-    let tags = JIEBA.tag(text, true);
+    let jieba = jieba();
+    let tags = jieba.tag(text, true);
+    // 整段分析使用同一份词典快照，运行时增删词条不影响进行中的分析
+    let dict = semantic_dict();
 
     let mut subject = String::new();
     let mut action = String::new();
@@ -200,15 +199,15 @@ fn analyze_subject_object_with_debug(
             continue;
         }
         let word_lower = word.to_lowercase();
-        if SEMANTIC_DICT.stop_words.contains(word_lower.as_str()) {
+        if dict.stop_words.contains(word_lower.as_str()) {
             continue;
         }
 
         let pos = tag.tag;
         let role = if pos == "eng" {
-            Some(classify_eng(word))
+            Some(classify_eng(&dict, word))
         } else {
-            classify_cn(pos, word)
+            classify_cn(&dict, pos, word)
         };
 
         if let Some(role) = role {
@@ -217,18 +216,17 @@ fn analyze_subject_object_with_debug(
                     if status.is_empty() {
                         status = word.to_string();
                         if let Some(ref mut d) = debug {
-                            d.status_rule =
-                                if SEMANTIC_DICT.status_words.contains(word_lower.as_str()) {
-                                    "rule1: status_word_match".to_string()
-                                } else {
-                                    "rule2: cn_pos_match".to_string()
-                                };
-                            d.status_confidence =
-                                if SEMANTIC_DICT.status_words.contains(word_lower.as_str()) {
-                                    1.0
-                                } else {
-                                    0.7
-                                };
+                            d.status_rule = if dict.status_words.contains(word_lower.as_str()) {
+                                "rule1: status_word_match".to_string()
+                            } else {
+                                "rule2: cn_pos_match".to_string()
+                            };
+                            d.status_confidence = if dict.status_words.contains(word_lower.as_str())
+                            {
+                                1.0
+                            } else {
+                                0.7
+                            };
                         }
                     }
                 }
@@ -237,22 +235,21 @@ fn analyze_subject_object_with_debug(
                         action = word.to_string();
                         action_seen = true;
                         if let Some(ref mut d) = debug {
-                            d.action_rule =
-                                if SEMANTIC_DICT.action_verbs.contains(word_lower.as_str()) {
-                                    "rule1: action_verb_match".to_string()
-                                } else if pos == "eng" && word_lower.ends_with("ing") {
-                                    "rule2: eng_ing_suffix".to_string()
-                                } else if pos == "eng" && word_lower.ends_with("ed") {
-                                    "rule2: eng_ed_suffix".to_string()
-                                } else {
-                                    format!("rule3: cn_pos({})", pos)
-                                };
-                            d.action_confidence =
-                                if SEMANTIC_DICT.action_verbs.contains(word_lower.as_str()) {
-                                    1.0
-                                } else {
-                                    0.7
-                                };
+                            d.action_rule = if dict.action_verbs.contains(word_lower.as_str()) {
+                                "rule1: action_verb_match".to_string()
+                            } else if pos == "eng" && word_lower.ends_with("ing") {
+                                "rule2: eng_ing_suffix".to_string()
+                            } else if pos == "eng" && word_lower.ends_with("ed") {
+                                "rule2: eng_ed_suffix".to_string()
+                            } else {
+                                format!("rule3: cn_pos({})", pos)
+                            };
+                            d.action_confidence = if dict.action_verbs.contains(word_lower.as_str())
+                            {
+                                1.0
+                            } else {
+                                0.7
+                            };
                         }
                     }
                 }
@@ -260,14 +257,13 @@ fn analyze_subject_object_with_debug(
                     if subject.is_empty() {
                         subject = word.to_string();
                         if let Some(ref mut d) = debug {
-                            d.subject_rule =
-                                if SEMANTIC_DICT.domain_words.contains(word_lower.as_str()) {
-                                    "rule1: domain_entity_match".to_string()
-                                } else {
-                                    format!("rule2: core_pos({}) + non_stopword", pos)
-                                };
+                            d.subject_rule = if dict.domain_words.contains(word_lower.as_str()) {
+                                "rule1: domain_entity_match".to_string()
+                            } else {
+                                format!("rule2: core_pos({}) + non_stopword", pos)
+                            };
                             d.subject_confidence =
-                                if SEMANTIC_DICT.domain_words.contains(word_lower.as_str()) {
+                                if dict.domain_words.contains(word_lower.as_str()) {
                                     1.0
                                 } else {
                                     0.8
@@ -276,18 +272,17 @@ fn analyze_subject_object_with_debug(
                     } else if action_seen && object.is_empty() {
                         object = word.to_string();
                         if let Some(ref mut d) = debug {
-                            d.object_rule =
-                                if SEMANTIC_DICT.domain_words.contains(word_lower.as_str()) {
-                                    "rule1: domain_entity_match (after_action)".to_string()
-                                } else {
-                                    format!("rule2: core_pos({}) + after_action", pos)
-                                };
-                            d.object_confidence =
-                                if SEMANTIC_DICT.domain_words.contains(word_lower.as_str()) {
-                                    1.0
-                                } else {
-                                    0.8
-                                };
+                            d.object_rule = if dict.domain_words.contains(word_lower.as_str()) {
+                                "rule1: domain_entity_match (after_action)".to_string()
+                            } else {
+                                format!("rule2: core_pos({}) + after_action", pos)
+                            };
+                            d.object_confidence = if dict.domain_words.contains(word_lower.as_str())
+                            {
+                                1.0
+                            } else {
+                                0.8
+                            };
                         }
                     }
                 }
@@ -306,54 +301,51 @@ fn analyze_subject_object_with_debug(
 /// - object：对象（作用于谁/什么）
 /// - status：状态（结果如何）
 /// - debug：调试信息（仅在debug模式下）
+///
+/// 字段名可通过 `rename`/`prefix` 参数定制；`flat` 模式下由管道展开为同级字段
 impl ValueProcessor for ExtractSubjectObject {
     fn value_cacu(&self, in_val: DataField) -> DataField {
-        if !is_semantic_enabled() {
-            return DataField::from_obj(in_val.get_name().to_string(), ObjectValue::default());
+        if is_semantic_enabled() && !matches!(in_val.get_value(), Value::Chars(_)) {
+            return in_val;
         }
-        match in_val.get_value() {
-            Value::Chars(x) => {
-                let cleaned = x.trim();
-                if cleaned.is_empty() {
-                    return DataField::from_obj(
-                        in_val.get_name().to_string(),
-                        ObjectValue::default(),
-                    );
-                }
-
-                let (subject, action, object, status, debug) =
-                    analyze_subject_object_with_debug(cleaned, self.debug);
-
-                let mut obj = ObjectValue::default();
-                obj.insert(
-                    "subject".to_string(),
-                    DataField::from_chars("subject", subject),
-                );
-                obj.insert(
-                    "action".to_string(),
-                    DataField::from_chars("action", action),
-                );
-                obj.insert(
-                    "object".to_string(),
-                    DataField::from_chars("object", object),
-                );
-                obj.insert(
-                    "status".to_string(),
-                    DataField::from_chars("status", status),
-                );
+        let mut obj = ObjectValue::default();
+        for field in self.extract_fields(&in_val) {
+            obj.insert(field.get_name().to_string(), field);
+        }
+        DataField::from_obj(in_val.get_name().to_string(), obj)
+    }
+}
 
-                // 如果启用debug，添加debug字段
-                if let Some(d) = debug {
-                    obj.insert(
-                        "debug".to_string(),
-                        DataField::from_chars("debug", d.to_json()),
-                    );
-                }
+impl ExtractSubjectObject {
+    /// 按 subject/action/object/status(/debug) 顺序输出字段；
+    /// 语义功能关闭、输入为空或非字符串时返回空
+    pub(crate) fn extract_fields(&self, in_val: &DataField) -> Vec<DataField> {
+        if !is_semantic_enabled() {
+            return Vec::new();
+        }
+        let Value::Chars(x) = in_val.get_value() else {
+            return Vec::new();
+        };
+        let cleaned = x.trim();
+        if cleaned.is_empty() {
+            return Vec::new();
+        }
 
-                DataField::from_obj(in_val.get_name().to_string(), obj)
-            }
-            _ => in_val,
+        let (subject, action, object, status, debug) =
+            analyze_subject_object_with_debug(cleaned, self.debug);
+        let mut fields: Vec<DataField> = [subject, action, object, status]
+            .into_iter()
+            .zip(Self::KEYS)
+            .map(|(value, key)| DataField::from_chars(self.output_name(key), value))
+            .collect();
+        // 如果启用debug，添加debug字段
+        if let Some(d) = debug {
+            fields.push(DataField::from_chars(
+                self.output_name("debug"),
+                d.to_json(),
+            ));
         }
+        fields
     }
 }
 
@@ -380,7 +372,9 @@ impl ValueProcessor for ExtractMainWord {
                 }
 
                 // 步骤2：jieba-rs 核心工作：分词+词性标注（使用HMM模式获得更细粒度的分词）
-                let tags = JIEBA.tag(cleaned_log, true);
+                let jieba = jieba();
+                let tags = jieba.tag(cleaned_log, true);
+                let dict = semantic_dict();
 
                 // 步骤3：定制规则筛选，返回第一个核心词
                 for tag in &tags {
@@ -395,7 +389,7 @@ impl ValueProcessor for ExtractMainWord {
                     let word_lower = word.to_lowercase();
 
                     // 规则1：日志领域词（优先级最高，直接返回）
-                    if SEMANTIC_DICT.domain_words.contains(word_lower.as_str()) {
+                    if dict.domain_words.contains(word_lower.as_str()) {
                         return DataField::from_chars(
                             in_val.get_name().to_string(),
                             word.to_string(),
@@ -403,8 +397,7 @@ impl ValueProcessor for ExtractMainWord {
                     }
 
                     // 规则2：核心词性 + 非停用词
-                    if SEMANTIC_DICT.core_pos.contains(pos)
-                        && !SEMANTIC_DICT.stop_words.contains(word_lower.as_str())
+                    if dict.core_pos.contains(pos) && !dict.stop_words.contains(word_lower.as_str())
                     {
                         return DataField::from_chars(
                             in_val.get_name().to_string(),
@@ -1119,6 +1112,124 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_subject_object_flat() {
+        enable_semantic();
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord::from(vec![DataField::from_chars(
+            "msg",
+            "Server failed to connect database",
+        )]);
+
+        let mut conf = r#"
+        name : test_flat
+        ---
+        nested = read(msg) | extract_subject_object ;
+        named = read(msg) | extract_subject_object(rename:[subject:who, status:result]) ;
+        evt = read(msg) | extract_subject_object(flat, prefix:'evt_') ;
+        dbg = read(msg) | extract_subject_object(flat, prefix:'dbg_', debug) ;
+        who, did = read(msg) | extract_subject_object(flat) ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        let obj_of = |name: &str| match target.get_field_owned(name).map(|f| f.value) {
+            Some(Value::Obj(obj)) => obj,
+            other => panic!("expected object for {}, got {:?}", name, other),
+        };
+        let chars_of = |name: &str| target.get_field_owned(name).map(|f| f.value.to_string());
+
+        // 展平输出与嵌套对象取值一致，语句目标本身不出现
+        let nested = obj_of("nested");
+        for key in ["subject", "action", "object", "status"] {
+            assert_eq!(
+                chars_of(&format!("evt_{}", key)),
+                nested.get(key).map(|f| f.get_value().to_string()),
+                "key {}",
+                key
+            );
+        }
+        assert!(target.get_field_owned("evt").is_none());
+        assert!(target.get_field_owned("evt_debug").is_none());
+
+        // 自定义键名
+        let named = obj_of("named");
+        assert_eq!(
+            named.get("who").map(|f| f.get_value()),
+            nested.get("subject").map(|f| f.get_value())
+        );
+        assert_eq!(
+            named.get("result").map(|f| f.get_value()),
+            nested.get("status").map(|f| f.get_value())
+        );
+        assert!(named.get("subject").is_none());
+
+        // debug 在展平模式下作为同级字段输出
+        assert!(chars_of("dbg_debug").is_some_and(|d| d.contains("tokenization")));
+
+        // 多目标时按 subject/action/... 顺序对应
+        assert_eq!(chars_of("who"), chars_of("evt_subject"));
+        assert_eq!(chars_of("did"), chars_of("evt_action"));
+    }
+
+    #[test]
+    fn test_runtime_status_word() {
+        use super::analyze_subject_object_with_debug;
+        use crate::core::evaluator::transform::pipe::semantic_dict_loader::{
+            SemanticCategory, add_semantic_entry, remove_semantic_entry,
+        };
+        enable_semantic();
+
+        let text = "file quarantined by scanner";
+        // 未登记时按 -ed 词缀识别为动作
+        let (_, action, _, status, _) = analyze_subject_object_with_debug(text, false);
+        assert_eq!(action, "quarantined");
+        assert!(status.is_empty());
+
+        assert!(add_semantic_entry(SemanticCategory::StatusWords, "Quarantined").unwrap());
+        assert!(!add_semantic_entry(SemanticCategory::StatusWords, "quarantined").unwrap());
+        let (_, action, _, status, _) = analyze_subject_object_with_debug(text, false);
+        assert_eq!(status, "quarantined");
+        assert_ne!(action, "quarantined");
+
+        assert!(remove_semantic_entry(SemanticCategory::StatusWords, "quarantined").unwrap());
+        let (_, action, _, status, _) = analyze_subject_object_with_debug(text, false);
+        assert_eq!(action, "quarantined");
+        assert!(status.is_empty());
+    }
+
+    #[test]
+    fn test_user_dict_keeps_domain_term() {
+        use super::analyze_subject_object_with_debug;
+        use crate::core::evaluator::transform::pipe::semantic_dict_loader::set_jieba_user_dict;
+        enable_semantic();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jieba_user.dict");
+        std::fs::write(&path, "天擎\nEDR控制台 nz\n").unwrap();
+        assert_eq!(set_jieba_user_dict(&path).unwrap(), 2);
+
+        // 产品名整词切出，作为主体参与分配
+        let (subject, _, _, _, debug) =
+            analyze_subject_object_with_debug("EDR控制台连接失败", true);
+        assert_eq!(subject, "EDR控制台");
+        assert_eq!(debug.unwrap().tokens[0], "EDR控制台");
+
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord::from(vec![DataField::from_chars("msg", "天擎终端检测到病毒")]);
+        let mut conf = r#"
+        name : test
+        ---
+        main = pipe read(msg) | extract_main_word ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+        assert_eq!(
+            target.field("main").map(|s| s.as_field()),
+            Some(&DataField::from_chars("main", "天擎"))
+        );
+    }
+
     #[test]
     fn test_debug_mode() {
         enable_semantic();
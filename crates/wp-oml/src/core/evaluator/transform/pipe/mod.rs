@@ -3,7 +3,7 @@ use wp_model_core::model::DataField;
 use crate::{core::ValueProcessor, language::PipeFun};
 
 mod array_obj;
-mod base64;
+mod encode;
 mod escape;
 mod extract_word;
 mod net;
@@ -22,22 +22,32 @@ impl ValueProcessor for PipeFun {
         match self {
             PipeFun::Base64Encode(o) => o.value_cacu(in_val),
             PipeFun::Base64Decode(o) => o.value_cacu(in_val),
+            PipeFun::Ascii85Encode(o) => o.value_cacu(in_val),
+            PipeFun::Ascii85Decode(o) => o.value_cacu(in_val),
             PipeFun::HtmlEscape(o) => o.value_cacu(in_val),
             PipeFun::HtmlUnescape(o) => o.value_cacu(in_val),
             PipeFun::StrEscape(o) => o.value_cacu(in_val),
             PipeFun::JsonEscape(o) => o.value_cacu(in_val),
             PipeFun::JsonUnescape(o) => o.value_cacu(in_val),
+            PipeFun::JsonPath(o) => o.value_cacu(in_val),
             PipeFun::TimeToTs(o) => o.value_cacu(in_val),
             PipeFun::TimeToTsMs(o) => o.value_cacu(in_val),
             PipeFun::TimeToTsUs(o) => o.value_cacu(in_val),
             PipeFun::TimeToTsZone(o) => o.value_cacu(in_val),
             PipeFun::Nth(o) => o.value_cacu(in_val),
+            PipeFun::Slice(o) => o.value_cacu(in_val),
             PipeFun::Get(o) => o.value_cacu(in_val),
             PipeFun::StartsWith(o) => o.value_cacu(in_val),
+            PipeFun::CountOccurrences(o) => o.value_cacu(in_val),
             PipeFun::MapTo(o) => o.value_cacu(in_val),
+            PipeFun::MapKeys(o) => o.value_cacu(in_val),
+            PipeFun::MapValues(o) => o.value_cacu(in_val),
             PipeFun::ToStr(o) => o.value_cacu(in_val),
             PipeFun::ToJson(o) => o.value_cacu(in_val),
+            PipeFun::ToBoolean(o) => o.value_cacu(in_val),
             PipeFun::SkipEmpty(o) => o.value_cacu(in_val),
+            PipeFun::Length(o) => o.value_cacu(in_val),
+            PipeFun::Unique(o) => o.value_cacu(in_val),
             PipeFun::Dumb(o) => o.value_cacu(in_val),
             PipeFun::PathGet(o) => o.value_cacu(in_val),
             PipeFun::UrlGet(o) => o.value_cacu(in_val),
@@ -1,4 +1,5 @@
 use crate::core::prelude::*;
+use std::collections::HashSet;
 use wp_model_core::model::{DataField, Value};
 
 impl ValueProcessor for crate::language::StartsWith {
@@ -21,6 +22,30 @@ impl ValueProcessor for crate::language::StartsWith {
     }
 }
 
+impl ValueProcessor for crate::language::CountOccurrences {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let cnt = match in_val.get_value() {
+            Value::Chars(value) => count_non_overlapping(value, &self.pattern),
+            _ => 0,
+        };
+        DataField::from_digit(in_val.get_name().to_string(), cnt as i64)
+    }
+}
+
+/// 不重叠计数：每次命中后跳过整个模式长度
+fn count_non_overlapping(haystack: &str, pattern: &str) -> usize {
+    if pattern.is_empty() {
+        return 0;
+    }
+    let mut cnt = 0;
+    let mut rest = haystack;
+    while let Some(pos) = rest.find(pattern) {
+        cnt += 1;
+        rest = &rest[pos + pattern.len()..];
+    }
+    cnt
+}
+
 impl ValueProcessor for crate::language::MapTo {
     fn value_cacu(&self, in_val: DataField) -> DataField {
         use crate::language::MapValue;
@@ -42,6 +67,51 @@ impl ValueProcessor for crate::language::MapTo {
     }
 }
 
+impl ValueProcessor for crate::language::Length {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let len = match in_val.get_value() {
+            Value::Chars(value) => value.chars().count(),
+            Value::Array(arr) => arr.len(),
+            _ => 0,
+        };
+        DataField::from_digit(in_val.get_name().to_string(), len as i64)
+    }
+}
+
+impl ValueProcessor for crate::language::Slice {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let (start, end) = self.bounds(arr.len());
+                let items: Vec<DataField> = arr[start..end]
+                    .iter()
+                    .map(|item| item.as_field().clone())
+                    .collect();
+                DataField::from_arr(in_val.get_name().to_string(), items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for crate::language::Unique {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let mut seen = HashSet::with_capacity(arr.len());
+                let items: Vec<DataField> = arr
+                    .iter()
+                    .map(|item| item.as_field())
+                    .filter(|field| seen.insert(field.get_value().to_string()))
+                    .cloned()
+                    .collect();
+                DataField::from_arr(in_val.get_name().to_string(), items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::DataTransformer;
@@ -441,4 +511,175 @@ mod tests {
             Some(&DataField::from_ignore("E"))
         );
     }
+
+    #[test]
+    fn test_pipe_length() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("msg", "日志abc")),
+            FieldStorage::from_owned(DataField::from_arr(
+                "arr",
+                vec![DataField::from_digit("a", 1), DataField::from_digit("b", 2)],
+            )),
+            FieldStorage::from_owned(DataField::from_digit("num", 12345)),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        A  =  pipe read(msg) | length ;
+        B  =  pipe read(arr) | length ;
+        C  =  pipe read(num) | length ;
+        D  =  match read(A) {
+            gt(4) => chars(long),
+            _ => chars(short),
+        };
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        assert_eq!(
+            target.field("A").map(|s| s.as_field()),
+            Some(&DataField::from_digit("A", 5))
+        );
+        assert_eq!(
+            target.field("B").map(|s| s.as_field()),
+            Some(&DataField::from_digit("B", 2))
+        );
+        assert_eq!(
+            target.field("C").map(|s| s.as_field()),
+            Some(&DataField::from_digit("C", 0))
+        );
+        assert_eq!(
+            target.field("D").map(|s| s.as_field()),
+            Some(&DataField::from_chars("D", "long"))
+        );
+    }
+
+    #[test]
+    fn test_pipe_count_occurrences() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("sql", "1' OR 1=1 -- x ---- y")),
+            FieldStorage::from_owned(DataField::from_chars("aaa", "aaaaa")),
+            FieldStorage::from_owned(DataField::from_digit("num", 42)),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        repeat_count = pipe read(sql) | count_occurrences('--') ;
+        A  =  pipe read(aaa) | count_occurrences('aa') ;
+        B  =  pipe read(sql) | count_occurrences('') ;
+        C  =  pipe read(num) | count_occurrences('4') ;
+        D  =  read(sql) | count_occurrences('#') ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        let digit = |name: &str| target.field(name).map(|s| s.as_field().clone());
+        // "--" 一次，"----" 不重叠计为两次
+        assert_eq!(
+            digit("repeat_count"),
+            Some(DataField::from_digit("repeat_count", 3))
+        );
+        assert_eq!(digit("A"), Some(DataField::from_digit("A", 2)));
+        assert_eq!(digit("B"), Some(DataField::from_digit("B", 0)));
+        assert_eq!(digit("C"), Some(DataField::from_digit("C", 0)));
+        assert_eq!(digit("D"), Some(DataField::from_digit("D", 0)));
+    }
+
+    #[test]
+    fn test_pipe_unique() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_arr(
+                "arr",
+                ["a", "b", "a", "c", "b"]
+                    .iter()
+                    .map(|v| DataField::from_chars("item", *v))
+                    .collect(),
+            )),
+            FieldStorage::from_owned(DataField::from_chars("msg", "a a")),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        A  =  pipe read(arr) | unique ;
+        B  =  pipe read(arr) | unique | unique ;
+        C  =  pipe read(msg) | unique ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        let expect: Vec<DataField> = ["a", "b", "c"]
+            .iter()
+            .map(|v| DataField::from_chars("item", *v))
+            .collect();
+        assert_eq!(
+            target.field("A").map(|s| s.as_field()),
+            Some(&DataField::from_arr("A", expect.clone()))
+        );
+        assert_eq!(
+            target.field("B").map(|s| s.as_field()),
+            Some(&DataField::from_arr("B", expect))
+        );
+        assert_eq!(
+            target.field("C").map(|s| s.as_field()),
+            Some(&DataField::from_chars("C", "a a"))
+        );
+    }
+
+    #[test]
+    fn test_pipe_slice() {
+        let cache = &mut FieldQueryCache::default();
+        let items = |vals: &[i64]| -> Vec<DataField> {
+            vals.iter()
+                .map(|v| DataField::from_digit("e", *v))
+                .collect()
+        };
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_arr("events", items(&[1, 2, 3, 4, 5]))),
+            FieldStorage::from_owned(DataField::from_chars("msg", "abc")),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        A  =  pipe read(events) | slice(0, 2) ;
+        B  =  pipe read(events) | slice(-3) ;
+        C  =  pipe read(events) | slice_last(2) ;
+        D  =  pipe read(events) | slice(1, -1) ;
+        E  =  pipe read(events) | slice(-10, 100) ;
+        F  =  pipe read(events) | slice(4, 2) ;
+        G  =  pipe read(events) | slice_last(0) ;
+        H  =  pipe read(msg) | slice(0, 1) ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        let field = |name: &str| target.field(name).map(|s| s.as_field().clone());
+        assert_eq!(field("A"), Some(DataField::from_arr("A", items(&[1, 2]))));
+        assert_eq!(
+            field("B"),
+            Some(DataField::from_arr("B", items(&[3, 4, 5])))
+        );
+        assert_eq!(field("C"), Some(DataField::from_arr("C", items(&[4, 5]))));
+        assert_eq!(
+            field("D"),
+            Some(DataField::from_arr("D", items(&[2, 3, 4])))
+        );
+        assert_eq!(
+            field("E"),
+            Some(DataField::from_arr("E", items(&[1, 2, 3, 4, 5])))
+        );
+        assert_eq!(field("F"), Some(DataField::from_arr("F", vec![])));
+        assert_eq!(field("G"), Some(DataField::from_arr("G", vec![])));
+        assert_eq!(field("H"), Some(DataField::from_chars("H", "abc")));
+    }
 }
@@ -4,7 +4,7 @@ use std::path::Path;
 use url::{Position, Url};
 use wp_model_core::model::{DataField, Value};
 
-/// 路径解析 - path(name|path)
+/// 路径解析 - path(name|path|parent|ext|stem)
 impl ValueProcessor for PathGet {
     fn value_cacu(&self, in_val: DataField) -> DataField {
         match in_val.get_value() {
@@ -21,6 +21,18 @@ impl ValueProcessor for PathGet {
                         .file_name()
                         .map(|f| f.to_string_lossy().into_owned())
                         .unwrap_or_else(|| x.to_string()),
+                    PathType::Parent => path
+                        .parent()
+                        .map(|f| f.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    PathType::Extension => path
+                        .extension()
+                        .map(|f| f.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    PathType::Stem => path
+                        .file_stem()
+                        .map(|f| f.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
                 };
                 DataField::from_chars(in_val.get_name().to_string(), val_str)
             }
@@ -29,9 +41,27 @@ impl ValueProcessor for PathGet {
     }
 }
 
-/// URL 解析 - url(domain|host|uri|path|params)
+/// URL 解析 - url(domain|host|uri|path|params|query_all:key)
 impl ValueProcessor for UrlGet {
     fn value_cacu(&self, in_val: DataField) -> DataField {
+        if let UrlType::QueryAll(key) = &self.key {
+            return match in_val.get_value() {
+                Value::Chars(x) => {
+                    let items: Vec<DataField> = Url::parse(x)
+                        .map(|url| {
+                            url.query_pairs()
+                                .filter(|(k, _)| k == key.as_str())
+                                .map(|(_, v)| {
+                                    DataField::from_chars(key.to_string(), v.into_owned())
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    DataField::from_arr(in_val.get_name().to_string(), items)
+                }
+                _ => in_val,
+            };
+        }
         match in_val.get_value() {
             Value::Chars(x) => {
                 let origin_url = x.clone();
@@ -46,7 +76,7 @@ impl ValueProcessor for UrlGet {
                         UrlType::HttpReqUri => url[Position::BeforePath..].to_string(),
                         UrlType::HttpReqPath => url.path().to_string(),
                         UrlType::HttpReqParams => url.query().unwrap_or("").to_string(),
-                        UrlType::Default => origin_url.to_string(),
+                        UrlType::Default | UrlType::QueryAll(_) => origin_url.to_string(),
                     },
                     Err(_) => origin_url.to_string(),
                 };
@@ -88,6 +118,54 @@ mod tests {
         assert_eq!(target.field("X").map(|s| s.as_field()), Some(&expect));
     }
 
+    #[test]
+    fn test_pipe_path_components() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            FieldStorage::from_owned(DataField::from_chars("A1", "/var/log/app.log")),
+            FieldStorage::from_owned(DataField::from_chars("A2", "C:\\Temp\\archive.tar.gz")),
+            FieldStorage::from_owned(DataField::from_chars("A3", "README")),
+        ];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        P1 = pipe read(A1) | path(parent);
+        E1 = pipe read(A1) | path(ext);
+        S1 = pipe read(A1) | path(stem);
+        P2 = pipe read(A2) | path(parent);
+        E2 = pipe read(A2) | path(ext);
+        S2 = pipe read(A2) | path(stem);
+        P3 = pipe read(A3) | path(parent);
+        E3 = pipe read(A3) | path(ext);
+        S3 = pipe read(A3) | path(stem);
+         "#;
+        let model = oml_parse_raw(&mut conf).unwrap();
+        let target = model.transform(src, cache);
+
+        let expects = [
+            ("P1", "/var/log"),
+            ("E1", "log"),
+            ("S1", "app"),
+            ("P2", "C:/Temp"),
+            ("E2", "gz"),
+            ("S2", "archive.tar"),
+            ("P3", ""),
+            ("E3", ""),
+            ("S3", "README"),
+        ];
+        for (name, value) in expects {
+            let expect = DataField::from_chars(name, value);
+            assert_eq!(
+                target.field(name).map(|s| s.as_field()),
+                Some(&expect),
+                "{}",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_pipe_url_get() {
         let cache = &mut FieldQueryCache::default();
@@ -127,4 +205,38 @@ mod tests {
         );
         assert_eq!(target.field("E").map(|s| s.as_field()), Some(&expect));
     }
+
+    #[test]
+    fn test_pipe_url_query_all() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![FieldStorage::from_owned(DataField::from_chars(
+            "A1",
+            "https://a.b.com/search?tag=a&q=x&tag=b%20c&tag=d",
+        ))];
+        let src = DataRecord::from(data);
+
+        let mut conf = r#"
+        name : test
+        ---
+        A = pipe read(A1) | url(query_all:tag);
+        B = pipe read(A1) | url(query_all:q);
+        C = pipe read(A1) | url(query_all:none);
+         "#;
+        let model = oml_parse_raw(&mut conf).unwrap();
+        let target = model.transform(src, cache);
+
+        let expect = DataField::from_arr(
+            "A".to_string(),
+            vec![
+                DataField::from_chars("tag", "a"),
+                DataField::from_chars("tag", "b c"),
+                DataField::from_chars("tag", "d"),
+            ],
+        );
+        assert_eq!(target.field("A").map(|s| s.as_field()), Some(&expect));
+        let expect = DataField::from_arr("B".to_string(), vec![DataField::from_chars("q", "x")]);
+        assert_eq!(target.field("B").map(|s| s.as_field()), Some(&expect));
+        let expect = DataField::from_arr("C".to_string(), vec![]);
+        assert_eq!(target.field("C").map(|s| s.as_field()), Some(&expect));
+    }
 }
@@ -36,4 +36,29 @@ impl FieldExtractor for PiPeOperation {
         }
         None
     }
+
+    fn extract_more(
+        &self,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+        _cache: &mut FieldQueryCache,
+    ) -> Vec<DataField> {
+        let Some(tail) = self.flat_tail() else {
+            return Vec::new();
+        };
+        let Some(mut from) = self
+            .from()
+            .extract_one(&EvaluationTarget::auto_default(), src, dst)
+        else {
+            return Vec::new();
+        };
+        for pipe in &self.items()[..self.items().len() - 1] {
+            from = pipe.value_cacu(from);
+        }
+        tail.extract_fields(&from)
+    }
+
+    fn support_batch(&self) -> bool {
+        self.flat_tail().is_some()
+    }
 }
@@ -1,16 +1,36 @@
+use jieba_rs::Jieba;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 
 /// 语义功能全局开关（默认关闭）
 static SEMANTIC_ENABLED: AtomicBool = AtomicBool::new(false);
 
 /// 设置语义功能开关（由主 crate 在启动时调用）
+///
+/// 开启时立即加载词典，外部词典文件的加载错误以 WARN 级别汇总输出
 pub fn set_semantic_enabled(v: bool) {
     SEMANTIC_ENABLED.store(v, Ordering::Relaxed);
+    if v {
+        // 分词器（含用户词典）同时构建，避免首条数据承担加载开销
+        Lazy::force(&JIEBA);
+        let errors = dict_load_errors();
+        if !errors.is_empty() {
+            warn_data!(
+                "semantic dict: {} file(s) failed to load, fallback to remaining dicts",
+                errors.len()
+            );
+            for e in &errors {
+                warn_data!("semantic dict: {}", e);
+            }
+        }
+    }
 }
 
 /// 查询语义功能是否启用
@@ -50,6 +70,33 @@ pub struct SemanticDictConf {
     pub entity_nouns: Option<EntityNounsConf>,
 }
 
+impl SemanticDictConf {
+    /// 配置中的词汇总数
+    pub fn word_count(&self) -> usize {
+        let mut total = 0;
+        if let Some(ref stop_words) = self.stop_words {
+            total += stop_words.chinese.len() + stop_words.english.len();
+        }
+        if let Some(ref domain_words) = self.domain_words {
+            total += domain_words
+                .categories
+                .values()
+                .map(Vec::len)
+                .sum::<usize>();
+        }
+        if let Some(ref status_words) = self.status_words {
+            total += status_words.english.len() + status_words.chinese.len();
+        }
+        if let Some(ref action_verbs) = self.action_verbs {
+            total += action_verbs.english.len() + action_verbs.chinese.len();
+        }
+        if let Some(ref entity_nouns) = self.entity_nouns {
+            total += entity_nouns.english.len() + entity_nouns.chinese.len();
+        }
+        total
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct StopWordsConf {
     #[serde(default)]
@@ -88,75 +135,496 @@ pub struct EntityNounsConf {
     pub chinese: Vec<String>,
 }
 
+/// 单个词典文件的加载错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictLoadError {
+    pub file: PathBuf,
+    /// 出错位置的行号（从 1 开始）；读取失败等无法定位时为 None
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+impl Display for DictLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.file.display(), line, self.reason),
+            None => write!(f, "{}: {}", self.file.display(), self.reason),
+        }
+    }
+}
+
+impl DictLoadError {
+    fn new(file: &Path, line: Option<usize>, reason: impl Into<String>) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
 /// 加载外部语义词典配置
 pub fn load_semantic_dict(config_path: &Path) -> Result<SemanticDictConf, String> {
-    let content = fs::read_to_string(config_path)
-        .map_err(|e| format!("Failed to read semantic_dict.toml: {}", e))?;
+    load_dict_file(config_path).map_err(|e| e.to_string())
+}
+
+fn load_dict_file(path: &Path) -> Result<SemanticDictConf, DictLoadError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| DictLoadError::new(path, None, format!("read failed: {}", e)))?;
+    parse_dict_content(path, &content)
+}
 
-    let conf: SemanticDictConf = toml::from_str(&content)
-        .map_err(|e| format!("Failed to parse semantic_dict.toml: {}", e))?;
+fn parse_dict_content(path: &Path, content: &str) -> Result<SemanticDictConf, DictLoadError> {
+    let conf: SemanticDictConf = toml::from_str(content).map_err(|e| {
+        let line = e.span().map(|span| line_of(content, span.start));
+        DictLoadError::new(path, line, e.message().to_string())
+    })?;
 
     if conf.version != SUPPORTED_VERSION {
-        return Err(format!(
-            "Unsupported semantic_dict version: {}. Expected: {}",
-            conf.version, SUPPORTED_VERSION
+        let line = content
+            .lines()
+            .position(|l| l.trim_start().starts_with("version"))
+            .map(|idx| idx + 1);
+        return Err(DictLoadError::new(
+            path,
+            line,
+            format!(
+                "unsupported version: {}, expected: {}",
+                conf.version, SUPPORTED_VERSION
+            ),
         ));
     }
 
     Ok(conf)
 }
 
-/// 从向量构建 HashSet（使用 Box::leak 转为 'static）
-fn build_hashset_from_vec(words: &[String]) -> HashSet<&'static str> {
-    words
-        .iter()
-        .map(|s| Box::leak(s.clone().into_boxed_str()) as &'static str)
-        .collect()
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// 配置路径下的词典文件：文件路径原样返回；目录返回其中按名称排序的 `*.toml`
+fn dict_files(path: &Path) -> Result<Vec<PathBuf>, DictLoadError> {
+    if !path.exists() {
+        return Err(DictLoadError::new(path, None, "not found"));
+    }
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let entries = fs::read_dir(path)
+        .map_err(|e| DictLoadError::new(path, None, format!("read dir failed: {}", e)))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+        .filter(|p| !p.ends_with(OVERRIDES_FILE))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// 加载配置路径下的全部词典文件；单个文件失败不影响其余文件
+fn load_dict_path(path: &Path) -> (Vec<(PathBuf, SemanticDictConf)>, Vec<DictLoadError>) {
+    let files = match dict_files(path) {
+        Ok(files) => files,
+        Err(e) => return (Vec::new(), vec![e]),
+    };
+    let mut loaded = Vec::new();
+    let mut errors = Vec::new();
+    for file in files {
+        match load_dict_file(&file) {
+            Ok(conf) => loaded.push((file, conf)),
+            Err(e) => errors.push(e),
+        }
+    }
+    (loaded, errors)
+}
+
+fn config_path_from_env() -> Option<PathBuf> {
+    std::env::var("SEMANTIC_DICT_CONFIG")
+        .ok()
+        .map(PathBuf::from)
+}
+
+/// 全局词典的外部文件加载结果
+#[derive(Debug, Default)]
+struct DictLoadReport {
+    files: Vec<PathBuf>,
+    errors: Vec<DictLoadError>,
+}
+
+/// 最近一次构建全局词典的加载结果，重载时整体替换
+static DICT_LOAD_REPORT: RwLock<DictLoadReport> = RwLock::new(DictLoadReport {
+    files: Vec::new(),
+    errors: Vec::new(),
+});
+
+fn dict_load_errors() -> Vec<DictLoadError> {
+    Lazy::force(&SEMANTIC_DICT);
+    DICT_LOAD_REPORT
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .errors
+        .clone()
+}
+
+/// 全局词典已成功加载的外部词典文件（按合并顺序）
+pub fn list_loaded_dict_files() -> Vec<PathBuf> {
+    Lazy::force(&SEMANTIC_DICT);
+    DICT_LOAD_REPORT
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .files
+        .clone()
+}
+
+/// 词典中的词集合；词条为共享的自有字符串，重载或增删时随旧词典一同释放
+pub type WordSet = HashSet<Arc<str>>;
+
+/// 从向量构建词集合
+fn build_hashset_from_vec(words: &[String]) -> WordSet {
+    words.iter().map(|s| Arc::from(s.as_str())).collect()
 }
 
-/// 从字符串切片构建 HashSet
-fn build_hashset_from_strs(words: &[&'static str]) -> HashSet<&'static str> {
-    words.iter().copied().collect()
+/// 从字符串切片构建词集合
+fn build_hashset_from_strs(words: &[&str]) -> WordSet {
+    words.iter().map(|s| Arc::from(*s)).collect()
 }
 
 /// 全局语义词典（使用 Lazy 延迟加载）
-pub static SEMANTIC_DICT: Lazy<SemanticDict> = Lazy::new(|| {
+///
+/// 运行时增删词条时整体替换内部的 Arc，正在进行的分类仍使用已取得的快照
+pub static SEMANTIC_DICT: Lazy<RwLock<Arc<SemanticDict>>> = Lazy::new(|| {
+    let (dict, report) = build_semantic_dict();
+    *DICT_LOAD_REPORT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = report;
+    RwLock::new(Arc::new(dict))
+});
+
+/// 按当前配置构建词典：内置词典 → 外部配置 → 持久化增删记录
+fn build_semantic_dict() -> (SemanticDict, DictLoadReport) {
     // 创建内置默认词典
     let mut dict = SemanticDict::builtin();
 
-    // 尝试加载外部配置
-    if let Ok(config_path) = std::env::var("SEMANTIC_DICT_CONFIG") {
-        match load_semantic_dict(Path::new(&config_path)) {
-            Ok(conf) => {
-                dict.merge(conf);
-            }
-            Err(e) => {
-                eprintln!(
-                    "Warning: Failed to load external semantic dict config: {}.",
-                    e
-                );
-            }
+    // 尝试加载外部配置（文件或目录），失败的文件跳过
+    let mut report = DictLoadReport::default();
+    if let Some(config_path) = config_path_from_env() {
+        let (loaded, errors) = load_dict_path(&config_path);
+        for (file, conf) in loaded {
+            dict.merge(conf);
+            report.files.push(file);
+        }
+        report.errors = errors;
+    }
+
+    // 最后合并运行时持久化的增删记录
+    if let Some(path) = overrides_path() {
+        match SemanticOverrides::load(&path) {
+            Ok(overrides) => dict.apply_overrides(&overrides),
+            Err(e) => warn_data!("semantic dict: load overrides failed: {}", e),
+        }
+    }
+
+    (dict, report)
+}
+
+/// 当前全局词典的快照
+pub fn semantic_dict() -> Arc<SemanticDict> {
+    SEMANTIC_DICT
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// 可在运行时增删的词典类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SemanticCategory {
+    StopWords,
+    DomainWords,
+    StatusWords,
+    ActionVerbs,
+    EntityNouns,
+}
+
+impl SemanticCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SemanticCategory::StopWords => "stop_words",
+            SemanticCategory::DomainWords => "domain_words",
+            SemanticCategory::StatusWords => "status_words",
+            SemanticCategory::ActionVerbs => "action_verbs",
+            SemanticCategory::EntityNouns => "entity_nouns",
+        }
+    }
+}
+
+impl Display for SemanticCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SemanticCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stop_words" => Ok(SemanticCategory::StopWords),
+            "domain_words" => Ok(SemanticCategory::DomainWords),
+            "status_words" => Ok(SemanticCategory::StatusWords),
+            "action_verbs" => Ok(SemanticCategory::ActionVerbs),
+            "entity_nouns" => Ok(SemanticCategory::EntityNouns),
+            _ => Err(format!("unknown semantic category: {}", s)),
+        }
+    }
+}
+
+/// 运行时增删记录的持久化文件名，位于外部词典配置旁（目录配置时位于目录内）
+const OVERRIDES_FILE: &str = "semantic_dict.overrides.toml";
+
+/// 持久化文件位置：优先取 SEMANTIC_DICT_OVERRIDES，否则依附 SEMANTIC_DICT_CONFIG；
+/// 均未配置时增删仅在内存中生效
+fn overrides_path() -> Option<PathBuf> {
+    path_beside_config("SEMANTIC_DICT_OVERRIDES", OVERRIDES_FILE)
+}
+
+/// 环境变量指定的路径优先，否则取外部词典配置旁的同名文件（目录配置时位于目录内）
+fn path_beside_config(env: &str, file_name: &str) -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(env) {
+        return Some(PathBuf::from(path));
+    }
+    let conf = config_path_from_env()?;
+    if conf.is_dir() {
+        Some(conf.join(file_name))
+    } else {
+        Some(conf.with_file_name(file_name))
+    }
+}
+
+/// 运行时增删记录，按类别保存；启动时在外部词典之后合并
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemanticOverrides {
+    #[serde(default)]
+    pub added: BTreeMap<String, BTreeSet<String>>,
+    #[serde(default)]
+    pub removed: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SemanticOverrides {
+    /// 读取持久化文件；文件不存在时返回空记录
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("read {} failed: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("parse {} failed: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|e| e.to_string())?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("create {} failed: {}", dir.display(), e))?;
+        }
+        // 先写临时文件再改名，避免进程中断留下半个文件
+        let tmp = path.with_extension("toml.tmp");
+        fs::write(&tmp, content).map_err(|e| format!("write {} failed: {}", tmp.display(), e))?;
+        fs::rename(&tmp, path).map_err(|e| format!("write {} failed: {}", path.display(), e))
+    }
+
+    /// 记录一次增删，返回记录是否变化
+    fn record(&mut self, category: SemanticCategory, word: &str, add: bool) -> bool {
+        let (to, from) = if add {
+            (&mut self.added, &mut self.removed)
+        } else {
+            (&mut self.removed, &mut self.added)
+        };
+        let key = category.as_str();
+        let dropped = from.get_mut(key).is_some_and(|set| set.remove(word));
+        if from.get(key).is_some_and(BTreeSet::is_empty) {
+            from.remove(key);
         }
+        let inserted = to
+            .entry(key.to_string())
+            .or_default()
+            .insert(word.to_string());
+        dropped || inserted
     }
+}
+
+/// 串行化运行时增删，并缓存当前持久化记录
+static OVERRIDES: Lazy<Mutex<SemanticOverrides>> = Lazy::new(|| Mutex::new(stored_overrides()));
+
+fn stored_overrides() -> SemanticOverrides {
+    overrides_path()
+        .and_then(|path| SemanticOverrides::load(&path).ok())
+        .unwrap_or_default()
+}
+
+/// 运行时向全局词典添加词条，立即对后续分类生效；配置了持久化位置时同步写入
+///
+/// 返回词典是否发生变化（词条已存在时为 false）
+pub fn add_semantic_entry(category: SemanticCategory, word: &str) -> Result<bool, String> {
+    update_semantic_entry(category, word, true)
+}
+
+/// 运行时从全局词典移除词条（包括内置词条），语义同 [`add_semantic_entry`]
+pub fn remove_semantic_entry(category: SemanticCategory, word: &str) -> Result<bool, String> {
+    update_semantic_entry(category, word, false)
+}
+
+fn update_semantic_entry(
+    category: SemanticCategory,
+    word: &str,
+    add: bool,
+) -> Result<bool, String> {
+    // 分类时按小写查词，词条统一转小写
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return Err("semantic entry is empty".to_string());
+    }
+    let mut overrides = OVERRIDES.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let mut dict = SemanticDict::clone(&semantic_dict());
+    let changed = dict.update(category, &word, add);
+    if changed {
+        *SEMANTIC_DICT
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(dict);
+    }
+
+    if overrides.record(category, &word, add)
+        && let Some(path) = overrides_path()
+    {
+        overrides.save(&path)?;
+    }
+    Ok(changed)
+}
+
+/// 按当前配置重新加载全局词典与 jieba 用户词典，返回加载失败的文件
+///
+/// 失败的文件跳过，其余照常生效；进行中的分析继续使用已取得的快照
+pub fn reload_semantic_dict() -> Vec<DictLoadError> {
+    let mut overrides = OVERRIDES.lock().unwrap_or_else(PoisonError::into_inner);
+    let (dict, report) = build_semantic_dict();
+    *overrides = stored_overrides();
+    *SEMANTIC_DICT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Arc::new(dict);
+    let mut errors = report.errors.clone();
+    *DICT_LOAD_REPORT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = report;
+    drop(overrides);
+
+    let (jieba, error) = build_jieba();
+    install_jieba(jieba);
+    errors.extend(error);
+    errors
+}
+
+/// jieba 用户词典文件名，位于外部词典配置旁（目录配置时位于目录内）
+const USER_DICT_FILE: &str = "jieba_user.dict";
+
+/// 用户词条未标注词性时按其他专名（nz）处理，使其参与核心词提取
+const USER_DICT_DEFAULT_TAG: &str = "nz";
 
-    dict
+/// 用户词典位置：优先取 SEMANTIC_USER_DICT，否则依附 SEMANTIC_DICT_CONFIG；文件不存在时不加载
+fn user_dict_path() -> Option<PathBuf> {
+    path_beside_config("SEMANTIC_USER_DICT", USER_DICT_FILE).filter(|p| p.is_file())
+}
+
+/// 全局 jieba 分词器（默认词典 + 用户词典），重载时整体替换
+static JIEBA: Lazy<RwLock<Arc<Jieba>>> = Lazy::new(|| {
+    let (jieba, error) = build_jieba();
+    if let Some(e) = error {
+        warn_data!("jieba user dict: {}", e);
+    }
+    RwLock::new(Arc::new(jieba))
 });
 
+/// 当前全局分词器
+pub(crate) fn jieba() -> Arc<Jieba> {
+    JIEBA.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+fn install_jieba(jieba: Jieba) {
+    *JIEBA.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(jieba);
+}
+
+/// 按配置构建分词器；用户词典加载失败时使用默认分词器并返回错误
+fn build_jieba() -> (Jieba, Option<DictLoadError>) {
+    let mut jieba = Jieba::new();
+    let error = user_dict_path().and_then(|path| load_user_dict(&mut jieba, &path).err());
+    (jieba, error)
+}
+
+/// 以指定用户词典重建全局分词器，返回加载的词条数；失败时保留当前分词器
+pub fn set_jieba_user_dict(path: &Path) -> Result<usize, DictLoadError> {
+    let mut jieba = Jieba::new();
+    let count = load_user_dict(&mut jieba, path)?;
+    install_jieba(jieba);
+    Ok(count)
+}
+
+/// 向分词器加载用户词典，返回加载的词条数
+///
+/// 每行 `词 [词频] [词性]`，空行与 `#` 开头的行忽略；省略词频时自动取足以整词切出的词频。
+/// 整个文件校验通过后才写入分词器
+pub fn load_user_dict(jieba: &mut Jieba, path: &Path) -> Result<usize, DictLoadError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| DictLoadError::new(path, None, format!("read failed: {}", e)))?;
+    let mut entries = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let Some(word) = parts.next() else {
+            continue;
+        };
+        let mut freq = None;
+        let mut tag = None;
+        for part in parts {
+            if freq.is_none() && tag.is_none() && part.chars().all(|c| c.is_ascii_digit()) {
+                let value = part.parse::<usize>().map_err(|e| {
+                    DictLoadError::new(path, Some(idx + 1), format!("invalid freq: {}", e))
+                })?;
+                freq = Some(value);
+            } else if tag.is_none() {
+                tag = Some(part);
+            } else {
+                return Err(DictLoadError::new(
+                    path,
+                    Some(idx + 1),
+                    format!("unexpected token '{}', expected: word [freq] [pos]", part),
+                ));
+            }
+        }
+        entries.push((word, freq, tag.unwrap_or(USER_DICT_DEFAULT_TAG)));
+    }
+    for (word, freq, tag) in &entries {
+        jieba.add_word(word, *freq, Some(tag));
+    }
+    Ok(entries.len())
+}
+
 /// 语义词典运行时结构
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SemanticDict {
     /// 核心词性标签（用于 extract_main_word）
     pub core_pos: HashSet<&'static str>,
     /// 停用词
-    pub stop_words: HashSet<&'static str>,
+    pub stop_words: WordSet,
     /// 日志领域关键词
-    pub domain_words: HashSet<&'static str>,
+    pub domain_words: WordSet,
     /// 状态词
-    pub status_words: HashSet<&'static str>,
+    pub status_words: WordSet,
     /// 动作词
-    pub action_verbs: HashSet<&'static str>,
+    pub action_verbs: WordSet,
     /// 实体名词
-    pub entity_nouns: HashSet<&'static str>,
+    pub entity_nouns: WordSet,
 }
 
 impl SemanticDict {
@@ -164,12 +632,14 @@ impl SemanticDict {
     pub fn builtin() -> Self {
         Self {
             // 核心词性（硬编码，不可配置）
-            core_pos: build_hashset_from_strs(&[
+            core_pos: [
                 "n", "nr", "ns", "nt", "nz", "ng", // 名词类
                 "v", "vn", "vd", // 动词类
                 "a", "ad", "an", // 形容词类
                 "eng", "m", "x", "t", "i", // 英文、数词等
-            ]),
+            ]
+            .into_iter()
+            .collect(),
 
             // 停用词
             stop_words: build_hashset_from_strs(&[
@@ -335,6 +805,41 @@ impl SemanticDict {
         }
     }
 
+    fn category_mut(&mut self, category: SemanticCategory) -> &mut WordSet {
+        match category {
+            SemanticCategory::StopWords => &mut self.stop_words,
+            SemanticCategory::DomainWords => &mut self.domain_words,
+            SemanticCategory::StatusWords => &mut self.status_words,
+            SemanticCategory::ActionVerbs => &mut self.action_verbs,
+            SemanticCategory::EntityNouns => &mut self.entity_nouns,
+        }
+    }
+
+    /// 增删单个词条，返回是否发生变化
+    fn update(&mut self, category: SemanticCategory, word: &str, add: bool) -> bool {
+        let set = self.category_mut(category);
+        if add {
+            !set.contains(word) && set.insert(Arc::from(word))
+        } else {
+            set.remove(word)
+        }
+    }
+
+    /// 合并运行时增删记录：先加后删，未知类别跳过
+    pub fn apply_overrides(&mut self, overrides: &SemanticOverrides) {
+        for (add, records) in [(true, &overrides.added), (false, &overrides.removed)] {
+            for (key, words) in records {
+                let Ok(category) = key.parse::<SemanticCategory>() else {
+                    warn_data!("semantic dict overrides: unknown category {}", key);
+                    continue;
+                };
+                for word in words {
+                    self.update(category, word, add);
+                }
+            }
+        }
+    }
+
     /// 合并外部配置
     pub fn merge(&mut self, conf: SemanticDictConf) {
         let mode = conf.mode;
@@ -426,74 +931,55 @@ impl SemanticDict {
 
 // ========== 公开的 API 方法 ==========
 
-/// 检查语义词典配置文件是否有效
+/// 检查语义词典配置是否有效
 ///
 /// 用于 `wproj check` 命令验证配置文件
 ///
 /// # 参数
-/// - `config_path`: 配置文件路径（可选），如果为 None 则检查环境变量 SEMANTIC_DICT_CONFIG
+/// - `config_path`: 配置文件或目录（可选），如果为 None 则检查环境变量 SEMANTIC_DICT_CONFIG；
+///   目录下的全部 `*.toml` 均会加载，单个文件失败后继续加载其余文件
 ///
 /// # 返回
-/// - Ok(Some(message)): 配置文件存在且有效，返回成功信息
+/// - Ok(Some(message)): 配置存在且全部有效，返回成功信息
 /// - Ok(None): 未配置外部语义词典（使用默认内置词典）
-/// - Err(message): 配置文件无效或加载失败
-pub fn check_semantic_dict_config(config_path: Option<&Path>) -> Result<Option<String>, String> {
+/// - Err(errors): 各加载失败文件的错误
+pub fn check_semantic_dict_config(
+    config_path: Option<&Path>,
+) -> Result<Option<String>, Vec<DictLoadError>> {
     // 确定配置文件路径
-    let path = if let Some(p) = config_path {
-        Some(p.to_path_buf())
-    } else {
-        std::env::var("SEMANTIC_DICT_CONFIG")
-            .ok()
-            .map(PathBuf::from)
-    };
+    let path = config_path
+        .map(Path::to_path_buf)
+        .or_else(config_path_from_env);
 
     // 如果没有配置，返回 None（使用内置词典）
     let Some(path) = path else {
         return Ok(None);
     };
 
-    // 检查文件是否存在
-    if !path.exists() {
-        return Err(format!("语义词典配置文件不存在: {}", path.display()));
+    let (loaded, errors) = load_dict_path(&path);
+    if !errors.is_empty() {
+        return Err(errors);
     }
 
-    // 尝试加载并验证配置
-    match load_semantic_dict(&path) {
-        Ok(conf) => {
-            // 统计配置的词汇数量
-            let mut total_words = 0;
-            if let Some(ref stop_words) = conf.stop_words {
-                total_words += stop_words.chinese.len() + stop_words.english.len();
-            }
-            if let Some(ref domain_words) = conf.domain_words {
-                for words in domain_words.categories.values() {
-                    total_words += words.len();
-                }
-            }
-            if let Some(ref status_words) = conf.status_words {
-                total_words += status_words.english.len() + status_words.chinese.len();
-            }
-            if let Some(ref action_verbs) = conf.action_verbs {
-                total_words += action_verbs.english.len() + action_verbs.chinese.len();
-            }
-            if let Some(ref entity_nouns) = conf.entity_nouns {
-                total_words += entity_nouns.english.len() + entity_nouns.chinese.len();
-            }
-
-            let mode_str = match conf.mode {
-                MergeMode::Add => "ADD（扩展内置词典）",
-                MergeMode::Replace => "REPLACE（替换内置词典）",
-            };
-
-            Ok(Some(format!(
-                "语义词典配置有效: {} | 模式: {} | 词汇数: {}",
-                path.display(),
-                mode_str,
-                total_words
-            )))
-        }
-        Err(e) => Err(format!("语义词典配置加载失败: {}", e)),
-    }
+    // 统计配置的词汇数量
+    let total_words: usize = loaded.iter().map(|(_, conf)| conf.word_count()).sum();
+    let modes: HashSet<&str> = loaded
+        .iter()
+        .map(|(_, conf)| match conf.mode {
+            MergeMode::Add => "ADD（扩展内置词典）",
+            MergeMode::Replace => "REPLACE（替换内置词典）",
+        })
+        .collect();
+    let mut modes: Vec<&str> = modes.into_iter().collect();
+    modes.sort();
+
+    Ok(Some(format!(
+        "语义词典配置有效: {} | 文件数: {} | 模式: {} | 词汇数: {}",
+        path.display(),
+        loaded.len(),
+        modes.join(", "),
+        total_words
+    )))
 }
 
 /// 初始化语义词典，加载外部配置（如果存在）
@@ -507,7 +993,7 @@ pub fn check_semantic_dict_config(config_path: Option<&Path>) -> Result<Option<S
 /// - Err(message): 加载失败（但会回退到内置词典）
 pub fn init_semantic_dict() -> Result<String, String> {
     // 触发 SEMANTIC_DICT 的延迟初始化
-    let dict = &*SEMANTIC_DICT;
+    let dict = semantic_dict();
 
     // 检查是否有外部配置
     if let Ok(config_path) = std::env::var("SEMANTIC_DICT_CONFIG") {
@@ -533,9 +1019,14 @@ pub fn init_semantic_dict() -> Result<String, String> {
 /// 用于 `wproj init` 命令创建配置文件模板
 ///
 /// # 返回
-/// 配置文件的 TOML 内容字符串
-pub fn generate_default_semantic_dict_config() -> String {
-    r#"# 语义词典外部配置（知识配置）
+/// 配置文件的 TOML 内容字符串；生成后立即按加载规则校验，失败时返回错误
+pub fn generate_default_semantic_dict_config() -> Result<String, Vec<DictLoadError>> {
+    let content = DEFAULT_SEMANTIC_DICT_CONFIG.to_string();
+    parse_dict_content(Path::new("semantic_dict.toml"), &content).map_err(|e| vec![e])?;
+    Ok(content)
+}
+
+const DEFAULT_SEMANTIC_DICT_CONFIG: &str = r#"# 语义词典外部配置（知识配置）
 # 文件位置：models/knowledge/semantic_dict.toml
 # 用于扩展或替换系统内置的语义词典
 
@@ -580,9 +1071,7 @@ mode = "add"
 # [entity_nouns]
 # english = ["migration", "notification"]
 # chinese = ["迁移任务", "通知"]
-"#
-    .to_string()
-}
+"#;
 
 #[cfg(test)]
 mod tests {
@@ -704,9 +1193,58 @@ chinese = ["部署"]
     #[test]
     fn test_global_semantic_dict() {
         // 测试全局词典可以访问
-        assert!(!SEMANTIC_DICT.core_pos.is_empty());
-        assert!(!SEMANTIC_DICT.stop_words.is_empty());
-        assert!(!SEMANTIC_DICT.domain_words.is_empty());
+        let dict = semantic_dict();
+        assert!(!dict.core_pos.is_empty());
+        assert!(!dict.stop_words.is_empty());
+        assert!(!dict.domain_words.is_empty());
+    }
+
+    #[test]
+    fn test_overrides_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(OVERRIDES_FILE);
+        let mut overrides = SemanticOverrides::default();
+        assert!(overrides.record(SemanticCategory::StatusWords, "quarantined", true));
+        assert!(!overrides.record(SemanticCategory::StatusWords, "quarantined", true));
+        assert!(overrides.record(SemanticCategory::ActionVerbs, "connect", false));
+        overrides.save(&path).unwrap();
+
+        let loaded = SemanticOverrides::load(&path).unwrap();
+        assert_eq!(loaded, overrides);
+        let mut dict = SemanticDict::builtin();
+        dict.apply_overrides(&loaded);
+        assert!(dict.status_words.contains("quarantined"));
+        assert!(!dict.action_verbs.contains("connect"));
+
+        // 删除后再添加，记录从 removed 移回 added
+        overrides.record(SemanticCategory::ActionVerbs, "connect", true);
+        assert!(overrides.removed.is_empty());
+
+        // 持久化文件不会被当作词典加载
+        std::fs::write(
+            dir.path().join("dict.toml"),
+            "version = 1\n[status_words]\nenglish = [\"queued\"]\n",
+        )
+        .unwrap();
+        let files = dict_files(dir.path()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("dict.toml"));
+    }
+
+    #[test]
+    fn test_update_keeps_owned_words() {
+        let mut dict = SemanticDict::builtin();
+        let word = String::from("kafka_lag");
+        assert!(dict.update(SemanticCategory::DomainWords, &word, true));
+        drop(word);
+        assert!(dict.domain_words.contains("kafka_lag"));
+        assert!(!dict.update(SemanticCategory::DomainWords, "kafka_lag", true));
+
+        // 克隆共享词条，增删互不影响
+        let snapshot = dict.clone();
+        assert!(dict.update(SemanticCategory::DomainWords, "kafka_lag", false));
+        assert!(!dict.domain_words.contains("kafka_lag"));
+        assert!(snapshot.domain_words.contains("kafka_lag"));
     }
 
     #[test]
@@ -811,4 +1349,64 @@ k8s_network = ["ingress", "endpoint"]
         // 验证词典大小等于配置的词汇数量
         assert_eq!(dict.domain_words.len(), 5); // pod, deployment, service, ingress, endpoint
     }
+
+    #[test]
+    fn test_check_dict_dir_collects_all_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("10-ok.toml"),
+            "version = 1\n[status_words]\nenglish = [\"queued\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("20-bad.toml"),
+            "version = 1\n\n[status_words\nenglish = []\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("30-old.toml"), "# old\nversion = 2\n").unwrap();
+        std::fs::write(dir.path().join("readme.txt"), "ignored").unwrap();
+
+        let errors = check_semantic_dict_config(Some(dir.path())).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].file.ends_with("20-bad.toml"));
+        assert_eq!(errors[0].line, Some(3));
+        assert!(errors[1].file.ends_with("30-old.toml"));
+        assert_eq!(errors[1].line, Some(2));
+        assert!(errors[1].reason.contains("unsupported version"));
+
+        std::fs::remove_file(dir.path().join("20-bad.toml")).unwrap();
+        std::fs::remove_file(dir.path().join("30-old.toml")).unwrap();
+        let msg = check_semantic_dict_config(Some(dir.path()))
+            .unwrap()
+            .unwrap();
+        assert!(msg.contains("文件数: 1"), "{}", msg);
+
+        let missing = check_semantic_dict_config(Some(&dir.path().join("none.toml"))).unwrap_err();
+        assert_eq!(missing[0].line, None);
+    }
+
+    #[test]
+    fn test_load_user_dict() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(USER_DICT_FILE);
+        std::fs::write(&path, "# 产品名\n天擎\nEDR控制台 20 nz\n\n态势感知 n\n").unwrap();
+        let mut jieba = Jieba::new();
+        assert_eq!(load_user_dict(&mut jieba, &path).unwrap(), 3);
+        let tags = jieba.tag("天擎终端", true);
+        assert_eq!(tags[0].word, "天擎");
+        assert_eq!(tags[0].tag, USER_DICT_DEFAULT_TAG);
+
+        std::fs::write(&path, "天擎\n态势感知 10 n extra\n").unwrap();
+        let err = load_user_dict(&mut Jieba::new(), &path).unwrap_err();
+        assert_eq!(err.line, Some(2));
+
+        let missing = load_user_dict(&mut Jieba::new(), &dir.path().join("none.dict"));
+        assert_eq!(missing.unwrap_err().line, None);
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        let content = generate_default_semantic_dict_config().unwrap();
+        assert!(content.contains("version = 1"));
+    }
 }
@@ -0,0 +1,53 @@
+//! OML 转换线程持有的查询缓存（[`FieldQueryCache`]）的构建参数。
+//! - 容量固定为 [`LOOKUP_CACHE_CAPACITY`]；
+//! - 有效期对应引擎配置 `[oml].lookup_cache_ttl_ms`，未设置时条目只受容量约束。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use wp_data_model::cache::FieldQueryCache;
+
+/// 每个转换线程缓存的查询结果条数
+pub const LOOKUP_CACHE_CAPACITY: usize = 1000;
+
+/// 有效期（毫秒）；`u64::MAX` 表示未设置
+static LOOKUP_CACHE_TTL_MS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// 设置查询缓存有效期（由主 crate 在启动时调用）；只影响之后创建的缓存
+pub fn set_ttl(ttl: Option<Duration>) {
+    let ms = ttl.map_or(u64::MAX, |ttl| {
+        ttl.as_millis().min(u64::MAX as u128 - 1) as u64
+    });
+    LOOKUP_CACHE_TTL_MS.store(ms, Ordering::Relaxed);
+}
+
+pub fn ttl() -> Option<Duration> {
+    match LOOKUP_CACHE_TTL_MS.load(Ordering::Relaxed) {
+        u64::MAX => None,
+        ms => Some(Duration::from_millis(ms)),
+    }
+}
+
+/// 按当前配置创建转换线程使用的查询缓存
+pub fn new_cache() -> FieldQueryCache {
+    match ttl() {
+        Some(ttl) => FieldQueryCache::with_ttl(LOOKUP_CACHE_CAPACITY, ttl),
+        None => FieldQueryCache::with_capacity(LOOKUP_CACHE_CAPACITY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_follows_configured_ttl() {
+        set_ttl(Some(Duration::from_millis(250)));
+        let cache = new_cache();
+        assert_eq!(cache.capacity(), LOOKUP_CACHE_CAPACITY);
+        assert_eq!(cache.ttl(), Some(Duration::from_millis(250)));
+
+        set_ttl(None);
+        assert_eq!(new_cache().ttl(), None);
+    }
+}
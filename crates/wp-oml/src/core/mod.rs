@@ -1,6 +1,9 @@
+pub mod assertion;
+pub mod capture;
 pub mod diagnostics;
 mod error;
 pub mod evaluator; // 公开 evaluator 模块
+pub mod lookup_cache;
 mod model;
 mod prelude;
 pub use error::OMLRunError;
@@ -12,12 +15,18 @@ use crate::language::EvaluationTarget;
 use crate::language::PreciseEvaluator;
 pub use evaluator::ConfADMExt;
 pub use evaluator::DataTransformer;
+pub use evaluator::aggregate::AggregateEvaluator;
 pub use evaluator::traits::BatchFetcher;
 pub use evaluator::traits::ExpEvaluator;
 pub use evaluator::traits::FieldCollector;
 pub use evaluator::traits::ValueProcessor;
 use wp_data_model::cache::FieldQueryCache;
-use wp_model_core::model::{DataField, DataRecord, FieldStorage};
+use wp_model_core::model::{DataField, DataRecord, FieldStorage, Value};
+
+/// coalesce 跳过空字符串结果
+fn is_empty_chars(value: &Value) -> bool {
+    matches!(value, Value::Chars(s) if s.is_empty())
+}
 
 pub trait FieldExtractor {
     /// Extract field as owned DataField
@@ -73,6 +82,13 @@ impl FieldExtractor for PreciseEvaluator {
             PreciseEvaluator::Fmt(o) => o.extract_one(target, src, dst),
             PreciseEvaluator::Collect(o) => o.extract_one(target, src, dst),
             PreciseEvaluator::Val(o) => o.extract_one(target, src, dst),
+            PreciseEvaluator::Coalesce(args) => args.iter().find_map(|arg| {
+                arg.extract_one(target, src, dst)
+                    .filter(|field| !is_empty_chars(field.get_value()))
+            }),
+            PreciseEvaluator::Env { value, .. } => value
+                .as_ref()
+                .map(|v| DataField::from_chars(target.safe_name(), v.clone())),
             PreciseEvaluator::ObjArc(arc) => arc.as_ref().extract_one(target, src, dst),
             PreciseEvaluator::StaticSymbol(sym) => {
                 panic!("unresolved static symbol during execution: {sym}")
@@ -116,6 +132,7 @@ impl FieldExtractor for PreciseEvaluator {
             PreciseEvaluator::Fmt(o) => o.extract_more(src, dst, cache),
             PreciseEvaluator::Collect(o) => o.extract_more(src, dst, cache),
             PreciseEvaluator::Val(o) => o.extract_more(src, dst, cache),
+            PreciseEvaluator::Coalesce(_) | PreciseEvaluator::Env { .. } => Vec::new(),
             PreciseEvaluator::StaticSymbol(sym) => {
                 panic!("unresolved static symbol during execution: {sym}")
             }
@@ -135,6 +152,7 @@ impl FieldExtractor for PreciseEvaluator {
             PreciseEvaluator::Fmt(o) => o.support_batch(),
             PreciseEvaluator::Collect(o) => o.support_batch(),
             PreciseEvaluator::Val(o) => o.support_batch(),
+            PreciseEvaluator::Coalesce(_) | PreciseEvaluator::Env { .. } => false,
             PreciseEvaluator::StaticSymbol(sym) => {
                 panic!("unresolved static symbol during execution: {sym}")
             }
@@ -1,4 +1,5 @@
 mod object;
 mod record;
+mod timeout;
 mod types;
 pub use record::DataRecordRef;
@@ -1,13 +1,19 @@
 use super::super::{ConfADMExt, DataTransformer};
+use super::timeout::run_with_timeout;
 use crate::core::diagnostics;
 use crate::core::evaluator::traits::ExpEvaluator;
 use crate::core::prelude::*;
+use crate::core::{OMLRunReason, OMLRunResult, assertion};
 use crate::language::ObjModel;
 use crate::parser::error::OMLCodeErrorTait;
-use crate::parser::oml_parse_raw;
+use crate::parser::{oml_parse_raw_cached, static_cache_path};
 use orion_error::{ContextRecord, ErrorOwe, ErrorWith, WithContext};
-use wp_data_model::cache::FieldQueryCache;
+use std::sync::Arc;
+use std::time::Duration;
+use wp_data_model::cache::{CacheStats, FieldQueryCache};
+use wp_data_model::limits::{self, RecordVerdict};
 use wp_error::parse_error::{OMLCodeError, OMLCodeReason, OMLCodeResult};
+use wp_knowledge::facade as kdb;
 use wp_model_core::model::DataRecord;
 use wp_model_core::model::FieldStorage;
 use wp_parser::comment::CommentParser;
@@ -18,12 +24,62 @@ impl DataTransformer for ObjModel {
     }
 
     fn transform_ref(&self, data: &DataRecord, cache: &mut FieldQueryCache) -> DataRecord {
+        match self.timeout_ms() {
+            Some(ms) => self.transform_within(
+                data,
+                cache,
+                Duration::from_millis(*ms),
+                |model, data, cache| model.eval_record(data, cache),
+            ),
+            None => self.eval_record(data, cache),
+        }
+    }
+
+    fn append(&self, data: &mut DataRecord) {
+        let empty = DataRecord::default();
+        let mut src = DataRecordRef::from(&empty);
+        let mut cache = FieldQueryCache::default();
+        for ado in &self.items {
+            ado.eval_proc(&mut src, data, &mut cache);
+        }
+    }
+}
+
+impl ObjModel {
+    /// 带断言检查的转换：严格模式下任一 `assert` 失败即返回 `OMLRunReason::AssertFailed`
+    pub fn try_transform_ref(
+        &self,
+        data: &DataRecord,
+        cache: &mut FieldQueryCache,
+    ) -> OMLRunResult<DataRecord> {
+        let out = self.transform_ref(data, cache);
+        match assertion::take_failure() {
+            Some(err) => Err(err),
+            None => Ok(out),
+        }
+    }
+
+    fn eval_record(&self, data: &DataRecord, cache: &mut FieldQueryCache) -> DataRecord {
         diagnostics::reset();
+        assertion::reset();
         let mut out = DataRecord::default();
         let mut tdo_ref = DataRecordRef::from(data);
+        // 与 debug_data! 同一日志目标，未开启 DEBUG 时不构造轨迹
+        let trace = self.debug && log::log_enabled!(target: "data", log::Level::Debug);
+        let lookups = LookupSnapshot::take(cache);
+        limits::begin_record();
         for ado in &self.items {
+            let mark = out.items.len();
             ado.eval_proc(&mut tdo_ref, &mut out, cache);
+            if trace {
+                let produced = &out.items[mark.min(out.items.len())..];
+                for line in self.trace_lines(ado, data, produced) {
+                    debug_data!("{}", line);
+                }
+            }
         }
+        self.lookup_stats
+            .record(LookupSnapshot::take(cache).since(&lookups));
         debug_data!("{} convert crate item : {}", self.name(), self.items.len());
 
         // Filter temporary fields only if the model has any
@@ -37,84 +93,147 @@ impl DataTransformer for ObjModel {
             }
         }
 
+        // 标记字段在临时字段过滤之后追加，避免被当作 `__` 临时字段忽略
+        match limits::finish_record() {
+            RecordVerdict::Intact => {}
+            RecordVerdict::Truncated(_) => out.append(limits::truncated_marker()),
+            RecordVerdict::Dropped(kind) => {
+                assertion::record_reason(OMLRunReason::LimitExceeded(kind.to_string()))
+            }
+        }
         out
     }
 
-    fn append(&self, data: &mut DataRecord) {
-        let empty = DataRecord::default();
-        let mut src = DataRecordRef::from(&empty);
-        let mut cache = FieldQueryCache::default();
-        for ado in &self.items {
-            ado.eval_proc(&mut src, data, &mut cache);
+    /// `debug : true` 的求值轨迹：每个输出字段一行，含求值器类型与直接读取的源字段值
+    fn trace_lines(
+        &self,
+        exp: &EvalExp,
+        input: &DataRecord,
+        produced: &[FieldStorage],
+    ) -> Vec<String> {
+        let (evaluator, source, targets) = match exp {
+            EvalExp::Single(x) => (
+                x.eval_way().kind_name(),
+                x.eval_way().source_field(),
+                x.target()
+                    .iter()
+                    .map(|t| t.name().as_deref().unwrap_or("*"))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            EvalExp::Batch(_) => ("Batch", None, "*".to_string()),
+            EvalExp::Assert(_) => return Vec::new(),
+        };
+        let input = match source {
+            Some(name) => match input.field(name) {
+                Some(f) => format!("{}:{}", name, f.get_value()),
+                None => format!("{}:<absent>", name),
+            },
+            None => "-".to_string(),
+        };
+        let head = format!("[model:{}]", self.name());
+        if produced.is_empty() {
+            return vec![format!(
+                "{} field={} evaluator={} input={} output=<none>",
+                head, targets, evaluator, input
+            )];
         }
+        produced
+            .iter()
+            .map(|field| {
+                format!(
+                    "{} field={} evaluator={} input={} output={}",
+                    head,
+                    field.get_name(),
+                    evaluator,
+                    input,
+                    field.get_value()
+                )
+            })
+            .collect()
     }
 
-    /// Optimized batch processing that reuses cache and model across all records
-    fn transform_batch(
+    /// 限时转换：`eval` 在工作线程中执行，超过 `timeout` 则原样返回输入记录。
+    ///
+    /// 查询缓存随任务移交工作线程，超时时调用方得到同容量的空缓存；
+    /// 断言失败与诊断信息位于线程局部缓冲，完成后转交回调用线程。
+    fn transform_within<F>(
         &self,
-        records: Vec<DataRecord>,
+        data: &DataRecord,
         cache: &mut FieldQueryCache,
-    ) -> Vec<DataRecord> {
-        // Pre-allocate result vector for better performance
-        let mut results = Vec::with_capacity(records.len());
-
-        // Process each record with shared cache
-        for record in records {
-            let mut out = DataRecord::default();
-            let mut tdo_ref = DataRecordRef::from(&record);
-
-            // Reuse the same cache across all records (key optimization)
-            for ado in &self.items {
-                ado.eval_proc(&mut tdo_ref, &mut out, cache);
-            }
-
-            // Filter temporary fields if needed
-            if self.has_temp_fields() {
-                for field in &mut out.items {
-                    if field.get_name().starts_with("__") {
-                        *field = FieldStorage::from_owned(DataField::from_ignore(field.get_name()));
-                    }
+        timeout: Duration,
+        eval: F,
+    ) -> DataRecord
+    where
+        F: FnOnce(&ObjModel, &DataRecord, &mut FieldQueryCache) -> DataRecord + Send + 'static,
+    {
+        let model = Arc::clone(self.shared.get_or_init(|| Arc::new(self.clone())));
+        let input = data.clone();
+        let fresh = cache.empty_like();
+        let mut worker_cache = std::mem::replace(cache, fresh);
+        let done = run_with_timeout(timeout, move || {
+            let out = eval(&model, &input, &mut worker_cache);
+            (
+                out,
+                worker_cache,
+                assertion::take_reason(),
+                diagnostics::take(),
+            )
+        });
+        match done {
+            Some((out, worker_cache, failure, issues)) => {
+                *cache = worker_cache;
+                assertion::reset();
+                if let Some(reason) = failure {
+                    assertion::record_reason(reason);
                 }
+                diagnostics::reset();
+                issues.into_iter().for_each(diagnostics::push);
+                out
+            }
+            None => {
+                warn_data!(
+                    "{} transform exceeded timeout_ms {}, pass record through unchanged",
+                    self.name(),
+                    timeout.as_millis()
+                );
+                data.clone()
             }
-
-            results.push(out);
         }
-
-        results
     }
+}
 
-    /// Optimized batch processing (reference version)
-    fn transform_batch_ref(
-        &self,
-        records: &[DataRecord],
-        cache: &mut FieldQueryCache,
-    ) -> Vec<DataRecord> {
-        // Pre-allocate result vector for better performance
-        let mut results = Vec::with_capacity(records.len());
-
-        // Process each record with shared cache
-        for record in records {
-            let mut out = DataRecord::default();
-            let mut tdo_ref = DataRecordRef::from(record);
-
-            // Reuse the same cache across all records (key optimization)
-            for ado in &self.items {
-                ado.eval_proc(&mut tdo_ref, &mut out, cache);
-            }
-
-            // Filter temporary fields if needed
-            if self.has_temp_fields() {
-                for field in &mut out.items {
-                    if field.get_name().starts_with("__") {
-                        *field = FieldStorage::from_owned(DataField::from_ignore(field.get_name()));
-                    }
-                }
-            }
+/// 转换前后的查询统计快照：记录级缓存与知识库线程缓存分开记录。
+///
+/// 每次查询只经过其中一层——开启知识库 `[cache]` 时记录级缓存被绕过，否则只用记录级缓存；
+/// 增量取发生变化的一层而不相加，线程缓存里其他模型留下的累计值也不会被计入。
+#[derive(Debug, Clone, Copy)]
+struct LookupSnapshot {
+    field: CacheStats,
+    kdb: CacheStats,
+}
 
-            results.push(out);
+impl LookupSnapshot {
+    fn take(cache: &FieldQueryCache) -> Self {
+        let kdb = kdb::query_cache_stats()
+            .map(|s| CacheStats {
+                hits: s.hits,
+                misses: s.misses,
+            })
+            .unwrap_or_default();
+        Self {
+            field: cache.stats(),
+            kdb,
         }
+    }
 
-        results
+    fn since(&self, earlier: &LookupSnapshot) -> CacheStats {
+        let kdb = self.kdb.since(earlier.kdb);
+        if kdb.lookups() > 0 {
+            kdb
+        } else {
+            self.field.since(earlier.field)
+        }
     }
 }
 
@@ -129,13 +248,149 @@ impl ConfADMExt for ObjModel {
             //.owe_rule::<OMLCodeError>()
             .owe(OMLCodeReason::NotFound("oml load fail".into()))
             .with(&ctx)?;
-        let mut raw_code = content.as_str();
-        let code = CommentParser::ignore_comment(&mut raw_code)
-            .map_err(|e| OMLCodeError::from_syntax(e, raw_code, path))?;
+        // 注释替换为空白而非删除，保证错误位置与原文件行列一致
+        let code = CommentParser::blank_comment(&content);
         let mut pure_code = code.as_str();
-        match oml_parse_raw(&mut pure_code) {
+        let cache_path = static_cache_path(std::path::Path::new(path));
+        match oml_parse_raw_cached(&mut pure_code, &cache_path) {
             Ok(res) => Ok(res),
-            Err(e) => Err(OMLCodeError::from_syntax(e, pure_code, path)).with(&ctx),
+            Err(e) => Err(OMLCodeError::from_syntax_at(e, &code, pure_code, path)).with(&ctx),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+
+    fn timed_model() -> ObjModel {
+        let mut code = r#"
+name : slow
+timeout_ms : 50
+---
+out = read(src);
+"#;
+        oml_parse_raw(&mut code).assert()
+    }
+
+    #[test]
+    fn slow_transform_passes_record_through() {
+        let model = timed_model();
+        let input = DataRecord::from(vec![DataField::from_chars("src", "v1")]);
+        let mut cache = FieldQueryCache::default();
+
+        let out = model.transform_ref(&input, &mut cache);
+        assert_eq!(
+            out.field("out").map(|f| f.get_value().to_string()),
+            Some("v1".to_string())
+        );
+
+        // 模拟慢求值（如阻塞的 SQL 查询）
+        let out = model.transform_within(
+            &input,
+            &mut cache,
+            Duration::from_millis(50),
+            |model, data, cache| {
+                std::thread::sleep(Duration::from_millis(500));
+                model.eval_record(data, cache)
+            },
+        );
+        assert!(out.field("out").is_none());
+        assert_eq!(
+            out.field("src").map(|f| f.get_value().to_string()),
+            Some("v1".to_string())
+        );
+    }
+
+    #[test]
+    fn debug_trace_lists_each_evaluated_field() {
+        let mut code = r#"
+name : traced
+debug : true
+---
+out = pipe read(src) | to_json ;
+tag = chars(fixed) ;
+"#;
+        let model = oml_parse_raw(&mut code).assert();
+        assert!(*model.debug());
+        let input = DataRecord::from(vec![DataField::from_chars("src", "v1")]);
+        let mut cache = FieldQueryCache::default();
+        let out = model.transform_ref(&input, &mut cache);
+
+        let lines: Vec<String> = model
+            .items
+            .iter()
+            .zip(out.items.chunks(1))
+            .flat_map(|(exp, produced)| model.trace_lines(exp, &input, produced))
+            .collect();
+        assert_eq!(lines.len(), 2, "{:?}", lines);
+        assert!(
+            lines[0].starts_with("[model:traced] field=out evaluator=Pipe input=src:v1 output="),
+            "{}",
+            lines[0]
+        );
+        assert_eq!(
+            lines[1],
+            "[model:traced] field=tag evaluator=Const input=- output=fixed"
+        );
+    }
+
+    #[test]
+    fn record_limits_truncate_or_drop_output() {
+        use wp_data_model::limits::{LimitPolicy, RecordLimits, TRUNCATED_FIELD};
+
+        let mut code = r#"
+name : limited
+---
+a = read(a) ;
+b = read(b) ;
+c = read(c) ;
+"#;
+        let model = oml_parse_raw(&mut code).assert();
+        let input = DataRecord::from(vec![
+            DataField::from_chars("a", "1"),
+            DataField::from_chars("b", "2"),
+            DataField::from_chars("c", "3"),
+        ]);
+        let mut cache = FieldQueryCache::default();
+        let limit = RecordLimits {
+            max_fields: 2,
+            ..Default::default()
+        };
+
+        let truncated = limits::scoped(limit, || model.try_transform_ref(&input, &mut cache));
+        let drop = RecordLimits {
+            policy: LimitPolicy::Drop,
+            ..limit
+        };
+        let dropped = limits::scoped(drop, || model.try_transform_ref(&input, &mut cache));
+
+        let out = truncated.assert();
+        let names: Vec<&str> = out.items.iter().map(|f| f.get_name()).collect();
+        assert_eq!(names, vec!["a", "b", TRUNCATED_FIELD]);
+        assert!(dropped.is_err());
+    }
+
+    #[test]
+    fn lookup_delta_counts_each_lookup_once() {
+        let stats = |hits, misses| CacheStats { hits, misses };
+        let before = LookupSnapshot {
+            field: stats(10, 5),
+            kdb: stats(40, 7),
+        };
+        // 知识库线程缓存开启：记录级缓存未变化，增量只来自线程缓存
+        let after = LookupSnapshot {
+            field: stats(10, 5),
+            kdb: stats(42, 8),
+        };
+        assert_eq!(after.since(&before), stats(2, 1));
+        // 未开启：只取记录级缓存的增量，线程缓存的历史累计不计入
+        let after = LookupSnapshot {
+            field: stats(13, 6),
+            kdb: stats(40, 7),
+        };
+        assert_eq!(after.since(&before), stats(3, 1));
+    }
+}
@@ -0,0 +1,193 @@
+//! 带超时的同步求值：任务交给调用线程专属的常驻工作线程执行，调用方限时等待。
+//!
+//! 同步求值无法被中断，超时的任务会继续运行至结束并丢弃结果；该工作线程留在池中，
+//! 任务结束后复用，期间新任务交给空闲或新建的工作线程。每个调用线程最多
+//! `MAX_WORKERS` 个工作线程，全部忙碌时直接按超时处理，卡死的任务不会无限累积线程。
+//! 工作线程常驻是为了复用其线程局部状态（如知识库的线程内存副本），不为每条记录重建。
+
+use std::cell::RefCell;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// 每个调用线程可同时持有的工作线程上限
+const MAX_WORKERS: usize = 4;
+
+struct Worker {
+    tx: Option<mpsc::Sender<Job>>,
+    busy: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Worker {
+    fn spawn() -> Option<Self> {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let name = format!(
+            "oml-timeout-{}",
+            std::thread::current().name().unwrap_or("worker")
+        );
+        let spawned = std::thread::Builder::new().name(name).spawn(move || {
+            for job in rx {
+                // 任务 panic 不终止工作线程，调用方收不到结果按超时处理
+                let _ = catch_unwind(AssertUnwindSafe(job));
+            }
+        });
+        match spawned {
+            Ok(handle) => Some(Self {
+                tx: Some(tx),
+                busy: Arc::new(AtomicBool::new(false)),
+                handle: Some(handle),
+            }),
+            Err(e) => {
+                warn_data!("spawn oml timeout worker failed: {}", e);
+                None
+            }
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.busy.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        // 关闭通道后工作线程在当前任务结束时退出；仍在运行的任务不等待，避免阻塞调用线程
+        self.tx.take();
+        if let Some(handle) = self.handle.take()
+            && self.is_idle()
+        {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// 任务结束（含 panic）时清除忙碌标记
+struct BusyGuard(Arc<AtomicBool>);
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+thread_local! {
+    static WORKERS: RefCell<Vec<Worker>> = const { RefCell::new(Vec::new()) };
+}
+
+/// 在工作线程中执行 `job`，`timeout` 内完成返回 `Some`，否则返回 `None`
+pub(crate) fn run_with_timeout<T, F>(timeout: Duration, job: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::sync_channel(1);
+    let sent = WORKERS.with(|workers| {
+        let mut workers = workers.borrow_mut();
+        let idx = match workers.iter().position(Worker::is_idle) {
+            Some(idx) => idx,
+            None if workers.len() < MAX_WORKERS => {
+                workers.push(Worker::spawn()?);
+                workers.len() - 1
+            }
+            None => {
+                warn_data!(
+                    "all {} oml timeout workers are busy, skip transform",
+                    MAX_WORKERS
+                );
+                return None;
+            }
+        };
+        let worker = &workers[idx];
+        worker.busy.store(true, Ordering::Release);
+        let guard = BusyGuard(Arc::clone(&worker.busy));
+        let job: Job = Box::new(move || {
+            let out = job();
+            // 先清除忙碌标记再回传结果，调用方拿到结果后即可复用该线程
+            drop(guard);
+            let _ = tx.send(out);
+        });
+        match worker.tx.as_ref()?.send(job) {
+            Ok(()) => Some(()),
+            Err(_) => {
+                // 工作线程已退出：任务随发送失败一起释放，忙碌标记随之清除
+                workers.remove(idx);
+                None
+            }
+        }
+    });
+    sent?;
+    rx.recv_timeout(timeout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn slow_job_times_out_and_worker_is_reused() {
+        let worker_of = || std::thread::current().id();
+        let first = run_with_timeout(Duration::from_secs(5), worker_of).unwrap();
+        assert_ne!(first, std::thread::current().id());
+        // 未超时的任务复用同一工作线程
+        assert_eq!(
+            run_with_timeout(Duration::from_secs(5), worker_of),
+            Some(first)
+        );
+
+        let slow = run_with_timeout(Duration::from_millis(20), || {
+            sleep(Duration::from_millis(300));
+            1
+        });
+        assert_eq!(slow, None);
+
+        // 慢任务仍在旧线程运行，新任务交给另一个工作线程，不必等待
+        let next = run_with_timeout(Duration::from_millis(200), worker_of).unwrap();
+        assert_ne!(next, first);
+
+        // 慢任务结束后原工作线程空闲，不再新建线程
+        sleep(Duration::from_millis(400));
+        assert_eq!(
+            run_with_timeout(Duration::from_secs(5), worker_of),
+            Some(first)
+        );
+        assert_eq!(WORKERS.with(|w| w.borrow().len()), 2);
+    }
+
+    #[test]
+    fn busy_workers_are_capped() {
+        for _ in 0..MAX_WORKERS {
+            let hung = run_with_timeout(Duration::from_millis(10), || {
+                sleep(Duration::from_millis(500));
+            });
+            assert_eq!(hung, None);
+        }
+        // 全部忙碌时不再新建线程，直接按超时处理
+        assert_eq!(run_with_timeout(Duration::from_secs(5), || 7), None);
+        assert_eq!(WORKERS.with(|w| w.borrow().len()), MAX_WORKERS);
+
+        sleep(Duration::from_millis(700));
+        assert_eq!(run_with_timeout(Duration::from_secs(5), || 7), Some(7));
+        assert_eq!(WORKERS.with(|w| w.borrow().len()), MAX_WORKERS);
+    }
+
+    #[test]
+    fn panicked_job_keeps_worker() {
+        let worker_of = || std::thread::current().id();
+        let first = run_with_timeout(Duration::from_secs(5), worker_of).unwrap();
+        assert_eq!(
+            run_with_timeout(Duration::from_millis(200), || -> u8 { panic!("boom") }),
+            None
+        );
+        assert_eq!(
+            run_with_timeout(Duration::from_secs(5), worker_of),
+            Some(first)
+        );
+    }
+}
@@ -3,37 +3,48 @@ pub use syntax::{
     NestedBinding,
     OmlKwGet,
     VarAccess,
-    accessors::{ArrOperation, FieldRead, FieldTake, FieldTakeBuilder, ReadOptionBuilder},
+    accessors::{
+        ArrOperation, FieldCapture, FieldRead, FieldTake, FieldTakeBuilder, ReadOptionBuilder,
+        SortOrder,
+    },
     accessors::{CondAccessor, DirectAccessor, GenericAccessor, NestedAccessor},
     accessors::{SqlFnArg, SqlFnExpr},
     bindings::GenericBinding,
     conditions::{ArgsTakeAble, CompareExpress, LogicalExpression},
     evaluators::{
-        BatchEvalExp, BatchEvalExpBuilder, BatchEvaluation, EvalExp, PreciseEvaluator,
-        SingleEvalExp, SingleEvalExpBuilder,
+        AssertCond, AssertExpr, BatchEvalExp, BatchEvalExpBuilder, BatchEvaluation, EvalExp,
+        PreciseEvaluator, SingleEvalExp, SingleEvalExpBuilder,
     },
     functions::{
-        Base64Decode, Base64Encode, BuiltinFunction, Dumb, EncodeType, ExtractMainWord,
-        ExtractSubjectObject, FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, FunOperation, Get,
-        HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape, JsonUnescape, MapTo, MapValue, NowDate,
-        NowHour, NowTime, Nth, PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE, PIPE_EXTRACT_MAIN_WORD,
-        PIPE_EXTRACT_SUBJECT_OBJECT, PIPE_GET, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE,
-        PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE, PIPE_JSON_UNESCAPE, PIPE_MAP_TO, PIPE_NTH, PIPE_PATH,
-        PIPE_SKIP_EMPTY, PIPE_STARTS_WITH, PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS,
-        PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_JSON, PIPE_TO_STR, PIPE_URL, PathGet,
-        PathType, PipeFun, SkipEmpty, StartsWith, StrEscape, TimeStampUnit, TimeToTs, TimeToTsMs,
-        TimeToTsUs, TimeToTsZone, ToJson, ToStr, UrlGet, UrlType,
+        Ascii85Decode, Ascii85Encode, Base64Decode, Base64Encode, BuiltinFunction,
+        CountOccurrences, Dumb, EncodeType, ExtractMainWord, ExtractSubjectObject, FUN_HOSTNAME,
+        FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, FUN_PROCESS_ID, FunOperation, Get, Hostname,
+        HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape, JsonPath, JsonPathExpr, JsonUnescape,
+        KeyTransform, Length, MapKeys, MapTo, MapValue, MapValues, NowDate, NowHour, NowTime, Nth,
+        PIPE_ASCII85_DECODE, PIPE_ASCII85_ENCODE, PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE,
+        PIPE_COUNT_OCCURRENCES, PIPE_EXTRACT_MAIN_WORD, PIPE_EXTRACT_SUBJECT_OBJECT, PIPE_GET,
+        PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE, PIPE_JSON_PATH,
+        PIPE_JSON_UNESCAPE, PIPE_LENGTH, PIPE_MAP_KEYS, PIPE_MAP_TO, PIPE_MAP_VALUES, PIPE_NTH,
+        PIPE_PATH, PIPE_PATH_EXT, PIPE_PATH_PARENT, PIPE_PATH_STEM, PIPE_SKIP_EMPTY, PIPE_SLICE,
+        PIPE_SLICE_LAST, PIPE_STARTS_WITH, PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS,
+        PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_BOOL, PIPE_TO_JSON, PIPE_TO_STR,
+        PIPE_UNIQUE, PIPE_URL, PathGet, PathType, PipeFun, ProcessId, SkipEmpty, Slice, StartsWith,
+        StrEscape, TimeStampUnit, TimeToTs, TimeToTsMs, TimeToTsUs, TimeToTsZone, ToBoolean,
+        ToJson, ToStr, URL_QUERY_ALL, Unique, UrlGet, UrlType,
     },
     //lib_prm::LookupQuery,
     operations::{
-        FmtOperation, MapOperation, MatchAble, MatchCase, MatchCond, MatchCondition, MatchFun,
-        MatchOperation, MatchSource, PiPeOperation, RecordOperation, RecordOperationBuilder,
-        SqlQuery,
+        AGG_COLLECT_DISTINCT, AGG_COUNT, AGG_MAX, AGG_MIN, AGG_SUM, AggregateExpr, AggregateItem,
+        AggregateOp, CompiledRegex, FmtOperation, MapOperation, MatchAble, MatchCase, MatchCond,
+        MatchCondition, MatchFun, MatchOperation, MatchSource, PiPeOperation, RecordOperation,
+        RecordOperationBuilder, SqlQuery,
     },
 };
 pub use types::model::DataModel;
+pub use types::model::LookupCounter;
 pub use types::model::ObjModel;
 pub use types::model::StubModel;
+pub use types::refs::FieldRef;
 pub use types::target::{BatchEvalTarget, EvaluationTarget, EvaluationTargetBuilder};
 mod syntax;
 mod types;
@@ -0,0 +1,31 @@
+use crate::language::prelude::*;
+
+/// `read_capture("name")`：读取所在 `match` 分支条件中 `regex_match` 的命名捕获
+#[derive(Default, Debug, Clone, PartialEq, Getters)]
+pub struct FieldCapture {
+    name: Option<String>,
+}
+
+impl FieldCapture {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+        }
+    }
+
+    pub fn capture_name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+}
+
+impl VarAccess for FieldCapture {
+    fn field_name(&self) -> &Option<String> {
+        &self.name
+    }
+}
+
+impl Display for FieldCapture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "read_capture(\"{}\")", self.capture_name())
+    }
+}
@@ -1,6 +1,8 @@
+pub mod capture;
 pub mod read;
 pub mod take;
 
+pub use capture::FieldCapture;
 pub use read::FieldRead;
 pub use read::ReadOptionBuilder;
 pub use take::FieldTake;
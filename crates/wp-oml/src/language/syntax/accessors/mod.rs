@@ -12,9 +12,10 @@ use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
 use super::functions::FunOperation;
+use super::operations::pipe::PiPeOperation;
 use super::operations::record::RecordOperation;
 pub use direct::*;
-pub use nested::arr::ArrOperation;
+pub use nested::arr::{ArrOperation, SortOrder};
 #[derive(Debug, Clone, PartialEq)]
 pub enum NestedAccessor {
     Field(DataField),
@@ -23,6 +24,8 @@ pub enum NestedAccessor {
     Direct(RecordOperation),
     Fun(FunOperation),
     Collect(ArrOperation),
+    /// `read(..) | fun | ...`（可省略 `pipe` 关键字）
+    Pipe(PiPeOperation),
     /// Placeholder for static symbol; resolved after parsing
     StaticSymbol(String),
 }
@@ -57,6 +60,7 @@ impl FieldExtractor for NestedAccessor {
             NestedAccessor::Direct(o) => o.extract_one(target, src, dst),
             NestedAccessor::Fun(o) => o.extract_one(target, src, dst),
             NestedAccessor::Collect(o) => o.extract_one(target, src, dst),
+            NestedAccessor::Pipe(o) => o.extract_one(target, src, dst),
             NestedAccessor::StaticSymbol(sym) => {
                 panic!("unresolved static symbol during execution: {sym}")
             }
@@ -74,6 +78,7 @@ impl FieldExtractor for NestedAccessor {
             NestedAccessor::Direct(o) => o.extract_more(src, dst, cache),
             NestedAccessor::Fun(o) => o.extract_more(src, dst, cache),
             NestedAccessor::Collect(o) => o.extract_more(src, dst, cache),
+            NestedAccessor::Pipe(o) => o.extract_more(src, dst, cache),
             NestedAccessor::StaticSymbol(sym) => {
                 panic!("unresolved static symbol during execution: {sym}")
             }
@@ -86,6 +91,7 @@ impl FieldExtractor for NestedAccessor {
             NestedAccessor::Direct(o) => o.support_batch(),
             NestedAccessor::Fun(o) => o.support_batch(),
             NestedAccessor::Collect(o) => o.support_batch(),
+            NestedAccessor::Pipe(o) => o.support_batch(),
             NestedAccessor::StaticSymbol(sym) => {
                 panic!("unresolved static symbol during execution: {sym}")
             }
@@ -111,6 +117,9 @@ impl Display for NestedAccessor {
             NestedAccessor::Fun(x) => {
                 write!(f, "{}", x)
             }
+            NestedAccessor::Pipe(x) => {
+                write!(f, "{}", x)
+            }
             NestedAccessor::StaticSymbol(sym) => {
                 write!(f, "{}", sym)
             }
@@ -140,6 +149,8 @@ impl NestedAccessor {
 pub enum DirectAccessor {
     Take(FieldTake),
     Read(FieldRead),
+    /// `read_capture("name")`：match 分支的命名捕获
+    Capture(FieldCapture),
 }
 
 impl VarAccess for DirectAccessor {
@@ -147,6 +158,7 @@ impl VarAccess for DirectAccessor {
         match self {
             DirectAccessor::Take(o) => o.field_name(),
             DirectAccessor::Read(o) => o.field_name(),
+            DirectAccessor::Capture(o) => o.field_name(),
         }
     }
 }
@@ -160,6 +172,9 @@ impl Display for DirectAccessor {
             DirectAccessor::Read(o) => {
                 write!(f, "{}", o)
             }
+            DirectAccessor::Capture(o) => {
+                write!(f, "{}", o)
+            }
         }
     }
 }
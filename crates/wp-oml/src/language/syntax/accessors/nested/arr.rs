@@ -1,19 +1,77 @@
+use crate::language::PipeFun;
 use crate::language::prelude::*;
 use std::fmt::{Display, Formatter};
 
-#[derive(Builder, Debug, Clone, Getters, PartialEq)]
+/// `order_by(field, asc|desc)` 的排序方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortOrder::Asc => write!(f, "asc"),
+            SortOrder::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+#[derive(Builder, Debug, Clone, Getters)]
 pub struct ArrOperation {
     pub dat_crate: DirectAccessor,
+    /// 收集后按字段排序：`collect take(...) order_by(port, desc)`
+    #[builder(default)]
+    pub order_by: Option<(String, SortOrder)>,
+    /// 收集结果依次经过的管道函数：`collect take(...) | unique`
+    #[builder(default)]
+    pub pipes: Vec<PipeFun>,
+}
+
+/// 管道函数未实现 `PartialEq`，按源码形式比较
+impl PartialEq for ArrOperation {
+    fn eq(&self, other: &Self) -> bool {
+        self.dat_crate == other.dat_crate
+            && self.order_by == other.order_by
+            && self.pipes.len() == other.pipes.len()
+            && self
+                .pipes
+                .iter()
+                .zip(&other.pipes)
+                .all(|(a, b)| a.to_string() == b.to_string())
+    }
 }
 
 impl Display for ArrOperation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, " collect {}", self.dat_crate)?;
-        Ok(())
+        write!(f, " collect {}", self.dat_crate)?;
+        if let Some((field, order)) = &self.order_by {
+            write!(f, " order_by({}, {})", field, order)?;
+        }
+        for p in &self.pipes {
+            write!(f, " | {}", p)?;
+        }
+        writeln!(f)
     }
 }
 impl ArrOperation {
     pub fn new(dat_crate: DirectAccessor) -> Self {
-        Self { dat_crate }
+        Self {
+            dat_crate,
+            order_by: None,
+            pipes: Vec::new(),
+        }
+    }
+
+    pub fn with_order_by(mut self, order_by: Option<(String, SortOrder)>) -> Self {
+        self.order_by = order_by;
+        self
+    }
+
+    pub fn with_pipes(mut self, pipes: Vec<PipeFun>) -> Self {
+        self.pipes = pipes;
+        self
     }
 }
@@ -0,0 +1,71 @@
+use crate::language::prelude::*;
+use orion_exp::CmpOperator;
+
+use super::PreciseEvaluator;
+
+/// 断言条件：`<left> <op> <right>`，两侧均为普通取值表达式
+#[derive(Debug, Clone, Getters)]
+pub struct AssertCond {
+    left: PreciseEvaluator,
+    op: CmpOperator,
+    right: PreciseEvaluator,
+}
+
+impl AssertCond {
+    pub fn new(left: PreciseEvaluator, op: CmpOperator, right: PreciseEvaluator) -> Self {
+        Self { left, op, right }
+    }
+
+    pub fn left_mut(&mut self) -> &mut PreciseEvaluator {
+        &mut self.left
+    }
+
+    pub fn right_mut(&mut self) -> &mut PreciseEvaluator {
+        &mut self.right
+    }
+}
+
+fn cmp_symbol(op: &CmpOperator) -> &'static str {
+    match op {
+        CmpOperator::Eq => "==",
+        CmpOperator::Ne => "!=",
+        CmpOperator::Gt => ">",
+        CmpOperator::Ge => ">=",
+        CmpOperator::Lt => "<",
+        CmpOperator::Le => "<=",
+        CmpOperator::We => "=*",
+    }
+}
+
+impl Display for AssertCond {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.left, cmp_symbol(&self.op), self.right)
+    }
+}
+
+/// `assert <cond> [, "message"] ;` —— 开发期不变量检查，不产生输出字段
+#[derive(Debug, Clone, Getters)]
+pub struct AssertExpr {
+    cond: AssertCond,
+    message: Option<String>,
+}
+
+impl AssertExpr {
+    pub fn new(cond: AssertCond, message: Option<String>) -> Self {
+        Self { cond, message }
+    }
+
+    pub fn cond_mut(&mut self) -> &mut AssertCond {
+        &mut self.cond
+    }
+}
+
+impl Display for AssertExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "assert {}", self.cond)?;
+        if let Some(msg) = &self.message {
+            write!(f, ", \"{}\"", msg)?;
+        }
+        write!(f, " ;  ")
+    }
+}
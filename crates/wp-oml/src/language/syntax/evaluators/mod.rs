@@ -1,8 +1,10 @@
 use std::fmt::{Display, Formatter};
 
+pub use assert::{AssertCond, AssertExpr};
 pub use pattern::{BatchEvalExp, BatchEvalExpBuilder, BatchEvaluation};
 pub use precise::{PreciseEvaluator, SingleEvalExp, SingleEvalExpBuilder};
 
+pub mod assert;
 pub mod pattern;
 pub mod precise;
 #[allow(clippy::large_enum_variant)]
@@ -10,12 +12,14 @@ pub mod precise;
 pub enum EvalExp {
     Single(SingleEvalExp),
     Batch(BatchEvalExp),
+    Assert(AssertExpr),
 }
 impl Display for EvalExp {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             EvalExp::Single(x) => Display::fmt(x, f),
             EvalExp::Batch(x) => Display::fmt(x, f),
+            EvalExp::Assert(x) => Display::fmt(x, f),
         }
     }
 }
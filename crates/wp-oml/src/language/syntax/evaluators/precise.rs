@@ -54,6 +54,15 @@ pub enum PreciseEvaluator {
     Fmt(FmtOperation),
     Collect(ArrOperation),
     Val(Value),
+    /// `coalesce(a, b, ...)`：依次求值，返回首个非缺失、非空字符串的结果
+    Coalesce(Vec<PreciseEvaluator>),
+    /// `env("NAME", "default")`：模型加载时读取环境变量并缓存，各记录复用同一取值；
+    /// 未设置且无默认值时 `value` 为空，目标字段不输出
+    Env {
+        var_name: String,
+        default: Option<String>,
+        value: Option<String>,
+    },
     /// Placeholder for static DSL symbol; resolved after parsing
     StaticSymbol(String),
 }
@@ -63,6 +72,52 @@ impl Default for PreciseEvaluator {
         PreciseEvaluator::Tdc(RecordOperation::default())
     }
 }
+impl PreciseEvaluator {
+    /// 读取环境变量构造 `env(...)` 求值器；取值只在此处读取一次
+    pub fn env(var_name: String, default: Option<String>) -> Self {
+        let value = std::env::var(&var_name).ok().or_else(|| default.clone());
+        PreciseEvaluator::Env {
+            var_name,
+            default,
+            value,
+        }
+    }
+
+    /// 批量输出的字段自带名称（展平的 `extract_subject_object`），单目标时不按目标改名
+    pub fn keeps_field_names(&self) -> bool {
+        matches!(self, PreciseEvaluator::Pipe(p) if p.flat_tail().is_some())
+    }
+
+    /// 求值器类型名，用于 `debug: true` 的求值轨迹
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            PreciseEvaluator::Sql(_) => "Sql",
+            PreciseEvaluator::Match(_) => "Match",
+            PreciseEvaluator::Obj(_) | PreciseEvaluator::ObjArc(_) | PreciseEvaluator::Val(_) => {
+                "Const"
+            }
+            PreciseEvaluator::Tdc(_) => "Read",
+            PreciseEvaluator::Map(_) => "Map",
+            PreciseEvaluator::Pipe(_) => "Pipe",
+            PreciseEvaluator::Fun(_) => "Fun",
+            PreciseEvaluator::Fmt(_) => "Fmt",
+            PreciseEvaluator::Collect(_) => "Collect",
+            PreciseEvaluator::Coalesce(_) => "Coalesce",
+            PreciseEvaluator::Env { .. } => "Env",
+            PreciseEvaluator::StaticSymbol(_) => "Static",
+        }
+    }
+
+    /// 直接读取的源字段名（`read`/`take` 及管道的起点），其余求值器返回 None
+    pub fn source_field(&self) -> Option<&str> {
+        match self {
+            PreciseEvaluator::Tdc(op) => op.dat_get.field_name().as_deref(),
+            PreciseEvaluator::Pipe(op) => op.from().field_name().as_deref(),
+            _ => None,
+        }
+    }
+}
+
 impl Display for PreciseEvaluator {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -78,6 +133,22 @@ impl Display for PreciseEvaluator {
             PreciseEvaluator::Fmt(x) => Display::fmt(x, f),
             PreciseEvaluator::Collect(x) => Display::fmt(x, f),
             PreciseEvaluator::Val(x) => Display::fmt(x, f),
+            PreciseEvaluator::Coalesce(args) => {
+                write!(f, "coalesce(")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg.to_string().trim())?;
+                }
+                write!(f, ")")
+            }
+            PreciseEvaluator::Env {
+                var_name, default, ..
+            } => match default {
+                Some(default) => write!(f, "env(\"{}\", \"{}\")", var_name, default),
+                None => write!(f, "env(\"{}\")", var_name),
+            },
             PreciseEvaluator::StaticSymbol(sym) => {
                 write!(f, "{}", sym)
             }
@@ -1,4 +1,5 @@
 pub mod pipe;
+pub mod sys;
 pub mod time;
 use std::fmt::{Display, Formatter};
 
@@ -11,6 +12,10 @@ pub enum BuiltinFunction {
     NowDate(NowDate),
     #[strum(to_string = "Now::hour")]
     NowHour(NowHour),
+    #[strum(to_string = "Hostname")]
+    Hostname(Hostname),
+    #[strum(to_string = "ProcessId")]
+    ProcessId(ProcessId),
 }
 
 #[derive(Debug, Clone, Getters, Serialize, Deserialize, PartialEq)]
@@ -29,14 +34,19 @@ impl Display for FunOperation {
 }
 
 pub use pipe::{
-    Base64Decode, Base64Encode, Dumb, EncodeType, ExtractMainWord, ExtractSubjectObject, Get,
-    HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape, JsonUnescape, MapTo, MapValue, Nth,
-    PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE, PIPE_EXTRACT_MAIN_WORD, PIPE_EXTRACT_SUBJECT_OBJECT,
-    PIPE_GET, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE,
-    PIPE_JSON_UNESCAPE, PIPE_MAP_TO, PIPE_NTH, PIPE_PATH, PIPE_SKIP_EMPTY, PIPE_STARTS_WITH,
-    PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS, PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE,
-    PIPE_TO_JSON, PIPE_TO_STR, PIPE_URL, PathGet, PathType, PipeFun, SkipEmpty, StartsWith,
-    StrEscape, TimeStampUnit, TimeToTs, TimeToTsMs, TimeToTsUs, TimeToTsZone, ToJson, ToStr,
-    UrlGet, UrlType,
+    Ascii85Decode, Ascii85Encode, Base64Decode, Base64Encode, CountOccurrences, Dumb, EncodeType,
+    ExtractMainWord, ExtractSubjectObject, Get, HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape,
+    JsonPath, JsonPathExpr, JsonUnescape, KeyTransform, Length, MapKeys, MapTo, MapValue,
+    MapValues, Nth, PIPE_ASCII85_DECODE, PIPE_ASCII85_ENCODE, PIPE_BASE64_DECODE,
+    PIPE_BASE64_ENCODE, PIPE_COUNT_OCCURRENCES, PIPE_EXTRACT_MAIN_WORD,
+    PIPE_EXTRACT_SUBJECT_OBJECT, PIPE_GET, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT,
+    PIPE_JSON_ESCAPE, PIPE_JSON_PATH, PIPE_JSON_UNESCAPE, PIPE_LENGTH, PIPE_MAP_KEYS, PIPE_MAP_TO,
+    PIPE_MAP_VALUES, PIPE_NTH, PIPE_PATH, PIPE_PATH_EXT, PIPE_PATH_PARENT, PIPE_PATH_STEM,
+    PIPE_SKIP_EMPTY, PIPE_SLICE, PIPE_SLICE_LAST, PIPE_STARTS_WITH, PIPE_STR_ESCAPE,
+    PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS, PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_BOOL,
+    PIPE_TO_JSON, PIPE_TO_STR, PIPE_UNIQUE, PIPE_URL, PathGet, PathType, PipeFun, SkipEmpty, Slice,
+    StartsWith, StrEscape, TimeStampUnit, TimeToTs, TimeToTsMs, TimeToTsUs, TimeToTsZone,
+    ToBoolean, ToJson, ToStr, URL_QUERY_ALL, Unique, UrlGet, UrlType,
 };
+pub use sys::*;
 pub use time::*;
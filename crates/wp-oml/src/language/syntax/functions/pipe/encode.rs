@@ -5,6 +5,14 @@ pub const PIPE_BASE64_ENCODE: &str = "base64_encode";
 #[derive(Default, Builder, Debug, Clone, Getters, Serialize, Deserialize)]
 pub struct Base64Encode {}
 
+pub const PIPE_ASCII85_ENCODE: &str = "ascii85_encode";
+#[derive(Default, Builder, Debug, Clone, Getters, Serialize, Deserialize)]
+pub struct Ascii85Encode {}
+
+pub const PIPE_ASCII85_DECODE: &str = "ascii85_decode";
+#[derive(Default, Builder, Debug, Clone, Getters, Serialize, Deserialize)]
+pub struct Ascii85Decode {}
+
 pub const PIPE_BASE64_DECODE: &str = "base64_decode";
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Base64Decode {
@@ -2,6 +2,11 @@ use crate::language::prelude::*;
 pub const PIPE_TO_JSON: &str = "to_json";
 #[derive(Default, Builder, Debug, Clone, Getters, Serialize, Deserialize)]
 pub struct ToJson {}
+pub const PIPE_TO_BOOL: &str = "to_bool";
+/// 布尔化：`true/yes/1/on` 与 `false/no/0/off`（不区分大小写）转为 bool，
+/// 数字按是否为 0 转换，其余值原样透传
+#[derive(Clone, Debug, Default)]
+pub struct ToBoolean {}
 pub const PIPE_JSON_ESCAPE: &str = "json_escape";
 #[derive(Clone, Debug, Default)]
 pub struct JsonEscape {}
@@ -9,3 +14,31 @@ pub struct JsonEscape {}
 pub const PIPE_JSON_UNESCAPE: &str = "json_unescape";
 #[derive(Clone, Debug, Default)]
 pub struct JsonUnescape {}
+
+/// 预编译的 JSONPath 表达式
+pub type JsonPathExpr = jsonpath_rust::JsonPath;
+
+pub const PIPE_JSON_PATH: &str = "json_path";
+/// JSONPath 提取：表达式在模型加载时编译；多个命中返回 array，单个标量命中返回 chars，
+/// 无命中或输入不是合法 JSON 时返回空 chars
+#[derive(Clone, Debug)]
+pub struct JsonPath {
+    pub(crate) path: String,
+    pub(crate) compiled: JsonPathExpr,
+}
+
+impl JsonPath {
+    pub fn compile(path: &str) -> Result<Self, String> {
+        let compiled = path.parse::<JsonPathExpr>().map_err(|e| e.to_string())?;
+        Ok(Self {
+            path: path.to_string(),
+            compiled,
+        })
+    }
+}
+
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}('{}')", PIPE_JSON_PATH, self.path)
+    }
+}
@@ -1,12 +1,12 @@
 use crate::language::prelude::*;
 
-pub mod base64;
+pub mod encode;
 pub mod escape;
 pub mod fmt;
 pub mod net;
 pub mod other;
 pub mod time;
-pub use base64::*;
+pub use encode::*;
 pub use escape::*;
 pub use fmt::*;
 pub use net::*;
@@ -18,22 +18,32 @@ pub use time::*;
 pub enum PipeFun {
     Base64Encode(Base64Encode),
     Base64Decode(Base64Decode),
+    Ascii85Encode(Ascii85Encode),
+    Ascii85Decode(Ascii85Decode),
     HtmlEscape(HtmlEscape),
     HtmlUnescape(HtmlUnescape),
     StrEscape(StrEscape),
     JsonEscape(JsonEscape),
     JsonUnescape(JsonUnescape),
+    JsonPath(JsonPath),
     TimeToTs(TimeToTs),
     TimeToTsMs(TimeToTsMs),
     TimeToTsUs(TimeToTsUs),
     TimeToTsZone(TimeToTsZone),
     Nth(Nth),
+    Slice(Slice),
     Get(Get),
     StartsWith(StartsWith),
+    CountOccurrences(CountOccurrences),
     MapTo(MapTo),
+    MapKeys(MapKeys),
+    MapValues(MapValues),
     ToStr(ToStr),
     ToJson(ToJson),
+    ToBoolean(ToBoolean),
     SkipEmpty(SkipEmpty),
+    Length(Length),
+    Unique(Unique),
     Dumb(Dumb),
     PathGet(PathGet),
     UrlGet(UrlGet),
@@ -47,22 +57,32 @@ impl Display for PipeFun {
         match self {
             PipeFun::Base64Encode(_) => write!(f, "{}", PIPE_BASE64_ENCODE),
             PipeFun::Base64Decode(v) => write!(f, "{}", v),
+            PipeFun::Ascii85Encode(_) => write!(f, "{}", PIPE_ASCII85_ENCODE),
+            PipeFun::Ascii85Decode(_) => write!(f, "{}", PIPE_ASCII85_DECODE),
             PipeFun::HtmlEscape(_) => write!(f, "{}", PIPE_HTML_ESCAPE),
             PipeFun::StrEscape(_) => write!(f, "{}", PIPE_STR_ESCAPE),
             PipeFun::JsonEscape(_) => write!(f, "{}", PIPE_JSON_ESCAPE),
             PipeFun::JsonUnescape(_) => write!(f, "{}", PIPE_JSON_UNESCAPE),
+            PipeFun::JsonPath(v) => write!(f, "{}", v),
             PipeFun::HtmlUnescape(_) => write!(f, "{}", PIPE_HTML_UNESCAPE),
             PipeFun::TimeToTs(_) => write!(f, "{}", PIPE_TIME_TO_TS),
             PipeFun::TimeToTsMs(_) => write!(f, "{}", PIPE_TIME_TO_TS_MS),
             PipeFun::TimeToTsUs(_) => write!(f, "{}", PIPE_TIME_TO_TS_US),
             PipeFun::TimeToTsZone(v) => write!(f, "{}", v),
             PipeFun::Nth(v) => write!(f, "{}", v),
+            PipeFun::Slice(v) => write!(f, "{}", v),
             PipeFun::Get(v) => write!(f, "{}", v),
             PipeFun::StartsWith(v) => write!(f, "{}", v),
+            PipeFun::CountOccurrences(v) => write!(f, "{}", v),
             PipeFun::MapTo(v) => write!(f, "{}", v),
+            PipeFun::MapKeys(v) => write!(f, "{}", v),
+            PipeFun::MapValues(v) => write!(f, "{}", v),
             PipeFun::ToJson(_) => write!(f, "{}", PIPE_TO_JSON),
+            PipeFun::ToBoolean(_) => write!(f, "{}", PIPE_TO_BOOL),
             PipeFun::ToStr(_) => write!(f, "{}", PIPE_TO_STR),
             PipeFun::SkipEmpty(_) => write!(f, "{}", PIPE_SKIP_EMPTY),
+            PipeFun::Length(_) => write!(f, "{}", PIPE_LENGTH),
+            PipeFun::Unique(_) => write!(f, "{}", PIPE_UNIQUE),
             PipeFun::Dumb(_) => write!(f, "{}", PIPE_TO_STR),
             PipeFun::PathGet(v) => write!(f, "{}", v),
             PipeFun::UrlGet(v) => write!(f, "{}", v),
@@ -1,7 +1,10 @@
 use crate::core::ValueProcessor;
 use crate::language::prelude::*;
+use smol_str::SmolStr;
 use strum_macros::EnumString;
 
+use super::PipeFun;
+
 use wp_parser::fun::fun_trait::Fun1Builder;
 
 pub const PIPE_TO_STR: &str = "to_str";
@@ -19,10 +22,155 @@ impl Display for Nth {
     }
 }
 
+pub const PIPE_SLICE: &str = "slice";
+pub const PIPE_SLICE_LAST: &str = "slice_last";
+/// 数组切片 `[start, end)`：负索引从末尾计数，越界按数组边界截断；非数组原样返回。
+/// `slice_last(n)` 等价于 `slice(-n)`
+#[derive(Clone, Debug, Default)]
+pub struct Slice {
+    pub(crate) start: isize,
+    pub(crate) end: Option<isize>,
+}
+
+impl Slice {
+    /// 按数组长度换算为有效区间；end 在 start 之前时为空区间
+    pub(crate) fn bounds(&self, len: usize) -> (usize, usize) {
+        let resolve = |i: isize| {
+            if i < 0 {
+                len.saturating_sub(i.unsigned_abs())
+            } else {
+                (i as usize).min(len)
+            }
+        };
+        let start = resolve(self.start);
+        let end = self.end.map_or(len, resolve);
+        (start, end.max(start))
+    }
+}
+
+impl Display for Slice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}({}, {})", PIPE_SLICE, self.start, end),
+            None => write!(f, "{}({})", PIPE_SLICE, self.start),
+        }
+    }
+}
+
 pub const PIPE_SKIP_EMPTY: &str = "skip_empty";
 #[derive(Clone, Debug, Default)]
 pub struct SkipEmpty {}
 
+pub const PIPE_LENGTH: &str = "length";
+/// 长度：chars 为字符数，array 为元素个数，其他类型为 0
+#[derive(Clone, Debug, Default)]
+pub struct Length {}
+
+pub const PIPE_UNIQUE: &str = "unique";
+/// 数组去重：按元素值的字符串形式保留首次出现的元素，顺序不变；非数组原样返回
+#[derive(Clone, Debug, Default)]
+pub struct Unique {}
+
+pub const PIPE_MAP_KEYS: &str = "map_keys";
+/// 对象键名转换方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, strum_macros::Display)]
+pub enum KeyTransform {
+    #[default]
+    #[strum(serialize = "camel_case")]
+    CamelCase,
+    #[strum(serialize = "snake_case")]
+    SnakeCase,
+    #[strum(serialize = "uppercase")]
+    Uppercase,
+    #[strum(serialize = "lowercase")]
+    Lowercase,
+}
+
+impl KeyTransform {
+    pub fn apply(&self, key: &str) -> String {
+        match self {
+            KeyTransform::CamelCase => {
+                let mut out = String::with_capacity(key.len());
+                for (i, word) in split_words(key).iter().enumerate() {
+                    let lower = word.to_lowercase();
+                    if i == 0 {
+                        out.push_str(&lower);
+                    } else {
+                        let mut chars = lower.chars();
+                        if let Some(first) = chars.next() {
+                            out.extend(first.to_uppercase());
+                            out.push_str(chars.as_str());
+                        }
+                    }
+                }
+                out
+            }
+            KeyTransform::SnakeCase => split_words(key)
+                .iter()
+                .map(|w| w.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            KeyTransform::Uppercase => key.to_uppercase(),
+            KeyTransform::Lowercase => key.to_lowercase(),
+        }
+    }
+}
+
+/// 按分隔符（非字母数字）与大小写边界切词：`srcIP` → `src`,`IP`；`HTTPStatus` → `HTTP`,`Status`
+fn split_words(key: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let chars: Vec<(usize, char)> = key.char_indices().collect();
+    let mut start: Option<usize> = None;
+    for (i, &(pos, c)) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if let Some(s) = start.take() {
+                words.push(&key[s..pos]);
+            }
+            continue;
+        }
+        if let Some(s) = start
+            && c.is_uppercase()
+        {
+            let prev = chars[i - 1].1;
+            let next_lower = chars.get(i + 1).is_some_and(|(_, n)| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                words.push(&key[s..pos]);
+                start = Some(pos);
+            }
+        }
+        start.get_or_insert(pos);
+    }
+    if let Some(s) = start {
+        words.push(&key[s..]);
+    }
+    words
+}
+
+/// 对象键名转换：仅作用于顶层键，重名时后者覆盖；非对象原样返回
+#[derive(Clone, Debug, Default)]
+pub struct MapKeys {
+    pub(crate) transform: KeyTransform,
+}
+
+impl Display for MapKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_MAP_KEYS, self.transform)
+    }
+}
+
+pub const PIPE_MAP_VALUES: &str = "map_values";
+/// 对象值转换：对每个顶层值执行内层管道函数；非对象原样返回
+#[derive(Clone, Debug)]
+pub struct MapValues {
+    pub(crate) inner: Box<PipeFun>,
+}
+
+impl Display for MapValues {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_MAP_VALUES, self.inner)
+    }
+}
+
 pub const PIPE_GET: &str = "get";
 #[derive(Clone, Debug, Default)]
 pub struct Get {
@@ -48,6 +196,19 @@ impl Display for StartsWith {
     }
 }
 
+pub const PIPE_COUNT_OCCURRENCES: &str = "count_occurrences";
+/// 子串出现次数（不重叠）：chars 之外的类型及空模式均为 0
+#[derive(Clone, Debug)]
+pub struct CountOccurrences {
+    pub(crate) pattern: SmolStr,
+}
+
+impl Display for CountOccurrences {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}('{}')", PIPE_COUNT_OCCURRENCES, self.pattern)
+    }
+}
+
 pub const PIPE_MAP_TO: &str = "map_to";
 #[derive(Clone, Debug)]
 pub enum MapValue {
@@ -88,8 +249,20 @@ pub enum PathType {
     FileName,
     #[strum(serialize = "path")]
     Path,
+    /// 上级目录：`/var/log/app.log` → `/var/log`
+    #[strum(serialize = "parent")]
+    Parent,
+    /// 扩展名（不含点）：`app.log` → `log`
+    #[strum(serialize = "ext")]
+    Extension,
+    /// 不含扩展名的文件名：`app.log` → `app`
+    #[strum(serialize = "stem")]
+    Stem,
 }
 pub const PIPE_PATH: &str = "path";
+pub const PIPE_PATH_PARENT: &str = "parent";
+pub const PIPE_PATH_EXT: &str = "ext";
+pub const PIPE_PATH_STEM: &str = "stem";
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct PathGet {
     pub key: PathType,
@@ -101,7 +274,7 @@ impl Display for PathGet {
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, EnumString, strum_macros::Display)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, EnumString)]
 pub enum UrlType {
     #[default]
     Default,
@@ -120,8 +293,27 @@ pub enum UrlType {
     /// 获取 HTTP 请求查询参数
     #[strum(serialize = "params")]
     HttpReqParams,
+    /// 获取同名查询参数的全部取值（数组），写作 `query_all:key`
+    #[strum(disabled)]
+    QueryAll(SmolStr),
 }
 
+impl Display for UrlType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UrlType::Default => write!(f, "Default"),
+            UrlType::Domain => write!(f, "domain"),
+            UrlType::HttpReqHost => write!(f, "host"),
+            UrlType::HttpReqUri => write!(f, "uri"),
+            UrlType::HttpReqPath => write!(f, "path"),
+            UrlType::HttpReqParams => write!(f, "params"),
+            UrlType::QueryAll(key) => write!(f, "{}:{}", URL_QUERY_ALL, key),
+        }
+    }
+}
+
+pub const URL_QUERY_ALL: &str = "query_all";
+
 pub const PIPE_URL: &str = "url";
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct UrlGet {
@@ -147,21 +339,68 @@ impl Display for ExtractMainWord {
 
 pub const PIPE_EXTRACT_SUBJECT_OBJECT: &str = "extract_subject_object";
 /// 提取日志主客体结构：subject, action, object, status
-#[derive(Clone, Debug, Default)]
+///
+/// 参数（均可选）：`extract_subject_object(flat, prefix:'evt_', rename:[subject:who], debug)`
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct ExtractSubjectObject {
     /// 是否启用debug模式（输出分词、词性、匹配规则等调试信息）
     pub debug: bool,
+    /// 展平输出：作为管道末尾时输出同级字段，而非单个对象
+    pub flat: bool,
+    /// 输出字段名前缀
+    pub prefix: String,
+    /// 自定义输出键名：(内置键, 新名称)
+    pub rename: Vec<(String, String)>,
 }
 
 impl ExtractSubjectObject {
+    /// 内置输出键，按输出顺序排列
+    pub const KEYS: [&'static str; 5] = ["subject", "action", "object", "status", "debug"];
+
     pub fn with_debug(debug: bool) -> Self {
-        Self { debug }
+        Self {
+            debug,
+            ..Default::default()
+        }
+    }
+
+    /// 内置键对应的输出字段名（先改名，再加前缀）
+    pub fn output_name(&self, key: &str) -> String {
+        let name = self
+            .rename
+            .iter()
+            .find(|(from, _)| from == key)
+            .map(|(_, to)| to.as_str())
+            .unwrap_or(key);
+        format!("{}{}", self.prefix, name)
     }
 }
 
 impl Display for ExtractSubjectObject {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", PIPE_EXTRACT_SUBJECT_OBJECT)
+        let mut args = Vec::new();
+        if self.flat {
+            args.push("flat".to_string());
+        }
+        if !self.prefix.is_empty() {
+            args.push(format!("prefix:'{}'", self.prefix));
+        }
+        if !self.rename.is_empty() {
+            let pairs: Vec<String> = self
+                .rename
+                .iter()
+                .map(|(from, to)| format!("{}:{}", from, to))
+                .collect();
+            args.push(format!("rename:[{}]", pairs.join(", ")));
+        }
+        if self.debug {
+            args.push("debug".to_string());
+        }
+        if args.is_empty() {
+            write!(f, "{}", PIPE_EXTRACT_SUBJECT_OBJECT)
+        } else {
+            write!(f, "{}({})", PIPE_EXTRACT_SUBJECT_OBJECT, args.join(", "))
+        }
     }
 }
 
@@ -0,0 +1,9 @@
+pub const FUN_HOSTNAME: &str = "Hostname";
+pub const FUN_PROCESS_ID: &str = "ProcessId";
+
+/// 当前节点主机名（`Value::Chars`）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Hostname {}
+/// 当前进程号（`Value::Digit`）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProcessId {}
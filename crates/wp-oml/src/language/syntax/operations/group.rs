@@ -0,0 +1,123 @@
+use crate::language::prelude::*;
+use std::time::Duration;
+
+pub const AGG_COUNT: &str = "count";
+pub const AGG_SUM: &str = "sum";
+pub const AGG_MIN: &str = "min";
+pub const AGG_MAX: &str = "max";
+pub const AGG_COLLECT_DISTINCT: &str = "collect_distinct";
+
+/// 分组聚合函数
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateExpr {
+    /// 组内记录数
+    Count,
+    /// 数值字段求和；全部为整数时输出 digit，否则输出 float
+    Sum(String),
+    /// 数值字段最小值
+    Min(String),
+    /// 数值字段最大值
+    Max(String),
+    /// 字段去重值列表（按首次出现顺序）
+    CollectDistinct(String),
+}
+
+impl AggregateExpr {
+    /// 聚合所读取的输入字段；`count()` 无输入字段
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            AggregateExpr::Count => None,
+            AggregateExpr::Sum(f)
+            | AggregateExpr::Min(f)
+            | AggregateExpr::Max(f)
+            | AggregateExpr::CollectDistinct(f) => Some(f.as_str()),
+        }
+    }
+}
+
+impl Display for AggregateExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateExpr::Count => write!(f, "{}()", AGG_COUNT),
+            AggregateExpr::Sum(x) => write!(f, "{}({})", AGG_SUM, x),
+            AggregateExpr::Min(x) => write!(f, "{}({})", AGG_MIN, x),
+            AggregateExpr::Max(x) => write!(f, "{}({})", AGG_MAX, x),
+            AggregateExpr::CollectDistinct(x) => write!(f, "{}({})", AGG_COLLECT_DISTINCT, x),
+        }
+    }
+}
+
+/// 汇总记录中的一个输出字段：`target = agg(field);`
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct AggregateItem {
+    target: String,
+    expr: AggregateExpr,
+}
+
+impl AggregateItem {
+    pub fn new(target: impl Into<String>, expr: AggregateExpr) -> Self {
+        Self {
+            target: target.into(),
+            expr,
+        }
+    }
+}
+
+impl Display for AggregateItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {};", self.target, self.expr)
+    }
+}
+
+/// 模型级有状态聚合：按 `key_fields` 对转换后的记录分桶，每 `emit_every` 输出一批汇总记录
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateOp {
+    GroupBy {
+        key_fields: Vec<String>,
+        aggs: Vec<AggregateItem>,
+        emit_every: Duration,
+    },
+}
+
+impl AggregateOp {
+    pub fn key_fields(&self) -> &[String] {
+        match self {
+            AggregateOp::GroupBy { key_fields, .. } => key_fields,
+        }
+    }
+
+    pub fn aggs(&self) -> &[AggregateItem] {
+        match self {
+            AggregateOp::GroupBy { aggs, .. } => aggs,
+        }
+    }
+
+    pub fn emit_every(&self) -> Duration {
+        match self {
+            AggregateOp::GroupBy { emit_every, .. } => *emit_every,
+        }
+    }
+}
+
+impl Display for AggregateOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateOp::GroupBy {
+                key_fields,
+                aggs,
+                emit_every,
+            } => {
+                writeln!(
+                    f,
+                    "group_by ({}) every {}ms {{",
+                    key_fields.join(", "),
+                    emit_every.as_millis()
+                )?;
+                for item in aggs {
+                    writeln!(f, "    {}", item)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
@@ -77,6 +77,55 @@ impl Display for MatchFun {
     }
 }
 
+/// Regex compiled once at parse time and shared between model clones.
+/// Equality and display use the source pattern.
+#[derive(Clone, Debug)]
+pub struct CompiledRegex(Arc<regex::Regex>);
+
+impl CompiledRegex {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        regex::Regex::new(pattern).map(|re| Self(Arc::new(re)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Only string values can match; other types never do
+    pub fn is_match(&self, value: &DataField) -> bool {
+        match value.get_value() {
+            wp_model_core::model::Value::Chars(s) => self.0.is_match(s),
+            _ => false,
+        }
+    }
+
+    /// 同 [`Self::is_match`]；模式含命名分组时把捕获写入分支上下文，供 `read_capture` 读取
+    pub fn match_capture(&self, value: &DataField) -> bool {
+        match value.get_value() {
+            wp_model_core::model::Value::Chars(s) => {
+                if self.0.capture_names().flatten().next().is_some() {
+                    crate::core::capture::stage(&self.0, s)
+                } else {
+                    self.0.is_match(s)
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for CompiledRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Display for CompiledRegex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum MatchCond {
     Eq(DataField),
@@ -86,6 +135,12 @@ pub enum MatchCond {
     Fun(MatchFun),
     /// OR matching - matches if any alternative matches
     Or(Vec<MatchCond>),
+    /// `regex_match('...')` with the pattern compiled at parse time
+    #[serde(skip)]
+    Regex(CompiledRegex),
+    /// `not_regex_match('...')` with the pattern compiled at parse time
+    #[serde(skip)]
+    RegexNeg(CompiledRegex),
 
     /// Arc-based variants for static symbols (zero-copy reference)
     /// These are created during rewrite phase to share DataField instances
@@ -199,6 +254,9 @@ impl MatchAble<&DataField> for MatchCond {
                 match_with_function(value, fun)
             }
             MatchCond::Or(alternatives) => alternatives.iter().any(|alt| alt.is_match(value)),
+            MatchCond::Regex(re) => re.match_capture(value),
+            // Same as not_regex_match: non-string values count as "not matched"
+            MatchCond::RegexNeg(re) => !re.is_match(value),
 
             // Arc-based variants (for static symbols, zero-copy)
             MatchCond::EqArc(x) => {
@@ -320,6 +378,37 @@ fn numeric_in_range(value: &DataField, min_str: &str, max_str: &str) -> bool {
     false
 }
 
+/// Empty string check; non-string values are considered non-empty
+fn is_empty_chars(value: &DataField) -> bool {
+    use wp_model_core::model::Value;
+    if let Value::Chars(s) = value.get_value() {
+        s.is_empty()
+    } else {
+        false
+    }
+}
+
+/// Regex check shared by `regex_match` / `not_regex_match`;
+/// `None` when the pattern argument is missing or invalid
+fn regex_match(value: &DataField, fun: &MatchFun) -> Option<bool> {
+    use wp_model_core::model::Value;
+    let Some(pattern) = fun.arg() else {
+        warn_data!("{} function requires a pattern argument", fun.name);
+        return None;
+    };
+    let re = match regex::Regex::new(pattern) {
+        Ok(re) => re,
+        Err(e) => {
+            warn_data!("invalid regex pattern '{}': {}", pattern, e);
+            return None;
+        }
+    };
+    match value.get_value() {
+        Value::Chars(s) => Some(re.is_match(s)),
+        _ => Some(false),
+    }
+}
+
 /// Execute a match function and determine if it matches
 fn match_with_function(value: &DataField, fun: &MatchFun) -> bool {
     use wp_model_core::model::Value;
@@ -361,32 +450,11 @@ fn match_with_function(value: &DataField, fun: &MatchFun) -> bool {
                 false
             }
         }
-        "regex_match" => {
-            if let Some(pattern) = fun.arg() {
-                if let Value::Chars(s) = value.get_value() {
-                    match regex::Regex::new(pattern) {
-                        Ok(re) => re.is_match(s),
-                        Err(e) => {
-                            warn_data!("invalid regex pattern '{}': {}", pattern, e);
-                            false
-                        }
-                    }
-                } else {
-                    false
-                }
-            } else {
-                warn_data!("regex_match function requires a pattern argument");
-                false
-            }
-        }
-        "is_empty" => {
-            if let Value::Chars(s) = value.get_value() {
-                s.is_empty()
-            } else {
-                // Non-string values are considered non-empty
-                false
-            }
-        }
+        "regex_match" => regex_match(value, fun).unwrap_or(false),
+        // 配置错误（缺参数、非法正则）时与 regex_match 一样不匹配
+        "not_regex_match" => regex_match(value, fun).is_some_and(|m| !m),
+        "is_empty" => is_empty_chars(value),
+        "not_empty" => !is_empty_chars(value),
         "iequals" => {
             if let Some(compare_val) = fun.arg() {
                 if let Value::Chars(s) = value.get_value() {
@@ -466,6 +534,12 @@ impl Display for MatchCond {
                     write!(f, "{}", alt)?;
                 }
             }
+            MatchCond::Regex(re) => {
+                write!(f, " regex_match('{}')  ", re)?;
+            }
+            MatchCond::RegexNeg(re) => {
+                write!(f, " not_regex_match('{}')  ", re)?;
+            }
 
             // Arc-based variants (same display as regular variants)
             MatchCond::EqArc(x) => {
@@ -1,10 +1,12 @@
 pub mod fmt;
+pub mod group;
 pub mod map;
 pub mod matchs;
 pub mod pipe;
 pub mod record;
 pub mod sql;
 pub use fmt::*;
+pub use group::*;
 pub use map::*;
 pub use matchs::*;
 pub use pipe::*;
@@ -17,6 +17,27 @@ impl PiPeOperation {
     pub fn items_mut(&mut self) -> &mut Vec<PipeFun> {
         &mut self.items
     }
+
+    /// 末尾为 `extract_subject_object(flat, ...)` 时，结果展开为多个同级字段
+    pub fn flat_tail(&self) -> Option<&crate::language::ExtractSubjectObject> {
+        match self.items.last() {
+            Some(PipeFun::ExtractSubjectObject(x)) if x.flat => Some(x),
+            _ => None,
+        }
+    }
+}
+
+/// 管道函数未实现 `PartialEq`，按源码形式比较
+impl PartialEq for PiPeOperation {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from
+            && self.items.len() == other.items.len()
+            && self
+                .items
+                .iter()
+                .zip(&other.items)
+                .all(|(a, b)| a.to_string() == b.to_string())
+    }
 }
 
 impl Display for PiPeOperation {
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 
-use crate::language::EvalExp;
+use crate::language::{AggregateOp, EvalExp};
 use derive_getters::Getters;
 use enum_dispatch::enum_dispatch;
+use wp_data_model::cache::CacheStats;
 use wp_model_core::model::DataField;
 use wp_specs::WildArray;
 
@@ -13,11 +15,47 @@ pub struct ObjModel {
     name: String,
     rules: WildArray,
     enable: bool,
+    /// 单条记录转换的超时（毫秒）；超时则放弃转换，原样返回输入记录
+    timeout_ms: Option<u64>,
+    /// `debug : true`：转换时按表达式输出求值轨迹（仅在 DEBUG 日志级别开启时生效）
+    debug: bool,
     pub items: Vec<EvalExp>,
     #[getter(skip)]
     has_temp_fields: bool,
     #[getter(skip)]
     static_fields: HashMap<String, Arc<DataField>>,
+    /// static 块只由字面量组成，物化结果可写入磁盘缓存
+    #[getter(skip)]
+    static_cacheable: bool,
+    #[getter(skip)]
+    group_by: Option<AggregateOp>,
+    /// 限时转换交给工作线程时共享的模型快照，首次使用时创建
+    #[getter(skip)]
+    pub(crate) shared: OnceLock<Arc<ObjModel>>,
+    /// 查询缓存命中统计；克隆（含限时转换的共享快照）共用同一计数
+    #[getter(skip)]
+    pub(crate) lookup_stats: Arc<LookupCounter>,
+}
+
+/// 模型级查询缓存命中计数；模型的所有克隆共享同一实例
+#[derive(Debug, Default)]
+pub struct LookupCounter {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl LookupCounter {
+    pub fn record(&self, delta: CacheStats) {
+        self.hits.fetch_add(delta.hits, Ordering::Relaxed);
+        self.misses.fetch_add(delta.misses, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
 }
 
 impl ObjModel {
@@ -31,6 +69,14 @@ impl ObjModel {
         self.enable = enable;
     }
 
+    pub(crate) fn set_timeout_ms(&mut self, ms: u64) {
+        self.timeout_ms = Some(ms);
+    }
+
+    pub(crate) fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
     pub fn has_temp_fields(&self) -> bool {
         self.has_temp_fields
     }
@@ -46,6 +92,34 @@ impl ObjModel {
     pub fn static_fields(&self) -> &HashMap<String, Arc<DataField>> {
         &self.static_fields
     }
+
+    pub(crate) fn set_static_cacheable(&mut self, cacheable: bool) {
+        self.static_cacheable = cacheable;
+    }
+
+    /// static 块的物化结果是否只取决于 OML 文本（不含知识库查询、内置函数与 `env(...)`）
+    pub fn static_cacheable(&self) -> bool {
+        self.static_cacheable
+    }
+
+    /// 模型级分组聚合；存在时逐条转换结果不直接下发，而是由聚合器周期输出汇总记录
+    pub fn group_by(&self) -> Option<&AggregateOp> {
+        self.group_by.as_ref()
+    }
+
+    pub(crate) fn set_group_by(&mut self, op: AggregateOp) {
+        self.group_by = Some(op);
+    }
+
+    /// 模型累计的知识库查询缓存命中统计（misses 即实际查询次数）
+    pub fn lookup_stats(&self) -> CacheStats {
+        self.lookup_stats.snapshot()
+    }
+
+    /// 命中计数的共享句柄，供指标导出在抓取时读取，无需持有模型本身
+    pub fn lookup_counter(&self) -> Arc<LookupCounter> {
+        Arc::clone(&self.lookup_stats)
+    }
 }
 
 impl ObjModel {
@@ -54,9 +128,15 @@ impl ObjModel {
             name,
             rules: WildArray::default(),
             enable: true,
+            timeout_ms: None,
+            debug: false,
             items: Vec::new(),
             has_temp_fields: false,
             static_fields: HashMap::new(),
+            static_cacheable: false,
+            group_by: None,
+            shared: OnceLock::new(),
+            lookup_stats: Arc::default(),
         }
     }
 }
@@ -73,6 +153,9 @@ impl Display for ObjModel {
         for i in &self.items {
             writeln!(f, "{}", i)?;
         }
+        if let Some(op) = &self.group_by {
+            writeln!(f, "{}", op)?;
+        }
         Ok(())
     }
 }
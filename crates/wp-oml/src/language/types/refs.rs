@@ -0,0 +1,232 @@
+//! 模型对输入记录字段的静态引用分析（`prj check` 中 OML/WPL 字段映射检查使用），
+//! 以及输出目标清单（工程 diff 使用）与显式声明的输出类型（路由 schema 校验使用）
+
+use std::collections::HashSet;
+
+use wp_model_core::model::DataType;
+
+use crate::language::{
+    DirectAccessor, EvalExp, MatchSource, NestedAccessor, ObjModel, PreciseEvaluator,
+};
+
+/// 一处 `read`/`take` 引用；`names` 为按顺序尝试的候选字段（目标名或 `get`，以及 `option`），
+/// 任一存在即可满足
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldRef {
+    pub names: Vec<String>,
+}
+
+impl ObjModel {
+    /// 收集模型按字面名读取/提取的输入字段。
+    ///
+    /// 通配名、`collect`、批量目标以及前序表达式已赋值的字段（`read` 会先查找输出记录）不计入，
+    /// 保证结果只包含可静态确认的引用。
+    pub fn input_field_refs(&self) -> Vec<FieldRef> {
+        let mut refs = Vec::new();
+        let mut assigned: HashSet<String> = HashSet::new();
+        for item in &self.items {
+            let EvalExp::Single(single) = item else {
+                continue;
+            };
+            let fallback = match single.target().as_slice() {
+                [target] => target.name().as_deref(),
+                _ => None,
+            };
+            let mut found = Vec::new();
+            precise_refs(single.eval_way(), fallback, &mut found);
+            for r in found {
+                if !r.names.iter().any(|n| assigned.contains(n)) && !refs.contains(&r) {
+                    refs.push(r);
+                }
+            }
+            for target in single.target() {
+                if let Some(name) = target.name() {
+                    assigned.insert(name.clone());
+                }
+            }
+        }
+        refs
+    }
+
+    /// 输出目标名及其定义文本（`目标 = 表达式`），按定义顺序；批量目标为通配名，断言不计入
+    pub fn output_targets(&self) -> Vec<(String, String)> {
+        let mut targets = Vec::new();
+        for item in &self.items {
+            match item {
+                EvalExp::Single(single) => {
+                    for target in single.target() {
+                        let text = format!("{} = {}", target, single.eval_way());
+                        targets.push((target.safe_name(), text));
+                    }
+                }
+                EvalExp::Batch(batch) => {
+                    let target = batch.target().origin();
+                    let text = format!("{} = {}", target, batch.eval_way());
+                    targets.push((target.safe_name(), text));
+                }
+                EvalExp::Assert(_) => {}
+            }
+        }
+        targets
+    }
+
+    /// 显式声明了类型的输出目标（`port : digit = ...`）；通配目标与临时字段（`__` 前缀）不计入
+    pub fn typed_targets(&self) -> Vec<(&str, &DataType)> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                EvalExp::Single(single) => Some(single.target()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|target| {
+                let name = target.name().as_deref()?;
+                let typed = !matches!(target.data_type(), DataType::Auto | DataType::Ignore);
+                (typed && !name.contains('*') && !name.starts_with("__"))
+                    .then_some((name, target.data_type()))
+            })
+            .collect()
+    }
+}
+
+fn precise_refs(eval: &PreciseEvaluator, fallback: Option<&str>, out: &mut Vec<FieldRef>) {
+    match eval {
+        PreciseEvaluator::Tdc(op) => out.extend(direct_ref(op.dat_get(), fallback)),
+        PreciseEvaluator::Pipe(op) => out.extend(direct_ref(op.from(), fallback)),
+        PreciseEvaluator::Fmt(op) => {
+            for sub in op.subs() {
+                out.extend(direct_ref(sub.dat_get(), None));
+            }
+        }
+        PreciseEvaluator::Match(op) => {
+            match op.dat_crate() {
+                MatchSource::Single(acc) => out.extend(direct_ref(acc, fallback)),
+                MatchSource::Multi(accs) => {
+                    for acc in accs.iter() {
+                        out.extend(direct_ref(acc, None));
+                    }
+                }
+            }
+            for case in op.items().iter().chain(op.default()) {
+                out.extend(nested_ref(case.result(), fallback));
+            }
+        }
+        PreciseEvaluator::Map(op) => {
+            for binding in op.subs() {
+                out.extend(nested_ref(
+                    binding.acquirer(),
+                    binding.target().name().as_deref(),
+                ));
+            }
+        }
+        PreciseEvaluator::Coalesce(args) => {
+            for arg in args {
+                precise_refs(arg, fallback, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn nested_ref(acc: &NestedAccessor, fallback: Option<&str>) -> Option<FieldRef> {
+    match acc {
+        NestedAccessor::Direct(op) => direct_ref(op.dat_get(), fallback),
+        NestedAccessor::Pipe(op) => direct_ref(op.from(), fallback),
+        _ => None,
+    }
+}
+
+fn direct_ref(acc: &DirectAccessor, fallback: Option<&str>) -> Option<FieldRef> {
+    let (get, option, collect) = match acc {
+        DirectAccessor::Take(x) => (&x.get, &x.option, &x.collect),
+        DirectAccessor::Read(x) => (&x.get, &x.option, &x.collect),
+        DirectAccessor::Capture(_) => return None,
+    };
+    if !collect.is_empty() {
+        return None;
+    }
+    let names: Vec<String> = get
+        .as_deref()
+        .or(fallback)
+        .into_iter()
+        .map(str::to_string)
+        .chain(option.iter().cloned())
+        .collect();
+    if names.is_empty() || !names.iter().all(|n| is_literal_name(n)) {
+        return None;
+    }
+    Some(FieldRef { names })
+}
+
+/// 仅分析普通字段名；通配、路径与下标访问无法静态对应到 WPL 输出
+fn is_literal_name(name: &str) -> bool {
+    !name.is_empty() && name != "_" && !name.contains(['*', '?', '[', '/'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+
+    fn names(refs: &[FieldRef]) -> Vec<Vec<&str>> {
+        refs.iter()
+            .map(|r| r.names.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_input_field_refs() {
+        let mut code = r#"
+name : test
+rule : /nginx/*
+---
+sip          = take() ;
+dst          = read(dst_ip) ;
+port         = take(option:[dport, port]) ;
+aler*        = take() ;
+list         = collect read(keys:[sport,dport]) ;
+copy         = read(sip) ;
+enc          = pipe take(payload) | base64_encode ;
+info         = object {
+    host = read(host_name) ;
+} ;
+version      = chars(1.0.0) ;
+        "#;
+        let model = oml_parse_raw(&mut code).assert();
+        let targets: Vec<String> = model.output_targets().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(
+            targets,
+            vec![
+                "sip", "dst", "port", "aler*", "list", "copy", "enc", "info", "version"
+            ]
+        );
+        assert_eq!(
+            names(&model.input_field_refs()),
+            vec![
+                vec!["sip"],
+                vec!["dst_ip"],
+                vec!["port", "dport", "port"],
+                vec!["payload"],
+                vec!["host_name"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typed_targets() {
+        let mut code = r#"
+name : test
+---
+port : digit = read(port) ;
+sip  : ip    = read(sip) ;
+msg          = read(msg) ;
+__tmp : chars = read(x) ;
+"#;
+        let model = oml_parse_raw(&mut code).assert();
+        assert_eq!(
+            model.typed_targets(),
+            vec![("port", &DataType::Digit), ("sip", &DataType::IP)]
+        );
+    }
+}
@@ -8,6 +8,9 @@ use wp_model_core::model::{DataField, DataType};
 pub struct EvaluationTarget {
     name: Option<String>,
     data_type: DataType,
+    /// 通配目标的排除模式（`*/path except A*/path, B1/path`），排除优先
+    #[builder(default)]
+    exclude: Vec<String>,
 }
 
 impl EvaluationTarget {
@@ -20,6 +23,7 @@ impl EvaluationTarget {
 pub struct BatchEvalTarget {
     origin: EvaluationTarget,
     wild: WildMatch,
+    excludes: Vec<WildMatch>,
 }
 
 impl BatchEvalTarget {
@@ -27,12 +31,16 @@ impl BatchEvalTarget {
         let name = origin.name().clone().unwrap_or("_".to_string());
         Self {
             wild: WildMatch::new(name.as_str()),
+            excludes: origin.exclude.iter().map(|p| WildMatch::new(p)).collect(),
             origin,
         }
     }
     pub fn match_it(&self, tdo: &DataField) -> bool {
         let key = tdo.get_name().trim();
         //let k2 = key.trim();
+        if self.excludes.iter().any(|w| w.matches(key)) {
+            return false;
+        }
         if self.wild().matches(key) && self.origin().data_type() == &DataType::Auto
             || tdo.get_meta() == self.origin().data_type()
         {
@@ -47,12 +55,14 @@ impl EvaluationTarget {
         Self {
             name: Some(name),
             data_type: meta,
+            exclude: Vec::new(),
         }
     }
     pub fn auto_default() -> Self {
         Self {
             name: None,
             data_type: DataType::Auto,
+            exclude: Vec::new(),
         }
     }
 }
@@ -61,6 +71,7 @@ impl From<(Option<String>, DataType)> for EvaluationTarget {
         Self {
             name: v.0,
             data_type: v.1,
+            exclude: Vec::new(),
         }
     }
 }
@@ -68,7 +79,17 @@ impl From<(Option<String>, DataType)> for EvaluationTarget {
 impl Display for EvaluationTarget {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let name = self.name.clone().unwrap_or("_".to_string());
-        write!(f, "{} : {} ", name, self.data_type)
+        if self.exclude.is_empty() {
+            write!(f, "{} : {} ", name, self.data_type)
+        } else {
+            write!(
+                f,
+                "{} except {} : {} ",
+                name,
+                self.exclude.join(", "),
+                self.data_type
+            )
+        }
     }
 }
 
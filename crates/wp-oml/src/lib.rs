@@ -25,10 +25,12 @@ mod test_utils;
 pub mod types;
 
 pub use core::{DataRecordRef, ExpEvaluator};
-pub use parser::oml_parse_raw;
+pub use parser::{oml_parse_raw, oml_parse_raw_cached};
 
 // 导出语义词典相关的公开 API
 pub use core::evaluator::transform::pipe::semantic_dict_loader::{
-    check_semantic_dict_config, generate_default_semantic_dict_config, init_semantic_dict,
+    DictLoadError, SemanticCategory, add_semantic_entry, check_semantic_dict_config,
+    generate_default_semantic_dict_config, init_semantic_dict, is_semantic_enabled,
+    list_loaded_dict_files, reload_semantic_dict, remove_semantic_entry, set_jieba_user_dict,
     set_semantic_enabled,
 };
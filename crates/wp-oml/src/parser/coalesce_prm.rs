@@ -0,0 +1,89 @@
+use crate::language::PreciseEvaluator;
+use crate::parser::fmt_prm::oml_aga_fmt;
+use crate::parser::fun_prm::oml_gw_fun;
+use crate::parser::keyword::kw_coalesce;
+use crate::parser::pipe_prm::{oml_aga_pipe, oml_aga_pipe_noprefix};
+use crate::parser::static_ctx::parse_static_value;
+use crate::parser::tdc_prm::{oml_aga_tdc, oml_aga_value};
+use winnow::ascii::multispace0;
+use winnow::combinator::{alt, peek, separated};
+use winnow::error::{StrContext, StrContextValue};
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::symbol::symbol_comma;
+use wpl::parser::utils::take_key;
+
+pub fn oml_aga_coalesce(data: &mut &str) -> WResult<PreciseEvaluator> {
+    kw_coalesce.parse_next(data)?;
+    (multispace0, "(").parse_next(data)?;
+    let args: Vec<PreciseEvaluator> = separated(1.., oml_coalesce_arg, symbol_comma)
+        .context(StrContext::Label("coalesce"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            ">> coalesce(<exp>, <exp>, ...)",
+        )))
+        .parse_next(data)?;
+    (multispace0, ")", multispace0).parse_next(data)?;
+    Ok(PreciseEvaluator::Coalesce(args))
+}
+
+/// 候选项：取值（可带管道）、字面量、内置函数、`fmt` 与静态符号
+fn oml_coalesce_arg(data: &mut &str) -> WResult<PreciseEvaluator> {
+    multispace0.parse_next(data)?;
+    let key = peek(take_key).parse_next(data)?;
+    match key {
+        "pipe" => oml_aga_pipe.parse_next(data),
+        "fmt" => oml_aga_fmt.parse_next(data),
+        "take" | "read" => alt((oml_aga_pipe_noprefix, oml_aga_tdc)).parse_next(data),
+        _ => alt((oml_aga_value, oml_gw_fun, parse_static_value)).parse_next(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::coalesce_prm::oml_aga_coalesce;
+    use crate::parser::oml_parse_raw;
+    use crate::parser::utils::for_test::assert_oml_parse_ext;
+    use orion_error::TestAssert;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord, FieldStorage};
+    use wp_parser::WResult as ModalResult;
+
+    #[test]
+    fn test_oml_coalesce() -> ModalResult<()> {
+        let mut code = r#" coalesce(read(a), read(b) | to_str, chars(fallback)) "#;
+        let expect = r#"coalesce(read(a), pipe read(b) | to_str, chars(fallback))"#;
+        assert_oml_parse_ext(&mut code, oml_aga_coalesce, expect);
+        Ok(())
+    }
+
+    #[test]
+    fn test_coalesce_first_present() {
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord::from(vec![
+            FieldStorage::from_owned(DataField::from_chars("b", "")),
+            FieldStorage::from_owned(DataField::from_chars("c", "from_c")),
+        ]);
+
+        let mut conf = r#"
+        name : test
+        ---
+        x = coalesce(read(a), read(b), read(c), chars(fallback));
+        y = coalesce(read(a), read(b), chars(fallback));
+        z = coalesce(read(a), read(b));
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        // a 缺失、b 为空字符串，均被跳过
+        assert_eq!(
+            target.field("x").map(|s| s.as_field()),
+            Some(&DataField::from_chars("x", "from_c"))
+        );
+        assert_eq!(
+            target.field("y").map(|s| s.as_field()),
+            Some(&DataField::from_chars("y", "fallback"))
+        );
+        assert!(target.field("z").is_none());
+    }
+}
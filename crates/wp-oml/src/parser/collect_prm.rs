@@ -1,10 +1,16 @@
 use crate::language::ArrOperation;
 use crate::language::PreciseEvaluator;
+use crate::language::SortOrder;
 use crate::parser::keyword::kw_gw_collect;
 use crate::parser::oml_aggregate::oml_var_get;
+use crate::parser::pipe_prm::oml_pipe;
+use winnow::ascii::multispace0;
+use winnow::combinator::{alt, opt, repeat};
 use winnow::error::{StrContext, StrContextValue};
 use wp_parser::Parser;
 use wp_parser::WResult;
+use wp_parser::symbol::{ctx_desc, symbol_bracket_beg, symbol_bracket_end, symbol_comma};
+use wpl::parser::utils::take_key;
 
 pub fn oml_aga_collect(data: &mut &str) -> WResult<PreciseEvaluator> {
     Ok(PreciseEvaluator::Collect(
@@ -20,8 +26,33 @@ pub fn oml_aga_collect(data: &mut &str) -> WResult<PreciseEvaluator> {
 pub fn oml_collect(data: &mut &str) -> WResult<ArrOperation> {
     kw_gw_collect.parse_next(data)?;
     let from = oml_var_get.parse_next(data)?;
-    //dat_crate::oml_crate.parse_next(data).map(GetWay::Direct)
-    Ok(ArrOperation::new(from))
+    let order_by = opt(oml_order_by).parse_next(data)?;
+    // 可省略 `pipe` 关键字直接接管道：collect take(...) | unique
+    let pipes = repeat(0.., oml_pipe).parse_next(data)?;
+    Ok(ArrOperation::new(from)
+        .with_order_by(order_by)
+        .with_pipes(pipes))
+}
+
+/// `order_by(<field>, asc|desc)`，方向省略时为 asc
+fn oml_order_by(data: &mut &str) -> WResult<(String, SortOrder)> {
+    (multispace0, "order_by", multispace0, symbol_bracket_beg).parse_next(data)?;
+    multispace0.parse_next(data)?;
+    let field = take_key
+        .context(StrContext::Label("order_by field"))
+        .parse_next(data)?;
+    let order = opt((
+        multispace0,
+        symbol_comma,
+        multispace0,
+        alt(("asc".value(SortOrder::Asc), "desc".value(SortOrder::Desc))),
+    ))
+    .context(ctx_desc("asc | desc"))
+    .parse_next(data)?
+    .map(|(_, _, _, order)| order)
+    .unwrap_or_default();
+    (multispace0, symbol_bracket_end).parse_next(data)?;
+    Ok((field.to_string(), order))
 }
 
 #[cfg(test)]
@@ -0,0 +1,78 @@
+use crate::language::PreciseEvaluator;
+use crate::parser::keyword::kw_env;
+use winnow::ascii::multispace0;
+use winnow::combinator::{opt, preceded};
+use winnow::error::{StrContext, StrContextValue};
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::symbol::symbol_comma;
+use wpl::parser::utils::quot_str;
+
+/// `env("NAME")` / `env("NAME", "default")`
+pub fn oml_aga_env(data: &mut &str) -> WResult<PreciseEvaluator> {
+    kw_env.parse_next(data)?;
+    (multispace0, "(", multispace0).parse_next(data)?;
+    let (var_name, default) = (quot_str, opt(preceded(symbol_comma, quot_str)))
+        .context(StrContext::Label("env"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            ">> env(\"<NAME>\", \"<default>\")",
+        )))
+        .parse_next(data)?;
+    (multispace0, ")", multispace0).parse_next(data)?;
+    Ok(PreciseEvaluator::env(
+        var_name.to_string(),
+        default.map(str::to_string),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::env_prm::oml_aga_env;
+    use crate::parser::oml_parse_raw;
+    use crate::parser::utils::for_test::assert_oml_parse_ext;
+    use orion_error::TestAssert;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord};
+    use wp_parser::WResult as ModalResult;
+
+    #[test]
+    fn test_oml_env() -> ModalResult<()> {
+        let mut code = r#" env("AWS_REGION", "us-east-1") "#;
+        assert_oml_parse_ext(&mut code, oml_aga_env, r#"env("AWS_REGION", "us-east-1")"#);
+        let mut code = r#" env( 'CLUSTER' ) "#;
+        assert_oml_parse_ext(&mut code, oml_aga_env, r#"env("CLUSTER")"#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_resolved_at_load() {
+        unsafe {
+            std::env::set_var("WP_OML_TEST_ENV_REGION", "cn-north-1");
+        }
+        let mut conf = r#"
+        name : test
+        ---
+        region = env("WP_OML_TEST_ENV_REGION", "us-east-1") ;
+        zone = env("WP_OML_TEST_ENV_UNSET", "zone-a") ;
+        cluster = env("WP_OML_TEST_ENV_UNSET") ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        // 加载后再修改环境变量，不影响已缓存的取值
+        unsafe {
+            std::env::set_var("WP_OML_TEST_ENV_REGION", "changed");
+        }
+        let cache = &mut FieldQueryCache::default();
+        let target = model.transform(DataRecord::default(), cache);
+
+        assert_eq!(
+            target.field("region").map(|s| s.as_field()),
+            Some(&DataField::from_chars("region", "cn-north-1"))
+        );
+        assert_eq!(
+            target.field("zone").map(|s| s.as_field()),
+            Some(&DataField::from_chars("zone", "zone-a"))
+        );
+        assert!(target.field("cluster").is_none());
+    }
+}
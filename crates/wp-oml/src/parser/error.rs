@@ -3,10 +3,14 @@ use wp_error::{
     parse_error::{OMLCodeError, OMLCodeReason},
     util::split_string,
 };
+use wp_parser::location::CodePos;
 
 use crate::winnow::error::ContextError;
 pub trait OMLCodeErrorTait {
     fn from_syntax(e: ErrMode<ContextError>, code: &str, path: &str) -> Self;
+
+    /// 带行列号的语法错误：`code` 为完整输入，`rest` 为解析失败时剩余的输入
+    fn from_syntax_at(e: ErrMode<ContextError>, code: &str, rest: &str, path: &str) -> Self;
 }
 
 impl OMLCodeErrorTait for OMLCodeError {
@@ -34,4 +38,53 @@ impl OMLCodeErrorTait for OMLCodeError {
             }
         }
     }
+
+    fn from_syntax_at(e: ErrMode<ContextError>, code: &str, rest: &str, path: &str) -> Self {
+        let e = match e {
+            ErrMode::Backtrack(e) | ErrMode::Cut(e) => e,
+            incomplete => return Self::from_syntax(incomplete, rest, path),
+        };
+        let pos = CodePos::locate(code, rest);
+        let msg = e.to_string();
+        let msg = if msg.is_empty() {
+            "oml code parse fail"
+        } else {
+            msg.as_str()
+        };
+        OMLCodeError::from(OMLCodeReason::Syntax(format!(
+            "[{}] error: {}\n --> {}:{}\n{}",
+            pos,
+            msg,
+            path,
+            pos,
+            pos.snippet(code)
+        )))
+        .with_position(pos.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::oml_parse;
+
+    #[test]
+    fn syntax_error_reports_line_and_column() {
+        let code = "name : demo\n---\nx = read(a) ;\ny = read(b ;\n";
+        let mut input = code;
+        let err = oml_parse(&mut input, "demo.oml").expect_err("unterminated read");
+        let text = err.to_string();
+        assert!(text.contains("[4:"), "line number in: {}", text);
+        assert!(text.contains("demo.oml:4:"), "path location in: {}", text);
+        assert!(
+            text.contains("4 | y = read(b ;"),
+            "source line in: {}",
+            text
+        );
+        assert!(
+            err.position()
+                .as_deref()
+                .is_some_and(|p| p.starts_with("4:"))
+        );
+    }
 }
@@ -19,7 +19,7 @@ pub fn oml_fmt_item(data: &mut &str) -> ModalResult<FmtOperation> {
     kw_fmt.parse_next(data)?;
     (multispace0, "(", multispace0).parse_next(data)?;
     let fmt_str = get_scope(data, '"', '"')?;
-    let args = repeat(1.., oml_arg_item).parse_next(data)?;
+    let args = repeat(0.., oml_arg_item).parse_next(data)?;
     (multispace0, ")", multispace0).parse_next(data)?;
     let _ = get_scope(data, '(', ')');
     let get = FmtOperation::new(fmt_str.to_string(), args);
@@ -43,4 +43,12 @@ mod tests {
         assert_oml_parse(&mut code, oml_aga_fmt);
         Ok(())
     }
+
+    #[test]
+    fn test_oml_fmt_named() -> ModalResult<()> {
+        let mut code = r#" fmt("{ip}:{port}")
+     "#;
+        assert_oml_parse(&mut code, oml_aga_fmt);
+        Ok(())
+    }
 }
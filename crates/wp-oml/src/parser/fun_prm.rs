@@ -1,6 +1,6 @@
 use crate::language::{
-    BuiltinFunction, FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, FunOperation, NowDate, NowHour,
-    NowTime, PreciseEvaluator,
+    BuiltinFunction, FUN_HOSTNAME, FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, FUN_PROCESS_ID,
+    FunOperation, Hostname, NowDate, NowHour, NowTime, PreciseEvaluator, ProcessId,
 };
 use winnow::ascii::multispace0;
 use winnow::combinator::alt;
@@ -19,6 +19,8 @@ pub fn oml_fun_item(data: &mut &str) -> WResult<BuiltinFunction> {
         FUN_NOW_DATE.map(|_| BuiltinFunction::NowDate(NowDate::default())),
         FUN_NOW_HOUR.map(|_| BuiltinFunction::NowHour(NowHour::default())),
         FUN_NOW_TIME.map(|_| BuiltinFunction::NowTime(NowTime::default())),
+        FUN_HOSTNAME.map(|_| BuiltinFunction::Hostname(Hostname::default())),
+        FUN_PROCESS_ID.map(|_| BuiltinFunction::ProcessId(ProcessId::default())),
     ))
     .parse_next(data)?;
     let _ = get_scope(data, '(', ')');
@@ -45,6 +47,14 @@ mod tests {
      "#;
         assert_oml_parse(&mut code, oml_gw_fun);
 
+        let mut code = r#" Hostname()
+     "#;
+        assert_oml_parse(&mut code, oml_gw_fun);
+
+        let mut code = r#" ProcessId()
+     "#;
+        assert_oml_parse(&mut code, oml_gw_fun);
+
         Ok(())
     }
 }
@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use crate::language::{
+    AGG_COLLECT_DISTINCT, AGG_COUNT, AGG_MAX, AGG_MIN, AGG_SUM, AggregateExpr, AggregateItem,
+    AggregateOp,
+};
+use crate::parser::keyword::{kw_every, kw_group_by};
+use winnow::ascii::{digit1, multispace0};
+use winnow::combinator::{alt, separated};
+use winnow::error::{ContextError, ErrMode, StrContext, StrContextValue};
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::symbol::{symbol_assign, symbol_semicolon};
+use wp_parser::utils::get_scope;
+use wpl::parser::utils::{take_key, take_var_name};
+
+/// `group_by (k1, k2) every 60s { cnt = count(); total = sum(bytes); }`
+pub fn oml_group_by(data: &mut &str) -> WResult<AggregateOp> {
+    kw_group_by.parse_next(data)?;
+    let mut keys_data = get_scope(data, '(', ')')?;
+    let key_fields: Vec<String> = separated(
+        1..,
+        (multispace0, take_key, multispace0).map(|(_, k, _): (_, &str, _)| k.to_string()),
+        ",",
+    )
+    .context(StrContext::Label("group_by keys"))
+    .parse_next(&mut keys_data)?;
+    kw_every.parse_next(data)?;
+    let emit_every = oml_duration.parse_next(data)?;
+
+    let mut block_data = get_scope(data, '{', '}')?;
+    let mut aggs = Vec::new();
+    loop {
+        multispace0.parse_next(&mut block_data)?;
+        if block_data.is_empty() {
+            break;
+        }
+        aggs.push(oml_agg_item.parse_next(&mut block_data)?);
+    }
+    if aggs.is_empty() {
+        return Err(cut_err("group_by aggregates", "at least one aggregate"));
+    }
+    Ok(AggregateOp::GroupBy {
+        key_fields,
+        aggs,
+        emit_every,
+    })
+}
+
+fn oml_agg_item(data: &mut &str) -> WResult<AggregateItem> {
+    multispace0.parse_next(data)?;
+    let target = take_var_name.parse_next(data)?;
+    symbol_assign.parse_next(data)?;
+    let expr = oml_agg_expr.parse_next(data)?;
+    symbol_semicolon.parse_next(data)?;
+    Ok(AggregateItem::new(target, expr))
+}
+
+fn oml_agg_expr(data: &mut &str) -> WResult<AggregateExpr> {
+    multispace0.parse_next(data)?;
+    let fun = alt((AGG_COLLECT_DISTINCT, AGG_COUNT, AGG_SUM, AGG_MIN, AGG_MAX))
+        .context(StrContext::Label("group_by aggregate"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "count|sum|min|max|collect_distinct",
+        )))
+        .parse_next(data)?;
+    let arg = get_scope(data, '(', ')')?.trim();
+    let expr = match fun {
+        AGG_COUNT if arg.is_empty() => AggregateExpr::Count,
+        AGG_COUNT => return Err(cut_err("count()", "no argument")),
+        _ if arg.is_empty() => return Err(cut_err("group_by aggregate", "field argument")),
+        AGG_SUM => AggregateExpr::Sum(arg.to_string()),
+        AGG_MIN => AggregateExpr::Min(arg.to_string()),
+        AGG_MAX => AggregateExpr::Max(arg.to_string()),
+        _ => AggregateExpr::CollectDistinct(arg.to_string()),
+    };
+    Ok(expr)
+}
+
+/// `500ms` / `30s` / `5m` / `1h`
+fn oml_duration(data: &mut &str) -> WResult<Duration> {
+    multispace0.parse_next(data)?;
+    let num: u64 = digit1
+        .try_map(str::parse)
+        .context(StrContext::Label("group_by every"))
+        .parse_next(data)?;
+    let unit = alt(("ms", "s", "m", "h"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "ms|s|m|h",
+        )))
+        .parse_next(data)?;
+    let dur = match unit {
+        "ms" => Duration::from_millis(num),
+        "s" => Duration::from_secs(num),
+        "m" => Duration::from_secs(num * 60),
+        _ => Duration::from_secs(num * 3600),
+    };
+    if dur.is_zero() {
+        return Err(cut_err("group_by every", "non-zero interval"));
+    }
+    Ok(dur)
+}
+
+fn cut_err(label: &'static str, expected: &'static str) -> ErrMode<ContextError> {
+    let mut err = ContextError::new();
+    err.push(StrContext::Label(label));
+    err.push(StrContext::Expected(StrContextValue::Description(expected)));
+    ErrMode::Cut(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::oml_parse_raw;
+    use crate::parser::utils::for_test::assert_oml_parse_ext;
+
+    #[test]
+    fn test_group_by_parse() {
+        let mut code = r#"group_by (src_ip, minute) every 1m {
+            hits = count();
+            bytes_sum = sum(bytes);
+            lo = min(bytes);
+            hi = max(bytes);
+            users = collect_distinct(user);
+        }"#;
+        let expect = r#"group_by (src_ip, minute) every 60000ms {
+            hits = count();
+            bytes_sum = sum(bytes);
+            lo = min(bytes);
+            hi = max(bytes);
+            users = collect_distinct(user);
+        }"#;
+        assert_oml_parse_ext(&mut code, oml_group_by, expect);
+    }
+
+    #[test]
+    fn test_group_by_rejects_bad_aggregates() {
+        for code in [
+            "group_by (a) every 1s { }",
+            "group_by (a) every 0s { n = count(); }",
+            "group_by (a) every 1s { n = count(x); }",
+            "group_by (a) every 1s { n = sum(); }",
+            "group_by (a) every 1s { n = avg(x); }",
+            "group_by () every 1s { n = count(); }",
+        ] {
+            let mut data = code;
+            assert!(oml_group_by.parse_next(&mut data).is_err(), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_model_with_group_by() {
+        let mut conf = r#"
+        name : per_src
+        ---
+        src_ip = read(src_ip);
+        bytes:digit = read(bytes);
+        group_by (src_ip) every 30s {
+            hits = count();
+            total = sum(bytes);
+        }
+        "#;
+        let model = oml_parse_raw(&mut conf).expect("parse model");
+        let op = model.group_by().expect("group_by");
+        assert_eq!(op.key_fields(), ["src_ip".to_string()]);
+        assert_eq!(op.emit_every(), Duration::from_secs(30));
+        assert_eq!(op.aggs().len(), 2);
+        assert_eq!(model.items.len(), 2);
+    }
+}
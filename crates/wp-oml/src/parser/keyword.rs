@@ -61,6 +61,26 @@ pub fn kw_oml_enable(data: &mut &str) -> WResult<()> {
         .parse_next(data)?;
     Ok(())
 }
+pub fn kw_oml_debug(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("debug")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'debug' ",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+pub fn kw_oml_timeout(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("timeout_ms")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'timeout_ms' ",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
 pub fn kw_static(data: &mut &str) -> WResult<()> {
     let _ = multispace0.parse_next(data)?;
     literal("static")
@@ -71,6 +91,26 @@ pub fn kw_static(data: &mut &str) -> WResult<()> {
         .parse_next(data)?;
     Ok(())
 }
+pub fn kw_group_by(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("group_by")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'group_by' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+pub fn kw_every(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("every")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'every' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
 pub fn kw_in(data: &mut &str) -> WResult<()> {
     let _ = multispace0.parse_next(data)?;
     literal(OML_CRATE_IN)
@@ -82,6 +122,17 @@ pub fn kw_in(data: &mut &str) -> WResult<()> {
     Ok(())
 }
 
+pub fn kw_except(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("except")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'except' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+
 pub fn kw_keys(data: &mut &str) -> WResult<()> {
     let _ = multispace0.parse_next(data)?;
     literal("keys")
@@ -115,6 +166,17 @@ pub fn kw_take(data: &mut &str) -> WResult<OmlKwGet> {
 }
 // 'crate' 关键字已废弃：解析层移除；若仍需兼容，请在上层引入别名解析。
 
+pub fn kw_assert(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("assert")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'assert' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+
 pub fn kw_read(data: &mut &str) -> WResult<OmlKwGet> {
     let _ = multispace0.parse_next(data)?;
     literal("read")
@@ -126,6 +188,17 @@ pub fn kw_read(data: &mut &str) -> WResult<OmlKwGet> {
     Ok(OmlKwGet::Read)
 }
 
+pub fn kw_read_capture(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("read_capture")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'read_capture' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+
 pub fn kw_crate_symbol(data: &mut &str) -> WResult<()> {
     let _ = multispace0.parse_next(data)?;
     literal("@")
@@ -188,6 +261,28 @@ pub fn kw_gw_pipe(data: &mut &str) -> WResult<()> {
     Ok(())
 }
 
+pub fn kw_coalesce(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("coalesce")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'coalesce' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+
+pub fn kw_env(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("env")
+        .context(StrContext::Label("oml keyword"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "need 'env' keyword",
+        )))
+        .parse_next(data)?;
+    Ok(())
+}
+
 pub fn kw_gw_collect(data: &mut &str) -> WResult<()> {
     let _ = multispace0.parse_next(data)?;
     literal("collect")
@@ -1,15 +1,16 @@
 use crate::language::NestedAccessor;
-use crate::language::{MatchCase, MatchCond};
+use crate::language::{CompiledRegex, MatchCase, MatchCond};
 use crate::language::{MatchCondition, MatchSource, PreciseEvaluator};
 use crate::language::{MatchFun, MatchOperation};
 use crate::parser::collect_prm::oml_aga_collect;
 use crate::parser::keyword::{kw_gw_match, kw_in};
 use crate::parser::oml_aggregate::oml_crate_calc_ref;
+use crate::parser::pipe_prm::{oml_aga_pipe, oml_aga_pipe_noprefix};
 use crate::parser::static_ctx::parse_static_value;
 use smallvec::SmallVec;
 use winnow::ascii::multispace0;
 use winnow::combinator::{alt, opt, peek, repeat};
-use winnow::error::{ContextError, StrContext, StrContextValue};
+use winnow::error::{ContextError, ErrMode, StrContext, StrContextValue};
 use winnow::stream::Stream;
 use winnow::token::take;
 use wp_parser::Parser;
@@ -29,7 +30,8 @@ use super::tdc_prm::{oml_aga_tdc, oml_aga_value};
 fn match_cond1_atom(data: &mut &str) -> WResult<MatchCond> {
     multispace0.parse_next(data)?;
     // Try cond_fun before cond_in to allow functions like in_range, in_*
-    alt((cond_neq, cond_fun, cond_in, cond_eq)).parse_next(data)
+    // cond_regex precedes cond_fun so regex patterns are compiled once at parse time
+    alt((cond_neq, cond_regex, cond_fun, cond_in, cond_eq)).parse_next(data)
 }
 
 /// Parse a match condition with OR support: `atom | atom | ...`
@@ -87,6 +89,8 @@ fn match_cond_multi_item(data: &mut &str) -> WResult<MatchCase> {
 fn match_calc_target(data: &mut &str) -> WResult<NestedAccessor> {
     symbol_match_to.parse_next(data)?;
     let gw = alt((
+        oml_aga_pipe,
+        oml_aga_pipe_noprefix,
         oml_aga_tdc,
         oml_aga_value,
         oml_aga_collect,
@@ -98,6 +102,7 @@ fn match_calc_target(data: &mut &str) -> WResult<NestedAccessor> {
     let sub_gw = match gw {
         PreciseEvaluator::Obj(x) => NestedAccessor::Field(x),
         PreciseEvaluator::Tdc(x) => NestedAccessor::Direct(x),
+        PreciseEvaluator::Pipe(x) => NestedAccessor::Pipe(x),
         PreciseEvaluator::Collect(x) => NestedAccessor::Collect(x),
         PreciseEvaluator::StaticSymbol(sym) => NestedAccessor::StaticSymbol(sym),
         _ => {
@@ -185,6 +190,49 @@ fn cond_in(data: &mut &str) -> WResult<MatchCond> {
     Ok(MatchCond::In(beg_tdo, end_tdo))
 }
 
+/// Parse `regex_match('pattern')` / `not_regex_match('pattern')` into a compiled regex.
+/// Other argument shapes fall through to `cond_fun`; an invalid pattern is a parse error.
+fn cond_regex(data: &mut &str) -> WResult<MatchCond> {
+    use winnow::token::take_while;
+
+    multispace0.parse_next(data)?;
+    let cp = data.checkpoint();
+    let fun_name: &str =
+        take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '_').parse_next(data)?;
+    let negate = match fun_name {
+        "regex_match" => false,
+        "not_regex_match" => true,
+        _ => {
+            data.reset(&cp);
+            return winnow::combinator::fail.parse_next(data);
+        }
+    };
+    multispace0.parse_next(data)?;
+    let Ok(arg_str) = get_scope(data, '(', ')') else {
+        data.reset(&cp);
+        return winnow::combinator::fail.parse_next(data);
+    };
+    let mut arg_data = arg_str.trim();
+    let pattern = match quot_str.parse_next(&mut arg_data) {
+        Ok(pattern) if arg_data.trim().is_empty() => pattern,
+        _ => {
+            data.reset(&cp);
+            return winnow::combinator::fail.parse_next(data);
+        }
+    };
+    let re = CompiledRegex::new(pattern).map_err(|_| {
+        let mut err = ContextError::new();
+        err.push(StrContext::Label("match condition"));
+        err.push(StrContext::Label("invalid regex pattern"));
+        ErrMode::Cut(err)
+    })?;
+    Ok(if negate {
+        MatchCond::RegexNeg(re)
+    } else {
+        MatchCond::Regex(re)
+    })
+}
+
 /// Parse function-based match condition like `starts_with('prefix')`
 fn cond_fun(data: &mut &str) -> WResult<MatchCond> {
     use winnow::token::take_while;
@@ -215,7 +263,9 @@ fn cond_fun(data: &mut &str) -> WResult<MatchCond> {
         "ends_with",
         "contains",
         "regex_match",
+        "not_regex_match",
         "is_empty",
+        "not_empty",
         "iequals",
         "gt",
         "lt",
@@ -832,6 +882,125 @@ Result = match read(status) {
             }
             other => panic!("Expected Fun condition for is_empty, got: {:?}", other),
         }
+
+        // Negation shortcuts
+        let mut code3 = r#"not_empty()"#;
+        match cond_fun(&mut code3) {
+            Ok(MatchCond::Fun(fun)) => {
+                assert_eq!(fun.name, "not_empty");
+                assert!(fun.args.is_empty());
+            }
+            other => panic!("Expected Fun condition for not_empty, got: {:?}", other),
+        }
+        let mut code4 = r#"not_regex_match('^\d+$')"#;
+        match cond_fun(&mut code4) {
+            Ok(MatchCond::Fun(fun)) => {
+                assert_eq!(fun.name, "not_regex_match");
+                assert_eq!(fun.args, vec![r"^\d+$".to_string()]);
+            }
+            other => panic!(
+                "Expected Fun condition for not_regex_match, got: {:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_cond_regex_parsing() {
+        let mut code = r#"regex_match('^\[ERROR\]')"#;
+        match match_cond1_atom(&mut code) {
+            Ok(MatchCond::Regex(re)) => assert_eq!(re.as_str(), r"^\[ERROR\]"),
+            other => panic!("Expected Regex condition, got: {:?}", other),
+        }
+
+        let mut code = r#"not_regex_match('^\d+$') | regex_match('^x')"#;
+        match match_cond1(&mut code) {
+            Ok(MatchCond::Or(alts)) => {
+                assert!(matches!(&alts[0], MatchCond::RegexNeg(re) if re.as_str() == r"^\d+$"));
+                assert!(matches!(&alts[1], MatchCond::Regex(re) if re.as_str() == "^x"));
+            }
+            other => panic!("Expected Or of regex conditions, got: {:?}", other),
+        }
+
+        // Unquoted / missing argument keeps the MatchFun path
+        let mut code = r#"regex_match()"#;
+        assert!(matches!(match_cond1_atom(&mut code), Ok(MatchCond::Fun(_))));
+
+        // Invalid pattern is reported at parse time
+        let mut code = r#"regex_match('[unclosed')"#;
+        assert!(match_cond1_atom(&mut code).is_err());
+    }
+
+    #[test]
+    fn test_match_with_negation_shortcuts() {
+        use crate::core::DataTransformer;
+        use crate::parser::oml_parse_raw;
+        use wp_data_model::cache::FieldQueryCache;
+        use wp_model_core::model::DataRecord;
+
+        let mut conf = r#"name : test
+---
+Status = match read(field) {
+    not_empty() => chars(filled),
+    _ => chars(blank),
+};
+Kind = match read(code) {
+    not_regex_match('^\d+$') => chars(text),
+    _ => chars(numeric),
+};
+"#;
+        let model = oml_parse_raw(&mut conf).expect("Failed to parse negation shortcuts");
+        let check = |field: &str, code: &str, status: &str, kind: &str| {
+            let cache = &mut FieldQueryCache::default();
+            let src = DataRecord::from(vec![
+                FieldStorage::from_owned(DataField::from_chars("field", field)),
+                FieldStorage::from_owned(DataField::from_chars("code", code)),
+            ]);
+            let target = model.transform(src, cache);
+            assert_eq!(
+                target.field("Status").map(|s| s.as_field().clone()),
+                Some(DataField::from_chars("Status", status))
+            );
+            assert_eq!(
+                target.field("Kind").map(|s| s.as_field().clone()),
+                Some(DataField::from_chars("Kind", kind))
+            );
+        };
+
+        check("x", "abc", "filled", "text");
+        check("", "404", "blank", "numeric");
+    }
+
+    #[test]
+    fn test_match_read_capture() {
+        use crate::core::DataTransformer;
+        use crate::parser::oml_parse_raw;
+        use wp_data_model::cache::FieldQueryCache;
+        use wp_model_core::model::DataRecord;
+
+        let mut conf = r#"name : test
+---
+log_level = match read(msg) {
+    regex_match('level=(?P<l>\w+)') => read_capture("l"),
+    _ => chars(unknown),
+};
+"#;
+        let model = oml_parse_raw(&mut conf).expect("Failed to parse read_capture");
+        assert!(format!("{}", model).contains(r#"read_capture("l")"#));
+        let check = |msg: &str, expect: &str| {
+            let cache = &mut FieldQueryCache::default();
+            let src = DataRecord::from(vec![FieldStorage::from_owned(DataField::from_chars(
+                "msg", msg,
+            ))]);
+            let target = model.transform(src, cache);
+            assert_eq!(
+                target.field("log_level").map(|s| s.as_field().clone()),
+                Some(DataField::from_chars("log_level", expect))
+            );
+        };
+
+        check("ts=1 level=warn user=a", "warn");
+        check("no level here", "unknown");
     }
 
     #[test]
@@ -1,9 +1,12 @@
+mod coalesce_prm;
 pub mod code;
 mod collect_prm;
 mod cond;
+mod env_prm;
 pub mod error;
 mod fmt_prm;
 mod fun_prm;
+mod group_prm;
 pub mod keyword;
 mod map_prm;
 mod match_prm;
@@ -14,9 +17,11 @@ mod oml_err;
 mod pipe_prm;
 //mod shm_prm;
 mod sql_prm;
+mod static_cache;
 mod static_ctx;
 mod syntax;
 mod tdc_prm;
 mod utils;
 
 pub use oml_conf::{oml_parse, oml_parse_raw};
+pub use static_cache::{oml_parse_raw_cached, static_cache_path};
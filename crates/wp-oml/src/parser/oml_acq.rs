@@ -4,6 +4,7 @@ use crate::language::PreciseEvaluator;
 use crate::language::{GenericAccessor, NestedAccessor};
 use crate::language::{SqlFnArg, SqlFnExpr};
 use crate::parser::fun_prm::oml_gw_fun;
+use crate::parser::pipe_prm::{oml_aga_pipe, oml_aga_pipe_noprefix};
 use crate::parser::static_ctx::parse_static_value;
 use winnow::ascii::multispace0;
 use winnow::combinator::{alt, opt, trace};
@@ -35,6 +36,9 @@ pub fn oml_gens_acq(data: &mut &str) -> ModalResult<GenericAccessor> {
 
 pub fn oml_sub_acq(data: &mut &str) -> ModalResult<NestedAccessor> {
     let gw = alt((
+        // 管道须先于 take/read 尝试，否则 `take(x)` 成功后遗留的 `| ...` 会被当作下一个绑定
+        trace("get pipe:", oml_aga_pipe),
+        trace("get pipe:", oml_aga_pipe_noprefix),
         trace("get take:", oml_aga_tdc),
         trace("get fun:", oml_gw_fun),
         trace("get value:", oml_aga_value),
@@ -45,6 +49,7 @@ pub fn oml_sub_acq(data: &mut &str) -> ModalResult<NestedAccessor> {
     let sub_gw = match gw {
         PreciseEvaluator::Obj(x) => NestedAccessor::Field(x),
         PreciseEvaluator::Tdc(x) => NestedAccessor::Direct(x),
+        PreciseEvaluator::Pipe(x) => NestedAccessor::Pipe(x),
         PreciseEvaluator::Fun(x) => NestedAccessor::Fun(x),
         PreciseEvaluator::StaticSymbol(sym) => NestedAccessor::StaticSymbol(sym),
         _ => {
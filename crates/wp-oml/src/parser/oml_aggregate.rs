@@ -1,21 +1,31 @@
 use crate::language::BatchEvalExpBuilder;
 use crate::language::EvalExp;
 use crate::language::EvaluationTargetBuilder;
+use crate::language::FieldCapture;
 use crate::language::FieldRead;
 use crate::language::FieldTakeBuilder;
+use crate::language::FunOperation;
+use crate::language::GenericAccessor;
 use crate::language::GenericBinding;
 use crate::language::NestedBinding;
+use crate::language::PreciseEvaluator;
 use crate::language::ReadOptionBuilder;
 use crate::language::RecordOperationBuilder;
 use crate::language::SingleEvalExpBuilder;
+use crate::language::{AssertCond, AssertExpr};
 use crate::language::{MatchSource, RecordOperation};
 
 use crate::language::DirectAccessor;
 use crate::language::{BatchEvalTarget, EvaluationTarget};
+use crate::parser::coalesce_prm::oml_aga_coalesce;
 use crate::parser::collect_prm::oml_aga_collect;
+use crate::parser::env_prm::oml_aga_env;
 use crate::parser::fmt_prm::oml_aga_fmt;
-use crate::parser::fun_prm::oml_gw_fun;
-use crate::parser::keyword::{kw_crate_symbol, kw_in, kw_keys, kw_option, kw_read, kw_take};
+use crate::parser::fun_prm::{oml_fun_item, oml_gw_fun};
+use crate::parser::keyword::{
+    kw_assert, kw_crate_symbol, kw_except, kw_in, kw_keys, kw_option, kw_read, kw_read_capture,
+    kw_take,
+};
 use crate::parser::map_prm::oml_aga_map;
 use crate::parser::match_prm::oml_aga_match;
 use crate::parser::pipe_prm; // for oml_aga_pipe_noprefix
@@ -25,12 +35,13 @@ use crate::parser::static_ctx::parse_static_value;
 use crate::parser::syntax::oml_default;
 use crate::parser::tdc_prm::{oml_aga_tdc, oml_aga_value, oml_batch_gw_get};
 use crate::parser::{oml_acq, syntax};
+use orion_exp::CmpOperator;
 use winnow::ascii::multispace0;
-use winnow::combinator::{alt, fail, peek, repeat, separated, trace};
+use winnow::combinator::{alt, fail, opt, peek, repeat, separated, trace};
 use winnow::error::StrContext;
 use winnow::error::StrContextValue;
 use winnow::stream::Stream;
-use wp_model_core::model::DataType;
+use wp_model_core::model::{DataField, DataType};
 use wp_parser::Parser;
 use wp_parser::WResult;
 use wp_parser::atom::{take_var_name, take_wild_key};
@@ -42,12 +53,23 @@ use wp_parser::symbol::{
 };
 use wp_parser::utils::{RestAble, err_convert, get_scope};
 use wpl::parser::datatype::take_datatype;
-use wpl::parser::utils::{peek_str, take_key};
+use wpl::parser::utils::{peek_str, quot_str, take_key};
 
 pub fn oml_target(data: &mut &str) -> WResult<EvaluationTarget> {
     let _ = multispace0.parse_next(data)?;
     let name_str = take_wild_key.parse_next(data)?;
     let _ = multispace0.parse_next(data)?;
+    // 通配目标可跟 `except <pat>, <pat>`；排除列表吃掉其后的逗号，因此通配目标只能单独出现
+    let exclude = if name_str.contains('*') && peek_str("except", data).is_ok() {
+        kw_except.parse_next(data)?;
+        let pats: Vec<&str> = separated(1.., take_wild_key, symbol_comma)
+            .context(ctx_desc(">> <wild> except <pat>, <pat>"))
+            .parse_next(data)?;
+        let _ = multispace0.parse_next(data)?;
+        pats.into_iter().map(str::to_string).collect()
+    } else {
+        Vec::new()
+    };
     let meta = if peek_str(":", data).is_ok() {
         symbol_colon.parse_next(data)?;
         take_datatype.parse_next(data)?
@@ -63,6 +85,7 @@ pub fn oml_target(data: &mut &str) -> WResult<EvaluationTarget> {
         EvaluationTargetBuilder::default()
             .name(target_name)
             .data_type(meta)
+            .exclude(exclude)
             .build(),
         "EvaluationTarget build failed",
     )
@@ -98,6 +121,13 @@ pub fn oml_target_vec(data: &mut &str) -> WResult<Vec<EvaluationTarget>> {
 }
 
 pub fn oml_aggregate(data: &mut &str) -> WResult<EvalExp> {
+    if is_assert_stmt(*data) {
+        return oml_assert.parse_next(data);
+    }
+    // 模型尾部的 group_by 段由 oml_conf 处理，这里回溯以结束条目解析
+    if is_group_by_stmt(*data) {
+        return fail.context(ctx_label("group_by")).parse_next(data);
+    }
     let target_vec = oml_target_vec
         .context(StrContext::Label("oml target"))
         .context(StrContext::Expected(StrContextValue::Description(
@@ -133,6 +163,8 @@ pub fn oml_aggregate(data: &mut &str) -> WResult<EvalExp> {
             "object" => oml_aga_map.parse_next(data)?,
             "pipe" => oml_aga_pipe.parse_next(data)?,
             "collect" => oml_aga_collect.parse_next(data)?,
+            "coalesce" => oml_aga_coalesce.parse_next(data)?,
+            "env" => oml_aga_env.parse_next(data)?,
             //"query" => oml_aga_shmlib.parse_next(data)?,
             "select" => oml_aga_sql.parse_next(data)?,
             "fmt" => oml_aga_fmt.parse_next(data)?,
@@ -167,6 +199,79 @@ pub fn oml_aggregate(data: &mut &str) -> WResult<EvalExp> {
     Ok(unit)
 }
 
+/// `assert` 后接空白且不是赋值/类型声明时才视为断言语句，`assert = read(..)` 仍是普通字段
+fn is_assert_stmt(data: &str) -> bool {
+    data.trim_start()
+        .strip_prefix("assert")
+        .is_some_and(|tail| {
+            tail.starts_with(char::is_whitespace) && !tail.trim_start().starts_with(['=', ':', ','])
+        })
+}
+
+fn is_group_by_stmt(data: &str) -> bool {
+    data.trim_start()
+        .strip_prefix("group_by")
+        .is_some_and(|tail| tail.trim_start().starts_with('('))
+}
+
+/// assert <left> <op> <right> [, "message"] ;
+pub fn oml_assert(data: &mut &str) -> WResult<EvalExp> {
+    kw_assert.parse_next(data)?;
+    let left = oml_assert_operand
+        .context(ctx_desc(">> assert <expr> <op> <expr> ;"))
+        .parse_next(data)?;
+    let op = oml_assert_cmp
+        .context(ctx_label("assert compare"))
+        .context(ctx_desc("== | != | > | >= | < | <="))
+        .parse_next(data)?;
+    let right = oml_assert_operand
+        .context(ctx_desc(">> assert <expr> <op> <expr> ;"))
+        .parse_next(data)?;
+    let message = if opt(symbol_comma).parse_next(data)?.is_some() {
+        multispace0.parse_next(data)?;
+        let msg = quot_str
+            .context(ctx_desc(">> assert <cond>, \"message\" ;"))
+            .parse_next(data)?;
+        Some(msg.to_string())
+    } else {
+        None
+    };
+    symbol_semicolon
+        .context(StrContext::Label("oml semicolon"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            ">> assert <cond> ;",
+        )))
+        .parse_next(data)?;
+    Ok(EvalExp::Assert(AssertExpr::new(
+        AssertCond::new(left, op, right),
+        message,
+    )))
+}
+
+fn oml_assert_operand(data: &mut &str) -> WResult<PreciseEvaluator> {
+    multispace0.parse_next(data)?;
+    let key = peek(take_key).parse_next(data)?;
+    match key {
+        "take" | "read" => alt((pipe_prm::oml_aga_pipe_noprefix, oml_aga_tdc)).parse_next(data),
+        "pipe" => oml_aga_pipe.parse_next(data),
+        _ => alt((oml_aga_value, oml_gw_fun, parse_static_value)).parse_next(data),
+    }
+}
+
+fn oml_assert_cmp(data: &mut &str) -> WResult<CmpOperator> {
+    multispace0.parse_next(data)?;
+    alt((
+        "==".value(CmpOperator::Eq),
+        "!=".value(CmpOperator::Ne),
+        ">=".value(CmpOperator::Ge),
+        "<=".value(CmpOperator::Le),
+        ">".value(CmpOperator::Gt),
+        "<".value(CmpOperator::Lt),
+        "=".value(CmpOperator::Eq),
+    ))
+    .parse_next(data)
+}
+
 pub fn oml_aggregate_sub(data: &mut &str) -> WResult<Vec<NestedBinding>> {
     let targets = oml_target_vec_same_meta.parse_next(data)?;
 
@@ -224,13 +329,52 @@ pub fn oml_crate_calc_ref(data: &mut &str) -> WResult<MatchSource> {
 
 //#[allow(clippy::manual_inspect)]
 pub fn oml_read(data: &mut &str) -> WResult<DirectAccessor> {
+    let cp = data.checkpoint();
+    let (accessor, default) = oml_read_with_default.parse_next(data)?;
+    if default.is_some() {
+        data.reset(&cp);
+        return fail
+            .context(ctx_desc(
+                "read default only allowed in assignment: <name> = read(<field>, <default>)",
+            ))
+            .parse_next(data);
+    }
+    Ok(accessor)
+}
+
+/// `read(<args>)` / `read(<field>, <default>)`：第二个参数为字段缺失时的默认值
+pub fn oml_read_with_default(data: &mut &str) -> WResult<(DirectAccessor, Option<GenericBinding>)> {
     kw_read.parse_next(data)?;
     let cp = data.checkpoint();
 
     let code = get_scope(data, '(', ')').inspect_err(|_e| {
         data.reset(&cp);
     })?;
-    let args: Vec<(String, String)> = repeat(0.., syntax::oml_args).parse_next(&mut &code[..])?;
+    let mut code_data: &str = code;
+    let mut args: Vec<(String, String)> = Vec::new();
+    let mut default = None;
+    while !code_data.trim_start().is_empty() {
+        // 首个参数之后先尝试默认值，避免 `chars(x)` 被当作字段路径
+        if !args.is_empty()
+            && let Some(d_val) = opt(oml_read_default).parse_next(&mut code_data)?
+        {
+            default = Some(d_val);
+            break;
+        }
+        match opt(syntax::oml_args).parse_next(&mut code_data)? {
+            Some(arg) => args.push(arg),
+            None => break,
+        }
+    }
+    if default.is_some() {
+        multispace0.parse_next(&mut code_data)?;
+        if !code_data.is_empty() {
+            data.reset(&cp);
+            return fail
+                .context(ctx_desc("read default must be the last argument"))
+                .parse_next(data);
+        }
+    }
 
     let mut builder = ReadOptionBuilder::default();
     for (k, v) in args {
@@ -256,7 +400,7 @@ pub fn oml_read(data: &mut &str) -> WResult<DirectAccessor> {
     }
 
     match builder.build() {
-        Ok(obj) => Ok(DirectAccessor::Read(FieldRead::from(obj))),
+        Ok(obj) => Ok((DirectAccessor::Read(FieldRead::from(obj)), default)),
         Err(_e) => {
             data.reset(&cp);
             fail.context(ctx_desc("read builder failed!"))
@@ -264,8 +408,39 @@ pub fn oml_read(data: &mut &str) -> WResult<DirectAccessor> {
         }
     }
 }
+
+/// read 默认值：字符串字面量按 `chars(...)` 处理，其余为 `<type>(<value>)` 常量或函数
+fn oml_read_default(data: &mut &str) -> WResult<GenericBinding> {
+    multispace0.parse_next(data)?;
+    let accessor = alt((
+        quot_str.map(|s: &str| GenericAccessor::Field(DataField::from_chars("", s))),
+        oml_fun_item.map(|f| GenericAccessor::Fun(FunOperation::new(f))),
+        syntax::oml_value.map(GenericAccessor::Field),
+    ))
+    .parse_next(data)?;
+    Ok(GenericBinding::new(
+        EvaluationTarget::auto_default(),
+        accessor,
+    ))
+}
+
 pub fn oml_var_get_std(data: &mut &str) -> WResult<DirectAccessor> {
-    alt((oml_take, oml_read)).parse_next(data)
+    // read_capture 须先于 read 尝试，二者前缀相同
+    alt((oml_read_capture, oml_take, oml_read)).parse_next(data)
+}
+
+/// `read_capture("name")`：读取 match 分支中 `regex_match` 的命名捕获
+pub fn oml_read_capture(data: &mut &str) -> WResult<DirectAccessor> {
+    kw_read_capture.parse_next(data)?;
+    (multispace0, "(", multispace0).parse_next(data)?;
+    let name = quot_str
+        .context(StrContext::Label("read_capture"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            ">> read_capture(\"<name>\")",
+        )))
+        .parse_next(data)?;
+    (multispace0, ")").parse_next(data)?;
+    Ok(DirectAccessor::Capture(FieldCapture::new(name)))
 }
 
 //#[allow(clippy::manual_inspect)]
@@ -327,10 +502,17 @@ pub fn oml_var_get_ref(data: &mut &str) -> WResult<DirectAccessor> {
 
 pub fn oml_tdo_get(data: &mut &str) -> WResult<RecordOperation> {
     let mut builder = RecordOperationBuilder::default();
-    let x = oml_var_get.parse_next(data)?;
+    let (x, inline_default) = alt((
+        oml_read_capture.map(|x| (x, None)),
+        oml_read_with_default,
+        oml_var_get.map(|x| (x, None)),
+    ))
+    .parse_next(data)?;
     builder.dat_get(x);
     multispace0.parse_next(data)?;
-    if let Ok(c) = wp_parser::utils::peek_one.parse_next(data) {
+    if inline_default.is_some() {
+        builder.default_val(inline_default);
+    } else if let Ok(c) = wp_parser::utils::peek_one.parse_next(data) {
         if c == "{" {
             let o = oml_default_body.parse_next(data)?;
             builder.default_val(Some(o));
@@ -368,12 +550,14 @@ pub fn oml_args_in(data: &mut &str) -> WResult<String> {
 #[cfg(test)]
 mod tests {
 
+    use orion_exp::CmpOperator;
     use winnow::{ModalResult, Parser};
 
-    use crate::language::PreciseEvaluator;
-    use crate::parser::oml_aggregate::oml_target;
+    use crate::language::{EvalExp, PreciseEvaluator};
+    use crate::parser::oml_aggregate::{oml_aggregate, oml_target};
     use crate::parser::tdc_prm::oml_aga_tdc;
     use crate::parser::utils::for_test::fmt_assert_eq;
+    use wp_model_core::model::DataType;
 
     #[test]
     fn test_oml_crate_removed() -> ModalResult<()> {
@@ -394,6 +578,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_oml_assert() -> ModalResult<()> {
+        let mut code = r#" assert read(status) == chars(active) ; "#;
+        match oml_aggregate.parse_next(&mut code)? {
+            EvalExp::Assert(x) => {
+                assert_eq!(*x.cond().op(), CmpOperator::Eq);
+                assert!(x.message().is_none());
+                assert!(x.to_string().starts_with("assert "));
+            }
+            other => panic!("expected assert, got {}", other),
+        }
+
+        let mut code = r#" assert read(items) | length > digit(0), "items must not be empty" ; "#;
+        match oml_aggregate.parse_next(&mut code)? {
+            EvalExp::Assert(x) => {
+                assert_eq!(*x.cond().op(), CmpOperator::Gt);
+                assert_eq!(x.message().as_deref(), Some("items must not be empty"));
+                assert!(x.to_string().contains("\"items must not be empty\""));
+            }
+            other => panic!("expected assert, got {}", other),
+        }
+
+        // 字段名为 assert 时仍按普通赋值解析
+        let mut code = r#" assert = read(flag) ; "#;
+        assert!(matches!(
+            oml_aggregate.parse_next(&mut code)?,
+            EvalExp::Single(_)
+        ));
+
+        let mut code = r#" assert read(status) chars(active) ; "#;
+        assert!(oml_aggregate.parse_next(&mut code).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_oml_target_item() -> ModalResult<()> {
         let mut code = r#" src : ip "#;
@@ -417,6 +635,34 @@ mod tests {
         Ok(())
     }
     #[test]
+    fn test_oml_target_except() -> ModalResult<()> {
+        let mut code = r#" */path except A*/path, B1/path : chars "#;
+        let x = oml_target.parse_next(&mut code)?;
+        assert_eq!(x.name().as_deref(), Some("*/path"));
+        assert_eq!(
+            x.exclude(),
+            &vec!["A*/path".to_string(), "B1/path".to_string()]
+        );
+        assert_eq!(x.data_type(), &DataType::Chars);
+        assert_eq!(x.to_string(), "*/path except A*/path, B1/path : chars ");
+
+        let mut code = r#" */path except A*/path = take() ; "#;
+        let x = oml_aggregate.parse_next(&mut code)?;
+        let EvalExp::Batch(batch) = x else {
+            panic!("expect batch target");
+        };
+        assert_eq!(
+            batch.target().origin().exclude(),
+            &vec!["A*/path".to_string()]
+        );
+
+        // 非通配目标不识别 except
+        let mut code = r#" path except A*/path "#;
+        let x = oml_target.parse_next(&mut code)?;
+        assert!(x.exclude().is_empty());
+        Ok(())
+    }
+    #[test]
     fn test_oml_target() -> ModalResult<()> {
         let mut code = r#" src : ip  = query lib(geo) where take ( src_ip)  {
             idx : geo , col : domain , _ : chars(office)
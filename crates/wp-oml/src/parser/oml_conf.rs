@@ -2,12 +2,15 @@ use crate::core::DataRecordRef;
 use crate::core::ExpEvaluator;
 use crate::language::{EvalExp, ObjModel, PreciseEvaluator};
 use crate::parser::error::OMLCodeErrorTait;
-use crate::parser::keyword::{kw_head_sep_line, kw_oml_enable, kw_oml_name, kw_static};
+use crate::parser::group_prm::oml_group_by;
+use crate::parser::keyword::{
+    kw_head_sep_line, kw_oml_debug, kw_oml_enable, kw_oml_name, kw_oml_timeout, kw_static,
+};
 use crate::parser::oml_aggregate::oml_aggregate;
-use crate::parser::static_ctx::{clear_symbols, install_symbols};
+use crate::parser::static_ctx::{StaticScope, clear_symbols, install_symbols, take_preloaded};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use winnow::ascii::multispace0;
+use winnow::ascii::{digit1, multispace0};
 use winnow::combinator::repeat;
 use winnow::error::{ContextError, ErrMode, StrContext, StrContextValue};
 use winnow::stream::Stream;
@@ -27,18 +30,21 @@ pub fn oml_parse_raw(data: &mut &str) -> WResult<ObjModel> {
     oml_conf_code.parse_next(data)
 }
 pub fn oml_parse(data: &mut &str, tag: &str) -> OMLCodeResult<ObjModel> {
+    let code = *data;
     match oml_conf_code.parse_next(data) {
         Ok(o) => Ok(o),
-        Err(e) => Err(OMLCodeError::from_syntax(e, data, tag)),
+        Err(e) => Err(OMLCodeError::from_syntax_at(e, code, data, tag)),
     }
 }
 
 pub fn oml_conf_code(data: &mut &str) -> WResult<ObjModel> {
+    // static 符号仅在当前文件内可见；解析失败时也不会泄漏给下一个模型
+    let _static_scope = StaticScope::enter();
     let name = oml_conf_head.parse_next(data)?;
     debug_rule!("obj model: {} begin ", name);
     let mut a_items = ObjModel::new(name);
 
-    // Parse optional config items (enable and rule) in any order
+    // Parse optional config items (enable, debug, timeout_ms and rule) in any order
     loop {
         multispace0.parse_next(data)?;
         let ck = data.checkpoint();
@@ -50,6 +56,22 @@ pub fn oml_conf_code(data: &mut &str) -> WResult<ObjModel> {
             continue;
         }
         data.reset(&ck);
+        if oml_conf_debug.parse_next(data).is_ok_and(|on| {
+            a_items.set_debug(on);
+            true
+        }) {
+            continue;
+        }
+        data.reset(&ck);
+        // timeout_ms 关键字已匹配但取值非法（如 0）时直接报错，不当作其他配置项继续尝试
+        match oml_conf_timeout.parse_next(data) {
+            Ok(ms) => {
+                a_items.set_timeout_ms(ms);
+                continue;
+            }
+            Err(e @ ErrMode::Cut(_)) => return Err(e),
+            Err(_) => data.reset(&ck),
+        }
         // Try to parse rules
         if oml_conf_rules.parse_next(data).is_ok_and(|rules| {
             a_items.bind_rules(Some(rules));
@@ -70,6 +92,11 @@ pub fn oml_conf_code(data: &mut &str) -> WResult<ObjModel> {
     debug_rule!("obj model: aggregate item  loaded!");
     //repeat(1.., terminated(oml_aggregate, symbol_semicolon)).parse_next(data)?;
     a_items.items.append(&mut items);
+    multispace0.parse_next(data)?;
+    if peek_str("group_by", data).is_ok() {
+        let op = oml_group_by.parse_next(data)?;
+        a_items.set_group_by(op);
+    }
     clear_symbols();
 
     finalize_static_blocks(&mut a_items, static_items)?;
@@ -104,6 +131,7 @@ fn check_temp_fields(items: &[EvalExp]) -> bool {
                     return true;
                 }
             }
+            EvalExp::Assert(_) => {}
         }
     }
     false
@@ -181,7 +209,7 @@ fn extract_static_target(exp: &EvalExp) -> Result<String, ErrMode<ContextError>>
                 Err(ErrMode::Cut(err))
             }
         }
-        EvalExp::Batch(_) => {
+        EvalExp::Batch(_) | EvalExp::Assert(_) => {
             let mut err = ContextError::new();
             err.push(StrContext::Label("static assignment"));
             err.push(StrContext::Expected(StrContextValue::Description(
@@ -201,12 +229,41 @@ fn finalize_static_blocks(
         return Ok(());
     }
 
-    let const_fields = materialize_static_items(&static_items)?;
+    model.set_static_cacheable(static_items.iter().all(static_item_is_literal));
+    let const_fields = match take_preloaded() {
+        Some(fields) => fields,
+        None => materialize_static_items(&static_items)?,
+    };
     rewrite_static_references(model, &const_fields)?;
     model.set_static_fields(const_fields);
     Ok(())
 }
 
+/// static 项的求值结果是否只取决于 OML 文本。知识库查询、内置函数（时间、主机名等）
+/// 与 `env(...)` 读取外部输入；match/map/pipe 等嵌套结构可能间接引用它们，一并保守排除
+fn static_item_is_literal(exp: &EvalExp) -> bool {
+    match exp {
+        EvalExp::Single(single) => evaluator_is_literal(single.eval_way()),
+        _ => false,
+    }
+}
+
+fn evaluator_is_literal(eval: &PreciseEvaluator) -> bool {
+    match eval {
+        PreciseEvaluator::Val(_) | PreciseEvaluator::Obj(_) | PreciseEvaluator::ObjArc(_) => true,
+        PreciseEvaluator::Tdc(op) => record_default_is_literal(op),
+        PreciseEvaluator::Fmt(op) => op.subs().iter().all(record_default_is_literal),
+        PreciseEvaluator::Coalesce(args) => args.iter().all(evaluator_is_literal),
+        _ => false,
+    }
+}
+
+fn record_default_is_literal(op: &crate::language::RecordOperation) -> bool {
+    op.default_val()
+        .as_ref()
+        .is_none_or(|d| !matches!(d.accessor(), crate::language::GenericAccessor::Fun(_)))
+}
+
 fn materialize_static_items(
     items: &[EvalExp],
 ) -> Result<HashMap<String, Arc<DataField>>, ErrMode<ContextError>> {
@@ -231,8 +288,16 @@ fn rewrite_static_references(
     const_fields: &HashMap<String, Arc<DataField>>,
 ) -> Result<(), ErrMode<ContextError>> {
     for item in &mut model.items {
-        if let EvalExp::Single(single) = item {
-            rewrite_precise_evaluator(single.eval_way_mut(), const_fields)?;
+        match item {
+            EvalExp::Single(single) => {
+                rewrite_precise_evaluator(single.eval_way_mut(), const_fields)?;
+            }
+            EvalExp::Assert(assert) => {
+                let cond = assert.cond_mut();
+                rewrite_precise_evaluator(cond.left_mut(), const_fields)?;
+                rewrite_precise_evaluator(cond.right_mut(), const_fields)?;
+            }
+            EvalExp::Batch(_) => {}
         }
     }
     Ok(())
@@ -260,6 +325,12 @@ fn rewrite_precise_evaluator(
         PreciseEvaluator::Map(map) => rewrite_map_operation(map, const_fields),
         PreciseEvaluator::Tdc(op) => rewrite_record_operation(op, const_fields),
         PreciseEvaluator::Collect(arr) => rewrite_arr_operation(arr, const_fields),
+        PreciseEvaluator::Coalesce(args) => {
+            for arg in args.iter_mut() {
+                rewrite_precise_evaluator(arg, const_fields)?;
+            }
+            Ok(())
+        }
         _ => Ok(()),
     }
 }
@@ -458,7 +529,11 @@ fn oml_rule_path<'a>(input: &mut &'a str) -> WResult<&'a str> {
     multispace0.parse_next(input)?;
     // Check if it's a reserved keyword before parsing
     let trimmed = input.trim_start();
-    if trimmed.starts_with("enable") || trimmed.starts_with("---") {
+    if trimmed.starts_with("enable")
+        || trimmed.starts_with("debug")
+        || trimmed.starts_with("timeout_ms")
+        || trimmed.starts_with("---")
+    {
         // Return backtrack error to stop repeat
         return Err(winnow::error::ErrMode::Backtrack(ContextError::new()));
     }
@@ -471,15 +546,26 @@ fn oml_rule_path<'a>(input: &mut &'a str) -> WResult<&'a str> {
 pub fn oml_conf_enable(data: &mut &str) -> WResult<bool> {
     multispace0.parse_next(data)?;
     let (_, _) = (kw_oml_enable, symbol_colon).parse_next(data)?;
+    oml_conf_bool(data, "enable value")
+}
+
+/// 求值轨迹开关：`debug : true`
+pub fn oml_conf_debug(data: &mut &str) -> WResult<bool> {
+    multispace0.parse_next(data)?;
+    let (_, _) = (kw_oml_debug, symbol_colon).parse_next(data)?;
+    oml_conf_bool(data, "debug value")
+}
+
+fn oml_conf_bool(data: &mut &str, label: &'static str) -> WResult<bool> {
     let value: &str = take_var_name
-        .context(StrContext::Label("enable value"))
+        .context(StrContext::Label(label))
         .parse_next(data)?;
     match value {
         "true" => Ok(true),
         "false" => Ok(false),
         _ => {
             let mut err = ContextError::new();
-            err.push(StrContext::Label("enable value"));
+            err.push(StrContext::Label(label));
             err.push(StrContext::Expected(StrContextValue::Description(
                 "true or false",
             )));
@@ -488,6 +574,27 @@ pub fn oml_conf_enable(data: &mut &str) -> WResult<bool> {
     }
 }
 
+/// 模型级转换超时：`timeout_ms : 500`，须为正整数
+pub fn oml_conf_timeout(data: &mut &str) -> WResult<u64> {
+    multispace0.parse_next(data)?;
+    let (_, _) = (kw_oml_timeout, symbol_colon).parse_next(data)?;
+    multispace0.parse_next(data)?;
+    let value: &str = digit1
+        .context(StrContext::Label("timeout_ms value"))
+        .parse_next(data)?;
+    match value.parse::<u64>() {
+        Ok(ms) if ms > 0 => Ok(ms),
+        _ => {
+            let mut err = ContextError::new();
+            err.push(StrContext::Label("timeout_ms value"));
+            err.push(StrContext::Expected(StrContextValue::Description(
+                "positive milliseconds",
+            )));
+            Err(ErrMode::Cut(err))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::oml_conf::oml_parse_raw;
@@ -579,6 +686,59 @@ version2 = take(ip) | to_str | base64_encode ;
         Ok(())
     }
 
+    #[test]
+    fn test_conf_pipe_optional_keyword_nested() -> ModalResult<()> {
+        use crate::language::{EvalExp, NestedAccessor, PreciseEvaluator};
+        use orion_error::TestAssert;
+
+        let mut code = r#"
+name : test
+---
+info = object {
+    host : chars = take(host) | to_str ;
+    port = read(option:[dport, port]) | to_str | base64_encode ;
+    path = pipe take(url) | path(name) ;
+    raw = take(raw) ;
+} ;
+level = match read(lv) {
+    chars(a) | chars(b) => take(msg) | to_str ,
+    _ => take(msg) ,
+} ;
+        "#;
+        let model = oml_parse_raw(&mut code).assert();
+        assert_eq!(model.items.len(), 2);
+
+        let EvalExp::Single(single) = &model.items[0] else {
+            panic!("expected single evaluator");
+        };
+        let PreciseEvaluator::Map(map) = single.eval_way() else {
+            panic!("expected object evaluator");
+        };
+        let kinds: Vec<bool> = map
+            .subs()
+            .iter()
+            .map(|b| matches!(b.acquirer(), NestedAccessor::Pipe(_)))
+            .collect();
+        assert_eq!(kinds, vec![true, true, true, false]);
+        match map.subs()[1].acquirer() {
+            NestedAccessor::Pipe(pipe) => assert_eq!(pipe.items().len(), 2),
+            other => panic!("expected pipe accessor, got {:?}", other),
+        }
+
+        let EvalExp::Single(single) = &model.items[1] else {
+            panic!("expected single evaluator");
+        };
+        let PreciseEvaluator::Match(op) = single.eval_way() else {
+            panic!("expected match evaluator");
+        };
+        assert!(matches!(op.items()[0].result(), NestedAccessor::Pipe(_)));
+        assert!(matches!(
+            op.default().as_ref().map(|d| d.result()),
+            Some(NestedAccessor::Direct(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_static_block_parsing() -> ModalResult<()> {
         use crate::language::{EvalExp, PreciseEvaluator};
@@ -1059,6 +1219,39 @@ field = chars(value);
         Ok(())
     }
 
+    #[test]
+    fn test_debug_config() -> ModalResult<()> {
+        use orion_error::TestAssert;
+
+        let mut code = r#"
+name : test
+rule : /a/b
+debug : true
+enable : true
+---
+field = chars(value);
+        "#;
+        let model = oml_parse_raw(&mut code).assert();
+        assert!(*model.debug());
+        assert_eq!(model.rules().as_ref().len(), 1);
+
+        let mut code = r#"
+name : test
+---
+field = chars(value);
+        "#;
+        assert!(!*oml_parse_raw(&mut code).assert().debug());
+
+        let mut code = r#"
+name : test
+debug : yes
+---
+field = chars(value);
+        "#;
+        assert!(oml_parse_raw(&mut code).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_enable_config_with_rule() -> ModalResult<()> {
         use orion_error::TestAssert;
@@ -1077,6 +1270,43 @@ field = chars(value);
         Ok(())
     }
 
+    #[test]
+    fn test_timeout_config() -> ModalResult<()> {
+        use crate::parser::oml_conf::oml_conf_timeout;
+        use orion_error::TestAssert;
+
+        let mut code = r#"
+name : test
+rule : /test/*
+timeout_ms : 500
+enable : true
+---
+field = chars(value);
+        "#;
+        let model = oml_parse_raw(&mut code).assert();
+        assert_eq!(*model.timeout_ms(), Some(500));
+        assert_eq!(model.rules().as_ref().len(), 1);
+
+        let mut code = r#"
+name : test
+---
+field = chars(value);
+        "#;
+        assert_eq!(*oml_parse_raw(&mut code).assert().timeout_ms(), None);
+
+        let mut code = r#"
+name : test
+timeout_ms : 0
+---
+field = chars(value);
+        "#;
+        assert!(oml_parse_raw(&mut code).is_err());
+
+        assert!(oml_conf_timeout(&mut "timeout_ms : 0 ").is_err());
+        assert!(oml_conf_timeout(&mut "timeout_ms : fast ").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_enable_explicit() -> ModalResult<()> {
         use crate::parser::oml_conf::oml_conf_enable;
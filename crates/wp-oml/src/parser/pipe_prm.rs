@@ -1,31 +1,39 @@
+use smol_str::SmolStr;
 use std::str::FromStr;
 
 use crate::language::{
-    Base64Decode, EncodeType, Get, HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape, JsonUnescape,
-    MapTo, MapValue, Nth, PIPE_BASE64_DECODE, PIPE_GET, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE,
-    PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE, PIPE_JSON_UNESCAPE, PIPE_MAP_TO, PIPE_NTH, PIPE_PATH,
-    PIPE_SKIP_EMPTY, PIPE_STARTS_WITH, PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS,
-    PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_JSON, PIPE_URL, PathGet, PathType,
-    PiPeOperation, PipeFun, PreciseEvaluator, SkipEmpty, StartsWith, StrEscape, TimeStampUnit,
-    TimeToTs, TimeToTsMs, TimeToTsUs, TimeToTsZone, ToJson, UrlGet, UrlType,
+    Ascii85Decode, Ascii85Encode, Base64Encode, ExtractMainWord, ExtractSubjectObject,
+    PIPE_ASCII85_DECODE, PIPE_ASCII85_ENCODE, PIPE_BASE64_ENCODE, PIPE_EXTRACT_MAIN_WORD,
+    PIPE_EXTRACT_SUBJECT_OBJECT, PIPE_SLICE, PIPE_SLICE_LAST, PIPE_TO_BOOL, PIPE_TO_STR, Slice,
+    ToBoolean, ToStr,
 };
 use crate::language::{
-    Base64Encode, ExtractMainWord, ExtractSubjectObject, PIPE_BASE64_ENCODE,
-    PIPE_EXTRACT_MAIN_WORD, PIPE_EXTRACT_SUBJECT_OBJECT, PIPE_TO_STR, ToStr,
+    Base64Decode, EncodeType, Get, HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape, JsonPath,
+    JsonUnescape, Length, MapTo, MapValue, Nth, PIPE_BASE64_DECODE, PIPE_GET, PIPE_HTML_ESCAPE,
+    PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE, PIPE_JSON_PATH, PIPE_JSON_UNESCAPE,
+    PIPE_LENGTH, PIPE_MAP_TO, PIPE_NTH, PIPE_PATH, PIPE_SKIP_EMPTY, PIPE_STARTS_WITH,
+    PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS, PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE,
+    PIPE_TO_JSON, PIPE_UNIQUE, PIPE_URL, PathGet, PathType, PiPeOperation, PipeFun,
+    PreciseEvaluator, SkipEmpty, StartsWith, StrEscape, TimeStampUnit, TimeToTs, TimeToTsMs,
+    TimeToTsUs, TimeToTsZone, ToJson, URL_QUERY_ALL, Unique, UrlGet, UrlType,
 };
+use crate::language::{CountOccurrences, PIPE_COUNT_OCCURRENCES};
+use crate::language::{KeyTransform, MapKeys, MapValues, PIPE_MAP_KEYS, PIPE_MAP_VALUES};
 use crate::parser::keyword::kw_gw_pipe;
 use crate::parser::oml_aggregate::oml_var_get;
 use crate::winnow::error::ParserError;
 use winnow::ascii::{alphanumeric0, digit1, multispace0};
-use winnow::combinator::{alt, fail, opt, repeat};
+use winnow::combinator::{alt, fail, opt, repeat, separated};
 use winnow::error::{ContextError, ErrMode, StrContext};
 use winnow::stream::Stream; // for checkpoint/reset on &str
-use winnow::token::take;
+use winnow::token::{take, take_while};
 use wp_parser::Parser;
 use wp_parser::WResult;
 use wp_parser::fun::fun_trait::{Fun1Builder, Fun2Builder};
 use wp_parser::fun::parser;
-use wp_parser::symbol::{ctx_desc, symbol_pipe};
+use wp_parser::symbol::{
+    ctx_desc, symbol_bracket_beg, symbol_bracket_end, symbol_comma, symbol_pipe,
+};
 use wpl::parser::utils::take_key;
 
 impl Fun1Builder for Nth {
@@ -45,6 +53,146 @@ impl Fun1Builder for Nth {
         Nth { index: args }
     }
 }
+
+/// 有符号索引，如 `3`、`-3`
+fn signed_index(data: &mut &str) -> WResult<isize> {
+    multispace0.parse_next(data)?;
+    let sign = opt("-").parse_next(data)?;
+    let digits = digit1.parse_next(data)?;
+    let i = digits
+        .parse::<isize>()
+        .map_err(|_| ErrMode::<ContextError>::from_input(data))?;
+    Ok(if sign.is_some() { -i } else { i })
+}
+
+impl Fun1Builder for Slice {
+    type ARG1 = isize;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        signed_index(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_SLICE
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Slice {
+            start: args,
+            end: None,
+        }
+    }
+}
+
+impl Fun2Builder for Slice {
+    type ARG1 = isize;
+    type ARG2 = isize;
+    fn fun_name() -> &'static str {
+        PIPE_SLICE
+    }
+    fn args1(data: &mut &str) -> WResult<isize> {
+        signed_index(data)
+    }
+    fn args2(data: &mut &str) -> WResult<isize> {
+        signed_index(data)
+    }
+    fn build(args: (isize, isize)) -> Slice {
+        Slice {
+            start: args.0,
+            end: Some(args.1),
+        }
+    }
+}
+
+/// `slice_last(n)`：取末尾 n 个元素
+fn pipe_slice_last(data: &mut &str) -> WResult<Slice> {
+    PIPE_SLICE_LAST.parse_next(data)?;
+    (multispace0, symbol_bracket_beg, multispace0).parse_next(data)?;
+    let n: usize = digit1
+        .parse_to()
+        .context(StrContext::Label("slice_last count"))
+        .parse_next(data)?;
+    (multispace0, symbol_bracket_end).parse_next(data)?;
+    Ok(Slice {
+        start: -(n as isize),
+        // slice(-0) 即 slice(0)，会取整个数组
+        end: (n == 0).then_some(0),
+    })
+}
+
+/// `extract_subject_object` 的可选参数
+enum SubjectObjectArg {
+    Flat,
+    Debug,
+    Prefix(String),
+    Rename(Vec<(String, String)>),
+}
+
+/// `extract_subject_object` 或 `extract_subject_object(flat, prefix:'evt_', rename:[subject:who], debug)`
+fn pipe_extract_subject_object(data: &mut &str) -> WResult<ExtractSubjectObject> {
+    PIPE_EXTRACT_SUBJECT_OBJECT.parse_next(data)?;
+    let mut fun = ExtractSubjectObject::default();
+    if opt((multispace0, symbol_bracket_beg))
+        .parse_next(data)?
+        .is_none()
+    {
+        return Ok(fun);
+    }
+    let args: Vec<SubjectObjectArg> = separated(1.., subject_object_arg, symbol_comma)
+        .context(StrContext::Label("extract_subject_object args"))
+        .context(ctx_desc(
+            "flat | debug | prefix:'<str>' | rename:[<key>:<name>, ...]",
+        ))
+        .parse_next(data)?;
+    (multispace0, symbol_bracket_end).parse_next(data)?;
+    for arg in args {
+        match arg {
+            SubjectObjectArg::Flat => fun.flat = true,
+            SubjectObjectArg::Debug => fun.debug = true,
+            SubjectObjectArg::Prefix(p) => fun.prefix = p,
+            SubjectObjectArg::Rename(pairs) => fun.rename.extend(pairs),
+        }
+    }
+    Ok(fun)
+}
+
+fn subject_object_arg(data: &mut &str) -> WResult<SubjectObjectArg> {
+    use wpl::parser::utils::quot_str;
+    multispace0.parse_next(data)?;
+    alt((
+        ("prefix", multispace0, ":", multispace0, quot_str)
+            .map(|(_, _, _, _, p)| SubjectObjectArg::Prefix(p.to_string())),
+        (
+            "rename",
+            multispace0,
+            ":",
+            multispace0,
+            "[",
+            separated(1.., subject_object_rename, symbol_comma),
+            multispace0,
+            "]",
+        )
+            .map(|(_, _, _, _, _, pairs, _, _)| SubjectObjectArg::Rename(pairs)),
+        "flat".map(|_| SubjectObjectArg::Flat),
+        "debug".map(|_| SubjectObjectArg::Debug),
+    ))
+    .parse_next(data)
+}
+
+fn subject_object_rename(data: &mut &str) -> WResult<(String, String)> {
+    multispace0.parse_next(data)?;
+    let key = take_key.parse_next(data)?;
+    if !ExtractSubjectObject::KEYS.contains(&key) {
+        return fail
+            .context(StrContext::Label("rename key"))
+            .context(ctx_desc("subject | action | object | status | debug"))
+            .parse_next(data);
+    }
+    (multispace0, ":", multispace0).parse_next(data)?;
+    let name = take_key.parse_next(data)?;
+    multispace0.parse_next(data)?;
+    Ok((key.to_string(), name.to_string()))
+}
+
 impl Fun2Builder for TimeToTsZone {
     type ARG1 = i32;
     type ARG2 = TimeStampUnit;
@@ -110,6 +258,24 @@ impl Fun1Builder for StartsWith {
     }
 }
 
+impl Fun1Builder for CountOccurrences {
+    type ARG1 = SmolStr;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        use wpl::parser::utils::quot_str;
+        multispace0.parse_next(data)?;
+        let pattern = quot_str.parse_next(data)?;
+        Ok(SmolStr::from(pattern))
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_COUNT_OCCURRENCES
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        CountOccurrences { pattern: args }
+    }
+}
+
 impl Fun1Builder for MapTo {
     type ARG1 = MapValue;
     fn args1(data: &mut &str) -> WResult<Self::ARG1> {
@@ -254,12 +420,18 @@ impl Fun1Builder for UrlGet {
     type ARG1 = UrlType;
     fn args1(data: &mut &str) -> WResult<Self::ARG1> {
         multispace0.parse_next(data)?;
-        let val: &str = alphanumeric0::<&str, ErrMode<ContextError>>
-            .parse_next(data)
-            .unwrap();
+        let val: &str =
+            take_while(0.., |c: char| c.is_ascii_alphanumeric() || c == '_').parse_next(data)?;
 
         if val.is_empty() {
             Ok(UrlType::Default)
+        } else if val == URL_QUERY_ALL {
+            // query_all:key
+            (multispace0, ':', multispace0).parse_next(data)?;
+            let key = take_key
+                .context(ctx_desc("query_all:<key>"))
+                .parse_next(data)?;
+            Ok(UrlType::QueryAll(key.into()))
         } else {
             Ok(UrlType::from_str(val).map_err(|e| {
                 warn_rule!("invalid url arg '{}': {}", val, e);
@@ -276,6 +448,66 @@ impl Fun1Builder for UrlGet {
         UrlGet { key: args }
     }
 }
+impl Fun1Builder for MapKeys {
+    type ARG1 = KeyTransform;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        multispace0.parse_next(data)?;
+        let val: &str =
+            take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '_').parse_next(data)?;
+        KeyTransform::from_str(val).map_err(|e| {
+            warn_rule!("invalid map_keys arg '{}': {}", val, e);
+            ErrMode::<ContextError>::from_input(data)
+        })
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_MAP_KEYS
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        MapKeys { transform: args }
+    }
+}
+
+impl Fun1Builder for MapValues {
+    type ARG1 = PipeFun;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        multispace0.parse_next(data)?;
+        oml_pipe_fun.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_MAP_VALUES
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        MapValues {
+            inner: Box::new(args),
+        }
+    }
+}
+
+impl Fun1Builder for JsonPath {
+    type ARG1 = JsonPath;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        use wpl::parser::utils::quot_str;
+        multispace0.parse_next(data)?;
+        let path = quot_str.parse_next(data)?;
+        // 模型加载时即编译，非法表达式作为语法错误上报
+        JsonPath::compile(path).map_err(|e| {
+            warn_rule!("invalid json path '{}': {}", path, e);
+            ErrMode::<ContextError>::from_input(data)
+        })
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_JSON_PATH
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        args
+    }
+}
 pub fn oml_aga_pipe(data: &mut &str) -> WResult<PreciseEvaluator> {
     kw_gw_pipe.parse_next(data)?;
     let from = oml_var_get.parse_next(data)?;
@@ -299,16 +531,28 @@ pub fn oml_aga_pipe_noprefix(data: &mut &str) -> WResult<PreciseEvaluator> {
 pub fn oml_pipe(data: &mut &str) -> WResult<PipeFun> {
     symbol_pipe.parse_next(data)?;
     multispace0.parse_next(data)?;
+    oml_pipe_fun.parse_next(data)
+}
+
+/// 单个管道函数（不含前导 `|`），亦用作 `map_values(...)` 的内层函数
+fn oml_pipe_fun(data: &mut &str) -> WResult<PipeFun> {
     let fun = alt((
         alt((
             parser::call_fun_args2::<TimeToTsZone>.map(PipeFun::TimeToTsZone),
             parser::call_fun_args1::<Nth>.map(PipeFun::Nth),
+            pipe_slice_last.map(PipeFun::Slice),
+            parser::call_fun_args2::<Slice>.map(PipeFun::Slice),
+            parser::call_fun_args1::<Slice>.map(PipeFun::Slice),
             parser::call_fun_args1::<Get>.map(PipeFun::Get),
             parser::call_fun_args1::<StartsWith>.map(PipeFun::StartsWith),
+            parser::call_fun_args1::<CountOccurrences>.map(PipeFun::CountOccurrences),
             parser::call_fun_args1::<MapTo>.map(PipeFun::MapTo),
             parser::call_fun_args1::<Base64Decode>.map(PipeFun::Base64Decode),
             parser::call_fun_args1::<PathGet>.map(PipeFun::PathGet),
             parser::call_fun_args1::<UrlGet>.map(PipeFun::UrlGet),
+            parser::call_fun_args1::<JsonPath>.map(PipeFun::JsonPath),
+            parser::call_fun_args1::<MapKeys>.map(PipeFun::MapKeys),
+            parser::call_fun_args1::<MapValues>.map(PipeFun::MapValues),
         )),
         alt((
             PIPE_HTML_ESCAPE.map(|_| PipeFun::HtmlEscape(HtmlEscape::default())),
@@ -317,16 +561,20 @@ pub fn oml_pipe(data: &mut &str) -> WResult<PipeFun> {
             PIPE_JSON_ESCAPE.map(|_| PipeFun::JsonEscape(JsonEscape::default())),
             PIPE_JSON_UNESCAPE.map(|_| PipeFun::JsonUnescape(JsonUnescape::default())),
             PIPE_BASE64_ENCODE.map(|_| PipeFun::Base64Encode(Base64Encode::default())),
+            PIPE_ASCII85_ENCODE.map(|_| PipeFun::Ascii85Encode(Ascii85Encode::default())),
+            PIPE_ASCII85_DECODE.map(|_| PipeFun::Ascii85Decode(Ascii85Decode::default())),
             PIPE_TIME_TO_TS_MS.map(|_| PipeFun::TimeToTsMs(TimeToTsMs::default())),
             PIPE_TIME_TO_TS_US.map(|_| PipeFun::TimeToTsUs(TimeToTsUs::default())),
             PIPE_TIME_TO_TS.map(|_| PipeFun::TimeToTs(TimeToTs::default())),
             PIPE_TO_JSON.map(|_| PipeFun::ToJson(ToJson::default())),
             PIPE_TO_STR.map(|_| PipeFun::ToStr(ToStr::default())),
+            PIPE_TO_BOOL.map(|_| PipeFun::ToBoolean(ToBoolean::default())),
             PIPE_SKIP_EMPTY.map(|_| PipeFun::SkipEmpty(SkipEmpty::default())),
+            PIPE_LENGTH.map(|_| PipeFun::Length(Length::default())),
+            PIPE_UNIQUE.map(|_| PipeFun::Unique(Unique::default())),
             PIPE_IP4_TO_INT.map(|_| PipeFun::Ip4ToInt(Ip4ToInt::default())),
             PIPE_EXTRACT_MAIN_WORD.map(|_| PipeFun::ExtractMainWord(ExtractMainWord::default())),
-            PIPE_EXTRACT_SUBJECT_OBJECT
-                .map(|_| PipeFun::ExtractSubjectObject(ExtractSubjectObject::default())),
+            pipe_extract_subject_object.map(PipeFun::ExtractSubjectObject),
         )),
     ))
     .context(StrContext::Label("pipe fun"))
@@ -338,7 +586,7 @@ pub fn oml_pipe(data: &mut &str) -> WResult<PipeFun> {
 #[cfg(test)]
 mod tests {
     use crate::parser::pipe_prm::oml_aga_pipe;
-    use crate::parser::utils::for_test::{assert_oml_parse, err_of_oml};
+    use crate::parser::utils::for_test::{assert_oml_parse, assert_oml_parse_ext, err_of_oml};
     use wp_parser::WResult;
 
     #[test]
@@ -346,12 +594,25 @@ mod tests {
         let mut code = r#" pipe take(ip) | to_str | to_json | base64_encode | base64_decode(Utf8)"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
+        let mut code = r#" pipe take(ip) | to_str | ascii85_encode | ascii85_decode"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
         let mut code = r#" pipe take(ip) | to_str | html_escape | html_unescape | str_escape"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
         let mut code = r#" pipe take(ip) | to_str | json_escape | json_unescape"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
+        let mut code = r#" pipe take(flag) | to_bool"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(events) | slice(0, 10) | slice(-3) | slice_last(2)"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            "pipe take(events) | slice(0, 10) | slice(-3) | slice(-2)",
+        );
+
         let mut code = r#" pipe take(ip) | Time::to_ts | Time::to_ts_ms | Time::to_ts_us"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
@@ -361,17 +622,58 @@ mod tests {
         let mut code = r#" pipe take(ip) | skip_empty"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
+        let mut code = r#" pipe take(msg) | length"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(arr) | unique"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(payload) | json_path('$.events[*].id')"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
         let mut code = r#" pipe take(ip) | path(name)"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
+        let mut code = r#" pipe take(file) | path(parent) | path(stem)"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(file) | path(ext)"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
         let mut code = r#" pipe take(ip) | url(host)"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
+        let mut code = r#" pipe take(url) | url(query_all:tag)"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
         let mut code = r#" pipe take(message) | extract_main_word"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
 
+        let mut code = r#" pipe take(event) | map_keys(camel_case) | map_keys(uppercase)"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(event) | map_values(to_str) | map_values(path(name))"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
         Ok(())
     }
+    #[test]
+    fn test_pipe_extract_subject_object_args() {
+        let mut code = r#" pipe take(msg) | extract_subject_object"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(msg) | extract_subject_object( debug , prefix : 'evt_', flat, rename:[subject:who, status : result])"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            "pipe take(msg) | extract_subject_object(flat, prefix:'evt_', rename:[subject:who, status:result], debug)",
+        );
+
+        // 只允许改名内置键
+        let mut code = r#" pipe take(msg) | extract_subject_object(rename:[verb:act])"#;
+        err_of_oml(&mut code, oml_aga_pipe);
+    }
+
     #[test]
     fn test_pipe_oml_err() {
         let mut code = r#" pipe take(ip) | xyz_get()"#;
@@ -379,6 +681,11 @@ mod tests {
         println!("err:{}, \nwhere:{}", e, code);
         assert!(e.to_string().contains("fun not found"));
 
+        // 非法 JSONPath 在加载期即报错
+        let mut code = r#" pipe take(payload) | json_path('$.events[')"#;
+        let e = err_of_oml(&mut code, oml_aga_pipe);
+        assert!(e.to_string().contains("fun not found"));
+
         let mut code = r#" ipe take(ip) | xyz_get()"#;
         let e = err_of_oml(&mut code, oml_aga_pipe);
         println!("err:{}, \nwhere:{}", e, code);
@@ -411,6 +718,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_pipe_count_occurrences() -> WResult<()> {
+        use crate::parser::pipe_prm::oml_aga_pipe;
+        use wp_parser::Parser;
+
+        let mut code = r#" pipe read(sql) | count_occurrences('--')"#;
+        let result = oml_aga_pipe.parse_next(&mut code)?;
+        assert!(result.to_string().contains("count_occurrences('--')"));
+
+        let mut code = r#" pipe read(sql) | count_occurrences"#;
+        assert!(oml_aga_pipe.parse_next(&mut code).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_map_to_large_integers() -> WResult<()> {
         use crate::parser::pipe_prm::oml_aga_pipe;
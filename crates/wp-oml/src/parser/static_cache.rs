@@ -0,0 +1,162 @@
+//! static 块物化结果的磁盘缓存（`.omlcache`，与 OML 源文件相邻）
+//!
+//! 仅缓存只由字面量组成的 static 块（见 [`ObjModel::static_cacheable`]）：其物化结果只取决于
+//! OML 文本，缓存中记录缓存格式与源文本的 md5，文本变化即失效。含知识库查询、内置函数或
+//! `env(...)` 的 static 块依赖外部输入，每次加载都重新求值且不写缓存。
+//! 缓存缺失、损坏或写入失败时退回正常求值，解析结果与 [`oml_parse_raw`] 一致。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use wp_model_core::model::DataField;
+use wp_parser::WResult;
+
+use super::oml_conf::oml_parse_raw;
+use super::static_ctx::install_preloaded;
+use crate::language::ObjModel;
+
+const CACHE_EXT: &str = "omlcache";
+/// 参与缓存键计算；早期格式未区分外部输入，升级后旧缓存一律失效
+const CACHE_FORMAT: &str = "omlcache-v2";
+
+#[derive(Serialize, Deserialize)]
+struct StaticCache {
+    source_md5: String,
+    fields: Vec<DataField>,
+}
+
+/// 源文件对应的缓存路径：`foo.oml` → `foo.omlcache`
+pub fn static_cache_path(source: &Path) -> PathBuf {
+    source.with_extension(CACHE_EXT)
+}
+
+/// 与 [`oml_parse_raw`] 相同，但 static 块优先取自 `cache_path`；缓存无效时求值并回写
+pub fn oml_parse_raw_cached(data: &mut &str, cache_path: &Path) -> WResult<ObjModel> {
+    let source_md5 = cache_key(data);
+    let cached = load_cache(cache_path, &source_md5);
+    let hit = cached.is_some();
+    install_preloaded(cached);
+    let parsed = oml_parse_raw(data);
+    install_preloaded(None);
+    let model = parsed?;
+    if !hit && model.static_cacheable() && !model.static_fields().is_empty() {
+        store_cache(cache_path, source_md5, model.static_fields());
+    }
+    Ok(model)
+}
+
+fn cache_key(code: &str) -> String {
+    format!("{:x}", md5::compute(format!("{}\n{}", CACHE_FORMAT, code)))
+}
+
+fn load_cache(path: &Path, source_md5: &str) -> Option<HashMap<String, Arc<DataField>>> {
+    let bytes = std::fs::read(path).ok()?;
+    let cache: StaticCache = match bincode::deserialize(&bytes) {
+        Ok(cache) => cache,
+        Err(e) => {
+            warn_rule!("ignore oml static cache {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    if cache.source_md5 != source_md5 {
+        return None;
+    }
+    Some(
+        cache
+            .fields
+            .into_iter()
+            .map(|f| (f.get_name().to_string(), Arc::new(f)))
+            .collect(),
+    )
+}
+
+fn store_cache(path: &Path, source_md5: String, fields: &HashMap<String, Arc<DataField>>) {
+    let cache = StaticCache {
+        source_md5,
+        fields: fields.values().map(|f| DataField::clone(f)).collect(),
+    };
+    let result = bincode::serialize(&cache)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| std::fs::write(path, bytes).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn_rule!("write oml static cache {} failed: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = r#"
+name : test
+---
+static {
+    tag = chars(E1);
+}
+
+target = tag;
+"#;
+
+    fn static_value(model: &ObjModel) -> String {
+        model.static_fields()["tag"].get_value().to_string()
+    }
+
+    #[test]
+    fn cache_is_written_reused_and_invalidated() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = static_cache_path(&dir.path().join("test.oml"));
+        assert_eq!(cache_path.file_name().unwrap(), "test.omlcache");
+
+        let model = oml_parse_raw_cached(&mut &*CODE, &cache_path).unwrap();
+        assert_eq!(static_value(&model), "E1");
+        assert!(cache_path.exists());
+
+        // 命中时直接使用缓存内容，不再求值
+        let md5 = cache_key(CODE);
+        let fields = HashMap::from([(
+            "tag".to_string(),
+            Arc::new(DataField::from_chars("tag", "cached")),
+        )]);
+        store_cache(&cache_path, md5, &fields);
+        let model = oml_parse_raw_cached(&mut &*CODE, &cache_path).unwrap();
+        assert_eq!(static_value(&model), "cached");
+
+        // 文本变化后缓存失效并被重写
+        let changed = CODE.replace("E1", "E2");
+        let model = oml_parse_raw_cached(&mut changed.as_str(), &cache_path).unwrap();
+        assert_eq!(static_value(&model), "E2");
+        let model = oml_parse_raw_cached(&mut changed.as_str(), &cache_path).unwrap();
+        assert_eq!(static_value(&model), "E2");
+
+        // 损坏的缓存被忽略
+        std::fs::write(&cache_path, b"not a cache").unwrap();
+        let model = oml_parse_raw_cached(&mut &*CODE, &cache_path).unwrap();
+        assert_eq!(static_value(&model), "E1");
+    }
+
+    #[test]
+    fn external_inputs_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = static_cache_path(&dir.path().join("env.oml"));
+        let code = r#"
+name : test
+---
+static {
+    tag = chars(E1);
+    host = Hostname();
+}
+
+target = tag;
+"#;
+        let model = oml_parse_raw_cached(&mut &*code, &cache_path).unwrap();
+        assert!(!model.static_cacheable());
+        assert_eq!(static_value(&model), "E1");
+        assert!(!cache_path.exists());
+
+        // 只含字面量的 static 块照常写入
+        let model = oml_parse_raw_cached(&mut &*CODE, &cache_path).unwrap();
+        assert!(model.static_cacheable());
+        assert!(cache_path.exists());
+    }
+}
@@ -1,9 +1,11 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use winnow::ascii::multispace0;
 use winnow::error::{ContextError, ErrMode};
 use winnow::stream::Stream;
+use wp_model_core::model::DataField;
 use wp_parser::Parser;
 use wp_parser::WResult;
 use wp_parser::atom::take_var_name;
@@ -12,6 +14,8 @@ use crate::language::PreciseEvaluator;
 
 thread_local! {
     static STATIC_SYMBOLS: RefCell<Option<HashSet<String>>> = const { RefCell::new(None) };
+    static PRELOADED_STATICS: RefCell<Option<HashMap<String, Arc<DataField>>>> =
+        const { RefCell::new(None) };
 }
 
 pub fn install_symbols(symbols: Vec<String>) {
@@ -30,7 +34,38 @@ pub fn clear_symbols() {
     });
 }
 
-fn contains(name: &str) -> bool {
+/// static 符号作用域：限定在单个 OML 文件的解析过程内。
+/// 进入时清空线程内符号表，离开时（含解析失败提前返回）再次清空，
+/// 避免同一线程上先后解析的模型共享同名 static 符号。
+pub struct StaticScope {
+    _private: (),
+}
+
+impl StaticScope {
+    pub fn enter() -> Self {
+        clear_symbols();
+        Self { _private: () }
+    }
+}
+
+impl Drop for StaticScope {
+    fn drop(&mut self) {
+        clear_symbols();
+    }
+}
+
+/// 预置 static 块的物化结果（来自缓存）；下一次解析直接使用而不再求值
+pub fn install_preloaded(fields: Option<HashMap<String, Arc<DataField>>>) {
+    PRELOADED_STATICS.with(|ctx| {
+        *ctx.borrow_mut() = fields;
+    });
+}
+
+pub fn take_preloaded() -> Option<HashMap<String, Arc<DataField>>> {
+    PRELOADED_STATICS.with(|ctx| ctx.borrow_mut().take())
+}
+
+pub(crate) fn contains(name: &str) -> bool {
     STATIC_SYMBOLS.with(|ctx| {
         ctx.borrow()
             .as_ref()
@@ -57,3 +92,18 @@ pub fn parse_static_value(data: &mut &str) -> WResult<PreciseEvaluator> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_clears_symbols_on_drop() {
+        {
+            let _scope = StaticScope::enter();
+            install_symbols(vec!["threshold".into()]);
+            assert!(contains("threshold"));
+        }
+        assert!(!contains("threshold"));
+    }
+}
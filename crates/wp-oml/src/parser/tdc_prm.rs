@@ -31,11 +31,16 @@ pub fn oml_batch_gw_get(data: &mut &str) -> WResult<BatchEvaluation> {
 
 #[cfg(test)]
 mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
     use crate::parser::tdc_prm::{oml_aga_tdc, oml_aga_value};
-    use crate::parser::utils::for_test::assert_oml_parse;
+    use crate::parser::utils::for_test::{assert_oml_parse, assert_oml_parse_ext};
+    use orion_error::TestAssert;
     use orion_error::{ToStructError, UvsConfFrom};
+    use wp_data_model::cache::FieldQueryCache;
     use wp_error::OMLCodeReason;
     use wp_error::parse_error::OMLCodeResult;
+    use wp_model_core::model::{DataField, DataRecord};
     use wp_parser::Parser;
     use wp_parser::WResult as ModalResult;
 
@@ -75,4 +80,49 @@ mod tests {
         assert_oml_parse(&mut code, oml_aga_value);
         Ok(())
     }
+
+    #[test]
+    fn test_oml_read_default_arg() {
+        let mut code = r#"read(src, "hello") "#;
+        assert_oml_parse_ext(&mut code, oml_aga_tdc, "read(src){ _ : chars(hello)}");
+        let mut code = r#"read(src, chars(hello)) "#;
+        assert_oml_parse_ext(&mut code, oml_aga_tdc, "read(src){ _ : chars(hello)}");
+        let mut code = r#"read(src_ip, ip(127.0.0.1)) "#;
+        assert_oml_parse_ext(&mut code, oml_aga_tdc, "read(src_ip){ _ : ip(127.0.0.1)}");
+        let mut code = r#"read(src, Now::time()) "#;
+        assert_oml_parse_ext(&mut code, oml_aga_tdc, "read(src){ _ : Now::time() }");
+        let mut code = r#"read(option:[a,b], "x") "#;
+        assert_oml_parse_ext(&mut code, oml_aga_tdc, "read(option:[a,b]){ _ : chars(x)}");
+
+        let mut code = r#"read(src, "a", "b") "#;
+        assert!(oml_aga_tdc.parse_next(&mut code).is_err());
+    }
+
+    #[test]
+    fn test_read_default_transform() {
+        let mut conf = r#"
+        name : test
+        ---
+        level = read(level, "info") ;
+        host = read(host, "unknown") ;
+        port = read(port, digit(80)) ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let src = DataRecord::from(vec![DataField::from_chars("host", "web-1")]);
+        let cache = &mut FieldQueryCache::default();
+        let target = model.transform(src, cache);
+
+        assert_eq!(
+            target.field("level").map(|s| s.as_field()),
+            Some(&DataField::from_chars("level", "info"))
+        );
+        assert_eq!(
+            target.field("host").map(|s| s.as_field()),
+            Some(&DataField::from_chars("host", "web-1"))
+        );
+        assert_eq!(
+            target.field("port").map(|s| s.as_field()),
+            Some(&DataField::from_digit("port", 80))
+        );
+    }
 }
@@ -0,0 +1,133 @@
+//! 省略 `pipe` 关键字的管道写法：在各类聚合上下文中应与显式写法解析结果一致
+use oml::core::DataTransformer;
+use oml::language::ObjModel;
+use oml::parser::oml_parse_raw;
+use orion_error::TestAssert;
+use wp_data_model::cache::FieldQueryCache;
+use wp_model_core::model::{DataField, DataRecord, Value};
+
+/// 分别解析隐式与显式写法，要求输出一致，且输出可再次解析为同一模型
+fn round_trip(implicit: &str, explicit: &str) -> ObjModel {
+    let mut code = implicit;
+    let implicit_model = oml_parse_raw(&mut code).assert();
+    let mut code = explicit;
+    let explicit_model = oml_parse_raw(&mut code).assert();
+    let printed = implicit_model.to_string();
+    assert_eq!(printed, explicit_model.to_string());
+
+    let mut code = printed.as_str();
+    let reparsed = oml_parse_raw(&mut code).assert();
+    assert_eq!(reparsed.to_string(), printed);
+    implicit_model
+}
+
+fn sample() -> DataRecord {
+    DataRecord::from(vec![
+        DataField::from_chars("host", "web-01"),
+        DataField::from_digit("sport", 22),
+        DataField::from_digit("dport", 22),
+        DataField::from_chars("msg", "hello"),
+    ])
+}
+
+#[test]
+fn top_level_statement() {
+    let model = round_trip(
+        r#"
+name : test
+---
+encoded = read(msg) | base64_encode ;
+        "#,
+        r#"
+name : test
+---
+encoded = pipe read(msg) | base64_encode ;
+        "#,
+    );
+    let target = model.transform(sample(), &mut FieldQueryCache::default());
+    assert_eq!(
+        target.get_field_owned("encoded"),
+        Some(DataField::from_chars("encoded", "aGVsbG8="))
+    );
+}
+
+#[test]
+fn object_binding() {
+    let model = round_trip(
+        r#"
+name : test
+---
+info = object {
+    host = read(host) | to_str ;
+    port = take(sport) | to_str ;
+} ;
+        "#,
+        r#"
+name : test
+---
+info = object {
+    host = pipe read(host) | to_str ;
+    port = pipe take(sport) | to_str ;
+} ;
+        "#,
+    );
+    let target = model.transform(sample(), &mut FieldQueryCache::default());
+    let Some(Value::Obj(info)) = target.get_field_owned("info").map(|f| f.value) else {
+        panic!("expected object field");
+    };
+    assert_eq!(info.get("port"), Some(&DataField::from_chars("port", "22")));
+}
+
+#[test]
+fn collect_expression() {
+    let mut code = r#"
+name : test
+---
+ports = collect read(keys:[sport, dport]) | unique ;
+all_ports = collect read(keys:[sport, dport]) ;
+    "#;
+    let model = oml_parse_raw(&mut code).assert();
+    let printed = model.to_string();
+    let mut reparse = printed.as_str();
+    assert_eq!(oml_parse_raw(&mut reparse).assert().to_string(), printed);
+
+    let target = model.transform(sample(), &mut FieldQueryCache::default());
+    let len_of = |name: &str| match target.get_field_owned(name).map(|f| f.value) {
+        Some(Value::Array(arr)) => arr.len(),
+        other => panic!("expected array for {}, got {:?}", name, other),
+    };
+    assert_eq!(len_of("ports"), 1);
+    assert_eq!(len_of("all_ports"), 2);
+}
+
+#[test]
+fn multi_target_assignment() {
+    let model = round_trip(
+        r#"
+name : test
+---
+a, b = read(msg) | to_str ;
+        "#,
+        r#"
+name : test
+---
+a, b = pipe read(msg) | to_str ;
+        "#,
+    );
+    let target = model.transform(sample(), &mut FieldQueryCache::default());
+    assert_eq!(
+        target.get_field_owned("a"),
+        Some(DataField::from_chars("a", "hello"))
+    );
+}
+
+#[test]
+fn explicit_keyword_still_parses() {
+    let mut code = r#"
+name : test
+---
+version : chars = pipe take(host) | to_str | base64_encode ;
+    "#;
+    let model = oml_parse_raw(&mut code).assert();
+    assert_eq!(model.items.len(), 1);
+}
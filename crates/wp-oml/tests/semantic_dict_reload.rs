@@ -0,0 +1,36 @@
+//! 语义词典重载：外部配置变更后重载即生效，已取得的快照不受影响
+use oml::core::evaluator::transform::pipe::semantic_dict_loader::semantic_dict;
+use oml::reload_semantic_dict;
+
+fn dict_conf(word: &str) -> String {
+    format!(
+        "version = 1\nmode = \"add\"\n\n[status_words]\nenglish = [\"{}\"]\nchinese = []\n",
+        word
+    )
+}
+
+#[test]
+fn reload_applies_changed_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let conf = dir.path().join("semantic_dict.toml");
+    std::fs::write(&conf, dict_conf("reload_first")).unwrap();
+    // 本测试文件独占一个进程，环境变量只在此处设置
+    unsafe { std::env::set_var("SEMANTIC_DICT_CONFIG", &conf) };
+    assert!(reload_semantic_dict().is_empty());
+    let before = semantic_dict();
+    assert!(before.status_words.contains("reload_first"));
+
+    std::fs::write(&conf, dict_conf("reload_second")).unwrap();
+    assert!(reload_semantic_dict().is_empty());
+    let after = semantic_dict();
+    assert!(after.status_words.contains("reload_second"));
+    assert!(!after.status_words.contains("reload_first"));
+    assert!(before.status_words.contains("reload_first"));
+
+    // 配置损坏时返回错误，内置词典照常生效
+    std::fs::write(&conf, "version = 1\n[status_words\n").unwrap();
+    assert_eq!(reload_semantic_dict().len(), 1);
+    let broken = semantic_dict();
+    assert!(!broken.status_words.contains("reload_second"));
+    assert!(!broken.stop_words.is_empty());
+}
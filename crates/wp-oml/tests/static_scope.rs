@@ -0,0 +1,66 @@
+//! static 块按 OML 文件隔离：链式应用的多个模型可定义同名 static 符号
+use oml::core::DataTransformer;
+use oml::parser::oml_parse_raw;
+use orion_error::TestAssert;
+use wp_data_model::cache::FieldQueryCache;
+use wp_model_core::model::{DataField, DataRecord};
+
+#[test]
+fn same_static_name_in_chained_models() {
+    let mut first = r#"
+name : first
+---
+static {
+    threshold = digit(10);
+}
+first_threshold = threshold;
+msg = read(msg);
+    "#;
+    let mut second = r#"
+name : second
+---
+static {
+    threshold = digit(99);
+}
+second_threshold = threshold;
+first_threshold = read(first_threshold);
+    "#;
+    let first = oml_parse_raw(&mut first).assert();
+    let second = oml_parse_raw(&mut second).assert();
+
+    let cache = &mut FieldQueryCache::default();
+    let src = DataRecord::from(vec![DataField::from_chars("msg", "hello")]);
+    let mid = first.transform(src, cache);
+    let out = second.transform(mid, &mut FieldQueryCache::default());
+
+    assert_eq!(
+        out.get_field_owned("first_threshold"),
+        Some(DataField::from_digit("first_threshold", 10))
+    );
+    assert_eq!(
+        out.get_field_owned("second_threshold"),
+        Some(DataField::from_digit("second_threshold", 99))
+    );
+}
+
+#[test]
+fn failed_parse_does_not_leak_static_symbols() {
+    let mut broken = r#"
+name : broken
+---
+static {
+    threshold = digit(10);
+}
+value = threshold ;
+bad = = ;
+    "#;
+    assert!(oml_parse_raw(&mut broken).is_err());
+
+    // 未声明 static 的模型不能把裸标识符当作上一个模型的 static 符号
+    let mut other = r#"
+name : other
+---
+value = threshold ;
+    "#;
+    assert!(oml_parse_raw(&mut other).is_err());
+}
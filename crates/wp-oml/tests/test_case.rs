@@ -292,6 +292,50 @@ fn test_map_get() {
     );
 }
 
+#[test]
+fn test_map_get_pipe_without_keyword() {
+    let cache = &mut FieldQueryCache::default();
+    let src = DataRecord::from(vec![
+        DataField::from_chars("A1", "hello1"),
+        DataField::from_chars("B2", "hello2"),
+        DataField::from_chars("C3", "hello3"),
+    ]);
+
+    let mut conf = r#"
+        name : test
+        ---
+        X : obj = object {
+            A1 : chars = read() | base64_encode ;
+            B2 : chars = read(option:[missing, B2]) | base64_encode ;
+            C3 : chars = pipe read() | base64_encode ;
+            D4 : chars = read(C3) ;
+        };
+        Y = match read(C3) {
+            chars(hello3) => read(A1) | base64_encode ,
+            _ => chars(none) ,
+        };
+        "#;
+    let model = oml_parse_raw(&mut conf).assert();
+    let target = model.transform(src, cache);
+
+    let x = target.field("X").expect("X");
+    let Value::Obj(obj) = x.get_value() else {
+        panic!("X is not an object: {}", x);
+    };
+    let sub = |name: &str| obj.get(name).map(|f| f.get_value().to_string());
+    assert_eq!(sub("A1").as_deref(), Some("aGVsbG8x"));
+    assert_eq!(sub("B2").as_deref(), Some("aGVsbG8y"));
+    assert_eq!(sub("C3").as_deref(), Some("aGVsbG8z"));
+    assert_eq!(sub("D4").as_deref(), Some("hello3"));
+    assert_eq!(
+        target
+            .field("Y")
+            .map(|f| f.get_value().to_string())
+            .as_deref(),
+        Some("aGVsbG8x")
+    );
+}
+
 #[test]
 fn test_match_get() {
     let cache = &mut FieldQueryCache::default();
@@ -758,6 +802,55 @@ fn test_sql_debug() -> AnyResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_sql_batch_matches_single() -> AnyResult<()> {
+    let _ = wp_knowledge::facade::init_mem_provider(MemDB::global());
+    MemDB::load_test()?;
+    let mut conf = r#"
+        name : test
+        ---
+        A2,B2  = select name,pinying from example where pinying = read(py) ;
+        seq = read(seq) ;
+        "#;
+    let model = oml_parse_raw(&mut conf).assert();
+    let records: Vec<DataRecord> = ["xiaolongnu", "linghuchong", "xiaolongnu", "nobody"]
+        .iter()
+        .enumerate()
+        .map(|(i, py)| {
+            DataRecord::from(vec![
+                DataField::from_chars("py", *py),
+                DataField::from_digit("seq", i as i64),
+            ])
+        })
+        .collect();
+
+    let single_cache = &mut FieldQueryCache::default();
+    let expect: Vec<String> = records
+        .iter()
+        .map(|r| {
+            Json.fmt_record(&model.transform(r.clone(), single_cache))
+                .to_string()
+        })
+        .collect();
+
+    // 批量转换与逐条转换共享同一缓存语义
+    let batch = model.transform_batch(records.clone(), &mut FieldQueryCache::default());
+    let got: Vec<String> = batch
+        .iter()
+        .map(|r| Json.fmt_record(r).to_string())
+        .collect();
+    assert_eq!(got, expect);
+
+    // 缓存容量不足以容纳全部键时结果不变
+    let batch = model.transform_batch_ref(&records, &mut FieldQueryCache::with_capacity(1));
+    let got: Vec<String> = batch
+        .iter()
+        .map(|r| Json.fmt_record(r).to_string())
+        .collect();
+    assert_eq!(got, expect);
+    Ok(())
+}
+
 #[test]
 fn test_value_arr1() {
     let cache = &mut FieldQueryCache::default();
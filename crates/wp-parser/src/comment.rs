@@ -58,6 +58,38 @@ impl CommentParser {
         }
         Ok(out)
     }
+
+    /// 与 `ignore_comment` 识别同样的注释，但用空格替换注释内容并保留所有换行，
+    /// 使结果中的行列号与原文一致（用于报告错误位置）
+    pub fn blank_comment(input: &str) -> String {
+        let mut in_block = false;
+        let mut out = String::with_capacity(input.len());
+        for line in input.split_inclusive('\n') {
+            let (mut rest, eol) = match line.strip_suffix('\n') {
+                Some(body) => (body, "\n"),
+                None => (line, ""),
+            };
+            loop {
+                let body = rest.trim_start();
+                out.push_str(&rest[..rest.len() - body.len()]);
+                let marker = if in_block { "*/" } else { "/*" };
+                if let Some(after) = body.strip_prefix(marker) {
+                    out.push_str("  ");
+                    in_block = !in_block;
+                    rest = after;
+                    continue;
+                }
+                if in_block || body.starts_with("//") {
+                    out.extend(body.chars().map(|_| ' '));
+                } else {
+                    out.push_str(body);
+                }
+                break;
+            }
+            out.push_str(eol);
+        }
+        out
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -78,4 +110,26 @@ mod tests {
         let p_code = CommentParser::ignore_comment(&mut code).expect("ignore comment fail");
         println!("{}", p_code)
     }
+
+    #[test]
+    fn test_blank_comment_keeps_positions() {
+        let code = "a=1;\n  // note\n/* block\n*/ b=2;\nc=3; // tail kept\n";
+        let blanked = CommentParser::blank_comment(code);
+        assert_eq!(blanked.len(), code.len());
+        assert_eq!(blanked.lines().count(), code.lines().count());
+        let lines: Vec<&str> = blanked.lines().collect();
+        assert_eq!(lines[0], "a=1;");
+        assert!(lines[1].trim().is_empty() && lines[2].trim().is_empty());
+        assert_eq!(
+            lines[3].find("b=2;"),
+            code.lines().nth(3).unwrap().find("b=2;")
+        );
+        // 与 ignore_comment 一致：行尾注释不属于整行注释
+        assert_eq!(lines[4], "c=3; // tail kept");
+
+        let mut input = code;
+        let pure = CommentParser::ignore_comment(&mut input).unwrap();
+        let squash = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        assert_eq!(squash(&pure), squash(&blanked));
+    }
 }
@@ -20,6 +20,7 @@
 //! - **Conditional parsers** (`cond`): Parse complex boolean expressions
 //! - **Function parsers** (`fun`): Parse function invocations with arguments
 //! - **Utilities** (`utils`, `comment`): Helper functions and comment stripping
+//! - **Locations** (`location`): Line/column of parse failures for diagnostics
 //!
 //! ## Examples
 //!
@@ -61,6 +62,7 @@ pub mod atom;
 pub mod comment;
 pub mod cond;
 pub mod fun;
+pub mod location;
 pub mod net;
 pub mod scope;
 pub mod sql_symbol;
@@ -0,0 +1,87 @@
+//! Source locations for parse diagnostics.
+//!
+//! Parsers consume `&mut &str`, so on failure only the unconsumed tail is left.
+//! [`CodePos::locate`] turns "full input + remaining tail" into a 1-based
+//! line/column pair that can be reported the way rustc does (`[line:col]`).
+
+use std::fmt::{Display, Formatter};
+
+/// 1-based line/column of a position in the parsed code (column counts chars).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CodePos {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl CodePos {
+    /// Position at byte `offset` of `code`; offsets past the end clamp to the end.
+    pub fn at_offset(code: &str, offset: usize) -> Self {
+        let mut offset = offset.min(code.len());
+        while !code.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let head = &code[..offset];
+        let line_start = head.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self {
+            line: head.matches('\n').count() + 1,
+            column: head[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Position where `rest` (the unconsumed tail of `code`) begins.
+    pub fn locate(code: &str, rest: &str) -> Self {
+        Self::at_offset(code, code.len().saturating_sub(rest.len()))
+    }
+
+    /// Source line with a caret under the column, rustc style:
+    ///
+    /// ```text
+    ///   |
+    /// 3 |     name = read(src_ip ;
+    ///   |                        ^
+    /// ```
+    pub fn snippet(&self, code: &str) -> String {
+        let text = code
+            .lines()
+            .nth(self.line - 1)
+            .unwrap_or_default()
+            .trim_end_matches('\r');
+        let gutter = self.line.to_string();
+        let pad = " ".repeat(gutter.len());
+        format!(
+            "{pad} |\n{gutter} | {text}\n{pad} | {caret:>col$}",
+            caret = "^",
+            col = self.column
+        )
+    }
+}
+
+impl Display for CodePos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_counts_lines_and_chars() {
+        let code = "name : demo\n---\nx = 中文 bad";
+        let rest = &code[code.find("bad").unwrap()..];
+        let pos = CodePos::locate(code, rest);
+        assert_eq!(pos, CodePos { line: 3, column: 8 });
+        assert_eq!(pos.to_string(), "3:8");
+        assert_eq!(CodePos::locate(code, code), CodePos { line: 1, column: 1 });
+        assert_eq!(CodePos::locate(code, "").line, 3);
+    }
+
+    #[test]
+    fn snippet_points_at_column() {
+        let code = "a = 1;\nb = ?;\n";
+        let pos = CodePos::locate(code, &code[11..]);
+        assert_eq!(pos, CodePos { line: 2, column: 5 });
+        assert_eq!(pos.snippet(code), "  |\n2 | b = ?;\n  |     ^");
+    }
+}
@@ -1,9 +1,12 @@
 use std::path::Path;
 
 use crate::connectors::{
+    defaults::{ensure_factories_registered, registered_kind_defs},
+    fix::{FixReport, fix_side, side_scope},
     lint::lint_rows_from_root,
+    schema::export_connector_schema,
     templates::init_definitions,
-    types::{LintRow, LintSeverity, SilentErrKind},
+    types::{LintRow, LintSeverity, Side, SilentErrKind},
 };
 use crate::traits::Component;
 use crate::types::CheckStatus;
@@ -11,6 +14,7 @@ use orion_error::{ToStructError, UvsConfFrom};
 use orion_variate::EnvDict;
 
 use super::paths::ConnectorsPaths;
+use wp_engine::facade::config::{ConnectorValidationReport, WarpConf};
 use wp_error::run_error::{RunReason, RunResult};
 
 #[derive(Clone)]
@@ -32,11 +36,45 @@ impl Connectors {
         lint_rows_from_root(work_root, dict)
     }
 
+    /// lint 并应用非破坏性自动修复（参数名大小写、未声明参数、`allow_override` 引用但缺失的参数、
+    /// 失效的 allow_override 项）
+    ///
+    /// `dry_run` 为真时只报告将要进行的修复，不写回文件；
+    /// 否则逐个文件只改动被修复的键（保留注释与排版），以“写临时文件 + rename”方式原子替换。
+    pub fn lint_with_fix<P: AsRef<Path>>(
+        &self,
+        work_root: P,
+        dict: &EnvDict,
+        dry_run: bool,
+    ) -> RunResult<FixReport> {
+        let work_root = work_root.as_ref();
+        let mut report = FixReport {
+            dry_run,
+            ..Default::default()
+        };
+        for side in [Side::Sources, Side::Sinks] {
+            let kinds = registered_kind_defs(side_scope(side));
+            fix_side(work_root, side, &kinds, dry_run, &mut report)?;
+        }
+        report.rows = lint_rows_from_root(work_root, dict);
+        Ok(report)
+    }
+
     pub fn init_definition<P: AsRef<Path>>(&self, work_root: P) -> RunResult<()> {
         // 仅生成 connectors/ 内的模板目录，避免在工作根制造 legacy source.d/sink.d
         init_definitions(work_root)
     }
 
+    /// 在 source.d / sink.d 中查找指定连接器并导出其 JSON Schema（Draft 7），供 IDE 校验连接器 TOML
+    pub fn export_schema<P: AsRef<Path>>(
+        &self,
+        work_root: P,
+        dict: &EnvDict,
+        connector_id: &str,
+    ) -> RunResult<serde_json::Value> {
+        export_connector_schema(work_root.as_ref(), dict, connector_id)
+    }
+
     /// 检查连接器配置是否有效
     ///
     /// # 参数
@@ -70,14 +108,49 @@ impl Connectors {
         }
     }
 
-    /// 收集所有 lint 错误
+    /// 校验 source.d / sink.d 下全部连接器文件，供 `wprop validate-connectors` 使用
+    ///
+    /// 通过时逐文件输出所含连接器；任一文件失败时输出全部错误并返回 Err（CLI 以非零码退出）。
+    pub fn validate_all<P: AsRef<Path>>(
+        &self,
+        work_root: P,
+        dict: &EnvDict,
+    ) -> RunResult<ConnectorValidationReport> {
+        ensure_factories_registered();
+        match WarpConf::new(work_root.as_ref()).validate_all_connectors(dict) {
+            Ok(report) => {
+                for file in &report.files {
+                    println!("✓ {} ({})", file.file.display(), file.ids.join(", "));
+                }
+                println!("✓ {} connector(s) validated", report.total);
+                Ok(report)
+            }
+            Err(errors) => {
+                for err in &errors {
+                    println!("✗ {}", err);
+                }
+                Err(RunReason::from_conf(format!(
+                    "Connectors validation failed: {} error(s)",
+                    errors.len()
+                ))
+                .to_err())
+            }
+        }
+    }
+
+    /// 收集所有 lint 错误，以及按已注册类型校验参数声明的错误（与 `validate_all` 一致）
+    ///
+    /// 加载失败由校验报告逐文件给出，lint 中只报首个加载错误的行不再重复。
     fn collect_lint_errors(&self, work_root: &Path, dict: &EnvDict) -> Vec<String> {
         let mut errors = Vec::new();
         for row in self.lint_rows_from_root(work_root, dict) {
-            if matches!(row.sev, LintSeverity::Error) {
+            if matches!(row.sev, LintSeverity::Error) && row.silent_err.is_some() {
                 errors.push(format_lint_error(&row));
             }
         }
+        ensure_factories_registered();
+        let report = WarpConf::new(work_root).connector_validation_report(dict);
+        errors.extend(report.errors().iter().map(ToString::to_string));
         errors
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Once;
 use wp_conf::connectors::{ConnectorDef, ConnectorScope};
 use wp_engine::connectors::registry;
@@ -16,6 +17,21 @@ pub fn registered_templates() -> Vec<ConnectorTemplate> {
     out
 }
 
+/// 已注册连接器类型的默认定义（type -> def），用作参数声明集合
+pub fn registered_kind_defs(scope: ConnectorScope) -> HashMap<String, ConnectorDef> {
+    ensure_factories_registered();
+    let mut defs = match scope {
+        ConnectorScope::Source => registry::registered_source_defs(),
+        ConnectorScope::Sink => registry::registered_sink_defs(),
+    };
+    defs.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut out = HashMap::new();
+    for def in defs {
+        out.entry(def.kind.to_ascii_lowercase()).or_insert(def);
+    }
+    out
+}
+
 fn templates_from_defs(mut defs: Vec<ConnectorDef>) -> Vec<ConnectorTemplate> {
     fn slugify(raw: &str) -> String {
         raw.chars()
@@ -40,7 +56,7 @@ fn templates_from_defs(mut defs: Vec<ConnectorDef>) -> Vec<ConnectorTemplate> {
         .collect()
 }
 
-fn ensure_factories_registered() {
+pub(crate) fn ensure_factories_registered() {
     static INIT: Once = Once::new();
     INIT.call_once(|| {
         wp_engine::connectors::startup::init_runtime_registries();
@@ -0,0 +1,546 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use orion_conf::{ErrorOwe, ErrorWith};
+use orion_error::{ToStructError, UvsConfFrom};
+use toml_edit::{Array, DocumentMut, Item, Key, TableLike, Value};
+use wp_conf::connectors::{
+    ConnectorDef, ConnectorScope, collect_connector_files, param_map_to_table,
+};
+use wp_error::run_error::{RunReason, RunResult};
+
+use super::lint::resolve_dir;
+use super::types::{LintRow, Side};
+use crate::utils::FsOps;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize)]
+pub enum FixKind {
+    /// 参数名大小写与声明不一致，改为声明的写法
+    NormalizeKeyCase,
+    /// 参数不在连接器类型声明的参数集合中
+    RemoveUnknownKey,
+    /// `allow_override` 引用了但 params 中缺失的参数，按类型默认值补全
+    FillDefault,
+    /// `allow_override` 中引用了类型未声明的参数
+    RemoveStaleOverride,
+}
+
+/// 单条自动修复；`before`/`after` 为该参数修复前后的 TOML 行
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ConnectorFix {
+    pub file: String,
+    pub id: String,
+    pub kind: FixKind,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl ConnectorFix {
+    /// 统一 diff 形式：`- old` / `+ new`
+    pub fn diff(&self) -> String {
+        let mut out = String::new();
+        if let Some(before) = &self.before {
+            out.push_str(&format!("- {}\n", before));
+        }
+        if let Some(after) = &self.after {
+            out.push_str(&format!("+ {}\n", after));
+        }
+        out
+    }
+}
+
+impl Display for ConnectorFix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} [{}] {:?}", self.file, self.id, self.kind)?;
+        write!(f, "{}", self.diff())
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct FixReport {
+    pub dry_run: bool,
+    pub fixes: Vec<ConnectorFix>,
+    /// 实际写回的文件（dry-run 时为空）
+    pub written: Vec<String>,
+    /// 修复后（dry-run 时为当前）的 lint 结果
+    pub rows: Vec<LintRow>,
+}
+
+impl FixReport {
+    pub fn is_clean(&self) -> bool {
+        self.fixes.is_empty()
+    }
+}
+
+/// 对 source.d / sink.d 下的连接器定义应用非破坏性修复；
+/// 仅处理已注册的连接器类型，写回时只改动被修复的键，注释与原有排版保持不变。
+pub(super) fn fix_side(
+    work_root: &Path,
+    side: Side,
+    kinds: &HashMap<String, ConnectorDef>,
+    dry_run: bool,
+    report: &mut FixReport,
+) -> RunResult<()> {
+    let dir = resolve_dir(side, work_root);
+    let files = collect_connector_files(&dir).map_err(|e| {
+        RunReason::from_conf(format!("list connectors in {}: {}", dir.display(), e)).to_err()
+    })?;
+    let mut docs = Vec::with_capacity(files.len());
+    for path in files {
+        let body = fs::read_to_string(&path)
+            .owe_res()
+            .want("read connector file")
+            .with(&path)?;
+        let doc: DocumentMut = body
+            .parse()
+            .owe_res()
+            .want("parse connector file")
+            .with(&path)?;
+        docs.push((path, doc));
+    }
+    // 基类须位于同一目录：先登记全部条目，`extends` 子类按继承链解析类型与参数
+    let mut bases = BaseIndex::default();
+    for (_, doc) in docs.iter_mut() {
+        for entry in connector_entries(doc) {
+            bases.register(entry);
+        }
+    }
+    for (path, mut doc) in docs {
+        let file = path.display().to_string();
+        let before = report.fixes.len();
+        for entry in connector_entries(&mut doc) {
+            fix_entry(&file, entry, kinds, &bases, &mut report.fixes);
+        }
+        if report.fixes.len() > before && !dry_run {
+            FsOps::write_atomic(&path, &doc.to_string())?;
+            report.written.push(file);
+        }
+    }
+    Ok(())
+}
+
+/// 同目录连接器条目的继承信息（修复前的原始内容）
+#[derive(Default)]
+struct BaseIndex {
+    entries: HashMap<String, BaseEntry>,
+}
+
+struct BaseEntry {
+    kind: Option<String>,
+    extends: Option<String>,
+    /// 小写参数名
+    params: BTreeSet<String>,
+}
+
+impl BaseIndex {
+    fn register(&mut self, entry: &dyn TableLike) {
+        let Some(id) = entry.get("id").and_then(Item::as_str) else {
+            return;
+        };
+        let params = entry
+            .get("params")
+            .and_then(Item::as_table_like)
+            .map(|p| p.iter().map(|(k, _)| k.to_ascii_lowercase()).collect())
+            .unwrap_or_default();
+        self.entries.insert(
+            id.to_string(),
+            BaseEntry {
+                kind: entry.get("type").and_then(Item::as_str).map(String::from),
+                extends: entry
+                    .get("extends")
+                    .and_then(Item::as_str)
+                    .map(String::from),
+                params,
+            },
+        );
+    }
+
+    /// 由近及远的继承链；基类缺失或循环继承时截断（由加载器报错）
+    fn chain(&self, base_id: &str) -> Vec<&BaseEntry> {
+        let mut chain = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut next = Some(base_id.to_string());
+        while let Some(id) = next.take() {
+            if !seen.insert(id.clone()) {
+                break;
+            }
+            let Some(base) = self.entries.get(&id) else {
+                break;
+            };
+            chain.push(base);
+            next = base.extends.clone();
+        }
+        chain
+    }
+}
+
+/// `[[connectors]]` 与内联数组 `connectors = [{ .. }]` 两种写法的条目
+fn connector_entries(doc: &mut DocumentMut) -> Vec<&mut dyn TableLike> {
+    match doc.get_mut("connectors") {
+        Some(Item::ArrayOfTables(tables)) => {
+            tables.iter_mut().map(|t| t as &mut dyn TableLike).collect()
+        }
+        Some(Item::Value(Value::Array(values))) => values
+            .iter_mut()
+            .filter_map(Value::as_inline_table_mut)
+            .map(|t| t as &mut dyn TableLike)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn fix_entry(
+    file: &str,
+    entry: &mut dyn TableLike,
+    kinds: &HashMap<String, ConnectorDef>,
+    bases: &BaseIndex,
+    fixes: &mut Vec<ConnectorFix>,
+) {
+    // `extends` 子类：未声明 type 时沿用基类；基类已提供的参数视为已存在
+    let chain = entry
+        .get("extends")
+        .and_then(Item::as_str)
+        .map(|base| bases.chain(base))
+        .unwrap_or_default();
+    let kind = entry
+        .get("type")
+        .and_then(Item::as_str)
+        .map(String::from)
+        .or_else(|| chain.iter().find_map(|b| b.kind.clone()));
+    let Some(kind) = kind else {
+        return;
+    };
+    let inherited: BTreeSet<&String> = chain.iter().flat_map(|b| b.params.iter()).collect();
+    let Some(kind_def) = kinds.get(&kind.to_ascii_lowercase()) else {
+        return;
+    };
+    let defaults = param_map_to_table(&kind_def.default_params);
+    // 小写 -> 声明写法
+    let mut declared: BTreeMap<String, String> = BTreeMap::new();
+    for key in defaults.keys().chain(kind_def.allow_override.iter()) {
+        declared.insert(key.to_ascii_lowercase(), key.clone());
+    }
+    if declared.is_empty() {
+        // 类型未声明参数集合时无法判断未知参数
+        return;
+    }
+    let id = entry
+        .get("id")
+        .and_then(Item::as_str)
+        .unwrap_or("-")
+        .to_string();
+    let mut record = |kind: FixKind, before: Option<String>, after: Option<String>| {
+        fixes.push(ConnectorFix {
+            file: file.to_string(),
+            id: id.clone(),
+            kind,
+            before,
+            after,
+        });
+    };
+
+    if let Some(allowed) = entry.get_mut("allow_override").and_then(Item::as_array_mut) {
+        let stale = allowed.iter().any(|v| {
+            v.as_str()
+                .is_none_or(|k| !declared.contains_key(&k.to_ascii_lowercase()))
+        });
+        if stale {
+            let before = override_line(allowed);
+            allowed.retain(|v| {
+                v.as_str()
+                    .is_some_and(|k| declared.contains_key(&k.to_ascii_lowercase()))
+            });
+            allowed.fmt();
+            record(
+                FixKind::RemoveStaleOverride,
+                Some(before),
+                Some(override_line(allowed)),
+            );
+        }
+    }
+
+    if let Some(params) = entry.get_mut("params").and_then(Item::as_table_like_mut) {
+        let keys: Vec<String> = params.iter().map(|(k, _)| k.to_string()).collect();
+        for key in keys {
+            let canonical = declared.get(&key.to_ascii_lowercase());
+            match canonical {
+                Some(canonical) if *canonical == key => {}
+                Some(canonical) if !params.contains_key(canonical) => {
+                    let value = params.get(&key).map(item_text).unwrap_or_default();
+                    rename_key(params, &key, canonical);
+                    record(
+                        FixKind::NormalizeKeyCase,
+                        Some(param_line(&key, &value)),
+                        Some(param_line(canonical, &value)),
+                    );
+                }
+                _ => {
+                    // 未声明，或与声明写法的同名参数重复
+                    let value = params.remove(&key).expect("key listed above");
+                    record(
+                        FixKind::RemoveUnknownKey,
+                        Some(param_line(&key, &item_text(&value))),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+
+    // 仅补全校验会失败的键：`allow_override` 引用了但 params（含继承链）中缺失的参数
+    let missing: Vec<String> = override_keys(entry)
+        .into_iter()
+        .filter(|key| {
+            !inherited.contains(&key.to_ascii_lowercase())
+                && !entry
+                    .get("params")
+                    .and_then(Item::as_table_like)
+                    .is_some_and(|p| p.contains_key(key))
+        })
+        .collect();
+    for key in missing {
+        let Some(value) = defaults.get(&key).and_then(edit_value) else {
+            continue;
+        };
+        if !entry.contains_key("params") {
+            entry.insert("params", toml_edit::table());
+        }
+        let Some(params) = entry.get_mut("params").and_then(Item::as_table_like_mut) else {
+            continue;
+        };
+        record(
+            FixKind::FillDefault,
+            None,
+            Some(param_line(&key, &value_text(&value))),
+        );
+        params.insert(&key, Item::Value(value));
+    }
+}
+
+fn override_keys(entry: &dyn TableLike) -> Vec<String> {
+    entry
+        .get("allow_override")
+        .and_then(Item::as_array)
+        .map(|a| {
+            a.iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 原位改名：保持键的位置与前置注释
+fn rename_key(table: &mut dyn TableLike, from: &str, to: &str) {
+    let keys: Vec<String> = table.iter().map(|(k, _)| k.to_string()).collect();
+    let mut entries = Vec::with_capacity(keys.len());
+    for k in &keys {
+        let Some((key, _)) = table.get_key_value(k) else {
+            continue;
+        };
+        let key = if k == from {
+            Key::new(to).with_leaf_decor(key.leaf_decor().clone())
+        } else {
+            key.clone()
+        };
+        let item = table.remove(k).expect("key listed above");
+        entries.push((key, item));
+    }
+    for (key, item) in entries {
+        table.entry_format(&key).or_insert(item);
+    }
+}
+
+fn edit_value(value: &toml::Value) -> Option<Value> {
+    value.to_string().parse().ok()
+}
+
+fn value_text(value: &Value) -> String {
+    value.clone().decorated("", "").to_string()
+}
+
+fn item_text(item: &Item) -> String {
+    match item.as_value() {
+        Some(value) => value_text(value),
+        None => item.to_string().trim().to_string(),
+    }
+}
+
+fn param_line(key: &str, value: &str) -> String {
+    format!("params.{} = {}", key, value)
+}
+
+fn override_line(allowed: &Array) -> String {
+    format!(
+        "allow_override = {}",
+        Value::Array(allowed.clone()).decorated("", "")
+    )
+}
+
+pub(super) fn side_scope(side: Side) -> ConnectorScope {
+    match side {
+        Side::Sources => ConnectorScope::Source,
+        Side::Sinks => ConnectorScope::Sink,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_file};
+    use wp_connector_api::ParamMap;
+
+    fn kafka_kinds() -> HashMap<String, ConnectorDef> {
+        let mut params = ParamMap::new();
+        params.insert("brokers".into(), serde_json::json!("localhost:9092"));
+        params.insert("topic".into(), serde_json::json!("wparse"));
+        let def = ConnectorDef {
+            id: "kafka_sink".into(),
+            kind: "kafka".into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["topic".into(), "group_id".into()],
+            default_params: params,
+            origin: None,
+        };
+        HashMap::from([("kafka".to_string(), def)])
+    }
+
+    const KAFKA_FILE: &str = r#"# kafka 出口
+[[connectors]]
+id = "kafka_sink"
+type = "kafka"
+allow_override = ["topic", "retries", "brokers"]
+[connectors.params]
+# 业务 topic
+Topic = "events"
+Group_ID = "g1"
+retries = 3
+"#;
+
+    #[test]
+    fn fix_normalizes_removes_and_fills() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        write_file(root, "connectors/sink.d/30-kafka.toml", KAFKA_FILE);
+
+        let mut report = FixReport::default();
+        fix_side(root, Side::Sinks, &kafka_kinds(), false, &mut report).unwrap();
+
+        let count = |kind: FixKind| report.fixes.iter().filter(|f| f.kind == kind).count();
+        assert_eq!(count(FixKind::NormalizeKeyCase), 2);
+        assert_eq!(count(FixKind::RemoveUnknownKey), 1);
+        assert_eq!(count(FixKind::FillDefault), 1);
+        assert_eq!(count(FixKind::RemoveStaleOverride), 1);
+        let topic = report
+            .fixes
+            .iter()
+            .find(|f| f.before.as_deref() == Some("params.Topic = \"events\""))
+            .expect("topic fix");
+        assert_eq!(
+            topic.diff(),
+            "- params.Topic = \"events\"\n+ params.topic = \"events\"\n"
+        );
+        assert_eq!(report.written.len(), 1);
+
+        let body = fs::read_to_string(root.join("connectors/sink.d/30-kafka.toml")).unwrap();
+        let doc: toml::Table = toml::from_str(&body).unwrap();
+        let params = doc["connectors"][0]["params"].as_table().unwrap();
+        assert_eq!(params["topic"].as_str(), Some("events"));
+        assert_eq!(params["group_id"].as_str(), Some("g1"));
+        assert_eq!(params["brokers"].as_str(), Some("localhost:9092"));
+        assert!(!params.contains_key("retries"));
+        assert_eq!(
+            doc["connectors"][0]["allow_override"].as_array().unwrap(),
+            &vec![toml::Value::from("topic"), toml::Value::from("brokers")]
+        );
+        // 只改动被修复的键：注释与键顺序保留
+        assert!(body.starts_with("# kafka 出口\n"));
+        let topic_at = body
+            .find("# 业务 topic\ntopic = \"events\"")
+            .expect("comment kept");
+        assert!(topic_at < body.find("group_id").unwrap());
+        assert!(
+            !root
+                .join("connectors/sink.d/30-kafka.toml.fix-tmp")
+                .exists()
+        );
+
+        // 修复后再次运行不再产生修复
+        let mut again = FixReport::default();
+        fix_side(root, Side::Sinks, &kafka_kinds(), false, &mut again).unwrap();
+        assert!(again.is_clean());
+    }
+
+    #[test]
+    fn fill_default_only_for_referenced_missing_keys() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        let file = "[[connectors]]\nid = \"kafka_sink\"\ntype = \"kafka\"\nallow_override = [\"topic\"]\nparams = { brokers = \"10.0.0.1:9092\" }\n";
+        write_file(root, "connectors/sink.d/30-kafka.toml", file);
+
+        let mut report = FixReport::default();
+        fix_side(root, Side::Sinks, &kafka_kinds(), false, &mut report).unwrap();
+        // 只有 allow_override 引用的 topic 缺失会导致校验失败；其余默认值不写入
+        assert_eq!(report.fixes.len(), 1);
+        assert_eq!(report.fixes[0].kind, FixKind::FillDefault);
+        assert_eq!(
+            report.fixes[0].after.as_deref(),
+            Some("params.topic = \"wparse\"")
+        );
+        let body = fs::read_to_string(root.join("connectors/sink.d/30-kafka.toml")).unwrap();
+        let doc: toml::Table = toml::from_str(&body).unwrap();
+        let params = doc["connectors"][0]["params"].as_table().unwrap();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params["brokers"].as_str(), Some("10.0.0.1:9092"));
+    }
+
+    #[test]
+    fn extends_children_resolve_against_base() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        write_file(
+            root,
+            "connectors/sink.d/30-kafka.toml",
+            "[[connectors]]\nid = \"kafka_base_sink\"\ntype = \"kafka\"\nallow_override = [\"topic\", \"brokers\"]\n[connectors.params]\nbrokers = \"10.0.0.1:9092\"\ntopic = \"base\"\n",
+        );
+        // 子类不声明 type、不重复基类参数；孙类改写 topic 但大小写有误
+        let children = "[[connectors]]\nid = \"kafka_tls_sink\"\nextends = \"kafka_base_sink\"\nallow_override = [\"brokers\"]\n\n[[connectors]]\nid = \"kafka_audit_sink\"\nextends = \"kafka_tls_sink\"\n[connectors.params]\nTopic = \"audit\"\n";
+        write_file(root, "connectors/sink.d/40-kafka-child.toml", children);
+
+        let mut report = FixReport::default();
+        fix_side(root, Side::Sinks, &kafka_kinds(), false, &mut report).unwrap();
+        // 基类已提供 brokers/topic：不向子类写入默认值；孙类沿用基类类型完成大小写修复
+        assert!(report.fixes.iter().all(|f| f.kind != FixKind::FillDefault));
+        assert_eq!(report.fixes.len(), 1);
+        assert_eq!(report.fixes[0].id, "kafka_audit_sink");
+        assert_eq!(report.fixes[0].kind, FixKind::NormalizeKeyCase);
+
+        let body = fs::read_to_string(root.join("connectors/sink.d/40-kafka-child.toml")).unwrap();
+        assert_eq!(body, children.replace("Topic = ", "topic = "));
+    }
+
+    #[test]
+    fn dry_run_reports_without_writing() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        write_file(root, "connectors/sink.d/30-kafka.toml", KAFKA_FILE);
+        write_file(
+            root,
+            "connectors/sink.d/40-other.toml",
+            "[[connectors]]\nid = \"x_sink\"\ntype = \"custom\"\n[connectors.params]\nAny = 1\n",
+        );
+
+        let mut report = FixReport {
+            dry_run: true,
+            ..Default::default()
+        };
+        fix_side(root, Side::Sinks, &kafka_kinds(), true, &mut report).unwrap();
+        assert_eq!(report.fixes.len(), 5);
+        assert!(report.fixes.iter().all(|f| f.id == "kafka_sink"));
+        assert!(report.written.is_empty());
+        let body = fs::read_to_string(root.join("connectors/sink.d/30-kafka.toml")).unwrap();
+        assert_eq!(body, KAFKA_FILE);
+    }
+}
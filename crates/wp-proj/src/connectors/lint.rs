@@ -1,6 +1,8 @@
 use orion_variate::EnvDict;
 use std::path::{Path, PathBuf};
-use wp_conf::connectors::{ConnectorDef, ConnectorScope, load_connector_defs_from_dir};
+use wp_conf::connectors::{
+    ConnectorDef, ConnectorScope, ResolvedConnector, load_resolved_connectors_from_dir,
+};
 use wp_conf::sources::io::resolve_connectors_base_dir;
 
 use super::types::{LintRow, LintSeverity, Side, SilentErrKind};
@@ -39,7 +41,7 @@ fn ok_id_chars_fn(id: &str) -> bool {
 fn sev_max(a: LintSeverity, b: LintSeverity) -> LintSeverity {
     if a >= b { a } else { b }
 }
-fn resolve_dir(side: Side, start: &Path) -> PathBuf {
+pub(super) fn resolve_dir(side: Side, start: &Path) -> PathBuf {
     match side {
         Side::Sources => resolve_connectors_base_dir(start)
             .unwrap_or_else(|| start.join("connectors").join("source.d")),
@@ -119,9 +121,9 @@ fn lint_side_rows_from(start: &Path, side: Side, dict: &EnvDict) -> Vec<LintRow>
         Side::Sources => ConnectorScope::Source,
         Side::Sinks => ConnectorScope::Sink,
     };
-    match load_connector_defs_from_dir(&dir, scope, dict) {
-        Ok(defs) => {
-            for def in defs {
+    match load_resolved_connectors_from_dir(&dir, scope, dict) {
+        Ok(resolved) => {
+            for ResolvedConnector { def, extends } in resolved {
                 let hint = def
                     .origin
                     .as_ref()
@@ -137,6 +139,7 @@ fn lint_side_rows_from(start: &Path, side: Side, dict: &EnvDict) -> Vec<LintRow>
                     kind: def.kind.to_ascii_lowercase(),
                     sev,
                     msg: if msg.is_empty() { "-".into() } else { msg },
+                    extends,
                     silent_err,
                 });
             }
@@ -148,6 +151,7 @@ fn lint_side_rows_from(start: &Path, side: Side, dict: &EnvDict) -> Vec<LintRow>
             kind: "-".into(),
             sev: LintSeverity::Error,
             msg: format!("load failed: {}", err),
+            extends: Vec::new(),
             silent_err: None,
         }),
     }
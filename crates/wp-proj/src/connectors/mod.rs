@@ -1,10 +1,13 @@
 pub mod core;
 mod defaults;
+pub mod fix;
 pub mod lint;
 pub mod paths;
+pub mod schema;
 pub mod templates;
 pub mod types;
 // Re-export for convenience
 pub use core::Connectors;
+pub use fix::{ConnectorFix, FixKind, FixReport};
 pub use paths::ProjectPaths;
 pub use types::LintSeverity;
@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use orion_error::{ToStructError, UvsConfFrom};
+use orion_variate::EnvDict;
+use serde_json::Value as JsonValue;
+use wp_conf::connectors::{
+    ConnectorSchema, ConnectorScope, load_connector_defs_from_dir, load_schema_docs,
+};
+use wp_error::run_error::{RunReason, RunResult};
+
+use super::lint::resolve_dir;
+use super::types::Side;
+
+/// 在 source.d / sink.d 中查找连接器并生成 JSON Schema
+pub fn export_connector_schema(
+    work_root: &Path,
+    dict: &EnvDict,
+    connector_id: &str,
+) -> RunResult<JsonValue> {
+    for (side, scope) in [
+        (Side::Sources, ConnectorScope::Source),
+        (Side::Sinks, ConnectorScope::Sink),
+    ] {
+        let dir = resolve_dir(side, work_root);
+        let defs = load_connector_defs_from_dir(&dir, scope, dict).map_err(|e| {
+            RunReason::from_conf(format!("load connectors from {}: {}", dir.display(), e)).to_err()
+        })?;
+        let Some(def) = defs.into_iter().find(|d| d.id == connector_id) else {
+            continue;
+        };
+        let doc = match def.origin.as_deref() {
+            Some(origin) => load_schema_docs(Path::new(origin))
+                .map_err(|e| RunReason::from_conf(e.to_string()).to_err())?
+                .remove(connector_id),
+            None => None,
+        };
+        return Ok(def.json_schema_with_doc(doc.as_deref()));
+    }
+    Err(RunReason::from_conf(format!("connector '{}' not found", connector_id)).to_err())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_file};
+    use wp_conf::connectors::JSON_SCHEMA_DRAFT7;
+    use wp_conf::test_support::ForTest;
+
+    #[test]
+    fn export_schema_for_sink_connector() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        write_file(
+            root,
+            "connectors/sink.d/30-kafka.toml",
+            r#"[[connectors]]
+id = "kafka_sink"
+type = "kafka"
+allow_override = ["topic"]
+schema_doc = "Kafka output"
+[connectors.params]
+brokers = "localhost:9092"
+topic = "wparse_output"
+"#,
+        );
+
+        let schema = export_connector_schema(root, &EnvDict::test_default(), "kafka_sink").unwrap();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DRAFT7);
+        assert_eq!(schema["description"], "Kafka output");
+        let params = &schema["properties"]["connectors"]["items"]["properties"]["params"];
+        assert_eq!(params["properties"]["topic"]["x-allow-override"], true);
+        assert_eq!(params["properties"]["brokers"]["x-allow-override"], false);
+
+        assert!(export_connector_schema(root, &EnvDict::test_default(), "missing_sink").is_err());
+    }
+}
@@ -35,6 +35,9 @@ pub struct LintRow {
     pub kind: String,
     pub sev: LintSeverity,
     pub msg: String,
+    /// `extends` 继承链，由近及远
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
     #[serde(skip_serializing)]
     pub silent_err: Option<SilentErrKind>,
 }
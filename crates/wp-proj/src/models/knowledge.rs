@@ -3,11 +3,13 @@ use crate::utils::error_conv::ResultExt;
 use wp_error::run_error::RunResult;
 
 // 重新导出 wp-cli-core 的类型，保持向后兼容
-pub use wp_cli_core::knowdb::{CheckReport, CleanReport, TableCheck};
+pub use wp_cli_core::knowdb::{
+    CheckReport, CleanReport, ColumnType, ImportOptions, ImportReport, InferredColumn, TableCheck,
+};
 
 /// 知识库管理组件
 ///
-/// 提供知识库的初始化、检查和清理功能。
+/// 提供知识库的初始化、检查、导入和清理功能。
 /// 实现委托给 wp-cli-core::knowdb。
 #[derive(Debug, Clone, Default)]
 pub struct Knowledge;
@@ -58,6 +60,32 @@ impl Knowledge {
         wp_cli_core::knowdb::check(work_root, dict).to_run_err("知识库检查失败")
     }
 
+    /// 导入 CSV/TSV 文件为知识库表
+    ///
+    /// 按样本推断列类型（digit/float/ip/chars，前导零数值保留为 chars），
+    /// 写入表目录（create.sql/insert.sql/data.csv）并在 knowdb.toml 追加 `[[tables]]`。
+    ///
+    /// # 参数
+    /// - `work_root`: 项目根目录
+    /// - `opts`: 导入选项（表名、键列、类型覆盖、dry-run）
+    ///
+    /// # 返回
+    /// 返回导入报告，包含推断的列类型与行数；dry-run 时不写文件，`table_dir` 为 None
+    ///
+    /// # 示例
+    /// ```no_run
+    /// use wp_proj::models::knowledge::{ImportOptions, Knowledge};
+    ///
+    /// let mut opts = ImportOptions::new("./hosts.csv");
+    /// opts.key = Some("ip".into());
+    /// let report = Knowledge::new().import("./my-project", &opts)?;
+    /// println!("{} rows", report.rows);
+    /// # Ok::<(), wp_error::run_error::RunError>(())
+    /// ```
+    pub fn import(&self, work_root: &str, opts: &ImportOptions) -> RunResult<ImportReport> {
+        wp_cli_core::knowdb::import(work_root, opts).to_run_err("知识库导入失败")
+    }
+
     /// 清理知识库数据
     ///
     /// 删除 models/knowledge/ 目录和 .run/authority.sqlite 缓存文件。
@@ -128,6 +156,40 @@ mod tests {
         assert_eq!(report.ok, report.total, "所有表应该通过检查");
     }
 
+    #[test]
+    fn knowledge_import_adds_table() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().to_str().unwrap();
+        let kb = Knowledge::new();
+        kb.init(root).unwrap();
+
+        let src = temp.path().join("agents.csv");
+        std::fs::write(
+            &src,
+            "code,name
+007,bond
+042,smith
+",
+        )
+        .unwrap();
+        let mut opts = ImportOptions::new(&src);
+        opts.key = Some("code".into());
+        let report = kb.import(root, &opts).unwrap();
+        assert_eq!(report.table, "agents");
+        assert_eq!(report.rows, 2);
+        // 前导零编号保留为 chars
+        assert_eq!(report.columns[0].ty, ColumnType::Chars);
+        assert!(report.table_dir.is_some());
+
+        let check = kb.check(root, &EnvDict::test_default()).unwrap();
+        assert!(check.tables.iter().any(|t| t.name == "agents"));
+        assert_eq!(check.ok, check.total);
+
+        // 重复导入同名表报错并带上下文
+        let err = kb.import(root, &opts).unwrap_err().to_string();
+        assert!(err.contains("知识库导入失败"), "{}", err);
+    }
+
     #[test]
     fn knowledge_clean_removes_files() {
         let temp = tempdir().unwrap();
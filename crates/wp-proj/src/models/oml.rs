@@ -1,3 +1,6 @@
+use oml::core::ConfADMExt;
+use oml::language::ObjModel;
+use orion_conf::{ErrorOwe, ErrorWith};
 use orion_error::{ToStructError, UvsConfFrom};
 use orion_variate::EnvDict;
 use std::path::{Path, PathBuf};
@@ -9,7 +12,7 @@ use wp_error::run_error::{RunReason, RunResult};
 
 use crate::traits::{Checkable, Component, ComponentBase, ComponentLifecycle, HasExamples};
 use crate::types::CheckStatus;
-use crate::utils::{TemplateInitializer, error_handler::ErrorHandler};
+use crate::utils::{FsOps, TemplateInitializer, error_handler::ErrorHandler};
 
 #[derive(Clone)]
 pub struct Oml {
@@ -38,7 +41,7 @@ impl Oml {
         }
     }
 
-    fn oml_root(&self) -> PathBuf {
+    pub(crate) fn oml_root(&self) -> PathBuf {
         self.resolve_path(self.eng_conf().oml_root())
     }
 
@@ -86,6 +89,32 @@ rule = "/example/*"
         Ok(())
     }
 
+    /// 将 OML 文件中的 CRLF 换行统一为 LF，返回被改写的文件
+    pub fn normalize_line_endings(&self) -> RunResult<Vec<PathBuf>> {
+        let oml_root = self.oml_root();
+        if !oml_root.exists() {
+            return Ok(Vec::new());
+        }
+        let root_str = oml_root
+            .to_str()
+            .ok_or_else(|| RunReason::from_conf("OML文件路径无效").to_err())?;
+        let oml_files = find_conf_files(root_str, WPARSE_OML_FILE)
+            .map_err(|e| RunReason::from_conf(format!("OML 查找失败: {}", e)).to_err())?;
+        let mut changed = Vec::new();
+        for f in oml_files {
+            let body = std::fs::read_to_string(&f)
+                .owe_res()
+                .want("read oml file")
+                .with(&f)?;
+            if body.contains('\r') {
+                let normalized = body.replace("\r\n", "\n").replace('\r', "\n");
+                FsOps::write_atomic(&f, &normalized)?;
+                changed.push(f);
+            }
+        }
+        Ok(changed)
+    }
+
     pub fn check(&self, _dict: &orion_variate::EnvDict) -> RunResult<CheckStatus> {
         let oml_root = self.oml_root();
         if !oml_root.exists() {
@@ -107,6 +136,35 @@ rule = "/example/*"
             .map_err(|e| RunReason::from_conf(format!("parse oml failed: {}", e)).to_err())?;
         Ok(CheckStatus::Suc)
     }
+
+    /// 加载全部启用的 OML 模型（逐文件解析，返回模型及其文件路径）
+    pub fn models(&self) -> RunResult<Vec<(PathBuf, ObjModel)>> {
+        let mut models = self.all_models()?;
+        models.retain(|(_, m)| *m.enable());
+        Ok(models)
+    }
+
+    /// 加载全部 OML 模型（含 `enable = false` 的模型）
+    pub fn all_models(&self) -> RunResult<Vec<(PathBuf, ObjModel)>> {
+        let oml_root = self.oml_root();
+        if !oml_root.exists() {
+            return Ok(Vec::new());
+        }
+        let root_str = oml_root
+            .to_str()
+            .ok_or_else(|| RunReason::from_conf("OML文件路径无效").to_err())?;
+        let oml_files = find_conf_files(root_str, WPARSE_OML_FILE)
+            .map_err(|e| RunReason::from_conf(format!("OML 查找失败: {}", e)).to_err())?;
+        let mut models = Vec::new();
+        for f in oml_files {
+            let path = f.to_string_lossy().to_string();
+            let model = ObjModel::load(&path).map_err(|e| {
+                RunReason::from_conf(format!("parse oml failed: {}: {}", path, e)).to_err()
+            })?;
+            models.push((f, model));
+        }
+        Ok(models)
+    }
 }
 
 // Trait implementations for unified component interface
@@ -156,4 +214,25 @@ mod tests {
         assert!(example_file.exists());
         assert!(!temp.path().join("models/oml/*.oml").exists());
     }
+
+    #[test]
+    fn normalize_line_endings_rewrites_crlf_only() {
+        let temp = temp_workdir();
+        let root = temp.path().to_str().unwrap();
+        let eng = Arc::new(EngineConfig::init(root).conf_absolutize(root));
+        let oml = Oml::new(root, eng);
+        let crlf = temp.path().join("models/oml/crlf.oml");
+        let lf = temp.path().join("models/oml/lf.oml");
+        std::fs::create_dir_all(crlf.parent().unwrap()).unwrap();
+        std::fs::write(&crlf, "name : a\r\n---\r\nx = chars(1) ;\r\n").unwrap();
+        std::fs::write(&lf, "name : b\n---\nx = chars(1) ;\n").unwrap();
+
+        let changed = oml.normalize_line_endings().unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&crlf).unwrap(),
+            "name : a\n---\nx = chars(1) ;\n"
+        );
+        assert!(oml.normalize_line_endings().unwrap().is_empty());
+    }
 }
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use wp_conf::engine::EngineConfig;
 use wp_engine::facade::config::WPARSE_RULE_FILE;
 use wp_error::run_error::{RunReason, RunResult};
-use wpl::WplCode;
+use wpl::{WplCode, WplRule, WplRuleOutput};
 
 use crate::traits::{Checkable, Component, ComponentBase, ComponentLifecycle, HasExamples};
 use crate::types::CheckStatus;
@@ -38,7 +38,7 @@ impl Wpl {
         }
     }
 
-    fn rule_root(&self) -> PathBuf {
+    pub(crate) fn rule_root(&self) -> PathBuf {
         self.resolve_path(self.eng_conf().rule_root())
     }
 
@@ -153,6 +153,57 @@ impl Wpl {
         }
         Ok(CheckStatus::Suc)
     }
+
+    /// 解析全部 WPL 规则，返回 `<package>/<rule>` 规则路径及其可静态推导的输出字段
+    pub fn rule_outputs(&self) -> RunResult<Vec<(String, WplRuleOutput)>> {
+        let mut outputs = Vec::new();
+        self.for_each_rule(|_, path, rule| outputs.push((path, rule.output_fields())))?;
+        Ok(outputs)
+    }
+
+    /// 解析全部 WPL 规则，返回 `<package>/<rule>` 规则路径及其所在文件
+    pub fn rule_files(&self) -> RunResult<Vec<(String, PathBuf)>> {
+        let mut files = Vec::new();
+        self.for_each_rule(|fp, path, _| files.push((path, fp.to_path_buf())))?;
+        Ok(files)
+    }
+
+    /// 解析全部 WPL 规则，返回 `<package>/<rule>` 规则路径、所在文件及规则体的规范化文本（不含规则名）
+    pub fn rule_bodies(&self) -> RunResult<Vec<(String, PathBuf, String)>> {
+        let mut bodies = Vec::new();
+        self.for_each_rule(|fp, path, rule| {
+            bodies.push((path, fp.to_path_buf(), rule.statement.to_string()))
+        })?;
+        Ok(bodies)
+    }
+
+    fn for_each_rule<F>(&self, mut f: F) -> RunResult<()>
+    where
+        F: FnMut(&Path, String, &WplRule),
+    {
+        let rule_root = self.rule_root();
+        let mut files =
+            wp_conf::utils::find_conf_files(rule_root.to_string_lossy().as_ref(), WPARSE_RULE_FILE)
+                .unwrap_or_default();
+        if files.is_empty()
+            && let Ok(glob_results) = glob::glob(&format!("{}/*.wpl", rule_root.display()))
+        {
+            files = glob_results.filter_map(Result::ok).collect();
+        }
+        for fp in files {
+            let raw = std::fs::read_to_string(&fp).unwrap_or_default();
+            let code = WplCode::build(fp.clone(), raw.as_str()).map_err(|e| {
+                RunReason::from_conf(format!("build wpl failed: {:?}: {}", fp, e)).to_err()
+            })?;
+            let pkg = code.parse_pkg().map_err(|e| {
+                RunReason::from_conf(format!("parse wpl failed: {:?}: {}", fp, e)).to_err()
+            })?;
+            for rule in &pkg.rules {
+                f(&fp, rule.path(&pkg.name), rule);
+            }
+        }
+        Ok(())
+    }
 }
 
 // Trait implementations for unified component interface
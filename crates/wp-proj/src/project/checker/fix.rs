@@ -0,0 +1,209 @@
+//! `prj check --fix` 的自动修复项
+//!
+//! 仅在 `--fix` 时、且只针对对应组件的失败执行；每个修复函数返回所做修复的描述，
+//! 空列表表示没有可修复的内容（调用方保持原检查结果）。
+//! 改写已有文件时只改动被修复的部分（注释与排版保留），并以临时文件 + rename 原子替换。
+
+use std::fs;
+use std::path::Path;
+
+use orion_variate::EnvDict;
+use toml_edit::{DocumentMut, Item, Table, value};
+use wp_engine::facade::config::{WPGEN_TOML, WarpConf};
+
+use super::{Cell, CheckErrorCode};
+use crate::connectors::templates::init_definitions;
+use crate::project::warp::WarpProject;
+use crate::utils::FsOps;
+
+const CONNECTOR_DIRS: [&str; 2] = ["connectors/source.d", "connectors/sink.d"];
+
+/// 依据检查结果决定是否修复：失败且开启 `fix` 时执行 `repair`，修复后重新检查
+pub(super) fn check_with_repair<C, R>(fix: bool, check: C, repair: R) -> Cell
+where
+    C: Fn() -> Cell,
+    R: FnOnce() -> Result<Vec<String>, String>,
+{
+    let cell = check();
+    if cell.ok || !fix {
+        return cell;
+    }
+    match repair() {
+        Ok(notes) if notes.is_empty() => cell,
+        Ok(notes) => {
+            let again = check();
+            if again.ok {
+                Cell::fixed(notes.join("; "))
+            } else {
+                let code = again.error_code().unwrap_or(CheckErrorCode::Other);
+                let msg = again.msg.unwrap_or_default();
+                Cell::failure(format!("{} (applied: {})", msg, notes.join("; "))).with_code(code)
+            }
+        }
+        Err(e) => {
+            let msg = cell.msg.unwrap_or_default();
+            Cell::failure(format!("{} (fix failed: {})", msg, e))
+                .with_code(CheckErrorCode::FixFailed)
+        }
+    }
+}
+
+/// 缺失的连接器目录按注册模板生成；已有文件不做改动。
+///
+/// 源/汇检查失败的原因不一定在连接器定义，因此只补建目录。
+pub(super) fn repair_connector_dirs(work_root: &Path) -> Result<Vec<String>, String> {
+    let mut notes = Vec::new();
+    let missing: Vec<&str> = CONNECTOR_DIRS
+        .iter()
+        .copied()
+        .filter(|rel| !work_root.join(rel).is_dir())
+        .collect();
+    if !missing.is_empty() {
+        init_definitions(work_root).map_err(|e| e.reason().to_string())?;
+        for rel in missing {
+            notes.push(format!("created {} from templates", rel));
+        }
+    }
+    Ok(notes)
+}
+
+/// 连接器检查失败时：补建缺失目录，并对已有连接器定义应用非破坏性修复
+/// （参数名大小写、未声明参数、缺失默认值、失效的 allow_override 项）
+pub(super) fn repair_connectors(
+    project: &WarpProject,
+    work_root: &Path,
+    dict: &EnvDict,
+) -> Result<Vec<String>, String> {
+    let mut notes = repair_connector_dirs(work_root)?;
+    let report = project
+        .connectors()
+        .lint_with_fix(work_root, dict, false)
+        .map_err(|e| e.reason().to_string())?;
+    for fix in &report.fixes {
+        notes.push(format!("{} [{}] {:?}", fix.file, fix.id, fix.kind));
+    }
+    Ok(notes)
+}
+
+/// OML 文件中的 CRLF 换行统一为 LF
+pub(super) fn repair_oml_line_endings(project: &WarpProject) -> Result<Vec<String>, String> {
+    let changed = project
+        .oml()
+        .normalize_line_endings()
+        .map_err(|e| e.reason().to_string())?;
+    Ok(changed
+        .iter()
+        .map(|f| format!("normalized line endings in {}", f.display()))
+        .collect())
+}
+
+/// wpgen.toml 缺少 `[logging]` 段时补全默认值（与 `wproj gen conf init` 生成的一致）
+pub(super) fn repair_wpgen_logging(work_root: &Path) -> Result<Vec<String>, String> {
+    let path = WarpConf::new(work_root).config_path_string(WPGEN_TOML);
+    let body = match fs::read_to_string(&path) {
+        Ok(body) => body,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("read {}: {}", path, e)),
+    };
+    let mut doc: DocumentMut = body.parse().map_err(|e| format!("parse {}: {}", path, e))?;
+    if doc.contains_key("logging") {
+        return Ok(Vec::new());
+    }
+    let mut logging = Table::new();
+    logging.insert("level", value("info"));
+    logging.insert("output", value("file"));
+    logging.insert("file_path", value("./data/logs"));
+    doc.insert("logging", Item::Table(logging));
+    FsOps::write_atomic(&path, &doc.to_string()).map_err(|e| e.reason().to_string())?;
+    Ok(vec![format!("added default [logging] to {}", path)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_file};
+    use std::cell::Cell as Flag;
+
+    #[test]
+    fn repair_is_skipped_when_check_passes_or_fix_disabled() {
+        let called = Flag::new(false);
+        let repair = || {
+            called.set(true);
+            Ok(vec!["x".to_string()])
+        };
+        assert!(check_with_repair(true, Cell::success, repair).passed());
+        assert!(!called.get());
+
+        let cell = check_with_repair(false, || Cell::failure("bad".into()), || Ok(vec![]));
+        assert!(!cell.ok);
+        assert!(!cell.fixed);
+    }
+
+    #[test]
+    fn repaired_cell_is_fixed_and_unrepaired_keeps_failure() {
+        let healed = Flag::new(false);
+        let check = || {
+            if healed.get() {
+                Cell::success()
+            } else {
+                Cell::failure("broken".into())
+            }
+        };
+        let cell = check_with_repair(true, check, || {
+            healed.set(true);
+            Ok(vec!["repaired".to_string()])
+        });
+        assert!(cell.ok && cell.fixed);
+        assert_eq!(cell.msg.as_deref(), Some("repaired"));
+
+        let cell = check_with_repair(true, || Cell::failure("broken".into()), || Ok(vec![]));
+        assert_eq!(cell, Cell::failure("broken".into()));
+
+        let cell = check_with_repair(
+            true,
+            || Cell::failure("broken".into()).with_code(CheckErrorCode::SinkInvalid),
+            || Err("read-only".into()),
+        );
+        assert_eq!(cell.error_code(), Some(CheckErrorCode::FixFailed));
+    }
+
+    #[test]
+    fn connector_dir_repair_leaves_existing_definitions() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        let body =
+            "[[connectors]]\nid = \"k\"\ntype = \"kafka\"\n[connectors.params]\nTopic = \"t\"\n";
+        let path = write_file(root, "connectors/sink.d/30-kafka.toml", body);
+
+        let notes = repair_connector_dirs(root).unwrap();
+        assert_eq!(
+            notes,
+            vec!["created connectors/source.d from templates".to_string()]
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), body);
+    }
+
+    #[test]
+    fn wpgen_logging_defaults_are_added_once() {
+        let temp = temp_workdir();
+        let root = temp.path();
+        let path = write_file(
+            root,
+            "conf/wpgen.toml",
+            "version = \"\"\n# 样本模式\n[generator]\nmode = \"sample\" # 勿改\n[output]\nconnect = \"file_json_sink\"\n",
+        );
+
+        let notes = repair_wpgen_logging(root).unwrap();
+        assert_eq!(notes.len(), 1);
+        let body = fs::read_to_string(&path).unwrap();
+        assert!(
+            body.contains("# 样本模式\n[generator]\nmode = \"sample\" # 勿改\n"),
+            "{}",
+            body
+        );
+        let doc: toml::Table = toml::from_str(&body).unwrap();
+        assert_eq!(doc["logging"]["level"].as_str(), Some("info"));
+        assert_eq!(doc["generator"]["mode"].as_str(), Some("sample"));
+        assert!(repair_wpgen_logging(root).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+//! OML 模型与其 `rule` 匹配到的 WPL 规则之间的字段映射检查
+//!
+//! 只做静态分析：WPL 侧取规则 AST 中的字面字段名，OML 侧取按字面名的 `read`/`take`；
+//! 匹配到含动态字段规则（json/kv 等）的模型整体排除，避免误报。
+
+use std::path::PathBuf;
+
+use oml::language::ObjModel;
+use wpl::WplRuleOutput;
+
+use super::types::{MappingIssue, MappingReport};
+use crate::project::warp::WarpProject;
+
+/// 引擎在解析后追加到每条记录的字段，任何模型都可读取
+const ENGINE_FIELDS: [&str; 2] = ["wp_src_key", "wp_src_ip"];
+
+pub(super) fn check_mapping(project: &WarpProject) -> Result<MappingReport, String> {
+    let rules = project
+        .wpl()
+        .rule_outputs()
+        .map_err(|e| e.reason().to_string())?;
+    let models = project.oml().models().map_err(|e| e.reason().to_string())?;
+    Ok(analyze(&rules, &models))
+}
+
+fn analyze(rules: &[(String, WplRuleOutput)], models: &[(PathBuf, ObjModel)]) -> MappingReport {
+    let mut report = MappingReport::default();
+    for (file, model) in models {
+        let matched: Vec<&(String, WplRuleOutput)> = rules
+            .iter()
+            .filter(|(path, _)| model.rules().as_ref().iter().any(|w| w.matches(path)))
+            .collect();
+        if matched.is_empty() || matched.iter().any(|(_, out)| out.dynamic) {
+            report.skipped += 1;
+            continue;
+        }
+        report.checked += 1;
+        let mut clean = true;
+        for field_ref in model.input_field_refs() {
+            let produced = field_ref.names.iter().any(|name| {
+                ENGINE_FIELDS.contains(&name.as_str())
+                    || matched.iter().any(|(_, out)| out.contains(name))
+            });
+            if !produced {
+                clean = false;
+                report.issues.push(MappingIssue {
+                    model: model.name().clone(),
+                    file: file.display().to_string(),
+                    field: field_ref.names.join("|"),
+                    rules: matched.iter().map(|(path, _)| path.clone()).collect(),
+                });
+            }
+        }
+        if clean {
+            report.passed += 1;
+        }
+    }
+    report
+}
+
+/// 告警摘要，如 `nginx: src_ip`
+pub(super) fn issues_text(report: &MappingReport) -> String {
+    report
+        .issues
+        .iter()
+        .map(|i| format!("{}: {}", i.model, i.field))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oml::oml_parse_raw;
+    use wpl::WplPackage;
+
+    fn rules_of(mut code: &str) -> Vec<(String, WplRuleOutput)> {
+        let pkg = WplPackage::parse(&mut code, "test").expect("parse wpl");
+        pkg.rules
+            .iter()
+            .map(|r| (r.path(&pkg.name), r.output_fields()))
+            .collect()
+    }
+
+    fn model_of(mut code: &str) -> (PathBuf, ObjModel) {
+        (
+            PathBuf::from("test.oml"),
+            oml_parse_raw(&mut code).expect("parse oml"),
+        )
+    }
+
+    #[test]
+    fn unknown_fields_are_reported_per_model() {
+        let rules = rules_of(
+            r#"package /fw/ {
+   rule deny {
+        (ip:srcip, ip:dstip, digit:dport)
+   }
+   rule js {
+        (json(chars@user:user))
+   }
+}"#,
+        );
+        let models = vec![
+            model_of(
+                "name : good\nrule : /fw/*deny\n---\nsip = take(srcip) ;\nsrc = read(wp_src_key) ;\n",
+            ),
+            model_of(
+                "name : typo\nrule : /fw/*deny\n---\nsip = take(src_ip) ;\nport = take(option:[sport, dport]) ;\n",
+            ),
+            model_of("name : dyn\nrule : /fw/*\n---\nuser = take(uid) ;\n"),
+            model_of("name : none\nrule : /other/*\n---\nx = take(y) ;\n"),
+        ];
+        let report = analyze(&rules, &models);
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].model, "typo");
+        assert_eq!(report.issues[0].field, "src_ip");
+        assert_eq!(report.issues[0].rules, vec!["/fw//deny".to_string()]);
+        assert_eq!(issues_text(&report), "typo: src_ip");
+    }
+}
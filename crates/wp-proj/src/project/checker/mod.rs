@@ -1,12 +1,18 @@
+mod fix;
+mod mapping;
 mod options;
 mod report;
+mod schema;
 mod types;
 
 pub use options::{CheckComponent, CheckComponents, CheckOptions};
-pub use types::{Cell, ConnectorCounts, Row, SourceBreakdown};
+pub use types::{
+    CHECK_SCHEMA_VERSION, Cell, CellError, CheckErrorCode, ConnectorCounts, MappingIssue,
+    MappingReport, Row, SchemaIssue, SourceBreakdown,
+};
 
 use report::{build_detail_table, component_cells};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::warp::WarpProject;
 use crate::types::CheckStatus;
@@ -14,11 +20,13 @@ use orion_conf::UvsConfFrom;
 use orion_error::ToStructError;
 use orion_variate::EnvDict;
 use wp_cli_core::business::connectors::{sinks as sink_connectors, sources as source_connectors};
+use wp_conf::loader::{included_files, variable_report};
 use wp_engine::facade::config::{self as cfg_face, ENGINE_CONF_FILE};
 use wp_error::run_error::RunResult;
 
 /// 检查工程（与 `wproj prj check` 语义一致）。
 /// 执行全面的项目检查，包括所有组件。
+/// `opts.fix` 为真时对可自动修复的失败项先修复再重新检查，结果单元格标记为 `fixed`。
 pub fn check_with(
     project: &WarpProject,
     opts: &CheckOptions,
@@ -42,7 +50,12 @@ pub fn check_with(
 fn component_stat_value(enabled: bool, count: &ComponentCount) -> serde_json::Value {
     use serde_json::json;
     if enabled {
-        json!({ "passed": count.ok, "total": count.total })
+        json!({
+            "passed": count.passed() - count.fixed,
+            "fixed": count.fixed,
+            "warnings": count.warn,
+            "total": count.total
+        })
     } else {
         serde_json::Value::Null
     }
@@ -79,51 +92,116 @@ fn collect_rows(
         } else {
             work.to_string_lossy().to_string()
         };
-        let row = evaluate_target(project, &wrs, opts, comps, dict);
-        rows.push(row);
+        match evaluate_target(project, &wrs, opts, comps, dict) {
+            Evaluated::Done(row) => rows.push(row),
+            Evaluated::Abort(row) => {
+                rows.push(row);
+                break;
+            }
+        }
     }
     rows
 }
 
+/// 单个工程的检查结果；`Abort` 表示命中 `fail_fast_component`，不再检查后续工程
+enum Evaluated {
+    Done(Row),
+    Abort(Row),
+}
+
+impl Evaluated {
+    fn stopped(row: Row, abort_all: bool) -> Self {
+        if abort_all {
+            Evaluated::Abort(row)
+        } else {
+            Evaluated::Done(row)
+        }
+    }
+}
+
+/// 组件失败后是否停止：`Some(true)` 命中 `fail_fast_component`，终止全部检查；
+/// `Some(false)` 为 `fail_fast`，仅结束当前工程
+fn failure_stop(opts: &CheckOptions, comp: CheckComponent, cell: &Cell) -> Option<bool> {
+    if cell.ok {
+        None
+    } else if opts.fail_fast_component == Some(comp) {
+        Some(true)
+    } else if opts.fail_fast {
+        Some(false)
+    } else {
+        None
+    }
+}
+
 fn evaluate_target(
     project: &WarpProject,
     wrs: &str,
     opts: &CheckOptions,
     comps: &CheckComponents,
     dict: &EnvDict,
-) -> Row {
+) -> Evaluated {
     let mut row = Row::new(wrs.to_string());
 
     if comps.engine {
         row.conf = match cfg_face::load_warp_engine_confs(&wrs, dict) {
             Ok((cm, _)) => {
-                row.conf_detail = Some(cm.config_path_string(ENGINE_CONF_FILE));
+                let main = cm.config_path_string(ENGINE_CONF_FILE);
+                // 含 include 时列出全部参与合并的文件（按优先级由低到高）
+                row.conf_files = match included_files(Path::new(&main), dict) {
+                    Ok(files) => files.iter().map(|f| f.display().to_string()).collect(),
+                    Err(_) => vec![main],
+                };
+                row.conf_detail = Some(row.conf_files.join(", "));
                 Cell::success()
             }
-            Err(e) => Cell::failure(e.to_string()),
+            Err(e) => Cell::failure(e.to_string()).with_code(CheckErrorCode::ConfLoad),
         };
-        if !row.conf.ok && opts.fail_fast {
-            return row;
+        let variables = variable_report(Path::new(wrs), dict);
+        if row.conf.ok && !variables.missing().is_empty() {
+            row.conf = Cell::failure(format!("missing variables: {}", variables.missing_text()))
+                .with_code(CheckErrorCode::VarMissing);
+        }
+        if !variables.variables.is_empty() {
+            row.variables = Some(variables);
+        }
+        // wpgen.toml 不属于引擎配置检查，仅在 --fix 时补全缺失的 [logging] 段
+        if row.conf.ok && opts.fix {
+            match fix::repair_wpgen_logging(Path::new(wrs)) {
+                Ok(notes) if !notes.is_empty() => row.conf = Cell::fixed(notes.join("; ")),
+                Ok(_) => {}
+                Err(e) => row.conf = Cell::failure(e).with_code(CheckErrorCode::FixFailed),
+            }
+        }
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Engine, &row.conf) {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.conf = Cell::skipped();
     }
 
     if comps.sources {
-        let sources_check = project
-            .sources_c()
-            .check(dict)
-            .map_err(|e| e.reason().to_string())
-            .map(|_| ());
-        let check_cell = Cell::from_result(sources_check);
+        let check_cell = fix::check_with_repair(
+            opts.fix,
+            || {
+                Cell::from_result(
+                    project
+                        .sources_c()
+                        .check(dict)
+                        .map_err(|e| e.reason().to_string())
+                        .map(|_| ()),
+                )
+                .with_code(CheckErrorCode::SourceInvalid)
+            },
+            || fix::repair_connector_dirs(Path::new(wrs)),
+        );
         // Use the unified check() for both syntax and runtime validation
         row.source_checks = Some(SourceBreakdown {
             syntax: check_cell.clone(),
             runtime: check_cell.clone(),
         });
         row.sources = check_cell;
-        if !row.sources.ok && opts.fail_fast {
-            return row;
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Sources, &row.sources) {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.sources = Cell::skipped();
@@ -131,107 +209,251 @@ fn evaluate_target(
     }
 
     if comps.connectors {
-        row.connectors = Cell::from_result(
-            project
-                .connectors()
-                .check(&wrs, dict)
-                .map(|_| ())
-                .map_err(|e| e.reason().to_string()),
+        row.connectors = fix::check_with_repair(
+            opts.fix,
+            || {
+                Cell::from_result(
+                    project
+                        .connectors()
+                        .check(wrs, dict)
+                        .map(|_| ())
+                        .map_err(|e| e.reason().to_string()),
+                )
+                .with_code(CheckErrorCode::ConnectorInvalid)
+            },
+            || fix::repair_connectors(project, Path::new(wrs), dict),
         );
-        match collect_connector_counts(&wrs, dict) {
+        match collect_connector_counts(wrs, dict) {
             Ok(stats) => row.connector_counts = Some(stats),
             Err(_e) => {
                 row.connector_counts = None;
             }
         }
-        if !row.connectors.ok && opts.fail_fast {
-            return row;
+        // 悬空引用只在运行期才会暴露，这里提前判为失败；未使用的定义仅提示。
+        // 对照本身无法进行（连接器或路由加载失败）时告警，不让悬空引用静默漏检
+        match sink_connectors::connector_xref(wrs, dict) {
+            Ok(xref) => {
+                if row.connectors.ok && !xref.dangling.is_empty() {
+                    row.connectors = Cell::failure(format!(
+                        "dangling sink connector refs: {}",
+                        dangling_refs_text(&xref)
+                    ))
+                    .with_code(CheckErrorCode::ConnectorDangling);
+                }
+                if !xref.is_clean() {
+                    row.connector_xref = Some(xref);
+                }
+            }
+            Err(e) if row.connectors.ok => {
+                let note = format!("connector xref not run: {}", e);
+                row.connectors.warning = true;
+                row.connectors.msg = Some(match row.connectors.msg.take() {
+                    Some(prev) => format!("{}; {}", prev, note),
+                    None => note,
+                });
+            }
+            Err(_) => {}
+        }
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Connectors, &row.connectors) {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.connectors = Cell::skipped();
         row.connector_counts = None;
+        row.connector_xref = None;
     }
 
     if comps.sinks {
-        row.sinks = Cell::from_result(
-            project
-                .sinks_c()
-                .check(dict)
-                .map_err(|e| e.reason().to_string())
-                .map(|_| ()),
+        row.sinks = fix::check_with_repair(
+            opts.fix,
+            || {
+                Cell::from_result(
+                    project
+                        .sinks_c()
+                        .check(dict)
+                        .map_err(|e| e.reason().to_string())
+                        .map(|_| ()),
+                )
+                .with_code(CheckErrorCode::SinkInvalid)
+            },
+            || fix::repair_connector_dirs(Path::new(wrs)),
         );
-        if !row.sinks.ok && opts.fail_fast {
-            return row;
+        // 路由 schema 与模型声明类型不一致只告警；对照本身无法进行（路由或模型加载失败）同样告警
+        if row.sinks.ok {
+            match schema::check_schema(project, dict) {
+                Ok(issues) if issues.is_empty() => {}
+                Ok(issues) => {
+                    row.sinks.warning = true;
+                    row.sinks.msg = Some(format!(
+                        "schema type mismatches: {}",
+                        schema::issues_text(&issues)
+                    ));
+                    row.schema_issues = Some(issues);
+                }
+                Err(e) => {
+                    row.sinks.warning = true;
+                    row.sinks.msg = Some(format!("schema check not run: {}", e));
+                }
+            }
+        }
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Sinks, &row.sinks) {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.sinks = Cell::skipped();
+        row.schema_issues = None;
     }
 
     if comps.wpl {
-        row.wpl = Cell::from_result(
-            project
-                .wpl()
-                .check(dict)
-                .map_err(|e| e.reason().to_string())
-                .map(|_| ()),
-        );
-        if !row.wpl.ok && opts.fail_fast {
-            return row;
+        row.wpl = match project.wpl().check(dict) {
+            Ok(_) => Cell::success(),
+            Err(e) => {
+                let msg = e.reason().to_string();
+                let code = if msg.contains("parse wpl failed") || msg.contains("build wpl failed") {
+                    CheckErrorCode::WplSyntax
+                } else {
+                    CheckErrorCode::WplInvalid
+                };
+                Cell::failure(msg).with_code(code)
+            }
+        };
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Wpl, &row.wpl) {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.wpl = Cell::skipped();
     }
 
     if comps.oml {
-        row.oml = match project.oml().check(dict) {
-            Ok(check_status) => match check_status {
-                CheckStatus::Suc => Cell::success(),
-                CheckStatus::Miss => Cell::success_with_message("OML 文件缺失".to_string()),
-                CheckStatus::Error => Cell::failure("OML 检查错误".to_string()),
+        row.oml = fix::check_with_repair(
+            opts.fix,
+            || match project.oml().check(dict) {
+                Ok(check_status) => match check_status {
+                    CheckStatus::Suc => Cell::success(),
+                    CheckStatus::Miss => Cell::success_with_message("OML 文件缺失".to_string()),
+                    CheckStatus::Error => Cell::failure("OML 检查错误".to_string())
+                        .with_code(CheckErrorCode::OmlInvalid),
+                },
+                Err(e) => {
+                    let msg = e.reason().to_string();
+                    let code = if msg.contains("parse oml failed") {
+                        CheckErrorCode::OmlSyntax
+                    } else {
+                        CheckErrorCode::OmlInvalid
+                    };
+                    Cell::failure(msg).with_code(code)
+                }
             },
-            Err(e) => Cell::failure(e.reason().to_string()),
-        };
-        if !row.oml.ok && opts.fail_fast {
-            return row;
+            || fix::repair_oml_line_endings(project),
+        );
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Oml, &row.oml) {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.oml = Cell::skipped();
     }
 
+    if comps.mapping {
+        // 字段不匹配只告警；仅在无法加载规则/模型时判为失败
+        match mapping::check_mapping(project) {
+            Ok(report) => {
+                row.mapping = if report.issues.is_empty() {
+                    Cell::success()
+                } else {
+                    Cell::warning(format!(
+                        "fields not produced by matched WPL rules: {}",
+                        mapping::issues_text(&report)
+                    ))
+                };
+                row.mapping_report = Some(report);
+            }
+            Err(e) => row.mapping = Cell::failure(e).with_code(CheckErrorCode::MappingLoad),
+        }
+        if let Some(abort_all) = failure_stop(opts, CheckComponent::Mapping, &row.mapping) {
+            return Evaluated::stopped(row, abort_all);
+        }
+    } else {
+        row.mapping = Cell::skipped();
+        row.mapping_report = None;
+    }
+
     if comps.semantic_dict {
         row.semantic_dict = match check_semantic_dict_config() {
             Ok(Some(msg)) => Cell::success_with_message(msg),
             Ok(None) => Cell::success_with_message("使用内置词典".to_string()),
-            Err(e) => Cell::failure(e),
+            Err(e) => Cell::failure(e).with_code(CheckErrorCode::SemanticDict),
         };
-        if !row.semantic_dict.ok && opts.fail_fast {
-            return row;
+        if let Some(abort_all) =
+            failure_stop(opts, CheckComponent::SemanticDict, &row.semantic_dict)
+        {
+            return Evaluated::stopped(row, abort_all);
         }
     } else {
         row.semantic_dict = Cell::skipped();
     }
 
-    row
+    Evaluated::Done(row)
 }
 
-/// 检查语义词典配置
+/// 检查语义词典配置；逐文件列出加载失败的原因
 fn check_semantic_dict_config() -> Result<Option<String>, String> {
     // 调用 wp-oml 提供的检查方法
-    oml::check_semantic_dict_config(None)
+    oml::check_semantic_dict_config(None).map_err(|errors| {
+        errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    })
 }
 
 #[derive(Default, Clone, Copy)]
 struct ComponentCount {
+    /// 未失败的项，含修复后通过与带告警通过
     ok: usize,
+    fixed: usize,
+    warn: usize,
     total: usize,
 }
 
 impl ComponentCount {
-    fn record(&mut self, passed: bool) {
+    fn record(&mut self, cell: &Cell) {
         self.total += 1;
-        if passed {
+        if cell.ok {
             self.ok += 1;
         }
+        if cell.fixed {
+            self.fixed += 1;
+        }
+        if cell.warning {
+            self.warn += 1;
+        }
+    }
+
+    /// 映射检查按模型计数，存在字段问题的模型计为告警；加载失败时按工程计一次失败
+    fn record_mapping(&mut self, row: &Row) {
+        match &row.mapping_report {
+            Some(report) => {
+                self.ok += report.checked;
+                self.warn += report.checked - report.passed;
+                self.total += report.checked;
+            }
+            None => self.record(&row.mapping),
+        }
+    }
+
+    /// 无告警通过的项（含修复后通过）
+    fn passed(&self) -> usize {
+        self.ok - self.warn
+    }
+
+    fn summary(&self) -> String {
+        let passed = format!("{}/{} passed", self.passed(), self.total);
+        if self.fixed > 0 {
+            format!("{:<16} warn {} ({} fixed)", passed, self.warn, self.fixed)
+        } else {
+            format!("{:<16} warn {}", passed, self.warn)
+        }
     }
 }
 
@@ -243,6 +465,7 @@ struct SummaryCounts {
     sinks: ComponentCount,
     wpl: ComponentCount,
     oml: ComponentCount,
+    mapping: ComponentCount,
     semantic_dict: ComponentCount,
 }
 
@@ -250,25 +473,28 @@ fn summarize_components(rows: &[Row], comps: &CheckComponents) -> SummaryCounts
     let mut stats = SummaryCounts::default();
     for r in rows {
         if comps.engine {
-            stats.conf.record(r.conf.ok);
+            stats.conf.record(&r.conf);
         }
         if comps.connectors {
-            stats.connectors.record(r.connectors.ok);
+            stats.connectors.record(&r.connectors);
         }
         if comps.sources {
-            stats.sources.record(r.sources.ok);
+            stats.sources.record(&r.sources);
         }
         if comps.sinks {
-            stats.sinks.record(r.sinks.ok);
+            stats.sinks.record(&r.sinks);
         }
         if comps.wpl {
-            stats.wpl.record(r.wpl.ok);
+            stats.wpl.record(&r.wpl);
         }
         if comps.oml {
-            stats.oml.record(r.oml.ok);
+            stats.oml.record(&r.oml);
+        }
+        if comps.mapping {
+            stats.mapping.record_mapping(r);
         }
         if comps.semantic_dict {
-            stats.semantic_dict.record(r.semantic_dict.ok);
+            stats.semantic_dict.record(&r.semantic_dict);
         }
     }
     stats
@@ -281,36 +507,7 @@ fn render_output(
     comps: &CheckComponents,
 ) {
     if opts.json {
-        use serde_json::{Map, Value, json};
-        let mut stat = Map::new();
-        stat.insert("total".into(), Value::from(rows.len()));
-        stat.insert(
-            "conf".into(),
-            component_stat_value(comps.engine, &stats.conf),
-        );
-        stat.insert(
-            "connectors".into(),
-            component_stat_value(comps.connectors, &stats.connectors),
-        );
-        stat.insert(
-            "sources".into(),
-            component_stat_value(comps.sources, &stats.sources),
-        );
-        stat.insert(
-            "sinks".into(),
-            component_stat_value(comps.sinks, &stats.sinks),
-        );
-        stat.insert("wpl".into(), component_stat_value(comps.wpl, &stats.wpl));
-        stat.insert("oml".into(), component_stat_value(comps.oml, &stats.oml));
-        stat.insert(
-            "semantic_dict".into(),
-            component_stat_value(comps.semantic_dict, &stats.semantic_dict),
-        );
-
-        let output = json!({
-            "stat": Value::Object(stat),
-            "detail": rows
-        });
+        let output = json_report(rows, stats, comps);
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
     } else if opts.console {
         println!();
@@ -320,9 +517,49 @@ fn render_output(
         print_text_summary(rows.len(), stats, comps);
         println!("\n{}", build_detail_table(rows, comps));
         output_failure_details(rows, comps);
+        output_warning_details(rows, comps);
     }
 }
 
+/// `--json` 输出：`schema_version` + 各组件统计 + 每个工程的明细行
+fn json_report(rows: &[Row], stats: &SummaryCounts, comps: &CheckComponents) -> serde_json::Value {
+    use serde_json::{Map, Value, json};
+    let mut stat = Map::new();
+    stat.insert("total".into(), Value::from(rows.len()));
+    stat.insert(
+        "conf".into(),
+        component_stat_value(comps.engine, &stats.conf),
+    );
+    stat.insert(
+        "connectors".into(),
+        component_stat_value(comps.connectors, &stats.connectors),
+    );
+    stat.insert(
+        "sources".into(),
+        component_stat_value(comps.sources, &stats.sources),
+    );
+    stat.insert(
+        "sinks".into(),
+        component_stat_value(comps.sinks, &stats.sinks),
+    );
+    stat.insert("wpl".into(), component_stat_value(comps.wpl, &stats.wpl));
+    stat.insert("oml".into(), component_stat_value(comps.oml, &stats.oml));
+    stat.insert(
+        "mapping".into(),
+        component_stat_value(comps.mapping, &stats.mapping),
+    );
+    stat.insert(
+        "semantic_dict".into(),
+        component_stat_value(comps.semantic_dict, &stats.semantic_dict),
+    );
+
+    json!({
+        "schema_version": CHECK_SCHEMA_VERSION,
+        "stat": Value::Object(stat),
+        "detail": rows
+    })
+}
+
 fn print_text_summary(total: usize, stats: &SummaryCounts, comps: &CheckComponents) {
     println!(
         "Project check completed ({} project{})",
@@ -330,46 +567,42 @@ fn print_text_summary(total: usize, stats: &SummaryCounts, comps: &CheckComponen
         if total == 1 { "" } else { "s" }
     );
     if comps.engine {
-        println!("Config: {}/{} passed", stats.conf.ok, stats.conf.total);
+        println!("Config: {}", stats.conf.summary());
     } else {
         println!("Config: skipped");
     }
     if comps.connectors {
-        println!(
-            "Connectors: {}/{} passed",
-            stats.connectors.ok, stats.connectors.total
-        );
+        println!("Connectors: {}", stats.connectors.summary());
     } else {
         println!("Connectors: skipped");
     }
     if comps.sources {
-        println!(
-            "Sources: {}/{} passed",
-            stats.sources.ok, stats.sources.total
-        );
+        println!("Sources: {}", stats.sources.summary());
     } else {
         println!("Sources: skipped");
     }
     if comps.sinks {
-        println!("Sinks: {}/{} passed", stats.sinks.ok, stats.sinks.total);
+        println!("Sinks: {}", stats.sinks.summary());
     } else {
         println!("Sinks: skipped");
     }
     if comps.wpl {
-        println!("WPL models: {}/{} passed", stats.wpl.ok, stats.wpl.total);
+        println!("WPL models: {}", stats.wpl.summary());
     } else {
         println!("WPL models: skipped");
     }
     if comps.oml {
-        println!("OML models: {}/{} passed", stats.oml.ok, stats.oml.total);
+        println!("OML models: {}", stats.oml.summary());
     } else {
         println!("OML models: skipped");
     }
+    if comps.mapping {
+        println!("OML/WPL mapping: {}", stats.mapping.summary());
+    } else {
+        println!("OML/WPL mapping: skipped");
+    }
     if comps.semantic_dict {
-        println!(
-            "Semantic dict: {}/{} passed",
-            stats.semantic_dict.ok, stats.semantic_dict.total
-        );
+        println!("Semantic dict: {}", stats.semantic_dict.summary());
     } else {
         println!("Semantic dict: skipped");
     }
@@ -385,6 +618,7 @@ fn output_failure_details(rows: &[Row], comps: &CheckComponents) {
                 || (comps.sinks && !r.sinks.ok)
                 || (comps.wpl && !r.wpl.ok)
                 || (comps.oml && !r.oml.ok)
+                || (comps.mapping && !r.mapping.ok)
                 || (comps.semantic_dict && !r.semantic_dict.ok)
         })
         .collect();
@@ -404,6 +638,28 @@ fn output_failure_details(rows: &[Row], comps: &CheckComponents) {
     }
 }
 
+fn output_warning_details(rows: &[Row], comps: &CheckComponents) {
+    let warnings: Vec<(&str, &str, &str)> = rows
+        .iter()
+        .flat_map(|r| {
+            component_cells(r, comps)
+                .into_iter()
+                .filter(|(_, cell)| cell.warning)
+                .map(move |(label, cell)| {
+                    (r.path.as_str(), label, cell.msg.as_deref().unwrap_or(""))
+                })
+        })
+        .collect();
+    if warnings.is_empty() {
+        return;
+    }
+    println!("Warnings:");
+    for (path, label, detail) in warnings {
+        println!("  - {} -> {}: {}", path, label, detail);
+    }
+}
+
+/// 是否存在失败项；告警不计为失败
 fn has_failures(rows: &[Row], comps: &CheckComponents) -> bool {
     rows.iter().any(|r| {
         (comps.engine && !r.conf.ok)
@@ -412,6 +668,7 @@ fn has_failures(rows: &[Row], comps: &CheckComponents) -> bool {
             || (comps.sinks && !r.sinks.ok)
             || (comps.wpl && !r.wpl.ok)
             || (comps.oml && !r.oml.ok)
+            || (comps.mapping && !r.mapping.ok)
             || (comps.semantic_dict && !r.semantic_dict.ok)
     })
 }
@@ -426,6 +683,14 @@ pub fn check_with_default(
     check_with(project, opts, &CheckComponents::default(), dict)
 }
 
+fn dangling_refs_text(xref: &sink_connectors::ConnectorXref) -> String {
+    xref.dangling
+        .iter()
+        .map(|d| format!("{} ({} [{}])", d.id, d.file, d.group))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn collect_connector_counts(work_root: &str, dict: &EnvDict) -> Result<ConnectorCounts, String> {
     let (_cm, main) =
         cfg_face::load_warp_engine_confs(work_root, dict).map_err(|e| e.to_string())?;
@@ -446,3 +711,247 @@ fn collect_connector_counts(work_root: &str, dict: &EnvDict) -> Result<Connector
         sink_routes,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_basic_wparse_config, write_file};
+
+    fn two_targets() -> (tempfile::TempDir, tempfile::TempDir, Vec<PathBuf>) {
+        let broken = temp_workdir();
+        write_file(broken.path(), "conf/wparse.toml", "not valid [[[ toml");
+        let good = temp_workdir();
+        write_basic_wparse_config(good.path());
+        let targets = vec![broken.path().to_path_buf(), good.path().to_path_buf()];
+        (broken, good, targets)
+    }
+
+    fn rows_for(opts: &CheckOptions, targets: &[PathBuf]) -> Vec<Row> {
+        let project = WarpProject::bare(&targets[1]);
+        let comps = CheckComponents::default().with_only([CheckComponent::Engine]);
+        collect_rows(
+            &project,
+            targets,
+            "",
+            opts,
+            &comps,
+            &EnvDict::test_default(),
+        )
+    }
+
+    #[test]
+    fn fail_fast_component_stops_all_targets() {
+        let (_broken, _good, targets) = two_targets();
+
+        let opts = CheckOptions::default();
+        assert_eq!(rows_for(&opts, &targets).len(), 2);
+
+        let opts = CheckOptions {
+            fail_fast_component: Some(CheckComponent::Engine),
+            ..Default::default()
+        };
+        let rows = rows_for(&opts, &targets);
+        assert_eq!(rows.len(), 1);
+        assert!(!rows[0].conf.ok);
+    }
+
+    #[test]
+    fn json_report_carries_schema_version_and_error_codes() {
+        let (_broken, _good, targets) = two_targets();
+        let rows = rows_for(&CheckOptions::default(), &targets);
+        let comps = CheckComponents::default().with_only([CheckComponent::Engine]);
+        let stats = summarize_components(&rows, &comps);
+        let json = json_report(&rows, &stats, &comps);
+
+        assert_eq!(CHECK_SCHEMA_VERSION, 2);
+        assert_eq!(json["schema_version"], 2);
+        let conf = &json["detail"][0]["conf"];
+        assert_eq!(conf["ok"], false);
+        assert_eq!(conf["error"]["code"], "CONF_LOAD");
+        assert_eq!(conf["error"]["message"], conf["msg"]);
+        assert!(json["detail"][1]["conf"].get("error").is_none());
+    }
+
+    #[test]
+    fn per_row_fail_fast_and_other_component_keep_going() {
+        let (_broken, _good, targets) = two_targets();
+
+        // fail_fast 只结束当前工程
+        let opts = CheckOptions {
+            fail_fast: true,
+            ..Default::default()
+        };
+        let rows = rows_for(&opts, &targets);
+        assert_eq!(rows.len(), 2);
+        assert!(!rows[0].conf.ok);
+        assert!(rows[1].conf.ok);
+
+        // 指定的组件未失败时不终止
+        let opts = CheckOptions {
+            fail_fast: true,
+            fail_fast_component: Some(CheckComponent::Wpl),
+            ..Default::default()
+        };
+        assert_eq!(rows_for(&opts, &targets).len(), 2);
+    }
+
+    #[test]
+    fn connectors_check_validates_declared_params() {
+        let tmp = temp_workdir();
+        let root = tmp.path();
+        write_basic_wparse_config(root);
+        write_file(
+            root,
+            "connectors/sink.d/00-tcp.toml",
+            "[[connectors]]\nid = \"tcp_out_sink\"\ntype = \"tcp\"\nallow_override = [\"max_backoff\"]\nparams = { addr = \"127.0.0.1\", port = 9000, max_backoff = true, sendq_backpressure = false }\n",
+        );
+        let project = WarpProject::bare(root);
+        let comps = CheckComponents::default().with_only([CheckComponent::Connectors]);
+        let targets = vec![root.to_path_buf()];
+        let opts = CheckOptions::default();
+        let rows = collect_rows(&project, &targets, "", &opts, &comps, &EnvDict::new());
+        assert!(rows[0].connectors.ok, "{:?}", rows[0].connectors);
+
+        write_file(
+            root,
+            "connectors/sink.d/10-bad.toml",
+            "[[connectors]]\nid = \"tcp_bad_sink\"\ntype = \"tcp\"\nparams = { addr = \"127.0.0.1\", bogus = 1 }\n",
+        );
+        let rows = collect_rows(&project, &targets, "", &opts, &comps, &EnvDict::new());
+        let msg = rows[0].connectors.msg.as_deref().unwrap_or_default();
+        assert!(!rows[0].connectors.ok);
+        assert!(msg.contains("'bogus'"), "{}", msg);
+    }
+
+    #[test]
+    fn connectors_check_warns_when_xref_cannot_load() {
+        let tmp = temp_workdir();
+        let root = tmp.path();
+        write_basic_wparse_config(root);
+        write_file(
+            root,
+            "connectors/sink.d/00-file.toml",
+            "[[connectors]]\nid = \"file_json_sink\"\ntype = \"file\"\n",
+        );
+        write_file(
+            root,
+            "models/sinks/business.d/broken.toml",
+            "not valid [[[ toml",
+        );
+        let project = WarpProject::bare(root);
+        let comps = CheckComponents::default().with_only([CheckComponent::Connectors]);
+        let rows = collect_rows(
+            &project,
+            &[root.to_path_buf()],
+            "",
+            &CheckOptions::default(),
+            &comps,
+            &EnvDict::new(),
+        );
+        let cell = &rows[0].connectors;
+        assert!(cell.ok, "{:?}", cell);
+        assert!(cell.warning, "{:?}", cell);
+        let msg = cell.msg.as_deref().unwrap_or_default();
+        assert!(msg.contains("connector xref not run"), "{}", msg);
+    }
+
+    #[test]
+    fn engine_check_lists_variables_and_fails_on_missing() {
+        let tmp = temp_workdir();
+        let root = tmp.path();
+        write_basic_wparse_config(root);
+        let conf = root.join("conf/wparse.toml");
+        let mut body = std::fs::read_to_string(&conf).unwrap();
+        body.push_str(
+            "\n[variables]\nWP_CHK_API_TOKEN = { required = true, description = \"api token\" }\n",
+        );
+        std::fs::write(&conf, body).unwrap();
+        write_file(
+            root,
+            "connectors/sink.d/00-file.toml",
+            "[[connectors]]\nid = \"file_json_sink\"\ntype = \"file\"\nparams = { file = \"${WP_CHK_OUT:-out.dat}\", token = \"${WP_CHK_API_TOKEN}\" }\n",
+        );
+        let project = WarpProject::bare(root);
+        let comps = CheckComponents::default().with_only([CheckComponent::Engine]);
+        let targets = vec![root.to_path_buf()];
+        let opts = CheckOptions::default();
+
+        let rows = collect_rows(&project, &targets, "", &opts, &comps, &EnvDict::new());
+        let row = &rows[0];
+        assert_eq!(row.conf.error_code(), Some(CheckErrorCode::VarMissing));
+        let msg = row.conf.msg.as_deref().unwrap_or_default();
+        assert!(
+            msg.contains("connectors/sink.d/00-file.toml: WP_CHK_API_TOKEN (api token)"),
+            "{}",
+            msg
+        );
+
+        let mut dict = EnvDict::new();
+        dict.insert("WP_CHK_API_TOKEN", orion_variate::ValueType::from("t0ken"));
+        let rows = collect_rows(&project, &targets, "", &opts, &comps, &dict);
+        assert!(rows[0].conf.ok, "{:?}", rows[0].conf);
+        let json = serde_json::to_string(&rows[0]).unwrap();
+        assert!(!json.contains("t0ken"), "{}", json);
+        let report = rows[0].variables.as_ref().expect("variables");
+        let sources: Vec<(&str, &str)> = report
+            .variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.source.as_str()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![("WP_CHK_API_TOKEN", "env"), ("WP_CHK_OUT", "default")]
+        );
+    }
+
+    #[test]
+    fn engine_check_lists_included_conf_files() {
+        let tmp = temp_workdir();
+        let root = tmp.path();
+        write_basic_wparse_config(root);
+        write_file(root, "conf/base.toml", "[performance]\nparse_workers = 4\n");
+        let conf = root.join("conf/wparse.toml");
+        let body = std::fs::read_to_string(&conf).unwrap();
+        std::fs::write(&conf, format!("include = [\"base.toml\"]\n{}", body)).unwrap();
+
+        let project = WarpProject::bare(root);
+        let comps = CheckComponents::default().with_only([CheckComponent::Engine]);
+        let rows = collect_rows(
+            &project,
+            &[root.to_path_buf()],
+            "",
+            &CheckOptions::default(),
+            &comps,
+            &EnvDict::test_default(),
+        );
+        assert!(rows[0].conf.ok, "{:?}", rows[0].conf);
+        let files = &rows[0].conf_files;
+        assert_eq!(files.len(), 2, "{:?}", files);
+        assert!(files[0].ends_with("base.toml"), "{:?}", files);
+        assert!(files[1].ends_with("wparse.toml"), "{:?}", files);
+        assert_eq!(
+            rows[0].conf_detail.as_deref(),
+            Some(files.join(", ").as_str())
+        );
+    }
+
+    #[test]
+    fn warnings_are_counted_separately_from_failures() {
+        let comps = CheckComponents::default().with_only([CheckComponent::Engine]);
+        let mut warned = Row::new("warned".into());
+        warned.conf = Cell::warning("param 'topic_name' is deprecated, use 'topic'".into());
+        let rows = vec![warned, Row::new("clean".into())];
+        assert!(!has_failures(&rows, &comps));
+
+        let stats = summarize_components(&rows, &comps);
+        assert_eq!(stats.conf.warn, 1);
+        assert_eq!(stats.conf.summary(), "1/2 passed      warn 1");
+        let json = json_report(&rows, &stats, &comps);
+        assert_eq!(
+            json["stat"]["conf"],
+            serde_json::json!({ "passed": 1, "fixed": 0, "warnings": 1, "total": 2 })
+        );
+        assert_eq!(json["detail"][0]["conf"]["warning"], true);
+        assert!(json["detail"][1]["conf"].get("warning").is_none());
+    }
+}
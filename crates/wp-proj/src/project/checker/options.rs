@@ -6,8 +6,12 @@ pub struct CheckOptions {
     pub what: String,
     pub console: bool,
     pub fail_fast: bool,
+    /// 指定组件在任一工程中失败时立即终止整个检查（`fail_fast` 只结束当前工程）
+    pub fail_fast_component: Option<CheckComponent>,
     pub json: bool,
     pub only_fail: bool,
+    /// 对可自动修复的失败项尝试修复后重新检查
+    pub fix: bool,
 }
 
 impl CheckOptions {
@@ -26,8 +30,10 @@ impl Default for CheckOptions {
             what: String::new(),
             console: false,
             fail_fast: false,
+            fail_fast_component: None,
             json: false,
             only_fail: false,
+            fix: false,
         }
     }
 }
@@ -40,6 +46,8 @@ pub struct CheckComponents {
     pub sinks: bool,
     pub wpl: bool,
     pub oml: bool,
+    /// OML 模型读取的字段与其匹配 WPL 规则输出字段的映射检查
+    pub mapping: bool,
     pub semantic_dict: bool,
 }
 
@@ -51,6 +59,7 @@ impl CheckComponents {
         self.sinks = false;
         self.wpl = false;
         self.oml = false;
+        self.mapping = false;
         self.semantic_dict = false;
     }
 
@@ -80,6 +89,7 @@ impl CheckComponents {
             CheckComponent::Sinks => self.sinks,
             CheckComponent::Wpl => self.wpl,
             CheckComponent::Oml => self.oml,
+            CheckComponent::Mapping => self.mapping,
             CheckComponent::SemanticDict => self.semantic_dict,
         }
     }
@@ -92,6 +102,7 @@ impl CheckComponents {
             CheckComponent::Sinks => self.sinks = value,
             CheckComponent::Wpl => self.wpl = value,
             CheckComponent::Oml => self.oml = value,
+            CheckComponent::Mapping => self.mapping = value,
             CheckComponent::SemanticDict => self.semantic_dict = value,
         }
     }
@@ -106,6 +117,7 @@ impl Default for CheckComponents {
             sinks: true,
             wpl: true,
             oml: true,
+            mapping: true,
             semantic_dict: true,
         }
     }
@@ -119,5 +131,6 @@ pub enum CheckComponent {
     Sinks,
     Wpl,
     Oml,
+    Mapping,
     SemanticDict,
 }
@@ -1,8 +1,10 @@
 use super::options::CheckComponents;
 use super::types::{Cell, Row};
-use comfy_table::{Cell as TCell, ContentArrangement, Table, presets::UTF8_FULL};
+use comfy_table::{Cell as TCell, Color, ContentArrangement, Table, presets::UTF8_FULL};
 use std::path::Path;
 
+const WARN_MARK: &str = "⚠";
+
 /// 截断路径，只保留最后 n 级
 fn truncate_path(path: &str, levels: usize) -> String {
     let p = Path::new(path);
@@ -40,7 +42,7 @@ pub fn build_detail_table(rows: &[Row], comps: &CheckComponents) -> Table {
                 TCell::new(entry.category),
                 TCell::new(entry.item),
                 TCell::new(entry.data),
-                TCell::new(entry.result),
+                result_cell(entry.result),
             ]);
         }
     }
@@ -67,6 +69,9 @@ pub fn component_cells<'a>(row: &'a Row, comps: &CheckComponents) -> Vec<(&'stat
     if comps.oml {
         cells.push(("OML", &row.oml));
     }
+    if comps.mapping {
+        cells.push(("Mapping", &row.mapping));
+    }
     if comps.semantic_dict {
         cells.push(("SemanticDict", &row.semantic_dict));
     }
@@ -87,17 +92,47 @@ fn detail_entries_for(row: &Row, comps: &CheckComponents) -> Vec<DetailEntry> {
         format!("{} / {}", short_path, section)
     };
     if comps.engine {
-        let config_data = row
-            .conf_detail
-            .as_ref()
-            .map(|path| truncate_path(path, 3))
-            .unwrap_or_else(|| cell_data(&row.conf));
+        let config_data = if row.conf_files.is_empty() {
+            cell_data(&row.conf)
+        } else {
+            row.conf_files
+                .iter()
+                .map(|path| truncate_path(path, 3))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
         entries.push(DetailEntry {
             category: cat("Config"),
             item: "Engine config".into(),
             data: config_data,
             result: status_mark(&row.conf).to_string(),
         });
+        if let Some(report) = &row.variables {
+            let data = report
+                .variables
+                .iter()
+                .map(|v| {
+                    format!(
+                        "{} = {} ({})",
+                        v.name,
+                        v.value.as_deref().unwrap_or("-"),
+                        v.source.as_str()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let result = if report.missing().is_empty() {
+                "✓"
+            } else {
+                "✗"
+            };
+            entries.push(DetailEntry {
+                category: cat("Config"),
+                item: "Variables".into(),
+                data,
+                result: result.into(),
+            });
+        }
     }
 
     if comps.connectors {
@@ -122,6 +157,36 @@ fn detail_entries_for(row: &Row, comps: &CheckComponents) -> Vec<DetailEntry> {
                 result: status_mark(&row.connectors).to_string(),
             });
         }
+        if let Some(xref) = &row.connector_xref {
+            if !xref.unused.is_empty() {
+                let data = xref
+                    .unused
+                    .iter()
+                    .map(|u| format!("{} ({})", u.id, truncate_path(&u.file, 2)))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                entries.push(DetailEntry {
+                    category: cat("Connectors"),
+                    item: "Unused sink connectors".into(),
+                    data,
+                    result: "⚠".into(),
+                });
+            }
+            if !xref.dangling.is_empty() {
+                let data = xref
+                    .dangling
+                    .iter()
+                    .map(|d| format!("{} ({})", d.id, truncate_path(&d.file, 2)))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                entries.push(DetailEntry {
+                    category: cat("Connectors"),
+                    item: "Dangling sink refs".into(),
+                    data,
+                    result: "✗".into(),
+                });
+            }
+        }
     }
 
     if comps.sources {
@@ -172,6 +237,39 @@ fn detail_entries_for(row: &Row, comps: &CheckComponents) -> Vec<DetailEntry> {
             result: status_mark(&row.oml).to_string(),
         });
     }
+    if comps.mapping {
+        match &row.mapping_report {
+            Some(report) if !report.issues.is_empty() => {
+                let data = report
+                    .issues
+                    .iter()
+                    .map(|i| format!("{}: {} ({})", i.model, i.field, truncate_path(&i.file, 2)))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                entries.push(DetailEntry {
+                    category: cat("Mapping"),
+                    item: "Unknown fields".into(),
+                    data,
+                    result: "⚠".into(),
+                });
+            }
+            Some(report) => entries.push(DetailEntry {
+                category: cat("Mapping"),
+                item: "OML/WPL fields".into(),
+                data: format!(
+                    "{}/{} models ({} skipped)",
+                    report.passed, report.checked, report.skipped
+                ),
+                result: status_mark(&row.mapping).to_string(),
+            }),
+            None => entries.push(DetailEntry {
+                category: cat("Mapping"),
+                item: "OML/WPL fields".into(),
+                data: cell_data(&row.mapping),
+                result: status_mark(&row.mapping).to_string(),
+            }),
+        }
+    }
     if comps.semantic_dict {
         entries.push(DetailEntry {
             category: cat("SemanticDict"),
@@ -185,7 +283,21 @@ fn detail_entries_for(row: &Row, comps: &CheckComponents) -> Vec<DetailEntry> {
 }
 
 fn status_mark(cell: &Cell) -> &'static str {
-    if cell.ok { "✓" } else { "✗" }
+    match (cell.ok, cell.fixed, cell.warning) {
+        (true, true, _) => "✓ fixed",
+        (true, false, true) => WARN_MARK,
+        (true, false, false) => "✓",
+        _ => "✗",
+    }
+}
+
+/// 告警以黄色显示，与通过、失败区分
+fn result_cell(result: String) -> TCell {
+    if result == WARN_MARK {
+        TCell::new(result).fg(Color::Yellow)
+    } else {
+        TCell::new(result)
+    }
 }
 
 fn cell_data(cell: &Cell) -> String {
@@ -0,0 +1,95 @@
+//! 路由 schema 与模型声明类型的对照检查
+//!
+//! 对每个配置了 `[sink_group.schema]` 的路由组，取按 `oml` 路由到该组的模型中显式声明了类型的
+//! 输出字段（`port : digit = ...`）逐一对照；未声明类型的字段由运行期的 schema 约束处理，这里不报。
+
+use std::path::PathBuf;
+
+use oml::language::ObjModel;
+use orion_variate::EnvDict;
+use wp_conf::structure::FlexGroup;
+
+use super::types::SchemaIssue;
+use crate::project::warp::WarpProject;
+
+pub(super) fn check_schema(
+    project: &WarpProject,
+    dict: &EnvDict,
+) -> Result<Vec<SchemaIssue>, String> {
+    let groups = project
+        .sinks_c()
+        .route_groups(dict)
+        .map_err(|e| e.reason().to_string())?;
+    let models = project.oml().models().map_err(|e| e.reason().to_string())?;
+    Ok(analyze(&groups, &models))
+}
+
+fn analyze(groups: &[FlexGroup], models: &[(PathBuf, ObjModel)]) -> Vec<SchemaIssue> {
+    let mut issues = Vec::new();
+    for group in groups {
+        for (file, model) in models {
+            for m in group.schema_mismatches(model.name(), model.typed_targets()) {
+                issues.push(SchemaIssue {
+                    group: group.name().clone(),
+                    model: model.name().clone(),
+                    file: file.display().to_string(),
+                    field: m.field,
+                    expected: m.expected,
+                    declared: m.declared,
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// 告警摘要，如 `es/nginx: port (chars, expect digit)`
+pub(super) fn issues_text(issues: &[SchemaIssue]) -> String {
+    issues
+        .iter()
+        .map(|i| {
+            format!(
+                "{}/{}: {} ({}, expect {})",
+                i.group, i.model, i.field, i.declared, i.expected
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oml::oml_parse_raw;
+    use wp_conf::structure::{RouteSchemaConf, extend_matches};
+
+    fn model_of(mut code: &str) -> (PathBuf, ObjModel) {
+        (
+            PathBuf::from("test.oml"),
+            oml_parse_raw(&mut code).expect("parse oml"),
+        )
+    }
+
+    #[test]
+    fn declared_types_checked_against_route_schema() {
+        let mut es = FlexGroup::test_new("es", "/fw/*");
+        es.oml = extend_matches(vec!["fw_*".to_string()]);
+        es.schema = Some(RouteSchemaConf {
+            fields: [("port".to_string(), "digit".to_string())].into(),
+            ..RouteSchemaConf::default()
+        });
+        let mut plain = FlexGroup::test_new("plain", "/fw/*");
+        plain.oml = extend_matches(vec!["fw_*".to_string()]);
+        let models = vec![
+            model_of("name : fw_a\n---\nport : chars = read(port) ;\n"),
+            model_of("name : fw_b\n---\nport : digit = read(port) ;\nmsg = read(msg) ;\n"),
+            model_of("name : web\n---\nport : chars = read(port) ;\n"),
+        ];
+        let issues = analyze(&[es, plain], &models);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].model, "fw_a");
+        assert_eq!(issues[0].field, "port");
+        assert_ne!(issues[0].expected, issues[0].declared);
+        assert!(issues_text(&issues).starts_with("es/fw_a: port ("));
+    }
+}
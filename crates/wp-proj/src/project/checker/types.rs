@@ -1,12 +1,115 @@
 use serde::Serialize;
+use wp_cli_core::business::connectors::sinks::ConnectorXref;
+use wp_conf::loader::VariableReport;
+
+/// `prj check --json` 输出结构的版本号；字段增删或语义变化时递增
+pub const CHECK_SCHEMA_VERSION: u32 = 2;
+
+/// 检查失败的机器可读错误码（JSON 中序列化为 `SCREAMING_SNAKE_CASE`）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CheckErrorCode {
+    /// 引擎配置（wparse.toml 等）加载失败
+    ConfLoad,
+    /// 配置引用的变量缺少取值（环境、默认值与 `[variables]` 均未提供）
+    VarMissing,
+    /// 输入源配置无效
+    SourceInvalid,
+    /// 连接器定义无效
+    ConnectorInvalid,
+    /// 路由引用了不存在的 sink 连接器
+    ConnectorDangling,
+    /// 输出接收器配置无效
+    SinkInvalid,
+    /// WPL 文件解析失败
+    WplSyntax,
+    /// WPL 目录/文件缺失或为空等非语法错误
+    WplInvalid,
+    /// OML 文件解析失败
+    OmlSyntax,
+    /// OML 目录/文件缺失或为空等非语法错误
+    OmlInvalid,
+    /// 字段映射检查无法加载规则或模型
+    MappingLoad,
+    /// 语义词典配置无效
+    SemanticDict,
+    /// `--fix` 修复过程出错
+    FixFailed,
+    /// 未归类的失败
+    Other,
+}
+
+/// 结构化的失败信息；`path`/`line` 从原始消息中尽量提取，无法确定时为 `null`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CellError {
+    pub code: CheckErrorCode,
+    pub message: String,
+    pub path: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl CellError {
+    pub fn new(code: CheckErrorCode, message: String) -> Self {
+        let (path, line) = locate(&message);
+        Self {
+            code,
+            message,
+            path,
+            line,
+        }
+    }
+}
+
+/// 从解析错误消息中提取文件路径与行号，支持以下形式：
+/// - OML：` --> path:line:col`
+/// - WPL：`parse wpl failed: "path": ...` 与 `parse error at line N`
+/// - TOML：`TOML parse error at line N`
+fn locate(msg: &str) -> (Option<String>, Option<u32>) {
+    if let Some(pos) = msg.find(" --> ") {
+        let rest = msg[pos + 5..].lines().next().unwrap_or_default().trim();
+        let mut parts = rest.rsplitn(3, ':');
+        let (col, line, path) = (parts.next(), parts.next(), parts.next());
+        if let (Some(_), Some(line), Some(path)) = (col, line, path)
+            && let Ok(line) = line.parse::<u32>()
+        {
+            return (Some(path.to_string()), Some(line));
+        }
+    }
+    let path = quoted_path(msg);
+    let line = msg.find("at line ").and_then(|pos| {
+        let digits: String = msg[pos + 8..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse::<u32>().ok()
+    });
+    (path, line)
+}
+
+/// 消息中第一个带已知扩展名的带引号路径
+fn quoted_path(msg: &str) -> Option<String> {
+    msg.split('"')
+        .skip(1)
+        .step_by(2)
+        .find(|s| [".wpl", ".oml", ".toml"].iter().any(|ext| s.ends_with(ext)))
+        .map(str::to_string)
+}
 
 /// 单个组件的检查结果单元格
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct Cell {
     /// 检查是否通过
     pub ok: bool,
-    /// 错误消息（如果检查失败）
+    /// 错误消息（如果检查失败）；保留供旧版消费方读取，新代码应使用 `error`
     pub msg: Option<String>,
+    /// 检查最初失败，经 `--fix` 自动修复后通过
+    pub fixed: bool,
+    /// 通过但有告警（如参数名已废弃），不计为失败
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub warning: bool,
+    /// 结构化失败信息，仅失败时存在
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<CellError>,
 }
 
 impl Default for Cell {
@@ -14,6 +117,9 @@ impl Default for Cell {
         Self {
             ok: true, // 默认为成功状态，未检查的组件不应显示为错误
             msg: None,
+            fixed: false,
+            warning: false,
+            error: None,
         }
     }
 }
@@ -24,17 +130,36 @@ impl Cell {
         Self {
             ok: true,
             msg: None,
+            fixed: false,
+            warning: false,
+            error: None,
         }
     }
 
-    /// 创建失败的检查结果
+    /// 创建失败的检查结果，错误码为 `Other`，可用 [`Cell::with_code`] 指定
     pub fn failure(msg: String) -> Self {
         Self {
             ok: false,
+            error: Some(CellError::new(CheckErrorCode::Other, msg.clone())),
             msg: Some(msg),
+            fixed: false,
+            warning: false,
         }
     }
 
+    /// 设置失败的错误码；成功的结果保持不变
+    pub fn with_code(mut self, code: CheckErrorCode) -> Self {
+        if let Some(err) = self.error.as_mut() {
+            err.code = code;
+        }
+        self
+    }
+
+    /// 失败的错误码
+    pub fn error_code(&self) -> Option<CheckErrorCode> {
+        self.error.as_ref().map(|e| e.code)
+    }
+
     /// 从 Result 转换
     pub fn from_result(result: Result<(), String>) -> Self {
         match result {
@@ -48,9 +173,39 @@ impl Cell {
         Self {
             ok: true,
             msg: Some(msg),
+            fixed: false,
+            warning: false,
+            error: None,
         }
     }
 
+    /// 创建自动修复后通过的检查结果，`msg` 记录所做的修复
+    pub fn fixed(msg: String) -> Self {
+        Self {
+            ok: true,
+            msg: Some(msg),
+            fixed: true,
+            warning: false,
+            error: None,
+        }
+    }
+
+    /// 创建带告警的通过结果：不影响运行的问题，不计为失败
+    pub fn warning(msg: String) -> Self {
+        Self {
+            ok: true,
+            msg: Some(msg),
+            fixed: false,
+            warning: true,
+            error: None,
+        }
+    }
+
+    /// 通过且未经修复、无告警
+    pub fn passed(&self) -> bool {
+        self.ok && !self.fixed && !self.warning
+    }
+
     /// 创建一个显式跳过的检查结果
     pub fn skipped() -> Self {
         Self::success_with_message("skipped".to_string())
@@ -64,23 +219,40 @@ pub struct Row {
     pub path: String,
     /// 配置检查结果
     pub conf: Cell,
+    /// 引擎配置文件；含 include 时为全部参与合并的文件（`, ` 分隔，优先级由低到高）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub conf_detail: Option<String>,
+    /// 与 `conf_detail` 相同的文件列表（优先级由低到高），供机器读取
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conf_files: Vec<String>,
+    /// 配置引用的变量及其来源（敏感值已打码）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<VariableReport>,
     /// 连接器检查结果
     pub connectors: Cell,
     /// 连接器额外统计
     #[serde(skip_serializing_if = "Option::is_none")]
     pub connector_counts: Option<ConnectorCounts>,
+    /// sink 连接器交叉引用：未被路由使用的定义、引用了不存在连接器的路由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connector_xref: Option<ConnectorXref>,
     /// 输入源检查结果
     pub sources: Cell,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_checks: Option<SourceBreakdown>,
     /// 输出接收器检查结果
     pub sinks: Cell,
+    /// 路由 schema 与模型声明类型不一致的字段（仅告警）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_issues: Option<Vec<SchemaIssue>>,
     /// WPL 检查结果
     pub wpl: Cell,
     /// OML 检查结果
     pub oml: Cell,
+    /// OML/WPL 字段映射检查结果（未知字段仅告警，不判为失败）
+    pub mapping: Cell,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mapping_report: Option<MappingReport>,
     /// 语义词典配置检查结果
     pub semantic_dict: Cell,
 }
@@ -91,7 +263,12 @@ impl Row {
         Self {
             path,
             connector_counts: None,
+            connector_xref: None,
+            mapping_report: None,
+            schema_issues: None,
             conf_detail: None,
+            conf_files: Vec::new(),
+            variables: None,
             source_checks: None,
             ..Default::default()
         }
@@ -119,6 +296,9 @@ impl Row {
         if !self.oml.ok {
             count += 1;
         }
+        if !self.mapping.ok {
+            count += 1;
+        }
         if !self.semantic_dict.ok {
             count += 1;
         }
@@ -147,6 +327,9 @@ impl Row {
         if self.oml.ok {
             count += 1;
         }
+        if self.mapping.ok {
+            count += 1;
+        }
         if self.semantic_dict.ok {
             count += 1;
         }
@@ -162,6 +345,41 @@ pub struct ConnectorCounts {
     pub sink_routes: usize,
 }
 
+/// OML 模型读取/提取的字段不在其匹配的任一 WPL 规则输出中
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MappingIssue {
+    pub model: String,
+    pub file: String,
+    /// 字段名；带 `option` 候选时为 `a|b`
+    pub field: String,
+    /// 模型 `rule` 匹配到的 WPL 规则路径
+    pub rules: Vec<String>,
+}
+
+/// 字段映射检查汇总：`checked` 为参与分析的模型数，`skipped` 为未匹配规则
+/// 或匹配到含动态字段规则（json/kv 等）而排除的模型数
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct MappingReport {
+    pub checked: usize,
+    pub passed: usize,
+    pub skipped: usize,
+    pub issues: Vec<MappingIssue>,
+}
+
+/// 模型显式声明的字段类型与其所在路由的 schema 不一致
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SchemaIssue {
+    /// 路由组名
+    pub group: String,
+    pub model: String,
+    pub file: String,
+    pub field: String,
+    /// schema 要求的类型
+    pub expected: String,
+    /// 模型声明的类型
+    pub declared: String,
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct SourceBreakdown {
     pub syntax: Cell,
@@ -181,12 +399,105 @@ mod tests {
         assert_eq!(err.msg.as_deref(), Some("boom"));
     }
 
+    #[test]
+    fn cell_json_schema_is_stable() {
+        let cell = Cell::failure(
+            "parse oml failed\n[3:7] error: expected ';'\n --> models/oml/nginx.oml:3:7".into(),
+        )
+        .with_code(CheckErrorCode::OmlSyntax);
+        let json = serde_json::to_value(&cell).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ok": false,
+                "msg": cell.msg.clone().unwrap(),
+                "fixed": false,
+                "error": {
+                    "code": "OML_SYNTAX",
+                    "message": cell.msg.clone().unwrap(),
+                    "path": "models/oml/nginx.oml",
+                    "line": 3
+                }
+            })
+        );
+
+        let json = serde_json::to_value(Cell::success()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "ok": true, "msg": null, "fixed": false })
+        );
+    }
+
+    #[test]
+    fn warning_cell_is_ok_but_not_passed() {
+        let cell = Cell::warning("deprecated".into());
+        assert!(cell.ok && cell.warning);
+        assert!(!cell.passed());
+        assert!(cell.error_code().is_none());
+        assert_eq!(
+            serde_json::to_value(&cell).unwrap(),
+            serde_json::json!({ "ok": true, "msg": "deprecated", "fixed": false, "warning": true })
+        );
+    }
+
+    #[test]
+    fn error_location_is_extracted_from_messages() {
+        let wpl = CellError::new(
+            CheckErrorCode::WplSyntax,
+            "parse wpl failed: \"models/wpl/nginx/parse.wpl\": parse error at line 4, column 9"
+                .into(),
+        );
+        assert_eq!(wpl.path.as_deref(), Some("models/wpl/nginx/parse.wpl"));
+        assert_eq!(wpl.line, Some(4));
+
+        let plain = CellError::new(CheckErrorCode::Other, "boom".into());
+        assert_eq!((plain.path, plain.line), (None, None));
+    }
+
+    #[test]
+    fn legacy_consumers_still_read_msg() {
+        #[derive(serde::Deserialize)]
+        struct LegacyCell {
+            ok: bool,
+            msg: Option<String>,
+        }
+        let cell = Cell::failure("bad source".into()).with_code(CheckErrorCode::SourceInvalid);
+        let legacy: LegacyCell =
+            serde_json::from_str(&serde_json::to_string(&cell).unwrap()).unwrap();
+        assert!(!legacy.ok);
+        assert_eq!(legacy.msg.as_deref(), Some("bad source"));
+        assert_eq!(cell.error_code(), Some(CheckErrorCode::SourceInvalid));
+    }
+
     #[test]
     fn row_counts_failures_and_successes() {
         let mut row = Row::new("/tmp".into());
         row.sources = Cell::failure("bad".into());
         row.oml = Cell::failure("boom".into());
         assert_eq!(row.count_failures(), 2);
-        assert_eq!(row.count_successes(), 5);
+        assert_eq!(row.count_successes(), 6);
+    }
+
+    #[test]
+    fn row_json_includes_connector_xref_only_when_present() {
+        use wp_cli_core::business::connectors::sinks::DanglingConnectorRef;
+
+        let mut row = Row::new("/tmp".into());
+        let json = serde_json::to_value(&row).unwrap();
+        assert!(json.get("connector_xref").is_none());
+
+        row.connector_xref = Some(ConnectorXref {
+            unused: vec![],
+            dangling: vec![DanglingConnectorRef {
+                id: "missing_sink".into(),
+                file: "models/sinks/business.d/demo.toml".into(),
+                group: "demo".into(),
+            }],
+        });
+        let json = serde_json::to_value(&row).unwrap();
+        assert_eq!(
+            json["connector_xref"]["dangling"][0]["id"].as_str(),
+            Some("missing_sink")
+        );
     }
 }
@@ -0,0 +1,220 @@
+//! 覆盖率报告（与 `wproj prj coverage` 语义一致）：将工程全部 WPL 规则 / OML 模型
+//! 与统计文件中累计的规则命中合并，标记零命中规则、失效模型与仍被路由引用的禁用模型。
+
+use std::path::{Path, PathBuf};
+
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use orion_variate::EnvDict;
+use wildmatch::WildMatch;
+use wp_cli_core::business::coverage::{
+    CoverageInventory, CoverageReport, ModelEntry, RuleEntry, coverage,
+};
+use wp_cli_core::utils::pretty::print_coverage;
+use wp_cli_core::utils::stats::{StatsFile, load_stats_file};
+use wp_error::run_error::{RunReason, RunResult};
+
+use super::warp::WarpProject;
+
+#[derive(Debug, Clone, Default)]
+pub struct CoverageOptions {
+    /// 统计文件（JSON），可多个（如按节点或按天），命中数累加
+    pub stats_files: Vec<PathBuf>,
+    pub json: bool,
+}
+
+impl CoverageOptions {
+    pub fn new<P: AsRef<Path>>(stats_files: &[P]) -> Self {
+        Self {
+            stats_files: stats_files
+                .iter()
+                .map(|p| p.as_ref().to_path_buf())
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 生成覆盖率报告并输出（表格或 JSON）
+pub fn coverage_with(
+    project: &WarpProject,
+    opts: &CoverageOptions,
+    dict: &EnvDict,
+) -> RunResult<CoverageReport> {
+    let report = run_coverage(project, opts, dict)?;
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        print_coverage(&report);
+    }
+    Ok(report)
+}
+
+/// 生成覆盖率报告，仅返回报告
+pub fn run_coverage(
+    project: &WarpProject,
+    opts: &CoverageOptions,
+    dict: &EnvDict,
+) -> RunResult<CoverageReport> {
+    let mut stats = Vec::with_capacity(opts.stats_files.len());
+    for path in &opts.stats_files {
+        let file: StatsFile = load_stats_file(path).map_err(|e| {
+            RunReason::from_conf(format!("load stats file failed: {:#}", e)).to_err()
+        })?;
+        stats.push(file);
+    }
+    let inventory = build_inventory(project, dict)?;
+    Ok(coverage(&inventory, &stats))
+}
+
+/// 收集工程的规则与模型清单（含禁用模型），以及业务路由对模型的引用
+pub fn build_inventory(project: &WarpProject, dict: &EnvDict) -> RunResult<CoverageInventory> {
+    let rules: Vec<RuleEntry> = project
+        .wpl()
+        .rule_files()?
+        .into_iter()
+        .map(|(rule, file)| RuleEntry {
+            rule,
+            modified: std::fs::metadata(&file).and_then(|m| m.modified()).ok(),
+            file: file.display().to_string(),
+        })
+        .collect();
+
+    let routes = project.sinks_c().route_rows(&[], &[], dict)?;
+    let mut models = Vec::new();
+    for (file, model) in project.oml().all_models()? {
+        let name = model.name().to_string();
+        let matched = rules
+            .iter()
+            .filter(|r| model.rules().as_ref().iter().any(|w| w.matches(&r.rule)))
+            .map(|r| r.rule.clone())
+            .collect();
+        // 通配 `*` 只是兜底路由，不视为对具体模型的引用
+        let mut routed_by: Vec<String> = routes
+            .iter()
+            .filter(|row| row.scope == "biz")
+            .filter(|row| {
+                row.oml
+                    .iter()
+                    .any(|pat| pat != "*" && WildMatch::new(pat).matches(&name))
+            })
+            .map(|row| row.group.clone())
+            .collect();
+        routed_by.dedup();
+        models.push(ModelEntry {
+            name,
+            file: file.display().to_string(),
+            enabled: *model.enable(),
+            rules: matched,
+            routed_by,
+        });
+    }
+    Ok(CoverageInventory { rules, models })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_basic_wparse_config, write_file};
+    use wp_cli_core::business::coverage::CoverageStatus;
+    use wp_conf::test_support::ForTest;
+
+    fn coverage_project(root: &Path) {
+        write_basic_wparse_config(root);
+        write_file(
+            root,
+            "models/wpl/parse.wpl",
+            include_str!("../example/wpl/nginx/parse.wpl"),
+        );
+        write_file(
+            root,
+            "models/wpl/extra.wpl",
+            "package /extra/ {\n   rule unused {\n        (digit:code)\n   }\n}\n",
+        );
+        write_file(
+            root,
+            "models/oml/nginx.oml",
+            include_str!("../example/oml/nginx.oml"),
+        );
+        write_file(
+            root,
+            "models/oml/extra.oml",
+            "name : extra\nrule : /extra/*\n---\n* : auto = take() ;\n",
+        );
+        write_file(
+            root,
+            "models/oml/legacy.oml",
+            "name : legacy\nrule : /legacy/*\nenable : false\n---\n* : auto = take() ;\n",
+        );
+        write_file(
+            root,
+            "connectors/sink.d/00-file.toml",
+            "[[connectors]]\nid = \"file_json_sink\"\ntype = \"file\"\nallow_override = [\"file\"]\n",
+        );
+        write_file(
+            root,
+            "models/sinks/business.d/legacy.toml",
+            "version = \"2.0\"\n\n[sink_group]\nname = \"legacy_out\"\noml = [\"legacy\"]\n\n[[sink_group.sinks]]\nname = \"out\"\nconnect = \"file_json_sink\"\nparams = { file = \"legacy.json\" }\n",
+        );
+    }
+
+    #[test]
+    fn coverage_joins_inventory_with_stats() {
+        let tmp = temp_workdir();
+        coverage_project(tmp.path());
+        let project = WarpProject::bare(tmp.path());
+        let dict = EnvDict::test_default();
+
+        let inventory = build_inventory(&project, &dict).expect("inventory");
+        let nginx_rule = inventory
+            .rules
+            .iter()
+            .find(|r| r.rule.starts_with("/nginx/"))
+            .expect("nginx rule")
+            .rule
+            .clone();
+        let stats = write_file(
+            tmp.path(),
+            "data/stats/rules.json",
+            &format!(
+                "{{\"window_start\":\"2000-01-01T00:00:00Z\",\"rules\":[{{\"name\":\"{}\",\"hits\":42,\"last_seen\":\"2000-01-02T00:00:00Z\"}}]}}",
+                nginx_rule
+            ),
+        );
+
+        let report =
+            run_coverage(&project, &CoverageOptions::new(&[&stats]), &dict).expect("coverage");
+        let rule = |prefix: &str| {
+            report
+                .rules
+                .iter()
+                .find(|r| r.rule.starts_with(prefix))
+                .unwrap()
+        };
+        assert_eq!(rule("/nginx/").hits, 42);
+        assert_eq!(rule("/nginx/").status, CoverageStatus::Ok);
+        // 规则文件在统计窗口开始后才写入
+        assert_eq!(rule("/extra/").status, CoverageStatus::New);
+
+        let model = |name: &str| report.models.iter().find(|m| m.model == name).unwrap();
+        assert_eq!(model("nginx").status, CoverageStatus::Ok);
+        assert_eq!(model("extra").status, CoverageStatus::New);
+        assert_eq!(model("legacy").status, CoverageStatus::DisabledReferenced);
+        assert_eq!(model("legacy").routed_by, vec!["legacy_out".to_string()]);
+        assert_eq!(report.summary.disabled_referenced, 1);
+
+        // 窗口在文件修改之后开始：未命中规则为 zero-hit，模型失效
+        let stats = write_file(
+            tmp.path(),
+            "data/stats/later.json",
+            "{\"window_start\":\"2999-01-01T00:00:00Z\",\"rules\":[]}",
+        );
+        let report =
+            run_coverage(&project, &CoverageOptions::new(&[&stats]), &dict).expect("coverage");
+        assert_eq!(report.summary.zero_hit, 2);
+        assert_eq!(report.summary.dead, 2);
+
+        let missing = tmp.path().join("data/stats/missing.json");
+        assert!(run_coverage(&project, &CoverageOptions::new(&[&missing]), &dict).is_err());
+    }
+}
@@ -0,0 +1,566 @@
+//! 工程差异（与 `wproj prj diff` 语义一致）：比较两个工作目录的 WPL 规则、OML 模型、
+//! 连接器定义与引擎配置项，用于规则包提升（如预发布 → 生产）前的变更审查。
+//!
+//! - 两侧均只做语法级加载（`PrjScope::Model`），不检查连接器连通性；
+//! - 文件与条目按内容哈希（md5）比较；一侧删除、另一侧新增且内容相同的视为改名；
+//! - OML 模型额外给出目标级差异（新增/删除/修改的输出目标）。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use comfy_table::{Cell as TCell, ContentArrangement, Table, presets::UTF8_FULL};
+use oml::language::ObjModel;
+use orion_conf::UvsConfFrom;
+use orion_error::{ErrorConv, ToStructError};
+use orion_variate::EnvDict;
+use serde::Serialize;
+use wp_conf::connectors::{
+    ConnectorDef, ConnectorScope, load_connector_defs_from_dir, param_map_to_table,
+};
+use wp_engine::facade::config::ENGINE_CONF_FILE;
+use wp_error::run_error::{RunReason, RunResult};
+
+use super::init::PrjScope;
+use super::warp::WarpProject;
+
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffComponent {
+    Wpl,
+    Oml,
+    Connectors,
+    Conf,
+}
+
+impl DiffComponent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffComponent::Wpl => "wpl",
+            DiffComponent::Oml => "oml",
+            DiffComponent::Connectors => "connectors",
+            DiffComponent::Conf => "conf",
+        }
+    }
+
+    /// 条目类型名（表格输出用）
+    fn item_label(&self) -> &'static str {
+        match self {
+            DiffComponent::Wpl => "rule",
+            DiffComponent::Oml => "model",
+            DiffComponent::Connectors => "connector",
+            DiffComponent::Conf => "key",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+    Renamed,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Renamed => "renamed",
+        }
+    }
+}
+
+/// 单个文件或条目的变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffChange {
+    pub kind: ChangeKind,
+    /// 右侧名称（删除时为左侧名称）
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+    /// 左侧摘要：文件/规则/模型/连接器为内容 md5，配置项为值本身
+    pub left: Option<String>,
+    pub right: Option<String>,
+    /// OML 模型的目标级差异
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets_added: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets_removed: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets_modified: Vec<String>,
+}
+
+impl DiffChange {
+    fn new(kind: ChangeKind, name: &str, left: Option<&String>, right: Option<&String>) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            renamed_from: None,
+            left: left.cloned(),
+            right: right.cloned(),
+            targets_added: Vec::new(),
+            targets_removed: Vec::new(),
+            targets_modified: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentDiff {
+    pub component: DiffComponent,
+    /// 文件级变化（路径相对于组件根目录）
+    pub files: Vec<DiffChange>,
+    /// 条目级变化（规则、模型、连接器、配置项）
+    pub items: Vec<DiffChange>,
+}
+
+impl ComponentDiff {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.items.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectDiff {
+    pub left: String,
+    pub right: String,
+    pub components: Vec<ComponentDiff>,
+}
+
+impl ProjectDiff {
+    pub fn has_changes(&self) -> bool {
+        self.components.iter().any(|c| !c.is_empty())
+    }
+
+    /// 进程退出码：有差异为 1，便于在 CI 中作为提升门禁
+    pub fn exit_code(&self) -> i32 {
+        i32::from(self.has_changes())
+    }
+
+    pub fn component(&self, component: DiffComponent) -> Option<&ComponentDiff> {
+        self.components.iter().find(|c| c.component == component)
+    }
+}
+
+/// 比较两个工程并输出（表格或 JSON）
+pub fn diff_with(
+    left: &Path,
+    right: &Path,
+    opts: &DiffOptions,
+    dict: &EnvDict,
+) -> RunResult<ProjectDiff> {
+    let diff = diff_projects(left, right, dict)?;
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+    } else {
+        print_table(&diff);
+    }
+    Ok(diff)
+}
+
+/// 比较两个工程，仅返回差异
+pub fn diff_projects(left: &Path, right: &Path, dict: &EnvDict) -> RunResult<ProjectDiff> {
+    let l = Snapshot::collect(&open_project(left, dict)?, dict)?;
+    let r = Snapshot::collect(&open_project(right, dict)?, dict)?;
+
+    let mut oml_items = diff_entries(&l.oml_items, &r.oml_items, true);
+    for change in &mut oml_items {
+        if !matches!(change.kind, ChangeKind::Modified | ChangeKind::Renamed) {
+            continue;
+        }
+        let from = change.renamed_from.as_deref().unwrap_or(&change.name);
+        if let (Some(lt), Some(rt)) = (l.oml_targets.get(from), r.oml_targets.get(&change.name)) {
+            diff_targets(lt, rt, change);
+        }
+    }
+
+    let components = vec![
+        ComponentDiff {
+            component: DiffComponent::Wpl,
+            files: diff_entries(&l.wpl_files, &r.wpl_files, true),
+            items: diff_entries(&l.wpl_items, &r.wpl_items, true),
+        },
+        ComponentDiff {
+            component: DiffComponent::Oml,
+            files: diff_entries(&l.oml_files, &r.oml_files, true),
+            items: oml_items,
+        },
+        ComponentDiff {
+            component: DiffComponent::Connectors,
+            files: diff_entries(&l.conn_files, &r.conn_files, true),
+            items: diff_entries(&l.conn_items, &r.conn_items, true),
+        },
+        // 配置项以值比较，值相同不代表改名
+        ComponentDiff {
+            component: DiffComponent::Conf,
+            files: diff_entries(&l.conf_files, &r.conf_files, true),
+            items: diff_entries(&l.conf_items, &r.conf_items, false),
+        },
+    ];
+    Ok(ProjectDiff {
+        left: left.display().to_string(),
+        right: right.display().to_string(),
+        components,
+    })
+}
+
+/// 仅接受已有工程：缺少主配置时报错，避免加载过程按默认值初始化目录
+fn open_project(root: &Path, dict: &EnvDict) -> RunResult<WarpProject> {
+    let conf = root.join("conf").join(ENGINE_CONF_FILE);
+    if !conf.is_file() {
+        return Err(
+            RunReason::from_conf(format!("not a project: missing {}", conf.display())).to_err(),
+        );
+    }
+    WarpProject::load(root, PrjScope::Model, dict)
+}
+
+type Entries = BTreeMap<String, String>;
+
+/// 一侧工程的内容摘要
+#[derive(Default)]
+struct Snapshot {
+    wpl_files: Entries,
+    wpl_items: Entries,
+    oml_files: Entries,
+    oml_items: Entries,
+    oml_targets: BTreeMap<String, Entries>,
+    conn_files: Entries,
+    conn_items: Entries,
+    conf_files: Entries,
+    conf_items: Entries,
+}
+
+impl Snapshot {
+    fn collect(project: &WarpProject, dict: &EnvDict) -> RunResult<Self> {
+        let mut snap = Snapshot {
+            wpl_files: file_hashes(&project.wpl().rule_root(), "wpl")?,
+            oml_files: file_hashes(&project.oml().oml_root(), "oml")?,
+            conn_files: file_hashes(&project.paths().connectors.base, "toml")?,
+            conf_files: file_hashes(&project.paths().conf_dir, "toml")?,
+            ..Default::default()
+        };
+        for (rule, _, body) in project.wpl().rule_bodies()? {
+            snap.wpl_items.insert(rule, digest(body.as_bytes()));
+        }
+        for (_, model) in project.oml().all_models()? {
+            snap.oml_items
+                .insert(model.name().clone(), model_digest(&model));
+            snap.oml_targets.insert(
+                model.name().clone(),
+                model.output_targets().into_iter().collect(),
+            );
+        }
+        let paths = &project.paths().connectors;
+        for (dir, scope, prefix) in [
+            (&paths.source_dir, ConnectorScope::Source, "source"),
+            (&paths.sink_dir, ConnectorScope::Sink, "sink"),
+        ] {
+            for def in load_connector_defs_from_dir(dir, scope, dict).err_conv()? {
+                snap.conn_items
+                    .insert(format!("{}:{}", prefix, def.id), connector_digest(&def));
+            }
+        }
+        let conf = project.paths().conf_dir.join(ENGINE_CONF_FILE);
+        let text = std::fs::read_to_string(&conf).map_err(|e| {
+            RunReason::from_conf(format!("read {}: {}", conf.display(), e)).to_err()
+        })?;
+        let value: toml::Value = toml::from_str(&text).map_err(|e| {
+            RunReason::from_conf(format!("parse {}: {}", conf.display(), e)).to_err()
+        })?;
+        flatten_toml("", &value, &mut snap.conf_items);
+        Ok(snap)
+    }
+}
+
+fn digest(bytes: &[u8]) -> String {
+    format!("{:x}", md5::compute(bytes))
+}
+
+/// `root` 下全部 `*.<ext>` 文件的 md5，键为相对路径；目录不存在时为空
+fn file_hashes(root: &Path, ext: &str) -> RunResult<Entries> {
+    let pattern = format!("{}/**/*.{}", root.display(), ext);
+    let paths = glob::glob(&pattern)
+        .map_err(|e| RunReason::from_conf(format!("glob {}: {}", pattern, e)).to_err())?;
+    let mut out = Entries::new();
+    for path in paths.flatten() {
+        let bytes = std::fs::read(&path).map_err(|e| {
+            RunReason::from_conf(format!("read {}: {}", path.display(), e)).to_err()
+        })?;
+        let rel = path.strip_prefix(root).unwrap_or(&path);
+        out.insert(rel.to_string_lossy().replace('\\', "/"), digest(&bytes));
+    }
+    Ok(out)
+}
+
+/// 模型内容摘要：不含模型名，以便识别改名
+fn model_digest(model: &ObjModel) -> String {
+    let text = model.to_string();
+    let body = text.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+    digest(format!("enable={}\n{}", model.enable(), body).as_bytes())
+}
+
+fn connector_digest(def: &ConnectorDef) -> String {
+    let params = toml::Value::Table(param_map_to_table(&def.default_params));
+    digest(format!("{}\n{:?}\n{}", def.kind, def.allow_override, params).as_bytes())
+}
+
+/// 展开为 `a.b.c = 值` 形式；数组按整体比较
+fn flatten_toml(prefix: &str, value: &toml::Value, out: &mut Entries) {
+    match value {
+        toml::Value::Table(table) => {
+            for (k, v) in table {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_toml(&key, v, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+/// 比较两侧条目；`detect_renames` 时将内容相同的删除/新增配对为改名
+fn diff_entries(left: &Entries, right: &Entries, detect_renames: bool) -> Vec<DiffChange> {
+    let mut changes = Vec::new();
+    let mut removed: Vec<&String> = left.keys().filter(|k| !right.contains_key(*k)).collect();
+    for (name, r) in right {
+        match left.get(name) {
+            Some(l) if l != r => changes.push(DiffChange::new(
+                ChangeKind::Modified,
+                name,
+                Some(l),
+                Some(r),
+            )),
+            Some(_) => {}
+            None => {
+                let from = removed
+                    .iter()
+                    .position(|old| detect_renames && left[*old] == *r)
+                    .map(|idx| removed.remove(idx));
+                match from {
+                    Some(old) => {
+                        let mut change =
+                            DiffChange::new(ChangeKind::Renamed, name, Some(&left[old]), Some(r));
+                        change.renamed_from = Some(old.clone());
+                        changes.push(change);
+                    }
+                    None => changes.push(DiffChange::new(ChangeKind::Added, name, None, Some(r))),
+                }
+            }
+        }
+    }
+    for name in removed {
+        changes.push(DiffChange::new(
+            ChangeKind::Removed,
+            name,
+            left.get(name),
+            None,
+        ));
+    }
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
+
+fn diff_targets(left: &Entries, right: &Entries, change: &mut DiffChange) {
+    for (name, text) in right {
+        match left.get(name) {
+            None => change.targets_added.push(name.clone()),
+            Some(old) if old != text => change.targets_modified.push(name.clone()),
+            Some(_) => {}
+        }
+    }
+    change.targets_removed = left
+        .keys()
+        .filter(|k| !right.contains_key(*k))
+        .cloned()
+        .collect();
+}
+
+fn print_table(diff: &ProjectDiff) {
+    println!("left : {}", diff.left);
+    println!("right: {}", diff.right);
+    if !diff.has_changes() {
+        println!("no differences");
+        return;
+    }
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        TCell::new("Component"),
+        TCell::new("Type"),
+        TCell::new("Change"),
+        TCell::new("Name"),
+        TCell::new("Detail"),
+    ]);
+    let mut total = 0;
+    for comp in &diff.components {
+        let rows = comp
+            .files
+            .iter()
+            .map(|c| ("file", c))
+            .chain(comp.items.iter().map(|c| (comp.component.item_label(), c)));
+        for (label, change) in rows {
+            total += 1;
+            table.add_row(vec![
+                TCell::new(comp.component.as_str()),
+                TCell::new(label),
+                TCell::new(change.kind.as_str()),
+                TCell::new(&change.name),
+                TCell::new(change_detail(comp.component, label, change)),
+            ]);
+        }
+    }
+    println!("{}", table);
+    println!("{} change(s)", total);
+}
+
+fn change_detail(component: DiffComponent, label: &str, change: &DiffChange) -> String {
+    let mut parts = Vec::new();
+    if let Some(from) = &change.renamed_from {
+        parts.push(format!("from {}", from));
+    }
+    if component == DiffComponent::Conf && label != "file" {
+        let side = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+        parts.push(format!("{} -> {}", side(&change.left), side(&change.right)));
+    }
+    for (sign, targets) in [
+        ('+', &change.targets_added),
+        ('-', &change.targets_removed),
+        ('~', &change.targets_modified),
+    ] {
+        if !targets.is_empty() {
+            parts.push(format!("{}{}", sign, targets.join(&format!(" {}", sign))));
+        }
+    }
+    parts.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_basic_wparse_config, write_file};
+    use wp_conf::test_support::ForTest;
+
+    const NGINX_WPL: &str = include_str!("../example/wpl/nginx/parse.wpl");
+
+    fn base_project(root: &Path) {
+        write_basic_wparse_config(root);
+        write_file(root, "models/wpl/nginx/parse.wpl", NGINX_WPL);
+        write_file(
+            root,
+            "models/oml/extra.oml",
+            "name : extra\nrule : /nginx/*\n---\nsip : ip = take() ;\nstatus : digit = take() ;\nsize : chars = take() ;\n",
+        );
+        write_file(
+            root,
+            "connectors/sink.d/00-file.toml",
+            "[[connectors]]\nid = \"file_json_sink\"\ntype = \"file\"\nallow_override = [\"file\"]\n",
+        );
+    }
+
+    fn change<'a>(changes: &'a [DiffChange], name: &str) -> &'a DiffChange {
+        changes
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("no change for {}: {:?}", name, changes))
+    }
+
+    #[test]
+    fn identical_projects_have_no_changes() {
+        let (a, b) = (temp_workdir(), temp_workdir());
+        base_project(a.path());
+        base_project(b.path());
+        let diff = diff_projects(a.path(), b.path(), &EnvDict::test_default()).expect("diff");
+        assert!(!diff.has_changes(), "{:?}", diff);
+        assert_eq!(diff.exit_code(), 0);
+    }
+
+    #[test]
+    fn diff_reports_changes_per_component() {
+        let (a, b) = (temp_workdir(), temp_workdir());
+        base_project(a.path());
+        base_project(b.path());
+        let rb = b.path();
+        // WPL：文件移动到新目录，内容不变
+        std::fs::remove_file(rb.join("models/wpl/nginx/parse.wpl")).unwrap();
+        write_file(rb, "models/wpl/web/parse.wpl", NGINX_WPL);
+        // OML：新增 dport、删除 size、修改 status 的类型
+        write_file(
+            rb,
+            "models/oml/extra.oml",
+            "name : extra\nrule : /nginx/*\n---\nsip : ip = take() ;\nstatus : chars = take() ;\ndport : digit = take() ;\n",
+        );
+        write_file(
+            rb,
+            "connectors/source.d/00-file.toml",
+            "[[connectors]]\nid = \"file_src\"\ntype = \"file\"\nallow_override = [\"path\"]\n",
+        );
+        let conf = rb.join("conf/wparse.toml");
+        let text = std::fs::read_to_string(&conf).unwrap();
+        std::fs::write(
+            &conf,
+            text.replacen("level = \"info\"", "level = \"debug\"", 1),
+        )
+        .unwrap();
+
+        let diff = diff_projects(a.path(), rb, &EnvDict::test_default()).expect("diff");
+        assert!(diff.has_changes());
+        assert_eq!(diff.exit_code(), 1);
+
+        let wpl = diff.component(DiffComponent::Wpl).unwrap();
+        let moved = change(&wpl.files, "web/parse.wpl");
+        assert_eq!(moved.kind, ChangeKind::Renamed);
+        assert_eq!(moved.renamed_from.as_deref(), Some("nginx/parse.wpl"));
+        assert!(wpl.items.is_empty());
+
+        let oml = diff.component(DiffComponent::Oml).unwrap();
+        assert_eq!(change(&oml.files, "extra.oml").kind, ChangeKind::Modified);
+        let model = change(&oml.items, "extra");
+        assert_eq!(model.kind, ChangeKind::Modified);
+        assert_eq!(model.targets_added, vec!["dport".to_string()]);
+        assert_eq!(model.targets_removed, vec!["size".to_string()]);
+        assert_eq!(model.targets_modified, vec!["status".to_string()]);
+
+        let conn = diff.component(DiffComponent::Connectors).unwrap();
+        assert_eq!(
+            change(&conn.items, "source:file_src").kind,
+            ChangeKind::Added
+        );
+        assert_eq!(
+            change(&conn.files, "source.d/00-file.toml").kind,
+            ChangeKind::Added
+        );
+
+        let conf = diff.component(DiffComponent::Conf).unwrap();
+        assert_eq!(
+            change(&conf.files, "wparse.toml").kind,
+            ChangeKind::Modified
+        );
+        let level = change(&conf.items, "log.level");
+        assert_eq!(level.kind, ChangeKind::Modified);
+        assert_eq!(level.left.as_deref(), Some("\"info\""));
+        assert_eq!(level.right.as_deref(), Some("\"debug\""));
+    }
+
+    #[test]
+    fn diff_rejects_missing_project() {
+        let (a, b) = (temp_workdir(), temp_workdir());
+        base_project(a.path());
+        assert!(diff_projects(a.path(), b.path(), &EnvDict::test_default()).is_err());
+        assert!(!b.path().join("conf").exists());
+    }
+}
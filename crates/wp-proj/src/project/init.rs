@@ -192,14 +192,22 @@ impl WarpProject {
         let semantic_dict_config_path = work_root.join(SEMANTIC_DICT_FILE);
         if !semantic_dict_config_path.exists() {
             // 从 wp-oml 获取默认配置内容
-            let config_content = oml::generate_default_semantic_dict_config();
-            if let Err(e) = fs::write(&semantic_dict_config_path, config_content) {
-                eprintln!("Warning: Failed to write semantic_dict.toml: {}", e);
-            } else {
-                println!(
-                    "✓ 语义词典配置文件已创建: {}",
-                    semantic_dict_config_path.display()
-                );
+            match oml::generate_default_semantic_dict_config() {
+                Ok(config_content) => {
+                    if let Err(e) = fs::write(&semantic_dict_config_path, config_content) {
+                        eprintln!("Warning: Failed to write semantic_dict.toml: {}", e);
+                    } else {
+                        println!(
+                            "✓ 语义词典配置文件已创建: {}",
+                            semantic_dict_config_path.display()
+                        );
+                    }
+                }
+                Err(errors) => {
+                    for e in errors {
+                        eprintln!("Warning: Invalid default semantic_dict.toml: {}", e);
+                    }
+                }
             }
         }
 
@@ -223,7 +231,7 @@ impl WarpProject {
             let conf = EngineConfig::init(&abs_root);
             conf.save_toml(&engine_config_path).owe_conf()?;
         }
-        let conf = EngineConfig::env_load_toml(&engine_config_path, dict)
+        let conf = EngineConfig::load_expanded(&engine_config_path, dict)
             .owe_conf()?
             .conf_absolutize(&abs_root);
         Ok(conf)
@@ -243,7 +251,7 @@ impl WarpProject {
             ))
             .err_result();
         }
-        let conf = EngineConfig::env_load_toml(&engine_config_path, dict)
+        let conf = EngineConfig::load_expanded(&engine_config_path, dict)
             .owe_conf()?
             .conf_absolutize(&abs_root);
         Ok(conf)
@@ -1,8 +1,12 @@
 // Project management: 项目管理模块（统一管理项目相关的所有功能）
 pub mod checker;
+mod coverage;
+mod diff;
 pub mod init;
+mod scaffold;
 //pub mod summary;
 pub mod tests;
+mod trial;
 pub mod warp;
 
 // Re-export for backward compatibility - now from their new modules
@@ -11,6 +15,14 @@ pub use super::models::{Oml, Wpl};
 pub use super::sinks::Sinks;
 pub use super::sources::Sources;
 pub use checker::{
-    Cell, CheckComponent, CheckComponents, CheckOptions, ConnectorCounts, Row, SourceBreakdown,
+    CHECK_SCHEMA_VERSION, Cell, CellError, CheckComponent, CheckComponents, CheckErrorCode,
+    CheckOptions, ConnectorCounts, Row, SourceBreakdown,
 };
+pub use coverage::{CoverageOptions, build_inventory, coverage_with, run_coverage};
+pub use diff::{
+    ChangeKind, ComponentDiff, DiffChange, DiffComponent, DiffOptions, ProjectDiff, diff_projects,
+    diff_with,
+};
+pub use scaffold::{DeviceProtocol, ScaffoldOptions, scaffold_device};
+pub use trial::{TrialOptions, run_trial, trial_with};
 pub use warp::WarpProject;
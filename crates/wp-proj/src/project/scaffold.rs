@@ -0,0 +1,231 @@
+//! 设备脚手架（与 `wproj scaffold` 语义一致）：按协议模板为新设备类型生成 WPL 包、
+//! 对应的 OML 模型与样本数据，免去手工复制已有包。
+//!
+//! - WPL：`<rule_root>/<name>/parse_<name>_base.wpl`（带 `parse` 前缀以匹配规则加载模式
+//!   `parse*.wpl`），包名 `/<name>/`，规则 `base` 带 `device`/`protocol` 标签；
+//! - 样本：同目录 `sample.dat`，可直接用于样本解析校验与 `prj trial`；
+//! - OML：`<oml_root>/<name>.oml`，`rule : /<name>/*`。
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use wp_error::run_error::RunResult;
+use wp_error::{RunError, RunReason};
+
+use super::warp::WarpProject;
+use crate::utils::TemplateInitializer;
+
+const NAME_PLACEHOLDER: &str = "{{name}}";
+const MODEL_TEMPLATE: &str = include_str!("../example/scaffold/model.oml");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceProtocol {
+    /// syslog 载荷为 `key=value` 序列
+    SyslogKv,
+    Cef,
+    Json,
+}
+
+impl DeviceProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeviceProtocol::SyslogKv => "syslog-kv",
+            DeviceProtocol::Cef => "cef",
+            DeviceProtocol::Json => "json",
+        }
+    }
+
+    fn rule_template(&self) -> &'static str {
+        match self {
+            DeviceProtocol::SyslogKv => include_str!("../example/scaffold/syslog_kv/parse.wpl"),
+            DeviceProtocol::Cef => include_str!("../example/scaffold/cef/parse.wpl"),
+            DeviceProtocol::Json => include_str!("../example/scaffold/json/parse.wpl"),
+        }
+    }
+
+    fn sample_template(&self) -> &'static str {
+        match self {
+            DeviceProtocol::SyslogKv => include_str!("../example/scaffold/syslog_kv/sample.dat"),
+            DeviceProtocol::Cef => include_str!("../example/scaffold/cef/sample.dat"),
+            DeviceProtocol::Json => include_str!("../example/scaffold/json/sample.dat"),
+        }
+    }
+}
+
+impl Display for DeviceProtocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DeviceProtocol {
+    type Err = RunError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "syslog-kv" => Ok(Self::SyslogKv),
+            "cef" => Ok(Self::Cef),
+            "json" => Ok(Self::Json),
+            _ => Err(RunReason::from_conf(format!(
+                "unknown protocol '{}', expected syslog-kv | cef | json",
+                s
+            ))
+            .to_err()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScaffoldOptions {
+    /// 覆盖已存在的文件
+    pub force: bool,
+}
+
+/// 为设备 `name` 生成 WPL 包、样本与 OML 模型，返回生成的文件
+///
+/// 任一目标文件已存在且未指定 `force` 时不写入任何文件。
+pub fn scaffold_device(
+    project: &WarpProject,
+    name: &str,
+    protocol: DeviceProtocol,
+    opts: &ScaffoldOptions,
+) -> RunResult<Vec<PathBuf>> {
+    check_device_name(name)?;
+    let pkg_dir = project.wpl().rule_root().join(name);
+    let oml_dir = project.oml().oml_root();
+    let rule_file = format!("parse_{}_base.wpl", name);
+    let model_file = format!("{}.oml", name);
+    let render = |tpl: &str| tpl.replace(NAME_PLACEHOLDER, name);
+
+    let files = vec![
+        pkg_dir.join(&rule_file),
+        pkg_dir.join("sample.dat"),
+        oml_dir.join(&model_file),
+    ];
+    if !opts.force {
+        let existing: Vec<String> = files
+            .iter()
+            .filter(|f| f.exists())
+            .map(|f| f.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            return Err(RunReason::from_conf(format!(
+                "refuse to overwrite existing files (use --force): {}",
+                existing.join(", ")
+            ))
+            .to_err());
+        }
+    }
+
+    TemplateInitializer::new(pkg_dir).write_files(&[
+        (
+            rule_file.as_str(),
+            render(protocol.rule_template()).as_str(),
+        ),
+        ("sample.dat", protocol.sample_template()),
+    ])?;
+    TemplateInitializer::new(oml_dir).write_file(&model_file, &render(MODEL_TEMPLATE))?;
+    Ok(files)
+}
+
+/// 设备名用作包名、目录名与模型名：字母开头，仅含字母、数字与下划线
+fn check_device_name(name: &str) -> RunResult<()> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(RunReason::from_conf(format!(
+            "invalid device name '{}': use letters, digits and '_', starting with a letter",
+            name
+        ))
+        .to_err())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::checker::{CheckOptions, check_with_default};
+    use crate::project::init::PrjScope;
+    use crate::project::{TrialOptions, run_trial};
+    use crate::test_utils::temp_workdir;
+    use orion_variate::EnvDict;
+    use wp_conf::test_support::ForTest;
+
+    const DEVICES: [(&str, DeviceProtocol); 3] = [
+        ("fw_kv", DeviceProtocol::SyslogKv),
+        ("fw_cef", DeviceProtocol::Cef),
+        ("fw_json", DeviceProtocol::Json),
+    ];
+
+    #[test]
+    fn scaffolded_devices_pass_check_and_trial() {
+        let tmp = temp_workdir();
+        let dict = EnvDict::test_default();
+        let project = WarpProject::init(tmp.path(), PrjScope::Full, &dict).expect("init");
+
+        for (name, protocol) in DEVICES {
+            let files = scaffold_device(&project, name, protocol, &ScaffoldOptions::default())
+                .expect("scaffold");
+            assert!(files.iter().all(|f| f.exists()), "{:?}", files);
+        }
+        check_with_default(&project, &CheckOptions::new(tmp.path()), &dict).expect("check");
+
+        for (name, _) in DEVICES {
+            let sample = project.wpl().rule_root().join(name).join("sample.dat");
+            let report = run_trial(&project, &TrialOptions::new(&sample), &dict).expect("trial");
+            assert_eq!(report.summary.miss, 0, "{}: {:?}", name, report.lines);
+            for line in &report.lines {
+                assert!(
+                    line.rule
+                        .as_deref()
+                        .is_some_and(|r| r.starts_with(&format!("/{}/", name))),
+                    "{}: {:?}",
+                    name,
+                    line
+                );
+                assert_eq!(line.model.as_deref(), Some(name));
+            }
+        }
+    }
+
+    #[test]
+    fn scaffold_refuses_to_overwrite_without_force() {
+        let tmp = temp_workdir();
+        let dict = EnvDict::test_default();
+        let project = WarpProject::init(tmp.path(), PrjScope::Normal, &dict).expect("init");
+        let opts = ScaffoldOptions::default();
+        scaffold_device(&project, "fw", DeviceProtocol::Json, &opts).expect("scaffold");
+
+        let model = project.oml().oml_root().join("fw.oml");
+        std::fs::write(&model, "edited").unwrap();
+        assert!(scaffold_device(&project, "fw", DeviceProtocol::Cef, &opts).is_err());
+        assert_eq!(std::fs::read_to_string(&model).unwrap(), "edited");
+
+        let force = ScaffoldOptions { force: true };
+        scaffold_device(&project, "fw", DeviceProtocol::Cef, &force).expect("force");
+        assert!(
+            std::fs::read_to_string(&model)
+                .unwrap()
+                .contains("rule : /fw/*")
+        );
+    }
+
+    #[test]
+    fn protocol_and_name_validation() {
+        assert_eq!(
+            "syslog-kv".parse::<DeviceProtocol>().unwrap(),
+            DeviceProtocol::SyslogKv
+        );
+        assert!("leef".parse::<DeviceProtocol>().is_err());
+        assert!(check_device_name("fw_01").is_ok());
+        for bad in ["", "1fw", "fw-01", "../fw"] {
+            assert!(check_device_name(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -672,8 +672,10 @@ mod tests {
             what: "all".to_string(),
             console: false,
             fail_fast: false,
+            fail_fast_component: None,
             json: false,
             only_fail: false,
+            fix: false,
         };
         let comps = CheckComponents::default();
 
@@ -703,6 +705,136 @@ mod tests {
         cleanup_test_dir(&work);
     }
 
+    #[test]
+    fn test_check_flags_dangling_connector_refs() {
+        let work = uniq_tmp_dir();
+
+        create_minimal_project_structure(&work);
+        create_basic_wparse_config(&work);
+        fs::write(
+            format!("{}/connectors/sink.d/00-file.toml", work),
+            "[[connectors]]\nid = \"file_json_sink\"\ntype = \"file\"\nallow_override = [\"file\"]\n\n[[connectors]]\nid = \"file_raw_sink\"\ntype = \"file\"\n",
+        )
+        .unwrap();
+        let route = |name: &str, connect: &str| {
+            format!(
+                "version = \"2.0\"\n\n[sink_group]\nname = \"{}\"\n\n[[sink_group.sinks]]\nname = \"out\"\nconnect = \"{}\"\nparams = {{ file = \"out.json\" }}\n",
+                name, connect
+            )
+        };
+        fs::write(
+            format!("{}/models/sinks/business.d/demo.toml", work),
+            route("demo", "file_json_sink"),
+        )
+        .unwrap();
+
+        let project = WarpProject::bare(&work);
+        let opts = CheckOptions::new(&work);
+        let comps = CheckComponents::default().with_only([CheckComponent::Connectors]);
+        let dict = EnvDict::test_default();
+
+        // 仅有未使用的定义（file_raw_sink）：提示但不失败
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_ok());
+
+        // 路由引用不存在的连接器：连接器检查失败
+        fs::write(
+            format!("{}/models/sinks/business.d/broken.toml", work),
+            route("broken", "missing_sink"),
+        )
+        .unwrap();
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_err());
+
+        cleanup_test_dir(&work);
+    }
+
+    #[test]
+    fn test_check_mapping_reports_unknown_oml_fields() {
+        let work = uniq_tmp_dir();
+
+        create_minimal_project_structure(&work);
+        create_basic_wparse_config(&work);
+        create_basic_wpl_file(&work);
+        fs::write(
+            format!("{}/models/oml/nginx.oml", work),
+            "name : nginx\nrule : /nginx/*\n---\nsip = take() ;\nsrc_ip = take(src_ip) ;\nrecv = read(recv_time) ;\n",
+        )
+        .unwrap();
+
+        let project = WarpProject::bare(&work);
+        let opts = CheckOptions::new(&work);
+        let comps = CheckComponents::default().with_only([CheckComponent::Mapping]);
+        let dict = EnvDict::test_default();
+
+        let outputs = project.wpl().rule_outputs().unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert!(outputs[0].1.contains("sip"));
+        assert_eq!(project.oml().models().unwrap().len(), 1);
+
+        // 未知字段只告警，检查本身通过
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_ok());
+
+        // 模型无法解析时映射检查失败
+        fs::write(
+            format!("{}/models/oml/broken.oml", work),
+            "name : broken\n---\nx = ;\n",
+        )
+        .unwrap();
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_err());
+
+        cleanup_test_dir(&work);
+    }
+
+    #[test]
+    fn test_check_fix_repairs_broken_project() {
+        let work = uniq_tmp_dir();
+
+        // 损坏的工程：缺少连接器目录、OML 为 CRLF 换行、wpgen.toml 缺少 [logging]
+        create_minimal_project_structure(&work);
+        create_basic_wparse_config(&work);
+        create_basic_wpsrc_config(&work);
+        fs::remove_dir_all(format!("{}/connectors", work)).unwrap();
+        let oml_path = format!("{}/models/oml/nginx.oml", work);
+        fs::write(
+            &oml_path,
+            include_str!("../example/oml/nginx.oml").replace('\n', "\r\n"),
+        )
+        .unwrap();
+        let wpgen_path = format!("{}/conf/wpgen.toml", work);
+        fs::write(
+            &wpgen_path,
+            "version = \"\"\n[generator]\nmode = \"sample\"\n[output]\nconnect = \"file_json_sink\"\n",
+        )
+        .unwrap();
+
+        let project = WarpProject::bare(&work);
+        let comps = CheckComponents::default().with_only([
+            CheckComponent::Engine,
+            CheckComponent::Sources,
+            CheckComponent::Connectors,
+            CheckComponent::Oml,
+        ]);
+        let dict = EnvDict::test_default();
+
+        let mut opts = CheckOptions::new(&work);
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_err());
+
+        opts.fix = true;
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_ok());
+        assert!(PathBuf::from(&work).join("connectors/source.d").is_dir());
+        assert!(!fs::read_to_string(&oml_path).unwrap().contains('\r'));
+        assert!(
+            fs::read_to_string(&wpgen_path)
+                .unwrap()
+                .contains("[logging]")
+        );
+
+        // 修复后的工程无需再次修复
+        opts.fix = false;
+        assert!(checker::check_with(&project, &opts, &comps, &dict).is_ok());
+
+        cleanup_test_dir(&work);
+    }
+
     #[test]
     // #[serial] // 暂时注释以解决编译问题
     fn test_individual_components_isolation() {
@@ -0,0 +1,238 @@
+//! 试运行（与 `wproj prj trial` 语义一致）：用工程的 WPL/OML 逐行处理样本文件，
+//! 报告每行命中的规则、转换模型与最终记录，不写入任何 sink。
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use orion_variate::EnvDict;
+use wp_cli_core::business::trial::{TrialLine, TrialReport};
+use wp_engine::facade::kit::TrialRunner;
+use wp_error::run_error::{RunReason, RunResult};
+
+use super::warp::WarpProject;
+
+#[derive(Debug, Clone, Default)]
+pub struct TrialOptions {
+    /// 样本文件，每行一条输入
+    pub input: PathBuf,
+    /// 源标识（wpsrc.toml 中的 key）；指定时按该源的连接器参数预处理输入
+    pub src_key: Option<String>,
+    /// 最多读取的行数
+    pub max_lines: Option<usize>,
+    pub json: bool,
+}
+
+impl TrialOptions {
+    pub fn new<P: AsRef<Path>>(input: P) -> Self {
+        Self {
+            input: input.as_ref().to_path_buf(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 执行试运行并输出报告（文本或 JSON）
+pub fn trial_with(
+    project: &WarpProject,
+    opts: &TrialOptions,
+    dict: &EnvDict,
+) -> RunResult<TrialReport> {
+    let report = run_trial(project, opts, dict)?;
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        print_text(&report);
+    }
+    Ok(report)
+}
+
+/// 执行试运行，仅返回报告
+pub fn run_trial(
+    project: &WarpProject,
+    opts: &TrialOptions,
+    dict: &EnvDict,
+) -> RunResult<TrialReport> {
+    // 引擎加载会切换工作目录，先固定样本路径
+    let input = std::path::absolute(&opts.input).map_err(|e| {
+        RunReason::from_conf(format!("input {}: {}", opts.input.display(), e)).to_err()
+    })?;
+    let file = File::open(&input).map_err(|e| {
+        RunReason::from_conf(format!("open input {}: {}", input.display(), e)).to_err()
+    })?;
+    let mut runner = TrialRunner::load(project.work_root(), opts.src_key.as_deref(), dict)?;
+
+    let mut lines = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        if opts.max_lines.is_some_and(|max| idx >= max) {
+            break;
+        }
+        let line = line.map_err(|e| {
+            RunReason::from_conf(format!("read input {}: {}", input.display(), e)).to_err()
+        })?;
+        lines.extend(runner.feed(idx + 1, line));
+    }
+    lines.extend(runner.finish());
+    Ok(TrialReport::new(lines))
+}
+
+fn print_text(report: &TrialReport) {
+    for line in &report.lines {
+        print_line(line);
+    }
+    let s = &report.summary;
+    println!();
+    println!(
+        "Summary: total={} hit={} miss={} partial={}",
+        s.total, s.hit, s.miss, s.partial
+    );
+    for (rule, count) in &s.rules {
+        println!("  {:<40} {}", rule, count);
+    }
+    if !s.top_errors.is_empty() {
+        println!("Top errors:");
+        for e in &s.top_errors {
+            println!("  {:>4}  {}", e.count, e.error);
+        }
+    }
+}
+
+fn print_line(line: &TrialLine) {
+    match &line.rule {
+        Some(rule) => {
+            let model = line.model.as_deref().unwrap_or("-");
+            println!("#{} {} -> {}", line.line, rule, model);
+            if let Some(record) = &line.record {
+                println!("  {}", record);
+            }
+            if let Some(residue) = &line.residue {
+                println!("  residue: {}", residue);
+            }
+        }
+        None => {
+            let closest = line.closest_rule.as_deref().unwrap_or("-");
+            println!("#{} MISS (closest: {})", line.line, closest);
+            println!("  input: {}", line.input);
+        }
+    }
+    if let Some(error) = &line.error {
+        println!("  error: {}", error.lines().next().unwrap_or(error));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_basic_wparse_config, write_file};
+    use wp_conf::test_support::ForTest;
+
+    const NGINX_LINE: &str = include_str!("../example/wpl/nginx/sample.dat");
+
+    /// 工程夹具：nginx 规则 + 全量透传模型
+    fn trial_project(root: &Path) {
+        write_basic_wparse_config(root);
+        write_file(
+            root,
+            "models/wpl/parse.wpl",
+            include_str!("../example/wpl/nginx/parse.wpl"),
+        );
+        write_file(
+            root,
+            "models/oml/nginx.oml",
+            include_str!("../example/oml/nginx.oml"),
+        );
+    }
+
+    /// 十行样本：8 行 nginx 访问日志 + 2 行无法解析的内容
+    fn ten_line_sample(prefix: &str) -> String {
+        let nginx = NGINX_LINE.trim_end();
+        let mut lines: Vec<String> = (0..8).map(|_| format!("{}{}", prefix, nginx)).collect();
+        lines.insert(3, format!("{}not an access log", prefix));
+        lines.push(format!("{}GET / HTTP/1.1", prefix));
+        lines.join("\n") + "\n"
+    }
+
+    #[test]
+    fn trial_reports_rule_hits_and_misses() {
+        let tmp = temp_workdir();
+        trial_project(tmp.path());
+        let input = write_file(tmp.path(), "data/sample.log", &ten_line_sample(""));
+
+        let project = WarpProject::bare(tmp.path());
+        let report = run_trial(
+            &project,
+            &TrialOptions::new(&input),
+            &EnvDict::test_default(),
+        )
+        .expect("trial");
+
+        let s = &report.summary;
+        assert_eq!((s.total, s.hit, s.miss), (10, 8, 2));
+        assert_eq!(s.rules.len(), 1);
+        let (rule, hits) = s.rules.iter().next().unwrap();
+        assert!(rule.starts_with("/nginx/"), "rule: {}", rule);
+        assert_eq!(*hits, 8);
+        assert_eq!(s.top_errors.iter().map(|e| e.count).sum::<usize>(), 2);
+
+        let first = &report.lines[0];
+        assert_eq!(first.line, 1);
+        assert_eq!(first.model.as_deref(), Some("nginx"));
+        let record: serde_json::Value =
+            serde_json::from_str(first.record.as_deref().unwrap()).unwrap();
+        assert_eq!(record["sip"], "222.133.52.20");
+
+        let miss = &report.lines[3];
+        assert_eq!(miss.line, 4);
+        assert!(!miss.is_hit());
+        assert!(miss.error.is_some());
+        assert!(miss.record.is_none());
+
+        // max_lines 只处理前 N 行
+        let mut opts = TrialOptions::new(&input);
+        opts.max_lines = Some(3);
+        let report = run_trial(&project, &opts, &EnvDict::test_default()).expect("trial");
+        assert_eq!((report.summary.total, report.summary.miss), (3, 0));
+    }
+
+    #[test]
+    fn trial_applies_source_preprocessing() {
+        let tmp = temp_workdir();
+        trial_project(tmp.path());
+        write_file(
+            tmp.path(),
+            "connectors/source.d/10-syslog.toml",
+            "[[connectors]]\nid = \"syslog_udp_src\"\ntype = \"syslog\"\nallow_override = [\"port\", \"header_mode\"]\n[connectors.params]\naddr = \"127.0.0.1\"\nport = 1514\nprotocol = \"udp\"\nheader_mode = \"raw\"\n",
+        );
+        write_file(
+            tmp.path(),
+            "topology/sources/wpsrc.toml",
+            "[[sources]]\nkey = \"gen_udp\"\nconnect = \"syslog_udp_src\"\ntags = [\"env:trial\"]\n[sources.params]\nheader_mode = \"skip\"\n",
+        );
+        let input = write_file(
+            tmp.path(),
+            "data/sample.log",
+            &ten_line_sample("<13>Oct 11 22:14:15 host app: "),
+        );
+        let project = WarpProject::bare(tmp.path());
+        let dict = EnvDict::test_default();
+
+        // 不做预处理时 syslog 头导致全部未命中
+        let raw = run_trial(&project, &TrialOptions::new(&input), &dict).expect("trial");
+        assert_eq!(raw.summary.miss, 10);
+
+        let mut opts = TrialOptions::new(&input);
+        opts.src_key = Some("gen_udp".into());
+        let report = run_trial(&project, &opts, &dict).expect("trial");
+        assert_eq!((report.summary.hit, report.summary.miss), (8, 2));
+        assert_eq!(report.lines[0].input, NGINX_LINE.trim_end());
+        let record: serde_json::Value =
+            serde_json::from_str(report.lines[0].record.as_deref().unwrap()).unwrap();
+        assert_eq!(record["env"], "trial");
+        assert_eq!(record["wp_src_key"], "gen_udp");
+
+        opts.src_key = Some("missing".into());
+        assert!(run_trial(&project, &opts, &dict).is_err());
+    }
+}
@@ -9,9 +9,10 @@ use wp_conf::sinks::{
     build_route_conf_from,
     io::{business_dir, infra_dir, load_connectors_for, load_route_files_from, load_sink_defaults},
 };
-use wp_conf::structure::SinkInstanceConf;
+use wp_conf::structure::{FlexGroup, SinkInstanceConf};
 use wp_connector_api::ParamMap;
-use wp_error::run_error::RunResult;
+use wp_engine::sinks::ClickHouseFactory;
+use wp_error::run_error::{RunReason, RunResult};
 
 use crate::traits::{Checkable, Component, ComponentBase, ComponentLifecycle, HasStatistics};
 use crate::types::CheckStatus;
@@ -52,10 +53,62 @@ impl Sinks {
     pub fn check(&self, dict: &orion_variate::EnvDict) -> RunResult<CheckStatus> {
         sinks_core::validate_routes(self.work_root().to_string_lossy().as_ref(), dict)
             .err_conv()?;
+        self.check_connections(dict)?;
         Ok(CheckStatus::Suc)
         //.map_err(|e| RunReason::from_conf(e.to_string()).to_err())
     }
 
+    // 对需要远端连接的 sink 做连通性检查（目前为 clickhouse）
+    fn check_connections(&self, dict: &EnvDict) -> RunResult<()> {
+        let sink_root = self.sink_root();
+        if !sink_root.exists() {
+            return Ok(());
+        }
+        let defaults = load_sink_defaults(&sink_root, dict).err_conv()?;
+        let conn_map =
+            load_connectors_for(sink_root.to_string_lossy().as_ref(), dict).err_conv()?;
+        for dir in [business_dir(&sink_root), infra_dir(&sink_root)] {
+            for rf in load_route_files_from(&dir, dict).err_conv()? {
+                let conf = build_route_conf_from(&rf, defaults.as_ref(), &conn_map).err_conv()?;
+                for sink in conf.sink_group.sinks.iter() {
+                    if sink.resolved_kind_str() != "clickhouse" {
+                        continue;
+                    }
+                    ClickHouseFactory
+                        .check_connection_blocking(&sink.resolved_params_table())
+                        .map_err(|e| {
+                            RunReason::from_conf(format!(
+                                "sink '{}' connection check failed: {}",
+                                sink.full_name(),
+                                e
+                            ))
+                            .to_err()
+                        })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 业务与基础路由组（已合并连接器参数与 schema 文件）
+    pub fn route_groups(&self, dict: &EnvDict) -> RunResult<Vec<FlexGroup>> {
+        let sink_root = self.sink_root();
+        if !sink_root.exists() {
+            return Ok(Vec::new());
+        }
+        let defaults = load_sink_defaults(&sink_root, dict).err_conv()?;
+        let conn_map =
+            load_connectors_for(sink_root.to_string_lossy().as_ref(), dict).err_conv()?;
+        let mut groups = Vec::new();
+        for dir in [business_dir(&sink_root), infra_dir(&sink_root)] {
+            for rf in load_route_files_from(&dir, dict).err_conv()? {
+                let conf = build_route_conf_from(&rf, defaults.as_ref(), &conn_map).err_conv()?;
+                groups.push(conf.sink_group);
+            }
+        }
+        Ok(groups)
+    }
+
     pub fn route_rows(
         &self,
         group_filters: &[String],
@@ -28,7 +28,10 @@ pub fn prepare_validate_context(
     let (_rows, groups, _total) =
         wp_cli_core::business::observability::build_groups_v2(&sink_root, &ctx, dict)
             .map_err(|e| RunReason::from_conf(e.to_string()).to_err())?;
-    let stats = stats_file.and_then(|p| load_stats_file(Path::new(p)));
+    let stats = stats_file
+        .map(|p| load_stats_file(Path::new(p)))
+        .transpose()
+        .map_err(|e| RunReason::from_conf(format!("load stats file failed: {:#}", e)).to_err())?;
     let input_from_sources =
         wp_cli_core::total_input_from_wpsrc(Path::new(&cm.work_root_path()), &main, &ctx, dict)
             .filter(|n| *n > 0);
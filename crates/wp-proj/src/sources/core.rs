@@ -11,10 +11,12 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use wp_cli_core::business::connectors::sources as sources_core;
+use wp_conf::loader::env_load_project_toml;
 use wp_conf::sources::types::{SourceItem, WarpSources};
 use wp_conf::{engine::EngineConfig, sources::build::load_source_instances_from_file};
 use wp_engine::facade::config::WPSRC_TOML;
 use wp_engine::sources::SourceConfigParser;
+use wp_engine::sources::preproc::validate_hook_names;
 use wp_error::run_error::{RunReason, RunResult};
 
 // Re-export modules and types
@@ -124,7 +126,7 @@ impl Sources {
         let parser = SourceConfigParser::new(work_root.to_path_buf());
 
         // Load configuration from TOML file
-        let sources_config = WarpSources::env_load_toml(wpsrc_path, dict).err_conv()?;
+        let sources_config: WarpSources = env_load_project_toml(wpsrc_path, dict).err_conv()?;
 
         // Serialize configuration to validate structure
         let config_content = toml::to_string_pretty(&sources_config).map_err(|e| {
@@ -143,9 +145,14 @@ impl Sources {
 
     /// Builds source specifications for validation
     fn build_source_specs(&self, wpsrc_path: &Path, dict: &EnvDict) -> RunResult<()> {
-        let _specs = load_source_instances_from_file(wpsrc_path, dict).map_err(|e| {
+        let specs = load_source_instances_from_file(wpsrc_path, dict).map_err(|e| {
             RunReason::from_conf(format!("Failed to build source specs: {}", e)).to_err()
         })?;
+        for spec in &specs {
+            validate_hook_names(&spec.preproc).map_err(|e| {
+                RunReason::from_conf(format!("source '{}': {}", spec.name(), e)).to_err()
+            })?;
+        }
         Ok(())
     }
 
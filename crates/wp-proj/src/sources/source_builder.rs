@@ -119,6 +119,10 @@ impl SourceItemBuilder {
             connect: self.connect,
             tags: self.tags,
             params: self.params,
+            rate_limit: None,
+            burst: None,
+            rate_limit_policy: None,
+            preproc: Vec::new(),
         }
     }
 }
@@ -106,6 +106,31 @@ impl FsOps {
         Self::write(path, content)
     }
 
+    /// 原子替换文件内容
+    ///
+    /// 先写同目录下的临时文件再 rename，中途失败不会留下半截内容。
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use wp_proj::utils::fs::FsOps;
+    /// FsOps::write_atomic("/path/to/conf.toml", "key = 1\n")?;
+    /// # Ok::<(), wp_error::run_error::RunError>(())
+    /// ```
+    pub fn write_atomic<P: AsRef<Path>>(path: P, content: &str) -> RunResult<()> {
+        let path = path.as_ref();
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".fix-tmp");
+        let tmp = path.with_file_name(name);
+        fs::write(&tmp, content)
+            .to_run_err_with(|e| format!("写入临时文件失败: {}: {}", tmp.display(), e))?;
+        fs::rename(&tmp, path)
+            .inspect_err(|_| {
+                let _ = fs::remove_file(&tmp);
+            })
+            .to_run_err_with(|e| format!("替换文件失败: {}: {}", path.display(), e))
+    }
+
     /// 查找符合模式的配置文件
     ///
     /// # 参数
@@ -228,6 +253,22 @@ mod tests {
         assert_eq!(current_content, "updated");
     }
 
+    #[test]
+    fn test_write_atomic_replaces_without_leftovers() {
+        let temp = temp_workdir();
+        let file = temp.path().join("conf.toml");
+
+        FsOps::write(&file, "a = 1\r\n").unwrap();
+        FsOps::write_atomic(&file, "a = 2\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a = 2\n");
+        let names: Vec<_> = fs::read_dir(temp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(names, vec![std::ffi::OsString::from("conf.toml")]);
+    }
+
     #[test]
     fn test_find_files() {
         let temp = temp_workdir();
@@ -3,11 +3,11 @@ use orion_conf::error::OrionConfResult;
 use orion_error::ErrorOwe;
 use orion_variate::EnvDict;
 use std::path::Path;
-use wp_conf::structure::SinkInstanceConf;
-use wp_engine::facade::config::{WarpConf, WpGenResolved};
-use wp_engine::facade::generator::SampleGRA;
-use wp_engine::runtime::generator::run_rule_direct;
-use wp_engine::runtime::generator::run_sample_direct;
+use wp_engine::facade::config::{WarpConf, WpGenOutput, WpGenResolved};
+use wp_engine::facade::generator::{ReplayGRA, SampleGRA};
+use wp_engine::runtime::generator::{
+    run_replay_direct_outputs, run_rule_direct_outputs, run_sample_direct_outputs,
+};
 use wp_error::run_error::RunResult;
 use wp_log::info_ctrl;
 
@@ -23,14 +23,16 @@ pub fn load_wpgen_resolved(
 
 /// 统一日志输出（解析后的 out_sink 关键信息）
 pub fn log_resolved_out_sink(wgr: &WpGenResolved) {
-    let kind = wgr.out_sink.resolved_kind_str();
-    let params = wgr.out_sink.resolved_params_table();
     // 不再按固定键提取（不同 sink 的参数不同）；直接输出完整 params，便于排查
-    info_ctrl!(
-        "wpgen out sink resolved: kind={}, params={:?}",
-        kind,
-        params
-    );
+    for out in &wgr.outputs {
+        info_ctrl!(
+            "wpgen out sink resolved: name={}, kind={}, route={:?}, params={:?}",
+            out.name,
+            out.sink.resolved_kind_str(),
+            out.route,
+            out.sink.resolved_params_table()
+        );
+    }
 }
 
 /// 初始化 wpgen 配置（conf/wpgen.toml），纯函数：不打印、不初始化日志
@@ -71,23 +73,34 @@ pub struct GenCleanReport {
     pub message: Option<String>,
 }
 
-/// 清理 wpgen 的输出文件（如果 out sink 为 file），返回结构化报告；不打印
-pub fn clean_wpgen_output_file(
+/// 清理 wpgen 各输出中 file sink 的输出文件，每个输出一份报告；配置无效时仅返回一份说明报告。不打印
+pub fn clean_wpgen_output_files(
     work_root: &str,
     conf_name: &str,
     local_only: bool,
     dict: &EnvDict,
-) -> Result<GenCleanReport> {
+) -> Result<Vec<GenCleanReport>> {
     if !local_only {
-        return Ok(GenCleanReport {
+        return Ok(vec![GenCleanReport {
             message: Some("local_only=false (skip)".to_string()),
             ..Default::default()
-        });
+        }]);
     }
     let god = WarpConf::new(work_root);
-    match load_wpgen_resolved(conf_name, &god, dict) {
-        Ok(conf) => {
-            if let Some(p) = conf.out_sink.resolve_file_path() {
+    let conf = match load_wpgen_resolved(conf_name, &god, dict) {
+        Ok(conf) => conf,
+        Err(_e) => {
+            return Ok(vec![GenCleanReport {
+                message: Some(format!("config '{}' not found or invalid", conf_name)),
+                ..Default::default()
+            }]);
+        }
+    };
+    let reports = conf
+        .all_outputs()
+        .iter()
+        .map(|out| match out.sink.resolve_file_path() {
+            Some(p) => {
                 let resolved_path = Path::new(&p);
                 let full_path = if resolved_path.is_absolute() {
                     resolved_path.to_path_buf()
@@ -99,53 +112,75 @@ pub fn clean_wpgen_output_file(
                 if existed {
                     cleaned = std::fs::remove_file(&full_path).is_ok();
                 }
-                Ok(GenCleanReport {
+                GenCleanReport {
                     path: Some(full_path.to_string_lossy().to_string()),
                     existed,
                     cleaned,
                     message: None,
-                })
-            } else {
-                Ok(GenCleanReport {
-                    path: None,
-                    existed: false,
-                    cleaned: false,
-                    message: Some("output target is not a file sink".to_string()),
-                })
+                }
             }
-        }
-        Err(_e) => Ok(GenCleanReport {
-            path: None,
-            existed: false,
-            cleaned: false,
-            message: Some(format!("config '{}' not found or invalid", conf_name)),
-        }),
-    }
+            None => GenCleanReport {
+                message: Some(format!("output '{}' is not a file sink", out.name)),
+                ..Default::default()
+            },
+        })
+        .collect();
+    Ok(reports)
 }
 
-/// 直连执行（规则：样本），使用 out_sink 规格按副本构建 sink 实例。
+/// 直连执行（样本）：按 wpgen 配置的全部输出分发（单一 `[output]` 即一路全量输出）。
 pub async fn sample_exec_direct_core(
     rule_root: &str,
     find_name: &str,
-    prepared: (SampleGRA, SinkInstanceConf),
+    prepared: (SampleGRA, WpGenResolved),
     rate_limit_rps: usize,
 ) -> RunResult<()> {
-    let g = prepared.0.gen_conf.clone();
-    run_sample_direct(rule_root, find_name, &g, &prepared.1, rate_limit_rps).await
+    let outputs = prepared.1.all_outputs();
+    sample_exec_outputs_core(rule_root, find_name, (prepared.0, outputs), rate_limit_rps).await
 }
-/// 直连执行（规则）：使用 out_sink 规格按副本构建 sink 实例，预编译规则后直接发送。
+/// 直连执行（规则）：按 wpgen 配置的全部输出分发，预编译规则后直接发送。
 pub async fn rule_exec_direct_core(
     stat_print: bool,
     rule_root: &str,
-    prepared: (
-        wp_engine::facade::generator::RuleGRA,
-        wp_conf::structure::SinkInstanceConf,
-    ),
+    prepared: (wp_engine::facade::generator::RuleGRA, WpGenResolved),
     rate_limit_rps: usize,
     dict: &EnvDict,
 ) -> RunResult<()> {
-    let g = prepared.0.gen_conf.clone();
     // stat_print 目前只用于日志输出控制；此处不额外处理
     let _ = stat_print;
-    run_rule_direct(rule_root, &g, &prepared.1, rate_limit_rps, dict).await
+    let outputs = prepared.1.all_outputs();
+    rule_exec_outputs_core(rule_root, (prepared.0, outputs), rate_limit_rps, dict).await
+}
+
+/// 直连执行（回放）：按事件时间戳的原始间隔单路发送，`speed_factor` 调整回放倍速；
+/// 每条事件按全部输出的 ratio/filter 分发。
+pub async fn replay_exec_direct_core(
+    prepared: (ReplayGRA, WpGenResolved),
+    rate_limit_rps: usize,
+) -> RunResult<()> {
+    let outputs = prepared.1.all_outputs();
+    run_replay_direct_outputs(&prepared.0, &outputs, rate_limit_rps).await?;
+    Ok(())
+}
+
+/// 直连执行（样本，多路输出）：按 `[[outputs]]` 的 ratio/filter 分发到各输出。
+pub async fn sample_exec_outputs_core(
+    rule_root: &str,
+    find_name: &str,
+    prepared: (SampleGRA, Vec<WpGenOutput>),
+    rate_limit_rps: usize,
+) -> RunResult<()> {
+    let g = prepared.0.gen_conf.clone();
+    run_sample_direct_outputs(rule_root, find_name, &g, &prepared.1, rate_limit_rps).await
+}
+
+/// 直连执行（规则，多路输出）：按 `[[outputs]]` 的 ratio/filter 分发到各输出。
+pub async fn rule_exec_outputs_core(
+    rule_root: &str,
+    prepared: (wp_engine::facade::generator::RuleGRA, Vec<WpGenOutput>),
+    rate_limit_rps: usize,
+    dict: &EnvDict,
+) -> RunResult<()> {
+    let g = prepared.0.gen_conf.clone();
+    run_rule_direct_outputs(rule_root, &g, &prepared.1, rate_limit_rps, dict).await
 }
@@ -3,7 +3,7 @@ use std::path::Path;
 use orion_variate::EnvDict;
 use wp_error::RunResult;
 
-use crate::wpgen::core::clean_wpgen_output_file;
+use crate::wpgen::core::clean_wpgen_output_files;
 
 /// WPgen 管理器
 #[derive(Debug, Clone)]
@@ -27,39 +27,37 @@ impl WpGenManager {
             return Ok(false);
         }
 
-        // 使用已抽离的 wp_proj::cli_ops::wpgen::clean_output 函数
-        // 这个函数会正确解析 wpgen.toml 并根据 connect 配置清理数据
-        match clean_wpgen_output_file(
+        // 按 wpgen.toml 中每个输出的 connect 配置定位并清理数据
+        let reports = match clean_wpgen_output_files(
             self.work_root.to_string_lossy().as_ref(),
             "wpgen.toml",
             true,
             dict,
         ) {
-            Ok(result) => {
-                if let Some(path) = result.path {
-                    if result.cleaned {
-                        println!("✓ Cleaned wpgen data from: {}", path);
-                        Ok(true)
-                    } else if result.existed {
-                        eprintln!("Warning: Failed to clean wpgen data from: {}", path);
-                        Ok(false)
-                    } else {
-                        println!("✓ No wpgen data to clean at: {}", path);
-                        Ok(false)
-                    }
-                } else if let Some(msg) = result.message {
-                    println!("✓ Wpgen cleanup skipped: {}", msg);
-                    Ok(false)
-                } else {
-                    println!("✓ No wpgen data to clean");
-                    Ok(false)
-                }
-            }
+            Ok(reports) => reports,
             Err(e) => {
                 eprintln!("Warning: Failed to clean wpgen data: {}", e);
-                Ok(false)
+                return Ok(false);
+            }
+        };
+        let mut any_cleaned = false;
+        for result in reports {
+            if let Some(path) = result.path {
+                if result.cleaned {
+                    println!("✓ Cleaned wpgen data from: {}", path);
+                    any_cleaned = true;
+                } else if result.existed {
+                    eprintln!("Warning: Failed to clean wpgen data from: {}", path);
+                } else {
+                    println!("✓ No wpgen data to clean at: {}", path);
+                }
+            } else if let Some(msg) = result.message {
+                println!("✓ Wpgen cleanup skipped: {}", msg);
+            } else {
+                println!("✓ No wpgen data to clean");
             }
         }
+        Ok(any_cleaned)
     }
 
     /// 获取工作根目录的 Path 引用
@@ -104,4 +102,49 @@ mod tests {
             "wpgen generated file should be removed"
         );
     }
+
+    #[test]
+    fn clean_outputs_remove_every_file_output() {
+        let case_path = TestCasePath::new("wgpen", "clean_multi").assert("test path");
+        let mut project = WarpProject::bare(case_path.path());
+        project
+            .init_basic(PrjScope::Full)
+            .assert("init project with connectors");
+        std::fs::write(
+            case_path.path().join("conf/wpgen.toml"),
+            r#"version = ""
+[generator]
+mode = "sample"
+count = 10
+[[outputs]]
+name = "to_engine"
+connect = "file_json_sink"
+params = { base = "data/in_dat", file = "engine.dat" }
+ratio = 0.9
+[[outputs]]
+name = "ground_truth"
+connect = "file_raw_sink"
+params = { base = "data/in_dat", file = "truth.dat" }
+ratio = 0.1
+[logging]
+level = "info"
+output = "stdout"
+"#,
+        )
+        .expect("write wpgen.toml");
+
+        let dir = case_path.path().join("data/in_dat");
+        std::fs::create_dir_all(&dir).expect("dir");
+        for name in ["engine.dat", "truth.dat"] {
+            std::fs::write(dir.join(name), "payload").expect("write sample");
+        }
+
+        let manager = WpGenManager::new(case_path.path());
+        let cleaned = manager
+            .clean_outputs(&EnvDict::test_default())
+            .expect("clean outputs");
+        assert!(cleaned);
+        assert!(!dir.join("engine.dat").exists());
+        assert!(!dir.join("truth.dat").exists());
+    }
 }
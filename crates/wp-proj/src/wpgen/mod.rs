@@ -1,12 +1,15 @@
 mod core;
 mod manage;
 
-pub use core::clean_wpgen_output_file;
+pub use core::clean_wpgen_output_files;
 pub use core::gen_conf_check;
 pub use core::gen_conf_clean;
 pub use core::gen_conf_init;
 pub use core::load_wpgen_resolved;
 pub use core::log_resolved_out_sink;
+pub use core::replay_exec_direct_core;
 pub use core::rule_exec_direct_core;
+pub use core::rule_exec_outputs_core;
 pub use core::sample_exec_direct_core;
+pub use core::sample_exec_outputs_core;
 pub use manage::WpGenManager;
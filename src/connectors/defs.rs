@@ -1,7 +1,10 @@
 use wp_conf::connectors::ConnectorDef;
 use wp_connector_api::SourceDefProvider;
 
-use crate::sources::{file::FileSourceFactory, syslog::SyslogSourceFactory, tcp::TcpSourceFactory};
+use crate::sources::{
+    channel::ChannelSourceFactory, file::FileSourceFactory, http::HttpSourceFactory,
+    kafka::KafkaSourceFactory, syslog::SyslogSourceFactory, tcp::TcpSourceFactory,
+};
 
 pub fn builtin_sink_defs() -> Vec<ConnectorDef> {
     crate::sinks::builtin_factories::builtin_sink_defs()
@@ -12,5 +15,8 @@ pub fn builtin_source_defs() -> Vec<ConnectorDef> {
     defs.append(&mut FileSourceFactory.source_defs());
     defs.append(&mut SyslogSourceFactory::default().source_defs());
     defs.append(&mut TcpSourceFactory.source_defs());
+    defs.append(&mut HttpSourceFactory.source_defs());
+    defs.append(&mut KafkaSourceFactory.source_defs());
+    defs.append(&mut ChannelSourceFactory.source_defs());
     defs
 }
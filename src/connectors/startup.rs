@@ -1,13 +1,13 @@
 //! Centralized initialization for engine-side connector registries.
 //! - Registers built-in sinks
-//! - Registers built-in sources (syslog, tcp, file)
+//! - Registers built-in sources (syslog, tcp, http, file, kafka, channel)
 //! - Imports any factories that were (still) registered via API registries
 //! - Logs the final registered kinds for diagnostics
 
 use crate::connectors::registry as reg;
 
 pub fn init_runtime_registries() {
-    // 1) register built-in sinks (file/syslog/tcp/test_rescue/blackhole)
+    // 1) register built-in sinks (file/syslog/tcp/test_rescue/blackhole/channel)
     crate::sinks::register_builtin_factories();
 
     // 2) register built-in sources
@@ -15,8 +15,14 @@ pub fn init_runtime_registries() {
     crate::sources::syslog::register_syslog_factory();
     // tcp factory
     crate::sources::tcp::register_tcp_factory();
+    // http ingestion factory
+    crate::sources::http::register_http_factory();
     // file factory explicit path
     crate::sources::file::register_factory_only();
+    // kafka factory (build requires feature 'kafka')
+    crate::sources::kafka::register_kafka_factory();
+    // in-process channel factory (embedded engine)
+    crate::sources::channel::register_channel_factory();
 
     // 3) log final kinds
     log_registered_kinds();
@@ -3,6 +3,7 @@
 use crate::core::parser::wpl_engine::parser::MultiParser;
 use crate::core::parser::{ParseOption, WplPipeline, WplRepository};
 use crate::stat::MonSend;
+use crate::stat::prometheus::ParseCounters;
 use crate::types::AnyResult;
 use getset::Getters;
 use wp_connector_api::SourceEvent;
@@ -17,6 +18,8 @@ use crate::sinks::{InfraSinkAgent, SinkGroupAgent, SinkInfraAble};
 pub struct WplEngine {
     pub pipelines: MultiParser,
     pub infra_agent: InfraSinkAgent,
+    /// Prometheus 解析计数句柄（本地缓存，热路径无锁）
+    pub metrics: ParseCounters,
 }
 
 impl WplEngine {
@@ -28,6 +31,7 @@ impl WplEngine {
         Ok(Self {
             pipelines: MultiParser::new(pipe_lines),
             infra_agent: infra,
+            metrics: ParseCounters::default(),
         })
     }
 
@@ -82,6 +86,7 @@ impl WplEngine {
         let mut workshop = WplEngine {
             pipelines: self.pipelines.clone(),
             infra_agent: self.infra_agent.clone(),
+            metrics: self.metrics.clone(),
         };
         workshop.proc_batch(batch, setting).await
     }
@@ -3,6 +3,7 @@
 use super::types::{ParsedDatSet, ProcessResult};
 use crate::core::parser::{ParseOption, WplEngine};
 use crate::sinks::{ProcMeta, SinkPackage, SinkRecUnit};
+use crate::stat::trace::{self, TraceStage};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -25,10 +26,18 @@ impl WplEngine {
         debug_data!("Processing events: len={}", batch.len());
         // 处理每个数据包
         for data in batch {
+            let traced = trace::sampled(data.event_id);
+            if traced {
+                trace::span(data.event_id, TraceStage::ParseStart, data.src_key.as_str());
+            }
             match self.pipelines.parse_event(&data, setting) {
                 ProcessResult::Success { wpl_key, record } => {
                     // 完全成功解析
+                    self.metrics.on_parsed(data.src_key.as_str(), &wpl_key);
                     let record = enrich_record_with_tags(record, &data.tags);
+                    if traced {
+                        trace::span(data.event_id, TraceStage::ParseEnd, &wpl_key);
+                    }
                     let rec_unit = SinkRecUnit::new(data.event_id, ProcMeta::Null, record);
                     sink_groups.entry(wpl_key).or_default().push(rec_unit);
                 }
@@ -38,7 +47,11 @@ impl WplEngine {
                     residue,
                 } => {
                     // 部分成功，有残留数据
+                    self.metrics.on_parsed(data.src_key.as_str(), &wpl_key);
                     let record = enrich_record_with_tags(record, &data.tags);
+                    if traced {
+                        trace::span(data.event_id, TraceStage::ParseEnd, &wpl_key);
+                    }
                     let rec_unit = SinkRecUnit::new(data.event_id, ProcMeta::Null, record);
                     sink_groups
                         .entry(wpl_key.clone())
@@ -53,6 +66,10 @@ impl WplEngine {
                         continue;
                     }
                     // 完全失败，记录深度最高的错误信息
+                    self.metrics.on_missed(data.src_key.as_str());
+                    if traced {
+                        trace::span(data.event_id, TraceStage::Miss, data.src_key.as_str());
+                    }
                     warn_edata!(data.event_id, "wpls miss data:\n{}", data.payload);
                     miss_packets.push((data, fail_info));
                 }
@@ -190,6 +207,7 @@ mod tests {
         WplEngine {
             pipelines: MultiParser::new(pipelines),
             infra_agent: InfraSinkAgent::use_null(),
+            metrics: Default::default(),
         }
     }
 
@@ -233,6 +251,41 @@ mod tests {
         assert_eq!(beta_ids, vec![id_b]);
     }
 
+    #[test]
+    fn batch_parse_package_traces_sampled_events() {
+        use crate::stat::trace::{self, TraceStage};
+        let _guard = trace::TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut engine = build_real_engine(&[("json_payload", JSON_RULE)]);
+        trace::configure(Some(0.5), 64);
+
+        let mut events: Vec<SourceEvent> = (0..64).map(|_| build_event(JSON_SAMPLE)).collect();
+        events.extend((0..64).map(|_| build_event("NOTHING-VALID")));
+        let hit = events[..64].iter().find(|e| trace::sampled(e.event_id));
+        let skip = events[..64].iter().find(|e| !trace::sampled(e.event_id));
+        let miss = events[64..].iter().find(|e| trace::sampled(e.event_id));
+        let (hit, skip, miss) = (
+            hit.expect("sampled event").event_id,
+            skip.expect("unsampled event").event_id,
+            miss.expect("sampled miss event").event_id,
+        );
+        engine
+            .batch_parse_package(events, &ParseOption::default())
+            .expect("parse batch");
+
+        let stages = |id| {
+            trace::lookup(id)
+                .map(|r| r.spans.into_iter().map(|s| s.stage).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            stages(hit),
+            vec![TraceStage::ParseStart, TraceStage::ParseEnd]
+        );
+        assert_eq!(stages(miss), vec![TraceStage::ParseStart, TraceStage::Miss]);
+        assert!(trace::lookup(skip).is_none());
+        trace::configure(None, 64);
+    }
+
     #[test]
     fn batch_parse_package_tracks_missed_packets() {
         let mut engine =
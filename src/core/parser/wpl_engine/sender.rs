@@ -8,6 +8,7 @@ use crate::facade::test_helpers::SinkTerminal;
 use crate::runtime::actor::constants::ACTOR_IDLE_TICK_MS;
 use crate::runtime::errors::err4_send_to_sink;
 use crate::sinks::{SinkDataEnum, SinkGroupAgent, SinkInfraAble, SinkPackage, SinkRecUnit};
+use crate::stat::trace;
 use orion_error::UvsReason;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -173,10 +174,22 @@ impl WplEngine {
         };
         */
 
-        let raw_data = format!(
-            "src_key: {}  | data:\n{}\n{}\n\n",
-            event.src_key, raw_str, err_msg
-        );
+        // 采样命中的事件带上追踪 ID，便于与 intake 打点关联
+        let raw_data = if trace::sampled(event.event_id) {
+            format!(
+                "src_key: {}  | {}: {}  | data:\n{}\n{}\n\n",
+                event.src_key,
+                trace::TRACE_ID_FIELD,
+                event.event_id,
+                raw_str,
+                err_msg
+            )
+        } else {
+            format!(
+                "src_key: {}  | data:\n{}\n{}\n\n",
+                event.src_key, raw_str, err_msg
+            )
+        };
         self.forward_raw_to_infra(|| self.miss(), event.event_id, raw_data)
             .await
     }
@@ -1,8 +1,11 @@
 //! 全局引擎阶段开关（轻量 Runtime 开关），用于在运行期跳过某些阶段。
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
 
 static SKIP_PARSE: AtomicBool = AtomicBool::new(false);
 static SKIP_SINK: AtomicBool = AtomicBool::new(false);
+// 退出时 sink drain 时限（毫秒）
+static DRAIN_TIMEOUT_MS: AtomicU64 = AtomicU64::new(5000);
 
 #[inline]
 pub fn set_skip_parse(v: bool) {
@@ -21,3 +24,12 @@ pub fn skip_parse() -> bool {
 pub fn skip_sink() -> bool {
     SKIP_SINK.load(Ordering::Relaxed)
 }
+
+#[inline]
+pub fn set_drain_timeout(v: Duration) {
+    DRAIN_TIMEOUT_MS.store(v.as_millis() as u64, Ordering::Relaxed);
+}
+#[inline]
+pub fn drain_timeout() -> Duration {
+    Duration::from_millis(DRAIN_TIMEOUT_MS.load(Ordering::Relaxed))
+}
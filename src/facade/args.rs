@@ -123,6 +123,24 @@ impl ParseArgs {
             skip_sink: conf.skip_sink(),
             // 语义分析开关来自 EngineConfig [semantic].enabled
             semantic_enabled: conf.semantic().enabled,
+            // OML 断言开关来自 EngineConfig [oml]
+            oml_compile_assert: conf.oml().compile_assert,
+            oml_assert_strict: conf.oml().assert_strict,
+            oml_lookup_cache_ttl_ms: conf.oml().lookup_cache_ttl_ms,
+            // Prometheus 指标导出来自 EngineConfig [metrics]
+            metrics_listen: conf
+                .metrics()
+                .enabled
+                .then(|| conf.metrics().listen.clone()),
+            // 累计计数落盘来自 EngineConfig [stat]
+            stats_checkpoint_sec: conf.stat_conf().checkpoint_sec.unwrap_or(30),
+            // 退出 drain 时限来自 EngineConfig [shutdown]
+            drain_timeout_ms: conf.shutdown().drain_timeout_ms,
+            // 记录级追踪来自 EngineConfig [trace]
+            trace_sample_rate: conf.trace().enabled.then(|| conf.trace().sample_rate),
+            trace_capacity: conf.trace().capacity,
+            // 记录体量限制来自 EngineConfig [record_limits]
+            record_limits: conf.record_limits().to_limits(),
             ..Default::default()
         })
     }
@@ -0,0 +1,186 @@
+//! 嵌入式引擎 Facade：在其他 Rust 服务内装配并运行 wparse 引擎。
+//!
+//! 与 `WpApp` 共用配置加载、资源装配与任务编排，但不初始化日志、不写 PID 文件、
+//! 不监听进程信号，生命周期完全由调用方控制：
+//! - 输入：`[[sources]]` 使用 `connect = "channel_src"`，通过 [`Engine::push`] 推送；
+//! - 输出：业务 sink 使用 `connect = "channel_sink"`，通过 [`Engine::subscribe_output`] 订阅。
+//!
+//! ```ignore
+//! let mut engine = Engine::builder().work_root("/opt/wp/work").build()?;
+//! let mut out = engine.subscribe_output("app");
+//! engine.start().await?;
+//! engine.push("app_in", "1,hello").await?;
+//! let record = out.recv().await;
+//! engine.shutdown(Duration::from_secs(5)).await?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use orion_conf::UvsConfFrom;
+use orion_error::{ErrorOwe, ToStructError, UvsLogicFrom};
+use orion_variate::EnvDict;
+use tokio::sync::mpsc;
+use wp_conf::engine::EngineConfig;
+use wp_conf::{RunArgs, RunMode};
+use wp_error::RunReason;
+use wp_error::run_error::RunResult;
+use wp_log::info_ctrl;
+use wp_model_core::model::DataRecord;
+use wp_stat::StatRequires;
+
+use crate::facade::args::ParseArgs;
+use crate::facade::engine::load_engine_res;
+use crate::orchestrator::config::loader::WarpConf;
+use crate::orchestrator::config::models::{load_warp_engine_confs, stat_reqs_from};
+use crate::orchestrator::engine::service::start_warp_service;
+use crate::runtime::actor::TaskManager;
+use crate::runtime::tasks::Sources;
+use crate::sources::channel;
+
+/// 订阅输出时每个接收端的默认队列容量
+pub const DEFAULT_OUTPUT_CAPACITY: usize = 1024;
+
+/// 嵌入式引擎构建器
+#[derive(Default)]
+pub struct EngineBuilder {
+    work_root: Option<PathBuf>,
+    env: Option<EnvDict>,
+}
+
+impl EngineBuilder {
+    /// 工作根目录（含 conf/、topology/、models/）；相对路径按当前目录解析
+    pub fn work_root(mut self, path: impl AsRef<Path>) -> Self {
+        self.work_root = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// 配置变量替换使用的环境字典
+    pub fn env(mut self, dict: EnvDict) -> Self {
+        self.env = Some(dict);
+        self
+    }
+
+    /// 加载主配置并初始化连接器注册表；此时尚未启动任何任务
+    pub fn build(self) -> RunResult<Engine> {
+        let work_root = match self.work_root {
+            Some(path) if path.is_absolute() => path,
+            Some(path) => std::env::current_dir().owe_conf()?.join(path),
+            None => {
+                return RunReason::from_conf("embedded engine requires work_root").err_result();
+            }
+        };
+        let env = self.env.unwrap_or_default();
+        let (conf_manager, main_conf) =
+            load_warp_engine_confs(work_root.to_string_lossy().as_ref(), &env)?;
+        let args = ParseArgs {
+            work_root: Some(work_root.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let run_args = args.completion_from(&main_conf)?;
+        let stat_reqs = stat_reqs_from(main_conf.stat_conf());
+        crate::connectors::startup::init_runtime_registries();
+        Ok(Engine {
+            main_conf,
+            conf_manager,
+            stat_reqs,
+            run_args,
+            env,
+            running: None,
+        })
+    }
+}
+
+struct Running {
+    task_manager: TaskManager,
+    sources: Sources,
+}
+
+/// 嵌入式引擎：`build` → `start` → `push`/`subscribe_output` → `shutdown`
+pub struct Engine {
+    main_conf: EngineConfig,
+    conf_manager: WarpConf,
+    stat_reqs: StatRequires,
+    run_args: RunArgs,
+    env: EnvDict,
+    running: Option<Running>,
+}
+
+impl Engine {
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::default()
+    }
+
+    /// 装配资源并以常驻模式启动采集/解析/输出任务
+    pub async fn start(&mut self) -> RunResult<()> {
+        if self.running.is_some() {
+            return RunReason::from_logic("engine already started").err_result();
+        }
+        let eng_res = load_engine_res(
+            &self.main_conf,
+            &self.conf_manager,
+            self.stat_reqs.clone(),
+            RunMode::Daemon,
+            &self.env,
+        )
+        .await?;
+        let (task_manager, sources) = start_warp_service(
+            eng_res,
+            RunMode::Daemon,
+            self.run_args.clone(),
+            self.stat_reqs.clone(),
+            PathBuf::from(self.conf_manager.work_root_path()),
+        )
+        .await?;
+        info_ctrl!("embedded engine started");
+        self.running = Some(Running {
+            task_manager,
+            sources,
+        });
+        Ok(())
+    }
+
+    /// 向 channel 源 `src_key` 推送一条原始数据；队列已满时等待
+    pub async fn push(&self, src_key: &str, payload: impl Into<Bytes>) -> RunResult<()> {
+        channel::push(src_key, payload)
+            .await
+            .map_err(|err| RunReason::from_conf(err.to_string()).to_err())
+    }
+
+    /// 订阅 channel sink 的输出（按 `route` 参数，缺省为 sink 名称）；应在 `start` 前订阅以免丢失早期记录
+    pub fn subscribe_output(&self, route: &str) -> mpsc::Receiver<DataRecord> {
+        crate::sinks::subscribe_output(route, DEFAULT_OUTPUT_CAPACITY)
+    }
+
+    /// 优雅退出：关闭 channel 源并等待已推送数据处理完毕，超时返回错误
+    pub async fn shutdown(mut self, timeout: Duration) -> RunResult<()> {
+        let Some(mut running) = self.running.take() else {
+            return Ok(());
+        };
+        let deadline = Instant::now() + timeout;
+        for key in running.sources.list_source_identifiers() {
+            channel::close_channel(&key);
+        }
+        // channel 源取完剩余批次后返回 EOF，采集任务随之结束
+        while !running.task_manager.main_is_finished() {
+            if Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let remain = deadline.saturating_duration_since(Instant::now());
+        crate::knowledge::stop_reload_watch();
+        match tokio::time::timeout(remain, running.task_manager.all_down_wait_signal_ex()).await {
+            Ok(res) => {
+                info_ctrl!("embedded engine stopped");
+                res
+            }
+            Err(_) => RunReason::from_logic(format!(
+                "embedded engine shutdown timed out after {:?}",
+                timeout
+            ))
+            .err_result(),
+        }
+    }
+}
@@ -9,8 +9,9 @@ use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time::timeout;
 use wp_knowledge::facade::init_thread_cloned_from_knowdb;
 
-use orion_error::{ErrorConv, ErrorOwe, ErrorWith, OperationContext};
+use orion_error::{ErrorConv, ErrorOwe, ErrorWith, OperationContext, ToStructError, UvsLogicFrom};
 use wp_conf::{RunArgs, RunMode};
+use wp_error::RunReason;
 use wp_error::run_error::RunResult;
 use wp_log::conf::log_init;
 // bring logging macros into scope (Rust 2018+ requires explicit import for macro_rules! macros)
@@ -24,10 +25,11 @@ use crate::orchestrator::config::models::{load_warp_engine_confs, stat_reqs_from
 use crate::orchestrator::engine::resource::EngineResource;
 use crate::orchestrator::engine::resource::WarpResourceBuilder;
 use crate::orchestrator::engine::service::start_warp_service;
-use crate::resources::core::manager::ResManager;
+use crate::resources::core::manager::{ResManager, RuleReloader};
 use crate::runtime::actor::{self, TaskManager};
 use crate::runtime::sink::act_sink::SinkService;
 use crate::runtime::sink::infrastructure::InfraSinkService;
+use crate::runtime::tasks::Sources;
 use crate::sources::SourceConfigParser;
 use crate::utils::process::PidRec;
 use wp_conf::constants;
@@ -47,6 +49,8 @@ pub struct WpApp {
     pid_guard: Option<PidRec>,
     bus_enabled: bool,
     env_dict: EnvDict,
+    sources: Option<Sources>,
+    rule_reloader: Option<RuleReloader>,
 }
 
 impl WpApp {
@@ -76,6 +80,8 @@ impl WpApp {
             pid_guard: None,
             bus_enabled: false,
             env_dict,
+            sources: None,
+            rule_reloader: None,
         })
     }
 
@@ -115,7 +121,7 @@ impl WpApp {
             self.run_args.line_max
         );
 
-        let eng_res = load_engine_res(
+        let mut eng_res = load_engine_res(
             &self.main_conf,
             &self.conf_manager,
             self.stat_reqs.clone(),
@@ -123,36 +129,87 @@ impl WpApp {
             env_dict,
         )
         .await?;
+        self.rule_reloader = eng_res.rule_reloader.take();
 
-        let task_manager = start_warp_service(
+        let (task_manager, sources) = start_warp_service(
             eng_res,
             run_mode,
             self.run_args.clone(),
             self.stat_reqs.clone(),
+            PathBuf::from(self.conf_manager.work_root_path()),
         )
         .await?;
+        self.sources = Some(sources);
         Ok(task_manager)
     }
 
+    /// 运行期数据源表（服务启动后可用），支持 `hot_add`/`hot_remove`
+    pub fn sources(&self) -> Option<Sources> {
+        self.sources.clone()
+    }
+
+    /// 热重载 WPL/OML：重新装载并校验，成功后替换规则集（source/sink 不受影响），返回新代号。
+    /// 校验失败时旧规则集继续生效并返回错误。
+    pub async fn reload_rules(&self) -> RunResult<u64> {
+        match &self.rule_reloader {
+            Some(reloader) => reloader.reload().await,
+            None => RunReason::from_logic("engine not started, nothing to reload").err_result(),
+        }
+    }
+
+    /// 重新读取 wpsrc.toml，将各实例的 `rate_limit` 应用到运行中的数据源，返回更新的实例数
+    pub fn reload_rate_limits(&self) -> RunResult<usize> {
+        match &self.sources {
+            Some(sources) => reload_source_rate_limits(sources, &self.wpsrc_path(), &self.env_dict),
+            None => RunReason::from_logic("engine not started, nothing to reload").err_result(),
+        }
+    }
+
+    fn wpsrc_path(&self) -> PathBuf {
+        PathBuf::from(self.main_conf.src_conf_of(constants::WPSRC_TOML))
+    }
+
+    /// 监听 SIGHUP 并触发规则与 source 限速热重载
+    fn spawn_reload_on_hup(&self) -> RunResult<Option<tokio::task::JoinHandle<()>>> {
+        let reloader = self.rule_reloader.clone();
+        let sources = self.sources.clone();
+        if reloader.is_none() && sources.is_none() {
+            return Ok(None);
+        }
+        let wpsrc_path = self.wpsrc_path();
+        let env_dict = self.env_dict.clone();
+        let mut signals = actor::signal::reload_signals()?;
+        Ok(Some(tokio::spawn(async move {
+            while signals.next().await.is_some() {
+                info_ctrl!("recv SIGHUP, reload wpl/oml rules and source rate limits");
+                // 失败时错误已记录，旧规则集/旧限速继续生效
+                if let Some(reloader) = &reloader {
+                    let _ = reloader.reload().await;
+                }
+                if let Some(sources) = &sources {
+                    let _ = reload_source_rate_limits(sources, &wpsrc_path, &env_dict);
+                }
+            }
+        })))
+    }
+
     /// 运行主循环：处理信号与控制面热重载
     async fn engine_working(&mut self, run_mode: RunMode) -> RunResult<()> {
         let mut signals = actor::signal::stop_signals()?;
         let mut task_admin = self
             .start_service(run_mode.clone(), &self.env_dict.clone())
             .await?;
+        let reload_task = self.spawn_reload_on_hup()?;
         warn_ctrl!("engine started!");
 
         if self.bus_enabled {
             loop {
                 tokio::select! {
-                    /*
                     Some(_) = self.cmd_recv.recv() => {
-                        info_ctrl!("wparse engine reloading...");
-                        task_admin.all_down_wait_signal_ex().await?;
-                        self.start_service(run_mode.clone()).await?;
-                        info_ctrl!("wparse engine reload done!");
+                        info_ctrl!("recv reload command, reload wpl/oml rules and source rate limits");
+                        let _ = self.reload_rules().await;
+                        let _ = self.reload_rate_limits();
                     }
-                    */
                     stop = async {
                         if let Ok(Some(_)) = timeout(Duration::from_millis(100), signals.next()).await {
                             info_ctrl!("recv signal, stop all routine!");
@@ -171,6 +228,10 @@ impl WpApp {
         } else {
             task_admin.all_down_wait_signal().await?;
         }
+        if let Some(task) = reload_task {
+            task.abort();
+        }
+        crate::knowledge::stop_reload_watch();
         Ok(())
     }
 
@@ -187,8 +248,26 @@ impl WpApp {
     }
 }
 
-/// 引擎资源装配（从 apps/wparse/work/loader 迁移并私有化）
-async fn load_engine_res(
+/// 重新读取 wpsrc.toml 并刷新运行中数据源的限速；读取失败时旧限速继续生效
+fn reload_source_rate_limits(
+    sources: &Sources,
+    wpsrc_path: &Path,
+    env_dict: &EnvDict,
+) -> RunResult<usize> {
+    let items = wp_conf::sources::load_source_instances_from_file(wpsrc_path, env_dict)
+        .err_conv()
+        .want("reload source rate limits")
+        .inspect_err(|e| warn_ctrl!("reload source rate limits failed: {}", e))?;
+    let updated = sources.reload_rate_limits(&items);
+    info_ctrl!(
+        "source rate limits reloaded: {} instance(s) updated",
+        updated
+    );
+    Ok(updated)
+}
+
+/// 引擎资源装配（从 apps/wparse/work/loader 迁移，供 WpApp 与嵌入式 Engine 共用）
+pub(crate) async fn load_engine_res(
     main_conf: &EngineConfig,
     conf_manager: &WarpConf,
     stat_reqs: StatRequires,
@@ -217,6 +296,7 @@ async fn load_engine_res(
                     env_dict,
                 );
                 handler.mark_initialized();
+                handler.start_reload_watch();
                 knowdb_handler = Some(handler);
             }
             Err(err) => {
@@ -241,7 +321,7 @@ async fn load_engine_res(
     // 源配置：解析 wpsrc.toml（统一 [[sources]] + connectors）
     let parser = SourceConfigParser::new(PathBuf::from(conf_manager.work_root_path()));
     let wpsrc_path = PathBuf::from(main_conf.src_conf_of(constants::WPSRC_TOML));
-    let (_src_keys, source_inits, acceptor_inits) = parser
+    let (source_instances, source_inits, acceptor_inits) = parser
         .build_source_handles(&wpsrc_path, run_mode, env_dict)
         .await
         .err_conv()
@@ -261,6 +341,11 @@ async fn load_engine_res(
         sink_service.agent(),
         stat_reqs.get_requ_items(StatStage::Parse),
     )?;
+    let rule_reloader = res_center.rule_reloader(
+        main_conf,
+        env_dict,
+        stat_reqs.get_requ_items(StatStage::Parse),
+    )?;
 
     // 输出 rule_mapping.dat 至工作目录 .run/rule_mapping.dat
     let res_path = conf_manager.runtime_path("rule_mapping.dat");
@@ -303,7 +388,9 @@ async fn load_engine_res(
         .with_sink_coordinator(sink_service)
         .with_acceptors(acceptor_inits)
         .with_sources(source_inits)
-        .with_knowdb_handler(knowdb_handler);
+        .with_source_instances(source_instances)
+        .with_knowdb_handler(knowdb_handler)
+        .with_rule_reloader(rule_reloader);
     ctx.mark_suc();
     Ok(builder.build_unchecked())
 }
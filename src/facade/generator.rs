@@ -1,7 +1,8 @@
 //! Facade: 生成器（规则/样本）对外入口（稳定 re-export）。
 
 pub use crate::core::generator::rules::{GenRuleUnit, fetch_oml_data, load_gen_confs};
-pub use crate::runtime::generator::types::{GenGRA, RuleGRA, SampleGRA};
+pub use crate::runtime::generator::FieldPools;
+pub use crate::runtime::generator::types::{GenGRA, ReplayGRA, RuleGRA, SampleGRA};
 
 use crate::sinks::SinkBackendType;
 use wp_error::run_error::{RunError, RunReason, RunResult};
@@ -2,3 +2,4 @@
 
 pub use crate::orchestrator::engine::definition::WplCodePKG;
 pub use crate::orchestrator::sync_processor::{engine_check, engine_proc_file, wpl_workshop_parse};
+pub use crate::orchestrator::trial::TrialRunner;
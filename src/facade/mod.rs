@@ -6,6 +6,7 @@ pub mod args;
 pub mod cli;
 pub mod config;
 pub mod diagnostics;
+pub mod embed;
 pub mod engine;
 pub mod enrich;
 pub mod generator;
@@ -15,6 +16,7 @@ pub mod test_helpers;
 pub mod usecases;
 
 // 常用导出，便于应用端按需使用
+pub use embed::{Engine, EngineBuilder};
 pub use engine::WpApp;
 pub use rescue::WpRescueApp;
 
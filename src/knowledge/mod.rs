@@ -1,7 +1,9 @@
 use orion_variate::EnvDict;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
 
 #[derive(Clone, Debug)]
 pub struct KnowdbHandler {
@@ -46,4 +48,74 @@ impl KnowdbHandler {
             }
         }
     }
+
+    /// 配置了 `reload_interval_secs` 时启动后台线程轮询表文件变更并热重载；
+    /// 单表重载失败保留旧数据，不影响运行中的查询。
+    /// 进程内只保留一个轮询线程：再次调用会先停止之前的线程，未开启热重载时直接返回
+    pub fn start_reload_watch(&self) {
+        stop_reload_watch();
+        let mut reloader = match wp_knowledge::reload::KnowdbReloader::when_enabled(
+            &self.root,
+            &self.conf,
+            &self.authority_uri,
+            &self.dict,
+        ) {
+            Ok(Some(r)) => r,
+            Ok(None) => return,
+            Err(err) => {
+                warn_ctrl!("knowdb reload watch disabled: {}", err);
+                return;
+            }
+        };
+        let Some(interval) = reloader.interval() else {
+            return;
+        };
+        let (stop, stopped) = mpsc::channel::<()>();
+        let spawned = std::thread::Builder::new()
+            .name("knowdb-reload".into())
+            .spawn(move || {
+                // 收到停止信号或发送端被丢弃时退出
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    match reloader.poll() {
+                        Ok(report) => {
+                            for (table, err) in &report.failed {
+                                warn_ctrl!("knowdb reload table {} failed: {}", table, err);
+                            }
+                        }
+                        Err(err) => warn_ctrl!("knowdb reload poll failed: {}", err),
+                    }
+                }
+            });
+        match spawned {
+            Ok(handle) => {
+                info_ctrl!("knowdb reload watch started, interval {:?}", interval);
+                *RELOAD_WATCH.lock().unwrap_or_else(PoisonError::into_inner) =
+                    Some(ReloadWatch { stop, handle });
+            }
+            Err(err) => warn_ctrl!("spawn knowdb reload watch failed: {}", err),
+        }
+    }
+}
+
+/// 知识库热重载轮询线程
+struct ReloadWatch {
+    stop: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+static RELOAD_WATCH: Mutex<Option<ReloadWatch>> = Mutex::new(None);
+
+/// 停止知识库热重载轮询线程并等待其退出（引擎停止时调用）；未启动时无操作
+pub fn stop_reload_watch() {
+    let watch = RELOAD_WATCH
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(ReloadWatch { stop, handle }) = watch {
+        let _ = stop.send(());
+        if handle.join().is_err() {
+            warn_ctrl!("knowdb reload watch panicked");
+        }
+        info_ctrl!("knowdb reload watch stopped");
+    }
 }
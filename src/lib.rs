@@ -28,6 +28,8 @@ pub mod test_support;
 pub mod types;
 // Facade: 对外提供稳定且收敛的应用入口，隐藏内部实现细节
 pub mod facade;
+// 嵌入式引擎入口：wp_engine::Engine
+pub use facade::embed::{Engine, EngineBuilder};
 // Engine-side registries and composition helpers for connectors
 pub mod connectors;
 // 运行期开关（跳过 parse/sink 阶段）
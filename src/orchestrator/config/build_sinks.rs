@@ -12,6 +12,7 @@ use crate::sinks::SinkRuntime;
 use crate::sinks::FileSink;
 use crate::sinks::FormatAdapter;
 use crate::sinks::SinkBackendType;
+use crate::sinks::{TargetBuildArgs, TargetRoutedSink, TargetTemplate};
 use crate::types::AnyResult;
 use orion_overload::append::Appendable;
 use wp_conf::limits::parser_channel_cap;
@@ -19,7 +20,7 @@ use wp_conf::structure::FileSinkConf;
 use wp_conf::structure::SinkInstanceConf;
 use wp_conf::structure::{FlexGroup, SinkGroupConf};
 use wp_connector_api::SinkBuildCtx;
-use wp_error::run_error::{RunReason, RunResult};
+use wp_error::run_error::{RunError, RunReason, RunResult};
 use wp_model_core::model::fmt_def::TextFmt;
 use wp_stat::StatReq;
 
@@ -69,6 +70,26 @@ pub async fn build_sink_target(
         // Factory path: use flattened params directly
         op.debug("load factory suc!");
         let spec: wp_connector_api::SinkSpec = core_to_resolved(&core);
+        let template = TargetTemplate::detect(&spec.params).map_err(|e| {
+            RunError::from(RunReason::Dist(DistFocus::SinkError(format!(
+                "sink '{}' target template: {}",
+                s_conf.name(),
+                e
+            ))))
+        })?;
+        if let Some(template) = template {
+            // 模板化目标：按记录解析 topic/index/table，按目标拆分批次后交给具体 sink
+            let args = TargetBuildArgs {
+                root: std::env::current_dir().unwrap(),
+                replica_idx,
+                replica_cnt,
+                rate_limit_rps,
+            };
+            let routed = TargetRoutedSink::new(factory, spec, args, template)
+                .owe(RunReason::Dist(DistFocus::SinkError(kind)))?;
+            op.mark_suc();
+            return Ok(SinkBackendType::Proxy(Box::new(routed)));
+        }
         let init = factory
             .build(&spec, &ctx)
             .await
@@ -76,9 +97,9 @@ pub async fn build_sink_target(
         op.mark_suc();
         Ok(SinkBackendType::Proxy(init.sink))
     } else {
-        Err(wp_error::run_error::RunError::from(RunReason::Dist(
-            DistFocus::SinkError(format!("sink factory not found for kind '{}'", kind)),
-        )))
+        Err(RunError::from(RunReason::Dist(DistFocus::SinkError(
+            format!("sink factory not found for kind '{}'", kind),
+        ))))
     }
 }
 
@@ -126,15 +147,18 @@ pub(crate) async fn infra_sink_group(
             let sink = build_sink_target(sc, rep, p_cnt, 0).await?;
             // 与业务组保持一致：使用 group/name 作为运行期名称，便于日志与统计
             let full_name = sc.full_name();
-            group.append(SinkRuntime::with_batch_size(
-                rescue.clone(),
-                full_name,
-                sc.clone(),
-                sink,
-                None,
-                stat_reqs.clone(),
-                conf.batch_size(),
-            ));
+            group.append(
+                SinkRuntime::with_batch_size(
+                    rescue.clone(),
+                    full_name,
+                    sc.clone(),
+                    sink,
+                    None,
+                    stat_reqs.clone(),
+                    conf.batch_size(),
+                )
+                .with_adaptive_batch(conf.adaptive_batch()),
+            );
         }
     }
     cxt.mark_suc();
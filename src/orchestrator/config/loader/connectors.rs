@@ -0,0 +1,363 @@
+use super::WarpConf;
+use crate::connectors::registry;
+use orion_variate::EnvDict;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use wp_conf::connectors::{
+    ConnectorDef, ConnectorScope, collect_connector_files, load_connectors_collecting,
+};
+
+/// 单个连接器文件的校验错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectorError {
+    pub file: PathBuf,
+    /// 出错的连接器 id；文件级错误（解析失败等）为 None
+    pub id: Option<String>,
+    pub message: String,
+}
+
+impl Display for ConnectorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "{} [{}]: {}", self.file.display(), id, self.message),
+            None => write!(f, "{}: {}", self.file.display(), self.message),
+        }
+    }
+}
+
+/// 单个连接器文件的校验状态
+#[derive(Debug, Clone)]
+pub struct ConnectorFileStatus {
+    pub file: PathBuf,
+    pub scope: ConnectorScope,
+    /// 文件中定义的连接器 id（按 id 排序）
+    pub ids: Vec<String>,
+    pub errors: Vec<ConnectorError>,
+}
+
+impl ConnectorFileStatus {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// `connectors/source.d` 与 `connectors/sink.d` 的整体校验结果
+#[derive(Debug, Clone, Default)]
+pub struct ConnectorValidationReport {
+    pub files: Vec<ConnectorFileStatus>,
+    /// 校验通过的连接器定义总数
+    pub total: usize,
+}
+
+impl ConnectorValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.files.iter().all(ConnectorFileStatus::is_ok)
+    }
+
+    pub fn errors(&self) -> Vec<ConnectorError> {
+        self.files
+            .iter()
+            .flat_map(|f| f.errors.iter().cloned())
+            .collect()
+    }
+}
+
+impl WarpConf {
+    /// 校验全部连接器文件（无需启动引擎）：逐目录解析（含 `extends`、重复 id），
+    /// 按已注册类型的参数声明校验 params，并检查 `allow_override` 中的键存在于 params。
+    ///
+    /// 任一文件失败时返回全部错误；参数声明取自运行时注册表，调用方需先完成连接器注册。
+    pub fn validate_all_connectors(
+        &self,
+        dict: &EnvDict,
+    ) -> Result<ConnectorValidationReport, Vec<ConnectorError>> {
+        let report = self.connector_validation_report(dict);
+        if report.is_ok() {
+            Ok(report)
+        } else {
+            Err(report.errors())
+        }
+    }
+
+    /// 生成逐文件的校验报告（不论成败）
+    pub fn connector_validation_report(&self, dict: &EnvDict) -> ConnectorValidationReport {
+        let root = self.work_root();
+        let src_dir = wp_conf::sources::io::resolve_connectors_base_dir(root)
+            .unwrap_or_else(|| root.join("connectors").join("source.d"));
+        let sink_dir = wp_conf::sinks::io::find_connectors_base_dir(root)
+            .unwrap_or_else(|| root.join("connectors").join("sink.d"));
+        let mut report = ConnectorValidationReport::default();
+        for (dir, scope, kinds) in [
+            (
+                src_dir,
+                ConnectorScope::Source,
+                registry::registered_source_defs(),
+            ),
+            (
+                sink_dir,
+                ConnectorScope::Sink,
+                registry::registered_sink_defs(),
+            ),
+        ] {
+            validate_dir(&dir, scope, &kinds, dict, &mut report);
+        }
+        report
+    }
+}
+
+fn validate_dir(
+    dir: &Path,
+    scope: ConnectorScope,
+    kinds: &[ConnectorDef],
+    dict: &EnvDict,
+    report: &mut ConnectorValidationReport,
+) {
+    let files = match collect_connector_files(dir) {
+        Ok(files) => files,
+        Err(e) => {
+            report.files.push(ConnectorFileStatus {
+                file: dir.to_path_buf(),
+                scope,
+                ids: Vec::new(),
+                errors: vec![file_error(dir, e.to_string())],
+            });
+            return;
+        }
+    };
+    let mut statuses: Vec<ConnectorFileStatus> = files
+        .into_iter()
+        .map(|file| ConnectorFileStatus {
+            file,
+            scope,
+            ids: Vec::new(),
+            errors: Vec::new(),
+        })
+        .collect();
+    let loaded = match load_connectors_collecting(dir, scope, dict) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            statuses.push(ConnectorFileStatus {
+                file: dir.to_path_buf(),
+                scope,
+                ids: Vec::new(),
+                errors: vec![file_error(dir, e.to_string())],
+            });
+            report.files.extend(statuses);
+            return;
+        }
+    };
+    for def in loaded.resolved.into_iter().map(|r| r.def) {
+        let Some(status) = statuses
+            .iter_mut()
+            .find(|s| def.origin.as_deref() == Some(&*s.file.display().to_string()))
+        else {
+            continue;
+        };
+        let errors = check_def(&def, kinds);
+        if errors.is_empty() {
+            report.total += 1;
+        }
+        status
+            .errors
+            .extend(errors.into_iter().map(|message| ConnectorError {
+                file: status.file.clone(),
+                id: Some(def.id.clone()),
+                message,
+            }));
+        status.ids.push(def.id);
+    }
+    // 加载错误逐条归属到所在文件
+    for (file, e) in loaded.errors {
+        let message = e.to_string();
+        match statuses.iter_mut().find(|s| s.file == file) {
+            Some(status) => status.errors.push(file_error(&status.file, message)),
+            None => statuses.push(ConnectorFileStatus {
+                errors: vec![file_error(&file, message)],
+                file,
+                scope,
+                ids: Vec::new(),
+            }),
+        }
+    }
+    report.files.extend(statuses);
+}
+
+/// 参数须为该类型声明的参数；`allow_override` 的键须存在于 params
+fn check_def(def: &ConnectorDef, kinds: &[ConnectorDef]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let declared: BTreeSet<String> = kinds
+        .iter()
+        .filter(|k| k.kind.eq_ignore_ascii_case(&def.kind))
+        .flat_map(|k| k.default_params.keys().chain(k.allow_override.iter()))
+        .cloned()
+        .collect();
+    // 类型未注册或未声明参数集合时无法判断未知参数
+    if !declared.is_empty() {
+        for key in def.default_params.keys() {
+            if !declared.contains(key) {
+                errors.push(format!(
+                    "param '{}' is not declared by type '{}'",
+                    key, def.kind
+                ));
+            }
+        }
+    }
+    for key in &def.allow_override {
+        if !def.default_params.contains_key(key) {
+            errors.push(format!("allow_override key '{}' not found in params", key));
+        }
+    }
+    errors
+}
+
+fn file_error(file: &Path, message: String) -> ConnectorError {
+    ConnectorError {
+        file: file.to_path_buf(),
+        id: None,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestCasePath;
+    use std::fs;
+    use wp_conf::connectors::ParamMap;
+    use wp_conf::test_support::ForTest;
+
+    fn write(root: &Path, rel: &str, body: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, body).unwrap();
+    }
+
+    #[test]
+    fn validate_all_connectors_reports_per_file() {
+        let tw = TestCasePath::new("wp", "validate_connectors").unwrap();
+        let cm = WarpConf::new(tw.path_string());
+        cm.clear_work_directory();
+        let root = cm.work_root().to_path_buf();
+        write(
+            &root,
+            "connectors/source.d/00-file-src.toml",
+            r#"[[connectors]]
+id = "demo_src"
+type = "demo"
+allow_override = ["path"]
+[connectors.params]
+path = "./data/in.dat"
+"#,
+        );
+        write(
+            &root,
+            "connectors/sink.d/00-file-sink.toml",
+            r#"[[connectors]]
+id = "demo_sink"
+type = "demo"
+allow_override = ["file"]
+[connectors.params]
+file = "out.dat"
+"#,
+        );
+        let report = cm
+            .validate_all_connectors(&EnvDict::test_default())
+            .expect("valid connectors");
+        assert_eq!(report.files.len(), 2);
+        assert_eq!(report.total, 2);
+        assert_eq!(report.files[0].ids, vec!["demo_src"]);
+
+        // allow_override 引用不存在的参数；另一个文件与已有 id 重复
+        write(
+            &root,
+            "connectors/sink.d/10-bad.toml",
+            r#"[[connectors]]
+id = "bad_sink"
+type = "demo"
+allow_override = ["missing"]
+[connectors.params]
+file = "bad.dat"
+"#,
+        );
+        write(
+            &root,
+            "connectors/source.d/10-dup.toml",
+            r#"[[connectors]]
+id = "demo_src"
+type = "demo"
+"#,
+        );
+        let errors = cm
+            .validate_all_connectors(&EnvDict::test_default())
+            .expect_err("invalid connectors");
+        assert_eq!(errors.len(), 2);
+        assert!(
+            errors.iter().any(|e| {
+                e.id.as_deref() == Some("bad_sink") && e.message.contains("'missing'")
+            })
+        );
+        assert!(errors.iter().any(|e| {
+            e.file.ends_with("10-dup.toml") && e.message.contains("duplicate connector id")
+        }));
+
+        // 多个文件加载失败时逐一报告，而不是停在第一个
+        write(
+            &root,
+            "connectors/source.d/20-syntax.toml",
+            "[[connectors]\nid = ",
+        );
+        write(
+            &root,
+            "connectors/source.d/30-noid.toml",
+            "[[connectors]]\ntype = \"demo\"\n",
+        );
+        let errors = cm
+            .validate_all_connectors(&EnvDict::test_default())
+            .expect_err("invalid connectors");
+        assert_eq!(errors.len(), 4);
+        for name in ["10-dup.toml", "20-syntax.toml", "30-noid.toml"] {
+            assert!(errors.iter().any(|e| e.file.ends_with(name)), "{}", name);
+        }
+    }
+
+    #[test]
+    fn check_def_accepts_tcp_sink_backoff_params() {
+        let kinds = crate::sinks::builtin_factories::builtin_sink_defs();
+        let kind = kinds
+            .iter()
+            .find(|k| k.kind == "tcp")
+            .expect("tcp sink def");
+        let mut def = kind.clone();
+        def.id = "tcp_out_sink".into();
+        def.default_params
+            .insert("max_backoff".into(), serde_json::json!(true));
+        def.default_params
+            .insert("sendq_backpressure".into(), serde_json::json!(false));
+        assert!(check_def(&def, &kinds).is_empty());
+    }
+
+    #[test]
+    fn check_def_rejects_undeclared_params() {
+        let mut params = ParamMap::new();
+        params.insert("base".into(), serde_json::json!("./out"));
+        let kind = ConnectorDef {
+            id: "demo_sink".into(),
+            kind: "demo".into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["file".into()],
+            default_params: params,
+            origin: None,
+        };
+        let mut def = kind.clone();
+        def.default_params
+            .insert("file".into(), serde_json::json!("a.dat"));
+        assert!(check_def(&def, std::slice::from_ref(&kind)).is_empty());
+
+        def.default_params
+            .insert("bogus".into(), serde_json::json!(1));
+        let errors = check_def(&def, &[kind]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'bogus'"));
+    }
+}
@@ -4,7 +4,7 @@ use crate::orchestrator::config::WPSRC_TOML;
 use crate::types::AnyResult;
 use futures_util::TryFutureExt;
 use orion_conf::error::{ConfIOReason, OrionConfResult};
-use orion_conf::{EnvTomlLoad, ErrorOwe, ToStructError, TomlIO};
+use orion_conf::{ErrorOwe, ToStructError, TomlIO};
 use orion_error::{ErrorWith, UvsResFrom};
 use orion_variate::EnvDict;
 use std::cell::OnceCell;
@@ -98,11 +98,10 @@ impl WarpConf {
     pub fn work_root_path(&self) -> String {
         self.work_root().to_string_lossy().to_string()
     }
-    /// 加载引擎配置
+    /// 加载引擎配置（展开 include 与 `${VAR:-default}`，与 `EngineConfig::load` 一致）
     pub fn load_engine_config(&self, dict: &EnvDict) -> OrionConfResult<EngineConfig> {
         let path = self.config_path(ENGINE_CONF_FILE);
-        let conf = EngineConfig::env_load_toml(&path, dict)
-            .owe_res()
+        let conf = EngineConfig::load_expanded(&path, dict)
             .with(&path)?
             .conf_absolutize(self.work_root());
         Ok(conf)
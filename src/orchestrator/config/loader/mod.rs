@@ -1,8 +1,10 @@
 // Loader: aggregate submodules for config loading
+mod connectors;
 mod manager;
 mod source;
 mod wpgen;
 
+pub use connectors::{ConnectorError, ConnectorFileStatus, ConnectorValidationReport};
 pub use manager::WarpConf;
 pub use wp_conf::loader::ConfDelegate;
 
@@ -118,6 +120,95 @@ output = "file"
         Ok(())
     }
 
+    #[test]
+    fn test_wpgen_resolved_with_two_outputs() -> AnyResult<()> {
+        use crate::orchestrator::config::models::wpgen::OutputRoute;
+        let tw = TestCasePath::new("wp", "wpgen_resolved_outputs")?;
+        let path = tw.path_string();
+        let cm = WarpConf::new(&path);
+        let cdir = format!("{}/connectors/sink.d", cm.work_root_path());
+        std::fs::create_dir_all(&cdir)?;
+        let connectors = r#"
+[[connectors]]
+id = "file_json_sink"
+type = "file"
+allow_override = ["base", "file", "fmt"]
+[connectors.params]
+fmt = "json"
+base = "./data/out_dat"
+file = "default.dat"
+
+[[connectors]]
+id = "file_raw"
+type = "file"
+allow_override = ["file"]
+[connectors.params]
+fmt = "raw"
+base = "./data/out_dat"
+file = "raw.dat"
+"#;
+        fs::write(format!("{}/01-file.toml", cdir), connectors)?;
+        let toml = r#"
+version = "1.0"
+[generator]
+mode = "rule"
+count = 10
+speed = 0
+[[outputs]]
+name = "to_engine"
+connect = "file_json_sink"
+params = { file = "engine.dat" }
+ratio = 0.9
+[[outputs]]
+name = "ground_truth"
+connect = "file_raw"
+params = { file = "truth.dat" }
+ratio = 0.1
+[logging]
+level = "info"
+output = "stdout"
+"#;
+        let p = cm.ensure_config_path_exists(WPGEN_TOML)?;
+        fs::write(&p, toml)?;
+        let rt = cm.load_wpgen_config(WPGEN_TOML, &EnvDict::test_default())?;
+        assert_eq!(rt.outputs.len(), 2);
+        assert_eq!(rt.outputs[0].name, "to_engine");
+        assert_eq!(rt.outputs[0].route, OutputRoute::Ratio(0.9));
+        assert_eq!(rt.outputs[1].route, OutputRoute::Ratio(0.1));
+        assert!(
+            rt.outputs[1]
+                .sink
+                .resolve_file_path()
+                .unwrap()
+                .ends_with("truth.dat")
+        );
+        assert_eq!(rt.out_sink, rt.outputs[0].sink);
+
+        // ratio 之和超过 1.0
+        fs::write(&p, toml.replace("ratio = 0.1", "ratio = 0.2"))?;
+        let msg = cm
+            .load_wpgen_config(WPGEN_TOML, &EnvDict::test_default())
+            .unwrap_err()
+            .to_string();
+        assert!(msg.contains("ratio sum"), "msg={}", msg);
+
+        // 白名单按各自 connector 校验：file_raw 不允许覆盖 fmt
+        fs::write(
+            &p,
+            toml.replace(
+                "params = { file = \"truth.dat\" }",
+                "params = { fmt = \"json\" }",
+            ),
+        )?;
+        let msg = cm
+            .load_wpgen_config(WPGEN_TOML, &EnvDict::test_default())
+            .unwrap_err()
+            .to_string();
+        assert!(msg.contains("override 'fmt' not allowed"), "msg={}", msg);
+        cm.clear_work_directory();
+        Ok(())
+    }
+
     #[test]
     fn test_wpgen_resolved_override_not_allowed() -> AnyResult<()> {
         let tw = TestCasePath::new("wp", "wpgen_resolved_2")?;
@@ -161,4 +252,54 @@ output = "stdout"
         cm.clear_work_directory();
         Ok(())
     }
+
+    #[test]
+    fn test_engine_conf_expands_env_defaults() -> AnyResult<()> {
+        let tw = TestCasePath::new("wp", "engine_env_default")?;
+        let cm = WarpConf::new(tw.path_string());
+        let p = cm.ensure_config_path_exists(crate::facade::config::ENGINE_CONF_FILE)?;
+        fs::write(
+            &p,
+            "[rescue]\npath = \"${WP_T_RESCUE_DIR:-./data/rescue_site}\"\n\n[variables]\nWP_T_OUT = { default = \"out_site\" }\n\n[topology]\nsinks = \"./${WP_T_OUT}\"\n",
+        )?;
+        let conf = cm.load_engine_config(&EnvDict::new())?;
+        assert!(
+            conf.rescue_root().ends_with("data/rescue_site"),
+            "rescue={}",
+            conf.rescue_root()
+        );
+        assert!(
+            conf.sinks_root().ends_with("out_site"),
+            "sinks={}",
+            conf.sinks_root()
+        );
+        cm.clear_work_directory();
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_conf_merges_include_overlay() -> AnyResult<()> {
+        let tw = TestCasePath::new("wp", "engine_include")?;
+        let cm = WarpConf::new(tw.path_string());
+        let base = cm.ensure_config_path_exists("base.toml")?;
+        fs::write(
+            &base,
+            "[performance]\nparse_workers = 4\n\n[metrics]\nenabled = true\nlisten = \"0.0.0.0:9464\"\n",
+        )?;
+        let site = cm.ensure_config_path_exists("site.toml")?;
+        fs::write(&site, "[metrics]\nlisten = \"10.0.0.1:9464\"\n")?;
+        let p = cm.ensure_config_path_exists(crate::facade::config::ENGINE_CONF_FILE)?;
+        fs::write(
+            &p,
+            "include = [\"base.toml\", \"site.toml\"]\n\n[performance]\nparse_workers = 8\n",
+        )?;
+
+        // 运行期经 WarpConf 加载：本文件覆盖 include，后列出的 include 覆盖先列出的嵌套表项
+        let conf = cm.load_engine_config(&EnvDict::new())?;
+        assert_eq!(conf.parallel(), 8);
+        assert!(conf.metrics().enabled);
+        assert_eq!(conf.metrics().listen, "10.0.0.1:9464");
+        cm.clear_work_directory();
+        Ok(())
+    }
 }
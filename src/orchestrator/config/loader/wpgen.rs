@@ -14,7 +14,9 @@ use wp_model_core::model::fmt_def::TextFmt;
 
 use super::WarpConf;
 use crate::orchestrator::config::WPGEN_TOML;
-use crate::orchestrator::config::models::wpgen::{WpGenConfig, WpGenResolved};
+use crate::orchestrator::config::models::wpgen::{
+    NamedOutputConfig, OutputRoute, WpGenConfig, WpGenOutput, WpGenResolved,
+};
 use crate::types::AnyResult;
 use wp_conf::engine::EngineConfig;
 
@@ -26,8 +28,22 @@ impl WarpConf {
         dict: &EnvDict,
     ) -> OrionConfResult<WpGenResolved> {
         let conf = self.parse_wpgen_config(file_name, dict)?;
+        if !conf.outputs.is_empty() {
+            let outputs = self.resolve_named_outputs(&conf, dict)?;
+            let out_sink = outputs[0].sink.clone();
+            return Ok(WpGenResolved {
+                conf,
+                out_sink,
+                outputs,
+            });
+        }
         let out_sink = self.resolve_out_sink(&conf, dict)?;
-        Ok(WpGenResolved { conf, out_sink })
+        let outputs = vec![WpGenOutput::all(out_sink.clone())];
+        Ok(WpGenResolved {
+            conf,
+            out_sink,
+            outputs,
+        })
     }
 
     // 1) 解析 wpgen.toml 为 WpGenConfig 并做基本验证
@@ -59,8 +75,93 @@ impl WarpConf {
                 .err_result();
             }
         };
+        self.build_out_sink(conf, out_name, conn_id, &conf.output.params, dict)
+    }
+
+    // 2') 多路输出：校验名称唯一、ratio 之和 ≤ 1.0、filter 可编译，并逐项按白名单装配
+    fn resolve_named_outputs(
+        &self,
+        conf: &WpGenConfig,
+        dict: &EnvDict,
+    ) -> OrionConfResult<Vec<WpGenOutput>> {
+        let mut outputs: Vec<WpGenOutput> = Vec::with_capacity(conf.outputs.len());
+        let mut ratio_sum = 0.0;
+        for item in &conf.outputs {
+            if item.name.is_empty() || outputs.iter().any(|o| o.name == item.name) {
+                return ConfIOReason::from_validation(format!(
+                    "wpgen.outputs: name '{}' must be non-empty and unique",
+                    item.name
+                ))
+                .err_result();
+            }
+            let route = Self::output_route(item)?;
+            if let OutputRoute::Ratio(r) = route {
+                ratio_sum += r;
+            }
+            let sink = self
+                .build_out_sink(
+                    conf,
+                    item.name.clone(),
+                    item.connect.clone(),
+                    &item.params,
+                    dict,
+                )
+                .with(item.name.as_str())?;
+            outputs.push(WpGenOutput {
+                name: item.name.clone(),
+                sink,
+                route,
+            });
+        }
+        // 允许浮点累加误差（如 0.7 + 0.2 + 0.1）
+        if ratio_sum > 1.0 + 1e-9 {
+            return ConfIOReason::from_validation(format!(
+                "wpgen.outputs: ratio sum {} exceeds 1.0",
+                ratio_sum
+            ))
+            .err_result();
+        }
+        Ok(outputs)
+    }
+
+    fn output_route(item: &NamedOutputConfig) -> OrionConfResult<OutputRoute> {
+        match (item.ratio, &item.filter) {
+            (Some(_), Some(_)) => ConfIOReason::from_validation(format!(
+                "wpgen.outputs '{}': ratio and filter are mutually exclusive",
+                item.name
+            ))
+            .err_result(),
+            (Some(r), None) if !(r.is_finite() && r > 0.0 && r <= 1.0) => {
+                ConfIOReason::from_validation(format!(
+                    "wpgen.outputs '{}': ratio must be in (0, 1], got {}",
+                    item.name, r
+                ))
+                .err_result()
+            }
+            (Some(r), None) => Ok(OutputRoute::Ratio(r)),
+            (None, Some(expr)) => match regex::Regex::new(expr) {
+                Ok(_) => Ok(OutputRoute::Filter(expr.clone())),
+                Err(e) => ConfIOReason::from_validation(format!(
+                    "wpgen.outputs '{}': invalid filter '{}': {}",
+                    item.name, expr, e
+                ))
+                .err_result(),
+            },
+            (None, None) => Ok(OutputRoute::All),
+        }
+    }
+
+    // 2'') 按 connector + 覆盖参数装配单个输出
+    fn build_out_sink(
+        &self,
+        conf: &WpGenConfig,
+        out_name: String,
+        conn_id: String,
+        params: &toml::value::Table,
+        dict: &EnvDict,
+    ) -> OrionConfResult<SinkInstanceConf> {
         let (_start_root, conn) = self.load_connector_by_id(&conn_id, dict)?;
-        let mut merged = Self::merge_params_with_whitelist(&conn, &conf.output.params, &conn_id)?;
+        let mut merged = Self::merge_params_with_whitelist(&conn, params, &conn_id)?;
         // 自动开启：当生成速率无限制（speed==0）且连接器类型为 tcp，且未显式设置 max_backoff/sendq_backoff/sendq_backpressure
         if conn.kind == "tcp" {
             let unlimited = conf.generator.speed == 0;
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 use crate::knowledge::KnowdbHandler;
-use crate::resources::ResManager;
+use crate::resources::{ResManager, RuleReloader};
 use crate::runtime::sink::act_sink::SinkService;
 use crate::runtime::sink::infrastructure::InfraSinkService;
+use crate::sources::SourceInstanceIds;
 use std::sync::Arc;
 use wp_connector_api::{AcceptorHandle, DataSource, ServiceAcceptor, SourceHandle};
 
@@ -15,8 +16,12 @@ pub struct EngineResource {
     pub resc: Option<ResManager>,
     pub sinks: Option<SinkService>,
     pub sources: Vec<SourceHandle>,
+    /// 启动期数据源实例（按配置顺序）：实例名及其展开出的 source 标识
+    pub source_instances: Vec<SourceInstanceIds>,
     pub acceptors: Vec<AcceptorHandle>,
     pub knowdb_handler: Option<Arc<KnowdbHandler>>,
+    /// WPL/OML 热重载器
+    pub rule_reloader: Option<RuleReloader>,
 }
 
 impl EngineResource {
@@ -90,14 +95,10 @@ impl EngineResource {
 
     /// 列出所有数据源的标识符
     pub fn list_source_identifiers(&self) -> Vec<String> {
-        let mut identifiers = Vec::new();
-
-        // 添加新格式源的标识符
-        for source_init in &self.sources {
-            identifiers.push(source_init.source.identifier());
-        }
-
-        identifiers
+        self.sources
+            .iter()
+            .map(|source_init| source_init.source.identifier())
+            .collect()
     }
 
     /// 验证资源的完整性
@@ -150,11 +151,21 @@ impl WarpResourceBuilder {
         self
     }
 
+    pub fn with_rule_reloader(mut self, reloader: RuleReloader) -> Self {
+        self.resource.rule_reloader = Some(reloader);
+        self
+    }
+
     pub fn with_sources(mut self, sources: Vec<SourceHandle>) -> Self {
         self.resource.sources = sources;
         self
     }
 
+    pub fn with_source_instances(mut self, instances: Vec<SourceInstanceIds>) -> Self {
+        self.resource.source_instances = instances;
+        self
+    }
+
     pub fn with_acceptors(mut self, acceptors: Vec<AcceptorHandle>) -> Self {
         self.resource.acceptors = acceptors;
         self
@@ -1,13 +1,17 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::runtime::actor::signal::ShutdownCmd;
 use crate::runtime::actor::{TaskGroup, TaskManager};
 use crate::runtime::supervisor::maintenance::ActMaintainer;
 use crate::runtime::tasks::{
-    add_acceptor_tasks, start_data_sinks, start_infra_working, start_moni_tasks,
-    start_parser_tasks_frames, start_picker_tasks,
+    PickerCtx, Sources, add_acceptor_tasks, start_data_sinks, start_infra_working,
+    start_moni_tasks, start_parser_tasks_frames, start_picker_tasks,
 };
+use crate::stat::ingest_checkpoint::spawn_ingest_checkpoint;
+use crate::stat::prometheus::spawn_metrics_server;
 use tokio::time::sleep;
+use wp_cli_core::business::observability::ingest_stats_path;
 use wp_conf::{RunArgs, RunMode};
 use wp_error::run_error::RunResult;
 use wp_stat::StatRequires;
@@ -21,15 +25,18 @@ use super::resource::EngineResource;
 /// - TaskManager 在优雅退出时采用“追加的逆序”进行下线（后进先出）；
 /// - 为确保下游（sink/infra/monitor）在上游（parser/picker）之后关闭、且能完整消费残留数据，
 ///   需要按既定顺序 append：
-///   1) monitor → 2) infra → 3) sink → 4) maint → 5) parser；主组设为 pickers；
+///   0) metrics（启用时）→ 0') 累计计数落盘（启用时）→ 1) monitor → 2) infra → 3) sink → 4) maint → 5) parser；主组设为 pickers；
 /// - 接受器（acceptors）属于采集链路的一部分，在 daemon 模式下被加入主组（pickers 组内），
 ///   以复用旧版“主流程完成→全局退出”的一致语义。
+///
+/// 返回的 `Sources` 为运行期数据源表：登记启动期数据源，并支持热添加/热移除。
 pub async fn start_warp_service(
     mut resource: EngineResource,
     run_mode: RunMode,
     args: RunArgs,
     stat_reqs: StatRequires,
-) -> RunResult<TaskManager> {
+    work_root: PathBuf,
+) -> RunResult<(TaskManager, Sources)> {
     let mode_s = match run_mode {
         RunMode::Daemon => "daemon",
         RunMode::Batch => "batch",
@@ -45,13 +52,48 @@ pub async fn start_warp_service(
     // 阶段开关（全局）
     crate::engine_flags::set_skip_parse(args.skip_parse);
     crate::engine_flags::set_skip_sink(args.skip_sink);
+    crate::engine_flags::set_drain_timeout(Duration::from_millis(args.drain_timeout_ms));
+
+    // 记录级追踪（可选）：采样命中的记录在各阶段打点，经 /debug/trace 查询
+    crate::stat::trace::configure(args.trace_sample_rate, args.trace_capacity);
+
+    // 单条记录体量限制（WPL 解析与 OML 转换写入字段时生效）
+    wp_data_model::limits::configure(args.record_limits);
 
     // 语义分析开关（控制 jieba 分词器和语义词典的加载）
     oml::set_semantic_enabled(args.semantic_enabled);
 
+    // OML assert 开关（生产环境可关闭；严格模式下断言失败的记录进入错误通道）
+    oml::core::assertion::set_compile_assert(args.oml_compile_assert);
+    oml::core::assertion::set_assert_strict(args.oml_assert_strict);
+
+    // OML 转换线程的查询缓存有效期（知识库数据会变化时设置）
+    oml::core::lookup_cache::set_ttl(args.oml_lookup_cache_ttl_ms.map(Duration::from_millis));
+
     // 提前设置全局构建期限速提示（发送单元构建期将读取该目标决定背压策略）。
     crate::sinks::set_global_rate_limit_rps(args.speed_limit);
 
+    // Prometheus 指标导出（可选）：最先启动、最后下线，便于观测整个退出过程
+    let mut metrics_group = None;
+    if let Some(listen) = args.metrics_listen.as_deref() {
+        let mut group = TaskGroup::new("metrics", ShutdownCmd::Immediate);
+        let (_, handle) = spawn_metrics_server(listen, group.subscribe()).await?;
+        group.append(handle);
+        metrics_group = Some(group);
+    }
+
+    // 累计计数落盘（可选）：在 sink 之后下线，退出前最后一次写入包含全部已下发数据
+    let mut checkpoint_group = None;
+    if args.stats_checkpoint_sec > 0 {
+        let mut group = TaskGroup::new("stats-ckpt", ShutdownCmd::Immediate);
+        group.append(spawn_ingest_checkpoint(
+            ingest_stats_path(&work_root),
+            Duration::from_secs(args.stats_checkpoint_sec),
+            group.subscribe(),
+        ));
+        checkpoint_group = Some(group);
+    }
+
     // 启动监控任务
     let (moni_send, moni_group) = start_moni_tasks(&args, &resource, &stat_reqs);
 
@@ -97,17 +139,20 @@ pub async fn start_warp_service(
     } else {
         None
     };
+    let source_instances = std::mem::take(&mut resource.source_instances);
     let all_sources = resource.get_all_sources();
 
     sleep(Duration::from_millis(100)).await;
     // 启动采集器（pickers）
-    let mut picker_group = start_picker_tasks(
-        &args,
-        all_sources,
-        moni_send.clone(),
-        subsc_channel,
-        &stat_reqs,
-    );
+    let picker_ctx = PickerCtx {
+        run_args: args.clone(),
+        mon_send: moni_send.clone(),
+        parse_senders: subsc_channel,
+        stat_reqs,
+    };
+    let mut picker_group = start_picker_tasks(&picker_ctx, all_sources);
+    let sources = Sources::new(picker_ctx, work_root, &picker_group);
+    sources.register_startup(source_instances)?;
 
     // 启动接受器（acceptors）并纳入主组
     if let Some(all_acceptors) = acceptors_prepared {
@@ -115,6 +160,12 @@ pub async fn start_warp_service(
     } else {
         info_ctrl!("run-mode=batch: 跳过启动接受器任务，以避免阻塞主组完成");
     }
+    if let Some(mg) = metrics_group {
+        task_manager.append_group(mg);
+    }
+    if let Some(cg) = checkpoint_group {
+        task_manager.append_group(cg);
+    }
     task_manager.append_group(moni_group);
     task_manager.append_group(infra_group);
     if let Some(sg) = sink_group_opt {
@@ -125,7 +176,7 @@ pub async fn start_warp_service(
     // 将采集任务组设为主组（主流程），用于整体生命周期与退出判断
     task_manager.set_main(picker_group);
 
-    Ok(task_manager)
+    Ok((task_manager, sources))
 }
 
 // 旧的兼容包装与混合模式已移除；统一使用当前架构启动。
@@ -3,6 +3,7 @@ pub mod config;
 pub mod constants;
 pub mod engine;
 pub mod sync_processor;
+pub mod trial;
 pub mod use_cases;
 pub mod utils;
 pub use config::models::infra::InfraSinkConf;
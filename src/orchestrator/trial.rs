@@ -0,0 +1,215 @@
+//! 试运行（dry-run）：按项目配置逐行解析样本并执行 OML 转换，不建立任何 sink。
+//!
+//! - WPL/OML 复用 `ResManager` 的加载逻辑（含规则到模型的匹配关系）；
+//! - 指定 `src_key` 时读取该源（wpsrc.toml + connectors/source.d）的参数，仅用于预处理
+//!   （syslog 头处理、多行合并、tags 注入），连接器不会被实例化，也不产生任何输出；
+//! - 不初始化知识库，依赖 KnowDB 的 OML 查询在试运行中返回空值。
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use oml::language::DataModel;
+use orion_error::{ErrorConv, ErrorOwe, ErrorWith, ToStructError, UvsConfFrom, UvsLogicFrom};
+use orion_variate::EnvDict;
+use wp_cli_core::business::trial::TrialLine;
+use wp_connector_api::{EventPreHook, SourceEvent, Tags};
+use wp_data_fmt::{Json, RecordFormatter};
+use wp_data_model::cache::FieldQueryCache;
+use wp_error::RunReason;
+use wp_error::run_error::RunResult;
+use wp_model_core::model::DataRecord;
+use wp_parse_api::RawData;
+use wpl::DEFAULT_KEY;
+
+use crate::core::parser::wpl_engine::processor::enrich_record_with_tags;
+use crate::core::parser::wpl_engine::types::ProcessResult;
+use crate::core::parser::{ParseOption, WplEngine};
+use crate::orchestrator::config::WPSRC_TOML;
+use crate::orchestrator::config::models::load_warp_engine_confs;
+use crate::resources::{ResManager, RuleKey};
+use crate::sinks::{InfraSinkAgent, SinkGroupAgent};
+use crate::sources::preproc::{MultilineJoiner, build_hook_chain, chain_after};
+use crate::sources::syslog::SyslogSourceSpec;
+use crate::sources::syslog::udp_source::build_preproc_hook;
+
+pub struct TrialRunner {
+    engine: WplEngine,
+    res: ResManager,
+    src_key: String,
+    tags: Arc<Tags>,
+    preproc: Option<EventPreHook>,
+    joiner: Option<MultilineJoiner>,
+    option: ParseOption,
+    cache: FieldQueryCache,
+}
+
+impl TrialRunner {
+    /// 加载 `work_root` 下的规则与模型；`src_key` 为 None 时不做源预处理
+    pub fn load(work_root: &str, src_key: Option<&str>, dict: &EnvDict) -> RunResult<Self> {
+        let (_, main_conf) = load_warp_engine_confs(work_root, dict)?;
+        let mut res = ResManager::default();
+        block_on(async {
+            res.load_all_wpl_code(&main_conf, &SinkGroupAgent::null())
+                .await?;
+            res.load_all_ldm(main_conf.oml_root()).await
+        })?;
+        let wpl_space = res
+            .wpl_space
+            .as_ref()
+            .ok_or(RunReason::from_logic("not init wpl space").to_err())?;
+        let engine = WplEngine::from_code(wpl_space, InfraSinkAgent::use_null()).owe_rule()?;
+
+        let mut runner = Self {
+            engine,
+            res,
+            src_key: DEFAULT_KEY.to_string(),
+            tags: Arc::new(Tags::new()),
+            preproc: None,
+            joiner: None,
+            option: ParseOption::new(true, Vec::new()),
+            cache: FieldQueryCache::default(),
+        };
+        if let Some(key) = src_key {
+            let wpsrc = PathBuf::from(main_conf.src_conf_of(WPSRC_TOML));
+            runner.use_source(&wpsrc, key, dict)?;
+        }
+        Ok(runner)
+    }
+
+    fn use_source(&mut self, wpsrc: &Path, key: &str, dict: &EnvDict) -> RunResult<()> {
+        let instances = wp_conf::sources::load_source_instances_from_file(wpsrc, dict)
+            .err_conv()
+            .want("load sources")
+            .with(wpsrc)?;
+        let ins = instances
+            .into_iter()
+            .find(|s| s.name() == key)
+            .ok_or_else(|| {
+                RunReason::from_conf(format!("source '{}' not found in {}", key, wpsrc.display()))
+                    .to_err()
+            })?;
+        let params = &ins.core.params;
+        if ins.kind() == "syslog" {
+            let spec = SyslogSourceSpec::from_params(params)
+                .map_err(|e| RunReason::from_conf(format!("source '{}': {}", key, e)).to_err())?;
+            self.preproc =
+                build_preproc_hook(spec.strip_header, spec.attach_meta_tags, spec.fast_strip);
+        }
+        if let Some(chain) = build_hook_chain(&ins.preproc)
+            .map_err(|e| RunReason::from_conf(format!("source '{}': {}", key, e)).to_err())?
+        {
+            self.preproc = Some(chain_after(self.preproc.take(), &chain));
+        }
+        self.joiner = MultilineJoiner::from_params(params)
+            .map_err(|e| RunReason::from_conf(format!("source '{}': {}", key, e)).to_err())?;
+        self.tags = Arc::new(Tags::from_parse(ins.tags()));
+        self.src_key = key.to_string();
+        Ok(())
+    }
+
+    /// 输入一行（`line_no` 从 1 开始）；多行合并时可能暂不产出结果
+    pub fn feed(&mut self, line_no: usize, line: String) -> Vec<TrialLine> {
+        let mut event = SourceEvent::new(
+            line_no as u64,
+            self.src_key.as_str(),
+            RawData::String(line),
+            Arc::clone(&self.tags),
+        );
+        event.preproc = self.preproc.clone();
+        let events = match self.joiner.as_mut() {
+            Some(joiner) => {
+                let mut out = Vec::new();
+                joiner.push(event, Instant::now(), &mut out);
+                out
+            }
+            None => {
+                if let Some(hook) = event.preproc.take() {
+                    (hook)(&mut event);
+                }
+                vec![event]
+            }
+        };
+        events.iter().map(|e| self.run_event(e)).collect()
+    }
+
+    /// 输出多行合并中尚未结束的记录（输入结束时调用）
+    pub fn finish(&mut self) -> Vec<TrialLine> {
+        let events = match self.joiner.as_mut() {
+            Some(joiner) => joiner.flush_all(),
+            None => Vec::new(),
+        };
+        let mut lines: Vec<TrialLine> = events.iter().map(|e| self.run_event(e)).collect();
+        lines.sort_by_key(|l| l.line);
+        lines
+    }
+
+    fn run_event(&mut self, event: &SourceEvent) -> TrialLine {
+        let mut line = TrialLine {
+            line: event.event_id as usize,
+            input: payload_text(&event.payload),
+            ..Default::default()
+        };
+        let (wpl_key, record) = match self.engine.pipelines.parse_event(event, &self.option) {
+            ProcessResult::Success { wpl_key, record } => (wpl_key, record),
+            ProcessResult::Partial {
+                wpl_key,
+                record,
+                residue,
+            } => {
+                line.residue = Some(residue);
+                (wpl_key, record)
+            }
+            ProcessResult::Miss(fail) => {
+                line.closest_rule = Some(fail.best_wpl).filter(|w| !w.is_empty());
+                line.error = Some(fail.best_error.to_string());
+                return line;
+            }
+        };
+        let record = enrich_record_with_tags(record, &event.tags);
+        line.rule = Some(wpl_key.clone());
+        let output = match model_of(&self.res, &wpl_key) {
+            Some((name, DataModel::Object(mdl))) => {
+                line.model = Some(name);
+                match mdl.try_transform_ref(record.as_ref(), &mut self.cache) {
+                    Ok(out) => out,
+                    Err(e) => {
+                        line.error = Some(e.reason().to_string());
+                        return line;
+                    }
+                }
+            }
+            _ => DataRecord::clone(&record),
+        };
+        line.record = Some(Json.fmt_record(&output).to_string());
+        line
+    }
+}
+
+/// 取 rule_mdl_relation 中该规则匹配表达式最长的模型
+fn model_of<'a>(res: &'a ResManager, wpl_key: &str) -> Option<(String, &'a DataModel)> {
+    let (name, _) = res.rule_mdl_relation.0.get(&RuleKey::from(wpl_key))?;
+    let mdl = res.name_mdl_res.get(name)?;
+    Some((name.0.clone(), mdl))
+}
+
+fn payload_text(payload: &RawData) -> String {
+    match payload {
+        RawData::String(s) => s.clone(),
+        RawData::Bytes(b) => String::from_utf8_lossy(b).into_owned(),
+        RawData::ArcBytes(b) => String::from_utf8_lossy(b).into_owned(),
+    }
+}
+
+// 加载逻辑为 async；与 engine_check 相同，在同步入口内阻塞执行
+fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        tokio::task::block_in_place(|| handle.block_on(fut))
+    } else {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build tokio runtime")
+            .block_on(fut)
+    }
+}
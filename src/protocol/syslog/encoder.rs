@@ -1,5 +1,69 @@
+use std::fmt::{Display, Formatter};
+
 use bytes::Bytes;
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use wp_model_core::model::{DataField, DataRecord, Value};
+
+/// RFC5424 SD-ELEMENT：`[SD-ID SD-PARAM*]`，参数值在渲染时按 §6.3.3 转义。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SdElement {
+    pub id: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl SdElement {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            params: Vec::new(),
+        }
+    }
+
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// 将记录顶层字段展开为 SD-PARAM：对象字段展开一层为 `<字段>.<键>`，
+    /// 名称不满足 SD-NAME 的字段被跳过；无可用字段时返回 None。
+    pub fn from_record(id: impl Into<String>, record: &DataRecord) -> Option<Self> {
+        let mut element = Self::new(id);
+        for item in &record.items {
+            let field = item.as_field();
+            match field.get_value() {
+                Value::Obj(obj) => {
+                    for (key, sub) in obj.iter() {
+                        let name = format!("{}.{}", field.get_name(), key);
+                        element.push_field(name, sub.as_field());
+                    }
+                }
+                _ => element.push_field(field.get_name().to_string(), field),
+            }
+        }
+        (!element.params.is_empty()).then_some(element)
+    }
+
+    fn push_field(&mut self, name: String, field: &DataField) {
+        if !is_sd_name(&name) {
+            return;
+        }
+        let value = match field.get_value() {
+            Value::Chars(v) => v.to_string(),
+            other => other.to_string(),
+        };
+        self.params.push((name, value));
+    }
+}
+
+impl Display for SdElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}", self.id)?;
+        for (name, value) in &self.params {
+            write!(f, " {}=\"{}\"", name, escape_sd_value(value))?;
+        }
+        f.write_str("]")
+    }
+}
 
 /// Static description of a syslog line used for encoding.
 #[derive(Debug, Clone)]
@@ -10,6 +74,12 @@ pub struct EmitMessage<'a> {
     pub message: &'a str,
     pub timestamp: Option<DateTime<Utc>>,
     pub append_newline: bool,
+    /// RFC5424 PROCID；缺省输出 `-`
+    pub proc_id: Option<&'a str>,
+    /// RFC5424 MSGID；缺省输出 `-`
+    pub msg_id: Option<&'a str>,
+    /// RFC5424 STRUCTURED-DATA 元素；缺省或为空时输出 `-`
+    pub structured_data: Option<Vec<SdElement>>,
 }
 
 impl<'a> EmitMessage<'a> {
@@ -21,6 +91,9 @@ impl<'a> EmitMessage<'a> {
             message,
             timestamp: None,
             append_newline: false,
+            proc_id: None,
+            msg_id: None,
+            structured_data: None,
         }
     }
 }
@@ -52,11 +125,83 @@ impl SyslogEncoder {
         }
         Bytes::from(line)
     }
+
+    /// Encode an RFC5424 line: `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID SD MSG`.
+    pub fn encode_rfc5424(&self, msg: &EmitMessage<'_>) -> Bytes {
+        let ts = msg
+            .timestamp
+            .unwrap_or_else(Utc::now)
+            .to_rfc3339_opts(SecondsFormat::Micros, true);
+        let hostname = header_field(msg.hostname, 255);
+        let app_name = header_field(msg.app_name, 48);
+        let proc_id = header_field(msg.proc_id, 128);
+        let msg_id = header_field(msg.msg_id, 32);
+        let sd = match msg.structured_data.as_deref() {
+            Some(elements) if !elements.is_empty() => elements
+                .iter()
+                .map(SdElement::to_string)
+                .collect::<String>(),
+            _ => "-".to_string(),
+        };
+        let mut line = format!(
+            "<{}>1 {} {} {} {} {} {}",
+            msg.priority, ts, hostname, app_name, proc_id, msg_id, sd
+        );
+        if !msg.message.is_empty() {
+            line.push(' ');
+            line.push_str(msg.message);
+        }
+        if msg.append_newline && !line.ends_with('\n') {
+            line.push('\n');
+        }
+        Bytes::from(line)
+    }
+}
+
+/// RFC5424 头部字段：仅保留可打印 ASCII（不含空格），按上限截断，空值输出 `-`。
+fn header_field(v: Option<&str>, max_len: usize) -> String {
+    let s: String = v
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if s.is_empty() { "-".to_string() } else { s }
+}
+
+/// 按 RFC5424 §6.3.3 转义 SD-PARAM 取值中的 `"`、`\` 与 `]`。
+pub fn escape_sd_value(v: &str) -> String {
+    let mut out = String::with_capacity(v.len());
+    for c in v.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// SD-NAME：1..=32 个可打印 ASCII，且不含 `=`、空格、`]`、`"`（RFC5424 §6.3）
+pub fn is_sd_name(name: &str) -> bool {
+    (1..=32).contains(&name.len())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && !matches!(b, b'=' | b']' | b'"'))
+}
+
+/// 按 RFC6587 §3.4.1 octet-counting 分帧：`MSG-LEN SP SYSLOG-MSG`。
+pub fn frame_octet_counted(msg: &[u8]) -> Bytes {
+    let mut buf = Vec::with_capacity(msg.len() + 8);
+    buf.extend_from_slice(msg.len().to_string().as_bytes());
+    buf.push(b' ');
+    buf.extend_from_slice(msg);
+    Bytes::from(buf)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wp_model_core::model::types::value::ObjectValue;
 
     #[test]
     fn encode_default_line() {
@@ -67,4 +212,110 @@ mod tests {
         assert!(text.contains("hello world"));
         assert!(text.contains("<13>"));
     }
+
+    #[test]
+    fn encode_rfc5424_line() {
+        let encoder = SyslogEncoder::new();
+        let mut msg = EmitMessage::new("user login");
+        msg.priority = 14;
+        msg.hostname = Some("edge-01");
+        msg.app_name = Some("wp engine");
+        msg.structured_data = Some(vec![
+            SdElement::new("wp@32473").with_param("src_ip", "10.0.0.1"),
+        ]);
+        msg.timestamp = Some(
+            DateTime::parse_from_rfc3339("2024-05-01T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let text = String::from_utf8(encoder.encode_rfc5424(&msg).to_vec()).unwrap();
+        assert_eq!(
+            text,
+            r#"<14>1 2024-05-01T08:00:00.000000Z edge-01 wpengine - - [wp@32473 src_ip="10.0.0.1"] user login"#
+        );
+    }
+
+    #[test]
+    fn encode_rfc5424_nil_values() {
+        let encoder = SyslogEncoder::new();
+        let mut msg = EmitMessage::new("");
+        msg.append_newline = true;
+        let text = String::from_utf8(encoder.encode_rfc5424(&msg).to_vec()).unwrap();
+        assert!(text.starts_with("<13>1 "));
+        assert!(text.ends_with(" - - - - -\n"), "{}", text);
+    }
+
+    #[test]
+    fn escape_sd_value_specials() {
+        assert_eq!(escape_sd_value(r#"a"b\c]d"#), r#"a\"b\\c\]d"#);
+        assert_eq!(escape_sd_value("plain"), "plain");
+    }
+
+    #[test]
+    fn sd_element_escapes_param_values() {
+        let element = SdElement::new("exampleSDID@32473")
+            .with_param("iut", "3")
+            .with_param("path", r#"C:\tmp\a.log"#)
+            .with_param("quote", r#"say "hi""#)
+            .with_param("bracket", "[x]")
+            .with_param("empty", "");
+        assert_eq!(
+            element.to_string(),
+            r#"[exampleSDID@32473 iut="3" path="C:\\tmp\\a.log" quote="say \"hi\"" bracket="[x\]" empty=""]"#
+        );
+        // 已转义的反斜杠再次出现时逐个转义，不做合并
+        assert_eq!(escape_sd_value(r#"\]"#), r#"\\\]"#);
+    }
+
+    #[test]
+    fn encode_rfc5424_multiple_sd_elements() {
+        let mut msg = EmitMessage::new("m");
+        msg.timestamp = Some(
+            DateTime::parse_from_rfc3339("2024-05-01T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        msg.structured_data = Some(vec![
+            SdElement::new("a@1").with_param("k", "v]"),
+            SdElement::new("b@2"),
+        ]);
+        let text = String::from_utf8(SyslogEncoder::new().encode_rfc5424(&msg).to_vec()).unwrap();
+        assert!(
+            text.ends_with(r#" - - - - [a@1 k="v\]"][b@2] m"#),
+            "{}",
+            text
+        );
+
+        msg.structured_data = Some(Vec::new());
+        let text = String::from_utf8(SyslogEncoder::new().encode_rfc5424(&msg).to_vec()).unwrap();
+        assert!(text.ends_with(" - - - - - m"), "{}", text);
+    }
+
+    #[test]
+    fn sd_element_from_record_flattens_objects() {
+        let mut obj = ObjectValue::default();
+        obj.insert("user".to_string(), DataField::from_chars("user", "bob"));
+        let mut record = DataRecord::default();
+        record.append(DataField::from_chars("src_ip", "10.0.0.1"));
+        record.append(DataField::from_digit("count", 3));
+        record.append(DataField::from_chars("bad name", "x"));
+        record.append(DataField::from_obj("actor", obj));
+
+        let element = SdElement::from_record("wp@32473", &record).unwrap();
+        assert_eq!(
+            element.params,
+            vec![
+                ("src_ip".to_string(), "10.0.0.1".to_string()),
+                ("count".to_string(), "3".to_string()),
+                ("actor.user".to_string(), "bob".to_string()),
+            ]
+        );
+        assert!(SdElement::from_record("wp@32473", &DataRecord::default()).is_none());
+    }
+
+    #[test]
+    fn octet_counting_frame() {
+        let framed = frame_octet_counted("<13>1 héllo".as_bytes());
+        assert_eq!(framed.as_ref(), "12 <13>1 héllo".as_bytes());
+    }
 }
@@ -6,4 +6,6 @@
 
 mod encoder;
 
-pub use encoder::{EmitMessage, SyslogEncoder};
+pub use encoder::{
+    EmitMessage, SdElement, SyslogEncoder, escape_sd_value, frame_octet_counted, is_sd_name,
+};
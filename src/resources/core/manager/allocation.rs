@@ -4,6 +4,7 @@ use crate::resources::sinks::sink_resources::SinkResUnit;
 use crate::resources::utils::multi_code_ins_parse_units;
 use crate::resources::{RuleKey, SinkID};
 use crate::sinks::{SinkGroupAgent, SinkRouteAgent};
+use oml::language::DataModel;
 use orion_error::UvsLogicFrom;
 use wp_error::RunReason;
 use wp_error::run_error::RunResult;
@@ -50,9 +51,17 @@ impl ParserResAlloc for ResManager {
 impl ResManager {
     pub async fn alloc_sink_res(&self, sink_name: &SinkID) -> RunResult<SinkResUnit> {
         info_ctrl!("alloc sink res : sink {} ", sink_name);
-        let sink_mdls_opt = self.sink_mdl_relation.get(sink_name);
         let mut sink_res = SinkResUnit::default();
-        if let Some(sink_mdls) = sink_mdls_opt {
+        for mdl in self.sink_models(sink_name) {
+            sink_res.push_model(mdl);
+        }
+        Ok(sink_res.bind_rule_slot(sink_name.clone(), self.rule_slot.clone()))
+    }
+
+    /// sink 组关联的 OML 模型
+    pub(crate) fn sink_models(&self, sink_name: &SinkID) -> Vec<DataModel> {
+        let mut models = Vec::new();
+        if let Some(sink_mdls) = self.sink_mdl_relation.get(sink_name) {
             for mdl_name in sink_mdls {
                 debug_ctrl!("will match model name {} ", mdl_name);
                 if let Some(mdl_obj) = self.name_mdl_res.get(mdl_name) {
@@ -61,12 +70,12 @@ impl ResManager {
                         sink_name,
                         mdl_name
                     );
-                    sink_res.push_model(mdl_obj.clone())
+                    models.push(mdl_obj.clone())
                 }
             }
             // PUBLIC_ADM additions 已废弃：不再注入全局 additions 模型
         }
-        Ok(sink_res)
+        models
     }
 }
 
@@ -87,6 +96,7 @@ impl ResManager {
                 multi_code_ins_parse_units(self, wpl_pkg, &mut idx_keeper, stat_reqs.clone())?;
             self.parse_units.append(&mut parsers);
         }
+        self.rule_slot.init(self.rule_set());
         Ok(())
     }
 }
@@ -78,4 +78,35 @@ mod tests {
         let rule_key3 = RuleKey::from(&String::from("/test/rule3"));
         assert_eq!(rule_key3.0, "/test/rule3");
     }
+
+    #[test]
+    fn test_route_schema_checks_routed_models() {
+        use crate::resources::ModelName;
+        use oml::language::DataModel;
+        use wp_conf::structure::{RouteSchemaConf, SinkRouteConf};
+
+        let mut res_manager = ResManager::default();
+        for (name, code) in [
+            ("es_a", "name : es_a\n---\nport : chars = read(port) ;\n"),
+            ("es_b", "name : es_b\n---\nport : digit = read(port) ;\n"),
+            ("other", "name : other\n---\nport : chars = read(port) ;\n"),
+        ] {
+            let mdl = oml::oml_parse_raw(&mut &code[..]).expect("parse oml");
+            res_manager
+                .name_mdl_res
+                .insert(ModelName::from(name), DataModel::Object(mdl));
+        }
+        let mut group = FlexGroup::test_new("es", "/test/*");
+        group.oml = extend_matches(vec!["es_*"]);
+        group.schema = Some(RouteSchemaConf {
+            fields: [("port".to_string(), "digit".to_string())].into(),
+            ..RouteSchemaConf::default()
+        });
+        let conf = SinkRouteConf {
+            version: "2.0".into(),
+            sink_group: group,
+        };
+        // 只有路由到本组且声明为 chars 的 es_a 不一致
+        assert_eq!(res_manager.check_route_schema(&conf), 1);
+    }
 }
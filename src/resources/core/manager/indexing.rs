@@ -1,5 +1,6 @@
 use crate::core::parser::SpaceIndex;
 use crate::resources::SinkID;
+use oml::language::DataModel;
 use wp_conf::structure::SinkRouteConf;
 
 use super::res_manager::ResManager;
@@ -35,6 +36,29 @@ impl ResManager {
         }
     }
 
+    /// 路由配置了 schema 时，对照按 `oml` 路由到该组的模型所声明的字段类型；不一致只告警
+    pub(crate) fn check_route_schema(&self, conf: &SinkRouteConf) -> usize {
+        let group_conf = &conf.sink_group;
+        let mut count = 0;
+        for (mdl_name, mdl) in &self.name_mdl_res {
+            let DataModel::Object(obj) = mdl else {
+                continue;
+            };
+            for m in group_conf.schema_mismatches(mdl_name.0.as_str(), obj.typed_targets()) {
+                warn_ctrl!(
+                    "sink group '{}' schema: model '{}' declares '{}' as {}, schema expects {}",
+                    group_conf.name(),
+                    mdl_name.0.as_str(),
+                    m.field,
+                    m.declared,
+                    m.expected
+                );
+                count += 1;
+            }
+        }
+        count
+    }
+
     pub(crate) fn update_sink_rule_index(
         &mut self,
         wpl_index: &SpaceIndex,
@@ -52,6 +52,7 @@ impl ResManager {
                     continue;
                 }
                 info_data!("oml load success, from {} ", path);
+                crate::stat::prometheus::watch_oml_model(mdl.name(), mdl.lookup_counter());
                 for w_rule in mdl.rules().as_ref() {
                     for r_path in wpl_index.rule_key().iter() {
                         if w_rule.matches(r_path.as_str()) {
@@ -94,6 +95,7 @@ impl ResManager {
                 // 现有的方法正确处理 FlexGroup rule 和 oml 字段
                 self.update_sink_rule_index(&wpl_index, &mut conf);
                 self.update_sink_mdl_index(&conf);
+                self.check_route_schema(&conf);
                 sink_route.add_route(conf.sink_group);
             }
             self.sink_table = Some(sink_route.clone());
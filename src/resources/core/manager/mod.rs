@@ -5,6 +5,7 @@ pub mod allocation;
 pub mod indexing;
 pub mod loading;
 pub mod oml_repository;
+pub mod reload;
 pub mod res_manager;
 
 #[cfg(test)]
@@ -15,4 +16,5 @@ mod flexgroup_rule_test;
 
 // Re-export types so external modules keep importing `core::manager::*` as before.
 pub use oml_repository::OmlRepository;
+pub use reload::{RuleReloader, RuleSet, RuleSetSlot};
 pub use res_manager::{ResManager, RuleMdlMapping};
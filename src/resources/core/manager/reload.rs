@@ -0,0 +1,230 @@
+//! WPL/OML 资源热重载：不重启引擎替换规则与模型。
+//!
+//! - [`RuleSet`] 是一代完整的规则集快照（解析管线 + 各 sink 组的 OML 模型）。
+//! - [`RuleSetSlot`] 持有当前一代，解析线程与 sink 组在批次边界检查代号并切换；
+//!   处理中的批次继续持有旧一代的 `Arc`，处理完后旧集合自然释放。
+//! - [`RuleReloader`] 复用启动期装载路径构建候选集合，校验失败时保留旧集合并返回错误。
+//!   source/sink 实例不受影响，只替换规则与模型。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use oml::language::DataModel;
+use orion_error::{ErrorOwe, ToStructError, UvsLogicFrom};
+use orion_variate::EnvDict;
+use wp_conf::engine::EngineConfig;
+use wp_error::RunReason;
+use wp_error::run_error::RunResult;
+use wp_stat::StatReq;
+
+use crate::core::parser::{WplPipeline, WplRepository};
+use crate::resources::SinkID;
+use crate::resources::utils::load_engine_code;
+use crate::sinks::{InfraSinkAgent, SinkRouteAgent};
+
+use super::res_manager::ResManager;
+
+/// 一代规则集：解析管线 + 按 sink 组归集的 OML 模型
+#[derive(Default)]
+pub struct RuleSet {
+    generation: u64,
+    parse_units: Vec<WplPipeline>,
+    sink_models: HashMap<SinkID, Vec<DataModel>>,
+}
+
+impl RuleSet {
+    pub fn new(
+        parse_units: Vec<WplPipeline>,
+        sink_models: HashMap<SinkID, Vec<DataModel>>,
+    ) -> Self {
+        Self {
+            generation: 0,
+            parse_units,
+            sink_models,
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn parse_units(&self) -> &Vec<WplPipeline> {
+        &self.parse_units
+    }
+
+    /// sink 组在本代规则集中关联的模型；未关联时为空
+    pub fn models_of(&self, sink: &SinkID) -> Vec<DataModel> {
+        self.sink_models.get(sink).cloned().unwrap_or_default()
+    }
+}
+
+/// 当前生效规则集的持有者；代号单调递增，供处理线程低成本判断是否需要切换
+#[derive(Default)]
+pub struct RuleSetSlot {
+    current: RwLock<Arc<RuleSet>>,
+    generation: AtomicU64,
+}
+
+impl RuleSetSlot {
+    /// 填充启动期规则集（不推进代号，启动期已分配的资源无需切换）
+    pub fn init(&self, mut set: RuleSet) {
+        set.generation = self.generation();
+        *self.current.write().expect("rule set slot poisoned") = Arc::new(set);
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub fn load(&self) -> Arc<RuleSet> {
+        self.current.read().expect("rule set slot poisoned").clone()
+    }
+
+    /// 原子替换为新一代规则集，返回新代号
+    pub fn swap(&self, mut set: RuleSet) -> u64 {
+        let mut current = self.current.write().expect("rule set slot poisoned");
+        let generation = current.generation + 1;
+        set.generation = generation;
+        *current = Arc::new(set);
+        self.generation.store(generation, Ordering::Release);
+        generation
+    }
+
+    /// 候选集合构建/校验成功时替换，失败时保留当前一代并返回错误
+    pub fn try_swap(&self, candidate: RunResult<RuleSet>) -> RunResult<u64> {
+        match candidate {
+            Ok(set) => {
+                let generation = self.swap(set);
+                info_ctrl!("rule set reloaded, generation={}", generation);
+                Ok(generation)
+            }
+            Err(e) => {
+                error_ctrl!(
+                    "rule set reload rejected, keep generation={}: {}",
+                    self.generation(),
+                    e
+                );
+                Err(e)
+            }
+        }
+    }
+}
+
+impl ResManager {
+    /// 由当前已装载的资源生成规则集快照
+    pub fn rule_set(&self) -> RuleSet {
+        let sink_models = self
+            .sink_mdl_relation
+            .iter()
+            .map(|(sink, _)| (sink.clone(), self.sink_models(sink)))
+            .collect();
+        RuleSet::new(self.parse_units.clone(), sink_models)
+    }
+
+    /// 构建热重载器；需在 `ins_engine_res` 之后调用（依赖 sink 路由句柄）
+    pub fn rule_reloader(
+        &self,
+        main_conf: &EngineConfig,
+        dict: &EnvDict,
+        stat_reqs: Vec<StatReq>,
+    ) -> RunResult<RuleReloader> {
+        let infra = self
+            .infra_agent
+            .clone()
+            .ok_or(RunReason::from_logic("not init infra agent").to_err())?;
+        let route = self
+            .route_agent
+            .clone()
+            .ok_or(RunReason::from_logic("not init route agent").to_err())?;
+        Ok(RuleReloader {
+            main_conf: main_conf.clone(),
+            dict: dict.clone(),
+            stat_reqs,
+            infra,
+            route,
+            slot: self.rule_slot.clone(),
+        })
+    }
+}
+
+/// WPL/OML 热重载器（SIGHUP / 控制面命令触发）
+#[derive(Clone)]
+pub struct RuleReloader {
+    main_conf: EngineConfig,
+    dict: EnvDict,
+    stat_reqs: Vec<StatReq>,
+    infra: InfraSinkAgent,
+    route: SinkRouteAgent,
+    slot: Arc<RuleSetSlot>,
+}
+
+impl RuleReloader {
+    pub fn slot(&self) -> &Arc<RuleSetSlot> {
+        &self.slot
+    }
+
+    /// 重新装载并校验 WPL/OML，成功后原子替换规则集，返回新代号；
+    /// 启用语义分析时一并重载语义词典（失败的词典文件跳过并告警）
+    pub async fn reload(&self) -> RunResult<u64> {
+        info_ctrl!("rule set reloading...");
+        if oml::is_semantic_enabled() {
+            for e in oml::reload_semantic_dict() {
+                warn_ctrl!("semantic dict reload: {}", e);
+            }
+        }
+        let candidate = self.build_candidate().await;
+        self.slot.try_swap(candidate)
+    }
+
+    async fn build_candidate(&self) -> RunResult<RuleSet> {
+        // 校验：与 check 一致按严格模式编译 WPL；启动路径是容错模式，会吞掉语法错误
+        let wpl_code = load_engine_code(&self.main_conf).await?;
+        WplRepository::from_wpl_strict(wpl_code).owe_rule()?;
+
+        let mut fresh = ResManager::default();
+        fresh.set_infra_agent(self.infra.clone());
+        fresh
+            .load_all_wpl_code(&self.main_conf, &self.infra.error)
+            .await?;
+        fresh.load_all_ldm(self.main_conf.oml_root()).await?;
+        fresh.load_all_sink(self.main_conf.sinks_root(), &self.dict)?;
+        fresh.ins_engine_res(self.route.clone(), self.stat_reqs.clone())?;
+        Ok(fresh.rule_set())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_with(sink: &str, cnt: usize) -> RuleSet {
+        let mut models = HashMap::new();
+        models.insert(SinkID::from(sink), vec![DataModel::default(); cnt]);
+        RuleSet::new(Vec::new(), models)
+    }
+
+    #[test]
+    fn swap_advances_generation_and_keeps_old_snapshot_alive() {
+        let slot = RuleSetSlot::default();
+        slot.init(set_with("biz", 1));
+        assert_eq!(slot.generation(), 0);
+
+        let in_flight = slot.load();
+        assert_eq!(slot.swap(set_with("biz", 2)), 1);
+        assert_eq!(in_flight.models_of(&SinkID::from("biz")).len(), 1);
+        assert_eq!(slot.load().models_of(&SinkID::from("biz")).len(), 2);
+        assert_eq!(slot.load().generation(), 1);
+    }
+
+    #[test]
+    fn failed_candidate_keeps_current_generation() {
+        let slot = RuleSetSlot::default();
+        slot.init(set_with("biz", 1));
+        let bad: RunResult<RuleSet> = Err(RunReason::from_logic("bad oml").to_err());
+        assert!(slot.try_swap(bad).is_err());
+        assert_eq!(slot.generation(), 0);
+        assert_eq!(slot.load().models_of(&SinkID::from("biz")).len(), 1);
+        assert_eq!(slot.try_swap(Ok(set_with("biz", 3))).unwrap(), 1);
+    }
+}
@@ -2,6 +2,7 @@ use derive_getters::Getters;
 use orion_variate::EnvDict;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
 use crate::core::parser::{SpaceIndex, WplPipeline, WplRepository};
 use crate::orchestrator::config::build_sinks::SinkRouteTable;
@@ -9,6 +10,8 @@ use crate::resources::{ModelName, SinkID};
 use crate::resources::{SinkModelIndex, SinkRuleRegistry};
 use crate::runtime::sink::infrastructure::InfraSinkService;
 use crate::sinks::{InfraSinkAgent, SinkRouteAgent};
+
+use super::reload::RuleSetSlot;
 use orion_error::{ErrorOwe, ToStructError, UvsLogicFrom};
 use wp_conf::engine::EngineConfig;
 use wp_error::RunReason;
@@ -56,6 +59,8 @@ pub struct ResManager {
     pub(crate) infra_agent: Option<InfraSinkAgent>,
     pub(crate) parse_units: Vec<WplPipeline>,
     pub(crate) sink_table: Option<SinkRouteTable>,
+    /// 当前生效的规则集（热重载时原子替换）
+    pub(crate) rule_slot: Arc<RuleSetSlot>,
 }
 
 impl ResManager {
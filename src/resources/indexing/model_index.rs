@@ -29,6 +29,9 @@ impl SinkModelIndex {
     pub fn get(&self, sink_name: &SinkID) -> Option<&ModelNameSet> {
         self.0.get(sink_name)
     }
+    pub fn iter(&self) -> impl Iterator<Item = (&SinkID, &ModelNameSet)> {
+        self.0.iter()
+    }
 }
 impl Display for SinkModelIndex {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
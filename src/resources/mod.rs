@@ -11,3 +11,4 @@ pub use sinks::sink_resources::SinkResUnit;
 
 pub use core::manager::OmlRepository;
 pub use core::manager::ResManager;
+pub use core::manager::{RuleReloader, RuleSet, RuleSetSlot};
@@ -1,9 +1,23 @@
+use std::sync::Arc;
+
 use derive_getters::Getters;
 use oml::language::DataModel;
 
+use crate::resources::{RuleSetSlot, SinkID};
+
 #[derive(Getters, Clone, Default)]
 pub struct SinkResUnit {
     aggregate_mdl: Vec<DataModel>,
+    #[getter(skip)]
+    hot: Option<HotModels>,
+}
+
+/// 热重载绑定：所属 sink 组 + 规则集槽 + 当前使用的代号
+#[derive(Clone)]
+struct HotModels {
+    sink: SinkID,
+    slot: Arc<RuleSetSlot>,
+    generation: u64,
 }
 
 impl SinkResUnit {
@@ -13,6 +27,32 @@ impl SinkResUnit {
     pub fn use_null() -> Self {
         Self {
             aggregate_mdl: Vec::new(),
+            hot: None,
+        }
+    }
+
+    /// 绑定规则集槽，使模型随热重载切换
+    pub fn bind_rule_slot(mut self, sink: SinkID, slot: Arc<RuleSetSlot>) -> Self {
+        let generation = slot.generation();
+        self.hot = Some(HotModels {
+            sink,
+            slot,
+            generation,
+        });
+        self
+    }
+
+    /// 规则集已被替换时切换到新一代模型；返回是否发生切换
+    pub fn refresh(&mut self) -> bool {
+        let Some(hot) = self.hot.as_mut() else {
+            return false;
+        };
+        if hot.slot.generation() == hot.generation {
+            return false;
         }
+        let set = hot.slot.load();
+        self.aggregate_mdl = set.models_of(&hot.sink);
+        hot.generation = set.generation();
+        true
     }
 }
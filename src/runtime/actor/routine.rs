@@ -27,6 +27,14 @@ impl TaskManager {
         self.h_groups.last_mut().unwrap()
     }
 
+    /// 主任务组（采集）是否已全部结束；未设置主组时视为已结束
+    pub fn main_is_finished(&self) -> bool {
+        self.main
+            .as_ref()
+            .map(|main| main.routin_is_finished())
+            .unwrap_or(true)
+    }
+
     pub async fn all_down_wait_signal(&mut self) -> RunResult<()> {
         let mut signals = stop_signals()?;
         if let Some(main) = &mut self.main {
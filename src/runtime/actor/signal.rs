@@ -33,6 +33,14 @@ pub fn stop_signals() -> RunResult<Signals> {
     Ok(signals)
 }
 
+/// 规则热重载信号（SIGHUP）
+pub fn reload_signals() -> RunResult<Signals> {
+    let signals = Signals::new([Signal::Hup])
+        .owe_sys()
+        .want("set reload signal")?;
+    Ok(signals)
+}
+
 pub async fn get_stop(is_end: impl Fn() -> bool) -> RunResult<ShutdownCmd> {
     if is_end() {
         return Ok(ShutdownCmd::Immediate);
@@ -18,6 +18,9 @@ pub(crate) const PICKER_BURST_MAX: usize = 16;
 /// Timeout (milliseconds) when fetching from blocking sources in a round.
 pub(crate) const PICKER_FETCH_TIMEOUT_MS: u64 = 300;
 
+/// Pause (milliseconds) per dispatch loop while a sink circuit breaker holds backpressure.
+pub(crate) const PICKER_SINK_BACKPRESSURE_PAUSE_MS: u64 = 200;
+
 /// Post-policy initial backoff rounds.
 pub(crate) const PICKER_POST_BACKOFF_INITIAL_ROUNDS: u32 = 1;
 
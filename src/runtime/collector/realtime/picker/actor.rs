@@ -2,6 +2,7 @@ use crate::runtime::collector::realtime::constants::PICKER_PENDING_CAPACITY;
 use crate::runtime::collector::realtime::picker::policy::PostPolicy;
 use crate::runtime::collector::realtime::picker::policy::PullPolicy;
 use crate::runtime::parser::workflow::ParseWorkerSender;
+use crate::stat::trace::{self, TraceStage};
 use crate::utils::rolling_queue::RollingQueue;
 use std::collections::VecDeque;
 
@@ -58,6 +59,11 @@ impl JMActPicker {
     }
     #[inline]
     pub(crate) fn extend_pending(&mut self, batch: SourceBatch) {
+        if trace::enabled() {
+            for event in &batch {
+                trace::span(event.event_id, TraceStage::Intake, event.src_key.as_str());
+            }
+        }
         self.pending.push_back(batch);
         // 当 pending 水位接近上限时，抽样打印，辅助定位“解析前积压”导致的内存增长
         const WARN_THRESHOLD: usize =
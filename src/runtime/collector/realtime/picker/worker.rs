@@ -3,13 +3,15 @@ use crate::runtime::actor::command::{CmdSubscriber, TaskController, spawn_ctrl_e
 use crate::runtime::actor::constants::ACTOR_IDLE_TICK_MS;
 use crate::runtime::collector::realtime::constants::{
     PICKER_CTRL_EVENT_BUFFER, PICKER_DEFAULT_ROUND_BATCH, PICKER_EVENT_CNT_OF_BATCH,
-    PICKER_FETCH_TIMEOUT_MS,
+    PICKER_FETCH_TIMEOUT_MS, PICKER_SINK_BACKPRESSURE_PAUSE_MS,
 };
 use crate::runtime::collector::realtime::picker::round::{RoundStat, SrcStatus};
 // stop_routine_run/err4_dispatch_data 仅在 dispatch.rs 中使用
 use crate::runtime::parser::workflow::ParseWorkerSender;
 use crate::runtime::prelude::*;
+use crate::sinks::SinkBackpressure;
 use crate::stat::metric_collect::MetricCollectors;
+use crate::stat::prometheus;
 use crate::stat::{MonSend, STAT_INTERVAL_MS};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
@@ -140,6 +142,15 @@ impl SourceWorker {
     }
 
     fn calc_sleep_duration(&self, round: &RoundStat, task_ctrl: &TaskController) -> Duration {
+        let base = self.base_sleep_duration(round, task_ctrl);
+        // 下游 sink 熔断时放缓拉取（仍保留少量流量，供半开探测使用）
+        if SinkBackpressure::global().is_active() {
+            return base.max(Duration::from_millis(PICKER_SINK_BACKPRESSURE_PAUSE_MS));
+        }
+        base
+    }
+
+    fn base_sleep_duration(&self, round: &RoundStat, task_ctrl: &TaskController) -> Duration {
         // 仅在“完成一个限速单元”时才执行限速休眠；
         // 如果本轮处理量低于目标（如下游偏慢），不再因为限速而额外休眠，避免越限越慢。
         let throttle = if task_ctrl.is_unit_end() {
@@ -169,6 +180,7 @@ impl SourceWorker {
     ) -> RunResult<()> {
         // 初始化统计与任务控制器
         let mut stat_ext = MetricCollectors::new(source.identifier(), stat_reqs);
+        let received = prometheus::source_received(&source.identifier());
         let rt_name = format!("{}-picker", source.identifier());
         let round_batch = PICKER_DEFAULT_ROUND_BATCH;
         let event_cnt_of_batch = PICKER_EVENT_CNT_OF_BATCH;
@@ -201,10 +213,12 @@ impl SourceWorker {
                     break 'main;
                 }
                 // 单轮流程：拉取→（可选）发送→记录统计
+                let delivered_before = task_ctrl.total_count();
                 let one_round = self
                     .picker
                     .round_pick(source, &mut task_ctrl, &mut stat_ext, timeout)
                     .await?;
+                received.add(task_ctrl.total_count().saturating_sub(delivered_before) as u64);
                 match one_round.src_status() {
                     SrcStatus::Ready => {
                         trace_ctrl!(
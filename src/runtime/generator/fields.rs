@@ -0,0 +1,218 @@
+//! 字段值池：将规则生成结果中的 `{name}` 占位符按 `[generator.fields]` 配置逐条替换。
+//!
+//! 每条事件对每个出现的字段取一次值（同一事件内重复出现的占位符取值相同）；
+//! 设置 `seed` 后各流水线使用 `seed + pipe_idx` 播种，单流水线时输出逐字节可复现。
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use orion_error::UvsReason;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use wp_conf::generator::wpgen::FieldPoolConfig;
+use wp_error::run_error::{RunError, RunReason, RunResult};
+
+#[derive(Debug)]
+enum Pool {
+    Weighted {
+        values: Vec<String>,
+        /// 累计权重，按二分查找定位取值
+        cumulative: Vec<u64>,
+    },
+    Ipv4 {
+        base: u32,
+        size: u64,
+    },
+    Int {
+        min: i64,
+        max: i64,
+    },
+    /// 各流水线共享计数，保证序号不重复
+    Seq(AtomicU64),
+}
+
+impl Pool {
+    fn from_conf(name: &str, conf: &FieldPoolConfig) -> RunResult<Self> {
+        let err = |msg: String| {
+            RunError::from(RunReason::Uvs(UvsReason::core_conf(format!(
+                "wpgen field '{}': {}",
+                name, msg
+            ))))
+        };
+        if let Some(values) = &conf.values {
+            let weights = conf
+                .weights
+                .clone()
+                .unwrap_or_else(|| vec![1; values.len()]);
+            if values.is_empty() || weights.len() != values.len() {
+                return Err(err("values/weights mismatch".to_string()));
+            }
+            let cumulative: Vec<u64> = weights
+                .iter()
+                .scan(0u64, |acc, w| {
+                    *acc += u64::from(*w);
+                    Some(*acc)
+                })
+                .collect();
+            if cumulative.last() == Some(&0) {
+                return Err(err("all weights are zero".to_string()));
+            }
+            return Ok(Pool::Weighted {
+                values: values.clone(),
+                cumulative,
+            });
+        }
+        if let Some(cidr) = &conf.ip_range {
+            let (base, size) = FieldPoolConfig::ipv4_range(cidr).map_err(err)?;
+            return Ok(Pool::Ipv4 { base, size });
+        }
+        if let Some([min, max]) = conf.int_range {
+            if min > max {
+                return Err(err(format!("int_range [{}, {}] is empty", min, max)));
+            }
+            return Ok(Pool::Int { min, max });
+        }
+        if let Some(start) = conf.seq {
+            return Ok(Pool::Seq(AtomicU64::new(start)));
+        }
+        Err(err(
+            "needs one of values | ip_range | int_range | seq".to_string()
+        ))
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> String {
+        match self {
+            Pool::Weighted { values, cumulative } => {
+                let total = cumulative.last().copied().unwrap_or(1);
+                let pick = rng.random_range(0..total);
+                let idx = cumulative.partition_point(|c| *c <= pick);
+                values[idx].clone()
+            }
+            Pool::Ipv4 { base, size } => {
+                let offset = rng.random_range(0..*size) as u32;
+                Ipv4Addr::from(base.wrapping_add(offset)).to_string()
+            }
+            Pool::Int { min, max } => rng.random_range(*min..=*max).to_string(),
+            Pool::Seq(next) => next.fetch_add(1, Ordering::Relaxed).to_string(),
+        }
+    }
+}
+
+/// 编译后的字段值池，可在流水线间共享
+#[derive(Debug, Clone)]
+pub struct FieldPools {
+    /// (占位符 `{name}`, 取值池)，按字段名排序以保证取值顺序稳定
+    pools: Arc<Vec<(String, Pool)>>,
+    seed: Option<u64>,
+}
+
+impl FieldPools {
+    pub fn from_conf(
+        fields: &BTreeMap<String, FieldPoolConfig>,
+        seed: Option<u64>,
+    ) -> RunResult<Self> {
+        let pools = fields
+            .iter()
+            .map(|(name, conf)| Ok((format!("{{{}}}", name), Pool::from_conf(name, conf)?)))
+            .collect::<RunResult<Vec<_>>>()?;
+        Ok(Self {
+            pools: Arc::new(pools),
+            seed,
+        })
+    }
+
+    pub(crate) fn templater(&self, pipe_idx: usize) -> FieldTemplater {
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(pipe_idx as u64)),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+        FieldTemplater {
+            pools: self.pools.clone(),
+            rng,
+        }
+    }
+}
+
+/// 单条流水线持有的替换器
+pub(crate) struct FieldTemplater {
+    pools: Arc<Vec<(String, Pool)>>,
+    rng: StdRng,
+}
+
+impl FieldTemplater {
+    pub(crate) fn render(&mut self, line: String) -> String {
+        let mut out = line;
+        for (placeholder, pool) in self.pools.iter() {
+            if out.contains(placeholder.as_str()) {
+                let value = pool.sample(&mut self.rng);
+                out = out.replace(placeholder.as_str(), &value);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "src={src_ip} user={user} status={status} id={req_id} raw={literal}";
+
+    fn pools(seed: Option<u64>) -> FieldPools {
+        let conf: BTreeMap<String, FieldPoolConfig> = toml::from_str(
+            r#"
+user = { values = ["alice", "bob", "carol"], weights = [6, 3, 1] }
+src_ip = { ip_range = "10.1.0.0/16" }
+status = { int_range = [200, 599] }
+req_id = { seq = 1 }
+"#,
+        )
+        .unwrap();
+        FieldPools::from_conf(&conf, seed).unwrap()
+    }
+
+    fn render_n(pools: &FieldPools, n: usize) -> Vec<String> {
+        let mut t = pools.templater(0);
+        (0..n).map(|_| t.render(TEMPLATE.to_string())).collect()
+    }
+
+    #[test]
+    fn weighted_values_follow_configured_distribution() {
+        let lines = render_n(&pools(Some(7)), 10_000);
+        let count = |user: &str| {
+            lines
+                .iter()
+                .filter(|l| l.contains(&format!("user={} ", user)))
+                .count() as f64
+                / 10_000.0
+        };
+        for (user, expect) in [("alice", 0.6), ("bob", 0.3), ("carol", 0.1)] {
+            let got = count(user);
+            assert!((got - expect).abs() < 0.02, "{}: {}", user, got);
+        }
+        for line in &lines {
+            assert!(line.starts_with("src=10.1."), "{}", line);
+            let status: i64 = line
+                .split("status=")
+                .nth(1)
+                .and_then(|s| s.split(' ').next())
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!((200..=599).contains(&status), "{}", line);
+            assert!(line.ends_with("raw={literal}"), "{}", line);
+        }
+        assert!(lines[0].contains("id=1 ") && lines[9_999].contains("id=10000 "));
+    }
+
+    #[test]
+    fn same_seed_reproduces_identical_output() {
+        let a = render_n(&pools(Some(42)), 1_000).join("\n");
+        let b = render_n(&pools(Some(42)), 1_000).join("\n");
+        assert_eq!(a, b);
+        let c = render_n(&pools(Some(43)), 1_000).join("\n");
+        assert_ne!(a, c);
+    }
+}
@@ -1,11 +1,16 @@
 //pub mod direct_runner;
 mod common;
+mod fields;
+mod outputs;
+mod replay;
 mod rule;
 pub mod rule_source;
 mod sample;
 pub mod speed;
 pub mod types;
 
-pub use rule::run_rule_direct;
-pub use sample::run_sample_direct;
+pub use fields::FieldPools;
+pub use replay::{ReplayStats, run_replay_direct, run_replay_direct_outputs};
+pub use rule::{run_rule_direct, run_rule_direct_outputs};
+pub use sample::{run_sample_direct, run_sample_direct_outputs};
 pub use speed::{DynamicRateLimiter, DynamicSpeedController, SpeedProfile};
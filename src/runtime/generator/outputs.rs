@@ -0,0 +1,151 @@
+//! 多路输出分流：`[[outputs]]` 中的 ratio 输出互斥分取生成行，filter 输出复制匹配行，
+//! 未设置二者的输出接收全部行。
+//!
+//! ratio 分流采用平滑加权轮询（确定性），任意前缀内各输出的条数与期望份额相差不超过 1，
+//! 便于端到端测试按份额核对各输出的数据量。
+
+use orion_error::UvsReason;
+use regex::Regex;
+use wp_conf::generator::wpgen::{OutputRoute, WpGenOutput};
+use wp_error::run_error::{RunError, RunErrorOwe, RunReason, RunResult};
+
+use super::common::build_sink_instance;
+use crate::sinks::SinkBackendType;
+
+/// ratio 分流器：余量（1 - Σratio）作为一个虚拟槽位参与轮询，命中时丢弃该行
+#[derive(Debug, Clone)]
+pub(crate) struct RatioSplitter {
+    ratios: Vec<f64>,
+    credits: Vec<f64>,
+}
+
+impl RatioSplitter {
+    pub(crate) fn new(ratios: Vec<f64>) -> Self {
+        let rest = (1.0 - ratios.iter().sum::<f64>()).max(0.0);
+        let mut ratios = ratios;
+        ratios.push(rest);
+        let credits = vec![0.0; ratios.len()];
+        Self { ratios, credits }
+    }
+
+    /// 返回本行归属的 ratio 输出序号；落入余量时返回 None
+    pub(crate) fn next(&mut self) -> Option<usize> {
+        let mut best = 0;
+        for (i, ratio) in self.ratios.iter().enumerate() {
+            self.credits[i] += ratio;
+            if self.credits[i] > self.credits[best] {
+                best = i;
+            }
+        }
+        self.credits[best] -= 1.0;
+        (best + 1 < self.ratios.len()).then_some(best)
+    }
+}
+
+/// 生成计数：`generated` 驱动总量配额与限速；`written` 为至少写入一个输出的行数，
+/// 落入 ratio 余量或未命中任何 filter 的行不计入
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct GenCount {
+    pub(crate) generated: usize,
+    pub(crate) written: usize,
+}
+
+impl std::ops::AddAssign for GenCount {
+    fn add_assign(&mut self, other: Self) {
+        self.generated += other.generated;
+        self.written += other.written;
+    }
+}
+
+enum Lane {
+    All,
+    /// ratio 输出在分流器中的序号
+    Ratio(usize),
+    Filter(Regex),
+}
+
+/// 单条流水线持有的多路输出
+pub(crate) struct RoutedSink {
+    lanes: Vec<(Lane, SinkBackendType)>,
+    splitter: Option<RatioSplitter>,
+}
+
+impl RoutedSink {
+    pub(crate) async fn build(
+        outputs: &[WpGenOutput],
+        replica_idx: usize,
+        replica_cnt: usize,
+        rate_limit_rps: usize,
+    ) -> RunResult<Self> {
+        let mut lanes = Vec::with_capacity(outputs.len());
+        let mut ratios = Vec::new();
+        for out in outputs {
+            let lane = match &out.route {
+                OutputRoute::All => Lane::All,
+                OutputRoute::Ratio(r) => {
+                    ratios.push(*r);
+                    Lane::Ratio(ratios.len() - 1)
+                }
+                OutputRoute::Filter(expr) => Lane::Filter(Regex::new(expr).map_err(|e| {
+                    RunError::from(RunReason::Uvs(UvsReason::core_conf(format!(
+                        "wpgen output '{}' filter: {}",
+                        out.name, e
+                    ))))
+                })?),
+            };
+            let sink =
+                build_sink_instance(&out.sink, replica_idx, replica_cnt, rate_limit_rps).await?;
+            lanes.push((lane, sink));
+        }
+        let splitter = (!ratios.is_empty()).then(|| RatioSplitter::new(ratios));
+        Ok(Self { lanes, splitter })
+    }
+
+    /// 按各输出规则发送一行，返回实际写入的输出数
+    pub(crate) async fn sink_str(&mut self, line: &str) -> RunResult<usize> {
+        let picked = self.splitter.as_mut().and_then(RatioSplitter::next);
+        let mut delivered = 0;
+        for (lane, sink) in self.lanes.iter_mut() {
+            let hit = match lane {
+                Lane::All => true,
+                Lane::Ratio(idx) => picked == Some(*idx),
+                Lane::Filter(re) => re.is_match(line),
+            };
+            if hit {
+                wp_connector_api::AsyncRawDataSink::sink_str(sink, line)
+                    .await
+                    .owe_sink()?;
+                delivered += 1;
+            }
+        }
+        Ok(delivered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratio_splitter_matches_shares_exactly() {
+        let mut splitter = RatioSplitter::new(vec![0.9, 0.1]);
+        let mut counts = [0usize; 3];
+        for _ in 0..1000 {
+            match splitter.next() {
+                Some(i) => counts[i] += 1,
+                None => counts[2] += 1,
+            }
+        }
+        assert_eq!(counts, [900, 100, 0]);
+
+        let mut splitter = RatioSplitter::new(vec![0.5, 0.25]);
+        let mut counts = [0usize; 3];
+        for _ in 0..400 {
+            match splitter.next() {
+                Some(i) => counts[i] += 1,
+                None => counts[2] += 1,
+            }
+        }
+        assert_eq!(counts, [200, 100, 100]);
+    }
+}
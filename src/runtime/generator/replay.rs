@@ -0,0 +1,524 @@
+//! 回放模式：按事件内嵌时间戳的原始间隔重放数据文件，用于性能回归测试。
+//!
+//! 每条事件相对首条事件的时间偏移（除以 `speed_factor`）即其发送时刻，按绝对偏移调度，
+//! 发送耗时不会累积成漂移。时间戳早于此前最大值的事件不等待直接发送（计入 `out_of_order`），
+//! 无法解析时间戳的事件同样不等待（计入 `unparsed`）。`count` 与 `duration_secs` 仍然生效。
+//! 配置了多路 `[[outputs]]` 时按各输出的 ratio/filter 分发，未写入任何输出的事件计入 `unrouted`。
+
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime};
+use orion_error::UvsReason;
+use wp_conf::structure::SinkInstanceConf;
+use wp_error::run_error::{RunError, RunReason, RunResult};
+use wp_log::{info_ctrl, warn_ctrl};
+
+use super::outputs::RoutedSink;
+use super::types::ReplayGRA;
+use wp_conf::generator::wpgen::{ReplayConfig, WpGenOutput};
+
+/// 回放计时：真实运行使用 tokio 时钟，测试可替换为模拟时钟
+pub(crate) trait ReplayClock {
+    /// 回放开始以来经过的时间
+    fn elapsed(&self) -> Duration;
+    async fn sleep(&mut self, dur: Duration);
+}
+
+struct TokioClock {
+    start: tokio::time::Instant,
+}
+
+impl ReplayClock for TokioClock {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    async fn sleep(&mut self, dur: Duration) {
+        tokio::time::sleep(dur).await;
+    }
+}
+
+/// 回放输出端；返回该行是否写入了至少一个输出
+pub(crate) trait LineSink {
+    async fn send_line(&mut self, line: &str) -> RunResult<bool>;
+}
+
+impl LineSink for RoutedSink {
+    async fn send_line(&mut self, line: &str) -> RunResult<bool> {
+        Ok(self.sink_str(line).await? > 0)
+    }
+}
+
+/// 回放上限：`count` 与 `duration_secs`
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ReplayLimits {
+    pub count: Option<usize>,
+    pub duration: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    pub sent: usize,
+    /// 未写入任何输出（落入 ratio 余量或未命中 filter）的事件数
+    pub unrouted: usize,
+    /// 时间戳倒退、按零间隔发送的事件数
+    pub out_of_order: usize,
+    /// 时间戳缺失或无法解析、按零间隔发送的事件数
+    pub unparsed: usize,
+}
+
+/// 将事件时间戳（微秒）换算为相对回放开始的发送时刻
+struct ReplaySchedule {
+    speed_factor: f64,
+    first: Option<i64>,
+    latest: i64,
+}
+
+impl ReplaySchedule {
+    fn new(speed_factor: f64) -> Self {
+        Self {
+            speed_factor,
+            first: None,
+            latest: i64::MIN,
+        }
+    }
+
+    /// 返回发送时刻；时间戳倒退时返回 `None`（沿用当前进度，不等待）
+    fn offset(&mut self, ts: i64) -> Option<Duration> {
+        let first = *self.first.get_or_insert(ts);
+        if ts < self.latest {
+            return None;
+        }
+        self.latest = ts;
+        let micros = (ts - first) as f64 / self.speed_factor;
+        Some(Duration::from_micros(micros as u64))
+    }
+}
+
+pub(crate) async fn replay_lines<C: ReplayClock, S: LineSink>(
+    lines: &[String],
+    conf: &ReplayConfig,
+    limits: ReplayLimits,
+    clock: &mut C,
+    sink: &mut S,
+) -> RunResult<ReplayStats> {
+    let mut schedule = ReplaySchedule::new(conf.speed_factor);
+    let mut stats = ReplayStats::default();
+    for line in lines {
+        if limits
+            .count
+            .is_some_and(|n| stats.sent + stats.unrouted >= n)
+        {
+            break;
+        }
+        let ts = event_timestamp(line, conf);
+        let target = match ts {
+            Some(ts) => {
+                let target = schedule.offset(ts);
+                if target.is_none() {
+                    stats.out_of_order += 1;
+                }
+                target
+            }
+            None => {
+                stats.unparsed += 1;
+                None
+            }
+        };
+        if let Some(target) = target {
+            if limits.duration.is_some_and(|limit| target >= limit) {
+                break;
+            }
+            let now = clock.elapsed();
+            if target > now {
+                clock.sleep(target - now).await;
+            }
+        }
+        if limits
+            .duration
+            .is_some_and(|limit| clock.elapsed() >= limit)
+        {
+            break;
+        }
+        if sink.send_line(line).await? {
+            stats.sent += 1;
+        } else {
+            stats.unrouted += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// 提取事件时间戳（Unix 微秒）
+fn event_timestamp(line: &str, conf: &ReplayConfig) -> Option<i64> {
+    let format = conf.timestamp_format.as_str();
+    match &conf.timestamp_field {
+        Some(field) => parse_timestamp(&field_value(line, field)?, format),
+        None => leading_timestamp(line, format),
+    }
+}
+
+fn field_value(line: &str, field: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('{') {
+        let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+        return match json.get(field)? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        };
+    }
+    let key = format!("{}=", field);
+    let mut rest = line;
+    while let Some(pos) = rest.find(&key) {
+        let at_boundary = rest[..pos]
+            .chars()
+            .last()
+            .is_none_or(|c| c.is_whitespace() || c == ',' || c == ';');
+        let value = &rest[pos + key.len()..];
+        if at_boundary {
+            return Some(match value.strip_prefix('"') {
+                Some(quoted) => quoted.split('"').next().unwrap_or_default().to_string(),
+                None => value
+                    .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+        rest = value;
+    }
+    None
+}
+
+fn leading_timestamp(line: &str, format: &str) -> Option<i64> {
+    match format {
+        "rfc3339" | "epoch" | "epoch_ms" => {
+            parse_timestamp(line.split_whitespace().next()?, format)
+        }
+        fmt => NaiveDateTime::parse_and_remainder(line.trim_start(), fmt)
+            .ok()
+            .map(|(dt, _)| dt.and_utc().timestamp_micros()),
+    }
+}
+
+fn parse_timestamp(text: &str, format: &str) -> Option<i64> {
+    let text = text.trim();
+    match format {
+        "rfc3339" => DateTime::parse_from_rfc3339(text)
+            .ok()
+            .map(|dt| dt.timestamp_micros()),
+        "epoch" => text
+            .parse::<f64>()
+            .ok()
+            .map(|secs| (secs * 1_000_000.0) as i64),
+        "epoch_ms" => text.parse::<i64>().ok().map(|ms| ms * 1000),
+        fmt => NaiveDateTime::parse_from_str(text, fmt)
+            .ok()
+            .map(|dt| dt.and_utc().timestamp_micros()),
+    }
+}
+
+fn load_events(path: &str) -> RunResult<Vec<String>> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        RunError::from(RunReason::Uvs(UvsReason::core_conf(format!(
+            "read replay file {} failed: {}",
+            path, e
+        ))))
+    })?;
+    Ok(text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// 回放直连执行：单条流水线按原始间隔发送（`parallel` 不适用于回放）
+pub async fn run_replay_direct(
+    gar: &ReplayGRA,
+    out_conf: &SinkInstanceConf,
+    rate_limit_rps: usize,
+) -> RunResult<ReplayStats> {
+    let outputs = [WpGenOutput::all(out_conf.clone())];
+    run_replay_direct_outputs(gar, &outputs, rate_limit_rps).await
+}
+
+/// 多路输出版本：按 `OutputRoute` 分发每条回放事件
+pub async fn run_replay_direct_outputs(
+    gar: &ReplayGRA,
+    outputs: &[WpGenOutput],
+    rate_limit_rps: usize,
+) -> RunResult<ReplayStats> {
+    // 节奏由时间戳决定，不再叠加生成速率限制
+    crate::sinks::set_global_rate_limit_rps(0);
+    let events = load_events(&gar.replay.file)?;
+    info_ctrl!(
+        "run_replay_direct: file='{}', events={}, speed_factor={}, total_line={:?}, duration_secs={:?}",
+        gar.replay.file,
+        events.len(),
+        gar.replay.speed_factor,
+        gar.gen_conf.total_line,
+        gar.duration_secs
+    );
+    if gar.gen_conf.parallel > 1 {
+        warn_ctrl!(
+            "run_replay_direct: parallel={} ignored, replay keeps a single ordered stream",
+            gar.gen_conf.parallel
+        );
+    }
+    let mut sink = RoutedSink::build(outputs, 0, 1, rate_limit_rps).await?;
+    let limits = ReplayLimits {
+        count: gar.gen_conf.total_line,
+        duration: gar.duration_secs.map(Duration::from_secs),
+    };
+    let mut clock = TokioClock {
+        start: tokio::time::Instant::now(),
+    };
+    let stats = replay_lines(&events, &gar.replay, limits, &mut clock, &mut sink).await?;
+    let ms = clock.elapsed().as_millis();
+    info_ctrl!(
+        "run_replay_direct: summary sent={}, unrouted={}, out_of_order={}, unparsed={}, elapsed={} ms",
+        stats.sent,
+        stats.unrouted,
+        stats.out_of_order,
+        stats.unparsed,
+        ms
+    );
+    println!(
+        "wpgen summary: generated={} lines, written={} lines, elapsed={} ms, mode=replay, out_of_order={}, unparsed={}",
+        stats.sent + stats.unrouted,
+        stats.sent,
+        ms,
+        stats.out_of_order,
+        stats.unparsed
+    );
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MockClock {
+        now: Duration,
+        sleeps: Vec<Duration>,
+    }
+
+    impl ReplayClock for MockClock {
+        fn elapsed(&self) -> Duration {
+            self.now
+        }
+
+        async fn sleep(&mut self, dur: Duration) {
+            self.sleeps.push(dur);
+            self.now += dur;
+        }
+    }
+
+    impl LineSink for Vec<String> {
+        async fn send_line(&mut self, line: &str) -> RunResult<bool> {
+            self.push(line.to_string());
+            Ok(true)
+        }
+    }
+
+    /// 只写出包含 `keep` 的行，模拟 filter 输出
+    struct KeepSink {
+        keep: &'static str,
+        out: Vec<String>,
+    }
+
+    impl LineSink for KeepSink {
+        async fn send_line(&mut self, line: &str) -> RunResult<bool> {
+            let hit = line.contains(self.keep);
+            if hit {
+                self.out.push(line.to_string());
+            }
+            Ok(hit)
+        }
+    }
+
+    fn conf(field: Option<&str>, format: &str, speed_factor: f64) -> ReplayConfig {
+        ReplayConfig {
+            file: String::new(),
+            timestamp_field: field.map(str::to_string),
+            timestamp_format: format.to_string(),
+            speed_factor,
+        }
+    }
+
+    fn three_event_file() -> (tempfile::NamedTempFile, Vec<String>) {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            concat!(
+                "{\"ts\":\"2024-05-01T10:00:00Z\",\"msg\":\"a\"}\n",
+                "{\"ts\":\"2024-05-01T10:00:01Z\",\"msg\":\"b\"}\n",
+                "\n",
+                "{\"ts\":\"2024-05-01T10:00:03.500Z\",\"msg\":\"c\"}\n",
+            ),
+        )
+        .unwrap();
+        let events = load_events(file.path().to_str().unwrap()).unwrap();
+        (file, events)
+    }
+
+    #[tokio::test]
+    async fn replay_sleeps_by_original_deltas_and_speed_factor() {
+        let (_file, events) = three_event_file();
+        assert_eq!(events.len(), 3);
+
+        let mut clock = MockClock::default();
+        let mut out = Vec::new();
+        let stats = replay_lines(
+            &events,
+            &conf(Some("ts"), "rfc3339", 1.0),
+            ReplayLimits::default(),
+            &mut clock,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.sent, 3);
+        assert_eq!(out, events);
+        assert_eq!(
+            clock.sleeps,
+            vec![Duration::from_secs(1), Duration::from_millis(2500)]
+        );
+
+        let mut clock = MockClock::default();
+        replay_lines(
+            &events,
+            &conf(Some("ts"), "rfc3339", 2.0),
+            ReplayLimits::default(),
+            &mut clock,
+            &mut Vec::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            clock.sleeps,
+            vec![Duration::from_millis(500), Duration::from_millis(1250)]
+        );
+    }
+
+    #[tokio::test]
+    async fn out_of_order_events_are_clamped_and_counted() {
+        let events: Vec<String> = [
+            "1714557600000 a",
+            "1714557602000 b",
+            "1714557601000 c",
+            "1714557603000 d",
+            "garbage e",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let mut clock = MockClock::default();
+        let stats = replay_lines(
+            &events,
+            &conf(None, "epoch_ms", 1.0),
+            ReplayLimits::default(),
+            &mut clock,
+            &mut Vec::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            stats,
+            ReplayStats {
+                sent: 5,
+                unrouted: 0,
+                out_of_order: 1,
+                unparsed: 1
+            }
+        );
+        assert_eq!(
+            clock.sleeps,
+            vec![Duration::from_secs(2), Duration::from_secs(1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn count_and_duration_limits_still_apply() {
+        let (_file, events) = three_event_file();
+        let replay = conf(Some("ts"), "rfc3339", 1.0);
+
+        let mut out = Vec::new();
+        let limits = ReplayLimits {
+            count: Some(2),
+            duration: None,
+        };
+        replay_lines(
+            &events,
+            &replay,
+            limits,
+            &mut MockClock::default(),
+            &mut out,
+        )
+        .await
+        .unwrap();
+        assert_eq!(out.len(), 2);
+
+        let mut out = Vec::new();
+        let mut clock = MockClock::default();
+        let limits = ReplayLimits {
+            count: None,
+            duration: Some(Duration::from_secs(2)),
+        };
+        replay_lines(&events, &replay, limits, &mut clock, &mut out)
+            .await
+            .unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!(clock.sleeps, vec![Duration::from_secs(1)]);
+    }
+
+    #[test]
+    fn timestamps_from_kv_fields_and_line_prefix() {
+        let kv = conf(Some("time"), "%Y-%m-%d %H:%M:%S", 1.0);
+        let a = event_timestamp("src=1.1.1.1 time=\"2024-05-01 10:00:00\" act=deny", &kv);
+        let b = event_timestamp("xtime=1 time=\"2024-05-01 10:00:02\"", &kv);
+        assert_eq!(b.unwrap() - a.unwrap(), 2_000_000);
+
+        let prefix = conf(None, "%Y-%m-%d %H:%M:%S%.f", 1.0);
+        let a = event_timestamp("2024-05-01 10:00:00.250 host sshd: ok", &prefix);
+        let b = event_timestamp("2024-05-01 10:00:01.000 host sshd: ok", &prefix);
+        assert_eq!(b.unwrap() - a.unwrap(), 750_000);
+
+        let epoch = conf(Some("ts"), "epoch", 1.0);
+        assert_eq!(
+            event_timestamp("{\"ts\":1714557600.5}", &epoch),
+            Some(1_714_557_600_500_000)
+        );
+        assert_eq!(event_timestamp("{\"other\":1}", &epoch), None);
+    }
+
+    #[tokio::test]
+    async fn unrouted_events_are_not_counted_as_sent() {
+        let events: Vec<String> = ["1714557600000 a", "1714557601000 b", "1714557602000 a"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut sink = KeepSink {
+            keep: " a",
+            out: Vec::new(),
+        };
+        let limits = ReplayLimits {
+            count: Some(2),
+            duration: None,
+        };
+        let stats = replay_lines(
+            &events,
+            &conf(None, "epoch_ms", 1.0),
+            limits,
+            &mut MockClock::default(),
+            &mut sink,
+        )
+        .await
+        .unwrap();
+        // count 按处理的事件数限制，未写出的事件不计入 sent
+        assert_eq!(stats.sent, 1);
+        assert_eq!(stats.unrouted, 1);
+        assert_eq!(sink.out, vec!["1714557600000 a"]);
+    }
+}
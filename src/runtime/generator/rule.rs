@@ -1,7 +1,8 @@
 use super::common::{
-    DEFAULT_UNIT_SIZE, build_sink_instance, default_batch, per_pipeline_speed,
-    split_total_among_parallel,
+    DEFAULT_UNIT_SIZE, default_batch, per_pipeline_speed, split_total_among_parallel,
 };
+use super::fields::FieldTemplater;
+use super::outputs::{GenCount, RoutedSink};
 use super::speed::{DynamicRateLimiter, SpeedProfile};
 use crate::orchestrator::config::models::stat_reqs_from;
 use crate::runtime::actor::TaskGroup;
@@ -9,10 +10,10 @@ use crate::runtime::actor::signal::ShutdownCmd;
 use crate::runtime::generator::rule_source::RuleGenSource;
 use crate::runtime::generator::types::GenGRA;
 use crate::runtime::supervisor::monitor::ActorMonitor;
-use crate::sinks::SinkBackendType;
 use orion_error::{ErrorOwe, ErrorWith, UvsReason};
 use orion_variate::EnvDict;
 use tokio::task::JoinHandle;
+use wp_conf::generator::wpgen::WpGenOutput;
 use wp_conf::stat::StatConf;
 use wp_conf::structure::SinkInstanceConf;
 use wp_error::run_error::{RunError, RunErrorOwe, RunResult};
@@ -22,15 +23,16 @@ use wp_stat::StatStage; // for record_task
 
 /// 批量发送一个“单元”的规则生成结果（逐条生成+发送，但作为一个批次）。
 async fn send_unit_rules(
-    sink: &mut SinkBackendType,
+    sink: &mut RoutedSink,
     src: &std::sync::Arc<RuleGenSource>,
     cur_idx: &mut usize,
     unit_cnt: usize,
+    fields: &mut Option<FieldTemplater>,
     collectors: &mut crate::stat::metric_collect::MetricCollectors,
-) -> RunResult<usize> {
+) -> RunResult<GenCount> {
     use wp_stat::StatRecorder; // bring trait for record_task
     let rules_len = src.rule_len().max(1);
-    let mut sent = 0usize;
+    let mut count = GenCount::default();
     for _ in 0..unit_cnt {
         let ffv = src.gen_one(*cur_idx).map_err(|e| {
             RunError::from(wp_error::run_error::RunReason::Uvs(UvsReason::core_conf(
@@ -39,14 +41,17 @@ async fn send_unit_rules(
         })?;
         *cur_idx = (*cur_idx + 1) % rules_len;
         // 将 FmtFieldVec 转换为字符串并调用 sink_str
-        let raw_line = wpl::generator::RAWGenFmt(&ffv).to_string();
-        wp_connector_api::AsyncRawDataSink::sink_str(sink, &raw_line)
-            .await
-            .owe_sink()?;
-        collectors.record_task("gen_direct_rule", ());
-        sent += 1;
+        let mut raw_line = wpl::generator::RAWGenFmt(&ffv).to_string();
+        if let Some(fields) = fields.as_mut() {
+            raw_line = fields.render(raw_line);
+        }
+        if sink.sink_str(&raw_line).await? > 0 {
+            collectors.record_task("gen_direct_rule", ());
+            count.written += 1;
+        }
+        count.generated += 1;
     }
-    Ok(sent)
+    Ok(count)
 }
 
 pub async fn run_rule_direct(
@@ -55,6 +60,18 @@ pub async fn run_rule_direct(
     out_conf: &SinkInstanceConf,
     rate_limit_rps: usize,
     dict: &EnvDict,
+) -> RunResult<()> {
+    let outputs = [WpGenOutput::all(out_conf.clone())];
+    run_rule_direct_outputs(rule_root, gar, &outputs, rate_limit_rps, dict).await
+}
+
+/// 多路输出版本：每条流水线为每个输出各建一个 sink 副本，按 `OutputRoute` 分发
+pub async fn run_rule_direct_outputs(
+    rule_root: &str,
+    gar: &GenGRA,
+    outputs: &[WpGenOutput],
+    rate_limit_rps: usize,
+    dict: &EnvDict,
 ) -> RunResult<()> {
     // 全局限速目标（构建期提示）：生成器直连路径在构建 sink 前设置；0 表示无限速。
     crate::sinks::set_global_rate_limit_rps(gar.gen_speed);
@@ -106,13 +123,14 @@ pub async fn run_rule_direct(
 
     // 启动流水线
     let start_at = std::time::Instant::now();
-    let mut tasks: Vec<JoinHandle<RunResult<usize>>> = Vec::with_capacity(parallel);
+    let mut tasks: Vec<JoinHandle<RunResult<GenCount>>> = Vec::with_capacity(parallel);
     for (i, cnt) in per_counts.iter().copied().enumerate().take(parallel) {
-        let sink = build_sink_instance(out_conf, i, parallel, rate_limit_rps).await?;
+        let sink = RoutedSink::build(outputs, i, parallel, rate_limit_rps).await?;
         let src = source.clone();
         let mon = mon_s.clone();
         let reqs = sink_reqs.clone();
         let profile = speed_profile.clone();
+        let fields = gar.field_pools.as_ref().map(|p| p.templater(i));
         info_ctrl!("run_rule_direct: spawn pipeline {} with count={:?}", i, cnt);
         let unit_size_cfg = DEFAULT_UNIT_SIZE;
         let pipe_idx = i;
@@ -123,6 +141,7 @@ pub async fn run_rule_direct(
                 src,
                 cnt,
                 profile,
+                fields,
                 pipe_idx,
                 pipe_cnt,
                 unit_size_cfg,
@@ -133,7 +152,7 @@ pub async fn run_rule_direct(
         }));
     }
 
-    let mut total_produced: usize = 0;
+    let mut total_produced = GenCount::default();
     for t in tasks {
         let n = t.await.map_err(|e| {
             RunError::from(wp_error::run_error::RunReason::Uvs(UvsReason::core_conf(
@@ -146,31 +165,33 @@ pub async fn run_rule_direct(
     let elapsed = start_at.elapsed();
     let ms = elapsed.as_millis();
     info_ctrl!(
-        "run_rule_direct: summary generated={} lines, elapsed={} ms, parallel={}, batch={}",
-        total_produced,
+        "run_rule_direct: summary generated={} lines, written={} lines, elapsed={} ms, parallel={}, batch={}",
+        total_produced.generated,
+        total_produced.written,
         ms,
         parallel,
         batch
     );
     println!(
-        "wpgen summary: generated={} lines, elapsed={} ms, mode=direct, parallel={}, batch={}",
-        total_produced, ms, parallel, batch
+        "wpgen summary: generated={} lines, written={} lines, elapsed={} ms, mode=direct, parallel={}, batch={}",
+        total_produced.generated, total_produced.written, ms, parallel, batch
     );
     Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
 async fn run_rule_pipeline(
-    mut sink: SinkBackendType,
+    mut sink: RoutedSink,
     src: std::sync::Arc<RuleGenSource>,
     count: Option<usize>,
     speed_profile: SpeedProfile,
+    mut fields: Option<FieldTemplater>,
     pipe_idx: usize,
     pipe_cnt: usize,
     unit_size_cfg: usize,
     mon_s: crate::stat::MonSend,
     sink_reqs: Vec<wp_stat::StatReq>,
-) -> RunResult<usize> {
+) -> RunResult<GenCount> {
     use crate::stat::metric_collect::MetricCollectors;
 
     // 统计/速率器
@@ -189,7 +210,7 @@ async fn run_rule_pipeline(
         DynamicRateLimiter::new(adjusted_profile, &format!("gen_rule_pipe_{}", pipe_idx));
 
     // 迭代状态
-    let mut produced = 0usize;
+    let mut produced = GenCount::default();
     let mut cur_idx = 0usize; // 当前规则索引起点
     let total_limit = count; // 可选总量限制
 
@@ -198,20 +219,28 @@ async fn run_rule_pipeline(
     let mut acc_lines: usize = 0;
     loop {
         if let Some(limit) = total_limit
-            && produced >= limit
+            && produced.generated >= limit
         {
             break;
         }
         let left_global = total_limit
-            .map(|l| l.saturating_sub(produced))
+            .map(|l| l.saturating_sub(produced.generated))
             .unwrap_or(usize::MAX);
         if left_global == 0 {
             break;
         }
         let take = unit_size.min(left_global);
-        let sent = send_unit_rules(&mut sink, &src, &mut cur_idx, take, &mut collectors).await?;
+        let sent = send_unit_rules(
+            &mut sink,
+            &src,
+            &mut cur_idx,
+            take,
+            &mut fields,
+            &mut collectors,
+        )
+        .await?;
         produced += sent;
-        acc_lines += sent;
+        acc_lines += sent.written;
         let mut reported = 0usize;
         while acc_lines >= REPORT_LINES_PER_TASK {
             collectors.record_task("gen_direct_rule", ());
@@ -222,7 +251,7 @@ async fn run_rule_pipeline(
             let _ = collectors.send_stat(&mon_s).await;
         }
         // 使用动态速率限制器
-        let wait = limiter.consume(sent);
+        let wait = limiter.consume(sent.generated);
         if !wait.is_zero() {
             tokio::time::sleep(wait).await;
         }
@@ -281,6 +310,18 @@ fn adjust_profile_for_pipeline(profile: &SpeedProfile, pipe_cnt: usize) -> Speed
             base: base / pipe_cnt,
             variance: *variance,
         },
+        SpeedProfile::Histogram {
+            buckets,
+            interpolation,
+            period_secs,
+        } => SpeedProfile::Histogram {
+            buckets: buckets
+                .iter()
+                .map(|rate| (rate / pipe_cnt).max(1))
+                .collect(),
+            interpolation: *interpolation,
+            period_secs: *period_secs,
+        },
         SpeedProfile::Composite {
             profiles,
             combine_mode,
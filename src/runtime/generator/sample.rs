@@ -1,19 +1,20 @@
-use super::common::{DEFAULT_UNIT_SIZE, build_sink_instance};
+use super::common::DEFAULT_UNIT_SIZE;
+use super::outputs::{GenCount, RoutedSink};
 use super::speed::{DynamicRateLimiter, SpeedProfile};
 use crate::orchestrator::config::models::stat_reqs_from;
 use crate::runtime::actor::TaskGroup;
 use crate::runtime::actor::signal::ShutdownCmd;
 use crate::runtime::generator::types::GenGRA;
 use crate::runtime::supervisor::monitor::ActorMonitor;
-use crate::sinks::SinkBackendType;
 use crate::stat::metric_collect::MetricCollectors;
 use orion_error::UvsReason;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio::task::JoinHandle;
+use wp_conf::generator::wpgen::WpGenOutput;
 use wp_conf::stat::StatConf;
 use wp_conf::structure::SinkInstanceConf;
-use wp_error::run_error::{RunError, RunErrorOwe, RunResult};
+use wp_error::run_error::{RunError, RunResult};
 use wp_log::info_ctrl;
 use wp_stat::{StatRecorder, StatStage};
 
@@ -49,25 +50,25 @@ fn load_samples(rule_root: &str, find_name: &str) -> RunResult<Vec<String>> {
 
 /// 批量发送一个"单元"的样本（逐条发送，但把本单元作为一个批次）。
 async fn send_unit_samples(
-    sink: &mut SinkBackendType,
+    sink: &mut RoutedSink,
     samples: &Arc<Vec<String>>,
     cur_idx: &mut usize,
     unit_cnt: usize,
     collectors: &mut MetricCollectors,
-) -> RunResult<usize> {
+) -> RunResult<GenCount> {
     let n = samples.len().max(1);
-    let mut sent = 0usize;
+    let mut count = GenCount::default();
     for _ in 0..unit_cnt {
         let line = &samples[*cur_idx];
-        wp_connector_api::AsyncRawDataSink::sink_str(sink, line.as_str())
-            .await
-            .owe_sink()?;
-        // 按条统计
-        collectors.record_task("gen_direct", ());
+        if sink.sink_str(line.as_str()).await? > 0 {
+            // 按条统计（仅统计实际写出的行）
+            collectors.record_task("gen_direct", ());
+            count.written += 1;
+        }
         *cur_idx = (*cur_idx + 1) % n;
-        sent += 1;
+        count.generated += 1;
     }
-    Ok(sent)
+    Ok(count)
 }
 
 #[derive(Clone)]
@@ -145,16 +146,16 @@ impl WorkQuota {
     }
 }
 
-/// 单条样本直连流水线：按微批次生成并发送，返回本流水线产出的总条数。
+/// 单条样本直连流水线：按微批次生成并发送，返回本流水线的生成/写出条数。
 async fn run_pipeline(
-    mut sink: SinkBackendType,
+    mut sink: RoutedSink,
     samples: Arc<Vec<String>>,
     quota: WorkQuota,
     speed_profile: SpeedProfile,
     pipe_idx: usize,
     mon_s: crate::stat::MonSend,
     sink_reqs: Vec<wp_stat::StatReq>,
-) -> RunResult<usize> {
+) -> RunResult<GenCount> {
     // 统计/速率器
     let unit_size_cfg = DEFAULT_UNIT_SIZE;
     let mut collectors = MetricCollectors::new("gen_direct".to_string(), sink_reqs);
@@ -169,7 +170,7 @@ async fn run_pipeline(
 
     // 迭代状态
     let mut cur_idx = 0usize;
-    let mut produced = 0usize; // 全局累计
+    let mut produced = GenCount::default(); // 全局累计
     // 不做微批缓冲：逐条发送
 
     // 批量发送一个"单元"，然后统一进行限速；统计：按条进行。
@@ -183,8 +184,8 @@ async fn run_pipeline(
                 .await
             {
                 Ok(sent) => {
-                    if sent < reserved {
-                        quota.release(reserved - sent);
+                    if sent.generated < reserved {
+                        quota.release(reserved - sent.generated);
                     }
                     sent
                 }
@@ -197,7 +198,7 @@ async fn run_pipeline(
         // 单元完成后发一次快照
         let _ = collectors.send_stat(&mon_s).await;
         // 使用动态速率限制器
-        let wait = limiter.consume(sent);
+        let wait = limiter.consume(sent.generated);
         if !wait.is_zero() {
             tokio::time::sleep(wait).await;
         }
@@ -256,6 +257,18 @@ fn adjust_profile_for_pipeline(profile: &SpeedProfile, pipe_cnt: usize) -> Speed
             base: base / pipe_cnt,
             variance: *variance,
         },
+        SpeedProfile::Histogram {
+            buckets,
+            interpolation,
+            period_secs,
+        } => SpeedProfile::Histogram {
+            buckets: buckets
+                .iter()
+                .map(|rate| (rate / pipe_cnt).max(1))
+                .collect(),
+            interpolation: *interpolation,
+            period_secs: *period_secs,
+        },
         SpeedProfile::Composite {
             profiles,
             combine_mode,
@@ -275,6 +288,18 @@ pub async fn run_sample_direct(
     gar: &GenGRA,
     out_conf: &SinkInstanceConf,
     rate_limit_rps: usize,
+) -> RunResult<()> {
+    let outputs = [WpGenOutput::all(out_conf.clone())];
+    run_sample_direct_outputs(rule_root, find_name, gar, &outputs, rate_limit_rps).await
+}
+
+/// 多路输出版本：每条流水线为每个输出各建一个 sink 副本，按 `OutputRoute` 分发
+pub async fn run_sample_direct_outputs(
+    rule_root: &str,
+    find_name: &str,
+    gar: &GenGRA,
+    outputs: &[WpGenOutput],
+    rate_limit_rps: usize,
 ) -> RunResult<()> {
     // 全局限速目标（构建期提示）
     crate::sinks::set_global_rate_limit_rps(gar.base_speed());
@@ -313,9 +338,9 @@ pub async fn run_sample_direct(
     });
 
     let start_at = std::time::Instant::now();
-    let mut tasks: Vec<JoinHandle<RunResult<usize>>> = Vec::with_capacity(parallel);
+    let mut tasks: Vec<JoinHandle<RunResult<GenCount>>> = Vec::with_capacity(parallel);
     for i in 0..parallel {
-        let sink = build_sink_instance(out_conf, i, parallel, rate_limit_rps).await?;
+        let sink = RoutedSink::build(outputs, i, parallel, rate_limit_rps).await?;
         let s = samples.clone();
         info_ctrl!(
             "run_sample_direct: spawn pipeline {} (shared_total={:?})",
@@ -331,7 +356,7 @@ pub async fn run_sample_direct(
             run_pipeline(sink, s, quota, profile, pipe_idx, mon, reqs).await
         }));
     }
-    let mut total_produced: usize = 0;
+    let mut total_produced = GenCount::default();
     for t in tasks {
         let produced = t.await.map_err(|e| {
             RunError::from(wp_error::run_error::RunReason::Uvs(UvsReason::core_conf(
@@ -344,14 +369,15 @@ pub async fn run_sample_direct(
     let elapsed = start_at.elapsed();
     let ms = elapsed.as_millis();
     info_ctrl!(
-        "run_sample_direct: summary generated={} lines, elapsed={} ms, parallel={} ",
-        total_produced,
+        "run_sample_direct: summary generated={} lines, written={} lines, elapsed={} ms, parallel={} ",
+        total_produced.generated,
+        total_produced.written,
         ms,
         parallel,
     );
     println!(
-        "wpgen summary: generated={} lines, elapsed={} ms, mode=direct, parallel={} ",
-        total_produced, ms, parallel,
+        "wpgen summary: generated={} lines, written={} lines, elapsed={} ms, mode=direct, parallel={} ",
+        total_produced.generated, total_produced.written, ms, parallel,
     );
     Ok(())
 }
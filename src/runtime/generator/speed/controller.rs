@@ -2,7 +2,7 @@
 //!
 //! 根据 SpeedProfile 计算当前时刻的目标速率
 
-use super::profile::{CombineMode, SpeedProfile};
+use super::profile::{CombineMode, Interpolation, SpeedProfile};
 use rand::rngs::StdRng;
 use rand::{RngExt, SeedableRng};
 use std::time::Instant;
@@ -120,6 +120,12 @@ impl DynamicSpeedController {
 
             SpeedProfile::RandomWalk { base, variance } => self.calc_random_walk(*base, *variance),
 
+            SpeedProfile::Histogram {
+                buckets,
+                interpolation,
+                period_secs,
+            } => Self::calc_histogram(buckets, *interpolation, *period_secs, elapsed_secs),
+
             SpeedProfile::Composite { .. } => unreachable!("Composite handled above"),
         }
     }
@@ -225,6 +231,38 @@ impl DynamicSpeedController {
         ((base as f64) * (1.0 + noise)).max(1.0) as usize
     }
 
+    /// 直方图计算
+    fn calc_histogram(
+        buckets: &[usize],
+        interpolation: Interpolation,
+        period_secs: f64,
+        elapsed_secs: f64,
+    ) -> usize {
+        if buckets.is_empty() {
+            return 1000; // 默认速率
+        }
+        if period_secs <= 0.0 {
+            return buckets[0].max(1);
+        }
+        let n = buckets.len();
+        let width = period_secs / n as f64;
+        let pos = (elapsed_secs % period_secs) / width;
+        // 0 在限速器中表示不限速，直方图速率至少为 1
+        let rate = match interpolation {
+            Interpolation::Step => buckets[(pos as usize).min(n - 1)],
+            Interpolation::Linear => {
+                // 以桶中点为锚点插值，首尾桶之间按周期环绕
+                let shifted = (pos - 0.5).rem_euclid(n as f64);
+                let idx = (shifted as usize).min(n - 1);
+                let frac = shifted - idx as f64;
+                let from = buckets[idx] as f64;
+                let to = buckets[(idx + 1) % n] as f64;
+                (from + (to - from) * frac).round() as usize
+            }
+        };
+        rate.max(1)
+    }
+
     /// 复合模式计算
     fn calc_composite(&mut self, combine_mode: CombineMode, elapsed_secs: f64) -> usize {
         if self.composite_children.is_empty() {
@@ -510,6 +548,93 @@ mod tests {
         assert_eq!(ctrl.current_speed(), 2000);
     }
 
+    /// 每秒采样一次模拟一天，返回各桶内速率均值
+    fn simulate_day_bucket_means(profile: SpeedProfile, buckets: usize) -> Vec<f64> {
+        let mut ctrl = DynamicSpeedController::new(profile);
+        let per_bucket = 86_400 / buckets;
+        let mut sums = vec![0f64; buckets];
+        for sec in 0..86_400 {
+            sums[sec / per_bucket] += ctrl.calculate_speed(sec as f64 + 0.5) as f64;
+        }
+        sums.iter().map(|s| s / per_bucket as f64).collect()
+    }
+
+    fn day_histogram() -> Vec<usize> {
+        // 夜间低谷、上午爬升、午后高峰、晚间回落
+        (0..24)
+            .map(|h: usize| match h {
+                0..=5 => 500 + h * 20,
+                6..=11 => 1000 + (h - 6) * 800,
+                12..=17 => 6000 - (h - 12) * 300,
+                _ => 4000 - (h - 18) * 550,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_histogram_step_matches_bucket_means() {
+        let buckets = day_histogram();
+        let means = simulate_day_bucket_means(
+            SpeedProfile::Histogram {
+                buckets: buckets.clone(),
+                interpolation: Interpolation::Step,
+                period_secs: 86_400.0,
+            },
+            24,
+        );
+        for (mean, expect) in means.iter().zip(&buckets) {
+            assert_eq!(*mean, *expect as f64);
+        }
+    }
+
+    #[test]
+    fn test_histogram_linear_tracks_bucket_means() {
+        let buckets: Vec<usize> = (0..96).map(|i| 2000 + (i % 24) * 50).collect();
+        let means = simulate_day_bucket_means(
+            SpeedProfile::Histogram {
+                buckets: buckets.clone(),
+                interpolation: Interpolation::Linear,
+                period_secs: 86_400.0,
+            },
+            96,
+        );
+        // 线性插值下桶均值为 (前 + 6*本 + 后) / 8，平滑段与桶值一致
+        for (i, (mean, expect)) in means.iter().zip(&buckets).enumerate() {
+            let prev = buckets[(i + 95) % 96] as f64;
+            let next = buckets[(i + 1) % 96] as f64;
+            let smoothed = (prev + 6.0 * *expect as f64 + next) / 8.0;
+            assert!((mean - smoothed).abs() < 1.0, "bucket {}: {}", i, mean);
+        }
+
+        // 中点处精确取桶值，周期末尾向首桶过渡
+        assert_eq!(
+            DynamicSpeedController::calc_histogram(&[100, 300], Interpolation::Linear, 10.0, 2.5),
+            100
+        );
+        assert_eq!(
+            DynamicSpeedController::calc_histogram(&[100, 300], Interpolation::Linear, 10.0, 5.0),
+            200
+        );
+        assert_eq!(
+            DynamicSpeedController::calc_histogram(&[100, 300], Interpolation::Linear, 10.0, 12.5),
+            100
+        );
+    }
+
+    #[test]
+    fn test_histogram_never_yields_zero() {
+        // 0 会被限速器当作不限速，计算结果至少为 1
+        for elapsed in [1.0, 4.0, 6.0, 9.0] {
+            for mode in [Interpolation::Step, Interpolation::Linear] {
+                assert!(DynamicSpeedController::calc_histogram(&[0, 1], mode, 10.0, elapsed) >= 1);
+            }
+        }
+        assert_eq!(
+            DynamicSpeedController::calc_histogram(&[0], Interpolation::Step, 0.0, 1.0),
+            1
+        );
+    }
+
     #[test]
     fn test_reset() {
         let mut ctrl = DynamicSpeedController::new(SpeedProfile::Ramp {
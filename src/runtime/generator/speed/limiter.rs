@@ -4,7 +4,9 @@
 
 use super::controller::DynamicSpeedController;
 use super::profile::SpeedProfile;
-use std::time::{Duration, Instant};
+use std::time::Duration;
+// 使用 tokio 时钟：运行时暂停（`tokio::time::pause`）时随虚拟时间推进，无运行时时等同系统时钟
+use tokio::time::Instant;
 
 /// 动态速率限制器
 ///
@@ -21,6 +23,8 @@ pub struct DynamicRateLimiter {
     // 令牌桶状态
     tokens: f64,
     max_tokens: f64,
+    // 显式突发容量；None 时按速率推算
+    burst: Option<usize>,
     last_refill: Instant,
     // 速率更新控制
     current_rate: usize,
@@ -47,6 +51,7 @@ impl DynamicRateLimiter {
             name: name.to_string(),
             tokens: max_tokens,
             max_tokens,
+            burst: None,
             last_refill: Instant::now(),
             current_rate: initial_rate,
             last_rate_update: Instant::now(),
@@ -61,11 +66,26 @@ impl DynamicRateLimiter {
         Self::new(SpeedProfile::Constant(rate), name)
     }
 
+    /// 指定突发容量（令牌桶上限），替代默认的 0.2 秒容量；桶初始为满
+    pub fn with_burst(mut self, burst: usize) -> Self {
+        self.burst = Some(burst.max(1));
+        self.max_tokens = self.bucket_size(self.current_rate);
+        self.tokens = self.max_tokens;
+        self
+    }
+
     /// 计算最大令牌数（基于速率的 0.2 秒容量）
     fn calc_max_tokens(rate: usize) -> f64 {
         (rate as f64 * 0.2).max(10.0)
     }
 
+    fn bucket_size(&self, rate: usize) -> f64 {
+        match self.burst {
+            Some(burst) => burst as f64,
+            None => Self::calc_max_tokens(rate),
+        }
+    }
+
     /// 获取当前目标速率
     pub fn current_rate(&self) -> usize {
         self.current_rate
@@ -96,7 +116,7 @@ impl DynamicRateLimiter {
             if rate_changed {
                 // 在更新前按照旧速率补齐令牌
                 self.refill_tokens_at(now);
-                self.max_tokens = Self::calc_max_tokens(new_rate);
+                self.max_tokens = self.bucket_size(new_rate);
                 self.tokens = self.tokens.min(self.max_tokens);
                 self.current_rate = new_rate;
             }
@@ -153,6 +173,51 @@ impl DynamicRateLimiter {
         }
     }
 
+    /// 非阻塞获取令牌：返回本次可放行的数量（0..=count），不产生欠账
+    ///
+    /// 用于“超速丢弃”策略；未获准的部分由调用方丢弃。
+    pub fn try_acquire(&mut self, count: usize) -> usize {
+        if self.current_rate == 0 {
+            self.total_consumed += count;
+            return count;
+        }
+        let now = Instant::now();
+        if !self.maybe_update_rate_at(now) {
+            self.refill_tokens_at(now);
+        }
+        let granted = (self.tokens.max(0.0).floor() as usize).min(count);
+        self.tokens -= granted as f64;
+        self.total_consumed += granted;
+        granted
+    }
+
+    /// 距下一个令牌可用还需等待的时间；已有令牌或速率为 0 时为零
+    pub fn next_token_wait(&mut self) -> Duration {
+        if self.current_rate == 0 {
+            return Duration::ZERO;
+        }
+        let now = Instant::now();
+        if !self.maybe_update_rate_at(now) {
+            self.refill_tokens_at(now);
+        }
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64((1.0 - self.tokens) / self.current_rate as f64)
+    }
+
+    /// 令牌是否已耗尽（不足 1 个）；速率为 0 时恒为 false
+    pub fn is_exhausted(&mut self) -> bool {
+        if self.current_rate == 0 {
+            return false;
+        }
+        let now = Instant::now();
+        if !self.maybe_update_rate_at(now) {
+            self.refill_tokens_at(now);
+        }
+        self.tokens < 1.0
+    }
+
     /// 记录开始（兼容旧 API）
     pub fn rec_beg(&mut self) {
         // 仅更新速率，不消耗令牌
@@ -184,7 +249,7 @@ impl DynamicRateLimiter {
     pub fn reset(&mut self) {
         self.controller.reset();
         self.current_rate = self.controller.current_speed();
-        self.max_tokens = Self::calc_max_tokens(self.current_rate);
+        self.max_tokens = self.bucket_size(self.current_rate);
         self.tokens = self.max_tokens;
         self.last_refill = Instant::now();
         self.last_rate_update = Instant::now();
@@ -440,6 +505,29 @@ mod tests {
         assert!(elapsed.as_millis() < 50);
     }
 
+    #[test]
+    fn test_burst_overrides_bucket_size() {
+        let mut limiter = DynamicRateLimiter::from_constant(1000, "test").with_burst(50);
+        // 桶初始为满：50 个令牌内不等待，超出后按 1000/s 计算欠账
+        assert_eq!(limiter.consume(50), Duration::ZERO);
+        let wait = limiter.consume(100);
+        assert!((90..=110).contains(&wait.as_millis()), "wait={:?}", wait);
+    }
+
+    #[test]
+    fn test_try_acquire_grants_up_to_tokens() {
+        let mut limiter = DynamicRateLimiter::from_constant(10, "test").with_burst(5);
+        assert_eq!(limiter.try_acquire(3), 3);
+        assert_eq!(limiter.try_acquire(10), 2);
+        assert_eq!(limiter.try_acquire(1), 0);
+        assert!(limiter.is_exhausted());
+        assert_eq!(limiter.total_consumed(), 5);
+
+        let mut unlimited = DynamicRateLimiter::from_constant(0, "test");
+        assert_eq!(unlimited.try_acquire(1000), 1000);
+        assert!(!unlimited.is_exhausted());
+    }
+
     #[test]
     fn test_sinusoidal_limiter() {
         let mut limiter = DynamicRateLimiter::new(
@@ -9,6 +9,7 @@
 //! - `Burst` - 突发模式（流量尖峰）
 //! - `Ramp` - 渐进模式（压测梯度）
 //! - `RandomWalk` - 随机波动（自然抖动）
+//! - `Histogram` - 实测流量直方图（日流量曲线）
 //! - `Composite` - 复合模式
 
 mod controller;
@@ -17,4 +18,4 @@ pub mod profile;
 
 pub use controller::DynamicSpeedController;
 pub use limiter::DynamicRateLimiter;
-pub use profile::{CombineMode, SpeedProfile};
+pub use profile::{CombineMode, Interpolation, SpeedProfile};
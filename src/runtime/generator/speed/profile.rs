@@ -133,6 +133,30 @@ pub enum SpeedProfile {
         variance: f64,
     },
 
+    /// 实测流量直方图
+    ///
+    /// 将 `period_secs` 均分为 `buckets.len()` 个时段，按实测速率回放一天（或任意周期）的流量曲线，结束后循环
+    ///
+    /// 适用场景：按 24/96 桶的实测 EPS 重现真实业务日流量
+    ///
+    /// # 示例
+    /// ```ignore
+    /// // 每小时一个桶，桶间线性过渡
+    /// SpeedProfile::Histogram {
+    ///     buckets: vec![800, 600, 500, /* ... */ 1200],
+    ///     interpolation: Interpolation::Linear,
+    ///     period_secs: 86400.0,
+    /// }
+    /// ```
+    Histogram {
+        /// 各时段速率 (行/秒)
+        buckets: Vec<usize>,
+        /// 桶间插值方式
+        interpolation: Interpolation,
+        /// 整条曲线的时长 (秒)
+        period_secs: f64,
+    },
+
     /// 复合模式
     ///
     /// 将多个模型的速率取平均值（或可配置为叠加/取最大等）
@@ -172,6 +196,16 @@ pub enum CombineMode {
     Sum,
 }
 
+/// 直方图桶间插值方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// 桶内保持该桶速率
+    #[default]
+    Step,
+    /// 相邻桶中点之间线性过渡（跨周期首尾相接）
+    Linear,
+}
+
 impl Default for SpeedProfile {
     fn default() -> Self {
         SpeedProfile::Constant(1000)
@@ -250,6 +284,9 @@ impl SpeedProfile {
             SpeedProfile::Burst { base, .. } => *base,
             SpeedProfile::Ramp { start, .. } => *start,
             SpeedProfile::RandomWalk { base, .. } => *base,
+            SpeedProfile::Histogram { buckets, .. } => {
+                buckets.iter().sum::<usize>() / buckets.len().max(1)
+            }
             SpeedProfile::Composite { profiles, .. } => {
                 let sum: usize = profiles.iter().map(|p| p.base_rate()).sum();
                 sum / profiles.len().max(1)
@@ -309,6 +346,15 @@ impl From<wp_conf::SpeedProfileConfig> for SpeedProfile {
                 base,
                 variance: variance.clamp(0.0, 1.0),
             },
+            Cfg::Histogram {
+                buckets,
+                interpolation,
+                period_secs,
+            } => SpeedProfile::Histogram {
+                buckets,
+                interpolation: interpolation.into(),
+                period_secs,
+            },
             Cfg::Composite {
                 profiles,
                 combine_mode,
@@ -333,6 +379,16 @@ impl From<wp_conf::CombineModeConfig> for CombineMode {
     }
 }
 
+/// 从配置类型转换为运行时类型
+impl From<wp_conf::InterpolationConfig> for Interpolation {
+    fn from(config: wp_conf::InterpolationConfig) -> Self {
+        match config {
+            wp_conf::InterpolationConfig::Step => Interpolation::Step,
+            wp_conf::InterpolationConfig::Linear => Interpolation::Linear,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
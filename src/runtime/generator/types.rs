@@ -1,4 +1,6 @@
+use super::fields::FieldPools;
 use super::speed::SpeedProfile;
+use wp_conf::generator::wpgen::ReplayConfig;
 
 #[derive(Clone, Debug)]
 pub struct GenGRA {
@@ -12,6 +14,8 @@ pub struct GenGRA {
     pub stat_sec: usize,
     pub stat_print: bool,
     pub rescue: String,
+    /// 字段值池（仅规则模式）：替换生成结果中的 `{name}` 占位符
+    pub field_pools: Option<FieldPools>,
 }
 
 impl Default for GenGRA {
@@ -24,6 +28,7 @@ impl Default for GenGRA {
             stat_sec: 1,
             stat_print: false,
             rescue: "./rescue".to_string(),
+            field_pools: None,
         }
     }
 }
@@ -38,6 +43,14 @@ pub struct RuleGRA {
     pub gen_conf: GenGRA,
 }
 
+/// 回放模式参数：`gen_conf.total_line` 仍作为条数上限，速率字段不参与
+#[derive(Clone, Debug)]
+pub struct ReplayGRA {
+    pub gen_conf: GenGRA,
+    pub replay: ReplayConfig,
+    pub duration_secs: Option<u64>,
+}
+
 impl GenGRA {
     /// 获取生成速率；若字段缺省返回默认值（与 Default 对齐）。
     /// 说明：用于在生成器直连路径上决定是否开启 backoff gate（gen_speed==0 视为无限速）。
@@ -68,6 +81,12 @@ impl GenGRA {
         self
     }
 
+    /// 设置字段值池
+    pub fn with_field_pools(mut self, pools: FieldPools) -> Self {
+        self.field_pools = Some(pools);
+        self
+    }
+
     /// 使用恒定速率
     pub fn with_constant_speed(mut self, rate: usize) -> Self {
         self.gen_speed = rate;
@@ -1,9 +1,11 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::core::parser::WplEngine;
 use crate::runtime::prelude::*;
 
 use crate::orchestrator::engine::definition::WplCodePKG;
+use crate::resources::RuleSetSlot;
 use crate::runtime::actor::command::{CmdSubscriber, TaskController};
 use crate::sinks::InfraSinkAgent;
 use crate::sinks::SinkRouteAgent;
@@ -21,6 +23,8 @@ use wpl::WparseResult;
 pub struct ActParser {
     pub engine: WplEngine,
     pub sinks: SinkRouteAgent,
+    /// 热重载：规则集槽与当前解析管线所属代号
+    rules: Option<(Arc<RuleSetSlot>, u64)>,
 }
 
 impl ActParser {
@@ -38,9 +42,45 @@ impl ActParser {
         Ok(ActParser {
             engine: pipe_lines,
             sinks,
+            rules: None,
         })
     }
 
+    /// 绑定规则集槽，使解析管线随热重载切换
+    pub fn with_rule_slot(mut self, slot: Arc<RuleSetSlot>) -> Self {
+        let generation = slot.generation();
+        self.rules = Some((slot, generation));
+        self
+    }
+
+    /// 规则集已被替换时，先上报旧管线的统计，再以新一代管线重建解析引擎
+    async fn refresh_rules(&mut self, mon_send: &MonSend) -> WparseResult<()> {
+        let Some((slot, generation)) = self.rules.as_mut() else {
+            return Ok(());
+        };
+        if slot.generation() == *generation {
+            return Ok(());
+        }
+        let set = slot.load();
+        *generation = set.generation();
+        self.engine.send_stat(mon_send).await?;
+        let infra = self.engine.infra_agent.clone();
+        match WplEngine::from(set.parse_units().clone(), infra) {
+            Ok(engine) => {
+                info_ctrl!(
+                    "parse engine switched to rule set generation {} ({} pipelines)",
+                    set.generation(),
+                    engine.pipelines.pipelines().len()
+                );
+                self.engine = engine;
+            }
+            Err(e) => {
+                error_ctrl!("parse engine reload failed, keep previous rules: {}", e);
+            }
+        }
+        Ok(())
+    }
+
     pub fn from_normal(
         wpl_code: WplCodePKG,
         sinks: SinkRouteAgent,
@@ -53,6 +93,7 @@ impl ActParser {
         Ok(ActParser {
             engine: pipe_lines,
             sinks,
+            rules: None,
         })
     }
 }
@@ -90,6 +131,8 @@ impl ActParser {
                    if crate::engine_flags::skip_parse() {
                        continue;
                    }
+                   // 在批次边界切换热重载后的规则，批次内始终使用同一代解析管线
+                   self.refresh_rules(mon_send).await?;
                    // 正常执行解析+下发
                    self.engine.proc_batch(batch, &setting).await?;
                     need_send_stat=true;
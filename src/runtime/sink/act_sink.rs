@@ -5,13 +5,13 @@ use crate::runtime::prelude::*;
 use tokio::time::MissedTickBehavior;
 use tokio::time::interval;
 use wp_connector_api::AsyncCtrl;
-use wp_data_model::cache::FieldQueryCache;
 
 use crate::orchestrator::config::build_sinks::{SinkRouteTable, build_sink_target};
 use crate::runtime::actor::command::{ActorCtrlCmd, TaskScope};
 use crate::runtime::actor::command::{CmdSubscriber, TaskController};
 use crate::runtime::actor::constants::ACTOR_IDLE_TICK_MS;
-use crate::runtime::sink::drain::{DrainEvent, DrainState};
+use crate::runtime::actor::signal::ShutdownCmd;
+use crate::runtime::sink::drain::{DrainEvent, DrainSignal, DrainState};
 use crate::sinks::SinkDispatcher;
 use crate::sinks::SinkRouteAgent;
 use crate::sinks::SinkRuntime;
@@ -24,7 +24,7 @@ use crate::stat::{MonSend, STAT_INTERVAL_MS};
 use orion_error::ContextRecord;
 use orion_error::OperationContext;
 use orion_overload::append::Appendable;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use wp_conf::TCondParser;
 use wp_conf::structure::SinkInstanceConf;
@@ -41,6 +41,31 @@ pub struct SinkService {
 
 pub struct SinkWork {}
 
+/// drain 截止时间：收到停止指令后按 `[shutdown].drain_timeout_ms` 计算
+fn drain_deadline() -> Instant {
+    Instant::now() + crate::engine_flags::drain_timeout()
+}
+
+/// 旁路监听停止指令并设置 drain 时限：sink 阻塞在下发中时主循环收不到指令，
+/// 由此让正在进行的下发按时限转入救援文件
+fn spawn_drain_watch(
+    mut cmd_r: CmdSubscriber,
+    signals: Vec<DrainSignal>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match cmd_r.recv().await {
+                Ok(ActorCtrlCmd::Stop(ShutdownCmd::Immediate)) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        let deadline = drain_deadline();
+        for signal in &signals {
+            signal.begin(deadline);
+        }
+    })
+}
+
 // 显式的基础组打包，避免依赖顺序传参
 pub struct InfraGroups {
     pub default: SinkDispatcher,
@@ -83,8 +108,8 @@ impl InfraChannel {
         self.closed = true;
     }
 
-    fn close_channel(&mut self) {
-        self.dispatcher.close_channel();
+    fn close_channel(&mut self, deadline: Instant) {
+        self.dispatcher.begin_drain(deadline);
     }
 
     async fn handle_pkg(
@@ -159,10 +184,11 @@ impl SinkWork {
         let mut ctx = OperationContext::want("sink start proc");
         let name = format!("work-sink:{:20}", sink.conf().name());
         let mut run_ctrl = TaskController::new(name.as_str(), cmd_r.clone(), None);
-        let mut cache = FieldQueryCache::with_capacity(1000);
+        let mut cache = oml::core::lookup_cache::new_cache();
         let sink_name = sink.get_name().to_string();
         ctx.record("name", name);
         let mut drain_state = DrainState::new(1);
+        let drain_watch = spawn_drain_watch(cmd_r.clone(), sink.drain_signals());
 
         let mut stat_tick = interval(Duration::from_millis(STAT_INTERVAL_MS as u64));
         stat_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -209,7 +235,7 @@ impl SinkWork {
                                     info_ctrl!("{} enter draining state", sink_name);
                                 }
                                 drain_state.start_draining();
-                                sink.close_channel();
+                                sink.begin_drain(drain_deadline());
                             }
                         }
                         Err(err) => {
@@ -218,7 +244,7 @@ impl SinkWork {
                                 info_ctrl!("{} enter draining state", sink_name);
                             }
                             drain_state.start_draining();
-                            sink.close_channel();
+                            sink.begin_drain(drain_deadline());
                         }
                     }
                 }
@@ -235,6 +261,8 @@ impl SinkWork {
                     }
                 }
                 _ = flush_tick.tick() => {
+                    sink.emit_group_summaries(false, &bad_sink_s, Some(&mon_send))
+                        .await?;
                     let sinks = sink.get_sinks_mut();
                     for s in sinks.iter_mut() {
                         s.flush(Some(&bad_sink_s), Some(&mon_send)).await?;
@@ -243,15 +271,20 @@ impl SinkWork {
                 }
             }
         }
+        // 退出前输出未满周期的 group_by 窗口
+        sink.emit_group_summaries(true, &bad_sink_s, Some(&mon_send))
+            .await?;
         let sinks = sink.get_sinks_mut();
         for s in sinks.iter_mut() {
             s.flush(Some(&bad_sink_s), Some(&mon_send)).await?;
         }
+        // 先停止 sink 使后端缓冲写出，最终统计在全部下发结束后发送
+        sink.proc_end().await?;
         let sinks = sink.get_sinks_mut();
         for s in sinks.iter_mut() {
             s.send_stat(&mon_send).await?;
         }
-        sink.proc_end().await?;
+        drain_watch.abort();
         info_ctrl!("{} async sinks proc end", sink_name);
         Ok(())
     }
@@ -290,6 +323,19 @@ impl SinkWork {
 
         let mut run_ctrl = TaskController::new("infra sinks ", cmd_r.clone(), None);
         let mut drain_state = DrainState::new(5);
+        let drain_watch = spawn_drain_watch(
+            cmd_r.clone(),
+            [
+                &default_sink,
+                &miss_cnn,
+                &residue_cnn,
+                &monitor_cnn,
+                &error_cnn,
+            ]
+            .iter()
+            .flat_map(|ch| ch.dispatcher.drain_signals())
+            .collect(),
+        );
 
         let mut stat_tick = interval(Duration::from_millis(STAT_INTERVAL_MS as u64));
         stat_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -342,7 +388,8 @@ impl SinkWork {
                                     info_ctrl!("infra sinks enter draining state");
                                 }
                                 drain_state.start_draining();
-                                for ch in [&mut default_sink, &mut miss_cnn, &mut residue_cnn, &mut monitor_cnn, &mut error_cnn] { ch.close_channel(); }
+                                let deadline = drain_deadline();
+                                for ch in [&mut default_sink, &mut miss_cnn, &mut residue_cnn, &mut monitor_cnn, &mut error_cnn] { ch.close_channel(deadline); }
                             }
                         }
                         Err(err) => {
@@ -351,7 +398,8 @@ impl SinkWork {
                                 info_ctrl!("infra sinks enter draining state");
                             }
                             drain_state.start_draining();
-                            for ch in [&mut default_sink, &mut miss_cnn, &mut residue_cnn, &mut monitor_cnn, &mut error_cnn] { ch.close_channel(); }
+                            let deadline = drain_deadline();
+                            for ch in [&mut default_sink, &mut miss_cnn, &mut residue_cnn, &mut monitor_cnn, &mut error_cnn] { ch.close_channel(deadline); }
                         }
                     }
                 }
@@ -397,7 +445,6 @@ impl SinkWork {
                 s.flush(Some(&bad_sink_s), Some(&mon_send)).await?;
             }
         }
-        // Send final stats before exit
         for ch in [
             &mut default_sink,
             &mut miss_cnn,
@@ -405,10 +452,10 @@ impl SinkWork {
             &mut monitor_cnn,
             &mut error_cnn,
         ] {
-            for s in ch.dispatcher.get_sinks_mut() {
-                s.send_stat(&mon_send).await?;
-            }
+            let sink_name = ch.proc_end().await?;
+            info_ctrl!("infra:{} async sinks proc end", sink_name);
         }
+        // Send final stats after every sink has stopped
         for ch in [
             &mut default_sink,
             &mut miss_cnn,
@@ -416,9 +463,11 @@ impl SinkWork {
             &mut monitor_cnn,
             &mut error_cnn,
         ] {
-            let sink_name = ch.proc_end().await?;
-            info_ctrl!("infra:{} async sinks proc end", sink_name);
+            for s in ch.dispatcher.get_sinks_mut() {
+                s.send_stat(&mon_send).await?;
+            }
         }
+        drain_watch.abort();
         Ok(())
     }
 
@@ -530,15 +579,19 @@ impl SinkService {
         // 运行态名称使用 full_name = group/inner_name（配置装配阶段已注入 group_name）
         let full_name = conf.full_name();
         let batch_size = sink_group.conf().batch_size();
-        sink_group.append(SinkRuntime::with_batch_size(
-            rescue.clone(),
-            full_name,
-            conf.clone(),
-            sink,
-            filter,
-            stat_reqs,
-            batch_size,
-        ));
+        let adaptive = sink_group.conf().adaptive_batch().cloned();
+        sink_group.append(
+            SinkRuntime::with_batch_size(
+                rescue.clone(),
+                full_name,
+                conf.clone(),
+                sink,
+                filter,
+                stat_reqs,
+                batch_size,
+            )
+            .with_adaptive_batch(adaptive.as_ref()),
+        );
         Ok(())
     }
 }
@@ -1,3 +1,7 @@
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::watch;
+
 /// 通用的 sink drain 状态机：在 Stop 指令到达后只消费数据通道，直到全部关闭
 #[derive(Debug, Clone)]
 pub struct DrainState {
@@ -49,6 +53,51 @@ impl DrainState {
     }
 }
 
+/// drain 时限信号：sink 阻塞在下发中时主循环收不到停止指令，由旁路任务设置时限，
+/// 正在进行的下发据此超时返回
+#[derive(Debug, Clone)]
+pub struct DrainSignal {
+    deadline: Arc<watch::Sender<Option<Instant>>>,
+}
+
+impl Default for DrainSignal {
+    fn default() -> Self {
+        Self {
+            deadline: Arc::new(watch::Sender::new(None)),
+        }
+    }
+}
+
+impl DrainSignal {
+    /// 设置 drain 时限（幂等，保留最早设置的时限）
+    pub fn begin(&self, deadline: Instant) {
+        self.deadline.send_if_modified(|cur| {
+            if cur.is_some() {
+                return false;
+            }
+            *cur = Some(deadline);
+            true
+        });
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        *self.deadline.borrow()
+    }
+
+    /// 等待时限到达；尚未进入 drain 时一直等待
+    pub async fn expired(&self) {
+        let mut rx = self.deadline.subscribe();
+        let deadline = match rx.wait_for(Option::is_some).await {
+            Ok(deadline) => *deadline,
+            Err(_) => None,
+        };
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +117,26 @@ mod tests {
         assert!(matches!(evt, DrainEvent::Drained));
     }
 
+    #[tokio::test]
+    async fn drain_signal_wakes_pending_waiter() {
+        let signal = DrainSignal::default();
+        let waiter = {
+            let signal = signal.clone();
+            tokio::spawn(async move { signal.expired().await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        let first = Instant::now() + std::time::Duration::from_millis(20);
+        signal.begin(first);
+        signal.begin(first + std::time::Duration::from_secs(60));
+        assert_eq!(signal.deadline(), Some(first));
+        tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .expect("waiter woken at deadline")
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn receiver_close_unblocks_drain_state() {
         let mut state = DrainState::new(1);
@@ -6,11 +6,13 @@ pub mod monitor;
 pub mod parse;
 pub mod pick;
 pub mod sink;
+pub mod sources;
 
 // Keep convenient re-exports for callers importing from `runtime::tasks::{...}`
 pub use accept::add_acceptor_tasks;
 pub use monitor::start_moni_tasks;
 #[allow(unused_imports)]
 pub use parse::start_parser_tasks_frames;
-pub use pick::start_picker_tasks;
+pub use pick::{PickerCtx, start_picker_tasks};
 pub use sink::{start_data_sinks, start_infra_working};
+pub use sources::{SourceOrigin, SourceState, Sources};
@@ -1,12 +1,14 @@
 use crate::core::parser::{ParseOption, WplPipeline};
 use crate::orchestrator::config::build_sinks::dat_channel_max;
 use crate::orchestrator::engine::resource::EngineResource;
+use crate::resources::RuleSetSlot;
 use crate::runtime::actor::TaskGroup;
 use crate::runtime::actor::signal::ShutdownCmd;
 use crate::runtime::parser::act_parser::ActParser;
 use crate::runtime::parser::workflow::{ActorWork, ParseWorkerSender};
 use crate::sinks::{InfraSinkAgent, SinkRouteAgent};
 use crate::stat::MonSend;
+use crate::stat::prometheus;
 use crate::types::EventBatchRecv;
 use crate::types::EventBatchSend;
 use std::sync::Arc;
@@ -39,11 +41,13 @@ pub async fn start_parser_tasks_frames(
         Arc::new(resc.get_parse_units().clone()),
         sinks.agent(),
         infra.agent(),
+        resc.rule_slot().clone(),
     ));
 
-    for _ in 0..args.parallel {
+    for idx in 0..args.parallel {
         let (dat_s, dat_r): (EventBatchSend, EventBatchRecv) =
             hold_channel.channel(dat_channel_max());
+        prometheus::watch_channel(format!("parse-{}", idx), &dat_s);
         let actuator = parser_factory.build().await?;
         // 使用通用的 ActorWork（定义在 runtime/parser/workflow.rs）
         // 代替在函数内部临时定义的 ActorFrameWork，避免重复与每轮循环重新定义类型。
@@ -70,20 +74,29 @@ struct ActParserFactory {
     pipelines: Arc<Vec<WplPipeline>>,
     sinks: SinkRouteAgent,
     infra: InfraSinkAgent,
+    rules: Arc<RuleSetSlot>,
 }
 
 impl ActParserFactory {
-    fn new(pipelines: Arc<Vec<WplPipeline>>, sinks: SinkRouteAgent, infra: InfraSinkAgent) -> Self {
+    fn new(
+        pipelines: Arc<Vec<WplPipeline>>,
+        sinks: SinkRouteAgent,
+        infra: InfraSinkAgent,
+        rules: Arc<RuleSetSlot>,
+    ) -> Self {
         Self {
             pipelines,
             sinks,
             infra,
+            rules,
         }
     }
 
     async fn build(&self) -> RunResult<ActParser> {
         // 仍需为每个 worker 生成独立的解析管线，但共享 blueprint/agent 句柄以减少资源构造
         let pipelines = self.pipelines.as_ref().clone();
-        ActParser::from_all_model(pipelines, self.sinks.clone(), self.infra.clone()).await
+        let parser =
+            ActParser::from_all_model(pipelines, self.sinks.clone(), self.infra.clone()).await?;
+        Ok(parser.with_rule_slot(self.rules.clone()))
     }
 }
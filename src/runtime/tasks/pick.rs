@@ -8,43 +8,51 @@ use wp_connector_api::SourceHandle;
 use wp_stat::StatRequires;
 use wp_stat::StatStage;
 
+/// 采集任务的共享启动参数（启动期数据源与热添加数据源共用）
+#[derive(Clone)]
+pub struct PickerCtx {
+    pub run_args: RunArgs,
+    pub mon_send: MonSend,
+    pub parse_senders: Vec<ParseWorkerSender>,
+    pub stat_reqs: StatRequires,
+}
+
 /// 启动采集任务（pickers）
 /// 使用 Frame 订阅通道启动采集任务（将 SourceFrame 分发到解析线程）
-pub fn start_picker_tasks(
-    run_args: &RunArgs,
-    all_sources: Vec<SourceHandle>,
-    mon_send: MonSend,
-    parse_senders: Vec<ParseWorkerSender>,
-    stat_reqs: &StatRequires,
-) -> TaskGroup {
+pub fn start_picker_tasks(ctx: &PickerCtx, all_sources: Vec<SourceHandle>) -> TaskGroup {
     let mut picker_group = TaskGroup::new("picker", ShutdownCmd::Immediate);
     info_ctrl!("启动数据收集(Frame)： {}个数据源", all_sources.len());
     for source_h in all_sources {
-        let worker = SourceWorker::new(
-            run_args.speed_limit,
-            run_args.line_max,
-            mon_send.clone(),
-            parse_senders.clone(),
-        );
-        let cmd_sub = picker_group.subscribe();
-        let c_args = run_args.clone();
-        let reqs = stat_reqs.get_requ_items(StatStage::Pick);
-        info_ctrl!(
-            "spawning picker for source '{}' (line_max={:?}, speed_limit={})",
-            source_h.source.identifier(),
-            c_args.line_max,
-            c_args.speed_limit
-        );
-        picker_group.append(tokio::spawn(async move {
-            let max_line = c_args.line_max;
-            let source_id = source_h.source.identifier();
-            info_ctrl!("启动数据源 picker(Frame): {}", source_id);
-            if let Err(e) = worker.run(source_h.source, cmd_sub, max_line, reqs).await {
-                error_ctrl!("数据源 '{}' picker 错误: {}", source_id, e);
-            } else {
-                info_ctrl!("数据源 '{}' picker 正常结束", source_id);
-            }
-        }));
+        spawn_picker(ctx, &mut picker_group, source_h);
     }
     picker_group
 }
+
+/// 为单个数据源启动 picker，任务句柄追加到 `group`，并订阅该组的控制命令
+pub fn spawn_picker(ctx: &PickerCtx, group: &mut TaskGroup, source_h: SourceHandle) {
+    let worker = SourceWorker::new(
+        ctx.run_args.speed_limit,
+        ctx.run_args.line_max,
+        ctx.mon_send.clone(),
+        ctx.parse_senders.clone(),
+    );
+    let cmd_sub = group.subscribe();
+    let c_args = ctx.run_args.clone();
+    let reqs = ctx.stat_reqs.get_requ_items(StatStage::Pick);
+    info_ctrl!(
+        "spawning picker for source '{}' (line_max={:?}, speed_limit={})",
+        source_h.source.identifier(),
+        c_args.line_max,
+        c_args.speed_limit
+    );
+    group.append(tokio::spawn(async move {
+        let max_line = c_args.line_max;
+        let source_id = source_h.source.identifier();
+        info_ctrl!("启动数据源 picker(Frame): {}", source_id);
+        if let Err(e) = worker.run(source_h.source, cmd_sub, max_line, reqs).await {
+            error_ctrl!("数据源 '{}' picker 错误: {}", source_id, e);
+        } else {
+            info_ctrl!("数据源 '{}' picker 正常结束", source_id);
+        }
+    }));
+}
@@ -22,7 +22,9 @@ pub fn start_data_sinks(
     let mut routine_group = TaskGroup::new("oml-sink", ShutdownCmd::Timeout(200));
     let sink_groups = act_sink.items;
     let knowdb_handler = knowdb;
-    for x in sink_groups {
+    for mut x in sink_groups {
+        // 转换线程在各自线程内完成 knowdb 就绪
+        x.start_workers(knowdb_handler.clone());
         let (bad_sink_s, fix_sink_r) = act_mt_sink.fix_channel();
         let sink_cmd_sub = routine_group.subscribe();
         let sink_mon = mon_send.clone();
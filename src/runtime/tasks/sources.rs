@@ -0,0 +1,470 @@
+//! 运行期数据源表：登记启动期数据源，并支持热添加/热移除。
+//!
+//! 启动期数据源的 picker 位于主组（pickers）中，生命周期随主流程；热添加的数据源各自
+//! 拥有独立任务组，并通过命令桥接跟随主组的隔离/停止命令，因此可以单独下线。
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use orion_conf::{ToStructError, UvsConfFrom};
+use orion_error::ErrorConv;
+use orion_variate::{EnvDict, EnvEvaluable};
+use tokio::task::JoinHandle;
+use wp_conf::structure::{SourceInstanceConf, SourceRateLimitConf};
+use wp_error::RunReason;
+use wp_error::run_error::RunResult;
+
+use super::accept::add_acceptor_tasks;
+use super::pick::{PickerCtx, spawn_picker};
+use crate::runtime::actor::TaskGroup;
+use crate::runtime::actor::command::CmdSubscriber;
+use crate::runtime::actor::signal::ShutdownCmd;
+use crate::sources::rate_limit;
+use crate::sources::{SourceConfigParser, SourceInstanceIds};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceOrigin {
+    /// 来自 wpsrc.toml，随主组启停
+    Startup,
+    /// 运行期通过 `Sources::hot_add` 添加
+    Hot,
+}
+
+/// 数据源在表中的快照（用于展示/诊断）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceState {
+    pub name: String,
+    pub origin: SourceOrigin,
+    /// 该实例构建出的 source 标识（一个实例可能展开为多个 source）
+    pub source_ids: Vec<String>,
+    pub running: bool,
+}
+
+struct HotTasks {
+    group: TaskGroup,
+    bridge: JoinHandle<()>,
+}
+
+struct SourceEntry {
+    name: String,
+    origin: SourceOrigin,
+    source_ids: Vec<String>,
+    hot: Option<HotTasks>,
+}
+
+impl SourceEntry {
+    fn running(&self) -> bool {
+        self.hot
+            .as_ref()
+            .map(|t| !t.group.routin_is_finished())
+            .unwrap_or(true)
+    }
+}
+
+/// 以实例名为键、按登记顺序排列的数据源表
+#[derive(Default)]
+struct SourceTable {
+    entries: Vec<SourceEntry>,
+}
+
+impl SourceTable {
+    fn get(&self, name: &str) -> Option<&SourceEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    fn remove(&mut self, name: &str) -> Option<SourceEntry> {
+        let pos = self.entries.iter().position(|e| e.name == name)?;
+        Some(self.entries.remove(pos))
+    }
+
+    /// 实例名与 source 标识均不得与已登记的实例重复
+    fn ensure_insertable(&self, name: &str, source_ids: &[String]) -> RunResult<()> {
+        if self.get(name).is_some() {
+            return Err(RunReason::from_conf(format!("source '{}' already exists", name)).to_err());
+        }
+        for id in source_ids {
+            if let Some(owner) = self.entries.iter().find(|e| e.source_ids.contains(id)) {
+                return Err(RunReason::from_conf(format!(
+                    "source '{}': identifier '{}' is already used by source '{}'",
+                    name, id, owner.name
+                ))
+                .to_err());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 运行期数据源表（可克隆，克隆体共享同一张表）
+#[derive(Clone)]
+pub struct Sources {
+    ctx: PickerCtx,
+    parser: Arc<SourceConfigParser>,
+    main_cmd: CmdSubscriber,
+    map: Arc<RwLock<SourceTable>>,
+}
+
+impl Sources {
+    /// `main` 为启动期 picker 所在的主组；热添加的数据源跟随其控制命令
+    pub fn new(ctx: PickerCtx, work_root: PathBuf, main: &TaskGroup) -> Self {
+        Self {
+            ctx,
+            parser: Arc::new(SourceConfigParser::new(work_root)),
+            main_cmd: main.subscribe(),
+            map: Arc::new(RwLock::new(SourceTable::default())),
+        }
+    }
+
+    /// 按配置顺序登记启动期数据源实例（其 picker 已在主组中启动）；实例名或 source 标识重复时报错
+    pub fn register_startup<I: IntoIterator<Item = SourceInstanceIds>>(
+        &self,
+        instances: I,
+    ) -> RunResult<()> {
+        let mut map = self.map.write().expect("source map poisoned");
+        for (name, source_ids) in instances {
+            map.ensure_insertable(&name, &source_ids)?;
+            map.entries.push(SourceEntry {
+                name,
+                origin: SourceOrigin::Startup,
+                source_ids,
+                hot: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// 校验并构建新的数据源实例，在当前 Tokio 运行时中启动其 picker/acceptor 并登记到表中
+    pub async fn hot_add(&self, def: SourceInstanceConf, dict: &EnvDict) -> RunResult<()> {
+        let def = def.env_eval(dict);
+        let name = def.name().clone();
+        self.ensure_absent(&name)?;
+        let (handles, acceptors) = self.parser.build_instance(def).await.err_conv()?;
+        if handles.is_empty() {
+            return Err(
+                RunReason::from_conf(format!("source '{}' built no data source", name)).to_err(),
+            );
+        }
+
+        let mut group = TaskGroup::new(format!("picker:{}", name), ShutdownCmd::Immediate);
+        let source_ids: Vec<String> = handles.iter().map(|h| h.source.identifier()).collect();
+        {
+            // 持写锁完成二次检查与登记，避免并发 hot_add 同名实例
+            let mut map = self.map.write().expect("source map poisoned");
+            map.ensure_insertable(&name, &source_ids)?;
+            for source_h in handles {
+                spawn_picker(&self.ctx, &mut group, source_h);
+            }
+            if !acceptors.is_empty() {
+                add_acceptor_tasks(&mut group, acceptors);
+            }
+            let bridge = bridge_main_cmds(self.main_cmd.clone(), &group);
+            map.entries.push(SourceEntry {
+                name: name.clone(),
+                origin: SourceOrigin::Hot,
+                source_ids: source_ids.clone(),
+                hot: Some(HotTasks { group, bridge }),
+            });
+        }
+        info_ctrl!("hot-added source '{}': {:?}", name, source_ids);
+        Ok(())
+    }
+
+    /// 优雅停止热添加的数据源并从表中移除；启动期数据源随主组启停，不支持移除
+    pub async fn hot_remove(&self, id: &str) -> RunResult<()> {
+        let entry = {
+            let mut map = self.map.write().expect("source map poisoned");
+            match map.get(id).map(|e| e.origin) {
+                None => {
+                    return Err(RunReason::from_conf(format!("source '{}' not found", id)).to_err());
+                }
+                Some(SourceOrigin::Startup) => {
+                    return Err(RunReason::from_conf(format!(
+                        "source '{}' is declared in wpsrc.toml and cannot be hot-removed",
+                        id
+                    ))
+                    .to_err());
+                }
+                Some(SourceOrigin::Hot) => map.remove(id),
+            }
+        };
+        if let Some(HotTasks { mut group, bridge }) = entry.and_then(|e| e.hot) {
+            bridge.abort();
+            group.cmd_alone().await?;
+            group.wait_grace_down(None).await?;
+        }
+        info_ctrl!("hot-removed source '{}'", id);
+        Ok(())
+    }
+
+    /// 运行期调整实例的限速参数（重载时调用），下一次拉取生效；
+    /// 仅对构建时已配置 `rate_limit` 的实例有效，新增限速需重新添加实例
+    pub fn reconfigure_rate_limit(&self, name: &str, conf: SourceRateLimitConf) -> RunResult<()> {
+        conf.validate()
+            .map_err(|e| RunReason::from_conf(format!("source '{}': {}", name, e)).to_err())?;
+        if !self.contains(name) {
+            return Err(RunReason::from_conf(format!("source '{}' not found", name)).to_err());
+        }
+        if rate_limit::reconfigure(name, &conf) == 0 {
+            return Err(RunReason::from_conf(format!(
+                "source '{}' was built without rate_limit; re-add it to enable limiting",
+                name
+            ))
+            .to_err());
+        }
+        info_ctrl!(
+            "source '{}' rate limit updated: rate={}, burst={:?}, policy={}",
+            name,
+            conf.rate,
+            conf.burst,
+            conf.policy
+        );
+        Ok(())
+    }
+
+    /// 按重新读取的实例配置刷新限速（配置重载时调用）：跳过未配置 `rate_limit` 或未在运行的实例，
+    /// 单个实例失败只记录告警；返回成功更新的实例数
+    pub fn reload_rate_limits(&self, items: &[SourceInstanceConf]) -> usize {
+        let mut updated = 0;
+        for item in items {
+            let Some(conf) = &item.rate_limit else {
+                continue;
+            };
+            if !self.contains(item.name()) {
+                continue;
+            }
+            match self.reconfigure_rate_limit(item.name(), conf.clone()) {
+                Ok(()) => updated += 1,
+                Err(e) => warn_ctrl!("reload rate limit skipped: {}", e),
+            }
+        }
+        updated
+    }
+
+    /// 表中是否存在该实例
+    pub fn contains(&self, id: &str) -> bool {
+        self.map
+            .read()
+            .expect("source map poisoned")
+            .get(id)
+            .is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.read().expect("source map poisoned").entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 按登记顺序列出所有 source 标识（含热添加实例展开出的 source）
+    pub fn list_source_identifiers(&self) -> Vec<String> {
+        let map = self.map.read().expect("source map poisoned");
+        map.entries
+            .iter()
+            .flat_map(|e| e.source_ids.clone())
+            .collect()
+    }
+
+    /// 按登记顺序（启动期实例按配置顺序，热添加实例随后）的快照
+    pub fn snapshot(&self) -> Vec<SourceState> {
+        let map = self.map.read().expect("source map poisoned");
+        map.entries
+            .iter()
+            .map(|e| SourceState {
+                name: e.name.clone(),
+                origin: e.origin,
+                source_ids: e.source_ids.clone(),
+                running: e.running(),
+            })
+            .collect()
+    }
+
+    /// 检查当前数据源表：至少存在一个数据源
+    pub fn check(&self) -> RunResult<()> {
+        if self.is_empty() {
+            return Err(RunReason::from_conf("No data sources configured".to_string()).to_err());
+        }
+        Ok(())
+    }
+
+    fn ensure_absent(&self, name: &str) -> RunResult<()> {
+        if self.contains(name) {
+            return Err(RunReason::from_conf(format!("source '{}' already exists", name)).to_err());
+        }
+        Ok(())
+    }
+}
+
+/// 将主组的控制命令转发到热添加数据源的任务组
+fn bridge_main_cmds(mut main_cmd: CmdSubscriber, group: &TaskGroup) -> JoinHandle<()> {
+    let publish = group.publish();
+    tokio::spawn(async move {
+        while let Ok(cmd) = main_cmd.recv().await {
+            if publish.broadcast(cmd).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::parser::workflow::ParseWorkerSender;
+    use serde_json::json;
+    use wp_conf::RunArgs;
+    use wp_connector_api::ParamMap;
+    use wp_stat::StatRequires;
+
+    fn test_sources(work_root: PathBuf) -> (Sources, TaskGroup) {
+        let (mon_send, _mon_recv) = tokio::sync::mpsc::channel(16);
+        let parse_senders: Vec<ParseWorkerSender> = Vec::new();
+        let ctx = PickerCtx {
+            run_args: RunArgs::default(),
+            mon_send,
+            parse_senders,
+            stat_reqs: StatRequires::from(Vec::new()),
+        };
+        let main = TaskGroup::new("picker", ShutdownCmd::Immediate);
+        (Sources::new(ctx, work_root, &main), main)
+    }
+
+    /// 每个实例只展开出一个与实例同名的 source
+    fn single(names: &[&str]) -> Vec<SourceInstanceIds> {
+        names
+            .iter()
+            .map(|n| (n.to_string(), vec![n.to_string()]))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn startup_sources_are_listed_but_not_removable() {
+        let (sources, _main) = test_sources(std::env::temp_dir());
+        assert!(sources.check().is_err());
+        sources
+            .register_startup(single(&["file_1", "tcp_1"]))
+            .expect("register");
+        sources.check().expect("has sources");
+        assert_eq!(sources.list_source_identifiers(), vec!["file_1", "tcp_1"]);
+        assert!(sources.hot_remove("file_1").await.is_err());
+        assert!(sources.hot_remove("missing").await.is_err());
+        // 未配置限速的实例不可运行期调参
+        let limit = SourceRateLimitConf::new(100, None, Default::default());
+        assert!(
+            sources
+                .reconfigure_rate_limit("file_1", limit.clone())
+                .is_err()
+        );
+        assert!(sources.reconfigure_rate_limit("missing", limit).is_err());
+        assert_eq!(sources.len(), 2);
+        let snap = sources.snapshot();
+        assert_eq!(snap[0].origin, SourceOrigin::Startup);
+        assert!(snap[0].running);
+    }
+
+    struct IdleSource(String);
+
+    #[async_trait::async_trait]
+    impl wp_connector_api::DataSource for IdleSource {
+        async fn receive(
+            &mut self,
+        ) -> wp_connector_api::SourceResult<wp_connector_api::SourceBatch> {
+            Ok(Default::default())
+        }
+        fn try_receive(&mut self) -> Option<wp_connector_api::SourceBatch> {
+            None
+        }
+        fn can_try_receive(&mut self) -> bool {
+            false
+        }
+        fn identifier(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn reload_applies_rate_limits_from_instance_confs() {
+        let (sources, _main) = test_sources(std::env::temp_dir());
+        sources
+            .register_startup(single(&["rl_src", "plain_src"]))
+            .expect("register");
+        let limit = SourceRateLimitConf::new(100, None, Default::default());
+        let _limited = rate_limit::RateLimitedSource::new(
+            "rl_src",
+            Box::new(IdleSource("rl_src".into())),
+            &limit,
+        );
+
+        let item = |name: &str, limit: Option<SourceRateLimitConf>| {
+            let mut conf =
+                SourceInstanceConf::new_type(name.into(), "file".into(), ParamMap::new(), vec![]);
+            conf.rate_limit = limit;
+            conf
+        };
+        let faster = SourceRateLimitConf::new(500, Some(50), Default::default());
+        let items = vec![
+            item("rl_src", Some(faster.clone())),
+            // 构建时未配置限速：仅告警
+            item("plain_src", Some(faster.clone())),
+            // 未在运行或未配置限速：跳过
+            item("stopped_src", Some(faster)),
+            item("rl_src_2", None),
+        ];
+        assert_eq!(sources.reload_rate_limits(&items), 1);
+    }
+
+    #[tokio::test]
+    async fn hot_add_rejects_invalid_and_duplicate() {
+        let (sources, _main) = test_sources(std::env::temp_dir());
+        sources
+            .register_startup(single(&["file_1"]))
+            .expect("register");
+
+        let dup =
+            SourceInstanceConf::new_type("file_1".into(), "file".into(), ParamMap::new(), vec![]);
+        assert!(sources.hot_add(dup, &EnvDict::default()).await.is_err());
+
+        let mut params = ParamMap::new();
+        params.insert("path".into(), json!("/dev/null"));
+        let unknown =
+            SourceInstanceConf::new_type("ghost".into(), "no_such_kind".into(), params, vec![]);
+        assert!(sources.hot_add(unknown, &EnvDict::default()).await.is_err());
+        assert!(!sources.contains("ghost"));
+        assert_eq!(sources.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn startup_sources_keep_order_and_reject_duplicates() {
+        let (sources, _main) = test_sources(std::env::temp_dir());
+        sources
+            .register_startup(vec![
+                ("zeta".to_string(), vec!["zeta".to_string()]),
+                (
+                    "files".to_string(),
+                    vec!["files_1".to_string(), "files_0".to_string()],
+                ),
+                ("alpha".to_string(), vec!["alpha".to_string()]),
+            ])
+            .expect("register");
+        assert_eq!(
+            sources.list_source_identifiers(),
+            vec!["zeta", "files_1", "files_0", "alpha"]
+        );
+        let names: Vec<String> = sources.snapshot().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["zeta", "files", "alpha"]);
+        // 按实例名查找，而非 source 标识
+        assert!(sources.contains("files"));
+        assert!(!sources.contains("files_1"));
+
+        // 实例名重复
+        assert!(sources.register_startup(single(&["alpha"])).is_err());
+        // source 标识与已有实例重复
+        assert!(
+            sources
+                .register_startup(vec![("other".to_string(), vec!["files_0".to_string()])])
+                .is_err()
+        );
+        assert_eq!(sources.len(), 3);
+    }
+}
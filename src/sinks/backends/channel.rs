@@ -0,0 +1,206 @@
+//! Channel sink：把记录投递给进程内订阅者（嵌入式引擎使用）
+//!
+//! 订阅者按 route 全局登记，同一进程内的 route 需唯一；无订阅者时记录直接丢弃。
+//! 订阅者处理过慢时发送等待，形成对上游的背压。
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde_json::json;
+use tokio::sync::mpsc;
+use wp_conf::connectors::{ConnectorDef, ConnectorScope, SinkDefProvider};
+use wp_connector_api::{ParamMap, SinkBuildCtx, SinkFactory, SinkHandle, SinkResult, SinkSpec};
+use wp_model_core::model::{DataField, DataRecord};
+
+static SUBSCRIBERS: Lazy<RwLock<HashMap<String, Vec<mpsc::Sender<DataRecord>>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 订阅 route 的输出；接收端丢弃后自动退订
+pub fn subscribe_output(route: &str, capacity: usize) -> mpsc::Receiver<DataRecord> {
+    let (tx, rx) = mpsc::channel(capacity.max(1));
+    SUBSCRIBERS
+        .write()
+        .expect("channel sink subscribers poisoned")
+        .entry(route.to_string())
+        .or_default()
+        .push(tx);
+    rx
+}
+
+fn subscribers_of(route: &str) -> Vec<mpsc::Sender<DataRecord>> {
+    SUBSCRIBERS
+        .read()
+        .expect("channel sink subscribers poisoned")
+        .get(route)
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn prune_closed(route: &str) {
+    let mut map = SUBSCRIBERS
+        .write()
+        .expect("channel sink subscribers poisoned");
+    if let Some(list) = map.get_mut(route) {
+        list.retain(|tx| !tx.is_closed());
+        if list.is_empty() {
+            map.remove(route);
+        }
+    }
+}
+
+pub struct ChannelSink {
+    route: String,
+}
+
+impl ChannelSink {
+    pub fn new(route: String) -> Self {
+        Self { route }
+    }
+
+    async fn deliver(&self, records: impl IntoIterator<Item = DataRecord>) {
+        let subscribers = subscribers_of(&self.route);
+        if subscribers.is_empty() {
+            return;
+        }
+        let mut closed = false;
+        for record in records {
+            for tx in &subscribers {
+                closed |= tx.send(record.clone()).await.is_err();
+            }
+        }
+        if closed {
+            prune_closed(&self.route);
+        }
+    }
+}
+
+fn raw_record(data: &str) -> DataRecord {
+    DataRecord::from(vec![DataField::from_chars("raw", data)])
+}
+
+#[async_trait]
+impl wp_connector_api::AsyncCtrl for ChannelSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl wp_connector_api::AsyncRecordSink for ChannelSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        self.deliver([data.clone()]).await;
+        Ok(())
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.deliver(
+            data.into_iter()
+                .map(|arc| Arc::try_unwrap(arc).unwrap_or_else(|arc| arc.as_ref().clone())),
+        )
+        .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl wp_connector_api::AsyncRawDataSink for ChannelSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.deliver([raw_record(data)]).await;
+        Ok(())
+    }
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.deliver([raw_record(&String::from_utf8_lossy(data))])
+            .await;
+        Ok(())
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.deliver(data.into_iter().map(raw_record)).await;
+        Ok(())
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.deliver(
+            data.into_iter()
+                .map(|b| raw_record(&String::from_utf8_lossy(b))),
+        )
+        .await;
+        Ok(())
+    }
+}
+
+pub struct ChannelFactory;
+
+#[async_trait]
+impl SinkFactory for ChannelFactory {
+    fn kind(&self) -> &'static str {
+        "channel"
+    }
+    fn validate_spec(&self, _spec: &SinkSpec) -> SinkResult<()> {
+        Ok(())
+    }
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        // 未指定 route 时以 sink 名称作为订阅键
+        let route = spec
+            .params
+            .get("route")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| spec.name.clone());
+        Ok(SinkHandle::new(Box::new(ChannelSink::new(route))))
+    }
+}
+
+impl SinkDefProvider for ChannelFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        let mut params = ParamMap::new();
+        params.insert("route".into(), json!(""));
+        ConnectorDef {
+            id: "channel_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["route".into()],
+            default_params: params,
+            origin: Some("builtin:channel".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_connector_api::{AsyncRawDataSink, AsyncRecordSink};
+
+    #[tokio::test]
+    async fn delivers_to_all_subscribers() {
+        let mut a = subscribe_output("chan_sink_test", 8);
+        let mut b = subscribe_output("chan_sink_test", 8);
+        let mut sink = ChannelSink::new("chan_sink_test".into());
+
+        let rec = DataRecord::from(vec![DataField::from_chars("msg", "hi")]);
+        sink.sink_records(vec![Arc::new(rec.clone())])
+            .await
+            .unwrap();
+        let msg = Some(DataField::from_chars("msg", "hi"));
+        assert_eq!(a.recv().await.unwrap().get_field_owned("msg"), msg);
+        assert_eq!(b.recv().await.unwrap().get_field_owned("msg"), msg);
+
+        drop(b);
+        sink.sink_str("raw line").await.unwrap();
+        assert_eq!(
+            a.recv().await.unwrap().get_field_owned("raw"),
+            Some(DataField::from_chars("raw", "raw line"))
+        );
+        assert_eq!(subscribers_of("chan_sink_test").len(), 1);
+
+        // 无订阅者的 route 直接丢弃
+        let mut other = ChannelSink::new("chan_sink_none".into());
+        other.sink_record(&rec).await.unwrap();
+    }
+}
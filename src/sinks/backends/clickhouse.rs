@@ -0,0 +1,914 @@
+//! ClickHouse sink: 通过 HTTP 接口以 `INSERT ... FORMAT JSONEachRow` 列式批量写入。
+//!
+//! - 记录按 `batch_rows` 攒批，达到行数或距上次写入超过 `flush_interval_ms` 时提交，
+//!   后台定时任务保证流量停止后缓冲的尾部数据也按间隔提交；
+//! - 网络/服务端故障返回 `SinkReason::Sink`，由运行时切换到 rescue 文件；已提交的行不会交还，
+//!   避免部分提交后整批重发；
+//! - 服务端拒绝（数据格式/类型不匹配）时逐行隔离，被拒的行连同服务端错误文本写入 rescue。
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use orion_conf::ErrorOwe;
+use serde_json::{Map as JsonMap, Value as JsonValue, json};
+use tokio::runtime::RuntimeFlavor;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use wp_conf::connectors::{ConnectorDef, ConnectorScope, ParamMap, SinkDefProvider};
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkBuildCtx, SinkError, SinkFactory, SinkHandle,
+    SinkReason, SinkResult, SinkSpec as ResolvedSinkSpec,
+};
+use wp_data_fmt::{Json, ValueFormatter};
+use wp_model_core::model::{DataRecord, Value};
+
+use crate::sinks::{RescueEntry, RescueFileSink};
+
+type AnyResult<T> = anyhow::Result<T>;
+
+const DEFAULT_BATCH_ROWS: usize = 10_000;
+const DEFAULT_FLUSH_INTERVAL_MS: u64 = 1_000;
+const DEFAULT_RESCUE_DIR: &str = "./data/rescue";
+const HTTP_TIMEOUT_SECS: u64 = 30;
+// ClickHouse 数据类错误码：CANNOT_PARSE_TEXT / CANNOT_PARSE_INPUT_ASSERTION_FAILED /
+// TYPE_MISMATCH / INCORRECT_DATA；命中时视为服务端拒收而非连接故障
+const REJECT_EXCEPTION_CODES: &[&str] = &["6", "27", "53", "117"];
+
+#[derive(Clone, Debug)]
+pub(crate) struct ClickHouseSinkSpec {
+    endpoint: String,
+    user: Option<String>,
+    password: Option<String>,
+    database: Option<String>,
+    table: String,
+    batch_rows: usize,
+    flush_interval: Duration,
+    columns: BTreeMap<String, String>,
+    rescue_dir: String,
+}
+
+impl ClickHouseSinkSpec {
+    pub(crate) fn from_params(params: &ParamMap) -> AnyResult<Self> {
+        let dsn = params
+            .get("dsn")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("clickhouse.dsn must be a string"))?;
+        let mut url = url::Url::parse(dsn)
+            .map_err(|e| anyhow::anyhow!("clickhouse.dsn is invalid: {}", e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            anyhow::bail!("clickhouse.dsn scheme must be 'http' or 'https'");
+        }
+        let user = (!url.username().is_empty()).then(|| url.username().to_string());
+        let password = url.password().map(|s| s.to_string());
+        let database = url
+            .path()
+            .trim_matches('/')
+            .split('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+        // 凭据通过请求头下发，避免出现在日志中的 URL 里
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+        url.set_path("/");
+        url.set_query(None);
+
+        let table = params
+            .get("table")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("clickhouse.table must be a non-empty string"))?
+            .to_string();
+        let batch_rows = match params.get("batch_rows") {
+            None => DEFAULT_BATCH_ROWS,
+            Some(v) => match v.as_i64() {
+                Some(n) if n > 0 => n as usize,
+                _ => anyhow::bail!("clickhouse.batch_rows must be a positive integer"),
+            },
+        };
+        let flush_interval_ms = match params.get("flush_interval_ms") {
+            None => DEFAULT_FLUSH_INTERVAL_MS,
+            Some(v) => match v.as_i64() {
+                Some(n) if n >= 0 => n as u64,
+                _ => anyhow::bail!("clickhouse.flush_interval_ms must be a non-negative integer"),
+            },
+        };
+        let mut columns = BTreeMap::new();
+        if let Some(v) = params.get("columns") {
+            let obj = v.as_object().ok_or_else(|| {
+                anyhow::anyhow!("clickhouse.columns must be a table of field = \"column\"")
+            })?;
+            for (field, col) in obj {
+                let col = col.as_str().ok_or_else(|| {
+                    anyhow::anyhow!("clickhouse.columns.{} must be a string", field)
+                })?;
+                columns.insert(field.clone(), col.to_string());
+            }
+        }
+        let rescue_dir = params
+            .get("rescue_dir")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_RESCUE_DIR)
+            .to_string();
+        Ok(Self {
+            endpoint: url.to_string(),
+            user,
+            password,
+            database,
+            table,
+            batch_rows,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+            columns,
+            rescue_dir,
+        })
+    }
+
+    fn insert_query(&self) -> String {
+        format!("INSERT INTO {} FORMAT JSONEachRow", self.table)
+    }
+
+    fn client() -> AnyResult<reqwest::Client> {
+        Ok(reqwest::Client::builder()
+            .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+            .build()?)
+    }
+
+    fn request(&self, client: &reqwest::Client, query: &str) -> reqwest::RequestBuilder {
+        let mut req = client
+            .post(&self.endpoint)
+            .query(&[("query", query), ("date_time_input_format", "best_effort")]);
+        if let Some(db) = &self.database {
+            req = req.query(&[("database", db.as_str())]);
+        }
+        if let Some(user) = &self.user {
+            req = req.header("X-ClickHouse-User", user);
+        }
+        if let Some(pass) = &self.password {
+            req = req.header("X-ClickHouse-Key", pass);
+        }
+        req
+    }
+
+    /// 连通性检查：验证 DSN 可达、凭据有效且目标表存在。
+    pub(crate) async fn check_connection(&self) -> AnyResult<()> {
+        let client = Self::client()?;
+        let resp = self
+            .request(&client, &format!("EXISTS TABLE {}", self.table))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("clickhouse {} unreachable: {}", self.endpoint, e))?;
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!("clickhouse check failed ({}): {}", status, body.trim());
+        }
+        if body.trim() != "1" {
+            anyhow::bail!("clickhouse table '{}' does not exist", self.table);
+        }
+        Ok(())
+    }
+
+    fn column_of<'a>(&'a self, field: &'a str) -> &'a str {
+        self.columns.get(field).map(|s| s.as_str()).unwrap_or(field)
+    }
+
+    /// 将一条记录映射为 JSONEachRow 行；Ignore 字段被跳过。
+    fn record_row(&self, record: &DataRecord) -> String {
+        let mut row = JsonMap::new();
+        for field in record.items.iter() {
+            if matches!(field.get_value(), Value::Ignore(_)) {
+                continue;
+            }
+            row.insert(
+                self.column_of(field.get_name()).to_string(),
+                value_to_json(field.get_value()),
+            );
+        }
+        JsonValue::Object(row).to_string()
+    }
+}
+
+/// 字段值到 ClickHouse 输入的类型映射：
+/// chars/digit/float/bool 原样，time 为 `YYYY-MM-DD hh:mm:ss[.f]`，ip 为文本，
+/// array 保持为 JSON 数组（对应 `Array(T)` 列），obj 序列化为 JSON 字符串。
+fn value_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Null | Value::Ignore(_) => JsonValue::Null,
+        Value::Bool(v) => json!(v),
+        Value::Digit(v) => json!(v),
+        Value::Float(v) => serde_json::Number::from_f64(*v)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::Null),
+        Value::Chars(v) => json!(v.to_string()),
+        Value::Symbol(v) => json!(v.to_string()),
+        Value::Hex(v) => json!(v.to_string()),
+        Value::Time(v) => json!(v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        Value::IpAddr(v) => json!(v.to_string()),
+        Value::IpNet(v) => json!(v.to_string()),
+        Value::Domain(v) => json!(v.0.to_string()),
+        Value::Url(v) => json!(v.0.to_string()),
+        Value::Email(v) => json!(v.0.to_string()),
+        Value::IdCard(v) => json!(v.0.to_string()),
+        Value::MobilePhone(v) => json!(v.0.to_string()),
+        Value::Array(items) => JsonValue::Array(
+            items
+                .iter()
+                .map(|item| value_to_json(item.as_field().get_value()))
+                .collect(),
+        ),
+        Value::Obj(_) => json!(Json.format_value(value).to_string()),
+    }
+}
+
+/// 待写入的一行：保留来源以便拒收时写入 rescue
+struct PendingRow {
+    line: String,
+    origin: RowOrigin,
+}
+
+enum RowOrigin {
+    Record(Arc<DataRecord>),
+    Raw,
+}
+
+impl PendingRow {
+    fn rescue_entry(&self, error: &str) -> RescueEntry {
+        match &self.origin {
+            RowOrigin::Record(rec) => RescueEntry::rejected_record(rec, error),
+            RowOrigin::Raw => RescueEntry::rejected_raw(self.line.clone(), error),
+        }
+    }
+
+    fn unsent_entry(&self) -> RescueEntry {
+        match &self.origin {
+            RowOrigin::Record(rec) => RescueEntry::record(rec),
+            RowOrigin::Raw => RescueEntry::raw_line(self.line.clone()),
+        }
+    }
+}
+
+enum InsertError {
+    /// 服务端拒收数据（格式/类型错误），携带服务端错误文本
+    Rejected(String),
+    /// 网络或服务端故障，交由运行时走 rescue/重连流程
+    Transport(String),
+}
+
+/// 写入状态；由 sink 与定时 flush 任务共享
+struct ClickHouseWriter {
+    name: String,
+    spec: ClickHouseSinkSpec,
+    client: reqwest::Client,
+    pending: Vec<PendingRow>,
+    last_flush: Instant,
+    rejected: Option<RescueFileSink>,
+}
+
+impl ClickHouseWriter {
+    fn sink_err<E: std::fmt::Display>(err: E) -> SinkError {
+        SinkError::from(SinkReason::Sink(err.to_string()))
+    }
+
+    async fn insert(&self, rows: &[PendingRow]) -> Result<(), InsertError> {
+        let mut body = String::with_capacity(rows.iter().map(|r| r.line.len() + 1).sum());
+        for row in rows {
+            body.push_str(&row.line);
+            body.push('\n');
+        }
+        let resp = self
+            .spec
+            .request(&self.client, &self.spec.insert_query())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| InsertError::Transport(e.to_string()))?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let code = resp
+            .headers()
+            .get("X-ClickHouse-Exception-Code")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let text = resp.text().await.unwrap_or_default().trim().to_string();
+        let rejected = status == reqwest::StatusCode::BAD_REQUEST
+            || code
+                .as_deref()
+                .is_some_and(|c| REJECT_EXCEPTION_CODES.contains(&c));
+        if rejected {
+            Err(InsertError::Rejected(text))
+        } else {
+            Err(InsertError::Transport(format!("{}: {}", status, text)))
+        }
+    }
+
+    async fn rescue_rejected(&mut self, row: &PendingRow, error: &str) -> SinkResult<()> {
+        if self.rejected.is_none() {
+            let fmt_time = Utc::now().format("%Y-%m-%d_%H:%M:%S").to_string();
+            let path = format!(
+                "{}/{}-rejected-{}.dat.lock",
+                self.spec.rescue_dir, self.name, fmt_time
+            );
+            let sink = RescueFileSink::new(&path).await.map_err(Self::sink_err)?;
+            self.rejected = Some(sink);
+        }
+        warn_data!("clickhouse sink {} rejected row: {}", self.name, error);
+        if let Some(sink) = self.rejected.as_mut() {
+            sink.write_entry(&row.rescue_entry(error)).await?;
+        }
+        Ok(())
+    }
+
+    /// 连接故障下未能提交的行写入常规 rescue 文件（不带拒收原因），可按原样回放
+    async fn rescue_unsent(&mut self, rows: Vec<PendingRow>) -> SinkResult<()> {
+        let fmt_time = Utc::now().format("%Y-%m-%d_%H:%M:%S").to_string();
+        let path = format!(
+            "{}/{}-{}.dat.lock",
+            self.spec.rescue_dir, self.name, fmt_time
+        );
+        warn_data!(
+            "clickhouse sink {} unreachable on stop, rescue {} rows to {}",
+            self.name,
+            rows.len(),
+            path
+        );
+        let mut sink = RescueFileSink::new(&path).await.map_err(Self::sink_err)?;
+        for row in rows.iter() {
+            sink.write_entry(&row.unsent_entry()).await?;
+        }
+        sink.stop().await
+    }
+
+    /// 逐行重试被整体拒收的批次，隔离出坏行写入 rescue；
+    /// 遇到连接故障时返回尚未处理的行
+    async fn isolate_rejected(
+        &mut self,
+        rows: Vec<PendingRow>,
+    ) -> Result<(), (Vec<PendingRow>, SinkError)> {
+        let mut rows = rows.into_iter();
+        while let Some(row) = rows.next() {
+            let res = match self.insert(std::slice::from_ref(&row)).await {
+                Ok(()) => Ok(()),
+                Err(InsertError::Rejected(text)) => self.rescue_rejected(&row, &text).await,
+                Err(InsertError::Transport(text)) => Err(Self::sink_err(text)),
+            };
+            if let Err(e) = res {
+                let mut rest = vec![row];
+                rest.extend(rows);
+                return Err((rest, e));
+            }
+        }
+        Ok(())
+    }
+
+    /// 按 `batch_rows` 分块提交 pending；返回已提交（写入或 rescue）的行数与首个故障，
+    /// 未提交的行按原顺序保留在 pending 队首
+    async fn flush_pending(&mut self) -> (usize, SinkResult<()>) {
+        let total = self.pending.len();
+        while !self.pending.is_empty() {
+            let n = self.pending.len().min(self.spec.batch_rows);
+            let chunk: Vec<PendingRow> = self.pending.drain(..n).collect();
+            let unsent = match self.insert(&chunk).await {
+                Ok(()) => None,
+                Err(InsertError::Rejected(_)) => self.isolate_rejected(chunk).await.err(),
+                Err(InsertError::Transport(text)) => Some((chunk, Self::sink_err(text))),
+            };
+            if let Some((rows, e)) = unsent {
+                let rest = std::mem::replace(&mut self.pending, rows);
+                self.pending.extend(rest);
+                return (total - self.pending.len(), Err(e));
+            }
+        }
+        self.last_flush = Instant::now();
+        (total, Ok(()))
+    }
+
+    /// 定时 flush：缓冲非空且距上次写入已超过 `flush_interval` 时提交
+    async fn flush_due(&mut self) {
+        if self.pending.is_empty() || self.last_flush.elapsed() < self.spec.flush_interval {
+            return;
+        }
+        if let (_, Err(e)) = self.flush_pending().await {
+            warn_data!(
+                "clickhouse sink {} timed flush failed, {} rows kept for retry: {}",
+                self.name,
+                self.pending.len(),
+                e
+            );
+        }
+    }
+
+    async fn push_rows(&mut self, rows: Vec<PendingRow>) -> SinkResult<()> {
+        let mark = self.pending.len();
+        self.pending.extend(rows);
+        if self.pending.len() < self.spec.batch_rows
+            && self.last_flush.elapsed() < self.spec.flush_interval
+        {
+            return Ok(());
+        }
+        let (committed, res) = self.flush_pending().await;
+        let Err(e) = res else {
+            return Ok(());
+        };
+        if committed <= mark {
+            // 本次调用的行均未提交：交还运行时（将进入 rescue），仅保留此前缓冲的行
+            self.pending.truncate(mark - committed);
+            return Err(e);
+        }
+        // 本次调用已有部分行提交，交还整批会导致重复写入；未提交的行留在缓冲中，
+        // 由定时 flush 或下一次写入重试，故障在下一次写入时上报
+        warn_data!(
+            "clickhouse sink {} partial flush failed after {} rows, {} rows kept for retry: {}",
+            self.name,
+            committed - mark,
+            self.pending.len(),
+            e
+        );
+        Ok(())
+    }
+}
+
+pub struct ClickHouseSink {
+    writer: Arc<Mutex<ClickHouseWriter>>,
+    ticker: Option<JoinHandle<()>>,
+}
+
+impl ClickHouseSink {
+    fn new(name: String, spec: ClickHouseSinkSpec) -> AnyResult<Self> {
+        let flush_interval = spec.flush_interval;
+        let writer = Arc::new(Mutex::new(ClickHouseWriter {
+            name,
+            client: ClickHouseSinkSpec::client()?,
+            spec,
+            pending: Vec::new(),
+            last_flush: Instant::now(),
+            rejected: None,
+        }));
+        let ticker = Self::spawn_ticker(&writer, flush_interval);
+        Ok(Self { writer, ticker })
+    }
+
+    /// 后台按 `flush_interval` 检查缓冲，避免流量停止后尾部数据滞留；
+    /// 间隔为 0 时每次写入都会提交，无需定时任务
+    fn spawn_ticker(
+        writer: &Arc<Mutex<ClickHouseWriter>>,
+        flush_interval: Duration,
+    ) -> Option<JoinHandle<()>> {
+        if flush_interval.is_zero() {
+            return None;
+        }
+        let handle = tokio::runtime::Handle::try_current().ok()?;
+        let writer = Arc::downgrade(writer);
+        Some(handle.spawn(async move {
+            let mut tick = tokio::time::interval(flush_interval);
+            tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tick.tick().await;
+                let Some(writer) = writer.upgrade() else {
+                    break;
+                };
+                writer.lock().await.flush_due().await;
+            }
+        }))
+    }
+
+    async fn push_rows(&mut self, rows: Vec<PendingRow>) -> SinkResult<()> {
+        self.writer.lock().await.push_rows(rows).await
+    }
+}
+
+impl Drop for ClickHouseSink {
+    fn drop(&mut self) {
+        if let Some(ticker) = self.ticker.take() {
+            ticker.abort();
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for ClickHouseSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        if let Some(ticker) = self.ticker.take() {
+            ticker.abort();
+        }
+        let mut writer = self.writer.lock().await;
+        let (_, res) = writer.flush_pending().await;
+        if res.is_err() {
+            // 连接故障：退出前无法提交的行写入常规 rescue 文件，避免丢失
+            let rows = std::mem::take(&mut writer.pending);
+            writer.rescue_unsent(rows).await?;
+        }
+        if let Some(mut sink) = writer.rejected.take() {
+            sink.stop().await?;
+        }
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for ClickHouseSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        // 单条入口拿不到 Arc，不深拷贝记录：rescue 时写入已映射列名的 JSONEachRow 行，
+        // 可经 raw 路径原样回放
+        let mut writer = self.writer.lock().await;
+        let row = PendingRow {
+            line: writer.spec.record_row(data),
+            origin: RowOrigin::Raw,
+        };
+        writer.push_rows(vec![row]).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let mut writer = self.writer.lock().await;
+        let rows = data
+            .into_iter()
+            .map(|rec| PendingRow {
+                line: writer.spec.record_row(&rec),
+                origin: RowOrigin::Record(rec),
+            })
+            .collect();
+        writer.push_rows(rows).await
+    }
+}
+
+/// Raw 输入按已格式化的 JSONEachRow 行直接写入
+#[async_trait]
+impl AsyncRawDataSink for ClickHouseSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.sink_str_batch(vec![data]).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.sink_str(&String::from_utf8_lossy(data)).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        let rows = data
+            .into_iter()
+            .map(|s| s.trim_end_matches('\n'))
+            .filter(|s| !s.is_empty())
+            .map(|s| PendingRow {
+                line: s.to_string(),
+                origin: RowOrigin::Raw,
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return Ok(());
+        }
+        self.push_rows(rows).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        let lines: Vec<String> = data
+            .iter()
+            .map(|b| String::from_utf8_lossy(b).into_owned())
+            .collect();
+        self.sink_str_batch(lines.iter().map(|s| s.as_str()).collect())
+            .await
+    }
+}
+
+pub struct ClickHouseFactory;
+
+impl ClickHouseFactory {
+    /// 供 `wproj prj check` 使用：按连接器参数检查 ClickHouse 连通性
+    pub async fn check_connection(&self, params: &ParamMap) -> AnyResult<()> {
+        ClickHouseSinkSpec::from_params(params)?
+            .check_connection()
+            .await
+    }
+
+    /// 同步入口：多线程 runtime 内通过 block_in_place 阻塞；current_thread runtime
+    /// 不支持 block_in_place，改在独立线程上构建临时 runtime；无 runtime 时直接构建
+    pub fn check_connection_blocking(&self, params: &ParamMap) -> AnyResult<()> {
+        let run = || -> AnyResult<()> {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(self.check_connection(params))
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| handle.block_on(self.check_connection(params)))
+            }
+            Ok(_) => std::thread::scope(|s| s.spawn(run).join())
+                .unwrap_or_else(|_| Err(anyhow::anyhow!("clickhouse check thread panicked"))),
+            Err(_) => run(),
+        }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for ClickHouseFactory {
+    fn kind(&self) -> &'static str {
+        "clickhouse"
+    }
+    fn validate_spec(&self, spec: &ResolvedSinkSpec) -> SinkResult<()> {
+        ClickHouseSinkSpec::from_params(&spec.params).owe_conf()?;
+        Ok(())
+    }
+    async fn build(&self, spec: &ResolvedSinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let resolved = ClickHouseSinkSpec::from_params(&spec.params).owe_conf()?;
+        log::info!(
+            "clickhouse sink ready: endpoint={} table={} batch_rows={}",
+            resolved.endpoint,
+            resolved.table,
+            resolved.batch_rows
+        );
+        let sink = ClickHouseSink::new(spec.name.clone(), resolved).owe_res()?;
+        Ok(SinkHandle::new(Box::new(sink)))
+    }
+}
+
+impl SinkDefProvider for ClickHouseFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        let mut params = ParamMap::new();
+        params.insert(
+            "dsn".into(),
+            json!("http://default:@127.0.0.1:8123/default"),
+        );
+        params.insert("table".into(), json!("wp_events"));
+        params.insert("batch_rows".into(), json!(DEFAULT_BATCH_ROWS));
+        params.insert("flush_interval_ms".into(), json!(DEFAULT_FLUSH_INTERVAL_MS));
+        ConnectorDef {
+            id: "clickhouse_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "dsn".into(),
+                "table".into(),
+                "batch_rows".into(),
+                "flush_interval_ms".into(),
+                "columns".into(),
+            ],
+            default_params: params,
+            origin: Some("builtin:clickhouse_sink".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::RescuePayload;
+    use httpmock::prelude::*;
+    use wp_model_core::model::DataField;
+
+    fn params(dsn: &str, extra: &[(&str, JsonValue)]) -> ParamMap {
+        let mut p = ParamMap::new();
+        p.insert("dsn".into(), json!(dsn));
+        p.insert("table".into(), json!("events"));
+        for (k, v) in extra {
+            p.insert((*k).into(), v.clone());
+        }
+        p
+    }
+
+    #[test]
+    fn spec_parses_dsn_credentials_and_database() {
+        let spec =
+            ClickHouseSinkSpec::from_params(&params("http://u:p@10.0.0.1:8123/logs", &[])).unwrap();
+        assert_eq!(spec.endpoint, "http://10.0.0.1:8123/");
+        assert_eq!(spec.user.as_deref(), Some("u"));
+        assert_eq!(spec.password.as_deref(), Some("p"));
+        assert_eq!(spec.database.as_deref(), Some("logs"));
+        assert_eq!(spec.batch_rows, DEFAULT_BATCH_ROWS);
+    }
+
+    #[test]
+    fn spec_rejects_bad_params() {
+        assert!(ClickHouseSinkSpec::from_params(&params("tcp://h:9000", &[])).is_err());
+        assert!(
+            ClickHouseSinkSpec::from_params(&params("http://h:8123", &[("batch_rows", json!(0))]))
+                .is_err()
+        );
+        let mut p = params("http://h:8123", &[]);
+        p.remove("table");
+        assert!(ClickHouseSinkSpec::from_params(&p).is_err());
+    }
+
+    #[test]
+    fn record_row_maps_types_and_columns() {
+        let spec = ClickHouseSinkSpec::from_params(&params(
+            "http://h:8123",
+            &[("columns", json!({"sip": "src_ip"}))],
+        ))
+        .unwrap();
+        let mut rec = DataRecord::default();
+        rec.append(DataField::from_chars("msg", "hello"));
+        rec.append(DataField::from_digit("size", 42));
+        rec.append(DataField::from_ip(
+            "sip",
+            "192.168.1.1".parse::<std::net::IpAddr>().unwrap(),
+        ));
+        let row: JsonValue = serde_json::from_str(&spec.record_row(&rec)).unwrap();
+        assert_eq!(row["msg"], json!("hello"));
+        assert_eq!(row["size"], json!(42));
+        assert_eq!(row["src_ip"], json!("192.168.1.1"));
+        assert!(row.get("sip").is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn batch_insert_posts_json_each_row() -> anyhow::Result<()> {
+        let server = MockServer::start_async().await;
+        let m = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .query_param("query", "INSERT INTO events FORMAT JSONEachRow")
+                    .body_includes("\"msg\":\"a\"")
+                    .body_includes("\"msg\":\"b\"");
+                then.status(200);
+            })
+            .await;
+        let spec = ClickHouseSinkSpec::from_params(&params(
+            &server.base_url(),
+            &[("batch_rows", json!(2))],
+        ))?;
+        let mut sink = ClickHouseSink::new("ck".into(), spec)?;
+        let recs = ["a", "b"]
+            .iter()
+            .map(|v| {
+                let mut rec = DataRecord::default();
+                rec.append(DataField::from_chars("msg", *v));
+                Arc::new(rec)
+            })
+            .collect();
+        AsyncRecordSink::sink_records(&mut sink, recs).await?;
+        m.assert_async().await;
+        assert!(sink.writer.lock().await.pending.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejected_rows_are_rescued_with_server_error() -> anyhow::Result<()> {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).body_includes("\"size\":\"bad\"");
+                then.status(400)
+                    .header("X-ClickHouse-Exception-Code", "27")
+                    .body("Code: 27. Cannot parse input");
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST);
+                then.status(200);
+            })
+            .await;
+        let temp = tempfile::tempdir()?;
+        let spec = ClickHouseSinkSpec::from_params(&params(
+            &server.base_url(),
+            &[
+                ("batch_rows", json!(1)),
+                ("rescue_dir", json!(temp.path().display().to_string())),
+            ],
+        ))?;
+        let mut sink = ClickHouseSink::new("ck".into(), spec)?;
+        AsyncRawDataSink::sink_str(&mut sink, r#"{"size":"bad"}"#).await?;
+        AsyncRawDataSink::sink_str(&mut sink, r#"{"size":1}"#).await?;
+        AsyncCtrl::stop(&mut sink).await?;
+
+        let files: Vec<_> = std::fs::read_dir(temp.path())?.collect::<Result<_, _>>()?;
+        assert_eq!(files.len(), 1);
+        let body = std::fs::read_to_string(files[0].path())?;
+        assert_eq!(body.lines().count(), 1);
+        let entry = RescueEntry::parse(body.lines().next().unwrap())?;
+        assert_eq!(entry.error(), Some("Code: 27. Cannot parse input"));
+        assert!(matches!(entry.payload(), RescuePayload::Raw { raw } if raw.contains("bad")));
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn transport_error_hands_rows_back_to_runtime() -> anyhow::Result<()> {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST);
+                then.status(503).body("service unavailable");
+            })
+            .await;
+        let spec = ClickHouseSinkSpec::from_params(&params(
+            &server.base_url(),
+            &[("batch_rows", json!(1))],
+        ))?;
+        let mut sink = ClickHouseSink::new("ck".into(), spec)?;
+        let res = AsyncRawDataSink::sink_str(&mut sink, r#"{"a":1}"#).await;
+        assert!(res.is_err());
+        assert!(sink.writer.lock().await.pending.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn partial_flush_failure_keeps_uncommitted_rows() -> anyhow::Result<()> {
+        let server = MockServer::start_async().await;
+        let ok = server
+            .mock_async(|when, then| {
+                when.method(POST).body_includes("\"a\":1");
+                then.status(200);
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST);
+                then.status(503).body("service unavailable");
+            })
+            .await;
+        let temp = tempfile::tempdir()?;
+        let spec = ClickHouseSinkSpec::from_params(&params(
+            &server.base_url(),
+            &[
+                ("batch_rows", json!(1)),
+                ("rescue_dir", json!(temp.path().display().to_string())),
+            ],
+        ))?;
+        let mut sink = ClickHouseSink::new("ck".into(), spec)?;
+        // 第一行已写入，交还整批会重复写入：未提交的行留在缓冲中
+        AsyncRawDataSink::sink_str_batch(&mut sink, vec![r#"{"a":1}"#, r#"{"a":2}"#]).await?;
+        ok.assert_hits_async(1).await;
+        {
+            let writer = sink.writer.lock().await;
+            assert_eq!(writer.pending.len(), 1);
+            assert_eq!(writer.pending[0].line, r#"{"a":2}"#);
+        }
+
+        // 退出时连接仍不可用：写入常规 rescue 文件，不带拒收原因
+        AsyncCtrl::stop(&mut sink).await?;
+        let files: Vec<_> = std::fs::read_dir(temp.path())?.collect::<Result<_, _>>()?;
+        assert_eq!(files.len(), 1);
+        let name = files[0].file_name().to_string_lossy().into_owned();
+        assert!(!name.contains("rejected") && name.ends_with(".dat"));
+        let body = std::fs::read_to_string(files[0].path())?;
+        let entry = RescueEntry::parse(body.lines().next().unwrap())?;
+        assert_eq!(entry.error(), None);
+        assert!(matches!(entry.payload(), RescuePayload::Raw { raw } if raw == r#"{"a":2}"#));
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn idle_rows_are_flushed_by_timer() -> anyhow::Result<()> {
+        let server = MockServer::start_async().await;
+        let m = server
+            .mock_async(|when, then| {
+                when.method(POST).body_includes("\"msg\":\"tail\"");
+                then.status(200);
+            })
+            .await;
+        let spec = ClickHouseSinkSpec::from_params(&params(
+            &server.base_url(),
+            &[("batch_rows", json!(100)), ("flush_interval_ms", json!(50))],
+        ))?;
+        let mut sink = ClickHouseSink::new("ck".into(), spec)?;
+        AsyncRawDataSink::sink_str(&mut sink, r#"{"msg":"tail"}"#).await?;
+        assert_eq!(sink.writer.lock().await.pending.len(), 1);
+        for _ in 0..40 {
+            if sink.writer.lock().await.pending.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        m.assert_async().await;
+        assert!(sink.writer.lock().await.pending.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn check_connection_blocking_inside_current_thread_runtime() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        // current_thread runtime 内调用不得 panic（block_in_place 仅支持多线程 runtime）
+        let res = rt.block_on(async {
+            ClickHouseFactory.check_connection_blocking(&params("http://127.0.0.1:1", &[]))
+        });
+        assert!(res.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn check_connection_requires_existing_table() -> anyhow::Result<()> {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .query_param("query", "EXISTS TABLE events");
+                then.status(200).body("0\n");
+            })
+            .await;
+        let err = ClickHouseFactory
+            .check_connection(&params(&server.base_url(), &[]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+        Ok(())
+    }
+}
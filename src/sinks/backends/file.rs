@@ -2,6 +2,7 @@ use crate::core::sinks::sync_sink::traits::SyncCtrl;
 use crate::core::sinks::sync_sink::{RecSyncSink, TrySendStatus};
 use crate::sinks::prelude::*;
 use crate::sinks::utils::buffer_monitor::BufferMonitor;
+use crate::sinks::utils::encoder::{FMT_NDJSON_BULK, LineEncoder};
 use crate::sinks::utils::formatter::FormatAdapter;
 use crate::sinks::{SinkEndpoint, SinkRecUnit};
 use crate::types::{AnyResult, Build1, SafeH};
@@ -50,6 +51,7 @@ pub fn create_watch_out(fmt: TextFmt) -> (SafeH<Cursor<Vec<u8>>>, SinkEndpoint)
 #[derive(Clone, Debug)]
 pub(crate) struct FileSinkSpec {
     fmt: TextFmt,
+    encoder: Option<LineEncoder>,
     base: String,
     file_name: String,
     sync: bool,
@@ -58,20 +60,27 @@ pub(crate) struct FileSinkSpec {
 impl FileSinkSpec {
     pub(crate) fn from_resolved(_kind: &str, spec: &ResolvedSinkSpec) -> AnyResult<Self> {
         if let Some(s) = spec.params.get("fmt").and_then(|v| v.as_str()) {
-            let ok = matches!(s, "json" | "csv" | "show" | "kv" | "raw" | "proto-text");
+            let ok = matches!(
+                s,
+                "json" | "csv" | "show" | "kv" | "raw" | "proto-text" | FMT_NDJSON_BULK
+            );
             if !ok {
                 anyhow::bail!(
-                    "invalid fmt: '{}'; allowed: json,csv,show,kv,raw,proto-text",
+                    "invalid fmt: '{}'; allowed: json,csv,show,kv,raw,proto-text,ndjson_bulk",
                     s
                 );
             }
         }
-        let fmt = spec
+        let fmt_name = spec
             .params
             .get("fmt")
             .and_then(|v| v.as_str())
-            .map(TextFmt::from)
-            .unwrap_or(TextFmt::Json);
+            .unwrap_or("json");
+        let encoder = LineEncoder::from_params(fmt_name, &spec.params)?;
+        let fmt = match fmt_name {
+            FMT_NDJSON_BULK => TextFmt::Json,
+            other => TextFmt::from(other),
+        };
         let base = spec
             .params
             .get("base")
@@ -91,6 +100,7 @@ impl FileSinkSpec {
             .unwrap_or(false);
         Ok(Self {
             fmt,
+            encoder,
             base,
             file_name,
             sync,
@@ -101,6 +111,10 @@ impl FileSinkSpec {
         self.fmt
     }
 
+    pub(crate) fn encoder(&self) -> Option<LineEncoder> {
+        self.encoder.clone()
+    }
+
     pub(crate) fn sync(&self) -> bool {
         self.sync
     }
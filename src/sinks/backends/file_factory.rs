@@ -1,5 +1,5 @@
 use super::file::FileSinkSpec;
-use crate::sinks::build_file_sink_with_sync;
+use crate::sinks::sink_build::build_file_sink_with_encoder;
 use async_trait::async_trait;
 use orion_error::ErrorOwe;
 use serde_json::json;
@@ -27,7 +27,7 @@ impl SinkFactory for FileFactory {
         let fmt = resolved.text_fmt();
         let sync = resolved.sync();
         let dummy = wp_conf::structure::SinkInstanceConf::null_new(spec.name.clone(), fmt, None);
-        let f = build_file_sink_with_sync(&dummy, &path, sync)
+        let f = build_file_sink_with_encoder(&dummy, &path, sync, resolved.encoder())
             .await
             .owe_res()?;
         Ok(wp_connector_api::SinkHandle::new(Box::new(f)))
@@ -109,6 +109,50 @@ impl SinkDefProvider for FileFactory {
             origin: Some("builtin:file".into()),
         });
 
+        let mut params = ParamMap::new();
+        params.insert("fmt".into(), json!("csv"));
+        params.insert("base".into(), json!("./data/out_dat"));
+        params.insert("file".into(), json!("default.csv"));
+        params.insert("sync".into(), json!(false));
+        params.insert("csv_delimiter".into(), json!(","));
+        params.insert("csv_header".into(), json!(true));
+        defs.push(ConnectorDef {
+            id: "file_csv_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "base".into(),
+                "file".into(),
+                "sync".into(),
+                "csv_delimiter".into(),
+                "csv_header".into(),
+            ],
+            default_params: params,
+            origin: Some("builtin:file".into()),
+        });
+
+        let mut params = ParamMap::new();
+        params.insert("fmt".into(), json!("ndjson_bulk"));
+        params.insert("base".into(), json!("./data/out_dat"));
+        params.insert("file".into(), json!("default.ndjson"));
+        params.insert("sync".into(), json!(false));
+        params.insert("bulk_index_field".into(), json!("_index"));
+        params.insert("bulk_id_field".into(), json!("_id"));
+        defs.push(ConnectorDef {
+            id: "file_ndjson_bulk_sink".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "base".into(),
+                "file".into(),
+                "sync".into(),
+                "bulk_index_field".into(),
+                "bulk_id_field".into(),
+            ],
+            default_params: params,
+            origin: Some("builtin:file".into()),
+        });
+
         defs
     }
 }
@@ -1,5 +1,7 @@
 pub mod blackhole;
 pub mod blackhole_factory;
+pub mod channel;
+pub mod clickhouse;
 pub mod file;
 pub mod file_factory;
 pub mod syslog;
@@ -1,26 +1,55 @@
 use async_trait::async_trait;
+use bytes::Bytes;
 use orion_conf::{EnvTomlLoad, ErrorOwe};
 use orion_variate::EnvDict;
 use serde_json::json;
+use std::collections::VecDeque;
 use std::str::FromStr;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
 use wp_conf::SafeDefault;
 use wp_conf::connectors::{ConnectorDef, ConnectorScope, SinkDefProvider, param_map_to_table};
 use wp_conf::structure::Protocol as ConfProtocol;
-use wp_conf::structure::SyslogSinkConf;
-use wp_connector_api::SinkResult;
+use wp_conf::structure::{SyslogFormat, SyslogFraming, SyslogSinkConf};
 use wp_connector_api::{
     AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, SinkBuildCtx, SinkFactory, SinkHandle,
     SinkSpec as ResolvedSinkSpec,
 };
+use wp_connector_api::{SinkError, SinkReason, SinkResult};
 use wp_data_fmt::RecordFormatter; // for fmt_record
 // no extra orion-error/conf helpers needed after route-builder removal
 
 type AnyResult<T> = anyhow::Result<T>;
-use crate::protocol::syslog::{EmitMessage, SyslogEncoder};
+use crate::protocol::syslog::{
+    EmitMessage, SdElement, SyslogEncoder, frame_octet_counted, is_sd_name,
+};
+use crate::sinks::net::tls::{TlsClientConf, TlsDialer};
 use crate::sinks::net::transport::{
     BackoffMode, NetSendPolicy, NetWriter, Transport, net_backoff_adaptive,
 };
 
+const DEFAULT_SD_ID: &str = "wp@32473";
+// 重连退避上限
+const RECONNECT_BACKOFF_MAX_MS: u64 = 10_000;
+// TLS 链路每写入若干帧 flush 一次；flush 失败时这些帧会被重新排队
+const TLS_FLUSH_EVERY_FRAMES: usize = 256;
+// 透传到 SyslogSinkConf 的可选参数
+const PASSTHROUGH_KEYS: [&str; 11] = [
+    "format",
+    "framing",
+    "sd_id",
+    "sd_fields",
+    "tls",
+    "tls_ca_file",
+    "tls_cert_file",
+    "tls_key_file",
+    "tls_server_name",
+    "reconnect_attempts",
+    "reconnect_backoff_ms",
+];
+
 fn syslog_conf_from_spec(spec: &ResolvedSinkSpec) -> AnyResult<SyslogSinkConf> {
     let addr = spec
         .params
@@ -43,6 +72,34 @@ fn syslog_conf_from_spec(spec: &ResolvedSinkSpec) -> AnyResult<SyslogSinkConf> {
     {
         anyhow::bail!("syslog.app_name must be a string");
     }
+    if let Some(v) = spec.params.get("format") {
+        let f = v.as_str().unwrap_or_default();
+        if f != "rfc3164" && f != "rfc5424" {
+            anyhow::bail!("syslog.format must be 'rfc3164' or 'rfc5424'");
+        }
+    }
+    if let Some(v) = spec.params.get("framing") {
+        let f = v.as_str().unwrap_or_default();
+        if f != "lf" && f != "octet" {
+            anyhow::bail!("syslog.framing must be 'lf' or 'octet'");
+        }
+    }
+    if let Some(v) = spec.params.get("sd_fields") {
+        let names = v
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("syslog.sd_fields must be an array of strings"))?;
+        for name in names {
+            match name.as_str() {
+                Some(n) if is_sd_name(n) => {}
+                _ => anyhow::bail!("syslog.sd_fields contains invalid SD-PARAM name: {}", name),
+            }
+        }
+    }
+    if let Some(v) = spec.params.get("sd_id")
+        && !v.as_str().is_some_and(is_sd_name)
+    {
+        anyhow::bail!("syslog.sd_id must be a valid SD-ID");
+    }
     let port = spec
         .params
         .get("port")
@@ -61,20 +118,195 @@ fn syslog_conf_from_spec(spec: &ResolvedSinkSpec) -> AnyResult<SyslogSinkConf> {
     if let Some(app) = spec.params.get("app_name").and_then(|v| v.as_str()) {
         params.insert("app_name".to_string(), json!(app));
     }
+    for key in PASSTHROUGH_KEYS {
+        if let Some(v) = spec.params.get(key) {
+            params.insert(key.to_string(), v.clone());
+        }
+    }
     let toml_str = toml::to_string(&param_map_to_table(&params))?;
     let dict = EnvDict::safe_default();
     let conf: SyslogSinkConf = SyslogSinkConf::env_parse_toml(&toml_str, &dict)?;
+    if conf.tls && !matches!(conf.protocol, ConfProtocol::TCP) {
+        anyhow::bail!("syslog.tls requires protocol 'tcp'");
+    }
+    if conf.tls_cert_file.is_some() != conf.tls_key_file.is_some() {
+        anyhow::bail!("syslog.tls_cert_file and syslog.tls_key_file must be set together");
+    }
     Ok(conf)
 }
 
+/// 报文编码选项（格式、分帧、结构化数据字段）
+#[derive(Debug, Clone)]
+struct SyslogEmitOpts {
+    format: SyslogFormat,
+    framing: SyslogFraming,
+    sd_id: String,
+    sd_fields: Vec<String>,
+}
+
+impl Default for SyslogEmitOpts {
+    fn default() -> Self {
+        Self {
+            format: SyslogFormat::Rfc3164,
+            framing: SyslogFraming::Lf,
+            sd_id: DEFAULT_SD_ID.to_string(),
+            sd_fields: Vec::new(),
+        }
+    }
+}
+
+impl SyslogEmitOpts {
+    fn from_conf(conf: &SyslogSinkConf) -> Self {
+        Self {
+            format: conf.format,
+            framing: conf.framing,
+            sd_id: conf
+                .sd_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SD_ID.to_string()),
+            sd_fields: conf.sd_fields.clone(),
+        }
+    }
+}
+
+/// TLS 链路：断线后按指数退避重连，未写出的帧重新排队发送。
+struct TlsLink {
+    addr: String,
+    dialer: TlsDialer,
+    stream: Option<TlsStream<TcpStream>>,
+    reconnect_attempts: usize,
+    reconnect_backoff: Duration,
+}
+
+impl TlsLink {
+    async fn connect(
+        addr: &str,
+        dialer: TlsDialer,
+        reconnect_attempts: usize,
+        reconnect_backoff: Duration,
+    ) -> SinkResult<Self> {
+        let mut link = Self {
+            addr: addr.to_string(),
+            dialer,
+            stream: None,
+            reconnect_attempts: reconnect_attempts.max(1),
+            reconnect_backoff,
+        };
+        link.connect_with_backoff().await?;
+        Ok(link)
+    }
+
+    async fn connect_with_backoff(&mut self) -> SinkResult<()> {
+        let mut delay = self.reconnect_backoff;
+        let max_delay = Duration::from_millis(RECONNECT_BACKOFF_MAX_MS);
+        let mut last_err = String::new();
+        for attempt in 1..=self.reconnect_attempts {
+            match self.dialer.connect(&self.addr).await {
+                Ok(stream) => {
+                    log::info!("syslog tls sink connected: target={}", self.addr);
+                    self.stream = Some(stream);
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn_data!(
+                        "syslog tls connect {} failed (attempt {}/{}): {}",
+                        self.addr,
+                        attempt,
+                        self.reconnect_attempts,
+                        e
+                    );
+                    last_err = e.to_string();
+                    if attempt < self.reconnect_attempts {
+                        tokio::time::sleep(delay).await;
+                        delay = (delay * 2).min(max_delay);
+                    }
+                }
+            }
+        }
+        Err(SinkError::from(SinkReason::Sink(format!(
+            "syslog tls connect {} failed: {}",
+            self.addr, last_err
+        ))))
+    }
+
+    /// 发送一批已分帧的报文。连接中断时，尚未确认写出的帧回到队首，重连后继续发送；
+    /// 重连次数耗尽后返回错误，由运行时走 rescue。
+    async fn send(&mut self, frames: Vec<Bytes>) -> SinkResult<()> {
+        let mut queue: VecDeque<Bytes> = frames.into();
+        let mut unflushed: Vec<Bytes> = Vec::new();
+        let mut failures = 0usize;
+        loop {
+            if self.stream.is_none() {
+                self.connect_with_backoff().await?;
+            }
+            let Some(stream) = self.stream.as_mut() else {
+                continue;
+            };
+            match write_frames(stream, &mut queue, &mut unflushed).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    self.stream = None;
+                    for frame in unflushed.drain(..).rev() {
+                        queue.push_front(frame);
+                    }
+                    failures += 1;
+                    warn_data!(
+                        "syslog tls send to {} failed: {}; requeue {} frames",
+                        self.addr,
+                        e,
+                        queue.len()
+                    );
+                    if failures > self.reconnect_attempts {
+                        return Err(SinkError::from(SinkReason::Sink(format!(
+                            "syslog tls send error: {}",
+                            e
+                        ))));
+                    }
+                }
+            }
+        }
+    }
+
+    async fn shutdown(&mut self) {
+        if let Some(mut stream) = self.stream.take() {
+            let _ = stream.shutdown().await;
+        }
+    }
+}
+
+async fn write_frames(
+    stream: &mut TlsStream<TcpStream>,
+    queue: &mut VecDeque<Bytes>,
+    unflushed: &mut Vec<Bytes>,
+) -> std::io::Result<()> {
+    while let Some(frame) = queue.pop_front() {
+        let res = stream.write_all(&frame).await;
+        unflushed.push(frame);
+        res?;
+        if unflushed.len() >= TLS_FLUSH_EVERY_FRAMES {
+            stream.flush().await?;
+            unflushed.clear();
+        }
+    }
+    stream.flush().await?;
+    unflushed.clear();
+    Ok(())
+}
+
+enum SyslogLink {
+    Net(NetWriter),
+    Tls(TlsLink),
+}
+
 pub struct SyslogSink {
-    // Underlying transport writer (UDP/TCP)
-    writer: NetWriter,
+    // Underlying link (UDP/TCP writer or TLS stream)
+    link: SyslogLink,
     // Simple counter to emit first-send debug without spamming logs
     sent_cnt: u64,
     encoder: SyslogEncoder,
     hostname: String,
     app_name: String,
+    opts: SyslogEmitOpts,
 }
 
 impl SyslogSink {
@@ -94,7 +326,7 @@ impl SyslogSink {
         } else {
             log::info!("syslog udp sink connected: target={}", addr);
         }
-        Ok(Self::with_writer(writer, app_name))
+        Ok(Self::with_link(SyslogLink::Net(writer), app_name))
     }
     async fn tcp(addr: &str, app_name: Option<String>, rate_limit_rps: usize) -> AnyResult<Self> {
         // Align to TcpSink: enable backpressure when unlimited
@@ -113,7 +345,17 @@ impl SyslogSink {
         )
         .await?;
         log::info!("syslog tcp sink connected: target={}", addr);
-        Ok(Self::with_writer(writer, app_name))
+        Ok(Self::with_link(SyslogLink::Net(writer), app_name))
+    }
+    async fn tls(
+        addr: &str,
+        app_name: Option<String>,
+        dialer: TlsDialer,
+        reconnect_attempts: usize,
+        reconnect_backoff: Duration,
+    ) -> SinkResult<Self> {
+        let link = TlsLink::connect(addr, dialer, reconnect_attempts, reconnect_backoff).await?;
+        Ok(Self::with_link(SyslogLink::Tls(link), app_name))
     }
 
     fn current_process_name() -> String {
@@ -124,34 +366,155 @@ impl SyslogSink {
             .unwrap_or_else(|| "wp-engine".to_string())
     }
 
-    fn with_writer(writer: NetWriter, app_name: Option<String>) -> Self {
+    fn with_link(link: SyslogLink, app_name: Option<String>) -> Self {
         let hostname = hostname::get()
             .ok()
             .and_then(|h| h.into_string().ok())
             .unwrap_or_else(|| "localhost".to_string());
         Self {
-            writer,
+            link,
             sent_cnt: 0,
             encoder: SyslogEncoder::new(),
             hostname,
             app_name: app_name.unwrap_or_else(Self::current_process_name),
+            opts: SyslogEmitOpts::default(),
         }
     }
+
+    fn with_opts(mut self, opts: SyslogEmitOpts) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    fn transport_tag(&self) -> &'static str {
+        match &self.link {
+            SyslogLink::Net(w) => match w.transport {
+                Transport::Udp(_) => "udp",
+                Transport::Tcp(_) => "tcp",
+                #[cfg(test)]
+                Transport::Null => "null",
+            },
+            SyslogLink::Tls(_) => "tls",
+        }
+    }
+
+    fn is_stream(&self) -> bool {
+        match &self.link {
+            SyslogLink::Net(w) => matches!(w.transport, Transport::Tcp(_)),
+            SyslogLink::Tls(_) => true,
+        }
+    }
+
+    /// 从记录中选取 `sd_fields` 组成 RFC5424 SD-ELEMENT；无可用字段时返回 None
+    fn structured_data(&self, record: &wp_model_core::model::DataRecord) -> Option<SdElement> {
+        if self.opts.format != SyslogFormat::Rfc5424 || self.opts.sd_fields.is_empty() {
+            return None;
+        }
+        let mut element = SdElement::new(self.opts.sd_id.as_str());
+        for name in &self.opts.sd_fields {
+            if let Some(field) = record.field(name) {
+                let value = match field.get_value() {
+                    wp_model_core::model::Value::Chars(v) => v.to_string(),
+                    other => other.to_string(),
+                };
+                element.params.push((name.clone(), value));
+            }
+        }
+        (!element.params.is_empty()).then_some(element)
+    }
+
+    /// 编码单条报文并按传输方式分帧
+    fn encode(&self, body: &str, structured_data: Option<SdElement>) -> Bytes {
+        let stream = self.is_stream();
+        let octet = stream && self.opts.framing == SyslogFraming::Octet;
+        let mut emit = EmitMessage::new(body);
+        emit.priority = 13;
+        emit.hostname = Some(self.hostname.as_str());
+        emit.app_name = Some(self.app_name.as_str());
+        emit.append_newline = stream && !octet;
+        emit.structured_data = structured_data.map(|sd| vec![sd]);
+        let msg = match self.opts.format {
+            SyslogFormat::Rfc3164 => self.encoder.encode_rfc3164(&emit),
+            SyslogFormat::Rfc5424 => self.encoder.encode_rfc5424(&emit),
+        };
+        if octet {
+            frame_octet_counted(&msg)
+        } else {
+            msg
+        }
+    }
+
+    fn encode_record(&self, record: &wp_model_core::model::DataRecord) -> Bytes {
+        let raw = wp_data_fmt::Raw::new().fmt_record(record);
+        let sd = self.structured_data(record);
+        self.encode(raw.as_str(), sd)
+    }
+
+    async fn send_frames(&mut self, frames: Vec<Bytes>) -> SinkResult<()> {
+        if frames.is_empty() {
+            return Ok(());
+        }
+        let tag = self.transport_tag();
+        if self.sent_cnt == 0 {
+            let payload = frames[0].as_ref();
+            log::info!(
+                "syslog {} sink first-send: msg_len={} preview='{}'",
+                tag,
+                payload.len(),
+                String::from_utf8_lossy(&payload[..payload.len().min(64)])
+            );
+        }
+        trace_data!(
+            "syslog {} sink send seq={} records={} bytes={}",
+            tag,
+            self.sent_cnt + 1,
+            frames.len(),
+            frames.iter().map(|f| f.len()).sum::<usize>()
+        );
+        match &mut self.link {
+            SyslogLink::Net(writer) => {
+                // UDP：一条报文一个数据报；TCP：合并为一次写入
+                if !matches!(writer.transport, Transport::Tcp(_)) {
+                    for frame in &frames {
+                        writer.write(frame.as_ref()).await?;
+                    }
+                } else {
+                    let mut buf: Vec<u8> = Vec::with_capacity(frames.iter().map(|f| f.len()).sum());
+                    for frame in &frames {
+                        buf.extend_from_slice(frame.as_ref());
+                    }
+                    writer.write(&buf).await?;
+                }
+            }
+            SyslogLink::Tls(link) => link.send(frames).await?,
+        }
+        self.sent_cnt = self.sent_cnt.saturating_add(1);
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl AsyncCtrl for SyslogSink {
     async fn stop(&mut self) -> SinkResult<()> {
-        // For TCP, try graceful shutdown and drain
-        if let Transport::Tcp(_) = &self.writer.transport {
-            self.writer.shutdown().await?;
-            self.writer
-                .drain_until_empty(std::time::Duration::from_secs(10))
-                .await;
+        match &mut self.link {
+            // For TCP, try graceful shutdown and drain
+            SyslogLink::Net(writer) => {
+                if let Transport::Tcp(_) = &writer.transport {
+                    writer.shutdown().await?;
+                    writer
+                        .drain_until_empty(std::time::Duration::from_secs(10))
+                        .await;
+                }
+            }
+            SyslogLink::Tls(link) => link.shutdown().await,
         }
         Ok(())
     }
     async fn reconnect(&mut self) -> SinkResult<()> {
+        if let SyslogLink::Tls(link) = &mut self.link {
+            link.shutdown().await;
+            link.connect_with_backoff().await?;
+        }
         Ok(())
     }
 }
@@ -159,97 +522,32 @@ impl AsyncCtrl for SyslogSink {
 #[async_trait]
 impl AsyncRecordSink for SyslogSink {
     async fn sink_record(&mut self, data: &wp_model_core::model::DataRecord) -> SinkResult<()> {
-        // Format record to raw text then reuse raw path
-        let raw = wp_data_fmt::Raw::new().fmt_record(data);
-        AsyncRawDataSink::sink_str(self, raw.as_str()).await
+        let frame = self.encode_record(data);
+        self.send_frames(vec![frame]).await
     }
 
     async fn sink_records(
         &mut self,
         data: Vec<std::sync::Arc<wp_model_core::model::DataRecord>>,
     ) -> SinkResult<()> {
-        for record in data {
-            self.sink_record(&record).await?;
-        }
-        Ok(())
+        let frames = data.iter().map(|r| self.encode_record(r)).collect();
+        self.send_frames(frames).await
     }
 }
 
 #[async_trait]
 impl AsyncRawDataSink for SyslogSink {
     async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
-        // Format as RFC3164 syslog message
-        let mut emit = EmitMessage::new(data);
-        emit.priority = 13;
-        emit.hostname = Some(self.hostname.as_str());
-        emit.app_name = Some(self.app_name.as_str());
-        emit.append_newline = matches!(self.writer.transport, Transport::Tcp(_));
-
-        let syslog_msg = self.encoder.encode_rfc3164(&emit);
-        let payload = syslog_msg.as_ref();
-        if self.sent_cnt == 0 {
-            let tag = match self.writer.transport {
-                Transport::Udp(_) => "udp",
-                Transport::Tcp(_) => "tcp",
-                #[cfg(test)]
-                Transport::Null => "null",
-            };
-            log::info!(
-                "syslog {} sink first-send: msg_len={} preview='{}'",
-                tag,
-                payload.len(),
-                String::from_utf8_lossy(&payload[..payload.len().min(64)])
-            );
-        }
-        trace_data!(
-            "syslog {} sink send seq={} bytes={}",
-            match self.writer.transport {
-                Transport::Udp(_) => "udp",
-                Transport::Tcp(_) => "tcp",
-                #[cfg(test)]
-                Transport::Null => "null",
-            },
-            self.sent_cnt + 1,
-            payload.len()
-        );
-        self.writer.write(payload).await?;
-        self.sent_cnt = self.sent_cnt.saturating_add(1);
-        Ok(())
+        let frame = self.encode(data, None);
+        self.send_frames(vec![frame]).await
     }
     async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
         Ok(())
     }
 
     async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
-        if data.is_empty() {
-            return Ok(());
-        }
-        let is_tcp = matches!(self.writer.transport, Transport::Tcp(_));
-        let mut total = 0usize;
-        for s in &data {
-            total = total.saturating_add(s.len() + 64);
-        }
-        let mut buf: Vec<u8> = Vec::with_capacity(total);
-        for str_data in data.iter() {
-            let mut emit = EmitMessage::new(str_data);
-            emit.priority = 13;
-            emit.hostname = Some(self.hostname.as_str());
-            emit.app_name = Some(self.app_name.as_str());
-            emit.append_newline = is_tcp;
-            let msg = self.encoder.encode_rfc3164(&emit);
-            buf.extend_from_slice(msg.as_ref());
-        }
-        let record_cnt = data.len();
-        trace_data!(
-            "syslog {} sink send-batch seq={} records={} bytes={}",
-            if is_tcp { "tcp" } else { "udp" },
-            self.sent_cnt + 1,
-            record_cnt,
-            buf.len()
-        );
-        self.writer.write(&buf).await?;
-        self.sent_cnt = self.sent_cnt.saturating_add(1);
-        Ok(())
+        let frames = data.iter().map(|s| self.encode(s, None)).collect();
+        self.send_frames(frames).await
     }
 
     async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
@@ -274,7 +572,11 @@ impl SinkFactory for SyslogFactory {
         "syslog"
     }
     fn validate_spec(&self, spec: &ResolvedSinkSpec) -> SinkResult<()> {
-        syslog_conf_from_spec(spec).owe_conf()?;
+        let conf = syslog_conf_from_spec(spec).owe_conf()?;
+        if conf.tls {
+            // 预加载证书/私钥，尽早暴露路径或格式错误
+            TlsDialer::new(&tls_client_conf(&conf)).owe_conf()?;
+        }
         Ok(())
     }
     async fn build(&self, spec: &ResolvedSinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
@@ -290,16 +592,40 @@ impl SinkFactory for SyslogFactory {
             ConfProtocol::UDP => SyslogSink::udp(target.as_str(), Some(app_name.clone()))
                 .await
                 .owe_res()?,
+            ConfProtocol::TCP if conf.tls => {
+                let dialer = TlsDialer::new(&tls_client_conf(&conf)).owe_conf()?;
+                SyslogSink::tls(
+                    target.as_str(),
+                    Some(app_name.clone()),
+                    dialer,
+                    conf.reconnect_attempts,
+                    Duration::from_millis(conf.reconnect_backoff_ms),
+                )
+                .await?
+            }
             ConfProtocol::TCP => {
                 SyslogSink::tcp(target.as_str(), Some(app_name.clone()), _ctx.rate_limit_rps)
                     .await
                     .owe_res()?
             }
         };
+        let runtime = runtime.with_opts(SyslogEmitOpts::from_conf(&conf));
         Ok(SinkHandle::new(Box::new(runtime)))
     }
 }
 
+fn tls_client_conf(conf: &SyslogSinkConf) -> TlsClientConf {
+    TlsClientConf {
+        ca_file: conf.tls_ca_file.clone(),
+        cert_file: conf.tls_cert_file.clone(),
+        key_file: conf.tls_key_file.clone(),
+        server_name: conf
+            .tls_server_name
+            .clone()
+            .unwrap_or_else(|| conf.host().to_string()),
+    }
+}
+
 impl SinkDefProvider for SyslogFactory {
     fn sink_def(&self) -> ConnectorDef {
         let mut params = wp_connector_api::ParamMap::new();
@@ -309,6 +635,9 @@ impl SinkDefProvider for SyslogFactory {
         params.insert("strip_header".into(), json!(true));
         params.insert("attach_meta_tags".into(), json!(true));
         params.insert("tcp_recv_bytes".into(), json!(256000));
+        params.insert("format".into(), json!("rfc3164"));
+        params.insert("framing".into(), json!("lf"));
+        params.insert("tls".into(), json!(false));
         ConnectorDef {
             id: "syslog_sink".into(),
             kind: self.kind().into(),
@@ -318,6 +647,17 @@ impl SinkDefProvider for SyslogFactory {
                 "port".into(),
                 "protocol".into(),
                 "app_name".into(),
+                "format".into(),
+                "framing".into(),
+                "sd_id".into(),
+                "sd_fields".into(),
+                "tls".into(),
+                "tls_ca_file".into(),
+                "tls_cert_file".into(),
+                "tls_key_file".into(),
+                "tls_server_name".into(),
+                "reconnect_attempts".into(),
+                "reconnect_backoff_ms".into(),
             ],
             default_params: params,
             origin: Some("builtin:syslog_sink".into()),
@@ -328,7 +668,244 @@ impl SinkDefProvider for SyslogFactory {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rustls::RootCertStore;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+    use rustls::server::WebPkiClientVerifier;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tokio::io::AsyncReadExt;
     use tokio::net::TcpListener;
+    use tokio_rustls::TlsAcceptor;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    struct TestCert {
+        der: CertificateDer<'static>,
+        key_der: Vec<u8>,
+        cert_path: String,
+        key_path: String,
+    }
+
+    impl TestCert {
+        fn generate(dir: &Path, name: &str) -> Self {
+            let ck = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+                .expect("generate cert");
+            let cert_path = dir.join(format!("{}.pem", name));
+            let key_path = dir.join(format!("{}.key", name));
+            std::fs::write(&cert_path, ck.cert.pem()).expect("write cert");
+            std::fs::write(&key_path, ck.key_pair.serialize_pem()).expect("write key");
+            Self {
+                der: ck.cert.der().clone(),
+                key_der: ck.key_pair.serialize_der(),
+                cert_path: cert_path.display().to_string(),
+                key_path: key_path.display().to_string(),
+            }
+        }
+
+        fn key(&self) -> PrivateKeyDer<'static> {
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(self.key_der.clone()))
+        }
+    }
+
+    fn acceptor(server: &TestCert, client_ca: Option<&TestCert>) -> TlsAcceptor {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .expect("protocol versions");
+        let builder = match client_ca {
+            Some(ca) => {
+                let mut roots = RootCertStore::empty();
+                roots.add(ca.der.clone()).expect("client ca");
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()
+                        .expect("client verifier");
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(vec![server.der.clone()], server.key())
+            .expect("server cert");
+        TlsAcceptor::from(Arc::new(config))
+    }
+
+    fn dialer(server: &TestCert, client: Option<&TestCert>) -> TlsDialer {
+        TlsDialer::new(&TlsClientConf {
+            ca_file: Some(server.cert_path.clone()),
+            cert_file: client.map(|c| c.cert_path.clone()),
+            key_file: client.map(|c| c.key_path.clone()),
+            server_name: "localhost".into(),
+        })
+        .expect("build dialer")
+    }
+
+    fn octet_opts(sd_fields: &[&str]) -> SyslogEmitOpts {
+        SyslogEmitOpts {
+            format: SyslogFormat::Rfc5424,
+            framing: SyslogFraming::Octet,
+            sd_id: DEFAULT_SD_ID.to_string(),
+            sd_fields: sd_fields.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// 按 octet-counting 拆帧；`strict` 为 false 时忽略末尾不完整的帧
+    fn split_octet_frames(mut buf: &[u8], strict: bool) -> Vec<String> {
+        let mut frames = Vec::new();
+        while !buf.is_empty() {
+            let Some(sp) = buf.iter().position(|b| *b == b' ') else {
+                assert!(!strict, "missing MSG-LEN separator");
+                break;
+            };
+            let len: usize = std::str::from_utf8(&buf[..sp])
+                .expect("ascii len")
+                .parse()
+                .expect("MSG-LEN digits");
+            let rest = &buf[sp + 1..];
+            if rest.len() < len {
+                assert!(!strict, "truncated frame");
+                break;
+            }
+            frames.push(String::from_utf8(rest[..len].to_vec()).expect("utf8 frame"));
+            buf = &rest[len..];
+        }
+        frames
+    }
+
+    async fn bind_local() -> Option<TcpListener> {
+        match TcpListener::bind("127.0.0.1:0").await {
+            Ok(lst) => Some(lst),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => None,
+            Err(e) => panic!("bind test listener: {}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn syslog_sink_tls_octet_counted_rfc5424() {
+        let Some(listener) = bind_local().await else {
+            return;
+        };
+        let addr = listener.local_addr().expect("addr");
+        let dir = tempfile::tempdir().expect("tempdir");
+        let server = TestCert::generate(dir.path(), "server");
+        let client = TestCert::generate(dir.path(), "client");
+        let acceptor = acceptor(&server, Some(&client));
+
+        let accept_task = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.expect("accept");
+            let mut tls = acceptor.accept(tcp).await.expect("tls accept");
+            let mut buf = Vec::new();
+            tls.read_to_end(&mut buf).await.expect("read");
+            buf
+        });
+
+        let mut sink = SyslogSink::tls(
+            addr.to_string().as_str(),
+            Some("wpgen".into()),
+            dialer(&server, Some(&client)),
+            3,
+            Duration::from_millis(10),
+        )
+        .await
+        .expect("connect tls sink")
+        .with_opts(octet_opts(&["src_ip", "missing"]));
+
+        let mut rec = DataRecord::default();
+        rec.append(DataField::from_chars("src_ip", "10.0.0.1"));
+        rec.append(DataField::from_chars("msg", "login"));
+        let rec = std::sync::Arc::new(rec);
+        sink.sink_records(vec![rec.clone(), rec])
+            .await
+            .expect("sink records");
+        sink.sink_str("raw body").await.expect("sink str");
+        sink.stop().await.expect("stop");
+
+        let bytes = accept_task.await.expect("join");
+        let frames = split_octet_frames(&bytes, true);
+        assert_eq!(frames.len(), 3, "frames: {:?}", frames);
+        for frame in &frames[..2] {
+            assert!(frame.starts_with("<13>1 "), "not rfc5424: {}", frame);
+            assert!(
+                frame.contains(r#" wpgen - - [wp@32473 src_ip="10.0.0.1"] "#),
+                "structured data missing: {}",
+                frame
+            );
+            assert!(!frame.ends_with('\n'), "octet frame must not end with LF");
+        }
+        assert!(
+            frames[2].ends_with(" wpgen - - - raw body"),
+            "{}",
+            frames[2]
+        );
+    }
+
+    #[tokio::test]
+    async fn syslog_sink_tls_requeues_after_connection_loss() {
+        let Some(listener) = bind_local().await else {
+            return;
+        };
+        let addr = listener.local_addr().expect("addr");
+        let dir = tempfile::tempdir().expect("tempdir");
+        let server = TestCert::generate(dir.path(), "server");
+        let acceptor = acceptor(&server, None);
+
+        let accept_task = tokio::spawn(async move {
+            // 第一条连接：只读少量数据后关闭；未读数据使内核回送 RST，客户端写入失败
+            let (tcp, _) = listener.accept().await.expect("accept first");
+            let mut tls = acceptor.accept(tcp).await.expect("tls accept first");
+            let mut head = vec![0u8; 64 * 1024];
+            tls.read_exact(&mut head).await.expect("read head");
+            drop(tls);
+            // 第二条连接：重连后收取重新排队的报文
+            let (tcp, _) = listener.accept().await.expect("accept second");
+            let mut tls = acceptor.accept(tcp).await.expect("tls accept second");
+            let mut buf = Vec::new();
+            tls.read_to_end(&mut buf).await.expect("read");
+            buf
+        });
+
+        let mut sink = SyslogSink::tls(
+            addr.to_string().as_str(),
+            Some("wpgen".into()),
+            dialer(&server, None),
+            5,
+            Duration::from_millis(10),
+        )
+        .await
+        .expect("connect tls sink")
+        .with_opts(octet_opts(&[]));
+
+        let total = 40_000usize;
+        let padding = "x".repeat(400);
+        let bodies: Vec<String> = (0..total)
+            .map(|i| format!("seq={:06} {}", i, padding))
+            .collect();
+        let send = sink.sink_str_batch(bodies.iter().map(|s| s.as_str()).collect());
+        tokio::time::timeout(Duration::from_secs(30), send)
+            .await
+            .expect("send batch timeout")
+            .expect("send batch");
+        sink.stop().await.expect("stop");
+
+        let bytes = tokio::time::timeout(Duration::from_secs(30), accept_task)
+            .await
+            .expect("server timeout")
+            .expect("join");
+        let frames = split_octet_frames(&bytes, true);
+        assert!(!frames.is_empty(), "no frames after reconnect");
+        let seqs: Vec<usize> = frames
+            .iter()
+            .map(|f| {
+                let pos = f.find("seq=").expect("seq marker") + 4;
+                f[pos..pos + 6].parse().expect("seq digits")
+            })
+            .collect();
+        assert!(seqs[0] > 0, "requeue should resume mid-batch");
+        assert_eq!(*seqs.last().unwrap(), total - 1, "tail of batch lost");
+        assert!(
+            seqs.windows(2).all(|w| w[1] == w[0] + 1),
+            "requeued frames must stay in order"
+        );
+    }
 
     #[tokio::test]
     async fn syslog_sink_tcp_emits_rfc3164_message() {
@@ -346,7 +923,6 @@ mod tests {
         let accept_task = tokio::spawn(async move {
             let (mut stream, _) = listener.accept().await.expect("accept");
             let mut buf = Vec::new();
-            use tokio::io::AsyncReadExt;
             stream.read_to_end(&mut buf).await.expect("read");
             buf
         });
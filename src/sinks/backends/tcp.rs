@@ -268,11 +268,20 @@ impl SinkDefProvider for TcpFactory {
         params.insert("addr".into(), json!("127.0.0.1"));
         params.insert("port".into(), json!(9000));
         params.insert("framing".into(), json!("line"));
+        // 发送队列感知的退让开关，仅校验为布尔值；实际策略由限速目标决定
+        params.insert("max_backoff".into(), json!(false));
+        params.insert("sendq_backpressure".into(), json!(false));
         ConnectorDef {
             id: "tcp_sink".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Sink,
-            allow_override: vec!["addr".into(), "port".into(), "framing".into()],
+            allow_override: vec![
+                "addr".into(),
+                "port".into(),
+                "framing".into(),
+                "max_backoff".into(),
+                "sendq_backpressure".into(),
+            ],
             default_params: params,
             origin: Some("builtin:tcp_sink".into()),
         }
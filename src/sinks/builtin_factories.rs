@@ -1,5 +1,7 @@
 use crate::sinks::backends::blackhole::BlackHoleSink;
 use crate::sinks::backends::blackhole_factory::BlackHoleFactory;
+use crate::sinks::backends::channel::ChannelFactory;
+use crate::sinks::backends::clickhouse::ClickHouseFactory;
 use crate::sinks::backends::file_factory::FileFactory;
 use crate::sinks::backends::syslog::SyslogFactory;
 use crate::sinks::backends::tcp::TcpFactory;
@@ -8,6 +10,8 @@ use wp_conf::connectors::{ConnectorDef, SinkDefProvider};
 
 pub fn register_builtin_factories() {
     crate::connectors::registry::register_sink_factory(BlackHoleFactory);
+    crate::connectors::registry::register_sink_factory(ChannelFactory);
+    crate::connectors::registry::register_sink_factory(ClickHouseFactory);
     crate::connectors::registry::register_sink_factory(FileFactory);
     crate::connectors::registry::register_sink_factory(SyslogFactory);
     crate::connectors::registry::register_sink_factory(TcpFactory);
@@ -17,6 +21,8 @@ pub fn register_builtin_factories() {
 pub fn builtin_sink_defs() -> Vec<ConnectorDef> {
     let mut defs = Vec::new();
     defs.append(&mut BlackHoleFactory.sink_defs());
+    defs.append(&mut ChannelFactory.sink_defs());
+    defs.append(&mut ClickHouseFactory.sink_defs());
     defs.append(&mut FileFactory.sink_defs());
     defs.append(&mut SyslogFactory.sink_defs());
     defs.append(&mut TcpFactory.sink_defs());
@@ -1,3 +1,6 @@
+pub mod schema;
+pub mod shaped;
 pub mod stub;
 pub mod sync_pipeline;
+pub mod target_route;
 pub mod test_proxy;
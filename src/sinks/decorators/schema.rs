@@ -0,0 +1,422 @@
+//! 路由级字段类型约束：按 `[sink_group.schema]` 在写出前检查并转换字段类型。
+//!
+//! 只处理 schema 列出的字段；可安全转换的情形（数字字符串 → digit、digit → float、
+//! 标量 → chars 等）在 `coerce`/`drop` 模式下直接转换，无法转换时按 `on_conflict` 处理：
+//! `warn` 原样下发，`coerce` 移除该字段，`drop` 丢弃整条记录并计入 sink 的 dropped 计数。
+//! 记录无需改动时原样下发，不复制字段。
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use wp_conf::structure::{RouteSchemaConf, SchemaConflict};
+use wp_connector_api::SinkResult;
+use wp_model_core::model::{DataField, DataRecord, DataType, Value};
+
+use crate::sinks::prelude::*;
+use crate::stat::prometheus::Counter;
+
+/// 单个字段对照 schema 的结果
+enum Checked {
+    Match,
+    Coerced(DataField),
+    Conflict,
+}
+
+/// 单个字段的处理方式
+enum Edit {
+    Keep,
+    Replace(DataField),
+    Remove,
+}
+
+/// 由 [`RouteSchemaConf`] 编译得到的字段类型表
+#[derive(Debug, Clone)]
+pub struct FieldSchema {
+    types: BTreeMap<String, DataType>,
+    on_conflict: SchemaConflict,
+}
+
+impl FieldSchema {
+    /// 类型名已在装载时校验，这里出错时退化为空表
+    pub fn from_conf(conf: &RouteSchemaConf) -> Self {
+        Self {
+            types: conf.field_types().unwrap_or_default(),
+            on_conflict: conf.on_conflict,
+        }
+    }
+
+    /// 返回处理后的记录与冲突字段名；`None` 表示整条记录丢弃。
+    /// 没有字段被转换或移除时借用原记录，首个改动出现后才复制字段
+    pub fn apply<'a>(&self, record: &'a DataRecord) -> (Option<Cow<'a, DataRecord>>, Vec<String>) {
+        let mut conflicts = Vec::new();
+        let mut out: Option<Vec<DataField>> = None;
+        for (idx, item) in record.items.iter().enumerate() {
+            let field = item.as_field();
+            let edit = match self.types.get(field.get_name()).map(|ty| check(field, ty)) {
+                None | Some(Checked::Match) => Edit::Keep,
+                Some(Checked::Coerced(coerced)) if self.on_conflict != SchemaConflict::Warn => {
+                    Edit::Replace(coerced)
+                }
+                Some(Checked::Coerced(_)) => {
+                    conflicts.push(field.get_name().to_string());
+                    Edit::Keep
+                }
+                Some(Checked::Conflict) => {
+                    conflicts.push(field.get_name().to_string());
+                    match self.on_conflict {
+                        SchemaConflict::Warn => Edit::Keep,
+                        SchemaConflict::Coerce => Edit::Remove,
+                        SchemaConflict::Drop => return (None, conflicts),
+                    }
+                }
+            };
+            match edit {
+                Edit::Keep => {
+                    if let Some(out) = out.as_mut() {
+                        out.push(field.clone());
+                    }
+                }
+                Edit::Replace(_) | Edit::Remove => {
+                    let out = out.get_or_insert_with(|| {
+                        let mut head = Vec::with_capacity(record.items.len());
+                        head.extend(record.items[..idx].iter().map(|f| f.as_field().clone()));
+                        head
+                    });
+                    if let Edit::Replace(coerced) = edit {
+                        out.push(coerced);
+                    }
+                }
+            }
+        }
+        let out = match out {
+            Some(fields) => Cow::Owned(DataRecord::from(fields)),
+            None => Cow::Borrowed(record),
+        };
+        (Some(out), conflicts)
+    }
+}
+
+fn check(field: &DataField, ty: &DataType) -> Checked {
+    let name = field.get_name();
+    let coerced = match (ty, field.get_value()) {
+        (_, Value::Null) => return Checked::Match,
+        (DataType::Chars, Value::Chars(_))
+        | (DataType::Digit, Value::Digit(_))
+        | (DataType::Float, Value::Float(_))
+        | (DataType::Bool, Value::Bool(_))
+        | (DataType::IP, Value::IpAddr(_)) => return Checked::Match,
+        (DataType::Digit, Value::Chars(s)) => s
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .map(|v| DataField::from_digit(name, v)),
+        (DataType::Float, Value::Digit(v)) => Some(DataField::from_float(name, *v as f64)),
+        (DataType::Float, Value::Chars(s)) => s
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|v| DataField::from_float(name, v)),
+        (DataType::Bool, Value::Chars(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" => Some(DataField::from_bool(name, true)),
+            "false" => Some(DataField::from_bool(name, false)),
+            _ => None,
+        },
+        (DataType::IP, Value::Chars(s)) => s
+            .trim()
+            .parse::<IpAddr>()
+            .ok()
+            .map(|ip| DataField::from_ip(name, ip)),
+        (DataType::Chars, Value::Obj(_) | Value::Array(_)) => None,
+        (DataType::Chars, v) => Some(DataField::from_chars(name, v.to_string())),
+        _ => None,
+    };
+    coerced.map_or(Checked::Conflict, Checked::Coerced)
+}
+
+/// 对记录写出路径套用 [`FieldSchema`]；冲突按字段首次出现告警一次，
+/// 丢弃的记录计入所属 sink 的 `wparse_sink_dropped_total`
+pub struct SchemaSink {
+    schema: FieldSchema,
+    warned: HashSet<String>,
+    dropped: u64,
+    dropped_counter: Counter,
+    /// 尚未被调用方结算的丢弃数，见 [`SchemaSink::drop_tally`]
+    unsettled: Arc<AtomicU64>,
+    inner: Box<dyn AsyncSink>,
+}
+
+impl SchemaSink {
+    pub fn new(schema: FieldSchema, dropped_counter: Counter, inner: Box<dyn AsyncSink>) -> Self {
+        Self {
+            schema,
+            warned: HashSet::new(),
+            dropped: 0,
+            dropped_counter,
+            unsettled: Arc::new(AtomicU64::new(0)),
+            inner,
+        }
+    }
+
+    /// 丢弃计数的共享句柄：调用方在记一批写出时取走（`swap(0)`）并从 sent 中扣除，
+    /// 使 sent + dropped 与进入该 sink 的记录数一致
+    pub fn drop_tally(&self) -> Arc<AtomicU64> {
+        self.unsettled.clone()
+    }
+
+    fn enforce<'a>(&mut self, record: &'a DataRecord) -> Option<Cow<'a, DataRecord>> {
+        let (out, conflicts) = self.schema.apply(record);
+        for name in conflicts {
+            if !self.warned.contains(&name) {
+                warn_data!(
+                    "schema conflict on field '{}' (on_conflict={:?})",
+                    name,
+                    self.schema.on_conflict
+                );
+                self.warned.insert(name);
+            }
+        }
+        if out.is_none() {
+            self.dropped += 1;
+            self.dropped_counter.inc();
+            self.unsettled.fetch_add(1, Ordering::Relaxed);
+            if self.dropped.is_power_of_two() {
+                warn_data!("schema dropped {} records", self.dropped);
+            }
+        }
+        out
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for SchemaSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for SchemaSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        match self.enforce(data) {
+            Some(rec) => self.inner.sink_record(&rec).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let mut checked: Vec<Arc<DataRecord>> = Vec::with_capacity(data.len());
+        for rec in data {
+            // 未改动的记录沿用原 Arc
+            let changed = match self.enforce(&rec) {
+                None => continue,
+                Some(Cow::Borrowed(_)) => None,
+                Some(Cow::Owned(changed)) => Some(Arc::new(changed)),
+            };
+            checked.push(changed.unwrap_or(rec));
+        }
+        if checked.is_empty() {
+            return Ok(());
+        }
+        self.inner.sink_records(checked).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawdatSink for SchemaSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn schema(on_conflict: SchemaConflict) -> FieldSchema {
+        FieldSchema::from_conf(&RouteSchemaConf {
+            on_conflict,
+            fields: [
+                ("port".to_string(), "digit".to_string()),
+                ("ratio".to_string(), "float".to_string()),
+            ]
+            .into(),
+            ..RouteSchemaConf::default()
+        })
+    }
+
+    fn fields(rec: &DataRecord) -> Vec<DataField> {
+        rec.items.iter().map(|f| f.as_field().clone()).collect()
+    }
+
+    #[test]
+    fn chars_coerced_to_digit() {
+        let rec = DataRecord::from(vec![
+            DataField::from_chars("port", " 8080"),
+            DataField::from_digit("ratio", 3),
+            DataField::from_chars("host", "web-1"),
+        ]);
+        let (out, conflicts) = schema(SchemaConflict::Coerce).apply(&rec);
+        let out = out.expect("kept");
+        assert!(conflicts.is_empty());
+        assert_eq!(
+            out.items[0].as_field(),
+            &DataField::from_digit("port", 8080)
+        );
+        assert_eq!(
+            out.items[1].as_field(),
+            &DataField::from_float("ratio", 3.0)
+        );
+        assert_eq!(
+            out.items[2].as_field(),
+            &DataField::from_chars("host", "web-1")
+        );
+
+        // warn 模式不改写，只报告
+        let (out, conflicts) = schema(SchemaConflict::Warn).apply(&rec);
+        assert_eq!(fields(&out.expect("kept")), fields(&rec));
+        assert_eq!(conflicts, vec!["port", "ratio"]);
+    }
+
+    #[test]
+    fn unchanged_record_is_borrowed() {
+        let rec = DataRecord::from(vec![
+            DataField::from_digit("port", 8080),
+            DataField::from_chars("host", "web-1"),
+        ]);
+        for policy in [
+            SchemaConflict::Warn,
+            SchemaConflict::Coerce,
+            SchemaConflict::Drop,
+        ] {
+            let (out, conflicts) = schema(policy).apply(&rec);
+            assert!(matches!(out, Some(Cow::Borrowed(r)) if std::ptr::eq(r, &rec)));
+            assert!(conflicts.is_empty());
+        }
+
+        // 只有发生转换的记录才复制，未列出的字段按原顺序保留
+        let rec = DataRecord::from(vec![
+            DataField::from_chars("host", "web-1"),
+            DataField::from_chars("port", "8080"),
+        ]);
+        let (out, _) = schema(SchemaConflict::Coerce).apply(&rec);
+        let out = out.expect("kept");
+        assert!(matches!(out, Cow::Owned(_)));
+        assert_eq!(
+            fields(&out),
+            vec![
+                DataField::from_chars("host", "web-1"),
+                DataField::from_digit("port", 8080)
+            ]
+        );
+    }
+
+    /// 记录下发到内层的 Arc
+    struct ArcCapture(Arc<Mutex<Vec<Arc<DataRecord>>>>);
+
+    #[async_trait]
+    impl AsyncCtrl for ArcCapture {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for ArcCapture {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            self.0.lock().unwrap().push(Arc::new(data.clone()));
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            self.0.lock().unwrap().extend(data);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawdatSink for ArcCapture {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_records_are_counted() -> SinkResult<()> {
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let counter = Counter::default();
+        let mut sink = SchemaSink::new(
+            schema(SchemaConflict::Drop),
+            counter.clone(),
+            Box::new(ArcCapture(out.clone())),
+        );
+        let bad = Arc::new(DataRecord::from(vec![DataField::from_chars(
+            "port", "http",
+        )]));
+        let good = Arc::new(DataRecord::from(vec![DataField::from_digit("port", 80)]));
+        sink.sink_records(vec![bad.clone(), good.clone(), bad.clone()])
+            .await?;
+        sink.sink_record(&bad).await?;
+        assert_eq!(counter.get(), 3);
+        let tally = sink.drop_tally();
+        assert_eq!(tally.swap(0, Ordering::Relaxed), 3);
+
+        // 未改动的记录沿用原 Arc 下发
+        let out = out.lock().unwrap();
+        assert_eq!(out.len(), 1);
+        assert!(Arc::ptr_eq(&out[0], &good));
+        Ok(())
+    }
+
+    #[test]
+    fn uncoercible_value_per_policy() {
+        let rec = DataRecord::from(vec![
+            DataField::from_chars("port", "http"),
+            DataField::from_chars("host", "web-1"),
+        ]);
+        let (out, conflicts) = schema(SchemaConflict::Coerce).apply(&rec);
+        let out = out.expect("kept");
+        assert_eq!(conflicts, vec!["port"]);
+        assert_eq!(out.items.len(), 1);
+        assert_eq!(out.items[0].get_name(), "host");
+
+        let (out, conflicts) = schema(SchemaConflict::Drop).apply(&rec);
+        assert!(out.is_none());
+        assert_eq!(conflicts, vec!["port"]);
+
+        let (out, _) = schema(SchemaConflict::Warn).apply(&rec);
+        assert_eq!(fields(&out.expect("kept")), fields(&rec));
+    }
+}
@@ -0,0 +1,272 @@
+//! sink 级输出形态：在写出前按 `format` 配置调整字段顺序、重命名并省略空值。
+//!
+//! 形态只作用于交给具体 sink 的记录副本，不改动路由共享的原始记录；
+//! 同一路由下的多个 sink 因此可以各自输出不同的字段顺序与键名。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use wp_conf::structure::SinkFormatConf;
+use wp_connector_api::SinkResult;
+use wp_model_core::model::{DataField, DataRecord, Value};
+
+use crate::sinks::prelude::*;
+
+/// 由 [`SinkFormatConf`] 编译得到的字段排布规则
+#[derive(Debug, Clone, Default)]
+pub struct RecordShape {
+    fields: Vec<String>,
+    append_rest: bool,
+    rename: HashMap<String, String>,
+    omit_empty: bool,
+}
+
+impl RecordShape {
+    pub fn from_conf(conf: &SinkFormatConf) -> Self {
+        Self {
+            fields: conf.fields.clone(),
+            append_rest: conf.append_rest,
+            rename: conf
+                .rename
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            omit_empty: conf.omit_empty,
+        }
+    }
+
+    /// 按配置重排字段；列出的字段中记录缺失的直接跳过
+    pub fn apply(&self, record: &DataRecord) -> DataRecord {
+        let mut out: Vec<DataField> = Vec::with_capacity(record.items.len());
+        let mut taken: HashSet<usize> = HashSet::new();
+        for name in &self.fields {
+            if let Some(idx) = record
+                .items
+                .iter()
+                .position(|f| f.get_name() == name.as_str())
+            {
+                taken.insert(idx);
+                self.push(&mut out, record.items[idx].as_field());
+            }
+        }
+        if self.append_rest || self.fields.is_empty() {
+            for (idx, item) in record.items.iter().enumerate() {
+                if !taken.contains(&idx) {
+                    self.push(&mut out, item.as_field());
+                }
+            }
+        }
+        DataRecord::from(out)
+    }
+
+    fn push(&self, out: &mut Vec<DataField>, field: &DataField) {
+        if self.omit_empty && is_empty_value(field.get_value()) {
+            return;
+        }
+        let mut field = field.clone();
+        if let Some(to) = self.rename.get(field.get_name()) {
+            field.set_name(to.clone());
+        }
+        out.push(field);
+    }
+}
+
+fn is_empty_value(v: &Value) -> bool {
+    match v {
+        Value::Null => true,
+        Value::Chars(s) => s.is_empty(),
+        _ => false,
+    }
+}
+
+/// 对记录写出路径套用 [`RecordShape`]；原始数据写出路径原样透传
+pub struct ShapedSink {
+    shape: RecordShape,
+    inner: Box<dyn AsyncSink>,
+}
+
+impl ShapedSink {
+    pub fn new(shape: RecordShape, inner: Box<dyn AsyncSink>) -> Self {
+        Self { shape, inner }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for ShapedSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.inner.stop().await
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        self.inner.reconnect().await
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for ShapedSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let shaped = self.shape.apply(data);
+        self.inner.sink_record(&shaped).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        let shaped = data
+            .iter()
+            .map(|rec| Arc::new(self.shape.apply(rec)))
+            .collect();
+        self.inner.sink_records(shaped).await
+    }
+}
+
+#[async_trait]
+impl AsyncRawdatSink for ShapedSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.inner.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.inner.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        self.inner.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        self.inner.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use wp_data_fmt::{Json, RecordFormatter};
+
+    /// 以 JSON 记录写出内容的测试 sink
+    struct JsonCapture(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl AsyncCtrl for JsonCapture {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for JsonCapture {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            let line = Json.fmt_record(data).to_string();
+            self.0.lock().unwrap().push(line);
+            Ok(())
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            for rec in data {
+                self.sink_record(&rec).await?;
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawdatSink for JsonCapture {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    fn record() -> DataRecord {
+        DataRecord::from(vec![
+            DataField::from_chars("ts", "2026-01-01 00:00:00"),
+            DataField::from_chars("host", "web-1"),
+            DataField::from_chars("msg", "login ok"),
+            DataField::from_chars("note", ""),
+        ])
+    }
+
+    fn key_pos(line: &str, key: &str) -> usize {
+        line.find(&format!("\"{}\"", key))
+            .unwrap_or_else(|| panic!("key {} missing in {}", key, line))
+    }
+
+    fn shaped(conf: SinkFormatConf, out: &Arc<Mutex<Vec<String>>>) -> ShapedSink {
+        ShapedSink::new(
+            RecordShape::from_conf(&conf),
+            Box::new(JsonCapture(out.clone())),
+        )
+    }
+
+    #[tokio::test]
+    async fn same_record_two_shapes() -> SinkResult<()> {
+        let out_a = Arc::new(Mutex::new(Vec::new()));
+        let out_b = Arc::new(Mutex::new(Vec::new()));
+        // sink A：msg 提前并改名，其余字段追加
+        let mut sink_a = shaped(
+            SinkFormatConf {
+                fields: vec!["msg".into(), "ts".into()],
+                rename: [("msg".to_string(), "message".to_string())].into(),
+                ..SinkFormatConf::default()
+            },
+            &out_a,
+        );
+        // sink B：仅保留 host/ts，省略空值
+        let mut sink_b = shaped(
+            SinkFormatConf {
+                fields: vec!["host".into(), "ts".into(), "note".into()],
+                append_rest: false,
+                rename: [("ts".to_string(), "@timestamp".to_string())].into(),
+                omit_empty: true,
+                ..SinkFormatConf::default()
+            },
+            &out_b,
+        );
+        let rec = Arc::new(record());
+        sink_a.sink_records(vec![rec.clone()]).await?;
+        sink_b.sink_record(&rec).await?;
+
+        let a = out_a.lock().unwrap()[0].clone();
+        assert!(key_pos(&a, "message") < key_pos(&a, "ts"));
+        assert!(key_pos(&a, "ts") < key_pos(&a, "host"));
+        assert!(key_pos(&a, "host") < key_pos(&a, "note"));
+        assert!(!a.contains("\"msg\""));
+
+        let b = out_b.lock().unwrap()[0].clone();
+        assert!(key_pos(&b, "host") < key_pos(&b, "@timestamp"));
+        assert!(!b.contains("\"msg\""));
+        assert!(!b.contains("\"note\""));
+        assert!(!b.contains("\"ts\""));
+
+        // 原始记录不受影响
+        assert_eq!(rec.items[0].get_name(), "ts");
+        assert_eq!(rec.items.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_listed_fields_are_skipped() {
+        let shape = RecordShape::from_conf(&SinkFormatConf {
+            fields: vec!["absent".into(), "host".into()],
+            ..SinkFormatConf::default()
+        });
+        let names: Vec<String> = shape
+            .apply(&record())
+            .items
+            .iter()
+            .map(|f| f.get_name().to_string())
+            .collect();
+        assert_eq!(names, vec!["host", "ts", "msg", "note"]);
+    }
+}
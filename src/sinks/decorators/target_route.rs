@@ -0,0 +1,640 @@
+//! 按记录字段值动态解析 sink 目标（topic/index/table）。
+//!
+//! 目标参数中的 `{field}` 取自转换后记录的字段值，`{yyyy.MM.dd}` 等日期占位符按处理时间展开。
+//! 批次先按解析出的目标拆分，再交给各目标对应的具体 sink；每个目标的 sink 由同一工厂按需构建。
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Local;
+use serde_json::json;
+use wp_connector_api::{
+    AsyncCtrl, AsyncRawDataSink, AsyncRecordSink, AsyncSink, ParamMap, SinkBuildCtx, SinkFactory,
+    SinkResult, SinkSpec,
+};
+use wp_model_core::model::{DataRecord, Value};
+
+/// 支持模板化的目标参数
+pub const TARGET_PARAM_KEYS: [&str; 3] = ["index", "topic", "table"];
+/// 字段缺失时使用的目标值
+pub const PARAM_TARGET_FALLBACK: &str = "target_fallback";
+/// 单次 flush 内允许的不同目标数上限（同时也是保持打开的目标 sink 数上限）
+pub const PARAM_MAX_TARGETS: &str = "max_targets";
+
+const DEFAULT_MAX_TARGETS: usize = 16;
+const MISSING_FIELD_VALUE: &str = "unknown";
+
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart {
+    Lit(String),
+    Field(String),
+    Time(String),
+}
+
+/// 模板化的 sink 目标，如 `logs-{tenant}-{yyyy.MM.dd}`
+#[derive(Debug, Clone)]
+pub struct TargetTemplate {
+    param: String,
+    parts: Vec<TemplatePart>,
+    fallback: Option<String>,
+    max_targets: usize,
+}
+
+impl TargetTemplate {
+    /// 从 sink 参数中识别模板化目标；目标参数不含占位符时返回 None
+    pub fn detect(params: &ParamMap) -> anyhow::Result<Option<Self>> {
+        let mut found: Option<(&str, Vec<TemplatePart>)> = None;
+        for key in TARGET_PARAM_KEYS {
+            let Some(raw) = params.get(key).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let parts = parse_template(raw);
+            if parts.iter().all(|p| matches!(p, TemplatePart::Lit(_))) {
+                continue;
+            }
+            if let Some((prev, _)) = &found {
+                anyhow::bail!(
+                    "only one templated sink target is supported, got '{}' and '{}'",
+                    prev,
+                    key
+                );
+            }
+            found = Some((key, parts));
+        }
+        let Some((param, parts)) = found else {
+            return Ok(None);
+        };
+        let fallback = match params.get(PARAM_TARGET_FALLBACK) {
+            Some(v) => Some(
+                v.as_str()
+                    .filter(|s| !s.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("target_fallback must be a non-empty string"))?
+                    .to_string(),
+            ),
+            None => None,
+        };
+        let max_targets = match params.get(PARAM_MAX_TARGETS) {
+            Some(v) => v
+                .as_u64()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| anyhow::anyhow!("max_targets must be a positive integer"))?
+                as usize,
+            None => DEFAULT_MAX_TARGETS,
+        };
+        Ok(Some(Self {
+            param: param.to_string(),
+            parts,
+            fallback,
+            max_targets,
+        }))
+    }
+
+    pub fn param(&self) -> &str {
+        self.param.as_str()
+    }
+
+    pub fn max_targets(&self) -> usize {
+        self.max_targets
+    }
+
+    /// 解析记录对应的目标；字段缺失时返回 fallback，未配置 fallback 时以 `unknown` 填充
+    pub fn resolve(&self, record: Option<&DataRecord>) -> String {
+        let now = Local::now();
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Lit(s) => out.push_str(s),
+                TemplatePart::Time(fmt) => out.push_str(&now.format(fmt).to_string()),
+                TemplatePart::Field(name) => match record.and_then(|r| field_text(r, name)) {
+                    Some(v) => out.push_str(&v),
+                    None => match &self.fallback {
+                        Some(fallback) => return fallback.clone(),
+                        None => out.push_str(MISSING_FIELD_VALUE),
+                    },
+                },
+            }
+        }
+        out
+    }
+
+    /// 无法从记录解析目标时使用的目标（原始数据路径、超出目标数上限的记录）
+    pub fn fallback_target(&self) -> String {
+        self.fallback.clone().unwrap_or_else(|| self.resolve(None))
+    }
+}
+
+fn field_text(record: &DataRecord, name: &str) -> Option<String> {
+    let field = record.field(name)?;
+    let text = match field.get_value() {
+        Value::Ignore(_) => return None,
+        Value::Chars(v) => v.to_string(),
+        other => other.to_string(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn parse_template(raw: &str) -> Vec<TemplatePart> {
+    let mut parts = Vec::new();
+    let mut lit = String::new();
+    let mut rest = raw;
+    while let Some(beg) = rest.find('{') {
+        let Some(len) = rest[beg + 1..].find('}') else {
+            break;
+        };
+        let name = rest[beg + 1..beg + 1 + len].trim();
+        lit.push_str(&rest[..beg]);
+        if name.is_empty() {
+            lit.push_str(&rest[beg..beg + 2 + len]);
+        } else {
+            if !lit.is_empty() {
+                parts.push(TemplatePart::Lit(std::mem::take(&mut lit)));
+            }
+            parts.push(match date_format(name) {
+                Some(fmt) => TemplatePart::Time(fmt),
+                None => TemplatePart::Field(name.to_string()),
+            });
+        }
+        rest = &rest[beg + 2 + len..];
+    }
+    lit.push_str(rest);
+    if !lit.is_empty() {
+        parts.push(TemplatePart::Lit(lit));
+    }
+    parts
+}
+
+/// `yyyy.MM.dd` 风格的日期占位符转换为 chrono 格式；非日期占位符返回 None
+fn date_format(pat: &str) -> Option<String> {
+    const TOKENS: [(&str, &str); 6] = [
+        ("yyyy", "%Y"),
+        ("MM", "%m"),
+        ("dd", "%d"),
+        ("HH", "%H"),
+        ("mm", "%M"),
+        ("ss", "%S"),
+    ];
+    let mut out = String::new();
+    let mut rest = pat;
+    let mut has_token = false;
+    'scan: while !rest.is_empty() {
+        for (tok, fmt) in TOKENS {
+            if let Some(r) = rest.strip_prefix(tok) {
+                out.push_str(fmt);
+                rest = r;
+                has_token = true;
+                continue 'scan;
+            }
+        }
+        let c = rest.chars().next()?;
+        if !matches!(c, '.' | '-' | '_' | '/' | ':') {
+            return None;
+        }
+        out.push(c);
+        rest = &rest[1..];
+    }
+    has_token.then_some(out)
+}
+
+/// 构建目标 sink 所需的上下文参数
+#[derive(Debug, Clone)]
+pub struct TargetBuildArgs {
+    pub root: PathBuf,
+    pub replica_idx: usize,
+    pub replica_cnt: usize,
+    pub rate_limit_rps: usize,
+}
+
+impl TargetBuildArgs {
+    fn ctx(&self) -> SinkBuildCtx {
+        SinkBuildCtx::new_with_replica(self.root.clone(), self.replica_idx, self.replica_cnt)
+            .with_limit(self.rate_limit_rps)
+    }
+}
+
+struct TargetSlot {
+    sink: Box<dyn AsyncSink>,
+    last_used: u64,
+}
+
+/// 按目标拆分批次的 sink 装饰器
+pub struct TargetRoutedSink {
+    factory: Arc<dyn SinkFactory>,
+    spec: SinkSpec,
+    args: TargetBuildArgs,
+    template: TargetTemplate,
+    targets: HashMap<String, TargetSlot>,
+    tick: u64,
+}
+
+impl TargetRoutedSink {
+    pub fn new(
+        factory: Arc<dyn SinkFactory>,
+        spec: SinkSpec,
+        args: TargetBuildArgs,
+        template: TargetTemplate,
+    ) -> SinkResult<Self> {
+        let routed = Self {
+            factory,
+            spec,
+            args,
+            template,
+            targets: HashMap::new(),
+            tick: 0,
+        };
+        // 以 fallback 目标预检参数，尽早暴露配置错误；具体目标按需构建
+        routed
+            .factory
+            .validate_spec(&routed.target_spec(&routed.template.fallback_target()))?;
+        Ok(routed)
+    }
+
+    fn target_spec(&self, target: &str) -> SinkSpec {
+        let mut spec = self.spec.clone();
+        spec.params
+            .insert(self.template.param().to_string(), json!(target));
+        spec.params.remove(PARAM_TARGET_FALLBACK);
+        spec.params.remove(PARAM_MAX_TARGETS);
+        spec
+    }
+
+    async fn sink_for(&mut self, target: &str) -> SinkResult<&mut Box<dyn AsyncSink>> {
+        self.tick += 1;
+        if !self.targets.contains_key(target) {
+            self.evict_idle().await;
+            let spec = self.target_spec(target);
+            let handle = self.factory.build(&spec, &self.args.ctx()).await?;
+            info_data!(
+                "sink {} open target {}={}",
+                self.spec.name,
+                self.template.param(),
+                target
+            );
+            self.targets.insert(
+                target.to_string(),
+                TargetSlot {
+                    sink: handle.sink,
+                    last_used: 0,
+                },
+            );
+        }
+        let tick = self.tick;
+        let slot = self
+            .targets
+            .get_mut(target)
+            .expect("target sink inserted above");
+        slot.last_used = tick;
+        Ok(&mut slot.sink)
+    }
+
+    /// 打开的目标数达到上限时，关闭最久未使用的目标（如按天滚动的旧索引）
+    async fn evict_idle(&mut self) {
+        if self.targets.len() < self.template.max_targets() {
+            return;
+        }
+        let Some(key) = self
+            .targets
+            .iter()
+            .min_by_key(|(_, slot)| slot.last_used)
+            .map(|(k, _)| k.clone())
+        else {
+            return;
+        };
+        if let Some(mut slot) = self.targets.remove(&key)
+            && let Err(e) = slot.sink.stop().await
+        {
+            warn_data!(
+                "sink {} close idle target {} failed: {}",
+                self.spec.name,
+                key,
+                e
+            );
+        }
+    }
+
+    /// 按目标分组（组内保持原顺序）；单批次内超过 `max_targets` 的新目标并入 fallback
+    fn group_records(&self, data: Vec<Arc<DataRecord>>) -> Vec<(String, Vec<Arc<DataRecord>>)> {
+        let fallback = self.template.fallback_target();
+        let mut groups: Vec<(String, Vec<Arc<DataRecord>>)> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut overflow = 0usize;
+        for record in data {
+            let mut target = self.template.resolve(Some(&record));
+            if !index.contains_key(&target)
+                && index.len() >= self.template.max_targets()
+                && target != fallback
+            {
+                overflow += 1;
+                target = fallback.clone();
+            }
+            let idx = match index.get(&target) {
+                Some(idx) => *idx,
+                None => {
+                    groups.push((target.clone(), Vec::new()));
+                    index.insert(target, groups.len() - 1);
+                    groups.len() - 1
+                }
+            };
+            groups[idx].1.push(record);
+        }
+        if overflow > 0 {
+            warn_data!(
+                "sink {} resolved more than {} targets in one flush; {} records routed to fallback target '{}'",
+                self.spec.name,
+                self.template.max_targets(),
+                overflow,
+                fallback
+            );
+        }
+        groups
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for TargetRoutedSink {
+    async fn stop(&mut self) -> SinkResult<()> {
+        let mut first_err = None;
+        for (_, mut slot) in self.targets.drain() {
+            if let Err(e) = slot.sink.stop().await {
+                first_err.get_or_insert(e);
+            }
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        for slot in self.targets.values_mut() {
+            slot.sink.reconnect().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for TargetRoutedSink {
+    async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+        let target = self.template.resolve(Some(data));
+        self.sink_for(&target).await?.sink_record(data).await
+    }
+
+    async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        for (target, records) in self.group_records(data) {
+            self.sink_for(&target).await?.sink_records(records).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for TargetRoutedSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        let target = self.template.fallback_target();
+        self.sink_for(&target).await?.sink_str(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        let target = self.template.fallback_target();
+        self.sink_for(&target).await?.sink_bytes(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        let target = self.template.fallback_target();
+        self.sink_for(&target).await?.sink_str_batch(data).await
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        let target = self.template.fallback_target();
+        self.sink_for(&target).await?.sink_bytes_batch(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use wp_connector_api::SinkHandle;
+    use wp_model_core::model::DataField;
+
+    type Deliveries = Arc<Mutex<Vec<(String, Vec<String>)>>>;
+
+    struct RecSink {
+        target: String,
+        log: Deliveries,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for RecSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for RecSink {
+        async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
+            self.sink_records(vec![Arc::new(data.clone())]).await
+        }
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            let seqs = data
+                .iter()
+                .map(|r| {
+                    r.field("seq")
+                        .map(|f| f.get_value().to_string())
+                        .unwrap_or_default()
+                })
+                .collect();
+            self.log.lock().unwrap().push((self.target.clone(), seqs));
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for RecSink {
+        async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+            self.log
+                .lock()
+                .unwrap()
+                .push((self.target.clone(), vec![data.to_string()]));
+            Ok(())
+        }
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+            for s in data {
+                self.sink_str(s).await?;
+            }
+            Ok(())
+        }
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    struct RecFactory {
+        log: Deliveries,
+    }
+
+    #[async_trait]
+    impl SinkFactory for RecFactory {
+        fn kind(&self) -> &'static str {
+            "test_target_rec"
+        }
+        fn validate_spec(&self, _spec: &SinkSpec) -> SinkResult<()> {
+            Ok(())
+        }
+        async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+            let target = spec
+                .params
+                .get("topic")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            Ok(SinkHandle::new(Box::new(RecSink {
+                target,
+                log: self.log.clone(),
+            })))
+        }
+    }
+
+    fn routed(params: &[(&str, serde_json::Value)]) -> (TargetRoutedSink, Deliveries) {
+        let mut map = ParamMap::new();
+        for (k, v) in params {
+            map.insert(k.to_string(), v.clone());
+        }
+        let template = TargetTemplate::detect(&map)
+            .expect("detect template")
+            .expect("templated target");
+        let log: Deliveries = Arc::default();
+        let spec = SinkSpec {
+            group: String::new(),
+            name: "per_tenant".into(),
+            kind: "test_target_rec".into(),
+            connector_id: String::new(),
+            params: map,
+            filter: None,
+        };
+        let args = TargetBuildArgs {
+            root: PathBuf::from("."),
+            replica_idx: 0,
+            replica_cnt: 1,
+            rate_limit_rps: 0,
+        };
+        let factory: Arc<dyn SinkFactory> = Arc::new(RecFactory { log: log.clone() });
+        let sink = TargetRoutedSink::new(factory, spec, args, template).expect("routed sink");
+        (sink, log)
+    }
+
+    fn record(seq: i64, tenant: Option<&str>) -> Arc<DataRecord> {
+        let mut rec = DataRecord::default();
+        rec.append(DataField::from_digit("seq", seq));
+        if let Some(t) = tenant {
+            rec.append(DataField::from_chars("tenant", t));
+        }
+        Arc::new(rec)
+    }
+
+    #[test]
+    fn detect_ignores_plain_targets() {
+        let mut map = ParamMap::new();
+        map.insert("topic".into(), json!("wparse_output"));
+        map.insert("path".into(), json!("./{tenant}.dat"));
+        assert!(TargetTemplate::detect(&map).unwrap().is_none());
+
+        map.insert("topic".into(), json!("evt_{tenant}"));
+        map.insert("index".into(), json!("logs-{tenant}"));
+        assert!(TargetTemplate::detect(&map).is_err());
+    }
+
+    #[test]
+    fn resolve_fields_and_dates() {
+        let mut map = ParamMap::new();
+        map.insert("index".into(), json!("logs-{tenant}-{yyyy.MM.dd}"));
+        let tpl = TargetTemplate::detect(&map).unwrap().unwrap();
+        let today = Local::now().format("%Y.%m.%d").to_string();
+        let rec = record(1, Some("acme"));
+        let resolved = tpl.resolve(Some(&rec));
+        // 跨零点时日期可能变化，仅在同一天内断言完整值
+        if today == Local::now().format("%Y.%m.%d").to_string() {
+            assert_eq!(resolved, format!("logs-acme-{}", today));
+        }
+        assert!(tpl.resolve(None).starts_with("logs-unknown-"));
+    }
+
+    #[tokio::test]
+    async fn two_tenants_land_in_two_topics() {
+        let (mut sink, log) = routed(&[("topic", json!("evt_{tenant}"))]);
+        sink.sink_records(vec![
+            record(1, Some("a")),
+            record(2, Some("b")),
+            record(3, Some("a")),
+        ])
+        .await
+        .unwrap();
+        let got = log.lock().unwrap().clone();
+        assert_eq!(
+            got,
+            vec![
+                ("evt_a".to_string(), vec!["1".to_string(), "3".to_string()]),
+                ("evt_b".to_string(), vec!["2".to_string()]),
+            ]
+        );
+        assert_eq!(sink.targets.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn missing_field_and_raw_data_use_fallback() {
+        let (mut sink, log) = routed(&[
+            ("topic", json!("evt_{tenant}")),
+            ("target_fallback", json!("evt_other")),
+        ]);
+        sink.sink_records(vec![record(1, None), record(2, Some("a"))])
+            .await
+            .unwrap();
+        sink.sink_str("raw line").await.unwrap();
+        let got = log.lock().unwrap().clone();
+        assert_eq!(
+            got,
+            vec![
+                ("evt_other".to_string(), vec!["1".to_string()]),
+                ("evt_a".to_string(), vec!["2".to_string()]),
+                ("evt_other".to_string(), vec!["raw line".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn targets_beyond_cap_go_to_fallback() {
+        let (mut sink, log) = routed(&[
+            ("topic", json!("evt_{tenant}")),
+            ("target_fallback", json!("evt_other")),
+            ("max_targets", json!(2)),
+        ]);
+        sink.sink_records(vec![
+            record(1, Some("a")),
+            record(2, Some("b")),
+            record(3, Some("c")),
+            record(4, Some("a")),
+        ])
+        .await
+        .unwrap();
+        let got = log.lock().unwrap().clone();
+        assert_eq!(
+            got,
+            vec![
+                ("evt_a".to_string(), vec!["1".to_string(), "4".to_string()]),
+                ("evt_b".to_string(), vec!["2".to_string()]),
+                ("evt_other".to_string(), vec!["3".to_string()]),
+            ]
+        );
+        // 打开的目标数受 max_targets 约束，最久未用的目标被关闭
+        assert_eq!(sink.targets.len(), 2);
+    }
+}
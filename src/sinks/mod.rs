@@ -17,6 +17,7 @@ mod utils;
 // Keep public only the items required by external apps/tests; rest are crate-internal
 pub(crate) use backends::file::FileSink;
 pub use backends::file::create_watch_out; // tests rely on this helper
+pub(crate) use decorators::target_route::{TargetBuildArgs, TargetRoutedSink, TargetTemplate};
 pub(crate) use decorators::test_proxy::ASinkTestProxy;
 pub(crate) use decorators::test_proxy::HealthController;
 pub(crate) use rescue::RescueFileSink;
@@ -28,6 +29,7 @@ pub(crate) use routing::dispatcher::SinkDispatcher;
 pub use routing::dispatcher::perf::{OmlBatchPerfCase, SinkBatchBufferPerfCase};
 pub use routing::registry::SinkRegistry; // used by apps/tests
 pub use routing::registry::SinkRouteAgent; // used by tests
+pub use runtime::breaker::{BreakerState, SinkBackpressure};
 pub(crate) use runtime::manager::SinkRuntime;
 pub use sink_build::{build_file_sink, build_file_sink_with_sync};
 pub use types::*; // SinkBackendType, SinkEndpoint (used by apps/tests)
@@ -47,6 +49,8 @@ pub use builtin_factories::register_builtin_factories as register_builtin_sinks;
 // Expose a simple null sink for benches and external tests
 pub use backends::blackhole::BlackHoleSink;
 pub use backends::blackhole_factory::BlackHoleFactory;
+pub use backends::channel::{ChannelFactory, subscribe_output};
+pub use backends::clickhouse::ClickHouseFactory;
 pub use backends::file_factory::FileFactory;
 pub use backends::syslog::SyslogFactory;
 pub use backends::syslog::register_factory_syslog;
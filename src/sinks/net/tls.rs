@@ -0,0 +1,97 @@
+//! TLS 客户端连接（rustls），供 syslog 等基于 TCP 的 sink 复用。
+
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use anyhow::Context;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, RootCertStore};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::client::TlsStream;
+
+/// TLS 客户端配置（PEM 文件路径）
+#[derive(Debug, Clone, Default)]
+pub struct TlsClientConf {
+    /// 服务端 CA；未配置时使用 webpki 内置根证书
+    pub ca_file: Option<String>,
+    /// 客户端证书链（双向认证）
+    pub cert_file: Option<String>,
+    /// 客户端私钥（双向认证）
+    pub key_file: Option<String>,
+    /// SNI 与证书校验使用的服务名
+    pub server_name: String,
+}
+
+/// 已完成配置加载的 TLS 连接器，可重复用于重连。
+#[derive(Clone)]
+pub struct TlsDialer {
+    connector: TlsConnector,
+    server_name: ServerName<'static>,
+}
+
+impl TlsDialer {
+    pub fn new(conf: &TlsClientConf) -> anyhow::Result<Self> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut roots = RootCertStore::empty();
+        match conf.ca_file.as_deref() {
+            Some(path) => {
+                for cert in load_certs(path)? {
+                    roots
+                        .add(cert)
+                        .with_context(|| format!("invalid CA certificate in {}", path))?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots);
+        let config = match (conf.cert_file.as_deref(), conf.key_file.as_deref()) {
+            (Some(cert), Some(key)) => {
+                builder.with_client_auth_cert(load_certs(cert)?, load_key(key)?)?
+            }
+            (None, None) => builder.with_no_client_auth(),
+            _ => anyhow::bail!("tls client cert and key must be configured together"),
+        };
+        let server_name = ServerName::try_from(conf.server_name.clone())
+            .with_context(|| format!("invalid tls server name '{}'", conf.server_name))?;
+        Ok(Self {
+            connector: TlsConnector::from(Arc::new(config)),
+            server_name,
+        })
+    }
+
+    /// 建立 TCP 连接并完成 TLS 握手
+    pub async fn connect(&self, addr: &str) -> anyhow::Result<TlsStream<TcpStream>> {
+        let tcp = TcpStream::connect(addr).await?;
+        let stream = self
+            .connector
+            .connect(self.server_name.clone(), tcp)
+            .await
+            .with_context(|| format!("tls handshake with {} failed", addr))?;
+        Ok(stream)
+    }
+}
+
+fn load_certs(path: &str) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(
+        File::open(path).with_context(|| format!("open certificate file {}", path))?,
+    );
+    let certs = rustls_pemfile::certs(&mut reader)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("parse certificate file {}", path))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificate found in {}", path);
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &str) -> anyhow::Result<PrivateKeyDer<'static>> {
+    let mut reader =
+        BufReader::new(File::open(path).with_context(|| format!("open key file {}", path))?);
+    rustls_pemfile::private_key(&mut reader)
+        .with_context(|| format!("parse key file {}", path))?
+        .ok_or_else(|| anyhow::anyhow!("no private key found in {}", path))
+}
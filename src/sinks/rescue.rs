@@ -25,6 +25,9 @@ pub struct RescueEntry {
     version: u8,
     #[serde(flatten)]
     payload: RescuePayload,
+    /// 下游拒收原因（如服务端校验错误文本）；普通 rescue 为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 impl RescueEntry {
@@ -40,6 +43,7 @@ impl RescueEntry {
             payload: RescuePayload::Record {
                 record: record.clone(),
             },
+            error: None,
         }
     }
 
@@ -47,6 +51,23 @@ impl RescueEntry {
         Self {
             version: Self::CURRENT_VERSION,
             payload: RescuePayload::Raw { raw },
+            error: None,
+        }
+    }
+
+    /// 被下游拒收的记录，附带拒收原因
+    pub fn rejected_record(record: &DataRecord, error: &str) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::record(record)
+        }
+    }
+
+    /// 被下游拒收的原始行，附带拒收原因
+    pub fn rejected_raw(raw: String, error: &str) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::raw_line(raw)
         }
     }
 
@@ -61,6 +82,10 @@ impl RescueEntry {
     pub fn into_payload(self) -> RescuePayload {
         self.payload
     }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
 }
 
 pub struct RescueFileSink {
@@ -92,7 +117,7 @@ impl RescueFileSink {
         SinkError::from(SinkReason::Sink(err.to_string()))
     }
 
-    async fn write_entry(&mut self, entry: &RescueEntry) -> SinkResult<()> {
+    pub(crate) async fn write_entry(&mut self, entry: &RescueEntry) -> SinkResult<()> {
         let mut line = serde_json::to_vec(entry).map_err(Self::sink_err)?;
         line.push(b'\n');
         self.writer.write_all(&line).await.map_err(Self::sink_err)?;
@@ -201,6 +226,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rescue_entry_keeps_reject_reason() {
+        let plain = serde_json::to_string(&RescueEntry::raw_line("x".into())).unwrap();
+        assert!(!plain.contains("error"));
+        let entry = RescueEntry::rejected_raw("x".into(), "Code: 27");
+        let line = serde_json::to_string(&entry).expect("serialize entry");
+        let parsed = RescueEntry::parse(&line).expect("parse entry");
+        assert_eq!(parsed.error(), Some("Code: 27"));
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn rescue_sink_writes_structured_lines() -> AnyResult<()> {
         let temp = tempdir()?;
@@ -0,0 +1,43 @@
+use std::time::Instant;
+
+use super::SinkDispatcher;
+use crate::sinks::{ASinkSender, ProcMeta, SinkRecUnit};
+use crate::stat::MonSend;
+use wp_connector_api::SinkResult;
+
+impl SinkDispatcher {
+    /// 输出 group_by 窗口的汇总记录；`force` 为真时忽略周期立即输出（退出/模型切换）
+    ///
+    /// 汇总记录与普通记录一样经过 sink 条件与 pre_tags 扇出，返回输出的汇总条数。
+    pub(crate) async fn emit_group_summaries(
+        &mut self,
+        force: bool,
+        bad_s: &ASinkSender,
+        mon: Option<&MonSend>,
+    ) -> SinkResult<usize> {
+        if self.groups.is_empty() {
+            return Ok(0);
+        }
+        let now = Instant::now();
+        let mut summaries = Vec::new();
+        for (name, agg) in self.groups.iter_mut() {
+            let out = if force { agg.drain(now) } else { agg.poll(now) };
+            if !out.is_empty() {
+                debug_data!("group_by {} emit {} summaries", name, out.len());
+            }
+            summaries.extend(out);
+        }
+        if summaries.is_empty() {
+            return Ok(0);
+        }
+        let emitted = summaries.len();
+        let mut per_sink: Vec<Vec<SinkRecUnit>> = (0..self.sinks.len())
+            .map(|_| self.unit_pool.take())
+            .collect();
+        for record in summaries {
+            self.push_transformed_record(0, ProcMeta::Null, record, &mut per_sink);
+        }
+        self.send_per_sink(per_sink, bad_s, mon).await?;
+        Ok(emitted)
+    }
+}
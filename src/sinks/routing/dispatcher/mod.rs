@@ -1,11 +1,15 @@
 use super::agent::InfraSinkAgent;
 use std::collections::HashMap;
+use std::time::Instant;
 use wp_conf::limits::sink_channel_cap;
 
 use crate::resources::SinkResUnit;
+use crate::runtime::sink::drain::DrainSignal;
 use crate::sinks::SinkRuntime;
 use crate::sinks::{ASinkSender, SinkDatYReceiver, SinkDatYSender, SinkPackage, SinkRecUnit};
 use crate::stat::MonSend;
+use crate::stat::prometheus;
+use ::oml::core::AggregateEvaluator;
 use derive_getters::Getters;
 use orion_overload::append::Appendable;
 use wp_conf::structure::SinkGroupConf;
@@ -14,11 +18,13 @@ use wp_data_model::cache::FieldQueryCache;
 
 // split internal helpers
 
+mod group; // group_by 分组聚合
 mod io; // 直发/原始数据下发
 mod oml; // OML/条件路由
 #[cfg(any(test, feature = "perf-ci"))]
 pub mod perf; // 性能基准工具
 mod recovery; // 故障恢复与收尾
+mod workers; // 组内并行 OML 转换
 type GroupedRecords = HashMap<String, Vec<SinkRecUnit>>;
 
 struct SinkRecUnitPool {
@@ -56,12 +62,19 @@ pub struct SinkDispatcher {
     dat_r: SinkDatYReceiver,
     res: SinkResUnit,
     unit_pool: SinkRecUnitPool,
+    // 模型名 -> group_by 聚合器（仅含 group_by 的模型，首次命中时创建）
+    #[getter(skip)]
+    groups: HashMap<String, AggregateEvaluator>,
+    // 组内 OML 转换线程池（workers > 1 时由 start_workers 启动）
+    #[getter(skip)]
+    workers: Option<workers::TransformPool>,
 }
 
 impl SinkDispatcher {
     pub fn new(conf: SinkGroupConf, res: SinkResUnit) -> Self {
         // 改用 tokio::mpsc 事件化通道，便于与 runtime 协作
         let (dat_s, dat_r) = tokio::sync::mpsc::channel(sink_channel_cap());
+        prometheus::watch_channel(format!("sink-{}", conf.name()), &dat_s);
         Self {
             conf,
             sinks: Vec::new(),
@@ -69,6 +82,8 @@ impl SinkDispatcher {
             dat_r,
             res,
             unit_pool: SinkRecUnitPool::new(),
+            groups: HashMap::new(),
+            workers: None,
         }
     }
     pub fn get_dat_r_mut(&mut self) -> &mut SinkDatYReceiver {
@@ -80,6 +95,17 @@ impl SinkDispatcher {
     pub fn close_channel(&mut self) {
         self.dat_r.close();
     }
+    /// 进入 drain：关闭数据通道（已入队数据仍可读出），并为各 sink 设置下发时限
+    pub fn begin_drain(&mut self, deadline: Instant) {
+        self.dat_r.close();
+        for sink_rt in self.sinks.iter_mut() {
+            sink_rt.begin_drain(deadline);
+        }
+    }
+    /// 组内各 sink 的 drain 时限信号
+    pub fn drain_signals(&self) -> Vec<DrainSignal> {
+        self.sinks.iter().map(|s| s.drain_signal()).collect()
+    }
     pub fn get_name(&self) -> &str {
         self.conf.name().as_str()
     }
@@ -105,6 +131,21 @@ impl SinkDispatcher {
         }
     }
 
+    /// 规则集被热重载后切换到新一代 OML 模型
+    pub(crate) fn refresh_models(&mut self) -> bool {
+        let switched = self.res.refresh();
+        if switched {
+            if let Some(pool) = self.workers.as_mut() {
+                pool.reset_models();
+            }
+            info_data!(
+                "{} sink group switched to reloaded oml models",
+                self.conf.name()
+            );
+        }
+        switched
+    }
+
     /// 批量处理数据包（支持批量优化）
     pub(crate) async fn group_sink_package(
         &mut self,
@@ -114,6 +155,12 @@ impl SinkDispatcher {
         mon: Option<&MonSend>,
         cache: &mut FieldQueryCache,
     ) -> SinkResult<usize> {
+        // 在包边界切换热重载后的模型，同一个包内始终使用同一代模型；
+        // 旧模型的 group_by 窗口先行输出，避免跨代混合聚合
+        if self.refresh_models() {
+            self.emit_group_summaries(true, bad_s, mon).await?;
+            self.groups.clear();
+        }
         let mut processed_count = 0;
 
         // 先按规则分组，同一规则共享一次 OML 批处理
@@ -135,38 +182,53 @@ impl SinkDispatcher {
             let Some(meta) = units.first().map(|unit| unit.meta().clone()) else {
                 continue;
             };
-            let mut per_sink_units = self.oml_proc_batch(units, infra, cache, &meta)?;
-            for (idx, sink_rt) in self.sinks.iter_mut().enumerate() {
-                let payload = {
-                    if !sink_rt.is_ready() {
-                        let unused = std::mem::take(&mut per_sink_units[idx]);
-                        self.unit_pool.recycle(unused);
+            let per_sink_units = if self.use_workers(&meta, units.len()) {
+                self.oml_proc_batch_parallel(units, infra, &meta).await?
+            } else {
+                self.oml_proc_batch(units, infra, cache, &meta)?
+            };
+            self.send_per_sink(per_sink_units, bad_s, mon).await?;
+        }
+
+        Ok(processed_count)
+    }
+
+    /// 将按 sink 分好的记录下发到各就绪 sink，并回收缓冲
+    async fn send_per_sink(
+        &mut self,
+        mut per_sink_units: Vec<Vec<SinkRecUnit>>,
+        bad_s: &ASinkSender,
+        mon: Option<&MonSend>,
+    ) -> SinkResult<()> {
+        for (idx, sink_rt) in self.sinks.iter_mut().enumerate() {
+            let payload = {
+                if !sink_rt.is_ready() {
+                    let unused = std::mem::take(&mut per_sink_units[idx]);
+                    self.unit_pool.recycle(unused);
+                    None
+                } else {
+                    let units = std::mem::take(&mut per_sink_units[idx]);
+                    if units.is_empty() {
+                        self.unit_pool.recycle(units);
                         None
                     } else {
-                        let units = std::mem::take(&mut per_sink_units[idx]);
-                        if units.is_empty() {
-                            self.unit_pool.recycle(units);
-                            None
-                        } else {
-                            let pkg = SinkPackage::from_units(units.into_iter());
-                            let name_snapshot = sink_rt.name.clone();
-                            sink_rt.send_package_to_sink(&pkg, Some(bad_s), mon).await?;
-                            let vec_back = pkg.into_inner();
-                            Some((name_snapshot, vec_back))
-                        }
+                        let pkg = SinkPackage::from_units(units.into_iter());
+                        let name_snapshot = sink_rt.name.clone();
+                        sink_rt.send_package_to_sink(&pkg, Some(bad_s), mon).await?;
+                        let vec_back = pkg.into_inner();
+                        Some((name_snapshot, vec_back))
                     }
-                };
-                if let Some((name, vec_back)) = payload {
-                    self.unit_pool.recycle(vec_back);
-                    info_data!("sink {} send batch rec suc!", name);
                 }
-            }
-            for leftover in per_sink_units.into_iter() {
-                self.unit_pool.recycle(leftover);
+            };
+            if let Some((name, vec_back)) = payload {
+                self.unit_pool.recycle(vec_back);
+                info_data!("sink {} send batch rec suc!", name);
             }
         }
-
-        Ok(processed_count)
+        for leftover in per_sink_units.into_iter() {
+            self.unit_pool.recycle(leftover);
+        }
+        Ok(())
     }
 
     // heavy OML pipeline helpers are moved to dispatcher::oml
@@ -8,7 +8,8 @@ use crate::sinks::prelude::*;
 use crate::sinks::ProcMeta;
 use crate::sinks::SinkRecUnit;
 use crate::sinks::SinkRuntime;
-use oml::core::DataTransformer;
+use crate::stat::trace::{self, TraceStage};
+use oml::core::{AggregateEvaluator, DataTransformer};
 use oml::language::{DataModel, ObjModel};
 // std::collections used to be required for HashMap-based fanout; kept minimal now
 use wp_connector_api::SinkResult;
@@ -31,7 +32,7 @@ enum OmlOutcome {
     Failure(DataRecord),
 }
 
-struct TransformedRecUnit {
+pub(super) struct TransformedRecUnit {
     pkg_id: PkgID,
     meta: ProcMeta,
     record: DataRecord,
@@ -56,7 +57,7 @@ impl SinkDispatcher {
         self.sinks.iter().any(|sink| sink.get_cond().is_some())
     }
     // OML model selection by rule
-    fn get_match_oml(&self, rule: &ProcMeta) -> Option<&ObjModel> {
+    pub(super) fn get_match_oml(&self, rule: &ProcMeta) -> Option<&ObjModel> {
         for mdl in self.res.aggregate_mdl() {
             if let (DataModel::Object(om), ProcMeta::Rule(r)) = (mdl, rule) {
                 for w_rule in om.rules().as_ref() {
@@ -81,7 +82,23 @@ impl SinkDispatcher {
         };
 
         let original_len = input.items.len();
-        let output = om_ins.transform(input, cache);
+        let output = match om_ins.try_transform_ref(&input, cache) {
+            Ok(output) => output,
+            Err(e) => {
+                let mut failed = input;
+                failed.append(DataField::from_chars("__assert", e.reason().to_string()));
+                Self::annotate_err(
+                    &mut failed,
+                    "oml_assert_failed",
+                    rule,
+                    self.conf.name(),
+                    om_ins.name(),
+                    original_len,
+                    0,
+                );
+                return Ok(OmlOutcome::Failure(failed));
+            }
+        };
         if output.items.is_empty() {
             let mut failed = output.clone();
             Self::annotate_err(
@@ -117,19 +134,62 @@ impl SinkDispatcher {
             return Ok((passthrough, Vec::new()));
         };
 
+        Ok(Self::transform_units(
+            om_ins,
+            self.conf.name(),
+            wpl_meta,
+            input,
+            cache,
+        ))
+    }
+
+    /// 逐条执行 OML 转换，返回（成功记录, 带诊断字段的失败记录）；
+    /// 不依赖分发器状态，组内转换线程复用同一实现
+    pub(super) fn transform_units(
+        om_ins: &ObjModel,
+        group: &str,
+        wpl_meta: &ProcMeta,
+        input: Vec<SinkRecUnit>,
+        cache: &mut FieldQueryCache,
+    ) -> (Vec<TransformedRecUnit>, Vec<SinkRecUnit>) {
         let mut successes = Vec::with_capacity(input.len());
         let mut failures = Vec::new();
         for unit in input {
             let (event_id, meta, record_arc) = unit.into_parts();
             let original_len = record_arc.items.len();
-            let output = om_ins.transform_ref(record_arc.as_ref(), cache);
+            trace::span(event_id, TraceStage::TransformStart, om_ins.name());
+            let transformed = om_ins.try_transform_ref(record_arc.as_ref(), cache);
+            trace::span(event_id, TraceStage::TransformEnd, om_ins.name());
+            let output = match transformed {
+                Ok(output) => output,
+                Err(e) => {
+                    let mut failed = record_arc.as_ref().clone();
+                    failed.append(DataField::from_chars("__assert", e.reason().to_string()));
+                    Self::annotate_err(
+                        &mut failed,
+                        "oml_assert_failed",
+                        wpl_meta,
+                        group,
+                        om_ins.name(),
+                        original_len,
+                        0,
+                    );
+                    warn_data!("oml assert fail!{},{}", event_id, failed.to_string());
+                    failures.push(SinkRecUnit::with_record(
+                        event_id,
+                        meta.clone(),
+                        Arc::new(failed),
+                    ));
+                    continue;
+                }
+            };
             if output.items.is_empty() {
                 let mut failed = output.clone();
                 Self::annotate_err(
                     &mut failed,
                     "oml_transform_empty",
                     wpl_meta,
-                    self.conf.name(),
+                    group,
                     om_ins.name(),
                     original_len,
                     output.items.len(),
@@ -145,7 +205,7 @@ impl SinkDispatcher {
                 successes.push(TransformedRecUnit::new(event_id, meta, output));
             }
         }
-        Ok((successes, failures))
+        (successes, failures)
     }
 
     // 为错误记录添加标准诊断字段
@@ -178,6 +238,7 @@ impl SinkDispatcher {
             "oml_transform_empty" => {
                 "OML 输出为空；请检查模型 rules 是否匹配当前 WPL 路径，以及 read/take 字段名是否存在"
             }
+            "oml_assert_failed" => "OML assert 断言失败（严格模式）；详见 __assert 字段",
             "oml_transform_nochange" => {
                 "OML 输出与输入一致；可能规则未生效或字段映射缺失，请核对字段与类型转换"
             }
@@ -224,6 +285,22 @@ impl SinkDispatcher {
             let record = Arc::try_unwrap(bad_arc).unwrap_or_else(|arc| arc.as_ref().clone());
             self.emit_oml_failure(pkg_id, infra, rule, record)?;
         }
+        // 含 group_by 的模型：转换结果进入聚合窗口，由 flush 周期统一输出汇总记录
+        if let Some((name, op)) = self
+            .get_match_oml(rule)
+            .and_then(|om| om.group_by().map(|op| (om.name().clone(), op.clone())))
+        {
+            let agg = self
+                .groups
+                .entry(name)
+                .or_insert_with(|| AggregateEvaluator::new(op));
+            for entry in successes {
+                agg.feed(&entry.record);
+            }
+            return Ok((0..self.sinks.len())
+                .map(|_| self.unit_pool.take())
+                .collect());
+        }
         Ok(self.fanout_transformed_batch(successes))
     }
 
@@ -245,7 +322,10 @@ impl SinkDispatcher {
         outputs
     }
 
-    fn emit_without_transform_batch(&mut self, entries: Vec<SinkRecUnit>) -> Vec<Vec<SinkRecUnit>> {
+    pub(super) fn emit_without_transform_batch(
+        &mut self,
+        entries: Vec<SinkRecUnit>,
+    ) -> Vec<Vec<SinkRecUnit>> {
         let mut per_sink: Vec<Vec<SinkRecUnit>> = (0..self.sinks.len())
             .map(|_| self.unit_pool.take())
             .collect();
@@ -301,7 +381,7 @@ impl SinkDispatcher {
         Ok(outputs)
     }
 
-    fn fanout_transformed_batch(
+    pub(super) fn fanout_transformed_batch(
         &mut self,
         entries: Vec<TransformedRecUnit>,
     ) -> Vec<Vec<SinkRecUnit>> {
@@ -315,7 +395,7 @@ impl SinkDispatcher {
         per_sink
     }
 
-    fn push_transformed_record(
+    pub(super) fn push_transformed_record(
         &self,
         pkg_id: PkgID,
         meta: ProcMeta,
@@ -382,7 +462,7 @@ impl SinkDispatcher {
         }
     }
 
-    fn emit_oml_failure(
+    pub(super) fn emit_oml_failure(
         &self,
         pkg_id: PkgID,
         infra: &InfraSinkAgent,
@@ -1,8 +1,7 @@
 use super::SinkDispatcher;
 use crate::sinks::ASinkHandle;
 use crate::stat::MonSend;
-use wp_connector_api::AsyncCtrl;
-use wp_connector_api::SinkResult; // trait for stop()
+use wp_connector_api::SinkResult;
 
 impl SinkDispatcher {
     pub async fn proc_fix(
@@ -19,9 +18,10 @@ impl SinkDispatcher {
         Ok(Some(sink_h))
     }
 
+    /// 停止组内各 sink（drain 期间受时限约束）；应在最终 flush 之后、最终统计之前调用
     pub async fn proc_end(&mut self) -> SinkResult<()> {
         for sink_rt in self.sinks.iter_mut() {
-            sink_rt.primary.stop().await?;
+            sink_rt.stop().await?;
         }
         Ok(())
     }
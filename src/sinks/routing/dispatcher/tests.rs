@@ -380,4 +380,239 @@ converted : chars = chars(done) ;
     }
 }
 
+#[test]
+fn hot_reload_swaps_model_between_packages() {
+    use crate::resources::{RuleSet, RuleSetSlot, SinkID};
+    use std::collections::HashMap;
+    use wp_model_core::model::DataField;
+
+    fn model_of(version: &str) -> DataModel {
+        let code = format!(
+            "name : reload_model\nrule :\n    /reload/rule\n---\nversion : chars = chars({}) ;\n",
+            version
+        );
+        DataModel::Object(oml_parse_raw(&mut code.as_str()).expect("parse oml model"))
+    }
+    fn rule_set(version: &str) -> RuleSet {
+        let mut models = HashMap::new();
+        models.insert(SinkID::from("reload"), vec![model_of(version)]);
+        RuleSet::new(Vec::new(), models)
+    }
+
+    let slot = Arc::new(RuleSetSlot::default());
+    slot.init(rule_set("v1"));
+    let mut sink_res = SinkResUnit::default();
+    sink_res.push_model(model_of("v1"));
+    let sink_res = sink_res.bind_rule_slot(SinkID::from("reload"), slot.clone());
+
+    let mut group = FlexGroup::default();
+    group.name = "reload".to_string();
+    let mut dispatcher = SinkDispatcher::new(SinkGroupConf::Flexi(group), sink_res);
+    let sink_conf = SinkInstanceConf::null_new("sink".to_string(), TextFmt::Json, None);
+    dispatcher.append(SinkRuntime::new(
+        "./rescue".to_string(),
+        "sink".to_string(),
+        sink_conf,
+        SinkBackendType::Proxy(crate::sinks::builtin_factories::make_blackhole_sink()),
+        None,
+        Vec::new(),
+    ));
+
+    let rule = crate::sinks::ProcMeta::Rule("/reload/rule".to_string());
+    let mut cache = FieldQueryCache::default();
+    let mut run = |dispatcher: &mut SinkDispatcher, id: u64| {
+        dispatcher.refresh_models();
+        let mut rec = DataRecord::default();
+        rec.append(DataField::from_chars("src", "x"));
+        let batch = vec![SinkRecUnit::with_record(id, rule.clone(), Arc::new(rec))];
+        let outputs = dispatcher
+            .oml_proc_batch(batch, &InfraSinkAgent::use_null(), &mut cache, &rule)
+            .unwrap();
+        match outputs[0][0].data().get_value("version") {
+            Some(Value::Chars(v)) => v.to_string(),
+            other => panic!("unexpected version field: {:?}", other),
+        }
+    };
+
+    assert_eq!(run(&mut dispatcher, 1), "v1");
+    assert!(!dispatcher.refresh_models());
+    // 处理中的一代在替换后仍然可用
+    let in_flight = slot.load();
+    slot.swap(rule_set("v2"));
+    assert_eq!(run(&mut dispatcher, 2), "v2");
+    assert_eq!(run(&mut dispatcher, 3), "v2");
+    assert_eq!(in_flight.models_of(&SinkID::from("reload")).len(), 1);
+}
+
+#[test]
+fn batch_group_by_model_buffers_into_aggregate() {
+    use wp_model_core::model::DataField;
+
+    let mut sink_res = SinkResUnit::use_null();
+    let mut code = r#"
+name : group_model
+rule :
+    /group/rule
+---
+src = read(src) ;
+bytes : digit = read(bytes) ;
+group_by (src) every 60s {
+    hits = count();
+    total = sum(bytes);
+}
+"#;
+    let model = oml_parse_raw(&mut code).expect("parse oml model");
+    sink_res.push_model(DataModel::Object(model));
+
+    let mut group = FlexGroup::default();
+    group.name = "group".to_string();
+    let mut dispatcher = SinkDispatcher::new(SinkGroupConf::Flexi(group), sink_res);
+    let sink_conf = SinkInstanceConf::null_new("sink".to_string(), TextFmt::Json, None);
+    dispatcher.append(SinkRuntime::new(
+        "./rescue".to_string(),
+        "sink".to_string(),
+        sink_conf,
+        SinkBackendType::Proxy(crate::sinks::builtin_factories::make_blackhole_sink()),
+        None,
+        Vec::new(),
+    ));
+
+    let rule = crate::sinks::ProcMeta::Rule("/group/rule".to_string());
+    let batch = [("a", 3), ("b", 1), ("a", 4)]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (src, bytes))| {
+            let mut rec = DataRecord::default();
+            rec.append(DataField::from_chars("src", src));
+            rec.append(DataField::from_digit("bytes", bytes));
+            SinkRecUnit::with_record(idx as u64, rule.clone(), Arc::new(rec))
+        })
+        .collect();
+
+    let mut cache = FieldQueryCache::default();
+    let outputs = dispatcher
+        .oml_proc_batch(batch, &InfraSinkAgent::use_null(), &mut cache, &rule)
+        .unwrap();
+    // 聚合模型不逐条下发
+    assert_eq!(outputs.len(), 1);
+    assert!(outputs[0].is_empty());
+
+    let agg = dispatcher.groups.get_mut("group_model").expect("aggregate");
+    assert_eq!(agg.group_count(), 2);
+    let summaries = agg.drain(std::time::Instant::now());
+    assert_eq!(summaries.len(), 2);
+    assert!(matches!(
+        summaries[0].get_value("hits"),
+        Some(Value::Digit(2))
+    ));
+    assert!(matches!(
+        summaries[0].get_value("total"),
+        Some(Value::Digit(7))
+    ));
+    assert!(matches!(
+        summaries[1].get_value("hits"),
+        Some(Value::Digit(1))
+    ));
+}
+
 // 隐私相关逻辑与字段已移除：对应行为测试一并删除
+
+fn worker_dispatcher(workers: usize, preserve_order: bool) -> SinkDispatcher {
+    let mut sink_res = SinkResUnit::use_null();
+    let mut code = r#"
+name : worker_model
+rule :
+    /worker/rule
+---
+idx : digit = read(idx) ;
+label = read(src) ;
+tag : chars = chars(done) ;
+"#;
+    let model = oml_parse_raw(&mut code).expect("parse oml model");
+    sink_res.push_model(DataModel::Object(model));
+
+    let mut group = FlexGroup::default();
+    group.name = "worker".to_string();
+    group.workers = workers;
+    group.preserve_order = preserve_order;
+    let mut dispatcher = SinkDispatcher::new(SinkGroupConf::Flexi(group), sink_res);
+    let sink_conf = SinkInstanceConf::null_new("sink".to_string(), TextFmt::Json, None);
+    dispatcher.append(SinkRuntime::new(
+        "./rescue".to_string(),
+        "sink".to_string(),
+        sink_conf,
+        SinkBackendType::Proxy(crate::sinks::builtin_factories::make_blackhole_sink()),
+        None,
+        Vec::new(),
+    ));
+    dispatcher.start_workers(None);
+    dispatcher
+}
+
+fn worker_batch(rule: &crate::sinks::ProcMeta, count: usize) -> Vec<SinkRecUnit> {
+    use wp_model_core::model::DataField;
+    (0..count)
+        .map(|idx| {
+            let mut rec = DataRecord::default();
+            rec.append(DataField::from_digit("idx", idx as i64));
+            rec.append(DataField::from_chars("src", format!("host-{}", idx % 7)));
+            SinkRecUnit::with_record(idx as u64, rule.clone(), Arc::new(rec))
+        })
+        .collect()
+}
+
+fn render(units: &[SinkRecUnit]) -> Vec<(u64, String)> {
+    units
+        .iter()
+        .map(|unit| (*unit.id(), unit.data().to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn parallel_workers_match_serial_output_in_order() {
+    let rule = crate::sinks::ProcMeta::Rule("/worker/rule".to_string());
+    let infra = InfraSinkAgent::use_null();
+
+    let mut serial = worker_dispatcher(1, false);
+    let mut cache = FieldQueryCache::default();
+    let expect = serial
+        .oml_proc_batch(worker_batch(&rule, 257), &infra, &mut cache, &rule)
+        .unwrap();
+
+    let mut parallel = worker_dispatcher(4, true);
+    assert!(parallel.use_workers(&rule, 257));
+    for _ in 0..3 {
+        let outputs = parallel
+            .oml_proc_batch_parallel(worker_batch(&rule, 257), &infra, &rule)
+            .await
+            .unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(render(&outputs[0]), render(&expect[0]));
+    }
+}
+
+#[tokio::test]
+async fn parallel_workers_without_order_keep_every_record() {
+    let rule = crate::sinks::ProcMeta::Rule("/worker/rule".to_string());
+    let infra = InfraSinkAgent::use_null();
+
+    let mut serial = worker_dispatcher(1, false);
+    assert!(!serial.use_workers(&rule, 100));
+    let mut cache = FieldQueryCache::default();
+    let mut expect = render(
+        &serial
+            .oml_proc_batch(worker_batch(&rule, 100), &infra, &mut cache, &rule)
+            .unwrap()[0],
+    );
+
+    let mut parallel = worker_dispatcher(3, false);
+    let mut got = render(
+        &parallel
+            .oml_proc_batch_parallel(worker_batch(&rule, 100), &infra, &rule)
+            .await
+            .unwrap()[0],
+    );
+    expect.sort();
+    got.sort();
+    assert_eq!(got, expect);
+}
@@ -0,0 +1,303 @@
+//! 组内并行 OML 转换。
+//!
+//! `workers > 1` 时，同一规则的批次按块分发到 N 个常驻转换线程（各自持有
+//! `FieldQueryCache`，并在线程内完成 knowdb 就绪），结果回到组任务后合并再路由下发；
+//! `preserve_order = true` 时按块序号经重排缓冲还原输入顺序。
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::sync::mpsc as std_mpsc;
+use std::thread::JoinHandle;
+
+use oml::language::ObjModel;
+use tokio::sync::mpsc;
+use wp_connector_api::{SinkError, SinkReason, SinkResult};
+
+use super::SinkDispatcher;
+use super::oml::TransformedRecUnit;
+use crate::knowledge::KnowdbHandler;
+use crate::sinks::{InfraSinkAgent, ProcMeta, SinkRecUnit};
+use crate::stat::prometheus;
+
+struct TransformJob {
+    seq: u64,
+    model: Arc<ObjModel>,
+    meta: ProcMeta,
+    units: Vec<SinkRecUnit>,
+}
+
+struct TransformDone {
+    seq: u64,
+    successes: Vec<TransformedRecUnit>,
+    failures: Vec<SinkRecUnit>,
+}
+
+/// 按序号重排：仅当序号连续时放出结果
+pub(crate) struct ReorderBuffer<T> {
+    next: u64,
+    pending: BTreeMap<u64, T>,
+}
+
+impl<T> ReorderBuffer<T> {
+    pub(crate) fn new(first: u64) -> Self {
+        Self {
+            next: first,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, seq: u64, item: T) -> Vec<T> {
+        self.pending.insert(seq, item);
+        let mut ready = Vec::new();
+        while let Some(item) = self.pending.remove(&self.next) {
+            ready.push(item);
+            self.next += 1;
+        }
+        ready
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+pub(crate) struct TransformPool {
+    jobs: Vec<std_mpsc::Sender<TransformJob>>,
+    done_r: mpsc::UnboundedReceiver<TransformDone>,
+    handles: Vec<JoinHandle<()>>,
+    // 模型名 -> 共享给转换线程的模型快照；热重载切换后清空
+    models: HashMap<String, Arc<ObjModel>>,
+    preserve_order: bool,
+    next_seq: u64,
+}
+
+impl TransformPool {
+    pub(crate) fn new(
+        group: &str,
+        workers: usize,
+        preserve_order: bool,
+        knowdb: Option<Arc<KnowdbHandler>>,
+    ) -> std::io::Result<Self> {
+        let (done_s, done_r) = mpsc::unbounded_channel();
+        let mut jobs = Vec::with_capacity(workers);
+        let mut handles = Vec::with_capacity(workers);
+        for idx in 0..workers {
+            let (job_s, job_r) = std_mpsc::channel::<TransformJob>();
+            let group = group.to_string();
+            let done_s = done_s.clone();
+            let knowdb = knowdb.clone();
+            let counter = prometheus::sink_worker_records(&group, idx);
+            let handle = std::thread::Builder::new()
+                .name(format!("oml-{}-{}", group, idx))
+                .spawn(move || {
+                    if let Some(handler) = knowdb.as_ref() {
+                        handler.ensure_thread_ready();
+                    }
+                    let mut cache = oml::core::lookup_cache::new_cache();
+                    while let Ok(job) = job_r.recv() {
+                        let count = job.units.len() as u64;
+                        let (successes, failures) = SinkDispatcher::transform_units(
+                            &job.model, &group, &job.meta, job.units, &mut cache,
+                        );
+                        counter.add(count);
+                        let done = TransformDone {
+                            seq: job.seq,
+                            successes,
+                            failures,
+                        };
+                        if done_s.send(done).is_err() {
+                            break;
+                        }
+                    }
+                })?;
+            jobs.push(job_s);
+            handles.push(handle);
+        }
+        Ok(Self {
+            jobs,
+            done_r,
+            handles,
+            models: HashMap::new(),
+            preserve_order,
+            next_seq: 0,
+        })
+    }
+
+    pub(crate) fn reset_models(&mut self) {
+        self.models.clear();
+    }
+
+    /// 按线程数均分为若干块依次投递，返回首块序号与块数
+    fn submit(
+        &mut self,
+        model: Arc<ObjModel>,
+        meta: &ProcMeta,
+        units: Vec<SinkRecUnit>,
+    ) -> SinkResult<(u64, usize)> {
+        let chunk = units.len().div_ceil(self.jobs.len()).max(1);
+        let first = self.next_seq;
+        let mut iter = units.into_iter();
+        let mut sent = 0;
+        loop {
+            let part: Vec<SinkRecUnit> = iter.by_ref().take(chunk).collect();
+            if part.is_empty() {
+                break;
+            }
+            let job = TransformJob {
+                seq: self.next_seq,
+                model: model.clone(),
+                meta: meta.clone(),
+                units: part,
+            };
+            self.jobs[sent % self.jobs.len()]
+                .send(job)
+                .map_err(|_| SinkError::from(SinkReason::Sink("oml worker exited".into())))?;
+            self.next_seq += 1;
+            sent += 1;
+        }
+        Ok((first, sent))
+    }
+
+    async fn recv(&mut self) -> SinkResult<TransformDone> {
+        self.done_r
+            .recv()
+            .await
+            .ok_or_else(|| SinkError::from(SinkReason::Sink("oml workers exited".into())))
+    }
+}
+
+impl Drop for TransformPool {
+    fn drop(&mut self) {
+        // 关闭任务通道后线程自行退出
+        self.jobs.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl SinkDispatcher {
+    /// 按组配置启动转换线程；`workers <= 1` 时保持组任务内串行转换
+    pub fn start_workers(&mut self, knowdb: Option<Arc<KnowdbHandler>>) {
+        let wp_conf::structure::SinkGroupConf::Flexi(group) = &self.conf else {
+            return;
+        };
+        let workers = group.worker_cnt();
+        if workers <= 1 {
+            return;
+        }
+        match TransformPool::new(group.name(), workers, group.preserve_order, knowdb) {
+            Ok(pool) => {
+                info_ctrl!(
+                    "sink group {} start {} oml workers (preserve_order={})",
+                    group.name(),
+                    workers,
+                    group.preserve_order
+                );
+                self.workers = Some(pool);
+            }
+            Err(e) => {
+                warn_ctrl!(
+                    "sink group {} start oml workers failed, fallback to serial: {}",
+                    group.name(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// 是否走并行转换：需启用线程池、命中非聚合模型且批次可拆分
+    pub(super) fn use_workers(&self, rule: &ProcMeta, batch_len: usize) -> bool {
+        self.workers.is_some()
+            && batch_len > 1
+            && self
+                .get_match_oml(rule)
+                .is_some_and(|om| om.group_by().is_none())
+    }
+
+    /// `oml_proc_batch` 的并行版本：转换由线程池完成，失败记录与路由下发仍在组任务内进行
+    pub(super) async fn oml_proc_batch_parallel(
+        &mut self,
+        batch: Vec<SinkRecUnit>,
+        infra: &InfraSinkAgent,
+        rule: &ProcMeta,
+    ) -> SinkResult<Vec<Vec<SinkRecUnit>>> {
+        let Some(name) = self.get_match_oml(rule).map(|om| om.name().clone()) else {
+            return Ok(self.emit_without_transform_batch(batch));
+        };
+        let cached = self
+            .workers
+            .as_ref()
+            .and_then(|pool| pool.models.get(&name).cloned());
+        let model = match cached {
+            Some(model) => model,
+            None => {
+                let Some(om) = self.get_match_oml(rule) else {
+                    return Ok(self.emit_without_transform_batch(batch));
+                };
+                let model = Arc::new(om.clone());
+                if let Some(pool) = self.workers.as_mut() {
+                    pool.models.insert(name, model.clone());
+                }
+                model
+            }
+        };
+        let Some(pool) = self.workers.as_mut() else {
+            return Ok(self.emit_without_transform_batch(batch));
+        };
+        let preserve_order = pool.preserve_order;
+        let total = batch.len();
+        let (first, jobs) = pool.submit(model, rule, batch)?;
+
+        let mut reorder = ReorderBuffer::new(first);
+        let mut successes = Vec::with_capacity(total);
+        let mut failed = None;
+        for _ in 0..jobs {
+            let Some(pool) = self.workers.as_mut() else {
+                break;
+            };
+            let done = pool.recv().await?;
+            // 失败记录下发出错后仍须收回本批其余任务，否则其结果会混入下一批
+            if failed.is_some() {
+                continue;
+            }
+            let ready = if preserve_order {
+                reorder.push(done.seq, done)
+            } else {
+                vec![done]
+            };
+            'parts: for part in ready {
+                for bad in part.failures {
+                    let (pkg_id, _, bad_arc) = bad.into_parts();
+                    let record =
+                        Arc::try_unwrap(bad_arc).unwrap_or_else(|arc| arc.as_ref().clone());
+                    if let Err(e) = self.emit_oml_failure(pkg_id, infra, rule, record) {
+                        failed = Some(e);
+                        break 'parts;
+                    }
+                }
+                successes.extend(part.successes);
+            }
+        }
+        if let Some(e) = failed {
+            return Err(e);
+        }
+        debug_assert!(reorder.is_empty());
+        Ok(self.fanout_transformed_batch(successes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReorderBuffer;
+
+    #[test]
+    fn reorder_buffer_releases_contiguous_items() {
+        let mut buf = ReorderBuffer::new(5);
+        assert!(buf.push(7, "c").is_empty());
+        assert!(buf.push(6, "b").is_empty());
+        assert_eq!(buf.push(5, "a"), vec!["a", "b", "c"]);
+        assert!(buf.is_empty());
+        assert_eq!(buf.push(8, "d"), vec!["d"]);
+    }
+}
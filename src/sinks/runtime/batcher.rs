@@ -0,0 +1,136 @@
+//! Sink 自适应批量（AIMD）。
+//!
+//! 以单批下发耗时为反馈：满批且耗时未超过目标时加性增长（每次 `max_batch / 64`），
+//! 耗时超过目标时乘性收缩（×3/4），批量始终限制在 `[min_batch, max_batch]` 内。
+//! 未满批（输入较慢、由超时 flush 触发）的批次不参与增长，避免空闲时批量无限放大。
+
+use std::time::Duration;
+
+use wp_conf::structure::AdaptiveBatchConf;
+
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatcher {
+    target: Duration,
+    min_batch: usize,
+    max_batch: usize,
+    step: usize,
+    current: usize,
+    last_latency: Option<Duration>,
+}
+
+impl AdaptiveBatcher {
+    /// `initial` 通常取组配置的 `batch_size`，会被裁剪到上下限之内
+    pub fn new(conf: &AdaptiveBatchConf, initial: usize) -> Self {
+        let min_batch = conf.min_batch.max(1);
+        let max_batch = conf.max_batch.max(min_batch);
+        Self {
+            target: Duration::from_millis(conf.target_latency_ms.max(1)),
+            min_batch,
+            max_batch,
+            step: (max_batch / 64).max(1),
+            current: initial.clamp(min_batch, max_batch),
+            last_latency: None,
+        }
+    }
+
+    /// 当前批量
+    pub fn batch_size(&self) -> usize {
+        self.current
+    }
+
+    /// 最近一次成功下发的耗时
+    pub fn last_latency(&self) -> Option<Duration> {
+        self.last_latency
+    }
+
+    /// 记录一次成功下发（`sent` 条，耗时 `elapsed`），返回调整后的批量
+    pub fn observe(&mut self, sent: usize, elapsed: Duration) -> usize {
+        self.last_latency = Some(elapsed);
+        if elapsed > self.target {
+            self.current = (self.current * 3 / 4).max(self.min_batch);
+        } else if sent >= self.current {
+            self.current = (self.current + self.step).min(self.max_batch);
+        }
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf(target_ms: u64, min_batch: usize, max_batch: usize) -> AdaptiveBatchConf {
+        AdaptiveBatchConf {
+            target_latency_ms: target_ms,
+            min_batch,
+            max_batch,
+        }
+    }
+
+    /// 模拟 sink：固定开销 5ms + 每条 0.2ms
+    fn simulated_latency(batch: usize) -> Duration {
+        Duration::from_micros(5_000 + 200 * batch as u64)
+    }
+
+    #[test]
+    fn converges_around_target_latency() {
+        let mut batcher = AdaptiveBatcher::new(&conf(200, 16, 8192), 16);
+        // 目标 200ms 对应的理想批量
+        let ideal = (200_000 - 5_000) / 200;
+        for _ in 0..300 {
+            let n = batcher.batch_size();
+            batcher.observe(n, simulated_latency(n));
+        }
+        for _ in 0..100 {
+            let n = batcher.batch_size();
+            assert!(
+                n >= ideal * 3 / 4 - 1 && n <= ideal + 128,
+                "batch {} out of band around {}",
+                n,
+                ideal
+            );
+            batcher.observe(n, simulated_latency(n));
+            assert!(batcher.last_latency().unwrap() <= Duration::from_millis(231));
+        }
+    }
+
+    #[test]
+    fn shrinks_when_sink_slows_down() {
+        let mut batcher = AdaptiveBatcher::new(&conf(200, 16, 8192), 4096);
+        let before = batcher.batch_size();
+        for _ in 0..50 {
+            let n = batcher.batch_size();
+            // sink 变慢：每条 1ms
+            batcher.observe(n, Duration::from_millis(n as u64));
+        }
+        assert!(batcher.batch_size() < before);
+        assert!(batcher.batch_size() <= 200 + 128);
+    }
+
+    #[test]
+    fn stays_within_bounds() {
+        let mut fast = AdaptiveBatcher::new(&conf(200, 16, 1024), 100);
+        for _ in 0..1000 {
+            let n = fast.batch_size();
+            fast.observe(n, Duration::from_millis(1));
+        }
+        assert_eq!(fast.batch_size(), 1024);
+
+        let mut slow = AdaptiveBatcher::new(&conf(200, 16, 1024), 100);
+        for _ in 0..100 {
+            let n = slow.batch_size();
+            slow.observe(n, Duration::from_secs(1));
+        }
+        assert_eq!(slow.batch_size(), 16);
+    }
+
+    #[test]
+    fn partial_batches_do_not_grow() {
+        let mut batcher = AdaptiveBatcher::new(&conf(200, 16, 8192), 256);
+        for _ in 0..100 {
+            // 输入较慢：超时 flush 下发的批次远小于当前批量
+            batcher.observe(10, Duration::from_millis(1));
+        }
+        assert_eq!(batcher.batch_size(), 256);
+    }
+}
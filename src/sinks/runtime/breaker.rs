@@ -0,0 +1,250 @@
+//! Sink 熔断与上游背压。
+//!
+//! 连续 `error_threshold` 个批次失败后熔断（Open）：冷却期内不再访问主 sink，并抬起
+//! 全局背压标志，source picker 观察到后放缓拉取；冷却到期后进入半开（HalfOpen），
+//! 仅用一个小批次探测，成功则恢复（Closed）并撤销背压，失败则重新熔断。
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use wp_conf::structure::SinkBreakerConf;
+
+/// 处于熔断状态的 sink 计数；大于 0 时 source 侧放缓拉取
+#[derive(Debug, Default)]
+pub struct SinkBackpressure {
+    open: AtomicUsize,
+}
+
+impl SinkBackpressure {
+    /// 进程级共享实例（sink 写入、source 读取）
+    pub fn global() -> &'static SinkBackpressure {
+        static GLOBAL: OnceLock<SinkBackpressure> = OnceLock::new();
+        GLOBAL.get_or_init(SinkBackpressure::default)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.open.load(Ordering::Relaxed) > 0
+    }
+
+    pub fn open_count(&self) -> usize {
+        self.open.load(Ordering::Relaxed)
+    }
+
+    fn raise(&self) {
+        self.open.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn release(&self) {
+        let _ = self
+            .open
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// 本次发送的放行结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerGate {
+    /// 正常发送
+    Pass,
+    /// 半开探测：仅发送前 N 条
+    Probe(usize),
+    /// 熔断中：不访问主 sink
+    Reject,
+}
+
+pub struct CircuitBreaker {
+    error_threshold: u32,
+    cooldown: Duration,
+    probe_size: usize,
+    state: BreakerState,
+    failures: u32,
+    opened_at: Option<Instant>,
+    trips: u64,
+    backpressure: &'static SinkBackpressure,
+}
+
+impl CircuitBreaker {
+    pub fn new(conf: &SinkBreakerConf) -> Self {
+        Self::with_backpressure(conf, SinkBackpressure::global())
+    }
+
+    pub fn with_backpressure(
+        conf: &SinkBreakerConf,
+        backpressure: &'static SinkBackpressure,
+    ) -> Self {
+        Self {
+            error_threshold: conf.error_threshold.max(1),
+            cooldown: Duration::from_secs(conf.cooldown_secs),
+            probe_size: conf.probe_size.max(1),
+            state: BreakerState::Closed,
+            failures: 0,
+            opened_at: None,
+            trips: 0,
+            backpressure,
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.state
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn trips(&self) -> u64 {
+        self.trips
+    }
+
+    pub fn gate(&mut self) -> BreakerGate {
+        self.gate_at(Instant::now())
+    }
+
+    fn gate_at(&mut self, now: Instant) -> BreakerGate {
+        match self.state {
+            BreakerState::Closed => BreakerGate::Pass,
+            BreakerState::HalfOpen => BreakerGate::Probe(self.probe_size),
+            BreakerState::Open => {
+                let elapsed = self
+                    .opened_at
+                    .map(|at| now.saturating_duration_since(at))
+                    .unwrap_or_default();
+                if elapsed >= self.cooldown {
+                    self.state = BreakerState::HalfOpen;
+                    BreakerGate::Probe(self.probe_size)
+                } else {
+                    BreakerGate::Reject
+                }
+            }
+        }
+    }
+
+    /// 记录一次成功；返回是否由半开恢复为关闭
+    pub fn on_success(&mut self) -> bool {
+        self.failures = 0;
+        if self.state == BreakerState::Closed {
+            return false;
+        }
+        self.state = BreakerState::Closed;
+        self.opened_at = None;
+        self.backpressure.release();
+        true
+    }
+
+    /// 记录一次失败；返回是否因此进入熔断
+    pub fn on_failure(&mut self) -> bool {
+        self.on_failure_at(Instant::now())
+    }
+
+    fn on_failure_at(&mut self, now: Instant) -> bool {
+        self.failures = self.failures.saturating_add(1);
+        match self.state {
+            BreakerState::Closed if self.failures >= self.error_threshold => {
+                self.state = BreakerState::Open;
+                self.opened_at = Some(now);
+                self.trips += 1;
+                self.backpressure.raise();
+                true
+            }
+            BreakerState::HalfOpen => {
+                // 探测失败：重新计时，背压保持
+                self.state = BreakerState::Open;
+                self.opened_at = Some(now);
+                self.trips += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Drop for CircuitBreaker {
+    fn drop(&mut self) {
+        if self.state != BreakerState::Closed {
+            self.backpressure.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaked_flag() -> &'static SinkBackpressure {
+        Box::leak(Box::new(SinkBackpressure::default()))
+    }
+
+    fn conf(threshold: u32, cooldown_secs: u64) -> SinkBreakerConf {
+        SinkBreakerConf {
+            error_threshold: threshold,
+            cooldown_secs,
+            probe_size: 2,
+        }
+    }
+
+    #[test]
+    fn opens_after_threshold_and_probes_after_cooldown() {
+        let flag = leaked_flag();
+        let mut cb = CircuitBreaker::with_backpressure(&conf(3, 10), flag);
+        let t0 = Instant::now();
+        assert!(!cb.on_failure_at(t0));
+        assert!(!cb.on_failure_at(t0));
+        assert!(!flag.is_active());
+        assert!(cb.on_failure_at(t0));
+        assert_eq!(cb.state(), BreakerState::Open);
+        assert!(flag.is_active());
+
+        assert_eq!(cb.gate_at(t0 + Duration::from_secs(5)), BreakerGate::Reject);
+        assert_eq!(
+            cb.gate_at(t0 + Duration::from_secs(10)),
+            BreakerGate::Probe(2)
+        );
+        assert_eq!(cb.state(), BreakerState::HalfOpen);
+
+        assert!(cb.on_success());
+        assert_eq!(cb.state(), BreakerState::Closed);
+        assert!(!flag.is_active());
+        assert_eq!(cb.gate_at(t0), BreakerGate::Pass);
+    }
+
+    #[test]
+    fn failed_probe_reopens_and_success_resets_count() {
+        let flag = leaked_flag();
+        let mut cb = CircuitBreaker::with_backpressure(&conf(2, 1), flag);
+        let t0 = Instant::now();
+        cb.on_failure_at(t0);
+        cb.on_success();
+        assert_eq!(cb.failures(), 0);
+        cb.on_failure_at(t0);
+        assert!(cb.on_failure_at(t0));
+
+        let t1 = t0 + Duration::from_secs(1);
+        assert_eq!(cb.gate_at(t1), BreakerGate::Probe(2));
+        assert!(cb.on_failure_at(t1));
+        assert_eq!(cb.state(), BreakerState::Open);
+        assert_eq!(cb.trips(), 2);
+        assert_eq!(flag.open_count(), 1);
+        assert_eq!(cb.gate_at(t1), BreakerGate::Reject);
+
+        drop(cb);
+        assert!(!flag.is_active());
+    }
+}
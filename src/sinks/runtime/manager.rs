@@ -8,13 +8,14 @@ use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
-use wp_conf::structure::default_batch_size;
+use wp_conf::structure::{AdaptiveBatchConf, default_batch_size};
 use wp_model_core::model::{DataField, fmt_def::TextFmt};
 
 // 全局计数器，用于生成唯一的救援文件序号
 static RESCUE_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
 
 use crate::runtime::errors::err4_send_to_sink;
+use crate::runtime::sink::drain::DrainSignal;
 use crate::sinks::RescueFileSink;
 use crate::sinks::{
     ASinkHandle, ASinkSender, ProcMeta, SinkBackendType, SinkDataEnum, SinkFFVPackage, SinkPackage,
@@ -22,6 +23,8 @@ use crate::sinks::{
 };
 use crate::stat::MonSend;
 use crate::stat::metric_collect::MetricCollectors;
+use crate::stat::prometheus::SinkCounters;
+use crate::stat::trace::{self, TraceStage};
 use wp_conf::structure::SinkInstanceConf;
 use wp_connector_api::{SinkReason, SinkResult};
 use wp_error::error_handling::{ErrorHandlingStrategy, sys_robust_mode};
@@ -34,7 +37,11 @@ use wp_stat::StatRecorder;
 use wp_stat::StatReq;
 use wp_stat::TimedStat;
 
+use super::batcher::AdaptiveBatcher;
+use super::breaker::{BreakerGate, BreakerState, CircuitBreaker};
 use super::stat::RuntimeStautus;
+use crate::sinks::decorators::schema::{FieldSchema, SchemaSink};
+use crate::sinks::decorators::shaped::{RecordShape, ShapedSink};
 
 #[derive(Getters)]
 pub struct SinkRuntime {
@@ -48,13 +55,55 @@ pub struct SinkRuntime {
     cond: Option<Expression<DataField, RustSymbol>>,
     batch_size: usize,
     pending_records: Vec<Arc<DataRecord>>,
+    // pending 缓冲中被追踪采样的记录 ID，随缓冲一起下发后打 SinkAck 点
+    #[getter(skip)]
+    pending_traced: Vec<u64>,
     status: RuntimeStautus,
     normal_stat: MetricCollectors,
     backup_stat: MetricCollectors,
+    #[getter(skip)]
+    prom: SinkCounters,
     timer: TimedStat,
     backup_used: bool,
     timer_poll_ticks: u8,
     last_stat_sent_at: Instant,
+    // 熔断器（仅在 sink 配置了 breaker 时启用）
+    breaker: Option<CircuitBreaker>,
+    // 熔断期间修复完成的主 sink，待冷却结束后再切回探测
+    parked: Option<SinkBackendType>,
+    last_breaker_report: Instant,
+    // 退出 drain 时限：到期后未写出的批次转入救援文件
+    #[getter(skip)]
+    drain: DrainSignal,
+    // 自适应批量（组配置 batch_mode = "adaptive" 时启用）
+    batcher: Option<AdaptiveBatcher>,
+    // 路由配置了 schema 时，其丢弃计数句柄；写出计数时扣除
+    #[getter(skip)]
+    schema_drops: Option<Arc<AtomicU64>>,
+}
+
+/// 追踪采样命中的记录进入 sink 缓冲时打点，并登记以便写出后确认
+fn trace_enqueue(sink: &str, event_id: u64, traced: &mut Vec<u64>) {
+    if trace::sampled(event_id) {
+        trace::span(event_id, TraceStage::SinkEnqueue, sink);
+        traced.push(event_id);
+    }
+}
+
+/// 在 drain 时限内执行一次下发：下发开始后才进入 drain 的同样受限，
+/// 时限到达返回 None（下发 future 随之丢弃）；`drain` 为 None 时不限时
+async fn within_drain<T>(
+    drain: Option<DrainSignal>,
+    fut: impl std::future::Future<Output = SinkResult<T>>,
+) -> Option<SinkResult<T>> {
+    let Some(drain) = drain else {
+        return Some(fut.await);
+    };
+    tokio::select! {
+        biased;
+        res = fut => Some(res),
+        _ = drain.expired() => None,
+    }
 }
 
 /// 批量发送错误处理结果
@@ -99,23 +148,84 @@ impl SinkRuntime {
         let backup_stat = MetricCollectors::new(backup_name.clone(), stat_reqs);
         info_ctrl!("create sink:{} batch_size={}", conf.full_name(), batch_size);
         let pre_tags = Self::compile_tags(&conf);
+        let breaker = conf.breaker.as_ref().map(CircuitBreaker::new);
+        let name: String = name.into();
+        let prom = SinkCounters::new(&name);
+        let (sink, schema_drops) = Self::shape_sink(&conf, sink, &prom);
 
         Self {
             rescue,
-            name: name.into(),
+            name,
             conf,
             pre_tags,
             primary: sink,
             cond,
             batch_size,
             pending_records: Vec::with_capacity(batch_size),
+            pending_traced: Vec::new(),
             normal_stat,
             backup_stat,
+            prom,
             status: RuntimeStautus::Ready,
             timer: TimedStat::new(),
             backup_used: false,
             timer_poll_ticks: 0,
             last_stat_sent_at: Instant::now(),
+            breaker,
+            parked: None,
+            last_breaker_report: Instant::now(),
+            drain: DrainSignal::default(),
+            batcher: None,
+            schema_drops,
+        }
+    }
+
+    /// 配置了 `format` 时在主 sink 外包一层字段排布，所属路由配置了 `schema` 时再在外层做类型约束
+    /// （按原始字段名检查，先于重命名，丢弃的记录计入 `prom.dropped`，并返回其丢弃计数句柄）；
+    /// 备份（rescue）路径保持原样
+    fn shape_sink(
+        conf: &SinkInstanceConf,
+        sink: SinkBackendType,
+        prom: &SinkCounters,
+    ) -> (SinkBackendType, Option<Arc<AtomicU64>>) {
+        let sink = match (conf.format.as_ref(), sink) {
+            (Some(format), SinkBackendType::Proxy(inner)) => SinkBackendType::Proxy(Box::new(
+                ShapedSink::new(RecordShape::from_conf(format), inner),
+            )),
+            (None, sink) => sink,
+        };
+        match (conf.schema.as_ref(), sink) {
+            (Some(schema), SinkBackendType::Proxy(inner)) => {
+                let sink =
+                    SchemaSink::new(FieldSchema::from_conf(schema), prom.dropped.clone(), inner);
+                let tally = sink.drop_tally();
+                (SinkBackendType::Proxy(Box::new(sink)), Some(tally))
+            }
+            (None, sink) => (sink, None),
+        }
+    }
+
+    /// 启用自适应批量：以组配置的 `batch_size` 为初始值，按下发耗时调整
+    pub fn with_adaptive_batch(mut self, conf: Option<&AdaptiveBatchConf>) -> Self {
+        if let Some(conf) = conf {
+            let batcher = AdaptiveBatcher::new(conf, self.batch_size);
+            self.batch_size = batcher.batch_size();
+            info_ctrl!(
+                "sink:{} adaptive batch target={}ms range=[{}, {}]",
+                self.name,
+                conf.target_latency_ms,
+                conf.min_batch,
+                conf.max_batch
+            );
+            self.batcher = Some(batcher);
+        }
+        self
+    }
+
+    /// 反馈一次成功的整批下发耗时，调整下一批的批量
+    fn observe_batch_latency(&mut self, sent: usize, elapsed: std::time::Duration) {
+        if let Some(batcher) = self.batcher.as_mut() {
+            self.batch_size = batcher.observe(sent, elapsed);
         }
     }
     // 将配置中的 tags 解析为去重后的字段列表（后写覆盖），以降低运行期构造开销
@@ -183,6 +293,88 @@ impl SinkRuntime {
                 .owe_sys()
                 .want("back sink stat")?;
         }
+        self.report_breaker();
+        Ok(())
+    }
+
+    /// 熔断未关闭时周期性输出熔断状态
+    fn report_breaker(&mut self) {
+        const BREAKER_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+        let Some(breaker) = self.breaker.as_ref() else {
+            return;
+        };
+        if breaker.state() == BreakerState::Closed
+            || self.last_breaker_report.elapsed() < BREAKER_REPORT_INTERVAL
+        {
+            return;
+        }
+        self.last_breaker_report = Instant::now();
+        info_mtrc!(
+            "sink {} breaker state={} failures={} trips={} parked={}",
+            self.name,
+            breaker.state().as_str(),
+            breaker.failures(),
+            breaker.trips(),
+            self.parked.is_some()
+        );
+    }
+
+    pub fn breaker_state(&self) -> Option<BreakerState> {
+        self.breaker.as_ref().map(|b| b.state())
+    }
+
+    /// 进入 drain：此后下发需在 `deadline` 前完成，否则批次写入救援文件（幂等，保留最早时限）
+    pub fn begin_drain(&mut self, deadline: Instant) {
+        self.drain.begin(deadline);
+    }
+
+    /// drain 时限信号，供旁路任务在 sink 阻塞于下发时设置时限
+    pub fn drain_signal(&self) -> DrainSignal {
+        self.drain.clone()
+    }
+
+    /// 当前下发适用的 drain 时限；已切到救援文件时不设限，避免写到一半被中断
+    fn drain_limit(&self) -> Option<DrainSignal> {
+        (!self.backup_used).then(|| self.drain.clone())
+    }
+
+    /// drain 超时：停止主 sink 并切换到救援文件，此后的下发均写入救援文件
+    async fn enter_drain_rescue(&mut self, pending: usize) -> SinkResult<()> {
+        if self.backup_used {
+            return Ok(());
+        }
+        // 未完成的批次整批转入救援文件，其中已被 schema 丢弃的不再扣除
+        self.take_schema_drops();
+        warn_data!(
+            "sink {} drain timeout, rescue {} pending records",
+            self.name,
+            pending
+        );
+        let old_primary = self
+            .swap_backsink()
+            .await
+            .map_err(|e| SinkError::from(SinkReason::Sink(e.to_string())))?;
+        self.backup_used = true;
+        // 时限已到：stop 只获得一次推进机会，阻塞的 sink 不会再拖延退出
+        for mut old in old_primary.into_iter().chain(self.parked.take()) {
+            match within_drain(Some(self.drain.clone()), old.stop()).await {
+                Some(Ok(())) => {}
+                Some(Err(e)) => warn_data!("sink {} stop after drain timeout: {}", self.name, e),
+                None => warn_data!("sink {} stop timed out after drain timeout", self.name),
+            }
+        }
+        Ok(())
+    }
+
+    /// drain 超时：将批次写入救援文件
+    async fn rescue_records(&mut self, records: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        self.enter_drain_rescue(records.len()).await?;
+        let count = records.len();
+        self.primary.sink_records(records.clone()).await?;
+        self.prom_delivered(count);
+        for record in records {
+            self.stat_end(&SinkDataEnum::Rec(ProcMeta::Rule("flush".into()), record));
+        }
         Ok(())
     }
 }
@@ -198,24 +390,39 @@ impl SinkRuntime {
         loop {
             let mut redo = false;
             self.stat_beg(&data);
+            let deadline = self.drain_limit();
             // 避免不必要的数据克隆，改为按引用下发
-            let result = match &data {
-                SinkDataEnum::Rec(_rule, dat) => self.primary.sink_record(dat).await,
+            let sent = match &data {
+                SinkDataEnum::Rec(_rule, dat) => {
+                    within_drain(deadline, self.primary.sink_record(dat)).await
+                }
                 SinkDataEnum::FFV(dat) => {
                     let raw = TextFmt::Raw
                         .gen_data(dat.clone())
                         .map_err(|e| SinkError::from(SinkReason::Sink(e.to_string())))?;
                     match raw {
-                        RawData::String(line) => self.primary.sink_str(&line).await,
-                        RawData::Bytes(bytes) => self.primary.sink_bytes(&bytes).await,
-                        RawData::ArcBytes(bytes) => self.primary.sink_bytes(&bytes).await,
+                        RawData::String(line) => {
+                            within_drain(deadline, self.primary.sink_str(&line)).await
+                        }
+                        RawData::Bytes(bytes) => {
+                            within_drain(deadline, self.primary.sink_bytes(&bytes)).await
+                        }
+                        RawData::ArcBytes(bytes) => {
+                            within_drain(deadline, self.primary.sink_bytes(&bytes)).await
+                        }
                     }
                 }
-                SinkDataEnum::Raw(dat) => self.primary.sink_str(dat).await,
+                SinkDataEnum::Raw(dat) => within_drain(deadline, self.primary.sink_str(dat)).await,
+            };
+            let Some(result) = sent else {
+                // drain 超时：切到救援文件后重发
+                self.enter_drain_rescue(1).await?;
+                continue;
             };
 
             //写入数据出错, 原因: sink 断连. 或 sink 失效. 处理的方案,只有重连.
             if let Err(e) = result {
+                self.prom.failed.inc();
                 match err4_send_to_sink(&e, &sys_robust_mode()) {
                     ErrorHandlingStrategy::FixRetry => {
                         if let Some(bad_sink_send) = bad_s {
@@ -243,6 +450,8 @@ impl SinkRuntime {
                 }
             } else {
                 self.stat_end(&data);
+                self.prom_delivered(1);
+                trace::span(event_id, TraceStage::SinkAck, &self.name);
                 debug_edata!(event_id, "sink {} send suc!", self.name);
             }
             if !redo {
@@ -270,7 +479,9 @@ impl SinkRuntime {
             &mut self.pending_records,
             Vec::with_capacity(self.batch_size),
         );
-        self.send_records_batch(records, bad_s, mon, true).await
+        let traced = std::mem::take(&mut self.pending_traced);
+        self.send_records_batch(records, traced, bad_s, mon, true)
+            .await
     }
 
     /// 直接发送当前 package（绕过 pending 缓冲）
@@ -281,16 +492,26 @@ impl SinkRuntime {
         mon: Option<&MonSend>,
     ) -> SinkResult<()> {
         let mut records = Vec::with_capacity(package.len());
+        let mut traced = Vec::new();
         for unit in package.iter() {
             records.push(unit.data().clone());
+            trace_enqueue(&self.name, *unit.id(), &mut traced);
+        }
+        self.send_records_batch(records, traced, bad_s, mon, false)
+            .await
+    }
+
+    fn trace_ack(&self, traced: &[u64]) {
+        for id in traced {
+            trace::span(*id, TraceStage::SinkAck, &self.name);
         }
-        self.send_records_batch(records, bad_s, mon, false).await
     }
 
     /// 发送一批 records；`requeue_on_throw=true` 时在 Throw 分支回填 pending 缓冲
     async fn send_records_batch(
         &mut self,
         records: Vec<Arc<DataRecord>>,
+        traced: Vec<u64>,
         bad_s: Option<&ASinkSender>,
         mon: Option<&MonSend>,
         requeue_on_throw: bool,
@@ -310,8 +531,79 @@ impl SinkRuntime {
         }
 
         loop {
-            match self.primary.sink_records(records.clone()).await {
+            let gate = self.breaker_gate().await?;
+            let guarded = !self.backup_used && self.breaker.is_some();
+            let result = match gate {
+                BreakerGate::Pass => {
+                    let started = Instant::now();
+                    match within_drain(
+                        self.drain_limit(),
+                        self.primary.sink_records(records.clone()),
+                    )
+                    .await
+                    {
+                        Some(result) => {
+                            if result.is_ok() && !self.backup_used {
+                                self.observe_batch_latency(records.len(), started.elapsed());
+                            }
+                            result
+                        }
+                        None => {
+                            self.rescue_records(records).await?;
+                            self.trace_ack(&traced);
+                            return Ok(());
+                        }
+                    }
+                }
+                BreakerGate::Reject => Err(self.circuit_open_error()),
+                BreakerGate::Probe(n) if n < records.len() => {
+                    // 半开探测：仅发送一个小批次，成功后其余记录回填 pending 等待下次 flush
+                    let probe = records[..n].to_vec();
+                    let Some(result) =
+                        within_drain(self.drain_limit(), self.primary.sink_records(probe)).await
+                    else {
+                        self.rescue_records(records).await?;
+                        self.trace_ack(&traced);
+                        return Ok(());
+                    };
+                    if result.is_ok() {
+                        self.observe_breaker(true);
+                        self.prom_delivered(n);
+                        self.pending_records.extend_from_slice(&records[n..]);
+                        // 无法区分探测批次中的追踪记录，统一留待下次 flush 确认
+                        self.pending_traced.extend(traced);
+                        // 回填的记录在下次 flush 时再结束统计
+                        for record in &records[..n] {
+                            self.stat_end(&SinkDataEnum::Rec(
+                                ProcMeta::Rule("flush".into()),
+                                record.clone(),
+                            ));
+                        }
+                        return Ok(());
+                    }
+                    result
+                }
+                BreakerGate::Probe(_) => match within_drain(
+                    self.drain_limit(),
+                    self.primary.sink_records(records.clone()),
+                )
+                .await
+                {
+                    Some(result) => result,
+                    None => {
+                        self.rescue_records(records).await?;
+                        self.trace_ack(&traced);
+                        return Ok(());
+                    }
+                },
+            };
+            if guarded && gate != BreakerGate::Reject {
+                self.observe_breaker(result.is_ok());
+            }
+            match result {
                 Ok(()) => {
+                    self.prom_delivered(records.len());
+                    self.trace_ack(&traced);
                     // 统计结束
                     for record in &records {
                         self.stat_end(&SinkDataEnum::Rec(
@@ -322,6 +614,7 @@ impl SinkRuntime {
                     return Ok(());
                 }
                 Err(e) => {
+                    self.prom.failed.add(records.len() as u64);
                     for e_id in &ids {
                         error_edata!(*e_id, "flush sink data failed: {}", e);
                     }
@@ -340,6 +633,7 @@ impl SinkRuntime {
                             if requeue_on_throw {
                                 // 失败时将数据放回 buffer
                                 self.pending_records = records;
+                                self.pending_traced = traced;
                                 // 统计结束 - 在放回 buffer 后，先克隆数据再调用 stat_end
                                 let buffer_copy: Vec<Arc<DataRecord>> =
                                     self.pending_records.clone();
@@ -376,14 +670,19 @@ impl SinkRuntime {
             return Ok(());
         }
 
-        // 自动策略：当 pending 为空且入站包已达到阈值，直接下发可减少无效缓冲开销
-        if self.pending_records.is_empty() && package.len() >= self.batch_size {
+        // 自动策略：当 pending 为空且入站包已达到阈值，直接下发可减少无效缓冲开销；
+        // 自适应批量需按当前批量切分，不走直通
+        if self.batcher.is_none()
+            && self.pending_records.is_empty()
+            && package.len() >= self.batch_size
+        {
             return self.send_package_bypass_buffer(package, bad_s, mon).await;
         }
 
         // 将 package 中的数据添加到 buffer
         for unit in package.iter() {
             self.pending_records.push(unit.data().clone());
+            trace_enqueue(&self.name, *unit.id(), &mut self.pending_traced);
 
             // 当 buffer 达到批次大小时自动 flush
             if self.pending_records.len() >= self.batch_size {
@@ -394,7 +693,7 @@ impl SinkRuntime {
         Ok(())
     }
 
-    /// 公开的 flush 方法，用于手动触发 buffer 刷新
+    /// 公开的 flush 方法，用于手动触发 buffer 刷新；drain 期间受时限约束，超时批次写入救援文件
     pub async fn flush(
         &mut self,
         bad_s: Option<&ASinkSender>,
@@ -403,6 +702,20 @@ impl SinkRuntime {
         self.flush_pending_buffer(bad_s, mon).await
     }
 
+    /// 停止当前 sink：后端在 stop 中写出自身缓冲，drain 期间同样受时限约束
+    pub async fn stop(&mut self) -> SinkResult<()> {
+        match within_drain(self.drain_limit(), self.primary.stop()).await {
+            Some(result) => result,
+            None => {
+                warn_data!(
+                    "sink {} stop exceeded drain timeout, buffered data may be lost",
+                    self.name
+                );
+                Ok(())
+            }
+        }
+    }
+
     /// 批量发送 FFV 数据包到 Sink
     pub async fn send_ffv_package_to_sink(
         &mut self,
@@ -431,32 +744,41 @@ impl SinkRuntime {
                 }
             }
 
-            let result = if !raw_strings.is_empty() {
+            let deadline = self.drain_limit();
+            let sent = if !raw_strings.is_empty() {
                 let refs: Vec<&str> = raw_strings.iter().map(|s| s.as_str()).collect();
-                self.primary.sink_str_batch(refs).await
+                within_drain(deadline, self.primary.sink_str_batch(refs)).await
             } else if !raw_bytes.is_empty() {
                 let refs: Vec<&[u8]> = raw_bytes.iter().map(|b| b.as_ref()).collect();
-                self.primary.sink_bytes_batch(refs).await
+                within_drain(deadline, self.primary.sink_bytes_batch(refs)).await
             } else {
-                Ok(())
+                Some(Ok(()))
+            };
+            let Some(result) = sent else {
+                self.enter_drain_rescue(package.len()).await?;
+                continue;
             };
 
             match result {
                 Ok(()) => {
+                    self.prom_delivered(package.len());
                     self.record_package_stats_end_ffv(&package);
                     return Ok(());
                 }
-                Err(e) => match self.handle_send_error(&e, bad_s, mon).await? {
-                    BatchErrHandle::Retry => continue,
-                    BatchErrHandle::Consume => {
-                        self.record_package_stats_end_ffv(&package);
-                        return Ok(());
-                    }
-                    BatchErrHandle::Throw => {
-                        self.record_package_stats_end_ffv(&package);
-                        return Err(e);
+                Err(e) => {
+                    self.prom.failed.add(package.len() as u64);
+                    match self.handle_send_error(&e, bad_s, mon).await? {
+                        BatchErrHandle::Retry => continue,
+                        BatchErrHandle::Consume => {
+                            self.record_package_stats_end_ffv(&package);
+                            return Ok(());
+                        }
+                        BatchErrHandle::Throw => {
+                            self.record_package_stats_end_ffv(&package);
+                            return Err(e);
+                        }
                     }
-                },
+                }
             }
         }
     }
@@ -475,24 +797,33 @@ impl SinkRuntime {
         self.record_package_stats_begin_str(&package);
         loop {
             let raw_strings: Vec<&str> = package.iter().map(|unit| unit.data().as_str()).collect();
-            let result = self.primary.sink_str_batch(raw_strings).await;
+            let Some(result) =
+                within_drain(self.drain_limit(), self.primary.sink_str_batch(raw_strings)).await
+            else {
+                self.enter_drain_rescue(package.len()).await?;
+                continue;
+            };
 
             match result {
                 Ok(()) => {
+                    self.prom_delivered(package.len());
                     self.record_package_stats_end_str(&package);
                     return Ok(());
                 }
-                Err(e) => match self.handle_send_error(&e, bad_s, mon).await? {
-                    BatchErrHandle::Retry => continue,
-                    BatchErrHandle::Consume => {
-                        self.record_package_stats_end_str(&package);
-                        return Ok(());
-                    }
-                    BatchErrHandle::Throw => {
-                        self.record_package_stats_end_str(&package);
-                        return Err(e);
+                Err(e) => {
+                    self.prom.failed.add(package.len() as u64);
+                    match self.handle_send_error(&e, bad_s, mon).await? {
+                        BatchErrHandle::Retry => continue,
+                        BatchErrHandle::Consume => {
+                            self.record_package_stats_end_str(&package);
+                            return Ok(());
+                        }
+                        BatchErrHandle::Throw => {
+                            self.record_package_stats_end_str(&package);
+                            return Err(e);
+                        }
                     }
-                },
+                }
             }
         }
     }
@@ -525,6 +856,77 @@ impl SinkRuntime {
         }
     }
 
+    /// 计算本次发送的熔断放行结果；主 sink 在修复中（写救援文件）时直接放行
+    async fn breaker_gate(&mut self) -> SinkResult<BreakerGate> {
+        let Some(breaker) = self.breaker.as_mut() else {
+            return Ok(BreakerGate::Pass);
+        };
+        if !self.backup_used {
+            return Ok(breaker.gate());
+        }
+        if self.parked.is_none() {
+            return Ok(BreakerGate::Pass);
+        }
+        match breaker.gate() {
+            // 冷却未结束：继续写救援文件
+            BreakerGate::Reject => Ok(BreakerGate::Pass),
+            gate => {
+                self.unpark_primary().await?;
+                Ok(gate)
+            }
+        }
+    }
+
+    fn observe_breaker(&mut self, success: bool) {
+        let Some(breaker) = self.breaker.as_mut() else {
+            return;
+        };
+        if success {
+            if breaker.on_success() {
+                info_data!("sink {} breaker closed after probe", self.name);
+            }
+        } else if breaker.on_failure() {
+            warn_data!(
+                "sink {} breaker open after {} consecutive failed batches (trips={})",
+                self.name,
+                breaker.failures(),
+                breaker.trips()
+            );
+        }
+    }
+
+    fn circuit_open_error(&self) -> SinkError {
+        SinkError::from(SinkReason::Sink(format!("sink {} circuit open", self.name)))
+    }
+
+    /// 冷却结束：切回修复完成的主 sink，关闭救援文件
+    async fn unpark_primary(&mut self) -> SinkResult<()> {
+        if let Some(parked) = self.parked.take() {
+            let mut rescue = std::mem::replace(&mut self.primary, parked);
+            rescue.stop().await?;
+            self.backup_used = false;
+        }
+        Ok(())
+    }
+
+    /// Prometheus 计数：成功写出 n 条；救急期间写入的是备份文件，计为 rescued。
+    /// 其中被 schema 丢弃的记录已计入 dropped，从 n 中扣除
+    fn prom_delivered(&self, n: usize) {
+        let n = (n as u64).saturating_sub(self.take_schema_drops());
+        if self.backup_used {
+            self.prom.rescued.add(n);
+        } else {
+            self.prom.sent.add(n);
+        }
+    }
+
+    /// 取走 schema 自上次结算以来的丢弃数
+    fn take_schema_drops(&self) -> u64 {
+        self.schema_drops
+            .as_ref()
+            .map_or(0, |tally| tally.swap(0, Ordering::Relaxed))
+    }
+
     /// 处理发送错误
     async fn handle_send_error(
         &mut self,
@@ -532,6 +934,8 @@ impl SinkRuntime {
         bad_s: Option<&ASinkSender>,
         mon: Option<&MonSend>,
     ) -> SinkResult<BatchErrHandle> {
+        // 失败批次会整批重试或转救援，其中已被 schema 丢弃的不再扣除
+        self.take_schema_drops();
         match err4_send_to_sink(error, &sys_robust_mode()) {
             ErrorHandlingStrategy::FixRetry => {
                 if let Some(bad_sink_send) = bad_s {
@@ -633,6 +1037,16 @@ impl SinkRuntime {
     }
     pub async fn recover_sink(&mut self, sink_h: ASinkHandle, mon: &MonSend) -> SinkResult<bool> {
         if self.name == sink_h.name {
+            if self
+                .breaker
+                .as_ref()
+                .is_some_and(|b| b.state() == BreakerState::Open)
+            {
+                // 熔断冷却中：暂存修复完成的 sink，冷却结束后再切回探测
+                self.parked = Some(sink_h.sink);
+                self.send_stat(mon).await?;
+                return Ok(true);
+            }
             let mut old_primary = std::mem::replace(&mut self.primary, sink_h.sink);
             old_primary.stop().await?;
             self.send_stat(mon).await?;
@@ -648,6 +1062,7 @@ mod tests {
     use super::*;
     use crate::sinks::ProcMeta;
     use crate::sinks::SinkRecUnit;
+    use crate::sinks::SinkStrUnit;
     use async_trait::async_trait;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use tempfile::tempdir;
@@ -812,6 +1227,308 @@ mod tests {
         Ok(())
     }
 
+    /// 下发永不返回的 sink；记录是否被 stop
+    struct StuckSink {
+        stopped: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for StuckSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            self.stopped.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for StuckSink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            std::future::pending().await
+        }
+
+        async fn sink_records(&mut self, _data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            std::future::pending().await
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawdatSink for StuckSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            std::future::pending().await
+        }
+
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            std::future::pending().await
+        }
+
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            std::future::pending().await
+        }
+
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            std::future::pending().await
+        }
+    }
+
+    /// 下发耗时与批量成正比的模拟 sink
+    struct SlowSink {
+        per_record: std::time::Duration,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for SlowSink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for SlowSink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            tokio::time::sleep(self.per_record).await;
+            Ok(())
+        }
+
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            tokio::time::sleep(self.per_record * data.len() as u32).await;
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawdatSink for SlowSink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn adaptive_batch_shrinks_for_slow_sink() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let conf = SinkInstanceConf::new_type(
+            "slow".into(),
+            TextFmt::Json,
+            "blackhole".into(),
+            Default::default(),
+            None,
+        );
+        let adaptive = AdaptiveBatchConf {
+            target_latency_ms: 10,
+            min_batch: 8,
+            max_batch: 1024,
+        };
+        let mut runtime = SinkRuntime::with_batch_size(
+            temp.path().display().to_string(),
+            "slow",
+            conf,
+            SinkBackendType::Proxy(Box::new(SlowSink {
+                per_record: std::time::Duration::from_micros(100),
+            })),
+            None,
+            Vec::new(),
+            512,
+        )
+        .with_adaptive_batch(Some(&adaptive));
+        assert_eq!(*runtime.batch_size(), 512);
+
+        // 大包也按当前批量切分下发，不走直通
+        for _ in 0..4 {
+            runtime
+                .send_package_to_sink(&build_package(600), None, None)
+                .await?;
+        }
+        runtime.flush(None, None).await?;
+        assert!(
+            *runtime.batch_size() < 200,
+            "batch size should shrink toward the 10ms target, got {}",
+            runtime.batch_size()
+        );
+        assert!(*runtime.batch_size() >= 8);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn schema_dropped_records_are_not_counted_as_sent() -> anyhow::Result<()> {
+        use wp_conf::structure::{RouteSchemaConf, SchemaConflict};
+
+        let temp = tempdir()?;
+        let mut conf = SinkInstanceConf::new_type(
+            "schema_count".into(),
+            TextFmt::Json,
+            "blackhole".into(),
+            Default::default(),
+            None,
+        );
+        conf.schema = Some(RouteSchemaConf {
+            file: None,
+            on_conflict: SchemaConflict::Drop,
+            fields: [("port".to_string(), "digit".to_string())].into(),
+        });
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut runtime = SinkRuntime::with_batch_size(
+            temp.path().display().to_string(),
+            "schema_count",
+            conf,
+            SinkBackendType::Proxy(Box::new(CountingSink::new(calls.clone()))),
+            None,
+            Vec::new(),
+            16,
+        );
+        let units = (0..10).map(|idx| {
+            let port = if idx % 3 == 0 {
+                DataField::from_chars("port", "http")
+            } else {
+                DataField::from_digit("port", 80)
+            };
+            SinkRecUnit::new(
+                idx as u64,
+                ProcMeta::Rule("/schema/rule".to_string()),
+                Arc::new(DataRecord::from(vec![port])),
+            )
+        });
+        runtime
+            .send_package_to_sink(&SinkPackage::from_units(units), None, None)
+            .await?;
+        runtime.flush(None, None).await?;
+
+        // idx 0/3/6/9 被 schema 丢弃：sent + dropped == 进入 sink 的记录数
+        assert_eq!(runtime.prom.dropped.get(), 4);
+        assert_eq!(runtime.prom.sent.get(), 6);
+        Ok(())
+    }
+
+    fn stuck_runtime(name: &str, rescue: &Path, stopped: &Arc<AtomicUsize>) -> SinkRuntime {
+        let conf = SinkInstanceConf::new_type(
+            name.into(),
+            TextFmt::Json,
+            "blackhole".into(),
+            Default::default(),
+            None,
+        );
+        SinkRuntime::with_batch_size(
+            rescue.display().to_string(),
+            name,
+            conf,
+            SinkBackendType::Proxy(Box::new(StuckSink {
+                stopped: stopped.clone(),
+            })),
+            None,
+            Vec::new(),
+            8,
+        )
+    }
+
+    /// 救援目录下的条目行
+    fn rescued_lines(dir: &Path) -> anyhow::Result<Vec<String>> {
+        let mut lines = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            assert_eq!(path.extension().and_then(|e| e.to_str()), Some("dat"));
+            lines.extend(std::fs::read_to_string(&path)?.lines().map(str::to_string));
+        }
+        Ok(lines)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn drain_timeout_rescues_pending_batch() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let mut runtime = stuck_runtime("stuck_batch", temp.path(), &stopped);
+        runtime
+            .send_package_to_sink(&build_package(5), None, None)
+            .await?;
+        runtime.begin_drain(Instant::now() + std::time::Duration::from_millis(50));
+        tokio::time::timeout(std::time::Duration::from_secs(5), runtime.flush(None, None))
+            .await
+            .expect("flush bounded by drain deadline")?;
+        assert!(runtime.pending_records().is_empty());
+        // 换下的主 sink 已被 stop
+        assert_eq!(stopped.load(Ordering::SeqCst), 1);
+        runtime.stop().await?;
+
+        let lines = rescued_lines(temp.path())?;
+        for line in &lines {
+            crate::sinks::RescueEntry::parse(line)?;
+        }
+        assert_eq!(lines.len(), 5);
+        assert_eq!(runtime.prom.rescued.get(), 5);
+        assert_eq!(runtime.prom.sent.get(), 0);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn drain_timeout_bounds_str_batch() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let mut runtime = stuck_runtime("stuck_str", temp.path(), &stopped);
+        runtime.begin_drain(Instant::now() + std::time::Duration::from_millis(50));
+        let bounded = std::time::Duration::from_secs(5);
+
+        let package = SinkStrPackage::from_units(
+            (0..3).map(|idx| SinkStrUnit::new(idx, format!("line{}", idx))),
+        );
+        tokio::time::timeout(
+            bounded,
+            runtime.send_str_package_to_sink(package, None, None),
+        )
+        .await
+        .expect("str batch bounded by drain deadline")?;
+        assert_eq!(stopped.load(Ordering::SeqCst), 1);
+
+        // 已切到救援文件：后续下发直接写入，不再等待主 sink
+        tokio::time::timeout(
+            bounded,
+            runtime.send_to_sink(7, SinkDataEnum::Raw("single".into()), None, None),
+        )
+        .await
+        .expect("single send bounded by drain deadline")?;
+        tokio::time::timeout(bounded, runtime.stop())
+            .await
+            .expect("stop bounded by drain deadline")?;
+
+        assert_eq!(rescued_lines(temp.path())?.len(), 4);
+        assert_eq!(stopped.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn drain_timeout_bounds_single_send() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let mut runtime = stuck_runtime("stuck_single", temp.path(), &stopped);
+        runtime.begin_drain(Instant::now() + std::time::Duration::from_millis(50));
+        tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            runtime.send_to_sink(1, SinkDataEnum::Raw("late".into()), None, None),
+        )
+        .await
+        .expect("single send bounded by drain deadline")?;
+        assert_eq!(stopped.load(Ordering::SeqCst), 1);
+        runtime.stop().await?;
+        assert_eq!(rescued_lines(temp.path())?.len(), 1);
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn small_package_stays_in_pending_buffer_until_flush() -> anyhow::Result<()> {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -871,4 +1588,122 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
         Ok(())
     }
+
+    struct FlakySink {
+        healthy: Arc<std::sync::atomic::AtomicBool>,
+        delivered: Arc<std::sync::Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for FlakySink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for FlakySink {
+        async fn sink_record(&mut self, _data: &DataRecord) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_records(&mut self, data: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            if !self.healthy.load(Ordering::SeqCst) {
+                return Err(SinkError::from(SinkReason::Sink("es down".into())));
+            }
+            self.delivered.lock().unwrap().push(data.len());
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawdatSink for FlakySink {
+        async fn sink_str(&mut self, _data: &str) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn breaker_opens_on_failures_and_recovers_with_probe() -> anyhow::Result<()> {
+        let healthy = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let delivered = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let primary = SinkBackendType::Proxy(Box::new(FlakySink {
+            healthy: healthy.clone(),
+            delivered: delivered.clone(),
+        }));
+        let mut conf = SinkInstanceConf::new_type(
+            "es".into(),
+            TextFmt::Json,
+            "blackhole".into(),
+            Default::default(),
+            None,
+        );
+        let breaker_conf = wp_conf::structure::SinkBreakerConf {
+            error_threshold: 2,
+            cooldown_secs: 1,
+            probe_size: 1,
+        };
+        conf.breaker = Some(breaker_conf.clone());
+        let mut runtime = SinkRuntime::with_batch_size(
+            "./rescue".to_string(),
+            "/sink/es/[0]",
+            conf,
+            primary,
+            None,
+            Vec::new(),
+            4,
+        );
+        // 使用独立的背压标志，避免影响并行运行的 source 测试
+        let backpressure = Box::leak(Box::new(crate::sinks::SinkBackpressure::default()));
+        runtime.breaker = Some(CircuitBreaker::with_backpressure(
+            &breaker_conf,
+            backpressure,
+        ));
+
+        // 未配置 bad sink 通道时失败批次按策略抛出或丢弃，这里只关心熔断状态
+        let _ = runtime
+            .send_package_to_sink(&build_package(4), None, None)
+            .await;
+        assert_eq!(runtime.breaker_state(), Some(BreakerState::Closed));
+        let _ = runtime
+            .send_package_to_sink(&build_package(4), None, None)
+            .await;
+        assert_eq!(runtime.breaker_state(), Some(BreakerState::Open));
+        assert!(backpressure.is_active());
+
+        // 冷却期内不访问主 sink
+        healthy.store(true, Ordering::SeqCst);
+        let _ = runtime
+            .send_package_to_sink(&build_package(4), None, None)
+            .await;
+        assert!(delivered.lock().unwrap().is_empty());
+
+        // 冷却结束：先以单条记录探测，成功后恢复，剩余记录留在 pending
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        runtime
+            .send_package_to_sink(&build_package(4), None, None)
+            .await?;
+        assert_eq!(runtime.breaker_state(), Some(BreakerState::Closed));
+        assert_eq!(runtime.pending_records().len(), 3);
+        runtime.flush(None, None).await?;
+        assert_eq!(*delivered.lock().unwrap(), vec![1, 3]);
+        assert!(!backpressure.is_active());
+        Ok(())
+    }
 }
@@ -1,2 +1,4 @@
+pub mod batcher;
+pub mod breaker;
 pub mod manager;
 pub mod stat;
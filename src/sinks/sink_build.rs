@@ -1,9 +1,12 @@
 use crate::sinks::prelude::*;
 // legacy imports removed after externalization
 
+use std::io::{BufRead, BufReader};
+
 use wp_conf::structure::SinkInstanceConf;
 
 use super::backends::file::AsyncFileSink;
+use super::utils::encoder::LineEncoder;
 use super::utils::formatter::AsyncFormatter;
 
 pub type AsyncFileSinkEx = AsyncFormatter<AsyncFileSink>;
@@ -20,9 +23,34 @@ pub async fn build_file_sink_with_sync(
     out_path: &str,
     sync: bool,
 ) -> AnyResult<AsyncFileSinkEx> {
-    let mut out: AsyncFileSinkEx = AsyncFormatter::new(conf.fmt);
+    build_file_sink_with_encoder(conf, out_path, sync, None).await
+}
+
+/// 带行编码器（csv/ndjson_bulk）的文件输出；目标文件已有内容时不重复输出表头，并按已有表头对齐列
+pub(crate) async fn build_file_sink_with_encoder(
+    conf: &SinkInstanceConf,
+    out_path: &str,
+    sync: bool,
+    encoder: Option<LineEncoder>,
+) -> AnyResult<AsyncFileSinkEx> {
+    let resumed = std::fs::metadata(out_path).is_ok_and(|m| m.len() > 0);
+    let encoder = encoder.map(|mut enc| {
+        if resumed {
+            enc.resume(first_line(out_path).as_deref());
+        }
+        enc
+    });
+    let mut out: AsyncFileSinkEx = AsyncFormatter::new(conf.fmt).with_encoder(encoder);
     out.next_pipe(AsyncFileSink::with_sync(out_path, sync).await?);
     Ok(out)
 }
 
+/// 已有文件的首行（不含换行符）
+fn first_line(path: &str) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut line = String::new();
+    BufRead::read_line(&mut BufReader::new(file), &mut line).ok()?;
+    Some(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
 // fast_file 已移除
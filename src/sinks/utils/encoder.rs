@@ -0,0 +1,386 @@
+//! 行编码器：需要跨记录状态（表头）或多行输出（bulk 动作行）的文件输出格式。
+//!
+//! - `csv`：可配置分隔符，`csv_header = true` 时每个文件仅输出一次表头（默认不输出，与旧版 csv 输出一致）；
+//!   含分隔符/引号/换行的单元格按 RFC 4180 加引号
+//! - `ndjson_bulk`：Elasticsearch bulk 格式，每条记录输出动作行 + 文档行
+//!
+//! Obj/Array 值统一编码为 JSON 文本，占一个单元格/字段。
+
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use wp_connector_api::ParamMap;
+use wp_data_fmt::{Json, RecordFormatter, ValueFormatter};
+use wp_model_core::model::{DataField, DataRecord, Value};
+
+use crate::types::AnyResult;
+
+pub const FMT_CSV: &str = "csv";
+pub const FMT_NDJSON_BULK: &str = "ndjson_bulk";
+
+const DEFAULT_BULK_INDEX_FIELD: &str = "_index";
+const DEFAULT_BULK_ID_FIELD: &str = "_id";
+
+#[derive(Debug, Clone)]
+pub enum LineEncoder {
+    Csv(CsvEncoder),
+    NdjsonBulk(BulkEncoder),
+}
+
+impl LineEncoder {
+    /// 按 sink 的 `fmt` 与参数表构建；其它格式返回 `None`，沿用 `TextFmt` 格式化
+    pub fn from_params(fmt: &str, params: &ParamMap) -> AnyResult<Option<Self>> {
+        match fmt {
+            FMT_CSV => {
+                let delimiter = match params.get("csv_delimiter").and_then(|v| v.as_str()) {
+                    None => ',',
+                    Some("\\t") => '\t',
+                    Some(s) => {
+                        let mut chars = s.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) if c != '"' && c != '\n' && c != '\r' => c,
+                            _ => anyhow::bail!(
+                                "invalid csv_delimiter: '{}'; expect a single character",
+                                s
+                            ),
+                        }
+                    }
+                };
+                let header = params
+                    .get("csv_header")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                Ok(Some(Self::Csv(CsvEncoder::new(delimiter, header))))
+            }
+            FMT_NDJSON_BULK => {
+                let pick = |key: &str, default: &str| {
+                    params
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(default)
+                        .to_string()
+                };
+                Ok(Some(Self::NdjsonBulk(BulkEncoder::new(
+                    pick("bulk_index_field", DEFAULT_BULK_INDEX_FIELD),
+                    pick("bulk_id_field", DEFAULT_BULK_ID_FIELD),
+                ))))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// 编码一条记录，结果以换行结尾
+    pub fn encode(&mut self, record: &DataRecord) -> String {
+        match self {
+            Self::Csv(enc) => enc.encode(record),
+            Self::NdjsonBulk(enc) => enc.encode(record),
+        }
+    }
+
+    /// 目标文件已有内容（追加写入）时调用，避免重复输出表头；`first_line` 为文件首行
+    pub fn resume(&mut self, first_line: Option<&str>) {
+        if let Self::Csv(enc) = self {
+            enc.resume(first_line);
+        }
+    }
+}
+
+/// CSV 编码：列由首条记录决定，后续记录按列名对齐，缺失列留空
+#[derive(Debug, Clone)]
+pub struct CsvEncoder {
+    delimiter: char,
+    header: bool,
+    // None 表示尚未确定列（也未输出表头）；空列表表示列未知，按记录自身顺序输出
+    columns: Option<Vec<String>>,
+}
+
+impl CsvEncoder {
+    pub fn new(delimiter: char, header: bool) -> Self {
+        Self {
+            delimiter,
+            header,
+            columns: None,
+        }
+    }
+
+    /// 续写已有文件：输出表头时按文件首行的列名对齐，首行为空时按记录自身顺序输出；
+    /// 不输出表头时文件中没有列名，仍由首条记录决定列
+    pub fn resume(&mut self, first_line: Option<&str>) {
+        if !self.header {
+            return;
+        }
+        let columns = first_line
+            .filter(|line| !line.is_empty())
+            .map(|line| self.parse_row(line))
+            .unwrap_or_default();
+        self.columns = Some(columns);
+    }
+
+    pub fn encode(&mut self, record: &DataRecord) -> String {
+        let fields: Vec<&DataField> = record
+            .items
+            .iter()
+            .map(|f| f.as_field())
+            .filter(|f| !matches!(f.get_value(), Value::Ignore(_)))
+            .collect();
+        let mut out = String::new();
+        if self.columns.is_none() {
+            let names: Vec<String> = fields.iter().map(|f| f.get_name().to_string()).collect();
+            if self.header {
+                self.push_row(&mut out, names.iter().map(String::as_str));
+            }
+            self.columns = Some(names);
+        }
+        let columns = self.columns.as_deref().unwrap_or_default();
+        if columns.is_empty() {
+            let cells: Vec<String> = fields.iter().map(|f| cell_text(f.get_value())).collect();
+            self.push_row(&mut out, cells.iter().map(String::as_str));
+        } else {
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|name| {
+                    fields
+                        .iter()
+                        .find(|f| f.get_name() == name.as_str())
+                        .map(|f| cell_text(f.get_value()))
+                        .unwrap_or_default()
+                })
+                .collect();
+            self.push_row(&mut out, cells.iter().map(String::as_str));
+        }
+        out
+    }
+
+    fn push_row<'a>(&self, out: &mut String, cells: impl Iterator<Item = &'a str>) {
+        for (idx, cell) in cells.enumerate() {
+            if idx > 0 {
+                out.push(self.delimiter);
+            }
+            self.push_cell(out, cell);
+        }
+        out.push('\n');
+    }
+
+    /// 按 RFC 4180 拆分单行（用于读回表头）
+    fn parse_row(&self, line: &str) -> Vec<String> {
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if quoted {
+                if c != '"' {
+                    cell.push(c);
+                } else if chars.next_if_eq(&'"').is_some() {
+                    cell.push('"');
+                } else {
+                    quoted = false;
+                }
+            } else if c == '"' {
+                quoted = true;
+            } else if c == self.delimiter {
+                cells.push(std::mem::take(&mut cell));
+            } else {
+                cell.push(c);
+            }
+        }
+        cells.push(cell);
+        cells
+    }
+
+    /// RFC 4180：含分隔符、双引号或换行时整体加引号，内部双引号翻倍
+    fn push_cell(&self, out: &mut String, cell: &str) {
+        let quote = cell
+            .chars()
+            .any(|c| c == self.delimiter || matches!(c, '"' | '\n' | '\r'));
+        if !quote {
+            out.push_str(cell);
+            return;
+        }
+        out.push('"');
+        for c in cell.chars() {
+            if c == '"' {
+                out.push('"');
+            }
+            out.push(c);
+        }
+        out.push('"');
+    }
+}
+
+fn cell_text(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::Chars(s) => s.to_string(),
+        Value::Obj(_) | Value::Array(_) => Json.format_value(v).to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// ES bulk 编码：`_index`/`_id` 取自配置字段；以 `_` 开头的元数据字段不进入文档
+#[derive(Debug, Clone)]
+pub struct BulkEncoder {
+    index_field: String,
+    id_field: String,
+}
+
+impl BulkEncoder {
+    pub fn new(index_field: String, id_field: String) -> Self {
+        Self {
+            index_field,
+            id_field,
+        }
+    }
+
+    pub fn encode(&mut self, record: &DataRecord) -> String {
+        let mut meta = JsonMap::new();
+        let mut doc: Vec<DataField> = Vec::with_capacity(record.items.len());
+        for item in record.items.iter() {
+            let field = item.as_field();
+            let name = field.get_name();
+            let key = if name == self.index_field.as_str() {
+                Some("_index")
+            } else if name == self.id_field.as_str() {
+                Some("_id")
+            } else {
+                None
+            };
+            if let Some(key) = key {
+                let text = cell_text(field.get_value());
+                if !text.is_empty() {
+                    meta.insert(key.to_string(), JsonValue::String(text));
+                }
+                if name.starts_with('_') {
+                    continue;
+                }
+            }
+            doc.push(field.clone());
+        }
+        let mut action = JsonMap::new();
+        action.insert("index".to_string(), JsonValue::Object(meta));
+        format!(
+            "{}\n{}\n",
+            JsonValue::Object(action),
+            Json.fmt_record(&DataRecord::from(doc))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wp_model_core::model::types::value::ObjectValue;
+
+    fn csv(delimiter: char) -> LineEncoder {
+        LineEncoder::Csv(CsvEncoder::new(delimiter, true))
+    }
+
+    #[test]
+    fn csv_header_once_and_quoting() {
+        let mut enc = csv(',');
+        let first = DataRecord::from(vec![
+            DataField::from_chars("host", "web-1"),
+            DataField::from_chars("msg", "a,b"),
+            DataField::from_chars("note", "say \"hi\"\nbye"),
+        ]);
+        assert_eq!(
+            enc.encode(&first),
+            "host,msg,note\nweb-1,\"a,b\",\"say \"\"hi\"\"\nbye\"\n"
+        );
+        // 列按表头对齐：缺失列留空，多余字段忽略
+        let second = DataRecord::from(vec![
+            DataField::from_chars("msg", "plain"),
+            DataField::from_chars("extra", "x"),
+            DataField::from_chars("host", "web-2"),
+        ]);
+        assert_eq!(enc.encode(&second), "web-2,plain,\n");
+    }
+
+    #[test]
+    fn csv_custom_delimiter_and_resume() {
+        let mut params = ParamMap::new();
+        params.insert("csv_delimiter".into(), json!("\\t"));
+        let mut enc = LineEncoder::from_params(FMT_CSV, &params).unwrap().unwrap();
+        let rec = DataRecord::from(vec![
+            DataField::from_chars("a", "x\ty"),
+            DataField::from_chars("b", "x,y"),
+        ]);
+        // 未配置 csv_header 时不输出表头；逗号不需要引号
+        assert_eq!(enc.encode(&rec), "\"x\ty\"\tx,y\n");
+
+        params.insert("csv_delimiter".into(), json!("||"));
+        assert!(LineEncoder::from_params(FMT_CSV, &params).is_err());
+        assert!(LineEncoder::from_params("json", &params).unwrap().is_none());
+    }
+
+    #[test]
+    fn csv_resume_aligns_to_existing_header() {
+        let mut enc = csv(',');
+        enc.resume(Some("host,\"msg,text\",port"));
+        let rec = DataRecord::from(vec![
+            DataField::from_digit("port", 443),
+            DataField::from_chars("host", "web-1"),
+            DataField::from_chars("extra", "x"),
+        ]);
+        // 不再输出表头，按已有列对齐
+        assert_eq!(enc.encode(&rec), "web-1,,443\n");
+
+        // 首行为空：列未知，按记录顺序输出
+        let mut enc = csv(',');
+        enc.resume(Some(""));
+        assert_eq!(enc.encode(&rec), "443,web-1,x\n");
+
+        // 不输出表头时续写与新文件一致，由首条记录决定列
+        let mut enc = LineEncoder::Csv(CsvEncoder::new(',', false));
+        enc.resume(Some("old,row"));
+        assert_eq!(enc.encode(&rec), "443,web-1,x\n");
+    }
+
+    #[test]
+    fn csv_obj_encoded_as_json_cell() {
+        let mut obj = ObjectValue::default();
+        obj.insert("k".to_string(), DataField::from_chars("k", "v"));
+        let rec = DataRecord::from(vec![
+            DataField::from_chars("id", "1"),
+            DataField::from_obj("detail", obj),
+        ]);
+        let out = csv(',').encode(&rec);
+        let row = out.lines().nth(1).unwrap();
+        let (_, cell) = row.split_once(',').unwrap();
+        assert!(cell.starts_with('"') && cell.ends_with('"'));
+        let unquoted = cell[1..cell.len() - 1].replace("\"\"", "\"");
+        let parsed: JsonValue = serde_json::from_str(&unquoted).unwrap();
+        assert_eq!(parsed["k"], json!("v"));
+    }
+
+    #[test]
+    fn bulk_pairs() {
+        let mut params = ParamMap::new();
+        params.insert("bulk_index_field".into(), json!("index_name"));
+        let mut enc = LineEncoder::from_params(FMT_NDJSON_BULK, &params)
+            .unwrap()
+            .unwrap();
+        let rec = DataRecord::from(vec![
+            DataField::from_chars("index_name", "logs-2026"),
+            DataField::from_chars("_id", "evt-1"),
+            DataField::from_chars("msg", "hello"),
+        ]);
+        let out = enc.encode(&rec);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let action: JsonValue = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(
+            action,
+            json!({"index": {"_index": "logs-2026", "_id": "evt-1"}})
+        );
+        let doc: JsonValue = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(doc["msg"], json!("hello"));
+        assert_eq!(doc["index_name"], json!("logs-2026"));
+        assert!(doc.get("_id").is_none());
+
+        // 缺少元数据字段时动作行为空对象
+        let bare = DataRecord::from(vec![DataField::from_chars("msg", "x")]);
+        let out = enc.encode(&bare);
+        let action: JsonValue = serde_json::from_str(out.lines().next().unwrap()).unwrap();
+        assert_eq!(action, json!({"index": {}}));
+    }
+}
@@ -10,6 +10,7 @@ use wp_parse_api::RawData;
 use wpl::generator::{CSVGenFmt, JsonGenFmt, KVGenFmt, ProtoGenFmt, RAWGenFmt};
 
 use crate::sinks::SinkRecUnit;
+use crate::sinks::utils::encoder::LineEncoder;
 use crate::types::AnyResult;
 use std::sync::Arc;
 use wp_connector_api::SinkResult;
@@ -42,6 +43,8 @@ where
     T: AsyncCtrl + AsyncRawdatSink,
 {
     fmt: TextFmt,
+    // 有状态的行编码（csv 表头、bulk 动作行）；设置后优先于 fmt
+    encoder: Option<LineEncoder>,
     next_proc: Option<T>,
 }
 
@@ -52,6 +55,11 @@ where
     pub fn next_pipe(&mut self, assembler: T) {
         self.next_proc = Some(assembler);
     }
+
+    pub fn with_encoder(mut self, encoder: Option<LineEncoder>) -> Self {
+        self.encoder = encoder;
+        self
+    }
 }
 
 #[async_trait]
@@ -82,6 +90,9 @@ where
 {
     async fn sink_record(&mut self, data: &DataRecord) -> SinkResult<()> {
         if let Some(ref mut next_proc) = self.next_proc {
+            if let Some(encoder) = self.encoder.as_mut() {
+                return next_proc.sink_str(&encoder.encode(data)).await;
+            }
             let data: RawData = self.fmt.cov_data(data.clone()).owe_data()?;
             match data {
                 RawData::String(data_str) => {
@@ -103,6 +114,10 @@ where
             return Ok(());
         }
         if let Some(ref mut next_proc) = self.next_proc {
+            if let Some(encoder) = self.encoder.as_mut() {
+                let lines: String = data.iter().map(|rec| encoder.encode(rec)).collect();
+                return next_proc.sink_str(&lines).await;
+            }
             let mut str_batch: Vec<String> = Vec::with_capacity(data.len());
             let mut bytes_batch: Vec<Vec<u8>> = Vec::new();
 
@@ -176,6 +191,7 @@ where
     pub fn new(fmt: TextFmt) -> Self {
         AsyncFormatter {
             fmt,
+            encoder: None,
             next_proc: None,
         }
     }
@@ -1,3 +1,4 @@
 pub mod buffer_monitor;
+pub mod encoder;
 pub mod formatter;
 pub mod view;
@@ -0,0 +1,146 @@
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use serde_json::json;
+use tokio::sync::mpsc;
+use wp_conf::connectors::{ConnectorDef, ConnectorScope, ParamMap};
+use wp_connector_api::{
+    SourceBuildCtx, SourceDefProvider, SourceFactory, SourceHandle, SourceMeta, SourceReason,
+    SourceResult, SourceSpec as ResolvedSourceSpec, SourceSvcIns, Tags,
+};
+
+use super::registry;
+use super::source::ChannelSource;
+
+/// 队列容量（批次数）默认值；每次 `push` 写入一个批次
+pub const DEFAULT_CHANNEL_QUEUE_BATCHES: usize = 1024;
+
+pub struct ChannelSourceFactory;
+
+fn queue_batches(params: &ParamMap) -> anyhow::Result<usize> {
+    match params.get("queue_batches") {
+        None => Ok(DEFAULT_CHANNEL_QUEUE_BATCHES),
+        Some(v) => match v.as_u64() {
+            Some(n) if n > 0 => Ok(n as usize),
+            _ => anyhow::bail!("queue_batches must be a positive integer, got {}", v),
+        },
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceFactory for ChannelSourceFactory {
+    fn kind(&self) -> &'static str {
+        "channel"
+    }
+
+    fn validate_spec(&self, spec: &ResolvedSourceSpec) -> SourceResult<()> {
+        let res: anyhow::Result<()> = (|| {
+            if let Err(e) = Tags::validate(&spec.tags) {
+                anyhow::bail!("Invalid tags: {}", e);
+            }
+            queue_batches(&spec.params)?;
+            Ok(())
+        })();
+        res.map_err(|e| SourceReason::from_conf(e.to_string()).to_err())
+    }
+
+    async fn build(
+        &self,
+        spec: &ResolvedSourceSpec,
+        _ctx: &SourceBuildCtx,
+    ) -> SourceResult<SourceSvcIns> {
+        let capacity = queue_batches(&spec.params)
+            .map_err(|e| SourceReason::from_conf(e.to_string()).to_err())?;
+        let mut tags = Tags::from_parse(&spec.tags);
+        tags.set("access_source", "channel".to_string());
+
+        let (tx, rx) = mpsc::channel(capacity);
+        registry::register(&spec.name, tx, tags.clone());
+        let source = ChannelSource::new(spec.name.clone(), rx);
+
+        let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
+        for (k, v) in tags.iter() {
+            meta.tags.set(k, v);
+        }
+        Ok(SourceSvcIns::new().with_sources(vec![SourceHandle::new(Box::new(source), meta)]))
+    }
+}
+
+impl SourceDefProvider for ChannelSourceFactory {
+    fn source_def(&self) -> ConnectorDef {
+        let mut params = ParamMap::new();
+        params.insert("queue_batches".into(), json!(DEFAULT_CHANNEL_QUEUE_BATCHES));
+        ConnectorDef {
+            id: "channel_src".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Source,
+            allow_override: vec!["queue_batches".into()],
+            default_params: params,
+            origin: Some("builtin:channel_source".into()),
+        }
+    }
+}
+
+/// 注册 channel 源工厂（集中由引擎启动入口调用）
+pub fn register_channel_factory() {
+    crate::connectors::registry::register_source_factory(ChannelSourceFactory);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_connector_api::ControlEvent;
+    use wp_parse_api::RawData;
+
+    fn spec_with(name: &str) -> ResolvedSourceSpec {
+        ResolvedSourceSpec {
+            name: name.into(),
+            kind: "channel".into(),
+            connector_id: "channel_src".into(),
+            params: wp_connector_api::parammap_from_toml_map(toml::map::Map::new()),
+            tags: vec!["env:test".into()],
+        }
+    }
+
+    #[tokio::test]
+    async fn push_then_close_drains_to_eof() {
+        let ctx = SourceBuildCtx::new(std::env::current_dir().unwrap());
+        let mut svc = ChannelSourceFactory
+            .build(&spec_with("chan_test_in"), &ctx)
+            .await
+            .expect("build channel source");
+        let mut handle = svc.sources.remove(0);
+        let (_ctrl_tx, ctrl_rx) = async_broadcast::broadcast::<ControlEvent>(4);
+        handle.source.start(ctrl_rx).await.unwrap();
+
+        registry::push("chan_test_in", "line one").await.unwrap();
+        registry::push("chan_test_in", String::from("line two"))
+            .await
+            .unwrap();
+        assert!(registry::push("chan_test_missing", "x").await.is_err());
+        assert!(registry::close_channel("chan_test_in"));
+
+        let mut got = Vec::new();
+        while let Ok(batch) = handle.source.receive().await {
+            for ev in batch {
+                assert_eq!(ev.src_key.as_str(), "chan_test_in");
+                match ev.payload {
+                    RawData::Bytes(b) => got.push(String::from_utf8_lossy(&b).into_owned()),
+                    _ => panic!("unexpected payload type"),
+                }
+            }
+        }
+        assert_eq!(got, vec!["line one", "line two"]);
+    }
+
+    #[test]
+    fn validate_queue_batches() {
+        let mut spec = spec_with("chan_validate");
+        ChannelSourceFactory
+            .validate_spec(&spec)
+            .expect("default ok");
+        let mut t = toml::map::Map::new();
+        t.insert("queue_batches".into(), toml::Value::Integer(0));
+        spec.params = wp_connector_api::parammap_from_toml_map(t);
+        assert!(ChannelSourceFactory.validate_spec(&spec).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+//! Channel 采集源：进程内推送数据（嵌入式引擎使用）
+//!
+//! 模块结构：
+//! - registry.rs：按 source key 登记发送端，`push` 写入对应队列
+//! - source.rs：ChannelSource，从队列交付批次
+//! - factory.rs：ChannelSourceFactory
+//!
+//! 发送端在进程内按 key 全局登记，同一进程内的 key 需唯一。关闭发送端后，
+//! source 取完剩余批次即返回 EOF，采集线程随之结束。
+//!
+//! ```toml
+//! [[sources]]
+//! key = "app_in"
+//! connect = "channel_src"
+//! ```
+
+pub mod factory;
+pub mod registry;
+pub mod source;
+
+pub use factory::{ChannelSourceFactory, register_channel_factory};
+pub use registry::{close_channel, push};
+pub use source::ChannelSource;
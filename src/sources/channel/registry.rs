@@ -0,0 +1,61 @@
+//! 进程内发送端登记表
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use bytes::Bytes;
+use once_cell::sync::Lazy;
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use tokio::sync::mpsc;
+use wp_connector_api::{SourceBatch, SourceEvent, SourceReason, SourceResult, Tags};
+use wp_parse_api::RawData;
+
+use crate::sources::event_id::next_event_id;
+
+struct ChannelEntry {
+    tx: mpsc::Sender<SourceBatch>,
+    tags: Arc<Tags>,
+}
+
+static CHANNELS: Lazy<RwLock<HashMap<String, ChannelEntry>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 登记 key 对应的发送端；同名 key 重新构建时替换旧发送端
+pub(crate) fn register(key: &str, tx: mpsc::Sender<SourceBatch>, tags: Tags) {
+    let mut map = CHANNELS.write().expect("channel registry poisoned");
+    map.insert(
+        key.to_string(),
+        ChannelEntry {
+            tx,
+            tags: Arc::new(tags),
+        },
+    );
+}
+
+/// 向 key 对应的 channel 源推送一条记录；队列已满时等待
+pub async fn push(key: &str, payload: impl Into<Bytes>) -> SourceResult<()> {
+    let (tx, tags) = {
+        let map = CHANNELS.read().expect("channel registry poisoned");
+        match map.get(key) {
+            Some(entry) => (entry.tx.clone(), entry.tags.clone()),
+            None => {
+                return SourceReason::from_conf(format!("channel source '{}' not found", key))
+                    .err_result();
+            }
+        }
+    };
+    let event = SourceEvent::new(next_event_id(), key, RawData::Bytes(payload.into()), tags);
+    tx.send(vec![event]).await.map_err(|_| {
+        SourceReason::from_conf(format!("channel source '{}' is closed", key)).to_err()
+    })
+}
+
+/// 移除 key 对应的发送端，返回是否存在；source 取完剩余批次后结束
+pub fn close_channel(key: &str) -> bool {
+    CHANNELS
+        .write()
+        .expect("channel registry poisoned")
+        .remove(key)
+        .is_some()
+}
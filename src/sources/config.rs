@@ -1,11 +1,13 @@
 use crate::connectors::registry;
+use crate::sources::preproc::{PreprocSource, build_hook_chain};
+use crate::sources::rate_limit::RateLimitedSource;
 use orion_conf::error::{ConfIOReason, OrionConfResult};
 use orion_conf::{EnvTomlLoad, ErrorOwe, ErrorWith};
 use orion_error::{ToStructError, UvsValidationFrom};
 use orion_variate::EnvDict;
 use serde_derive::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use wp_conf::structure::SourceInstanceConf;
+use wp_conf::structure::{SourceInstanceConf, Validate};
 use wp_connector_api::{AcceptorHandle, SourceBuildCtx, SourceHandle};
 use wp_log::info_ctrl;
 
@@ -42,6 +44,9 @@ impl wp_conf::sources::SourceFactoryRegistry for SourceFactoryLookup {
     }
 }
 
+/// 数据源实例名及其展开出的 source 标识
+pub type SourceInstanceIds = (String, Vec<String>);
+
 /// 配置解析和构建器
 pub struct SourceConfigParser {
     work_dir: PathBuf,
@@ -66,13 +71,39 @@ impl SourceConfigParser {
                 ))
                 .to_err()
             })?;
+            let chain = build_hook_chain(&item.preproc).map_err(|e| {
+                ConfIOReason::from_validation(format!("source '{}': {}", resolved.name, e)).to_err()
+            })?;
             let svc = fac.build(&resolved, &ctx).await.map_err(|e| {
                 ConfIOReason::from_validation(format!(
                     "Factory build failed for source '{}' of kind '{}': {}",
                     resolved.name, resolved.kind, e
                 ))
             })?;
-            sources.extend(svc.sources);
+            let handles = match &chain {
+                Some(chain) => {
+                    info_ctrl!(
+                        "source '{}' preproc hooks: {}",
+                        resolved.name,
+                        item.preproc.join(" -> ")
+                    );
+                    PreprocSource::wrap_all(svc.sources, chain)
+                }
+                None => svc.sources,
+            };
+            match &item.rate_limit {
+                Some(limit) => {
+                    info_ctrl!(
+                        "source '{}' rate limited: rate={}, burst={:?}, policy={}",
+                        resolved.name,
+                        limit.rate,
+                        limit.burst,
+                        limit.policy
+                    );
+                    sources.extend(RateLimitedSource::wrap_all(&resolved.name, handles, limit))
+                }
+                None => sources.extend(handles),
+            }
             if let Some(acc) = svc.acceptor {
                 acceptors.push(acc);
             }
@@ -128,6 +159,17 @@ impl SourceConfigParser {
         self.build_from_specs_with_ids(specs).await
     }
 
+    /// 校验并构建单个数据源实例（供运行期热添加；`item` 需已完成环境变量求值）
+    pub async fn build_instance(
+        &self,
+        item: SourceInstanceConf,
+    ) -> OrionConfResult<(Vec<SourceHandle>, Vec<AcceptorHandle>)> {
+        item.validate()?;
+        let specs = vec![item];
+        wp_conf::sources::validate_specs_with_factory(&specs, &SourceFactoryLookup)?;
+        self.build_from_specs_with_ids(specs).await
+    }
+
     /// 仅解析并执行最小校验（不进行实际构建，不触发 I/O）
     pub fn parse_and_validate_only(
         &self,
@@ -142,12 +184,19 @@ impl SourceConfigParser {
 
 impl SourceConfigParser {
     /// 解析并构建（带运行模式过滤）：batch 下忽略 tcp/syslog(tcp) 源
+    ///
+    /// 返回的实例清单按配置顺序列出实例名及其展开出的 source 标识；
+    /// 不同实例展开出相同的 source 标识时报错
     pub async fn build_source_handles(
         &self,
         wpsrc_path: &Path,
         run_mode: wp_conf::RunMode,
         dict: &EnvDict,
-    ) -> OrionConfResult<(Vec<String>, Vec<SourceHandle>, Vec<AcceptorHandle>)> {
+    ) -> OrionConfResult<(
+        Vec<SourceInstanceIds>,
+        Vec<SourceHandle>,
+        Vec<AcceptorHandle>,
+    )> {
         let specs = wp_conf::sources::load_source_instances_from_file(wpsrc_path, dict)?;
         wp_conf::sources::validate_specs_with_factory(&specs, &SourceFactoryLookup)?;
 
@@ -174,8 +223,26 @@ impl SourceConfigParser {
             _ => specs,
         };
 
-        let keys: Vec<String> = filtered.iter().map(|s| s.name().clone()).collect();
-        let (handles, acceptors) = self.build_from_specs_with_ids(filtered).await?;
-        Ok((keys, handles, acceptors))
+        let mut instances: Vec<SourceInstanceIds> = Vec::new();
+        let mut handles = Vec::new();
+        let mut acceptors = Vec::new();
+        for item in filtered {
+            let name = item.name().clone();
+            let (built, accs) = self.build_from_specs_with_ids(vec![item]).await?;
+            let ids: Vec<String> = built.iter().map(|h| h.source.identifier()).collect();
+            for id in &ids {
+                if let Some((owner, _)) = instances.iter().find(|(_, seen)| seen.contains(id)) {
+                    return ConfIOReason::from_validation(format!(
+                        "duplicate source identifier '{}' (sources '{}' and '{}')",
+                        id, owner, name
+                    ))
+                    .err_result();
+                }
+            }
+            instances.push((name, ids));
+            handles.extend(built);
+            acceptors.extend(accs);
+        }
+        Ok((instances, handles, acceptors))
     }
 }
@@ -0,0 +1,184 @@
+//! 文件读取位点持久化：按文件身份（device + inode）记录已交付的字节偏移。
+//!
+//! 重启后按 inode 匹配位点，因此文件改名后仍能续读；同名但 inode 变化（轮转后新建）
+//! 的文件视为新文件，从头读取。写入采用临时文件 + rename，避免中途退出留下半截内容。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// 文件身份：Unix 下为 (dev, inode)；其它平台退化为路径哈希（不跟随改名）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FileId {
+    pub dev: u64,
+    pub ino: u64,
+}
+
+impl FileId {
+    pub fn of(path: &Path, meta: &std::fs::Metadata) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let _ = path;
+            Self {
+                dev: meta.dev(),
+                ino: meta.ino(),
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            use std::hash::{Hash, Hasher};
+            let _ = meta;
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            path.hash(&mut hasher);
+            Self {
+                dev: 0,
+                ino: hasher.finish(),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointEntry {
+    pub path: String,
+    #[serde(flatten)]
+    pub id: FileId,
+    /// 已交付的字节数（只计完整行）
+    pub offset: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckpointFile {
+    files: Vec<CheckpointEntry>,
+}
+
+#[derive(Debug)]
+pub struct FileCheckpoint {
+    path: PathBuf,
+    entries: BTreeMap<FileId, CheckpointEntry>,
+    dirty: bool,
+}
+
+impl FileCheckpoint {
+    /// 读取位点文件；不存在时返回空表，内容损坏时告警并从头开始
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str::<CheckpointFile>(&text) {
+                Ok(file) => file.files.into_iter().map(|e| (e.id, e)).collect(),
+                Err(e) => {
+                    warn_data!(
+                        "file checkpoint {} is corrupt, ignored: {}",
+                        path.display(),
+                        e
+                    );
+                    BTreeMap::new()
+                }
+            },
+            Err(_) => BTreeMap::new(),
+        };
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 文件的续读位点；inode 不同视为新文件
+    pub fn offset_of(&self, id: &FileId) -> Option<u64> {
+        self.entries.get(id).map(|e| e.offset)
+    }
+
+    pub fn update(&mut self, id: FileId, path: &Path, offset: u64) {
+        let path = path.display().to_string();
+        if let Some(entry) = self.entries.get(&id)
+            && entry.offset == offset
+            && entry.path == path
+        {
+            return;
+        }
+        self.entries
+            .insert(id, CheckpointEntry { path, id, offset });
+        self.dirty = true;
+    }
+
+    pub fn remove(&mut self, id: &FileId) {
+        if self.entries.remove(id).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// 丢弃不在 `alive` 中的条目（已删除的文件）
+    pub fn retain(&mut self, alive: impl Fn(&FileId) -> bool) {
+        let before = self.entries.len();
+        self.entries.retain(|id, _| alive(id));
+        if self.entries.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &CheckpointEntry> {
+        self.entries.values()
+    }
+
+    pub fn save(&mut self) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(dir)?;
+        }
+        let body = CheckpointFile {
+            files: self.entries.values().cloned().collect(),
+        };
+        let text = serde_json::to_string_pretty(&body).map_err(std::io::Error::other)?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, text)?;
+        std::fs::rename(&tmp, &self.path)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_reload_by_file_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let ck_path = dir.path().join("ck/file_src.json");
+        let a = FileId { dev: 1, ino: 10 };
+        let b = FileId { dev: 1, ino: 11 };
+
+        let mut ck = FileCheckpoint::load(&ck_path);
+        ck.update(a, Path::new("/spool/access-01.log"), 120);
+        ck.update(b, Path::new("/spool/access-02.log"), 7);
+        ck.save().unwrap();
+
+        let mut ck = FileCheckpoint::load(&ck_path);
+        assert_eq!(ck.offset_of(&a), Some(120));
+        assert_eq!(ck.offset_of(&FileId { dev: 1, ino: 12 }), None);
+
+        ck.retain(|id| *id == a);
+        ck.save().unwrap();
+        let ck = FileCheckpoint::load(&ck_path);
+        assert_eq!(ck.entries().count(), 1);
+    }
+
+    #[test]
+    fn corrupt_checkpoint_starts_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let ck_path = dir.path().join("ck.json");
+        std::fs::write(&ck_path, "{not json").unwrap();
+        assert_eq!(FileCheckpoint::load(&ck_path).entries().count(), 0);
+    }
+}
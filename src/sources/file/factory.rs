@@ -1,4 +1,5 @@
 use super::source::{FileEncoding, FileSource};
+use super::watch::{DEFAULT_POLL_INTERVAL_MS, FileWatchSource, WatchSpec, is_glob};
 use async_trait::async_trait;
 use orion_conf::{ErrorWith, UvsConfFrom};
 use orion_error::{ToStructError, UvsDataFrom};
@@ -19,6 +20,8 @@ struct FileSourceSpec {
     path: String,
     encoding: FileEncoding,
     instances: usize,
+    /// 路径文件名含 glob 时进入目录监听模式
+    watch: Option<WatchSpec>,
 }
 
 impl FileSourceSpec {
@@ -56,10 +59,37 @@ impl FileSourceSpec {
             .and_then(|v| v.as_i64())
             .map(|n| n.clamp(1, FILE_SOURCE_MAX_INSTANCES as i64) as usize)
             .unwrap_or(1);
+        let watch = if is_glob(&path) {
+            let poll_ms = resolved
+                .params
+                .get("poll_interval_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_POLL_INTERVAL_MS);
+            anyhow::ensure!(poll_ms > 0, "poll_interval_ms must be > 0");
+            // 相对路径基于工作目录（引擎启动时切换到 work root）
+            let checkpoint = resolved
+                .params
+                .get("checkpoint")
+                .and_then(|v| v.as_str())
+                .map(std::path::PathBuf::from)
+                .unwrap_or_else(|| {
+                    Path::new(".run/checkpoint").join(format!("file_{}.json", resolved.name))
+                });
+            let spec = WatchSpec {
+                pattern: path.clone(),
+                poll_interval: std::time::Duration::from_millis(poll_ms),
+                checkpoint,
+            };
+            spec.watch_dir()?;
+            Some(spec)
+        } else {
+            None
+        };
         Ok(Self {
             path,
             encoding,
             instances,
+            watch,
         })
     }
 }
@@ -91,6 +121,21 @@ impl SourceFactory for FileSourceFactory {
         let fut = async {
             let spec = FileSourceSpec::from_resolved(resolved)?;
             let tagset = Tags::from_parse(&resolved.tags);
+            if let Some(watch) = spec.watch {
+                let source = FileWatchSource::new(
+                    resolved.name.clone(),
+                    watch,
+                    spec.encoding.clone(),
+                    tagset.clone(),
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to create FileWatchSource: {}", e))?;
+                let mut meta = SourceMeta::new(resolved.name.clone(), resolved.kind.clone());
+                for (k, v) in tagset.iter() {
+                    meta.tags.set(k, v);
+                }
+                return Ok(SourceSvcIns::new()
+                    .with_sources(vec![SourceHandle::new(Box::new(source), meta)]));
+            }
             let ranges = compute_file_ranges(Path::new(&spec.path), spec.instances)
                 .map_err(|e| {
                     SourceReason::from_data(
@@ -143,7 +188,13 @@ impl SourceDefProvider for FileSourceFactory {
             id: "file_src".into(),
             kind: self.kind().into(),
             scope: ConnectorScope::Source,
-            allow_override: vec!["base".into(), "file".into(), "encode".into()],
+            allow_override: vec![
+                "base".into(),
+                "file".into(),
+                "encode".into(),
+                "poll_interval_ms".into(),
+                "checkpoint".into(),
+            ],
             default_params: params,
             origin: Some("builtin:file_source".into()),
         }
@@ -258,6 +309,35 @@ mod tests {
         assert_eq!(ranges, vec![(0, Some(10)), (10, None)]);
     }
 
+    #[test]
+    fn glob_path_enables_watch_mode() {
+        let mut params = TomlMap::new();
+        params.insert("base".into(), toml::Value::String("/spool".into()));
+        params.insert("file".into(), toml::Value::String("access-*.log".into()));
+        let spec = ResolvedSourceSpec {
+            name: "spool".into(),
+            kind: "file".into(),
+            connector_id: String::new(),
+            params: parammap_from_toml_map(params),
+            tags: vec![],
+        };
+        let resolved = FileSourceSpec::from_resolved(&spec).expect("glob spec");
+        let watch = resolved.watch.expect("watch mode");
+        assert_eq!(watch.pattern, "/spool/access-*.log");
+        assert_eq!(
+            watch.checkpoint,
+            Path::new(".run/checkpoint/file_spool.json")
+        );
+
+        let mut params = TomlMap::new();
+        params.insert("path".into(), toml::Value::String("/spool/*/a.log".into()));
+        let spec = ResolvedSourceSpec {
+            params: parammap_from_toml_map(params),
+            ..spec
+        };
+        assert!(FileSourceSpec::from_resolved(&spec).is_err());
+    }
+
     #[tokio::test]
     async fn build_propagates_tags_into_metadata_and_events() {
         let file = NamedTempFile::new().expect("temp file");
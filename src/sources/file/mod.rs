@@ -1,6 +1,10 @@
+mod checkpoint;
 mod chunk_reader;
 mod factory;
 mod source;
+mod watch;
 
+pub use checkpoint::{CheckpointEntry, FileCheckpoint, FileId};
 pub use factory::{FileSourceFactory, register_factory_only};
 pub use source::{FileEncoding, FileSource};
+pub use watch::{FileWatchSource, WatchSpec};
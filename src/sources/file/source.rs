@@ -78,7 +78,7 @@ impl FileSource {
         })
     }
 
-    fn payload_from_line(encode: &FileEncoding, line: Vec<u8>) -> SourceResult<RawData> {
+    pub(super) fn payload_from_line(encode: &FileEncoding, line: Vec<u8>) -> SourceResult<RawData> {
         match encode {
             FileEncoding::Text => Ok(RawData::Bytes(Bytes::from(line))),
             FileEncoding::Base64 => {
@@ -0,0 +1,541 @@
+//! 目录监听模式：按 glob 匹配目录中的文件，持续追读并持久化读取位点。
+//!
+//! - 发现：启动及每个 `poll_interval` 重新扫描；notify（inotify 等）事件到达时立即扫描，
+//!   监听器创建失败时退化为纯轮询。
+//! - 追读：只交付以换行结尾的完整行，未写完的行留在缓冲中等待后续追加。
+//! - 轮转/改名：已打开的文件不再匹配（改名移出或被删除）时继续读到 EOF 再释放，
+//!   并删除其位点；改名后仍匹配的文件按 inode 继续追读。
+//! - 位点：按 (dev, inode) 记录已交付的字节数，周期性及关闭时写入位点文件，重启后续读。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::sync::mpsc;
+use wp_connector_api::{
+    DataSource, SourceBatch, SourceError, SourceEvent, SourceReason, SourceResult, Tags,
+};
+
+use super::checkpoint::{FileCheckpoint, FileId};
+use super::source::{FileEncoding, FileSource};
+use crate::sources::event_id::next_event_id;
+
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 1_000;
+const WATCH_BATCH_LINES: usize = 128;
+const CHECKPOINT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct WatchSpec {
+    /// 文件名部分可含 glob，例如 `/spool/access-*.log`
+    pub pattern: String,
+    pub poll_interval: Duration,
+    pub checkpoint: PathBuf,
+}
+
+impl WatchSpec {
+    /// 监听目录；glob 只允许出现在文件名中
+    pub fn watch_dir(&self) -> anyhow::Result<PathBuf> {
+        let path = Path::new(&self.pattern);
+        let dir = path
+            .parent()
+            .filter(|d| !d.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        anyhow::ensure!(
+            !is_glob(&dir.display().to_string()),
+            "file source glob is only supported in the file name: {}",
+            self.pattern
+        );
+        glob::Pattern::new(&self.pattern)
+            .map_err(|e| anyhow::anyhow!("Invalid file glob '{}': {}", self.pattern, e))?;
+        Ok(dir.to_path_buf())
+    }
+}
+
+pub fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+struct TailedFile {
+    path: PathBuf,
+    reader: BufReader<tokio::fs::File>,
+    tags: Arc<Tags>,
+    /// 已交付的字节数（完整行）
+    offset: u64,
+    /// 未以换行结尾的行尾
+    partial: Vec<u8>,
+    /// 已不再匹配（轮转/改名/删除）：读到 EOF 后释放
+    draining: bool,
+}
+
+pub struct FileWatchSource {
+    key: String,
+    spec: WatchSpec,
+    encode: FileEncoding,
+    base_tags: Tags,
+    files: BTreeMap<FileId, TailedFile>,
+    checkpoint: FileCheckpoint,
+    scanned: bool,
+    last_scan: Instant,
+    last_flush: Instant,
+    wake_rx: Option<mpsc::Receiver<()>>,
+    _watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl FileWatchSource {
+    pub fn new(
+        key: String,
+        spec: WatchSpec,
+        encode: FileEncoding,
+        tags: Tags,
+    ) -> SourceResult<Self> {
+        let dir = spec
+            .watch_dir()
+            .map_err(|e| SourceReason::from_conf(e.to_string()).to_err())?;
+        let (watcher, wake_rx) = match start_watcher(&dir) {
+            Ok((w, rx)) => (Some(w), Some(rx)),
+            Err(e) => {
+                warn_data!(
+                    "file source '{}' cannot watch {} ({}), falling back to polling every {:?}",
+                    key,
+                    dir.display(),
+                    e,
+                    spec.poll_interval
+                );
+                (None, None)
+            }
+        };
+        let checkpoint = FileCheckpoint::load(&spec.checkpoint);
+        info_data!(
+            "file source '{}' watching {} (checkpoint={})",
+            key,
+            spec.pattern,
+            checkpoint.path().display()
+        );
+        Ok(Self {
+            key,
+            spec,
+            encode,
+            base_tags: tags,
+            files: BTreeMap::new(),
+            checkpoint,
+            scanned: false,
+            last_scan: Instant::now(),
+            last_flush: Instant::now(),
+            wake_rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// 当前正在追读的文件
+    pub fn tracked_files(&self) -> Vec<PathBuf> {
+        self.files.values().map(|f| f.path.clone()).collect()
+    }
+
+    fn discover(&self) -> BTreeMap<FileId, PathBuf> {
+        let mut found = BTreeMap::new();
+        let entries = match glob::glob(&self.spec.pattern) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn_data!("file source '{}' glob failed: {}", self.key, e);
+                return found;
+            }
+        };
+        for path in entries.flatten() {
+            if let Ok(meta) = std::fs::metadata(&path)
+                && meta.is_file()
+            {
+                found.insert(FileId::of(&path, &meta), path);
+            }
+        }
+        found
+    }
+
+    async fn scan(&mut self) -> SourceResult<()> {
+        self.last_scan = Instant::now();
+        let found = self.discover();
+        if !self.scanned {
+            // 启动时已不存在的文件不会再出现，丢弃其位点
+            self.checkpoint.retain(|id| found.contains_key(id));
+            self.scanned = true;
+        }
+
+        for (id, file) in self.files.iter_mut() {
+            match found.get(id) {
+                Some(path) => {
+                    if *path != file.path {
+                        info_data!(
+                            "file source '{}' follows rename {} -> {}",
+                            self.key,
+                            file.path.display(),
+                            path.display()
+                        );
+                        file.path = path.clone();
+                    }
+                    // copytruncate：文件被截断后从头读
+                    let read_pos = file.offset + file.partial.len() as u64;
+                    if let Ok(meta) = std::fs::metadata(path)
+                        && meta.len() < read_pos
+                    {
+                        warn_data!(
+                            "file source '{}' detected truncation of {}, reading from start",
+                            self.key,
+                            path.display()
+                        );
+                        file.reader
+                            .seek(std::io::SeekFrom::Start(0))
+                            .await
+                            .map_err(|e| {
+                                SourceError::from(SourceReason::Disconnect(e.to_string()))
+                            })?;
+                        file.offset = 0;
+                        file.partial.clear();
+                    }
+                }
+                None if !file.draining => {
+                    debug_data!(
+                        "file source '{}' draining rotated/removed {}",
+                        self.key,
+                        file.path.display()
+                    );
+                    file.draining = true;
+                }
+                None => {}
+            }
+        }
+
+        for (id, path) in found {
+            if self.files.contains_key(&id) {
+                continue;
+            }
+            match self.open(id, &path).await {
+                Ok(file) => {
+                    info_data!(
+                        "file source '{}' picked up {} at offset {}",
+                        self.key,
+                        path.display(),
+                        file.offset
+                    );
+                    self.checkpoint.update(id, &path, file.offset);
+                    self.files.insert(id, file);
+                }
+                Err(e) => warn_data!(
+                    "file source '{}' cannot open {}: {}",
+                    self.key,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    async fn open(&self, id: FileId, path: &Path) -> std::io::Result<TailedFile> {
+        let mut file = tokio::fs::File::open(path).await?;
+        let len = file.metadata().await?.len();
+        let offset = self
+            .checkpoint
+            .offset_of(&id)
+            .filter(|off| *off <= len)
+            .unwrap_or(0);
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        let mut tags = self.base_tags.clone();
+        tags.set("access_source", path.display().to_string());
+        Ok(TailedFile {
+            path: path.to_path_buf(),
+            reader: BufReader::new(file),
+            tags: Arc::new(tags),
+            offset,
+            partial: Vec::new(),
+            draining: false,
+        })
+    }
+
+    async fn read_lines(&mut self, batch: &mut SourceBatch) -> SourceResult<()> {
+        let mut released = Vec::new();
+        for (id, file) in self.files.iter_mut() {
+            while batch.len() < WATCH_BATCH_LINES {
+                let read = file
+                    .reader
+                    .read_until(b'\n', &mut file.partial)
+                    .await
+                    .map_err(|e| SourceError::from(SourceReason::Disconnect(e.to_string())))?;
+                if file.partial.last() != Some(&b'\n') {
+                    if read > 0 {
+                        // 读到 EOF 前的半行，下一次读取确认是否已到末尾
+                        continue;
+                    }
+                    if file.draining {
+                        // 已轮转的文件不会再追加，剩余半行作为最后一行交付
+                        if !file.partial.is_empty() {
+                            let line = std::mem::take(&mut file.partial);
+                            Self::push_line(&self.key, &self.encode, file, line, batch)?;
+                        }
+                        released.push(*id);
+                    }
+                    break;
+                }
+                let line = std::mem::take(&mut file.partial);
+                Self::push_line(&self.key, &self.encode, file, line, batch)?;
+            }
+            if !released.contains(id) {
+                self.checkpoint.update(*id, &file.path, file.offset);
+            }
+        }
+        for id in released {
+            if let Some(file) = self.files.remove(&id) {
+                info_data!(
+                    "file source '{}' finished {} ({} bytes), released",
+                    self.key,
+                    file.path.display(),
+                    file.offset
+                );
+            }
+            self.checkpoint.remove(&id);
+        }
+        Ok(())
+    }
+
+    fn push_line(
+        key: &str,
+        encode: &FileEncoding,
+        file: &mut TailedFile,
+        mut line: Vec<u8>,
+        batch: &mut SourceBatch,
+    ) -> SourceResult<()> {
+        file.offset += line.len() as u64;
+        trim_crlf(&mut line);
+        let payload = FileSource::payload_from_line(encode, line)?;
+        batch.push(SourceEvent::new(
+            next_event_id(),
+            key,
+            payload,
+            file.tags.clone(),
+        ));
+        Ok(())
+    }
+
+    fn flush_checkpoint(&mut self, force: bool) {
+        if !force && self.last_flush.elapsed() < CHECKPOINT_FLUSH_INTERVAL {
+            return;
+        }
+        self.last_flush = Instant::now();
+        if let Err(e) = self.checkpoint.save() {
+            warn_data!(
+                "file source '{}' failed to save checkpoint {}: {}",
+                self.key,
+                self.checkpoint.path().display(),
+                e
+            );
+        }
+    }
+
+    async fn wait_for_change(&mut self) -> bool {
+        let remaining = self
+            .spec
+            .poll_interval
+            .saturating_sub(self.last_scan.elapsed());
+        match self.wake_rx.as_mut() {
+            Some(rx) => tokio::select! {
+                woke = rx.recv() => {
+                    if woke.is_none() {
+                        self.wake_rx = None;
+                    }
+                    true
+                }
+                _ = tokio::time::sleep(remaining) => false,
+            },
+            None => {
+                tokio::time::sleep(remaining).await;
+                false
+            }
+        }
+    }
+}
+
+fn start_watcher(dir: &Path) -> notify::Result<(notify::RecommendedWatcher, mpsc::Receiver<()>)> {
+    use notify::Watcher;
+    // 容量 1：事件只用于唤醒扫描，合并即可
+    let (tx, rx) = mpsc::channel(1);
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = tx.try_send(());
+        }
+    })?;
+    watcher.watch(dir, notify::RecursiveMode::NonRecursive)?;
+    Ok((watcher, rx))
+}
+
+fn trim_crlf(buf: &mut Vec<u8>) {
+    while buf
+        .last()
+        .copied()
+        .is_some_and(|b| b == b'\n' || b == b'\r')
+    {
+        buf.pop();
+    }
+}
+
+#[async_trait]
+impl DataSource for FileWatchSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        let mut force_scan = !self.scanned;
+        loop {
+            if force_scan || self.last_scan.elapsed() >= self.spec.poll_interval {
+                self.scan().await?;
+            }
+            let mut batch = SourceBatch::with_capacity(WATCH_BATCH_LINES);
+            self.read_lines(&mut batch).await?;
+            self.flush_checkpoint(false);
+            if !batch.is_empty() {
+                return Ok(batch);
+            }
+            force_scan = self.wait_for_change().await;
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        None
+    }
+
+    fn can_try_receive(&mut self) -> bool {
+        false
+    }
+
+    fn identifier(&self) -> String {
+        self.key.clone()
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.flush_checkpoint(true);
+        self.files.clear();
+        self._watcher = None;
+        info_data!("file source '{}' closed", self.key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use wp_parse_api::RawData;
+
+    fn spec(dir: &Path) -> WatchSpec {
+        WatchSpec {
+            pattern: dir.join("access-*.log").display().to_string(),
+            poll_interval: Duration::from_millis(50),
+            checkpoint: dir.join(".run/checkpoint.json"),
+        }
+    }
+
+    fn append(path: &Path, data: &str) {
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        f.write_all(data.as_bytes()).unwrap();
+    }
+
+    fn lines(batch: SourceBatch) -> Vec<String> {
+        batch
+            .into_iter()
+            .map(|ev| match ev.payload {
+                RawData::String(s) => s,
+                RawData::Bytes(b) => String::from_utf8_lossy(&b).into_owned(),
+                RawData::ArcBytes(b) => String::from_utf8_lossy(&b).into_owned(),
+            })
+            .collect()
+    }
+
+    /// 收集至少 `n` 行，超时失败
+    async fn collect(src: &mut FileWatchSource, n: usize) -> Vec<String> {
+        let mut out = Vec::new();
+        while out.len() < n {
+            let batch = tokio::time::timeout(Duration::from_secs(5), src.receive())
+                .await
+                .expect("timed out waiting for lines")
+                .expect("receive");
+            out.extend(lines(batch));
+        }
+        out.sort();
+        out
+    }
+
+    fn source(dir: &Path) -> FileWatchSource {
+        FileWatchSource::new("spool".into(), spec(dir), FileEncoding::Text, Tags::new())
+            .expect("watch source")
+    }
+
+    #[tokio::test]
+    async fn tails_appends_and_picks_up_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("access-01.log");
+        append(&first, "a1\na2\na3");
+        append(&dir.path().join("other.txt"), "ignored\n");
+
+        let mut src = source(dir.path());
+        assert_eq!(collect(&mut src, 2).await, vec!["a1", "a2"]);
+
+        // 写完半行 + 新文件出现
+        append(&first, "-tail\r\n");
+        append(&dir.path().join("access-02.log"), "b1\n");
+        assert_eq!(collect(&mut src, 2).await, vec!["a3-tail", "b1"]);
+        assert_eq!(src.tracked_files().len(), 2);
+        src.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn restart_resumes_from_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("access-01.log");
+        append(&file, "l1\nl2\n");
+
+        let mut src = source(dir.path());
+        assert_eq!(collect(&mut src, 2).await, vec!["l1", "l2"]);
+        src.close().await.unwrap();
+
+        append(&file, "l3\n");
+        let mut src = source(dir.path());
+        assert_eq!(collect(&mut src, 1).await, vec!["l3"]);
+        src.close().await.unwrap();
+
+        let ck = FileCheckpoint::load(dir.path().join(".run/checkpoint.json"));
+        let entry = ck.entries().next().expect("entry");
+        assert_eq!(entry.offset, 9);
+    }
+
+    #[tokio::test]
+    async fn rotated_file_is_finished_then_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("access-01.log");
+        append(&file, "r1\n");
+
+        let mut src = source(dir.path());
+        assert_eq!(collect(&mut src, 1).await, vec!["r1"]);
+
+        // 轮转前最后写入的内容（含未换行的尾部）在释放前交付
+        append(&file, "r2\nr3");
+        std::fs::rename(&file, dir.path().join("access-01.log.1")).unwrap();
+        append(&file, "n1\n");
+        assert_eq!(collect(&mut src, 3).await, vec!["n1", "r2", "r3"]);
+        src.close().await.unwrap();
+
+        let ck = FileCheckpoint::load(dir.path().join(".run/checkpoint.json"));
+        let paths: Vec<_> = ck.entries().map(|e| e.path.clone()).collect();
+        assert_eq!(paths, vec![file.display().to_string()]);
+
+        // 删除的文件在重启时丢弃位点
+        std::fs::remove_file(&file).unwrap();
+        let mut src = source(dir.path());
+        src.scan().await.unwrap();
+        src.close().await.unwrap();
+        let ck = FileCheckpoint::load(dir.path().join(".run/checkpoint.json"));
+        assert_eq!(ck.entries().count(), 0);
+    }
+}
@@ -0,0 +1,251 @@
+//! HTTP 接收器：监听地址，处理 POST 请求并将记录批次写入采集队列。
+//!
+//! 每个请求拆分出的记录作为一个批次 `try_send` 到有界队列；队列已满（采集线程跟不上）
+//! 或已关闭时直接返回 503 + `Retry-After`，不在接收侧堆积内存。
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode, header};
+use hyper_util::rt::TokioIo;
+use subtle::ConstantTimeEq;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, watch};
+use wp_connector_api::{
+    ControlEvent, CtrlRx, ServiceAcceptor, SourceBatch, SourceEvent, SourceReason, SourceResult,
+    Tags,
+};
+use wp_parse_api::RawData;
+
+use super::config::HttpSourceSpec;
+use super::ingest::{BodyFormat, IngestError, decode_body, split_records};
+use crate::sources::event_id::next_event_id;
+
+/// 请求处理共享的只读状态
+struct IngestState {
+    key: String,
+    path: String,
+    auth_token: Option<String>,
+    max_body_bytes: usize,
+    tags: Arc<Tags>,
+    tx: mpsc::Sender<SourceBatch>,
+}
+
+impl IngestState {
+    async fn ingest(&self, peer: SocketAddr, req: Request<Incoming>) -> Result<usize, IngestError> {
+        if req.uri().path() != self.path {
+            return Err(IngestError::NotFound);
+        }
+        if req.method() != Method::POST {
+            return Err(IngestError::MethodNotAllowed);
+        }
+        if let Some(token) = self.auth_token.as_deref() {
+            let presented = header_str(&req, header::AUTHORIZATION)
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(str::trim);
+            // 常量时间比较，避免按响应耗时逐字节猜测令牌
+            let matched =
+                presented.is_some_and(|p| bool::from(p.as_bytes().ct_eq(token.as_bytes())));
+            if !matched {
+                return Err(IngestError::Unauthorized);
+            }
+        }
+        let format = BodyFormat::from_content_type(header_str(&req, header::CONTENT_TYPE))?;
+        let encoding = header_str(&req, header::CONTENT_ENCODING).map(str::to_string);
+        // 声明的长度已超限时不读取请求体
+        if let Some(len) =
+            header_str(&req, header::CONTENT_LENGTH).and_then(|v| v.parse::<usize>().ok())
+            && len > self.max_body_bytes
+        {
+            return Err(IngestError::PayloadTooLarge(self.max_body_bytes));
+        }
+
+        let body = Limited::new(req.into_body(), self.max_body_bytes)
+            .collect()
+            .await
+            .map_err(|e| {
+                if e.is::<LengthLimitError>() {
+                    IngestError::PayloadTooLarge(self.max_body_bytes)
+                } else {
+                    IngestError::BadRequest(format!("read body failed: {}", e))
+                }
+            })?
+            .to_bytes();
+        let body = decode_body(body, encoding.as_deref(), self.max_body_bytes)?;
+        let records = split_records(body, format)?;
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        let count = records.len();
+        let batch: SourceBatch = records
+            .into_iter()
+            .map(|payload| {
+                let mut event = SourceEvent::new(
+                    next_event_id(),
+                    &self.key,
+                    RawData::Bytes(payload),
+                    self.tags.clone(),
+                );
+                event.ups_ip = Some(peer.ip());
+                event
+            })
+            .collect();
+        self.tx.try_send(batch).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => IngestError::Unavailable("ingest queue is full"),
+            mpsc::error::TrySendError::Closed(_) => {
+                IngestError::Unavailable("ingest queue is closed")
+            }
+        })?;
+        Ok(count)
+    }
+}
+
+fn header_str<B>(req: &Request<B>, name: header::HeaderName) -> Option<&str> {
+    req.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Full<Bytes>> {
+    let mut resp = Response::new(Full::new(Bytes::from(body.to_string())));
+    *resp.status_mut() = status;
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    resp
+}
+
+fn error_response(err: &IngestError) -> Response<Full<Bytes>> {
+    let mut resp = json_response(err.status(), serde_json::json!({ "error": err.message() }));
+    match err {
+        IngestError::Unauthorized => {
+            resp.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                header::HeaderValue::from_static("Bearer"),
+            );
+        }
+        IngestError::Unavailable(_) => {
+            resp.headers_mut()
+                .insert(header::RETRY_AFTER, header::HeaderValue::from_static("1"));
+        }
+        _ => {}
+    }
+    resp
+}
+
+async fn handle(
+    state: Arc<IngestState>,
+    peer: SocketAddr,
+    req: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let resp = match state.ingest(peer, req).await {
+        Ok(accepted) => json_response(
+            StatusCode::ACCEPTED,
+            serde_json::json!({ "accepted": accepted }),
+        ),
+        Err(e) => {
+            debug_data!(
+                "http source '{}' rejected request from {}: {} {}",
+                state.key,
+                peer,
+                e.status().as_u16(),
+                e.message()
+            );
+            error_response(&e)
+        }
+    };
+    Ok(resp)
+}
+
+pub struct HttpAcceptor {
+    address: String,
+    state: Arc<IngestState>,
+}
+
+impl HttpAcceptor {
+    pub fn new(
+        key: String,
+        conf: &HttpSourceSpec,
+        tags: Tags,
+        tx: mpsc::Sender<SourceBatch>,
+    ) -> Self {
+        Self {
+            address: conf.address(),
+            state: Arc::new(IngestState {
+                key,
+                path: conf.path.clone(),
+                auth_token: conf.auth_token.clone(),
+                max_body_bytes: conf.max_body_bytes,
+                tags: Arc::new(tags),
+                tx,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl ServiceAcceptor for HttpAcceptor {
+    async fn accept_connection(&mut self, mut ctrl_rx: CtrlRx) -> SourceResult<()> {
+        let key = self.state.key.clone();
+        let listener = TcpListener::bind(&self.address).await.map_err(|e| {
+            SourceReason::Disconnect(format!(
+                "http source '{}' failed to bind {}: {}",
+                key, self.address, e
+            ))
+        })?;
+        info_ctrl!(
+            "http source '{}' listening on {}{}",
+            key,
+            self.address,
+            self.state.path
+        );
+        // 停止时通知在途连接优雅关闭，已接收的请求处理完再退出
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        loop {
+            tokio::select! {
+                evt = ctrl_rx.recv() => match evt {
+                    Ok(ControlEvent::Stop) | Ok(ControlEvent::Isolate(true)) | Err(_) => break,
+                    Ok(_) => continue,
+                },
+                accepted = listener.accept() => {
+                    let (stream, peer) = match accepted {
+                        Ok(pair) => pair,
+                        Err(e) => {
+                            warn_data!("http source '{}' accept failed: {}", key, e);
+                            continue;
+                        }
+                    };
+                    let state = self.state.clone();
+                    let mut shutdown = shutdown_rx.clone();
+                    tokio::spawn(async move {
+                        let svc = service_fn(move |req| handle(state.clone(), peer, req));
+                        let conn = http1::Builder::new().serve_connection(TokioIo::new(stream), svc);
+                        tokio::pin!(conn);
+                        let res = tokio::select! {
+                            res = conn.as_mut() => res,
+                            _ = shutdown.changed() => {
+                                conn.as_mut().graceful_shutdown();
+                                conn.await
+                            }
+                        };
+                        if let Err(e) = res {
+                            debug_data!("http connection from {} closed: {}", peer, e);
+                        }
+                    });
+                }
+            }
+        }
+
+        let _ = shutdown_tx.send(true);
+        info_ctrl!("http source '{}' acceptor stopped", key);
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::ensure;
+
+pub const DEFAULT_HTTP_PATH: &str = "/ingest";
+pub const DEFAULT_HTTP_MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+pub const DEFAULT_HTTP_QUEUE_BATCHES: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct HttpSourceSpec {
+    pub addr: String,
+    pub port: u16,
+    /// 接收 POST 的路径
+    pub path: String,
+    /// 配置后要求 `Authorization: Bearer <token>`
+    pub auth_token: Option<String>,
+    /// 请求体上限（gzip 按解压后大小计算）
+    pub max_body_bytes: usize,
+    /// 待采集的请求批次队列容量；队列满时返回 503
+    pub queue_batches: usize,
+}
+
+impl HttpSourceSpec {
+    pub fn from_params(params: &wp_connector_api::ParamMap) -> anyhow::Result<Self> {
+        let addr = params
+            .get("addr")
+            .and_then(|v| v.as_str())
+            .unwrap_or("0.0.0.0")
+            .to_string();
+        let port_i64 = params.get("port").and_then(|v| v.as_i64()).unwrap_or(8080);
+        ensure!(
+            (0..=65535).contains(&port_i64),
+            "Invalid port: {}",
+            port_i64
+        );
+
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_HTTP_PATH)
+            .to_string();
+        ensure!(
+            path.starts_with('/'),
+            "http.path must start with '/': {}",
+            path
+        );
+
+        let auth_token = params
+            .get("auth_token")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+
+        let max_body_bytes = params
+            .get("max_body_bytes")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_HTTP_MAX_BODY_BYTES as i64);
+        ensure!(max_body_bytes > 0, "http.max_body_bytes must be > 0");
+
+        let queue_batches = params
+            .get("queue_batches")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_HTTP_QUEUE_BATCHES as i64);
+        ensure!(queue_batches > 0, "http.queue_batches must be > 0");
+
+        Ok(Self {
+            addr,
+            port: port_i64 as u16,
+            path,
+            auth_token,
+            max_body_bytes: max_body_bytes as usize,
+            queue_batches: queue_batches as usize,
+        })
+    }
+
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.addr, self.port)
+    }
+}
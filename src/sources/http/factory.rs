@@ -0,0 +1,294 @@
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use serde_json::json;
+use tokio::sync::mpsc;
+use wp_conf::connectors::{ConnectorDef, ConnectorScope, ParamMap};
+use wp_connector_api::{
+    AcceptorHandle, SourceBuildCtx, SourceDefProvider, SourceFactory, SourceHandle, SourceMeta,
+    SourceReason, SourceResult, SourceSpec as ResolvedSourceSpec, SourceSvcIns, Tags,
+};
+
+use super::acceptor::HttpAcceptor;
+use super::config::{
+    DEFAULT_HTTP_MAX_BODY_BYTES, DEFAULT_HTTP_PATH, DEFAULT_HTTP_QUEUE_BATCHES, HttpSourceSpec,
+};
+use super::source::HttpSource;
+
+pub struct HttpSourceFactory;
+
+#[async_trait::async_trait]
+impl SourceFactory for HttpSourceFactory {
+    fn kind(&self) -> &'static str {
+        "http"
+    }
+
+    fn validate_spec(&self, spec: &ResolvedSourceSpec) -> SourceResult<()> {
+        let res: anyhow::Result<()> = (|| {
+            if let Err(e) = Tags::validate(&spec.tags) {
+                anyhow::bail!("Invalid tags: {}", e);
+            }
+            HttpSourceSpec::from_params(&spec.params)?;
+            Ok(())
+        })();
+        res.map_err(|e| SourceReason::from_conf(e.to_string()).to_err())
+    }
+
+    async fn build(
+        &self,
+        spec: &ResolvedSourceSpec,
+        _ctx: &SourceBuildCtx,
+    ) -> SourceResult<SourceSvcIns> {
+        let fut = async {
+            let conf = HttpSourceSpec::from_params(&spec.params)?;
+            let mut tags = Tags::from_parse(&spec.tags);
+            tags.set("access_source", "http".to_string());
+
+            let (tx, rx) = mpsc::channel(conf.queue_batches);
+            let source = HttpSource::new(spec.name.clone(), rx);
+            let acceptor = HttpAcceptor::new(spec.name.clone(), &conf, tags.clone(), tx);
+
+            let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
+            for (k, v) in tags.iter() {
+                meta.tags.set(k, v);
+            }
+            Ok(SourceSvcIns::new()
+                .with_sources(vec![SourceHandle::new(Box::new(source), meta)])
+                .with_acceptor(AcceptorHandle::new(spec.name.clone(), Box::new(acceptor))))
+        };
+        fut.await
+            .map_err(|e: anyhow::Error| SourceReason::from_conf(e.to_string()).to_err())
+    }
+}
+
+impl SourceDefProvider for HttpSourceFactory {
+    fn source_def(&self) -> ConnectorDef {
+        let mut params = ParamMap::new();
+        params.insert("addr".into(), json!("0.0.0.0"));
+        params.insert("port".into(), json!(8080));
+        params.insert("path".into(), json!(DEFAULT_HTTP_PATH));
+        params.insert("max_body_bytes".into(), json!(DEFAULT_HTTP_MAX_BODY_BYTES));
+        params.insert("queue_batches".into(), json!(DEFAULT_HTTP_QUEUE_BATCHES));
+        ConnectorDef {
+            id: "http_src".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Source,
+            allow_override: vec![
+                "addr".into(),
+                "port".into(),
+                "path".into(),
+                "auth_token".into(),
+                "max_body_bytes".into(),
+                "queue_batches".into(),
+            ],
+            default_params: params,
+            origin: Some("builtin:http_source".into()),
+        }
+    }
+}
+
+/// 注册 HTTP 源工厂（集中由引擎启动入口调用）
+pub fn register_http_factory() {
+    crate::connectors::registry::register_source_factory(HttpSourceFactory);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::Duration;
+    use wp_connector_api::{ControlEvent, SourceBatch};
+    use wp_parse_api::RawData;
+
+    fn spec_with(params: toml::map::Map<String, toml::Value>) -> ResolvedSourceSpec {
+        ResolvedSourceSpec {
+            name: "http_in".into(),
+            kind: "http".into(),
+            connector_id: "http_src".into(),
+            params: wp_connector_api::parammap_from_toml_map(params),
+            tags: vec!["env:test".into()],
+        }
+    }
+
+    fn payloads(batch: SourceBatch) -> Vec<String> {
+        batch
+            .into_iter()
+            .map(|ev| match ev.payload {
+                RawData::String(s) => s,
+                RawData::Bytes(b) => String::from_utf8_lossy(&b).into_owned(),
+                RawData::ArcBytes(b) => String::from_utf8_lossy(&b).into_owned(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn validate_spec_and_default_def() {
+        let fac = HttpSourceFactory;
+        let def = fac.source_def();
+        assert_eq!(def.kind, "http");
+        assert!(HttpSourceSpec::from_params(&def.default_params).is_ok());
+
+        let mut t = toml::map::Map::new();
+        t.insert("port".into(), toml::Value::Integer(18080));
+        t.insert("auth_token".into(), toml::Value::String("s3cret".into()));
+        fac.validate_spec(&spec_with(t.clone())).expect("valid");
+
+        t.insert("path".into(), toml::Value::String("ingest".into()));
+        assert!(fac.validate_spec(&spec_with(t.clone())).is_err());
+        t.remove("path");
+        t.insert("max_body_bytes".into(), toml::Value::Integer(0));
+        assert!(fac.validate_spec(&spec_with(t)).is_err());
+    }
+
+    /// 构建并启动 source + acceptor，返回 source 句柄、停止信号与监听地址
+    async fn start_http(
+        extra: &[(&str, toml::Value)],
+    ) -> (SourceHandle, async_broadcast::Sender<ControlEvent>, String) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let mut t = toml::map::Map::new();
+        t.insert("addr".into(), toml::Value::String("127.0.0.1".into()));
+        t.insert("port".into(), toml::Value::Integer(port as i64));
+        for (k, v) in extra {
+            t.insert((*k).into(), v.clone());
+        }
+        let ctx = SourceBuildCtx::new(std::env::current_dir().unwrap());
+        let mut svc = HttpSourceFactory
+            .build(&spec_with(t), &ctx)
+            .await
+            .expect("build http source");
+        let mut handle = svc.sources.remove(0);
+        let mut acceptor = svc.acceptor.take().expect("acceptor").acceptor;
+
+        let (ctrl_tx, ctrl_rx) = async_broadcast::broadcast::<ControlEvent>(4);
+        handle.source.start(ctrl_rx.clone()).await.unwrap();
+        tokio::spawn(async move { acceptor.accept_connection(ctrl_rx).await });
+        // 等待监听就绪
+        let addr = format!("127.0.0.1:{}", port);
+        for _ in 0..50 {
+            if tokio::net::TcpStream::connect(&addr).await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        (handle, ctrl_tx, format!("http://{}/ingest", addr))
+    }
+
+    #[tokio::test]
+    async fn end_to_end_json_ndjson_text_and_gzip() {
+        // 在受限沙箱（无网络权限）环境下跳过
+        if std::env::var("WP_NET_TESTS").unwrap_or_default() != "1" {
+            return;
+        }
+        let (mut handle, ctrl_tx, url) = start_http(&[]).await;
+        let client = reqwest::Client::new();
+
+        let resp = client
+            .post(&url)
+            .header("content-type", "application/json")
+            .body(r#"[{"msg":"a"},{"msg":"b"}]"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 202);
+        assert_eq!(resp.text().await.unwrap(), r#"{"accepted":2}"#);
+        let batch = handle.source.receive().await.unwrap();
+        assert!(batch.iter().all(|ev| ev.src_key.as_str() == "http_in"));
+        assert!(batch.iter().all(|ev| ev.ups_ip.is_some()));
+        assert_eq!(payloads(batch), vec![r#"{"msg":"a"}"#, r#"{"msg":"b"}"#]);
+
+        let resp = client
+            .post(&url)
+            .header("content-type", "application/x-ndjson")
+            .body("{\"n\":1}\n{\"n\":2}\n")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 202);
+        let batch = handle.source.receive().await.unwrap();
+        assert_eq!(payloads(batch), vec![r#"{"n":1}"#, r#"{"n":2}"#]);
+
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"line one\nline two\n").unwrap();
+        let resp = client
+            .post(&url)
+            .header("content-type", "text/plain")
+            .header("content-encoding", "gzip")
+            .body(enc.finish().unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 202);
+        let batch = handle.source.receive().await.unwrap();
+        assert_eq!(payloads(batch), vec!["line one", "line two"]);
+
+        let resp = client
+            .post(&url)
+            .header("content-type", "application/json")
+            .body("[not json")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 400);
+        let resp = client.get(&url).send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 405);
+
+        ctrl_tx.broadcast(ControlEvent::Stop).await.unwrap();
+        handle.source.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn end_to_end_auth_size_limit_and_backpressure() {
+        if std::env::var("WP_NET_TESTS").unwrap_or_default() != "1" {
+            return;
+        }
+        let (mut handle, ctrl_tx, url) = start_http(&[
+            ("auth_token", toml::Value::String("s3cret".into())),
+            ("max_body_bytes", toml::Value::Integer(64)),
+            ("queue_batches", toml::Value::Integer(1)),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+
+        let resp = client.post(&url).body("x").send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 401);
+        assert_eq!(resp.headers()["www-authenticate"], "Bearer");
+        let resp = client
+            .post(&url)
+            .bearer_auth("wrong")
+            .body("x")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 401);
+
+        let resp = client
+            .post(&url)
+            .bearer_auth("s3cret")
+            .body(vec![b'a'; 65])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 413);
+
+        // 队列容量为 1：第一个批次入队，第二个返回 503，取走后恢复
+        let send = |body: &'static str| client.post(&url).bearer_auth("s3cret").body(body).send();
+        assert_eq!(send("first").await.unwrap().status().as_u16(), 202);
+        let resp = send("second").await.unwrap();
+        assert_eq!(resp.status().as_u16(), 503);
+        assert_eq!(resp.headers()["retry-after"], "1");
+        assert_eq!(
+            payloads(handle.source.receive().await.unwrap()),
+            vec!["first"]
+        );
+        assert_eq!(send("third").await.unwrap().status().as_u16(), 202);
+        assert_eq!(
+            payloads(handle.source.receive().await.unwrap()),
+            vec!["third"]
+        );
+
+        ctrl_tx.broadcast(ControlEvent::Stop).await.unwrap();
+        handle.source.close().await.unwrap();
+    }
+}
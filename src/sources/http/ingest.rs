@@ -0,0 +1,205 @@
+//! 请求体解码：gzip 解压、按 Content-Type 拆分为记录。
+
+use std::io::Read;
+
+use bytes::Bytes;
+use flate2::read::GzDecoder;
+use hyper::StatusCode;
+
+/// 拒绝请求的原因，对应返回给客户端的状态码
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IngestError {
+    Unauthorized,
+    NotFound,
+    MethodNotAllowed,
+    PayloadTooLarge(usize),
+    UnsupportedMediaType(String),
+    BadRequest(String),
+    /// 采集队列已满或已关闭，客户端应稍后重试
+    Unavailable(&'static str),
+}
+
+impl IngestError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            IngestError::Unauthorized => StatusCode::UNAUTHORIZED,
+            IngestError::NotFound => StatusCode::NOT_FOUND,
+            IngestError::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            IngestError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            IngestError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            IngestError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            IngestError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            IngestError::Unauthorized => "missing or invalid bearer token".into(),
+            IngestError::NotFound => "not found".into(),
+            IngestError::MethodNotAllowed => "only POST is accepted".into(),
+            IngestError::PayloadTooLarge(limit) => {
+                format!("request body exceeds {} bytes", limit)
+            }
+            IngestError::UnsupportedMediaType(ct) => format!(
+                "unsupported content-type '{}' (expect application/json, application/x-ndjson or text/plain)",
+                ct
+            ),
+            IngestError::BadRequest(msg) => msg.clone(),
+            IngestError::Unavailable(msg) => msg.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// JSON 数组（每个元素一条记录）或 NDJSON（每行一条记录）
+    Json,
+    /// 按行拆分的纯文本
+    Text,
+}
+
+impl BodyFormat {
+    /// 解析 Content-Type；缺省按纯文本处理
+    pub fn from_content_type(content_type: Option<&str>) -> Result<Self, IngestError> {
+        let Some(raw) = content_type else {
+            return Ok(BodyFormat::Text);
+        };
+        let mime = raw.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "application/json" | "application/x-ndjson" | "application/ndjson" => {
+                Ok(BodyFormat::Json)
+            }
+            "text/plain" | "" => Ok(BodyFormat::Text),
+            _ => Err(IngestError::UnsupportedMediaType(raw.to_string())),
+        }
+    }
+}
+
+/// 按 Content-Encoding 解压；解压后超过 `max_bytes` 视为超限
+pub fn decode_body(
+    body: Bytes,
+    content_encoding: Option<&str>,
+    max_bytes: usize,
+) -> Result<Bytes, IngestError> {
+    match content_encoding.map(|s| s.trim().to_ascii_lowercase()) {
+        None => Ok(body),
+        Some(enc) if enc.is_empty() || enc == "identity" => Ok(body),
+        Some(enc) if enc == "gzip" || enc == "x-gzip" => {
+            let mut out = Vec::with_capacity(body.len().saturating_mul(4).min(max_bytes));
+            GzDecoder::new(body.as_ref())
+                .take(max_bytes as u64 + 1)
+                .read_to_end(&mut out)
+                .map_err(|e| IngestError::BadRequest(format!("invalid gzip body: {}", e)))?;
+            if out.len() > max_bytes {
+                return Err(IngestError::PayloadTooLarge(max_bytes));
+            }
+            Ok(Bytes::from(out))
+        }
+        Some(enc) => Err(IngestError::UnsupportedMediaType(format!(
+            "content-encoding {}",
+            enc
+        ))),
+    }
+}
+
+/// 将请求体拆分为记录：JSON 数组按元素、NDJSON/文本按行（忽略空行）
+pub fn split_records(body: Bytes, format: BodyFormat) -> Result<Vec<Bytes>, IngestError> {
+    let trimmed_start = body
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(body.len());
+    if format == BodyFormat::Json && body.get(trimmed_start) == Some(&b'[') {
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&body)
+            .map_err(|e| IngestError::BadRequest(format!("invalid json array: {}", e)))?;
+        return Ok(items
+            .into_iter()
+            .map(|item| Bytes::from(item.to_string()))
+            .collect());
+    }
+
+    let mut out = Vec::new();
+    for (lineno, line) in body.split(|b| *b == b'\n').enumerate() {
+        let text = line.trim_ascii();
+        if text.is_empty() {
+            continue;
+        }
+        if format == BodyFormat::Json {
+            serde_json::from_slice::<serde::de::IgnoredAny>(text).map_err(|e| {
+                IngestError::BadRequest(format!("invalid ndjson line {}: {}", lineno + 1, e))
+            })?;
+        }
+        out.push(body.slice_ref(text));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    fn texts(records: Vec<Bytes>) -> Vec<String> {
+        records
+            .into_iter()
+            .map(|b| String::from_utf8(b.to_vec()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn split_json_array_ndjson_and_text() {
+        let arr = Bytes::from_static(br#" [{"a":1}, {"b":"x y"}, 3] "#);
+        assert_eq!(
+            texts(split_records(arr, BodyFormat::Json).unwrap()),
+            vec![r#"{"a":1}"#, r#"{"b":"x y"}"#, "3"]
+        );
+
+        let nd = Bytes::from_static(b"{\"a\":1}\r\n\n{\"b\":2}\n");
+        assert_eq!(
+            texts(split_records(nd, BodyFormat::Json).unwrap()),
+            vec![r#"{"a":1}"#, r#"{"b":2}"#]
+        );
+
+        let bad = Bytes::from_static(b"{\"a\":1}\nnot json\n");
+        assert!(matches!(
+            split_records(bad, BodyFormat::Json),
+            Err(IngestError::BadRequest(msg)) if msg.contains("line 2")
+        ));
+
+        let text = Bytes::from_static(b"line one\r\nline two\n\n");
+        assert_eq!(
+            texts(split_records(text, BodyFormat::Text).unwrap()),
+            vec!["line one", "line two"]
+        );
+    }
+
+    #[test]
+    fn content_type_and_gzip_decoding() {
+        assert_eq!(
+            BodyFormat::from_content_type(Some("application/json; charset=utf-8")),
+            Ok(BodyFormat::Json)
+        );
+        assert_eq!(BodyFormat::from_content_type(None), Ok(BodyFormat::Text));
+        assert_eq!(
+            BodyFormat::from_content_type(Some("image/png"))
+                .unwrap_err()
+                .status(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(&vec![b'a'; 4096]).unwrap();
+        let gz = Bytes::from(enc.finish().unwrap());
+        assert_eq!(
+            decode_body(gz.clone(), Some("gzip"), 4096).unwrap().len(),
+            4096
+        );
+        // 限制按解压后大小计算，防止压缩炸弹
+        assert_eq!(
+            decode_body(gz, Some("gzip"), 1024),
+            Err(IngestError::PayloadTooLarge(1024))
+        );
+        assert!(decode_body(Bytes::from_static(b"nope"), Some("gzip"), 1024).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! HTTP 采集源：接收 agent 推送的 POST 请求
+//!
+//! 模块结构：
+//! - config.rs：参数解析（addr/port/path/auth_token/max_body_bytes/queue_batches）
+//! - ingest.rs：请求体解码（gzip）与拆分（JSON 数组/NDJSON/纯文本）
+//! - acceptor.rs：HttpAcceptor，监听并处理请求，记录批次写入有界队列
+//! - source.rs：HttpSource，从队列交付批次
+//! - factory.rs：HttpSourceFactory
+//!
+//! 状态码：202 已接收；401 鉴权失败；404/405 路径或方法不匹配；413 请求体超限；
+//! 415 不支持的 Content-Type/Content-Encoding；400 内容无法解析；503 队列已满（带 `Retry-After`）。
+//!
+//! ```toml
+//! [[sources]]
+//! key = "agent_push"
+//! connect = "http_src"
+//! params_override = { port = 8080, path = "/ingest", auth_token = "${HTTP_INGEST_TOKEN}" }
+//! ```
+
+pub mod acceptor;
+pub mod config;
+pub mod factory;
+pub mod ingest;
+pub mod source;
+
+pub use acceptor::HttpAcceptor;
+pub use config::HttpSourceSpec;
+pub use factory::{HttpSourceFactory, register_http_factory};
+pub use source::HttpSource;
@@ -0,0 +1,53 @@
+//! HttpSource：从接收器队列取出请求批次交付给采集线程。
+
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TryRecvError;
+use wp_connector_api::{CtrlRx, DataSource, SourceBatch, SourceReason, SourceResult};
+
+pub struct HttpSource {
+    key: String,
+    rx: mpsc::Receiver<SourceBatch>,
+}
+
+impl HttpSource {
+    pub fn new(key: String, rx: mpsc::Receiver<SourceBatch>) -> Self {
+        Self { key, rx }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for HttpSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        // 接收器退出后队列关闭，剩余批次取完即结束
+        match self.rx.recv().await {
+            Some(batch) => Ok(batch),
+            None => Err(SourceReason::EOF.into()),
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        match self.rx.try_recv() {
+            Ok(batch) => Some(batch),
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => None,
+        }
+    }
+
+    fn can_try_receive(&mut self) -> bool {
+        !self.rx.is_empty()
+    }
+
+    fn identifier(&self) -> String {
+        self.key.clone()
+    }
+
+    async fn start(&mut self, _ctrl_rx: CtrlRx) -> SourceResult<()> {
+        info_data!("http source '{}' started", self.key);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.rx.close();
+        info_data!("http source '{}' closed", self.key);
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::{anyhow, ensure};
+use regex::Regex;
+
+pub const DEFAULT_KAFKA_MAX_POLL_RECORDS: usize = 500;
+pub const MAX_KAFKA_MAX_POLL_RECORDS: usize = 10_000;
+pub const DEFAULT_KAFKA_COMMIT_INTERVAL_MS: u64 = 1_000;
+
+/// 消费位点重置策略（组内无已提交位点时生效）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoOffsetReset {
+    Earliest,
+    Latest,
+}
+
+impl AutoOffsetReset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoOffsetReset::Earliest => "earliest",
+            AutoOffsetReset::Latest => "latest",
+        }
+    }
+}
+
+/// SASL/TLS 安全配置；口令等敏感值通过连接器参数的 `${ENV}` 替换注入
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KafkaSecurity {
+    /// plaintext|ssl|sasl_plaintext|sasl_ssl
+    pub protocol: Option<String>,
+    pub sasl_mechanism: Option<String>,
+    pub sasl_username: Option<String>,
+    pub sasl_password: Option<String>,
+    pub ssl_ca_location: Option<String>,
+    pub ssl_certificate_location: Option<String>,
+    pub ssl_key_location: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KafkaSourceSpec {
+    pub brokers: String,
+    /// 订阅主题；以 `^` 开头的条目按正则订阅
+    pub topics: Vec<String>,
+    pub group_id: String,
+    pub auto_offset_reset: AutoOffsetReset,
+    pub max_poll_records: usize,
+    pub commit_interval_ms: u64,
+    pub security: KafkaSecurity,
+    /// 透传给客户端的额外配置（`key=value`）
+    pub config: Vec<(String, String)>,
+}
+
+impl KafkaSourceSpec {
+    pub fn from_params(params: &wp_connector_api::ParamMap) -> anyhow::Result<Self> {
+        let brokers = opt_str(params, "brokers").unwrap_or_default();
+        ensure!(
+            !brokers.trim().is_empty(),
+            "kafka.brokers must not be empty"
+        );
+
+        let topics: Vec<String> = match params.get("topic") {
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| anyhow!("kafka.topic entries must be strings"))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Some(other) => return Err(anyhow!("Invalid kafka.topic: {}", other)),
+            None => Vec::new(),
+        };
+        ensure!(
+            topics.iter().any(|t| !t.trim().is_empty()),
+            "kafka.topic must list at least one topic"
+        );
+        for topic in topics.iter().filter(|t| t.starts_with('^')) {
+            Regex::new(topic)
+                .map_err(|e| anyhow!("Invalid kafka.topic regex '{}': {}", topic, e))?;
+        }
+
+        let group_id = opt_str(params, "group_id").unwrap_or_default();
+        ensure!(
+            !group_id.trim().is_empty(),
+            "kafka.group_id must not be empty"
+        );
+
+        let auto_offset_reset = match opt_str(params, "auto_offset_reset")
+            .unwrap_or_else(|| "latest".to_string())
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "earliest" | "smallest" => AutoOffsetReset::Earliest,
+            "latest" | "largest" => AutoOffsetReset::Latest,
+            other => {
+                return Err(anyhow!(
+                    "Invalid auto_offset_reset: {} (expect earliest|latest)",
+                    other
+                ));
+            }
+        };
+
+        let max_poll_records = params
+            .get("max_poll_records")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_KAFKA_MAX_POLL_RECORDS as i64);
+        ensure!(
+            (1..=MAX_KAFKA_MAX_POLL_RECORDS as i64).contains(&max_poll_records),
+            "kafka.max_poll_records must be between 1 and {}",
+            MAX_KAFKA_MAX_POLL_RECORDS
+        );
+
+        let commit_interval_ms = params
+            .get("commit_interval_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_KAFKA_COMMIT_INTERVAL_MS);
+
+        let security = KafkaSecurity {
+            protocol: opt_str(params, "security_protocol").map(|s| s.to_ascii_lowercase()),
+            sasl_mechanism: opt_str(params, "sasl_mechanism"),
+            sasl_username: opt_str(params, "sasl_username"),
+            sasl_password: opt_str(params, "sasl_password"),
+            ssl_ca_location: opt_str(params, "ssl_ca_location"),
+            ssl_certificate_location: opt_str(params, "ssl_certificate_location"),
+            ssl_key_location: opt_str(params, "ssl_key_location"),
+        };
+        if let Some(proto) = security.protocol.as_deref() {
+            ensure!(
+                matches!(proto, "plaintext" | "ssl" | "sasl_plaintext" | "sasl_ssl"),
+                "Invalid security_protocol: {} (expect plaintext|ssl|sasl_plaintext|sasl_ssl)",
+                proto
+            );
+            if proto.starts_with("sasl") {
+                ensure!(
+                    security.sasl_username.is_some() && security.sasl_password.is_some(),
+                    "security_protocol={} requires sasl_username and sasl_password",
+                    proto
+                );
+            }
+        }
+
+        let mut config = Vec::new();
+        if let Some(serde_json::Value::Array(items)) = params.get("config") {
+            for item in items {
+                let raw = item
+                    .as_str()
+                    .ok_or_else(|| anyhow!("kafka.config entries must be 'key=value' strings"))?;
+                let (k, v) = raw
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("Invalid kafka.config entry: {}", raw))?;
+                config.push((k.trim().to_string(), v.trim().to_string()));
+            }
+        }
+
+        Ok(Self {
+            brokers,
+            topics: topics
+                .into_iter()
+                .filter(|t| !t.trim().is_empty())
+                .collect(),
+            group_id,
+            auto_offset_reset,
+            max_poll_records: max_poll_records as usize,
+            commit_interval_ms,
+            security,
+            config,
+        })
+    }
+
+    /// 生成客户端配置项；位点由 source 在交付后手动提交，因此关闭自动提交
+    pub fn client_config(&self) -> Vec<(String, String)> {
+        let mut out = vec![
+            ("bootstrap.servers".to_string(), self.brokers.clone()),
+            ("group.id".to_string(), self.group_id.clone()),
+            ("enable.auto.commit".to_string(), "false".to_string()),
+            ("enable.auto.offset.store".to_string(), "false".to_string()),
+            (
+                "auto.offset.reset".to_string(),
+                self.auto_offset_reset.as_str().to_string(),
+            ),
+        ];
+        let sec = &self.security;
+        let pairs = [
+            ("security.protocol", &sec.protocol),
+            ("sasl.mechanism", &sec.sasl_mechanism),
+            ("sasl.username", &sec.sasl_username),
+            ("sasl.password", &sec.sasl_password),
+            ("ssl.ca.location", &sec.ssl_ca_location),
+            ("ssl.certificate.location", &sec.ssl_certificate_location),
+            ("ssl.key.location", &sec.ssl_key_location),
+        ];
+        for (key, val) in pairs {
+            if let Some(v) = val {
+                out.push((key.to_string(), v.clone()));
+            }
+        }
+        // 额外配置最后写入，允许覆盖上面的默认项
+        out.extend(self.config.iter().cloned());
+        out
+    }
+}
+
+fn opt_str(params: &wp_connector_api::ParamMap, key: &str) -> Option<String> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wp_connector_api::ParamMap;
+
+    fn base_params() -> ParamMap {
+        let mut p = ParamMap::new();
+        p.insert("brokers".into(), json!("k1:9092,k2:9092"));
+        p.insert("topic".into(), json!(["access_log", "^audit-.*"]));
+        p.insert("group_id".into(), json!("wparse"));
+        p
+    }
+
+    #[test]
+    fn parse_defaults_and_client_config() {
+        let spec = KafkaSourceSpec::from_params(&base_params()).expect("spec");
+        assert_eq!(spec.topics, vec!["access_log", "^audit-.*"]);
+        assert_eq!(spec.auto_offset_reset, AutoOffsetReset::Latest);
+        assert_eq!(spec.max_poll_records, DEFAULT_KAFKA_MAX_POLL_RECORDS);
+        let conf = spec.client_config();
+        assert!(conf.contains(&("enable.auto.commit".into(), "false".into())));
+        assert!(conf.contains(&("group.id".into(), "wparse".into())));
+    }
+
+    #[test]
+    fn parse_security_and_overrides() {
+        let mut p = base_params();
+        p.insert("topic".into(), json!("single"));
+        p.insert("auto_offset_reset".into(), json!("earliest"));
+        p.insert("security_protocol".into(), json!("SASL_SSL"));
+        p.insert("sasl_mechanism".into(), json!("SCRAM-SHA-512"));
+        p.insert("sasl_username".into(), json!("svc"));
+        p.insert("sasl_password".into(), json!("secret"));
+        p.insert("config".into(), json!(["fetch.min.bytes=1024"]));
+        let spec = KafkaSourceSpec::from_params(&p).expect("spec");
+        assert_eq!(spec.topics, vec!["single"]);
+        let conf = spec.client_config();
+        assert!(conf.contains(&("security.protocol".into(), "sasl_ssl".into())));
+        assert!(conf.contains(&("auto.offset.reset".into(), "earliest".into())));
+        assert_eq!(
+            conf.last(),
+            Some(&("fetch.min.bytes".to_string(), "1024".to_string()))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_params() {
+        let mut p = base_params();
+        p.insert("group_id".into(), json!(""));
+        assert!(KafkaSourceSpec::from_params(&p).is_err());
+
+        let mut p = base_params();
+        p.insert("topic".into(), json!(["^(unclosed"]));
+        assert!(KafkaSourceSpec::from_params(&p).is_err());
+
+        let mut p = base_params();
+        p.insert("security_protocol".into(), json!("sasl_plaintext"));
+        assert!(KafkaSourceSpec::from_params(&p).is_err());
+
+        let mut p = base_params();
+        p.insert("max_poll_records".into(), json!(0));
+        assert!(KafkaSourceSpec::from_params(&p).is_err());
+    }
+}
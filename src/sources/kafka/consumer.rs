@@ -0,0 +1,175 @@
+//! Kafka 消费端抽象：source 只依赖 `KafkaConsumer`，便于以进程内实现替换真实客户端做测试。
+
+use bytes::Bytes;
+
+use super::offsets::{CommitOffset, TopicPartition};
+
+#[derive(Debug, Clone)]
+pub struct KafkaRecord {
+    pub tp: TopicPartition,
+    pub offset: i64,
+    pub payload: Bytes,
+}
+
+#[derive(Debug)]
+pub enum PollEvent {
+    Records(Vec<KafkaRecord>),
+    /// 分区分配（再均衡完成）
+    Assigned(Vec<TopicPartition>),
+    /// 分区即将被回收：source 需同步提交这些分区已确认的位点
+    Revoked(Vec<TopicPartition>),
+    /// 本次等待内无数据
+    Idle,
+}
+
+#[async_trait::async_trait]
+pub trait KafkaConsumer: Send {
+    /// 拉取至多 `max` 条记录；无数据时返回 `Idle`，不得无限阻塞
+    async fn poll(&mut self, max: usize) -> anyhow::Result<PollEvent>;
+
+    /// 提交位点；`sync` 为 true 时等待 broker 确认（回收分区/关闭时使用）
+    fn commit(&mut self, offsets: &[CommitOffset], sync: bool) -> anyhow::Result<()>;
+
+    fn close(&mut self) {}
+}
+
+#[cfg(feature = "kafka")]
+pub use rd::RdKafkaConsumer;
+
+#[cfg(feature = "kafka")]
+mod rd {
+    use std::time::Duration;
+
+    use anyhow::Context;
+    use futures_util::FutureExt;
+    use rdkafka::consumer::{
+        BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer,
+    };
+    use rdkafka::{ClientConfig, ClientContext, Message, Offset, TopicPartitionList};
+
+    use super::super::config::KafkaSourceSpec;
+    use super::super::source::SharedTracker;
+    use super::*;
+
+    const POLL_WAIT: Duration = Duration::from_millis(200);
+
+    /// 再均衡回调：librdkafka 要求回收前的提交在回调内同步完成，
+    /// 因此这里直接提交，`poll` 不会产出 `Revoked/Assigned` 事件。
+    pub struct RebalanceCtx {
+        tracker: SharedTracker,
+    }
+
+    impl ClientContext for RebalanceCtx {}
+
+    impl ConsumerContext for RebalanceCtx {
+        fn pre_rebalance(&self, consumer: &BaseConsumer<Self>, rebalance: &Rebalance<'_>) {
+            if let Rebalance::Revoke(tpl) = rebalance {
+                let tps = tps_of(tpl);
+                let mut tracker = self.tracker.lock().expect("kafka offset tracker poisoned");
+                let offsets = tracker.committable_for(&tps);
+                if !offsets.is_empty() {
+                    match consumer.commit(&tpl_of(&offsets), CommitMode::Sync) {
+                        Ok(()) => tracker.mark_committed(&offsets),
+                        Err(e) => warn_data!("kafka commit before revoke failed: {}", e),
+                    }
+                }
+                tracker.revoke(&tps);
+            }
+        }
+
+        fn post_rebalance(&self, _consumer: &BaseConsumer<Self>, rebalance: &Rebalance<'_>) {
+            if let Rebalance::Assign(tpl) = rebalance {
+                let tps = tps_of(tpl);
+                info_data!("kafka partitions assigned: {:?}", tps);
+                self.tracker
+                    .lock()
+                    .expect("kafka offset tracker poisoned")
+                    .assign(&tps);
+            }
+        }
+    }
+
+    pub struct RdKafkaConsumer {
+        inner: StreamConsumer<RebalanceCtx>,
+    }
+
+    impl RdKafkaConsumer {
+        pub fn new(spec: &KafkaSourceSpec, tracker: SharedTracker) -> anyhow::Result<Self> {
+            let mut cfg = ClientConfig::new();
+            for (k, v) in spec.client_config() {
+                cfg.set(k, v);
+            }
+            let inner: StreamConsumer<RebalanceCtx> = cfg
+                .create_with_context(RebalanceCtx { tracker })
+                .context("create kafka consumer")?;
+            let topics: Vec<&str> = spec.topics.iter().map(String::as_str).collect();
+            inner
+                .subscribe(&topics)
+                .with_context(|| format!("subscribe kafka topics {:?}", topics))?;
+            Ok(Self { inner })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl KafkaConsumer for RdKafkaConsumer {
+        async fn poll(&mut self, max: usize) -> anyhow::Result<PollEvent> {
+            let first = match tokio::time::timeout(POLL_WAIT, self.inner.recv()).await {
+                Err(_) => return Ok(PollEvent::Idle),
+                Ok(Err(e)) => {
+                    // 客户端内部会重连/重试，这里只记录
+                    warn_data!("kafka consume error: {}", e);
+                    return Ok(PollEvent::Idle);
+                }
+                Ok(Ok(msg)) => record_of(&msg),
+            };
+            let mut out = Vec::with_capacity(max.min(64));
+            out.push(first);
+            while out.len() < max {
+                match self.inner.recv().now_or_never() {
+                    Some(Ok(msg)) => out.push(record_of(&msg)),
+                    _ => break,
+                }
+            }
+            Ok(PollEvent::Records(out))
+        }
+
+        fn commit(&mut self, offsets: &[CommitOffset], sync: bool) -> anyhow::Result<()> {
+            let mode = if sync {
+                CommitMode::Sync
+            } else {
+                CommitMode::Async
+            };
+            self.inner
+                .commit(&tpl_of(offsets), mode)
+                .context("commit kafka offsets")
+        }
+
+        fn close(&mut self) {
+            self.inner.unsubscribe();
+        }
+    }
+
+    fn record_of<M: Message>(msg: &M) -> KafkaRecord {
+        KafkaRecord {
+            tp: TopicPartition::new(msg.topic(), msg.partition()),
+            offset: msg.offset(),
+            payload: Bytes::copy_from_slice(msg.payload().unwrap_or_default()),
+        }
+    }
+
+    fn tps_of(tpl: &TopicPartitionList) -> Vec<TopicPartition> {
+        tpl.elements()
+            .iter()
+            .map(|e| TopicPartition::new(e.topic(), e.partition()))
+            .collect()
+    }
+
+    fn tpl_of(offsets: &[CommitOffset]) -> TopicPartitionList {
+        let mut tpl = TopicPartitionList::new();
+        for c in offsets {
+            // 偏移均来自已消费消息，此处不会越界
+            let _ = tpl.add_partition_offset(&c.tp.topic, c.tp.partition, Offset::Offset(c.offset));
+        }
+        tpl
+    }
+}
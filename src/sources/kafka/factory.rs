@@ -0,0 +1,164 @@
+use orion_conf::UvsConfFrom;
+use orion_error::ToStructError;
+use serde_json::json;
+use wp_conf::connectors::{ConnectorDef, ConnectorScope, ParamMap};
+use wp_connector_api::{
+    SourceBuildCtx, SourceDefProvider, SourceFactory, SourceReason, SourceResult,
+    SourceSpec as ResolvedSourceSpec, SourceSvcIns, Tags,
+};
+
+use super::config::KafkaSourceSpec;
+
+pub struct KafkaSourceFactory;
+
+#[async_trait::async_trait]
+impl SourceFactory for KafkaSourceFactory {
+    fn kind(&self) -> &'static str {
+        "kafka"
+    }
+
+    fn validate_spec(&self, spec: &ResolvedSourceSpec) -> SourceResult<()> {
+        let res: anyhow::Result<()> = (|| {
+            if let Err(e) = Tags::validate(&spec.tags) {
+                anyhow::bail!("Invalid tags: {}", e);
+            }
+            KafkaSourceSpec::from_params(&spec.params)?;
+            Ok(())
+        })();
+        res.map_err(|e| SourceReason::from_conf(e.to_string()).to_err())
+    }
+
+    #[cfg(feature = "kafka")]
+    async fn build(
+        &self,
+        spec: &ResolvedSourceSpec,
+        _ctx: &SourceBuildCtx,
+    ) -> SourceResult<SourceSvcIns> {
+        use super::consumer::RdKafkaConsumer;
+        use super::source::{KafkaSource, SharedTracker};
+        use wp_connector_api::{SourceHandle, SourceMeta};
+
+        let fut = async {
+            let conf = KafkaSourceSpec::from_params(&spec.params)?;
+            let mut tags = Tags::from_parse(&spec.tags);
+            tags.set("access_source", "kafka".to_string());
+            let tracker = SharedTracker::default();
+            let consumer = RdKafkaConsumer::new(&conf, tracker.clone())?;
+            info_ctrl!(
+                "kafka source '{}' subscribed {:?} (group={}, max_poll_records={})",
+                spec.name,
+                conf.topics,
+                conf.group_id,
+                conf.max_poll_records
+            );
+            let source = KafkaSource::new(
+                spec.name.clone(),
+                tags.clone(),
+                Box::new(consumer),
+                tracker,
+                conf.max_poll_records,
+                std::time::Duration::from_millis(conf.commit_interval_ms),
+            );
+            let mut meta = SourceMeta::new(spec.name.clone(), spec.kind.clone());
+            for (k, v) in tags.iter() {
+                meta.tags.set(k, v);
+            }
+            Ok(SourceSvcIns::new().with_sources(vec![SourceHandle::new(Box::new(source), meta)]))
+        };
+        fut.await
+            .map_err(|e: anyhow::Error| SourceReason::from_conf(e.to_string()).to_err())
+    }
+
+    #[cfg(not(feature = "kafka"))]
+    async fn build(
+        &self,
+        spec: &ResolvedSourceSpec,
+        _ctx: &SourceBuildCtx,
+    ) -> SourceResult<SourceSvcIns> {
+        Err(SourceReason::from_conf(format!(
+            "source '{}' of kind 'kafka' requires feature 'kafka'",
+            spec.name
+        ))
+        .to_err())
+    }
+}
+
+impl SourceDefProvider for KafkaSourceFactory {
+    fn source_def(&self) -> ConnectorDef {
+        let mut params = ParamMap::new();
+        params.insert("brokers".into(), json!("localhost:9092"));
+        params.insert("topic".into(), json!(["access_log"]));
+        params.insert("group_id".into(), json!("wparse_default_group"));
+        params.insert("auto_offset_reset".into(), json!("latest"));
+        params.insert("max_poll_records".into(), json!(500));
+        ConnectorDef {
+            id: "kafka_src".into(),
+            kind: self.kind().into(),
+            scope: ConnectorScope::Source,
+            allow_override: vec![
+                "brokers".into(),
+                "topic".into(),
+                "group_id".into(),
+                "auto_offset_reset".into(),
+                "max_poll_records".into(),
+                "commit_interval_ms".into(),
+                "security_protocol".into(),
+                "sasl_mechanism".into(),
+                "sasl_username".into(),
+                "sasl_password".into(),
+                "ssl_ca_location".into(),
+                "ssl_certificate_location".into(),
+                "ssl_key_location".into(),
+                "config".into(),
+            ],
+            default_params: params,
+            origin: Some("builtin:kafka_source".into()),
+        }
+    }
+}
+
+/// 注册 Kafka 源工厂；未启用 `kafka` 特性时仍可参与配置校验，构建时报错
+pub fn register_kafka_factory() {
+    crate::connectors::registry::register_source_factory(KafkaSourceFactory);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_with(params: toml::map::Map<String, toml::Value>) -> ResolvedSourceSpec {
+        ResolvedSourceSpec {
+            name: "kafka_in".into(),
+            kind: "kafka".into(),
+            connector_id: "kafka_src".into(),
+            params: wp_connector_api::parammap_from_toml_map(params),
+            tags: vec!["env:test".into()],
+        }
+    }
+
+    #[test]
+    fn validate_spec_checks_params() {
+        let fac = KafkaSourceFactory;
+        let mut t = toml::map::Map::new();
+        t.insert(
+            "brokers".into(),
+            toml::Value::String("127.0.0.1:9092".into()),
+        );
+        t.insert("topic".into(), toml::Value::String("logs".into()));
+        t.insert("group_id".into(), toml::Value::String("g1".into()));
+        fac.validate_spec(&spec_with(t.clone())).expect("valid");
+
+        t.insert(
+            "auto_offset_reset".into(),
+            toml::Value::String("sometime".into()),
+        );
+        assert!(fac.validate_spec(&spec_with(t)).is_err());
+    }
+
+    #[test]
+    fn default_def_passes_own_validation() {
+        let def = KafkaSourceFactory.source_def();
+        assert_eq!(def.kind, "kafka");
+        assert!(KafkaSourceSpec::from_params(&def.default_params).is_ok());
+    }
+}
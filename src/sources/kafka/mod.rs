@@ -0,0 +1,36 @@
+//! Kafka 源：消费组订阅 + 交付后提交位点（at-least-once）
+//!
+//! 模块结构：
+//! - config.rs：参数解析（brokers/topic/group_id/auto_offset_reset/max_poll_records/SASL/TLS）
+//! - consumer.rs：`KafkaConsumer` 抽象与 librdkafka 实现（`kafka` 特性）
+//! - offsets.rs：分区位点跟踪，只提交连续已确认的位点
+//! - source.rs：KafkaSource 实现
+//! - factory.rs：KafkaSourceFactory
+//!
+//! ```toml
+//! [[sources]]
+//! key = "kafka_in"
+//! connect = "kafka_src"
+//! params_override = {
+//!     brokers = "k1:9092,k2:9092",
+//!     topic = ["access_log", "^audit-.*"],   # ^ 开头按正则订阅
+//!     group_id = "wparse",
+//!     auto_offset_reset = "earliest",
+//!     security_protocol = "sasl_ssl",
+//!     sasl_mechanism = "SCRAM-SHA-512",
+//!     sasl_username = "${KAFKA_USER}",
+//!     sasl_password = "${KAFKA_PASSWORD}"
+//! }
+//! ```
+
+pub mod config;
+pub mod consumer;
+pub mod factory;
+pub mod offsets;
+pub mod source;
+
+pub use config::{AutoOffsetReset, KafkaSourceSpec};
+pub use consumer::{KafkaConsumer, KafkaRecord, PollEvent};
+pub use factory::{KafkaSourceFactory, register_kafka_factory};
+pub use offsets::{CommitOffset, OffsetTracker, TopicPartition};
+pub use source::KafkaSource;
@@ -0,0 +1,181 @@
+//! 分区位点跟踪：记录已交付但尚未被解析线程接收的消息，只提交连续已确认的位点。
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TopicPartition {
+    pub topic: String,
+    pub partition: i32,
+}
+
+impl TopicPartition {
+    pub fn new(topic: impl Into<String>, partition: i32) -> Self {
+        Self {
+            topic: topic.into(),
+            partition,
+        }
+    }
+}
+
+/// 待提交的位点（Kafka 语义：下一条待消费消息的 offset）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitOffset {
+    pub tp: TopicPartition,
+    pub offset: i64,
+}
+
+#[derive(Debug, Default)]
+struct PartitionOffsets {
+    in_flight: BTreeSet<i64>,
+    /// 已交付的最大 offset + 1
+    delivered: i64,
+    committed: i64,
+}
+
+impl PartitionOffsets {
+    fn watermark(&self) -> i64 {
+        self.in_flight.first().copied().unwrap_or(self.delivered)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OffsetTracker {
+    parts: HashMap<TopicPartition, PartitionOffsets>,
+    /// 已回收的分区：其缓冲消息照常交付，但不再跟踪/提交（由新的持有者重新消费）
+    revoked: HashSet<TopicPartition>,
+}
+
+impl OffsetTracker {
+    /// 消息交付给采集线程时登记；分区已回收时返回 false
+    pub fn track(&mut self, tp: &TopicPartition, offset: i64) -> bool {
+        if self.revoked.contains(tp) {
+            return false;
+        }
+        let part = self
+            .parts
+            .entry(tp.clone())
+            .or_insert_with(|| PartitionOffsets {
+                committed: offset,
+                delivered: offset,
+                ..Default::default()
+            });
+        part.in_flight.insert(offset);
+        part.delivered = part.delivered.max(offset + 1);
+        true
+    }
+
+    /// 解析线程接收到消息后确认；分区已被回收时忽略
+    pub fn ack(&mut self, tp: &TopicPartition, offset: i64) {
+        if let Some(part) = self.parts.get_mut(tp) {
+            part.in_flight.remove(&offset);
+        }
+    }
+
+    /// 所有分区中可前移的提交位点
+    pub fn committable(&self) -> Vec<CommitOffset> {
+        self.collect(|_| true)
+    }
+
+    /// 指定分区中可前移的提交位点（再均衡回收分区前使用）
+    pub fn committable_for(&self, tps: &[TopicPartition]) -> Vec<CommitOffset> {
+        self.collect(|tp| tps.contains(tp))
+    }
+
+    pub fn mark_committed(&mut self, offsets: &[CommitOffset]) {
+        for c in offsets {
+            if let Some(part) = self.parts.get_mut(&c.tp) {
+                part.committed = part.committed.max(c.offset);
+            }
+        }
+    }
+
+    /// 分区被回收：丢弃跟踪状态，之后对这些分区的确认不再产生提交
+    pub fn revoke(&mut self, tps: &[TopicPartition]) {
+        for tp in tps {
+            self.parts.remove(tp);
+            self.revoked.insert(tp.clone());
+        }
+    }
+
+    /// 分区（重新）分配给本实例
+    pub fn assign(&mut self, tps: &[TopicPartition]) {
+        for tp in tps {
+            self.revoked.remove(tp);
+        }
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.parts.values().map(|p| p.in_flight.len()).sum()
+    }
+
+    fn collect<F: Fn(&TopicPartition) -> bool>(&self, keep: F) -> Vec<CommitOffset> {
+        let mut out: Vec<CommitOffset> = self
+            .parts
+            .iter()
+            .filter(|(tp, _)| keep(tp))
+            .filter_map(|(tp, part)| {
+                let mark = part.watermark();
+                (mark > part.committed).then(|| CommitOffset {
+                    tp: tp.clone(),
+                    offset: mark,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| a.tp.cmp(&b.tp));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commits_only_contiguous_acked_offsets() {
+        let tp = TopicPartition::new("t", 0);
+        let mut tracker = OffsetTracker::default();
+        for off in 10..14 {
+            tracker.track(&tp, off);
+        }
+        assert!(tracker.committable().is_empty());
+
+        tracker.ack(&tp, 11);
+        assert!(tracker.committable().is_empty(), "10 still in flight");
+        tracker.ack(&tp, 10);
+        let c = tracker.committable();
+        assert_eq!(
+            c,
+            vec![CommitOffset {
+                tp: tp.clone(),
+                offset: 12
+            }]
+        );
+        tracker.mark_committed(&c);
+        assert!(tracker.committable().is_empty());
+
+        tracker.ack(&tp, 12);
+        tracker.ack(&tp, 13);
+        assert_eq!(tracker.committable()[0].offset, 14);
+        assert_eq!(tracker.in_flight(), 0);
+    }
+
+    #[test]
+    fn revoke_drops_partition_state() {
+        let a = TopicPartition::new("t", 0);
+        let b = TopicPartition::new("t", 1);
+        let mut tracker = OffsetTracker::default();
+        tracker.track(&a, 0);
+        tracker.track(&b, 5);
+        tracker.ack(&a, 0);
+        tracker.ack(&b, 5);
+        assert_eq!(tracker.committable_for(std::slice::from_ref(&a)).len(), 1);
+        tracker.revoke(std::slice::from_ref(&a));
+        tracker.ack(&a, 0);
+        assert!(!tracker.track(&a, 1), "revoked partition is not tracked");
+        let c = tracker.committable();
+        assert_eq!(c, vec![CommitOffset { tp: b, offset: 6 }]);
+
+        tracker.assign(std::slice::from_ref(&a));
+        assert!(tracker.track(&a, 7));
+    }
+}
@@ -0,0 +1,304 @@
+//! Kafka source：按批交付消息，解析线程接收后才确认位点（at-least-once）。
+//!
+//! 每条事件挂载一个预处理钩子，解析线程取到事件时调用以确认其 offset；source 在
+//! 后续 `receive`/`close` 中只提交各分区连续已确认的位点。分区被回收前先同步提交，
+//! 已拉取到本地缓冲的消息不会丢弃，仍照常交付。
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use wp_connector_api::{DataSource, SourceBatch, SourceEvent, SourceReason, SourceResult, Tags};
+use wp_parse_api::RawData;
+
+use super::consumer::{KafkaConsumer, KafkaRecord, PollEvent};
+use super::offsets::{OffsetTracker, TopicPartition};
+use crate::sources::event_id::next_event_id;
+
+pub type SharedTracker = Arc<Mutex<OffsetTracker>>;
+
+pub struct KafkaSource {
+    key: String,
+    tags: Arc<Tags>,
+    consumer: Box<dyn KafkaConsumer>,
+    tracker: SharedTracker,
+    buffer: VecDeque<KafkaRecord>,
+    max_poll_records: usize,
+    commit_interval: Duration,
+    last_commit: Instant,
+}
+
+impl KafkaSource {
+    pub fn new(
+        key: String,
+        tags: Tags,
+        consumer: Box<dyn KafkaConsumer>,
+        tracker: SharedTracker,
+        max_poll_records: usize,
+        commit_interval: Duration,
+    ) -> Self {
+        Self {
+            key,
+            tags: Arc::new(tags),
+            consumer,
+            tracker,
+            buffer: VecDeque::new(),
+            max_poll_records: max_poll_records.max(1),
+            commit_interval,
+            last_commit: Instant::now(),
+        }
+    }
+
+    fn commit_acked(&mut self, sync: bool) {
+        self.last_commit = Instant::now();
+        let offsets = self.lock_tracker().committable();
+        if offsets.is_empty() {
+            return;
+        }
+        match self.consumer.commit(&offsets, sync) {
+            Ok(()) => self.lock_tracker().mark_committed(&offsets),
+            Err(e) => warn_data!("kafka source '{}' commit failed: {}", self.key, e),
+        }
+    }
+
+    fn on_revoked(&mut self, tps: Vec<TopicPartition>) {
+        let offsets = self.lock_tracker().committable_for(&tps);
+        if !offsets.is_empty() {
+            match self.consumer.commit(&offsets, true) {
+                Ok(()) => self.lock_tracker().mark_committed(&offsets),
+                Err(e) => warn_data!(
+                    "kafka source '{}' commit before revoke failed: {}",
+                    self.key,
+                    e
+                ),
+            }
+        }
+        self.lock_tracker().revoke(&tps);
+        info_data!(
+            "kafka source '{}' partitions revoked: {:?} (buffered={})",
+            self.key,
+            tps,
+            self.buffer.len()
+        );
+    }
+
+    fn take_batch(&mut self) -> SourceBatch {
+        let n = self.buffer.len().min(self.max_poll_records);
+        let mut batch = Vec::with_capacity(n);
+        let mut tracker = self.tracker.lock().expect("kafka offset tracker poisoned");
+        for rec in self.buffer.drain(..n) {
+            let mut event = SourceEvent::new(
+                next_event_id(),
+                &self.key,
+                RawData::Bytes(rec.payload),
+                self.tags.clone(),
+            );
+            if tracker.track(&rec.tp, rec.offset) {
+                let tracker = self.tracker.clone();
+                let (tp, offset) = (rec.tp, rec.offset);
+                event.preproc = Some(Arc::new(move |_: &mut SourceEvent| {
+                    if let Ok(mut t) = tracker.lock() {
+                        t.ack(&tp, offset);
+                    }
+                }));
+            }
+            batch.push(event);
+        }
+        batch
+    }
+
+    fn lock_tracker(&self) -> std::sync::MutexGuard<'_, OffsetTracker> {
+        self.tracker.lock().expect("kafka offset tracker poisoned")
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for KafkaSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        loop {
+            if self.last_commit.elapsed() >= self.commit_interval {
+                self.commit_acked(false);
+            }
+            if !self.buffer.is_empty() {
+                return Ok(self.take_batch());
+            }
+            // 拉取结果先写入缓冲再交付，receive 被超时取消时不会丢失消息
+            let event = self
+                .consumer
+                .poll(self.max_poll_records)
+                .await
+                .map_err(|e| SourceReason::SupplierError(e.to_string()))?;
+            match event {
+                PollEvent::Records(records) => self.buffer.extend(records),
+                PollEvent::Revoked(tps) => self.on_revoked(tps),
+                PollEvent::Assigned(tps) => {
+                    info_data!("kafka source '{}' partitions assigned: {:?}", self.key, tps);
+                    self.lock_tracker().assign(&tps);
+                }
+                PollEvent::Idle => {}
+            }
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(self.take_batch())
+        }
+    }
+
+    fn can_try_receive(&mut self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    fn identifier(&self) -> String {
+        self.key.clone()
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.commit_acked(true);
+        let in_flight = self.lock_tracker().in_flight();
+        if in_flight > 0 {
+            warn_data!(
+                "kafka source '{}' closed with {} unacknowledged records; they will be redelivered",
+                self.key,
+                in_flight
+            );
+        }
+        self.consumer.close();
+        info_data!("kafka source '{}' closed", self.key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::kafka::offsets::CommitOffset;
+    use bytes::Bytes;
+
+    /// 进程内消费端：按脚本返回事件，记录提交结果
+    struct ScriptConsumer {
+        script: VecDeque<PollEvent>,
+        commits: Arc<Mutex<Vec<(CommitOffset, bool)>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl KafkaConsumer for ScriptConsumer {
+        async fn poll(&mut self, _max: usize) -> anyhow::Result<PollEvent> {
+            match self.script.pop_front() {
+                Some(ev) => Ok(ev),
+                None => anyhow::bail!("script exhausted"),
+            }
+        }
+
+        fn commit(&mut self, offsets: &[CommitOffset], sync: bool) -> anyhow::Result<()> {
+            let mut log = self.commits.lock().unwrap();
+            log.extend(offsets.iter().cloned().map(|c| (c, sync)));
+            Ok(())
+        }
+    }
+
+    fn records(topic: &str, partition: i32, offsets: std::ops::Range<i64>) -> PollEvent {
+        PollEvent::Records(
+            offsets
+                .map(|offset| KafkaRecord {
+                    tp: TopicPartition::new(topic, partition),
+                    offset,
+                    payload: Bytes::from(format!("{}-{}-{}", topic, partition, offset)),
+                })
+                .collect(),
+        )
+    }
+
+    fn source_with(
+        script: Vec<PollEvent>,
+        max_poll: usize,
+    ) -> (KafkaSource, Arc<Mutex<Vec<(CommitOffset, bool)>>>) {
+        let commits = Arc::new(Mutex::new(Vec::new()));
+        let consumer = ScriptConsumer {
+            script: script.into(),
+            commits: commits.clone(),
+        };
+        let src = KafkaSource::new(
+            "kafka_in".into(),
+            Tags::new(),
+            Box::new(consumer),
+            SharedTracker::default(),
+            max_poll,
+            // 测试中只观察显式/回收/关闭时的提交
+            Duration::from_secs(3600),
+        );
+        (src, commits)
+    }
+
+    fn hand_over(batch: &mut SourceBatch) {
+        for ev in batch.iter_mut() {
+            if let Some(hook) = ev.preproc.clone() {
+                (hook)(ev);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn commits_only_after_hand_over() {
+        let (mut src, commits) = source_with(vec![records("t", 0, 0..3), PollEvent::Idle], 10);
+        let mut batch = src.receive().await.expect("batch");
+        assert_eq!(batch.len(), 3);
+        assert!(src.try_receive().is_none());
+
+        // 未交付给解析线程前不提交
+        src.commit_acked(false);
+        assert!(commits.lock().unwrap().is_empty());
+
+        hand_over(&mut batch);
+        src.close().await.expect("close");
+        let log = commits.lock().unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].0.offset, 3);
+        assert!(log[0].1, "close commits synchronously");
+    }
+
+    #[tokio::test]
+    async fn revoke_commits_acked_and_keeps_buffered_records() {
+        let tp0 = TopicPartition::new("t", 0);
+        let (mut src, commits) = source_with(
+            vec![
+                records("t", 0, 0..4),
+                PollEvent::Revoked(vec![tp0.clone()]),
+                records("t", 1, 0..1),
+            ],
+            2,
+        );
+        // 最多交付 2 条，其余 2 条留在缓冲
+        let mut first = src.receive().await.expect("first");
+        assert_eq!(first.len(), 2);
+        hand_over(&mut first);
+
+        // 缓冲未清空前不会拉取，因此先交付剩余消息
+        let mut second = src.receive().await.expect("second");
+        assert_eq!(second.len(), 2);
+
+        // 缓冲清空后拉到回收事件：只提交已确认部分，随后继续消费其它分区
+        let third = src.receive().await.expect("third");
+        assert_eq!(third.len(), 1);
+        {
+            let log = commits.lock().unwrap();
+            assert_eq!(log.len(), 1);
+            assert_eq!(log[0].0, CommitOffset { tp: tp0, offset: 2 });
+            assert!(log[0].1, "revoke commits synchronously");
+        }
+
+        // 回收后再确认旧分区的消息不会产生提交
+        hand_over(&mut second);
+        src.commit_acked(false);
+        assert_eq!(commits.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn consumer_error_surfaces_as_source_error() {
+        let (mut src, _) = source_with(vec![], 10);
+        assert!(src.receive().await.is_err());
+    }
+}
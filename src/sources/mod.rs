@@ -1,11 +1,16 @@
+pub mod channel;
 pub mod config;
 pub mod event_id;
 pub mod file;
+pub mod http;
+pub mod kafka;
 pub mod net;
+pub mod preproc;
+pub mod rate_limit;
 pub mod syslog;
 pub mod tcp;
 
 // Common re-exports for convenience
-pub use config::SourceConfigParser;
+pub use config::{SourceConfigParser, SourceInstanceIds};
 pub use file::register_factory_only as register_file_factory;
 //pub use syslog::regi
@@ -234,6 +234,12 @@ impl ConnectionManager {
                     batch_processor.add_message(zcp_msg, zcp_sender, key, client_ip);
                 }
             }
+            FramingMode::Len4 => {
+                while let Some(data) = FramingExtractor::extract_len4_message(buf) {
+                    let zcp_msg = ZcpMessage::from_ip_addr(client_ip, data.to_vec());
+                    batch_processor.add_message(zcp_msg, zcp_sender, key, client_ip);
+                }
+            }
             FramingMode::Auto => {
                 let mut extracted_len = false;
                 while let Some(data) = FramingExtractor::extract_length_prefixed_message(buf) {
@@ -347,6 +353,13 @@ impl ConnectionManager {
                     let _ = event_tx.try_send(event);
                 }
             }
+            FramingMode::Len4 => {
+                while let Some(data) = FramingExtractor::extract_len4_message(buf) {
+                    let event =
+                        Self::build_direct_source_event(key, client_ip, &data, base_stags.clone());
+                    let _ = event_tx.try_send(event);
+                }
+            }
             FramingMode::Auto => {
                 while let Some(data) = FramingExtractor::extract_length_prefixed_message(buf) {
                     let event =
@@ -0,0 +1,281 @@
+//! 预处理钩子注册表：按名称登记逐事件的无状态钩子（结构同 WPL 的 plg_pipe 注册表）。
+//!
+//! - source 实例通过 `preproc = ["strip/bom", "syslog/header", "charset/gbk"]` 按序组合钩子；
+//! - 组合后的钩子挂在事件的 `preproc` 上，排在源自带钩子（如 syslog `header_mode`）之后，
+//!   与其一样在解析线程中、WPL 规则匹配之前执行；
+//! - 名称不区分大小写，未注册的名称在装载与 `wproj check` 时报错。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use anyhow::bail;
+use encoding_rs::GB18030;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use wp_connector_api::{
+    CtrlRx, DataSource, EventPreHook, SourceBatch, SourceEvent, SourceHandle, SourceResult,
+};
+use wp_parse_api::{PipeProcessor, RawData};
+use wpl::eval::builtins::bom::BomClearProc;
+
+use crate::sources::syslog::udp_source::build_preproc_hook;
+
+pub type PreprocHookBuilder = fn() -> EventPreHook;
+
+#[derive(Default)]
+struct PreprocHookRegistry {
+    builders: HashMap<SmolStr, PreprocHookBuilder>,
+}
+
+impl PreprocHookRegistry {
+    fn with_builtins() -> Self {
+        let mut reg = Self::default();
+        reg.register("strip/bom", strip_bom_hook);
+        reg.register("syslog/header", syslog_header_hook);
+        reg.register("syslog/tag", syslog_tag_hook);
+        reg.register("charset/gbk", charset_gbk_hook);
+        reg
+    }
+
+    fn register(&mut self, name: &str, builder: PreprocHookBuilder) {
+        self.builders
+            .insert(SmolStr::from(name.to_ascii_lowercase()), builder);
+    }
+
+    fn create(&self, name: &str) -> Option<EventPreHook> {
+        self.builders
+            .get(&SmolStr::from(name.trim().to_ascii_lowercase()))
+            .map(|builder| (builder)())
+    }
+
+    fn list(&self) -> Vec<SmolStr> {
+        let mut names: Vec<SmolStr> = self.builders.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+static PREPROC_HOOK_REGISTRY: Lazy<Mutex<PreprocHookRegistry>> =
+    Lazy::new(|| Mutex::new(PreprocHookRegistry::with_builtins()));
+
+fn registry() -> MutexGuard<'static, PreprocHookRegistry> {
+    PREPROC_HOOK_REGISTRY
+        .lock()
+        .expect("preproc hook registry poisoned")
+}
+
+pub fn register_preproc_hook(name: &str, builder: PreprocHookBuilder) {
+    registry().register(name, builder);
+}
+
+pub fn create_preproc_hook(name: &str) -> Option<EventPreHook> {
+    registry().create(name)
+}
+
+pub fn list_preproc_hooks() -> Vec<SmolStr> {
+    registry().list()
+}
+
+/// 按序组合钩子；空列表返回 None
+pub fn build_hook_chain(names: &[String]) -> anyhow::Result<Option<EventPreHook>> {
+    let reg = registry();
+    let mut hooks = Vec::with_capacity(names.len());
+    for name in names {
+        match reg.create(name) {
+            Some(hook) => hooks.push(hook),
+            None => bail!(
+                "unknown preproc hook '{}' (available: {})",
+                name,
+                reg.list().join(", ")
+            ),
+        }
+    }
+    Ok(match hooks.len() {
+        0 => None,
+        1 => hooks.pop(),
+        _ => Some(Arc::new(move |f: &mut SourceEvent| {
+            for hook in &hooks {
+                (hook)(f);
+            }
+        })),
+    })
+}
+
+pub fn validate_hook_names(names: &[String]) -> anyhow::Result<()> {
+    build_hook_chain(names).map(|_| ())
+}
+
+/// 先执行事件已带的钩子，再执行 `then`
+pub fn chain_after(first: Option<EventPreHook>, then: &EventPreHook) -> EventPreHook {
+    match first {
+        None => then.clone(),
+        Some(first) => {
+            let then = then.clone();
+            Arc::new(move |f: &mut SourceEvent| {
+                (first)(f);
+                (then)(f);
+            })
+        }
+    }
+}
+
+fn strip_bom_hook() -> EventPreHook {
+    Arc::new(|f: &mut SourceEvent| {
+        let raw = std::mem::replace(&mut f.payload, RawData::from_string(String::new()));
+        match BomClearProc.process(raw) {
+            Ok(out) => f.payload = out,
+            Err(e) => warn_data!("preproc strip/bom failed: {}", e),
+        }
+    })
+}
+
+fn syslog_header_hook() -> EventPreHook {
+    build_preproc_hook(true, false, false).expect("strip header hook")
+}
+
+fn syslog_tag_hook() -> EventPreHook {
+    build_preproc_hook(true, true, false).expect("strip header hook")
+}
+
+/// 非 UTF-8 的字节内容按 GBK（GB18030 超集）解码；已是合法 UTF-8 的内容保持不变
+fn charset_gbk_hook() -> EventPreHook {
+    Arc::new(|f: &mut SourceEvent| {
+        let bytes: &[u8] = match &f.payload {
+            RawData::String(_) => return,
+            RawData::Bytes(b) => b,
+            RawData::ArcBytes(b) => b,
+        };
+        if std::str::from_utf8(bytes).is_ok() {
+            return;
+        }
+        let (text, _, _) = GB18030.decode(bytes);
+        f.payload = RawData::String(text.into_owned());
+    })
+}
+
+/// 为源产出的事件挂上预处理钩子链
+pub struct PreprocSource {
+    inner: Box<dyn DataSource>,
+    chain: EventPreHook,
+}
+
+impl PreprocSource {
+    pub fn new(inner: Box<dyn DataSource>, chain: EventPreHook) -> Self {
+        Self { inner, chain }
+    }
+
+    /// 将钩子链应用到实例构建出的所有 source
+    pub fn wrap_all(handles: Vec<SourceHandle>, chain: &EventPreHook) -> Vec<SourceHandle> {
+        handles
+            .into_iter()
+            .map(|h| {
+                let source = PreprocSource::new(h.source, chain.clone());
+                SourceHandle::new(Box::new(source), h.meta)
+            })
+            .collect()
+    }
+
+    fn attach(&self, mut batch: SourceBatch) -> SourceBatch {
+        for event in batch.iter_mut() {
+            event.preproc = Some(chain_after(event.preproc.take(), &self.chain));
+        }
+        batch
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for PreprocSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        let batch = self.inner.receive().await?;
+        Ok(self.attach(batch))
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        let batch = self.inner.try_receive()?;
+        Some(self.attach(batch))
+    }
+
+    fn can_try_receive(&mut self) -> bool {
+        self.inner.can_try_receive()
+    }
+
+    fn identifier(&self) -> String {
+        self.inner.identifier()
+    }
+
+    async fn start(&mut self, ctrl_rx: CtrlRx) -> SourceResult<()> {
+        self.inner.start(ctrl_rx).await
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_connector_api::Tags;
+
+    fn event(payload: RawData) -> SourceEvent {
+        SourceEvent::new(1, "test", payload, Arc::new(Tags::new()))
+    }
+
+    fn run(hook: &EventPreHook, payload: RawData) -> RawData {
+        let mut ev = event(payload);
+        (hook)(&mut ev);
+        ev.payload
+    }
+
+    #[test]
+    fn chain_three_hooks_in_order() {
+        let (body, _, _) = GB18030.encode("登录失败 user=root");
+        let mut payload = b"\xEF\xBB\xBF<13>Oct 11 22:14:15 host sshd: ".to_vec();
+        payload.extend_from_slice(&body);
+
+        let names = ["strip/bom", "charset/gbk", "syslog/header"].map(String::from);
+        let chain = build_hook_chain(&names).unwrap().expect("chain");
+        let out = run(&chain, RawData::Bytes(payload.clone().into()));
+        assert_eq!(out.to_string(), "登录失败 user=root");
+
+        // 顺序有意义：syslog/header 只处理 UTF-8 文本，先于 charset/gbk 时不剥离头部
+        let names = ["strip/bom", "syslog/header", "charset/gbk"].map(String::from);
+        let chain = build_hook_chain(&names).unwrap().expect("chain");
+        let out = run(&chain, RawData::Bytes(payload.into()));
+        assert_eq!(
+            out.to_string(),
+            "<13>Oct 11 22:14:15 host sshd: 登录失败 user=root"
+        );
+    }
+
+    #[test]
+    fn chain_runs_after_source_hook() {
+        let mark = |tail: &'static str| -> EventPreHook {
+            Arc::new(move |f: &mut SourceEvent| {
+                f.payload = RawData::from_string(format!("{}{}", f.payload, tail));
+            })
+        };
+        let hook = chain_after(Some(mark("<src>")), &mark("<chain>"));
+        let out = run(&hook, RawData::from_string("body"));
+        assert_eq!(out.to_string(), "body<src><chain>");
+
+        let names = ["SYSLOG/HEADER".to_string()];
+        let chain = build_hook_chain(&names).unwrap().expect("chain");
+        let out = run(
+            &chain,
+            RawData::from_string("<13>Oct 11 22:14:15 host app: body"),
+        );
+        assert_eq!(out.to_string(), "body");
+    }
+
+    #[test]
+    fn unknown_hook_is_rejected() {
+        assert!(build_hook_chain(&[]).unwrap().is_none());
+        let err = validate_hook_names(&["strip/bom".into(), "charset/latin9".into()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("charset/latin9"));
+        assert!(err.contains("syslog/header"));
+        assert!(list_preproc_hooks().iter().any(|n| n == "charset/gbk"));
+    }
+}
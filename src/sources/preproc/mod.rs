@@ -0,0 +1,13 @@
+//! 源侧预处理：在事件交给 WPL 解析之前执行的步骤。
+//!
+//! - `multiline`：有状态的多行合并；
+//! - `hooks`：逐事件的无状态钩子（`EventPreHook`）注册表，按 source 配置组合。
+
+pub mod hooks;
+pub mod multiline;
+
+pub use hooks::{
+    PreprocSource, build_hook_chain, chain_after, create_preproc_hook, list_preproc_hooks,
+    register_preproc_hook, validate_hook_names,
+};
+pub use multiline::{ContinuationAnchor, MultilineJoiner, MultilineSource};
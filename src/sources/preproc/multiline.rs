@@ -0,0 +1,492 @@
+//! 多行合并：将跨多行的一条日志（行尾续行符、缩进续行等）在进入 WPL 解析前合并为一个事件。
+//!
+//! - 续行判定使用 WPL 分隔符模式（`{…}` 语法，见 [`wpl::build_pattern`]）：
+//!   - `tail`（默认）：当前行以模式结尾时，下一行并入当前记录（如行尾 `\`）；
+//!   - `head`：新到的行以模式开头时，并入上一条记录（如缩进续行 `{\h}`）。
+//! - 达到 `max_lines` 或超过 `timeout_ms` 未再续行时输出合并结果；行间以 `\n` 连接。
+//! - 按上游地址（`ups_ip`）分别缓冲，避免不同发送端的行被拼接在一起。
+//! - 事件自带的预处理钩子（如 syslog 头剥离）先于合并执行，合并结果不再携带钩子。
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, ensure};
+use wp_connector_api::{CtrlRx, DataSource, ParamMap, SourceBatch, SourceEvent, SourceResult};
+use wp_parse_api::RawData;
+use wpl::{SepPattern, build_pattern};
+
+pub const DEFAULT_MULTILINE_MAX_LINES: usize = 200;
+pub const DEFAULT_MULTILINE_TIMEOUT_MS: u64 = 1000;
+/// 行尾匹配只检查最后这么多字节，保证单行判定开销有界
+const TAIL_SCAN_BYTES: usize = 256;
+
+/// 连接器中用于配置多行合并的参数名
+pub const MULTILINE_PARAMS: [&str; 4] = [
+    "multiline_pattern",
+    "multiline_anchor",
+    "multiline_max_lines",
+    "multiline_timeout_ms",
+];
+
+/// 续行模式的锚定位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContinuationAnchor {
+    /// 当前行以模式结尾 => 下一行是续行
+    #[default]
+    Tail,
+    /// 新行以模式开头 => 该行是上一行的续行
+    Head,
+}
+
+struct Pending {
+    event: SourceEvent,
+    text: String,
+    lines: usize,
+    /// tail 模式下最后一行仍以续行符结尾
+    open: bool,
+    last: Instant,
+}
+
+pub struct MultilineJoiner {
+    pub continuation_pattern: SepPattern,
+    pub max_lines: usize,
+    pub timeout_ms: u64,
+    pub anchor: ContinuationAnchor,
+    pending: HashMap<Option<IpAddr>, Pending>,
+}
+
+impl MultilineJoiner {
+    pub fn new(continuation_pattern: SepPattern, max_lines: usize, timeout_ms: u64) -> Self {
+        Self {
+            continuation_pattern,
+            max_lines: max_lines.max(1),
+            timeout_ms,
+            anchor: ContinuationAnchor::Tail,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn with_anchor(mut self, anchor: ContinuationAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// 从连接器参数构建；未配置 `multiline_pattern` 时返回 None
+    pub fn from_params(params: &ParamMap) -> anyhow::Result<Option<Self>> {
+        let Some(raw) = params.get("multiline_pattern").and_then(|v| v.as_str()) else {
+            ensure!(
+                MULTILINE_PARAMS[1..]
+                    .iter()
+                    .all(|k| !params.contains_key(*k)),
+                "multiline_* params require multiline_pattern"
+            );
+            return Ok(None);
+        };
+        // 允许带或不带外层花括号：`{\h}` 与 `\h` 等价
+        let raw = raw
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .unwrap_or(raw);
+        let pattern = build_pattern(raw).map_err(|e| anyhow!("multiline_pattern: {}", e))?;
+        let anchor = match params
+            .get("multiline_anchor")
+            .and_then(|v| v.as_str())
+            .unwrap_or("tail")
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "tail" => ContinuationAnchor::Tail,
+            "head" => ContinuationAnchor::Head,
+            other => {
+                return Err(anyhow!(
+                    "Invalid multiline_anchor: {} (expect tail|head)",
+                    other
+                ));
+            }
+        };
+        let max_lines = params
+            .get("multiline_max_lines")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_MULTILINE_MAX_LINES as i64);
+        ensure!(max_lines > 0, "multiline_max_lines must be > 0");
+        let timeout_ms = params
+            .get("multiline_timeout_ms")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_MULTILINE_TIMEOUT_MS as i64);
+        ensure!(timeout_ms > 0, "multiline_timeout_ms must be > 0");
+        Ok(Some(
+            Self::new(pattern, max_lines as usize, timeout_ms as u64).with_anchor(anchor),
+        ))
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    fn head_match(&self, line: &str) -> bool {
+        self.continuation_pattern.match_at_start(line).is_some()
+    }
+
+    fn tail_match(&self, line: &str) -> bool {
+        let floor = line.len().saturating_sub(TAIL_SCAN_BYTES);
+        line.char_indices()
+            .rev()
+            .take_while(|(i, _)| *i >= floor)
+            .any(|(i, _)| {
+                self.continuation_pattern
+                    .match_at_start(&line[i..])
+                    .is_some_and(|m| i + m.matched == line.len())
+            })
+    }
+
+    /// 合并一个批次：返回已完成的记录，未完成的留在缓冲中
+    pub fn join_batch(&mut self, batch: SourceBatch) -> SourceBatch {
+        let now = Instant::now();
+        let mut out = Vec::with_capacity(batch.len());
+        for event in batch {
+            self.push(event, now, &mut out);
+        }
+        out
+    }
+
+    pub fn push(&mut self, mut event: SourceEvent, now: Instant, out: &mut SourceBatch) {
+        if let Some(hook) = event.preproc.take() {
+            (hook)(&mut event);
+        }
+        let Some(line) = payload_text(&event.payload) else {
+            // 非 UTF-8 内容无法判定续行，原样输出
+            out.push(event);
+            return;
+        };
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        let key = event.ups_ip;
+
+        if let Some(open) = self.pending.get(&key).map(|p| p.open) {
+            let continues = match self.anchor {
+                ContinuationAnchor::Tail => open,
+                ContinuationAnchor::Head => self.head_match(&line),
+            };
+            if continues {
+                let tail = self.anchor == ContinuationAnchor::Tail;
+                let still_open = tail && self.tail_match(&line);
+                let max_lines = self.max_lines;
+                let p = self.pending.get_mut(&key).expect("pending checked above");
+                p.text.push('\n');
+                p.text.push_str(&line);
+                p.lines += 1;
+                p.open = still_open;
+                p.last = now;
+                if p.lines >= max_lines || (tail && !still_open) {
+                    let p = self.pending.remove(&key).expect("pending checked above");
+                    out.push(p.finish());
+                }
+                return;
+            }
+            let p = self.pending.remove(&key).expect("pending checked above");
+            out.push(p.finish());
+        }
+
+        let buffered = match self.anchor {
+            ContinuationAnchor::Tail => self.tail_match(&line),
+            ContinuationAnchor::Head => true,
+        };
+        if !buffered || self.max_lines == 1 {
+            out.push(event);
+            return;
+        }
+        self.pending.insert(
+            key,
+            Pending {
+                event,
+                text: line,
+                lines: 1,
+                open: self.anchor == ContinuationAnchor::Tail,
+                last: now,
+            },
+        );
+    }
+
+    /// 输出超过 `timeout_ms` 未续行的记录
+    pub fn flush_expired(&mut self, now: Instant) -> SourceBatch {
+        let timeout = self.timeout();
+        let expired: Vec<Option<IpAddr>> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now.duration_since(p.last) >= timeout)
+            .map(|(k, _)| *k)
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|k| self.pending.remove(&k))
+            .map(Pending::finish)
+            .collect()
+    }
+
+    /// 输出全部缓冲记录（源结束时调用）
+    pub fn flush_all(&mut self) -> SourceBatch {
+        self.pending.drain().map(|(_, p)| p.finish()).collect()
+    }
+
+    /// 最早的超时时刻；无缓冲时为 None
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending
+            .values()
+            .map(|p| p.last)
+            .min()
+            .map(|t| t + self.timeout())
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl Pending {
+    fn finish(mut self) -> SourceEvent {
+        if self.lines > 1 {
+            self.event.payload = RawData::String(self.text);
+        }
+        self.event
+    }
+}
+
+fn payload_text(payload: &RawData) -> Option<&str> {
+    match payload {
+        RawData::String(s) => Some(s.as_str()),
+        RawData::Bytes(b) => std::str::from_utf8(b).ok(),
+        RawData::ArcBytes(b) => std::str::from_utf8(b).ok(),
+    }
+}
+
+/// 在数据源与 WPL 解析之间插入多行合并步骤
+pub struct MultilineSource {
+    inner: Box<dyn DataSource>,
+    joiner: MultilineJoiner,
+}
+
+impl MultilineSource {
+    pub fn new(inner: Box<dyn DataSource>, joiner: MultilineJoiner) -> Self {
+        Self { inner, joiner }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for MultilineSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        loop {
+            let expired = self.joiner.flush_expired(Instant::now());
+            if !expired.is_empty() {
+                return Ok(expired);
+            }
+            let received = match self.joiner.next_deadline() {
+                Some(deadline) => {
+                    tokio::select! {
+                        r = self.inner.receive() => r,
+                        _ = tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)) => continue,
+                    }
+                }
+                None => self.inner.receive().await,
+            };
+            let batch = match received {
+                Ok(batch) => batch,
+                // 源出错/结束前先交付缓冲中的记录，错误在下一次调用时再次返回
+                Err(e) if self.joiner.pending_count() > 0 => {
+                    debug_data!("multiline flush on source error: {}", e);
+                    return Ok(self.joiner.flush_all());
+                }
+                Err(e) => return Err(e),
+            };
+            let joined = self.joiner.join_batch(batch);
+            if !joined.is_empty() {
+                return Ok(joined);
+            }
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        let mut out = self.joiner.flush_expired(Instant::now());
+        if let Some(batch) = self.inner.try_receive() {
+            out.extend(self.joiner.join_batch(batch));
+        }
+        (!out.is_empty()).then_some(out)
+    }
+
+    fn can_try_receive(&mut self) -> bool {
+        self.inner.can_try_receive()
+    }
+
+    fn identifier(&self) -> String {
+        self.inner.identifier()
+    }
+
+    async fn start(&mut self, ctrl_rx: CtrlRx) -> SourceResult<()> {
+        self.inner.start(ctrl_rx).await
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        if self.joiner.pending_count() > 0 {
+            warn_data!(
+                "source '{}' closed with {} unfinished multiline records",
+                self.inner.identifier(),
+                self.joiner.pending_count()
+            );
+        }
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::event_id::next_event_id;
+    use serde_json::json;
+    use std::sync::Arc;
+    use wp_connector_api::Tags;
+
+    fn line(text: &str, peer: Option<[u8; 4]>) -> SourceEvent {
+        let mut ev = SourceEvent::new(
+            next_event_id(),
+            "ml",
+            RawData::from_string(text.to_string()),
+            Arc::new(Tags::new()),
+        );
+        ev.ups_ip = peer.map(IpAddr::from);
+        ev
+    }
+
+    fn texts(batch: &SourceBatch) -> Vec<String> {
+        batch
+            .iter()
+            .map(|ev| payload_text(&ev.payload).unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn tail_backslash_joins_until_plain_line() {
+        let mut j = MultilineJoiner::new(build_pattern("\\\\").unwrap(), 10, 1000);
+        let out = j.join_batch(vec![
+            line("a=1 \\", None),
+            line("  b=2 \\", None),
+            line("c=3", None),
+            line("single", None),
+        ]);
+        assert_eq!(texts(&out), vec!["a=1 \\\n  b=2 \\\nc=3", "single"]);
+        assert_eq!(j.pending_count(), 0);
+    }
+
+    #[test]
+    fn head_indent_joins_and_waits_for_next_record() {
+        let mut j = MultilineJoiner::new(build_pattern("\\h").unwrap(), 10, 1000)
+            .with_anchor(ContinuationAnchor::Head);
+        let out = j.join_batch(vec![
+            line("Exception in main", None),
+            line("\tat foo()", None),
+            line("    at bar()", None),
+            line("next record", None),
+        ]);
+        assert_eq!(
+            texts(&out),
+            vec!["Exception in main\n\tat foo()\n    at bar()"]
+        );
+        // 最后一条需等待后续行或超时
+        assert_eq!(j.pending_count(), 1);
+        assert_eq!(texts(&j.flush_all()), vec!["next record"]);
+    }
+
+    #[test]
+    fn max_lines_and_timeout_flush() {
+        let mut j = MultilineJoiner::new(build_pattern("\\\\").unwrap(), 2, 50);
+        let out = j.join_batch(vec![
+            line("x\\", None),
+            line("y\\", None),
+            line("z\\", None),
+        ]);
+        assert_eq!(texts(&out), vec!["x\\\ny\\"]);
+        assert!(j.flush_expired(Instant::now()).is_empty());
+        let later = Instant::now() + Duration::from_millis(60);
+        assert_eq!(texts(&j.flush_expired(later)), vec!["z\\"]);
+        assert!(j.next_deadline().is_none());
+    }
+
+    #[test]
+    fn peers_are_buffered_separately() {
+        let mut j = MultilineJoiner::new(build_pattern("\\\\").unwrap(), 10, 1000);
+        let a = Some([10, 0, 0, 1]);
+        let b = Some([10, 0, 0, 2]);
+        let out = j.join_batch(vec![
+            line("a1\\", a),
+            line("b1\\", b),
+            line("a2", a),
+            line("b2", b),
+        ]);
+        assert_eq!(texts(&out), vec!["a1\\\na2", "b1\\\nb2"]);
+    }
+
+    #[test]
+    fn from_params_validates() {
+        let mut params = ParamMap::new();
+        assert!(MultilineJoiner::from_params(&params).unwrap().is_none());
+        params.insert("multiline_max_lines".into(), json!(5));
+        assert!(MultilineJoiner::from_params(&params).is_err());
+
+        params.insert("multiline_pattern".into(), json!("{\\h}"));
+        params.insert("multiline_anchor".into(), json!("head"));
+        let j = MultilineJoiner::from_params(&params).unwrap().unwrap();
+        assert_eq!(j.anchor, ContinuationAnchor::Head);
+        assert_eq!(j.max_lines, 5);
+        assert_eq!(j.timeout_ms, DEFAULT_MULTILINE_TIMEOUT_MS);
+
+        params.insert("multiline_anchor".into(), json!("middle"));
+        assert!(MultilineJoiner::from_params(&params).is_err());
+    }
+
+    struct ScriptSource {
+        batches: Vec<SourceBatch>,
+    }
+
+    #[async_trait::async_trait]
+    impl DataSource for ScriptSource {
+        async fn receive(&mut self) -> SourceResult<SourceBatch> {
+            if self.batches.is_empty() {
+                // 无后续数据：模拟空闲的网络源
+                std::future::pending::<()>().await;
+            }
+            Ok(self.batches.remove(0))
+        }
+        fn try_receive(&mut self) -> Option<SourceBatch> {
+            None
+        }
+        fn can_try_receive(&mut self) -> bool {
+            false
+        }
+        fn identifier(&self) -> String {
+            "ml".into()
+        }
+    }
+
+    #[tokio::test]
+    async fn source_flushes_pending_after_timeout_without_new_data() {
+        let inner = ScriptSource {
+            batches: vec![vec![line("first\\", None)], vec![line("second", None)]],
+        };
+        let joiner = MultilineJoiner::new(build_pattern("\\\\").unwrap(), 10, 30);
+        let mut src = MultilineSource::new(Box::new(inner), joiner);
+        assert_eq!(
+            texts(&src.receive().await.unwrap()),
+            vec!["first\\\nsecond"]
+        );
+
+        let mut src = MultilineSource::new(
+            Box::new(ScriptSource {
+                batches: vec![vec![line("dangling\\", None)]],
+            }),
+            MultilineJoiner::new(build_pattern("\\\\").unwrap(), 10, 30),
+        );
+        let out = tokio::time::timeout(Duration::from_secs(1), src.receive())
+            .await
+            .expect("flushed by timeout")
+            .unwrap();
+        assert_eq!(texts(&out), vec!["dangling\\"]);
+    }
+}
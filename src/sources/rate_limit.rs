@@ -0,0 +1,364 @@
+//! source 实例级限速：在 `DataSource` 外包一层令牌桶（复用 wpgen 的 `DynamicRateLimiter`）。
+//!
+//! - `wait` 策略：令牌欠账时先休眠再拉取，背压传导到上游；休眠发生在拉取之前，
+//!   即使 picker 的读取超时取消了本次 receive，也不会丢失已取出的数据。
+//! - `drop` 策略：超出令牌的事件直接丢弃，丢弃数计入独立指标
+//!   `wparse_source_rate_limited_total`（不计入 pick 统计）；整批被丢弃时休眠到下一个令牌可用。
+//!
+//! 每个被包装的 source 在进程内表中登记一个控制块，运行期可通过 [`reconfigure`]
+//! 调整速率/突发/策略（配置重载时由 `Sources::reload_rate_limits` 调用），下一次拉取时生效。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use once_cell::sync::Lazy;
+use wp_conf::structure::{RateLimitPolicy, SourceRateLimitConf};
+use wp_connector_api::{CtrlRx, DataSource, SourceBatch, SourceHandle, SourceResult};
+
+use crate::runtime::generator::speed::DynamicRateLimiter;
+use crate::sample_log_with_hits;
+use crate::stat::prometheus::{self, Counter};
+
+/// 限速控制块：运行期调参入口与丢弃计数
+pub struct RateLimitCtl {
+    instance: String,
+    pending: Mutex<Option<SourceRateLimitConf>>,
+    changed: AtomicBool,
+    dropped: Counter,
+}
+
+impl RateLimitCtl {
+    fn new(instance: &str, source_id: &str) -> Self {
+        Self {
+            instance: instance.to_string(),
+            pending: Mutex::new(None),
+            changed: AtomicBool::new(false),
+            dropped: prometheus::source_rate_limited(source_id),
+        }
+    }
+
+    fn update(&self, conf: SourceRateLimitConf) {
+        *self.pending.lock().expect("rate limit ctl poisoned") = Some(conf);
+        self.changed.store(true, Ordering::Release);
+    }
+
+    fn take_update(&self) -> Option<SourceRateLimitConf> {
+        if !self.changed.swap(false, Ordering::Acquire) {
+            return None;
+        }
+        self.pending.lock().expect("rate limit ctl poisoned").take()
+    }
+
+    /// 进程启动以来该 source 的累计丢弃数
+    pub fn dropped(&self) -> u64 {
+        self.dropped.get()
+    }
+}
+
+/// source 标识 -> 控制块
+static RATE_LIMITS: Lazy<RwLock<HashMap<String, Arc<RateLimitCtl>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 查找 source 的限速控制块；未配置限速的 source 返回 None
+pub fn lookup(source_id: &str) -> Option<Arc<RateLimitCtl>> {
+    RATE_LIMITS
+        .read()
+        .expect("rate limit table poisoned")
+        .get(source_id)
+        .cloned()
+}
+
+/// 调整实例（含其展开出的所有 source）的限速参数，返回受影响的 source 数
+pub fn reconfigure(instance: &str, conf: &SourceRateLimitConf) -> usize {
+    let table = RATE_LIMITS.read().expect("rate limit table poisoned");
+    let mut hits = 0;
+    for ctl in table.values().filter(|c| c.instance == instance) {
+        ctl.update(conf.clone());
+        hits += 1;
+    }
+    hits
+}
+
+fn build_limiter(conf: &SourceRateLimitConf, name: &str) -> DynamicRateLimiter {
+    let limiter = DynamicRateLimiter::from_constant(conf.rate, name);
+    match conf.burst {
+        Some(burst) => limiter.with_burst(burst),
+        None => limiter,
+    }
+}
+
+/// 限速包装的数据源
+pub struct RateLimitedSource {
+    inner: Box<dyn DataSource>,
+    source_id: String,
+    limiter: DynamicRateLimiter,
+    policy: RateLimitPolicy,
+    ctl: Arc<RateLimitCtl>,
+}
+
+impl RateLimitedSource {
+    pub fn new(instance: &str, inner: Box<dyn DataSource>, conf: &SourceRateLimitConf) -> Self {
+        let source_id = inner.identifier();
+        let ctl = Arc::new(RateLimitCtl::new(instance, &source_id));
+        RATE_LIMITS
+            .write()
+            .expect("rate limit table poisoned")
+            .insert(source_id.clone(), ctl.clone());
+        Self {
+            limiter: build_limiter(conf, &source_id),
+            policy: conf.policy,
+            inner,
+            source_id,
+            ctl,
+        }
+    }
+
+    /// 将限速配置应用到实例构建出的所有 source
+    pub fn wrap_all(
+        instance: &str,
+        handles: Vec<SourceHandle>,
+        conf: &SourceRateLimitConf,
+    ) -> Vec<SourceHandle> {
+        handles
+            .into_iter()
+            .map(|h| {
+                let source = RateLimitedSource::new(instance, h.source, conf);
+                SourceHandle::new(Box::new(source), h.meta)
+            })
+            .collect()
+    }
+
+    pub fn ctl(&self) -> &Arc<RateLimitCtl> {
+        &self.ctl
+    }
+
+    fn apply_update(&mut self) {
+        if let Some(conf) = self.ctl.take_update() {
+            info_ctrl!(
+                "source '{}' rate limit reconfigured: rate={}, burst={:?}, policy={}",
+                self.source_id,
+                conf.rate,
+                conf.burst,
+                conf.policy
+            );
+            self.limiter = build_limiter(&conf, &self.source_id);
+            self.policy = conf.policy;
+        }
+    }
+
+    /// drop 策略：按可用令牌截断批次，其余计入丢弃
+    fn admit(&mut self, mut batch: SourceBatch) -> SourceBatch {
+        let granted = self.limiter.try_acquire(batch.len());
+        if granted < batch.len() {
+            let dropped = batch.len() - granted;
+            batch.truncate(granted);
+            self.ctl.dropped.add(dropped as u64);
+            sample_log_with_hits!(
+                RATE_LIMIT_DROP_HITS,
+                warn_mtrc,
+                "source '{}' over rate limit, dropped {} events",
+                self.source_id,
+                dropped
+            );
+        }
+        batch
+    }
+}
+
+impl Drop for RateLimitedSource {
+    fn drop(&mut self) {
+        let mut table = RATE_LIMITS.write().expect("rate limit table poisoned");
+        // 同名 source 可能已被重新构建（热移除后再添加），仅移除自己的登记
+        if table
+            .get(&self.source_id)
+            .is_some_and(|c| Arc::ptr_eq(c, &self.ctl))
+        {
+            table.remove(&self.source_id);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for RateLimitedSource {
+    async fn receive(&mut self) -> SourceResult<SourceBatch> {
+        self.apply_update();
+        match self.policy {
+            RateLimitPolicy::Wait => {
+                let wait = self.limiter.consume(0);
+                if !wait.is_zero() {
+                    tokio::time::sleep(wait).await;
+                }
+                let batch = self.inner.receive().await?;
+                let _ = self.limiter.consume(batch.len());
+                Ok(batch)
+            }
+            RateLimitPolicy::Drop => loop {
+                let batch = self.inner.receive().await?;
+                let batch = self.admit(batch);
+                if !batch.is_empty() {
+                    return Ok(batch);
+                }
+                // 整批被丢弃：令牌已耗尽，休眠到下一个令牌可用，避免空转
+                let wait = self.limiter.next_token_wait();
+                if wait.is_zero() {
+                    tokio::task::yield_now().await;
+                } else {
+                    tokio::time::sleep(wait).await;
+                }
+            },
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<SourceBatch> {
+        self.apply_update();
+        match self.policy {
+            RateLimitPolicy::Wait => {
+                if self.limiter.is_exhausted() {
+                    return None;
+                }
+                let batch = self.inner.try_receive()?;
+                let _ = self.limiter.consume(batch.len());
+                Some(batch)
+            }
+            RateLimitPolicy::Drop => {
+                let batch = self.inner.try_receive()?;
+                let batch = self.admit(batch);
+                (!batch.is_empty()).then_some(batch)
+            }
+        }
+    }
+
+    fn can_try_receive(&mut self) -> bool {
+        self.inner.can_try_receive()
+    }
+
+    fn identifier(&self) -> String {
+        self.source_id.clone()
+    }
+
+    async fn start(&mut self, ctrl_rx: CtrlRx) -> SourceResult<()> {
+        self.inner.start(ctrl_rx).await
+    }
+
+    async fn close(&mut self) -> SourceResult<()> {
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::event_id::next_event_id;
+    use tokio::time::Instant;
+    use wp_connector_api::{SourceEvent, Tags};
+    use wp_parse_api::RawData;
+
+    const FLOOD_BATCH: usize = 10;
+    const FLOOD_RATE: usize = 2000;
+    const FLOOD_BURST: usize = 20;
+    const FLOOD_SECS: f64 = 1.0;
+
+    /// 无限快速产出的数据源，模拟洪峰
+    struct FloodSource {
+        id: String,
+    }
+
+    #[async_trait::async_trait]
+    impl DataSource for FloodSource {
+        async fn receive(&mut self) -> SourceResult<SourceBatch> {
+            Ok(self.try_receive().unwrap_or_default())
+        }
+        fn try_receive(&mut self) -> Option<SourceBatch> {
+            let tags = Arc::new(Tags::new());
+            Some(
+                (0..FLOOD_BATCH)
+                    .map(|_| {
+                        SourceEvent::new(
+                            next_event_id(),
+                            &self.id,
+                            RawData::from_string("flood".to_string()),
+                            tags.clone(),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+        fn can_try_receive(&mut self) -> bool {
+            false
+        }
+        fn identifier(&self) -> String {
+            self.id.clone()
+        }
+    }
+
+    fn flood(id: &str, policy: RateLimitPolicy) -> RateLimitedSource {
+        let conf = SourceRateLimitConf::new(FLOOD_RATE, Some(FLOOD_BURST), policy);
+        RateLimitedSource::new(id, Box::new(FloodSource { id: id.into() }), &conf)
+    }
+
+    /// 持续拉取 `FLOOD_SECS` 秒（虚拟时钟），返回实际吞吐（事件/秒）
+    async fn measure(src: &mut RateLimitedSource) -> f64 {
+        let begin = Instant::now();
+        let mut passed = 0usize;
+        while begin.elapsed().as_secs_f64() < FLOOD_SECS {
+            passed += src.receive().await.unwrap().len();
+        }
+        passed as f64 / begin.elapsed().as_secs_f64()
+    }
+
+    fn assert_within_5pct(actual: f64, expect: usize) {
+        let expect = expect as f64;
+        assert!(
+            (actual - expect).abs() <= expect * 0.05,
+            "throughput {:.0}/s out of ±5% of {}",
+            actual,
+            expect
+        );
+    }
+
+    // 时钟暂停：休眠即推进虚拟时间，吞吐测量不受机器负载影响
+    #[tokio::test(start_paused = true)]
+    async fn wait_policy_holds_rate_under_flood() {
+        let mut src = flood("rl_wait", RateLimitPolicy::Wait);
+        let rate = measure(&mut src).await;
+        assert_within_5pct(rate, FLOOD_RATE);
+        assert_eq!(src.ctl().dropped(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn drop_policy_holds_rate_and_counts_drops() {
+        let mut src = flood("rl_drop", RateLimitPolicy::Drop);
+        let begin = Instant::now();
+        let mut pulls = 0usize;
+        let mut passed = 0usize;
+        while begin.elapsed().as_secs_f64() < FLOOD_SECS {
+            passed += src.receive().await.unwrap().len();
+            pulls += 1;
+        }
+        assert_within_5pct(passed as f64 / begin.elapsed().as_secs_f64(), FLOOD_RATE);
+        let ctl = lookup("rl_drop").expect("registered");
+        // 丢弃计入独立指标
+        assert!(ctl.dropped() > 0);
+        assert_eq!(
+            prometheus::source_rate_limited("rl_drop").get(),
+            ctl.dropped()
+        );
+        // 令牌耗尽时休眠到下一次补充而不是空转拉取（空转时虚拟时钟不会推进，测试无法结束）
+        assert!(pulls <= FLOOD_RATE * 3, "pulls={}", pulls);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reconfigure_applies_on_next_pull_and_unregisters_on_drop() {
+        let mut src = flood("rl_reload", RateLimitPolicy::Wait);
+        let conf =
+            SourceRateLimitConf::new(FLOOD_RATE * 2, Some(FLOOD_BURST), RateLimitPolicy::Wait);
+        assert_eq!(reconfigure("rl_reload", &conf), 1);
+        assert_eq!(reconfigure("missing", &conf), 0);
+        let rate = measure(&mut src).await;
+        assert_within_5pct(rate, FLOOD_RATE * 2);
+
+        drop(src);
+        assert!(lookup("rl_reload").is_none());
+    }
+}
@@ -6,7 +6,8 @@
 use super::config::{Protocol, SyslogSourceSpec};
 use super::tcp_source::TcpSyslogSource;
 use super::udp_source::UdpSyslogSource;
-use crate::sources::tcp::{FramingMode, TcpAcceptor, TcpSource};
+use crate::sources::preproc::multiline::{MULTILINE_PARAMS, MultilineJoiner, MultilineSource};
+use crate::sources::tcp::{ConnLimits, FramingMode, TcpAcceptor, TcpSource};
 use orion_conf::UvsConfFrom;
 use orion_error::ToStructError;
 use serde_json::json;
@@ -17,8 +18,8 @@ use wp_conf::connectors::{ConnectorDef, ConnectorScope};
 use wp_conf::limits::tcp_reader_batch_channel_cap;
 use wp_conf_base::ConfParser;
 use wp_connector_api::{
-    AcceptorHandle, SourceBuildCtx, SourceDefProvider, SourceFactory, SourceHandle, SourceMeta,
-    SourceResult, SourceSvcIns, Tags,
+    AcceptorHandle, DataSource, SourceBuildCtx, SourceDefProvider, SourceFactory, SourceHandle,
+    SourceMeta, SourceResult, SourceSvcIns, Tags,
 };
 use wp_connector_api::{ParamMap, SourceReason};
 
@@ -44,6 +45,15 @@ impl SourceFactory for SyslogSourceFactory {
         "syslog"
     }
 
+    fn validate_spec(&self, spec: &wp_connector_api::SourceSpec) -> SourceResult<()> {
+        let res: anyhow::Result<()> = (|| {
+            SyslogSourceSpec::from_params(&spec.params)?;
+            MultilineJoiner::from_params(&spec.params)?;
+            Ok(())
+        })();
+        res.map_err(|e| SourceReason::from_conf(e.to_string()).to_err())
+    }
+
     async fn build(
         &self,
         spec: &wp_connector_api::SourceSpec,
@@ -51,6 +61,14 @@ impl SourceFactory for SyslogSourceFactory {
     ) -> SourceResult<SourceSvcIns> {
         let fut = async {
             let config = SyslogSourceSpec::from_params(&spec.params)?;
+            // 可选多行合并：在 syslog 头剥离之后、WPL 解析之前执行
+            let with_multiline =
+                |source: Box<dyn DataSource>| -> anyhow::Result<Box<dyn DataSource>> {
+                    Ok(match MultilineJoiner::from_params(&spec.params)? {
+                        Some(joiner) => Box::new(MultilineSource::new(source, joiner)),
+                        None => source,
+                    })
+                };
             let mut base_tags = Tags::from_parse(&spec.tags);
             base_tags.set("access_source", "syslog".to_string());
             base_tags.set("syslog_protocol", format!("{:?}", config.protocol));
@@ -84,8 +102,10 @@ impl SourceFactory for SyslogSourceFactory {
                     )
                     .await?;
                     let meta = meta_builder(&spec.name, &tagset);
-                    SourceSvcIns::new()
-                        .with_sources(vec![SourceHandle::new(Box::new(source), meta)])
+                    SourceSvcIns::new().with_sources(vec![SourceHandle::new(
+                        with_multiline(Box::new(source))?,
+                        meta,
+                    )])
                 }
                 Protocol::Tcp => {
                     let tags = base_tags.clone();
@@ -99,6 +119,7 @@ impl SourceFactory for SyslogSourceFactory {
                         config.address(),
                         config.tcp_recv_bytes,
                         framing,
+                        ConnLimits::default(),
                         pool.clone(),
                         reg_rx,
                     )?;
@@ -122,7 +143,10 @@ impl SourceFactory for SyslogSourceFactory {
                     .await?;
 
                     SourceSvcIns::new()
-                        .with_sources(vec![SourceHandle::new(Box::new(syslog), meta)])
+                        .with_sources(vec![SourceHandle::new(
+                            with_multiline(Box::new(syslog))?,
+                            meta,
+                        )])
                         .with_acceptor(AcceptorHandle::new(spec.name.clone(), Box::new(acceptor)))
                 }
             };
@@ -157,7 +181,10 @@ impl SourceDefProvider for SyslogSourceFactory {
                 "udp_recv_buffer".into(),
                 "header_mode".into(),
                 "fast_strip".into(),
-            ],
+            ]
+            .into_iter()
+            .chain(MULTILINE_PARAMS.iter().map(|k| k.to_string()))
+            .collect(),
             default_params: params,
             origin: Some("builtin:syslog_source".into()),
         }
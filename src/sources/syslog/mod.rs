@@ -237,6 +237,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             4096,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
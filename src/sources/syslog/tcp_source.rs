@@ -412,6 +412,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             65536,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
@@ -465,6 +466,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             65536,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
@@ -516,6 +518,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             65536,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
@@ -554,6 +557,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             4096,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
@@ -591,6 +595,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             4096,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
@@ -628,6 +633,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             4096,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
@@ -685,6 +691,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             4096,
             crate::sources::tcp::FramingMode::Line,
+            crate::sources::tcp::ConnLimits::default(),
             pool,
             rx,
         )
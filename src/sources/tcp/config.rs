@@ -1,5 +1,7 @@
-use super::framing::{DEFAULT_TCP_RECV_BYTES, FramingMode};
+use super::conn::connection::ConnLimits;
+use super::framing::{DEFAULT_TCP_RECV_BYTES, FramingMode, MAX_FRAME_BYTES};
 use anyhow::{anyhow, ensure};
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct TcpSourceSpec {
@@ -8,6 +10,10 @@ pub struct TcpSourceSpec {
     pub tcp_recv_bytes: usize,
     pub framing: FramingMode,
     pub instances: usize,
+    /// 单帧上限：声明长度或未成帧的缓存超过该值时断开连接
+    pub max_frame_bytes: usize,
+    /// 连接空闲超时；None 表示不超时
+    pub idle_timeout: Option<Duration>,
 }
 
 pub const DEFAULT_TCP_SOURCE_INSTANCES: usize = 1;
@@ -41,9 +47,15 @@ impl TcpSourceSpec {
             .as_str()
         {
             "line" => FramingMode::Line,
-            "len" | "length" => FramingMode::Len,
+            "len" | "length" | "octet" => FramingMode::Len,
+            "len4" => FramingMode::Len4,
             "auto" => FramingMode::Auto,
-            other => return Err(anyhow!("Invalid framing: {} (expect auto|line|len)", other)),
+            other => {
+                return Err(anyhow!(
+                    "Invalid framing: {} (expect auto|line|len|len4)",
+                    other
+                ));
+            }
         };
 
         let instances = params
@@ -57,16 +69,42 @@ impl TcpSourceSpec {
         );
         let instances = instances as usize;
 
+        let max_frame_bytes = params
+            .get("max_frame_bytes")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(MAX_FRAME_BYTES as i64);
+        ensure!(max_frame_bytes > 0, "tcp.max_frame_bytes must be > 0");
+
+        let idle_timeout_secs = params
+            .get("idle_timeout_secs")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        ensure!(
+            idle_timeout_secs >= 0,
+            "tcp.idle_timeout_secs must be >= 0 (0 disables the timeout)"
+        );
+        let idle_timeout =
+            (idle_timeout_secs > 0).then(|| Duration::from_secs(idle_timeout_secs as u64));
+
         Ok(Self {
             addr,
             port,
             tcp_recv_bytes,
             framing,
             instances,
+            max_frame_bytes: max_frame_bytes as usize,
+            idle_timeout,
         })
     }
 
     pub fn address(&self) -> String {
         format!("{}:{}", self.addr, self.port)
     }
+
+    pub fn conn_limits(&self) -> ConnLimits {
+        ConnLimits {
+            max_frame_bytes: self.max_frame_bytes,
+            idle_timeout: self.idle_timeout,
+        }
+    }
 }
@@ -1,10 +1,11 @@
 use crate::sources::event_id::next_event_id;
-use crate::sources::tcp::framing::{FramingExtractor, FramingMode};
+use crate::sources::tcp::framing::{FramingExtractor, FramingMode, MAX_FRAME_BYTES};
 use bytes::{Bytes, BytesMut};
 use std::collections::VecDeque;
 use std::io::ErrorKind;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use wp_connector_api::{SourceBatch, SourceEvent, SourceReason, SourceResult, Tags};
 use wp_parse_api::RawData;
@@ -15,17 +16,41 @@ const MAX_BATCH_BYTES: usize = 64 * 1024; // soft cap; single payload may exceed
 // Balanced shrink thresholds：空闲时将过大的缓冲收缩到较小基线
 const SHRINK_HIGH_WATER_BYTES: usize = 1024 * 1024; // 若 capacity 超过 1MiB 且 len==0 则收缩
 const SHRINK_TARGET_BYTES: usize = 256 * 1024; // 收缩到 256KiB（降低扩容↔收缩抖动）
+// 长度头最长为 10 位十进制 + 空格（len4 仅 4 字节）
+const MAX_FRAME_HEADER_BYTES: usize = 11;
 
 pub enum ReadOutcome {
     NoData,
     Produced(SourceBatch),
     Closed,
+    /// 超过空闲超时未收到任何数据
+    IdleTimeout,
+}
+
+/// 单连接的分帧与空闲限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnLimits {
+    /// 单帧上限：声明长度或未成帧的缓存超过该值时断开连接
+    pub max_frame_bytes: usize,
+    /// 空闲超时；None 表示不超时
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for ConnLimits {
+    fn default() -> Self {
+        Self {
+            max_frame_bytes: MAX_FRAME_BYTES,
+            idle_timeout: None,
+        }
+    }
 }
 
 pub struct TcpConnection {
     stream: TcpStream,
     client_addr: SocketAddr,
     framing: FramingMode,
+    limits: ConnLimits,
+    last_active: Instant,
     batcher: BatchBuilder,
 }
 
@@ -69,6 +94,7 @@ impl TcpConnection {
         stream: TcpStream,
         client_addr: SocketAddr,
         framing: FramingMode,
+        limits: ConnLimits,
         base_tags: Tags,
         tcp_recv_bytes: usize,
         source_key: String,
@@ -78,6 +104,8 @@ impl TcpConnection {
             stream,
             client_addr,
             framing,
+            limits,
+            last_active: Instant::now(),
             batcher: BatchBuilder::new(
                 BytesMut::with_capacity(capacity),
                 base_tags,
@@ -120,12 +148,8 @@ impl TcpConnection {
                         self.batcher.pending_len(),
                         self.batcher.pending_bytes()
                     );
-                    self.batcher.drain_messages(
-                        self.framing,
-                        self.client_addr.ip(),
-                        &mut produced,
-                        &mut produced_bytes,
-                    );
+                    self.last_active = Instant::now();
+                    self.drain_buffer(&mut produced, &mut produced_bytes)?;
                     if !produced.is_empty() {
                         return Ok(ReadOutcome::Produced(produced));
                     }
@@ -133,6 +157,9 @@ impl TcpConnection {
                 }
                 Err(ref e) if e.kind() == ErrorKind::WouldBlock => {
                     if produced.is_empty() {
+                        if self.idle_remaining() == Some(Duration::ZERO) {
+                            return Ok(ReadOutcome::IdleTimeout);
+                        }
                         // No immediate data; opportunistically shrink buffer if idle
                         self.batcher.maybe_shrink();
                         return Ok(ReadOutcome::NoData);
@@ -160,7 +187,16 @@ impl TcpConnection {
             return Ok(ReadOutcome::Produced(produced));
         }
         loop {
-            if let Err(e) = self.stream.readable().await {
+            let readable = match self.idle_remaining() {
+                Some(remaining) => {
+                    match tokio::time::timeout(remaining, self.stream.readable()).await {
+                        Ok(res) => res,
+                        Err(_) => return Ok(ReadOutcome::IdleTimeout),
+                    }
+                }
+                None => self.stream.readable().await,
+            };
+            if let Err(e) = readable {
                 return Err(SourceReason::Disconnect(format!(
                     "tcp readable error ({}): {}",
                     self.client_addr, e
@@ -184,12 +220,8 @@ impl TcpConnection {
                         self.batcher.pending_len(),
                         self.batcher.pending_bytes()
                     );
-                    self.batcher.drain_messages(
-                        self.framing,
-                        self.client_addr.ip(),
-                        &mut produced,
-                        &mut produced_bytes,
-                    );
+                    self.last_active = Instant::now();
+                    self.drain_buffer(&mut produced, &mut produced_bytes)?;
                     if !produced.is_empty() {
                         return Ok(ReadOutcome::Produced(produced));
                     }
@@ -215,6 +247,43 @@ impl TcpConnection {
         self.client_addr.ip()
     }
 
+    /// 距离空闲超时的剩余时间；未配置超时时返回 None
+    fn idle_remaining(&self) -> Option<Duration> {
+        self.limits
+            .idle_timeout
+            .map(|idle| idle.saturating_sub(self.last_active.elapsed()))
+    }
+
+    fn drain_buffer(
+        &mut self,
+        produced: &mut SourceBatch,
+        produced_bytes: &mut usize,
+    ) -> SourceResult<()> {
+        self.batcher
+            .drain_messages(
+                self.framing,
+                self.limits.max_frame_bytes,
+                self.client_addr.ip(),
+                produced,
+                produced_bytes,
+            )
+            .map_err(|frame_len| {
+                warn_data!(
+                    "TCP source '{}' conn {} frame of {} bytes exceeds max_frame_bytes={}; dropping connection",
+                    self.batcher.source_key,
+                    self.client_addr,
+                    frame_len,
+                    self.limits.max_frame_bytes
+                );
+                self.batcher.buffer.clear();
+                SourceReason::Disconnect(format!(
+                    "tcp frame too large ({}): {} > {} bytes",
+                    self.client_addr, frame_len, self.limits.max_frame_bytes
+                ))
+                .into()
+            })
+    }
+
     pub fn pending_len(&self) -> usize {
         self.batcher.pending_len()
     }
@@ -286,14 +355,16 @@ impl BatchBuilder {
         }
     }
 
+    /// 抽取缓冲中的完整帧；遇到超长帧时返回其长度
     fn drain_messages(
         &mut self,
         framing: FramingMode,
+        max_frame_bytes: usize,
         peer_ip: IpAddr,
         batch: &mut SourceBatch,
         produced_bytes: &mut usize,
-    ) {
-        while let Some(payload) = extract_message(framing, &mut self.buffer) {
+    ) -> Result<(), usize> {
+        while let Some(payload) = next_frame(framing, max_frame_bytes, &mut self.buffer)? {
             let event = self.build_event(payload, peer_ip);
             let event_size = event_payload_len(&event);
             let would_exceed = *produced_bytes + event_size > self.max_batch_bytes;
@@ -327,6 +398,7 @@ impl BatchBuilder {
                 break;
             }
         }
+        Ok(())
     }
 
     fn pending_len(&self) -> usize {
@@ -353,11 +425,37 @@ fn extract_message(framing: FramingMode, buffer: &mut BytesMut) -> Option<Bytes>
     match framing {
         FramingMode::Line => FramingExtractor::extract_line_message(buffer),
         FramingMode::Len => FramingExtractor::extract_length_prefixed_message(buffer),
+        FramingMode::Len4 => FramingExtractor::extract_len4_message(buffer),
         FramingMode::Auto => FramingExtractor::extract_length_prefixed_message(buffer)
             .or_else(|| FramingExtractor::extract_line_message(buffer)),
     }
 }
 
+/// 带长度上限的分帧：声明长度超限，或缓冲中无完整帧且已超过上限时返回 Err(长度)
+fn next_frame(
+    framing: FramingMode,
+    max_frame_bytes: usize,
+    buffer: &mut BytesMut,
+) -> Result<Option<Bytes>, usize> {
+    let declared = match framing {
+        FramingMode::Line => None,
+        FramingMode::Len | FramingMode::Auto => FramingExtractor::length_prefix(buffer),
+        FramingMode::Len4 => FramingExtractor::len4_prefix(buffer),
+    };
+    let oversized = declared.filter(|&len| len > max_frame_bytes);
+    let extracted = match (framing, oversized) {
+        // Auto 下以超大数字开头的行（如 epoch 时间戳）不是长度头，按行分帧
+        (FramingMode::Auto, Some(_)) => FramingExtractor::extract_line_message(buffer),
+        (_, Some(len)) => return Err(len),
+        (_, None) => extract_message(framing, buffer),
+    };
+    match extracted {
+        Some(frame) => Ok(Some(frame)),
+        None if buffer.len() > max_frame_bytes + MAX_FRAME_HEADER_BYTES => Err(buffer.len()),
+        None => Ok(None),
+    }
+}
+
 pub fn batch_bytes(batch: &SourceBatch) -> usize {
     batch.iter().map(event_payload_len).sum()
 }
@@ -401,6 +499,7 @@ mod tests {
             stream,
             peer,
             FramingMode::Line,
+            ConnLimits::default(),
             Tags::new(),
             8192,
             "test".into(),
@@ -460,6 +559,7 @@ mod tests {
             stream,
             peer,
             FramingMode::Len,
+            ConnLimits::default(),
             Tags::new(),
             8192,
             "test_len".into(),
@@ -513,6 +613,7 @@ mod tests {
             stream,
             peer,
             FramingMode::Auto,
+            ConnLimits::default(),
             Tags::new(),
             8192,
             "test_auto".into(),
@@ -538,6 +639,197 @@ mod tests {
         }
     }
 
+    fn payload_text(ev: &SourceEvent) -> String {
+        match &ev.payload {
+            RawData::Bytes(b) => String::from_utf8_lossy(b).to_string(),
+            _ => panic!("expected bytes payload"),
+        }
+    }
+
+    /// 将每帧拆成多次写入（跨 TCP 分段），验证连接缓冲能正确重组
+    async fn assert_reassembles_split_writes(framing: FramingMode, wire: Vec<u8>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind listener");
+        let addr = listener.local_addr().unwrap();
+        let (chunk_tx, mut chunk_rx) = tokio::sync::mpsc::channel::<Vec<u8>>(8);
+        let writer = tokio::spawn(async move {
+            let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+            client.set_nodelay(true).unwrap();
+            while let Some(chunk) = chunk_rx.recv().await {
+                client.write_all(&chunk).await.unwrap();
+                client.flush().await.unwrap();
+            }
+        });
+        let (stream, peer) = listener.accept().await.expect("accept connection");
+        let mut conn = TcpConnection::new(
+            stream,
+            peer,
+            framing,
+            ConnLimits::default(),
+            Tags::new(),
+            8192,
+            "test_split".into(),
+        );
+
+        let mut got = Vec::new();
+        for chunk in wire.chunks(3) {
+            chunk_tx.send(chunk.to_vec()).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            if let ReadOutcome::Produced(batch) = conn.try_read_batch().expect("read") {
+                for ev in &batch {
+                    assert_eq!(ev.ups_ip, Some(peer.ip()), "peer ip attached");
+                    got.push(payload_text(ev));
+                }
+            }
+        }
+        drop(chunk_tx);
+        writer.await.unwrap();
+        // 最后一段可能晚于轮询到达
+        while got.len() < 3 {
+            match conn.read_batch().await.expect("read") {
+                ReadOutcome::Produced(batch) => got.extend(batch.iter().map(payload_text)),
+                _ => break,
+            }
+        }
+        assert_eq!(got, vec!["alpha", "multi\nline beta", "gamma"]);
+    }
+
+    #[tokio::test]
+    async fn split_writes_reassemble_for_all_framings() {
+        if std::env::var("WP_NET_TESTS").unwrap_or_default() != "1" {
+            return;
+        }
+        let frames = ["alpha", "multi\nline beta", "gamma"];
+
+        let mut octet = Vec::new();
+        let mut len4 = Vec::new();
+        for f in frames {
+            octet.extend_from_slice(format!("{} {}", f.len(), f).as_bytes());
+            len4.extend_from_slice(&(f.len() as u32).to_be_bytes());
+            len4.extend_from_slice(f.as_bytes());
+        }
+        assert_reassembles_split_writes(FramingMode::Len, octet).await;
+        assert_reassembles_split_writes(FramingMode::Len4, len4).await;
+
+        let lines = b"alpha\r\nline beta\ngamma\n".to_vec();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let writer = tokio::spawn(async move {
+            let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+            client.set_nodelay(true).unwrap();
+            for chunk in lines.chunks(4) {
+                client.write_all(chunk).await.unwrap();
+                client.flush().await.unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        });
+        let (stream, peer) = listener.accept().await.unwrap();
+        let mut conn = TcpConnection::new(
+            stream,
+            peer,
+            FramingMode::Line,
+            ConnLimits::default(),
+            Tags::new(),
+            8192,
+            "test_split_line".into(),
+        );
+        let mut got = Vec::new();
+        while got.len() < 3 {
+            match conn.read_batch().await.expect("read") {
+                ReadOutcome::Produced(batch) => got.extend(batch.iter().map(payload_text)),
+                ReadOutcome::Closed => break,
+                _ => {}
+            }
+        }
+        writer.await.unwrap();
+        assert_eq!(got, vec!["alpha", "line beta", "gamma"]);
+    }
+
+    #[tokio::test]
+    async fn oversized_frame_and_idle_connection_are_dropped() {
+        if std::env::var("WP_NET_TESTS").unwrap_or_default() != "1" {
+            return;
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let limits = ConnLimits {
+            max_frame_bytes: 16,
+            idle_timeout: Some(std::time::Duration::from_millis(100)),
+        };
+
+        let mut big = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (stream, peer) = listener.accept().await.unwrap();
+        let mut conn = TcpConnection::new(
+            stream,
+            peer,
+            FramingMode::Len4,
+            limits,
+            Tags::new(),
+            8192,
+            "test_limits".into(),
+        );
+        big.write_all(&1024u32.to_be_bytes()).await.unwrap();
+        assert!(
+            conn.read_batch().await.is_err(),
+            "declared length over limit"
+        );
+
+        let _quiet = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (stream, peer) = listener.accept().await.unwrap();
+        let mut conn = TcpConnection::new(
+            stream,
+            peer,
+            FramingMode::Line,
+            limits,
+            Tags::new(),
+            8192,
+            "test_idle".into(),
+        );
+        assert!(matches!(
+            conn.read_batch().await.expect("idle read"),
+            ReadOutcome::IdleTimeout
+        ));
+    }
+
+    #[test]
+    fn next_frame_enforces_max_frame_bytes() {
+        let mut buf = BytesMut::from("5 hello");
+        assert_eq!(
+            next_frame(FramingMode::Len, 8, &mut buf),
+            Ok(Some(Bytes::from("hello")))
+        );
+        let mut buf = BytesMut::from("9 toolong");
+        assert_eq!(next_frame(FramingMode::Len, 8, &mut buf), Err(9));
+        let mut buf = BytesMut::from(&[0, 0, 0, 9][..]);
+        assert_eq!(next_frame(FramingMode::Len4, 8, &mut buf), Err(9));
+
+        // 无换行的缓存超过上限 + 头部余量
+        let mut buf = BytesMut::from(&[b'x'; 20][..]);
+        assert_eq!(next_frame(FramingMode::Line, 8, &mut buf), Err(20));
+        let mut buf = BytesMut::from(&[b'x'; 12][..]);
+        assert_eq!(next_frame(FramingMode::Line, 8, &mut buf), Ok(None));
+    }
+
+    #[test]
+    fn next_frame_auto_falls_back_to_line_on_large_numbers() {
+        let mut buf = BytesMut::from("1700000000 login ok\n5 hello");
+        assert_eq!(
+            next_frame(FramingMode::Auto, 64, &mut buf),
+            Ok(Some(Bytes::from("1700000000 login ok")))
+        );
+        assert_eq!(
+            next_frame(FramingMode::Auto, 64, &mut buf),
+            Ok(Some(Bytes::from("hello")))
+        );
+
+        // 尚无换行时继续等待，直到超过上限 + 头部余量
+        let mut buf = BytesMut::from("1700000000 partial");
+        assert_eq!(next_frame(FramingMode::Auto, 64, &mut buf), Ok(None));
+        let mut buf = BytesMut::from("1700000000 partial");
+        assert_eq!(next_frame(FramingMode::Auto, 4, &mut buf), Err(18));
+    }
+
     #[test]
     fn test_batch_builder_maybe_shrink() {
         let mut batcher = BatchBuilder::new(
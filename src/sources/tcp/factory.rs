@@ -4,14 +4,16 @@ use serde_json::json;
 use wp_conf::connectors::{ConnectorDef, ConnectorScope, ParamMap};
 use wp_conf_base::ConfParser;
 use wp_connector_api::{
-    AcceptorHandle, SourceBuildCtx, SourceFactory, SourceHandle, SourceMeta, SourceResult,
-    SourceSpec as ResolvedSourceSpec, SourceSvcIns, Tags,
+    AcceptorHandle, DataSource, SourceBuildCtx, SourceFactory, SourceHandle, SourceMeta,
+    SourceResult, SourceSpec as ResolvedSourceSpec, SourceSvcIns, Tags,
 };
 use wp_connector_api::{SourceDefProvider, SourceReason};
 
 use super::TcpAcceptor;
 use super::config::TcpSourceSpec;
+use super::framing::MAX_FRAME_BYTES;
 use super::source::TcpSource;
+use crate::sources::preproc::multiline::{MULTILINE_PARAMS, MultilineJoiner, MultilineSource};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
@@ -33,6 +35,7 @@ impl SourceFactory for TcpSourceFactory {
                 anyhow::bail!("Invalid tags: {}", e);
             }
             TcpSourceSpec::from_params(&spec.params)?;
+            MultilineJoiner::from_params(&spec.params)?;
             Ok(())
         })();
         res.map_err(|e| SourceReason::from_conf(e.to_string()).to_err())
@@ -66,6 +69,7 @@ impl SourceFactory for TcpSourceFactory {
                     conf.address(),
                     conf.tcp_recv_bytes,
                     conf.framing,
+                    conf.conn_limits(),
                     connection_registry.clone(),
                     reader_reg_rx,
                 )?;
@@ -78,7 +82,12 @@ impl SourceFactory for TcpSourceFactory {
                     meta.tags.set("instance".to_string(), (idx + 1).to_string());
                 }
 
-                source_handles.push(SourceHandle::new(Box::new(source), meta));
+                let source: Box<dyn DataSource> = match MultilineJoiner::from_params(&spec.params)?
+                {
+                    Some(joiner) => Box::new(MultilineSource::new(Box::new(source), joiner)),
+                    None => Box::new(source),
+                };
+                source_handles.push(SourceHandle::new(source, meta));
             }
 
             let acceptor = TcpAcceptor::new(
@@ -108,6 +117,8 @@ impl SourceDefProvider for TcpSourceFactory {
         params.insert("port".into(), json!(9000));
         params.insert("framing".into(), json!("auto"));
         params.insert("tcp_recv_bytes".into(), json!(256_000));
+        params.insert("max_frame_bytes".into(), json!(MAX_FRAME_BYTES));
+        params.insert("idle_timeout_secs".into(), json!(0));
         params.insert("instances".into(), json!(1));
         ConnectorDef {
             id: "tcp_src".into(),
@@ -118,8 +129,13 @@ impl SourceDefProvider for TcpSourceFactory {
                 "port".into(),
                 "framing".into(),
                 "tcp_recv_bytes".into(),
+                "max_frame_bytes".into(),
+                "idle_timeout_secs".into(),
                 "instances".into(),
-            ],
+            ]
+            .into_iter()
+            .chain(MULTILINE_PARAMS.iter().map(|k| k.to_string()))
+            .collect(),
             default_params: params,
             origin: Some("builtin:tcp_source".into()),
         }
@@ -11,7 +11,7 @@ pub const STOP_CHANNEL_CAPACITY: usize = 2;
 
 // Octet-counting 相关限制
 const MAX_LEN_DIGITS: usize = 10; // 长度前缀最多 10 位十进制
-const MAX_FRAME_BYTES: usize = 10_000_000; // 单帧 10MB 上限
+pub const MAX_FRAME_BYTES: usize = 10_000_000; // 单帧 10MB 上限（tcp 源 max_frame_bytes 默认值）
 
 /// 事件消息：client-ip + payload
 pub type Message = (Arc<str>, Bytes);
@@ -27,6 +27,8 @@ pub enum FramingMode {
     Line,
     /// 按 RFC6587 风格 length 前缀分帧（`<len> <payload>`）
     Len,
+    /// 4 字节大端二进制长度前缀（`<u32 BE><payload>`）
+    Len4,
 }
 
 // Provide compatibility extractor struct API for modules expecting `FramingExtractor`
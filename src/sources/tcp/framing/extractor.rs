@@ -1,7 +1,10 @@
 use bytes::{Buf, Bytes, BytesMut};
 use memchr::memchr;
 
-/// Message framing extractor (line, length-prefixed RFC6587)
+/// Bytes of the binary length header used by `FramingMode::Len4`
+pub const LEN4_HEADER_BYTES: usize = 4;
+
+/// Message framing extractor (line, length-prefixed RFC6587, 4-byte binary length)
 pub struct FramingExtractor;
 
 impl FramingExtractor {
@@ -44,6 +47,33 @@ impl FramingExtractor {
         Some(payload.freeze())
     }
 
+    /// Declared payload length of a leading "<len><SP>" header, if complete
+    pub fn length_prefix(buf: &BytesMut) -> Option<usize> {
+        let mut pos = 0;
+        let mut len = 0usize;
+        while pos < buf.len() && pos < 10 && buf[pos].is_ascii_digit() {
+            len = len * 10 + (buf[pos] - b'0') as usize;
+            pos += 1;
+        }
+        (pos > 0 && pos < buf.len() && buf[pos] == b' ').then_some(len)
+    }
+
+    /// Extract message prefixed by a 4-byte big-endian length: "<u32 BE><payload>"
+    pub fn extract_len4_message(buf: &mut BytesMut) -> Option<Bytes> {
+        let len = Self::len4_prefix(buf)?;
+        if buf.len() < LEN4_HEADER_BYTES + len {
+            return None;
+        }
+        buf.advance(LEN4_HEADER_BYTES);
+        Some(buf.split_to(len).freeze())
+    }
+
+    /// Declared payload length of a leading 4-byte big-endian header, if complete
+    pub fn len4_prefix(buf: &BytesMut) -> Option<usize> {
+        let header: [u8; LEN4_HEADER_BYTES] = buf.get(..LEN4_HEADER_BYTES)?.try_into().ok()?;
+        Some(u32::from_be_bytes(header) as usize)
+    }
+
     pub fn has_length_prefix(buf: &BytesMut) -> bool {
         let mut pos = 0;
         let mut found_digit = false;
@@ -183,6 +213,43 @@ mod tests {
         assert!(!FramingExtractor::has_newline(&BytesMut::from("hello\r")));
     }
 
+    #[test]
+    fn test_extract_len4_message_split_header_and_payload() {
+        let mut wire = Vec::new();
+        for msg in [&b"hello"[..], &b"multi\nline"[..]] {
+            wire.extend_from_slice(&(msg.len() as u32).to_be_bytes());
+            wire.extend_from_slice(msg);
+        }
+        // Feed one byte at a time: frames only appear once complete
+        let mut buf = BytesMut::new();
+        let mut out = Vec::new();
+        for b in wire {
+            buf.extend_from_slice(&[b]);
+            if let Some(msg) = FramingExtractor::extract_len4_message(&mut buf) {
+                out.push(msg);
+            }
+        }
+        assert_eq!(out, vec![Bytes::from("hello"), Bytes::from("multi\nline")]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_declared_prefix_lengths() {
+        assert_eq!(
+            FramingExtractor::length_prefix(&BytesMut::from("12 abc")),
+            Some(12)
+        );
+        assert_eq!(FramingExtractor::length_prefix(&BytesMut::from("12")), None);
+        assert_eq!(
+            FramingExtractor::len4_prefix(&BytesMut::from(&[0u8, 0, 1, 0][..])),
+            Some(256)
+        );
+        assert_eq!(
+            FramingExtractor::len4_prefix(&BytesMut::from(&[0u8, 0][..])),
+            None
+        );
+    }
+
     // Integration test
     #[test]
     fn test_mixed_scenarios() {
@@ -1,7 +1,7 @@
 //! TCP 源实现 - 高性能分离式架构
 //!
 //! 模块结构：
-//! - framing.rs：TCP消息分帧处理（行/长度前缀/自动检测）
+//! - framing.rs：TCP消息分帧处理（行/长度前缀/4 字节长度头/自动检测）
 //! - source.rs：TcpSource 实现，直接管理监听 loop 交付的 sockets
 //! - factory.rs：参数解析与 TcpSourceFactory
 //! - zc_types.rs：零拷贝数据结构
@@ -18,6 +18,7 @@ pub mod source;
 pub mod worker;
 pub mod zc;
 pub use acceptor::TcpAcceptor;
+pub use conn::connection::ConnLimits;
 pub use factory::{TcpSourceFactory, register_tcp_factory};
 pub use framing::FramingMode;
 pub use source::TcpSource;
@@ -7,7 +7,7 @@ use tokio::sync::mpsc;
 use wp_connector_api::{CtrlRx, DataSource, SourceBatch, SourceReason, SourceResult, Tags};
 
 use super::ConnectionRegistry;
-use super::conn::connection::{ReadOutcome, TcpConnection, batch_bytes};
+use super::conn::connection::{ConnLimits, ReadOutcome, TcpConnection, batch_bytes};
 use super::framing::FramingMode;
 use super::worker::ConnectionRegistration;
 
@@ -43,6 +43,18 @@ impl<'a> ConnectionGuard<'a> {
         self.conn.take();
     }
 
+    fn deregister_idle(&mut self, peer: std::net::IpAddr) {
+        let idle = self.source.limits.idle_timeout.unwrap_or_default();
+        info_ctrl!(
+            "TCP source '{}' conn {} idle for {:?} (peer={}); closing",
+            self.source.key,
+            self.conn_id,
+            idle,
+            peer
+        );
+        self.deregister(&format!("idle timeout from {}", peer));
+    }
+
     async fn read(mut self) -> SourceResult<Option<SourceBatch>> {
         let key = self.source.key.clone();
         let conn_id = self.conn_id;
@@ -114,6 +126,10 @@ impl<'a> ConnectionGuard<'a> {
                 return Ok(None);
             }
             Ok(ReadOutcome::NoData) => {}
+            Ok(ReadOutcome::IdleTimeout) => {
+                self.deregister_idle(peer);
+                return Ok(None);
+            }
         }
 
         let key = self.source.key.clone();
@@ -168,6 +184,10 @@ impl<'a> ConnectionGuard<'a> {
                 self.requeue();
                 Ok(None)
             }
+            Ok(ReadOutcome::IdleTimeout) => {
+                self.deregister_idle(peer);
+                Ok(None)
+            }
             Ok(ReadOutcome::Closed) => {
                 let (pending_events, pending_bytes) = self
                     .conn
@@ -214,6 +234,7 @@ pub struct TcpSource {
     key: String,
     base_tags: Tags,
     framing: FramingMode,
+    limits: ConnLimits,
     tcp_recv_bytes: usize,
     registry: ConnectionRegistry,
     connection_rx: mpsc::Receiver<ConnectionRegistration>,
@@ -230,6 +251,7 @@ impl TcpSource {
         _address: String,
         tcp_recv_bytes: usize,
         framing: FramingMode,
+        limits: ConnLimits,
         registry: ConnectionRegistry,
         connection_rx: mpsc::Receiver<ConnectionRegistration>,
     ) -> SourceResult<Self> {
@@ -241,6 +263,7 @@ impl TcpSource {
             key,
             base_tags,
             framing,
+            limits,
             tcp_recv_bytes,
             registry,
             connection_rx,
@@ -260,6 +283,7 @@ impl TcpSource {
             reg.stream,
             reg.peer_addr,
             self.framing,
+            self.limits,
             self.base_tags.clone(),
             self.tcp_recv_bytes,
             self.key.clone(),
@@ -429,6 +453,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             8192,
             FramingMode::Line,
+            ConnLimits::default(),
             registry,
             rx,
         );
@@ -448,6 +473,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             8192,
             FramingMode::Line,
+            ConnLimits::default(),
             registry.clone(),
             reg_rx,
         )
@@ -495,6 +521,7 @@ mod tests {
             "127.0.0.1:0".to_string(),
             8192,
             FramingMode::Line,
+            ConnLimits::default(),
             registry.clone(),
             reg_rx,
         )
@@ -0,0 +1,239 @@
+//! 累计 source/sink/规则命中计数落盘：按周期及正常退出时写入 `<work_root>/.run/ingest_stats.json`，
+//! 启动时读回上次的累计值作为基线，使重启后的统计可连续累加（计费口径）。
+//! 文件同时记录累计窗口起点与各规则最近命中的落盘时间，供 `wproj prj coverage` 直接读取。
+//!
+//! 本次运行的计数取自 [`prometheus`](super::prometheus) 计数器；文件带校验头，
+//! 损坏或截断的文件改名为 `*.corrupt` 保留并告警，本次从零开始累计。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use wp_cli_core::business::observability::{IngestCheckpoint, IngestCounters};
+
+use super::prometheus::ingest_snapshot;
+use crate::runtime::actor::command::{ActorCtrlCmd, CmdSubscriber};
+
+pub struct IngestCheckpointer {
+    path: PathBuf,
+    /// 历次运行的累计值（不含本次）
+    baseline: IngestCounters,
+    /// 累计窗口起点（首次落盘前的启动时间）
+    window_start: String,
+    /// 规则 -> 最近一次命中数增长时的落盘时间；粒度为落盘周期
+    rule_last_seen: BTreeMap<String, String>,
+    /// 上次落盘时的规则累计命中
+    rule_hits: BTreeMap<String, u64>,
+}
+
+impl IngestCheckpointer {
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let now = chrono::Local::now().to_rfc3339();
+        let prev = match IngestCheckpoint::load(&path) {
+            Ok(Some(ckpt)) => ckpt,
+            Ok(None) => IngestCheckpoint::default(),
+            Err(e) => {
+                // 下次落盘会覆盖原文件：先移到一旁，便于人工核对或恢复累计值
+                let aside = corrupt_path(&path);
+                match std::fs::rename(&path, &aside) {
+                    Ok(()) => warn_ctrl!(
+                        "ingest stats checkpoint unreadable, moved to {}; lifetime totals restart from zero: {:#}",
+                        aside.display(),
+                        e
+                    ),
+                    Err(re) => warn_ctrl!(
+                        "ingest stats checkpoint unreadable and could not be moved to {} ({}); lifetime totals restart from zero: {:#}",
+                        aside.display(),
+                        re,
+                        e
+                    ),
+                }
+                IngestCheckpoint::default()
+            }
+        };
+        Self {
+            path,
+            window_start: prev.window_start.unwrap_or(now),
+            rule_last_seen: prev.rule_last_seen,
+            rule_hits: prev.lifetime.rules.clone(),
+            baseline: prev.lifetime,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn baseline(&self) -> &IngestCounters {
+        &self.baseline
+    }
+
+    /// 以给定的本次运行计数生成检查点；命中数较上次增长的规则记为本次落盘时间出现过
+    pub fn checkpoint_with(&mut self, since_start: IngestCounters) -> IngestCheckpoint {
+        let updated_at = chrono::Local::now().to_rfc3339();
+        let lifetime = self.baseline.merged(&since_start);
+        for (rule, hits) in &lifetime.rules {
+            let prev = self.rule_hits.insert(rule.clone(), *hits).unwrap_or(0);
+            if *hits > prev {
+                self.rule_last_seen.insert(rule.clone(), updated_at.clone());
+            }
+        }
+        IngestCheckpoint {
+            updated_at,
+            lifetime,
+            since_start,
+            window_start: Some(self.window_start.clone()),
+            rule_last_seen: self.rule_last_seen.clone(),
+        }
+    }
+
+    pub fn save(&mut self) {
+        let ckpt = self.checkpoint_with(ingest_snapshot());
+        if let Err(e) = ckpt.save(&self.path) {
+            warn_ctrl!("write ingest stats checkpoint failed: {:#}", e);
+        }
+    }
+}
+
+/// `ingest_stats.json` -> `ingest_stats.json.corrupt`
+fn corrupt_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".corrupt");
+    path.with_file_name(name)
+}
+
+/// 周期落盘任务；收到 Stop（或命令通道关闭）时写最后一次后退出
+pub fn spawn_ingest_checkpoint(
+    path: PathBuf,
+    every: Duration,
+    mut cmd_sub: CmdSubscriber,
+) -> JoinHandle<()> {
+    let mut ckpt = IngestCheckpointer::load(path);
+    info_ctrl!(
+        "ingest stats checkpoint: {} (every {}s)",
+        ckpt.path().display(),
+        every.as_secs()
+    );
+    tokio::spawn(async move {
+        let mut tick = interval(every);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        tick.tick().await;
+        loop {
+            tokio::select! {
+                cmd = cmd_sub.recv() => match cmd {
+                    Ok(ActorCtrlCmd::Stop(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                },
+                _ = tick.tick() => ckpt.save(),
+            }
+        }
+        ckpt.save();
+        info_ctrl!("ingest stats checkpoint saved on shutdown");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wp_cli_core::business::observability::{SinkTotals, SourceTotals};
+
+    fn run_counts(received: u64, sent: u64) -> IngestCounters {
+        let mut c = IngestCounters::default();
+        c.sources.insert(
+            "file_1".into(),
+            SourceTotals {
+                received,
+                parsed: received,
+                missed: 0,
+            },
+        );
+        c.sinks.insert(
+            "all_sink".into(),
+            SinkTotals {
+                sent,
+                ..Default::default()
+            },
+        );
+        c
+    }
+
+    #[test]
+    fn totals_continue_across_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".run/ingest_stats.json");
+
+        // 第一次运行
+        let mut first = IngestCheckpointer::load(&path);
+        assert_eq!(first.baseline(), &IngestCounters::default());
+        first
+            .checkpoint_with(run_counts(100, 90))
+            .save(&path)
+            .unwrap();
+
+        // 重启：基线为上次累计值，本次计数从零开始
+        let mut second = IngestCheckpointer::load(&path);
+        assert_eq!(second.baseline().total_received(), 100);
+        let ckpt = second.checkpoint_with(run_counts(40, 40));
+        assert_eq!(ckpt.lifetime.sources["file_1"].received, 140);
+        assert_eq!(ckpt.lifetime.sinks["all_sink"].sent, 130);
+        assert_eq!(ckpt.since_start.total_received(), 40);
+        ckpt.save(&path).unwrap();
+
+        let third = IngestCheckpointer::load(&path);
+        assert_eq!(third.baseline().total_sent(), 130);
+    }
+
+    #[test]
+    fn corrupt_checkpoint_starts_from_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ingest_stats.json");
+        IngestCheckpointer::load(&path)
+            .checkpoint_with(run_counts(7, 7))
+            .save(&path)
+            .unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let truncated = &text[..text.len() - 10];
+        std::fs::write(&path, truncated).unwrap();
+
+        let mut ckpt = IngestCheckpointer::load(&path);
+        assert_eq!(ckpt.baseline(), &IngestCounters::default());
+        // 损坏文件被移到一旁保留，随后的落盘不会覆盖它
+        let aside = dir.path().join("ingest_stats.json.corrupt");
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(&aside).unwrap(), truncated);
+        ckpt.checkpoint_with(run_counts(1, 1)).save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&aside).unwrap(), truncated);
+    }
+
+    #[test]
+    fn rule_hits_keep_window_and_last_seen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ingest_stats.json");
+        let with_rules = |rules: &[(&str, u64)]| {
+            let mut c = run_counts(10, 10);
+            for (rule, hits) in rules {
+                c.rules.insert(rule.to_string(), *hits);
+            }
+            c
+        };
+
+        let mut first = IngestCheckpointer::load(&path);
+        let ckpt = first.checkpoint_with(with_rules(&[("/nginx/access", 5), ("/nginx/error", 1)]));
+        let window = ckpt.window_start.clone().expect("window start");
+        let seen_error = ckpt.rule_last_seen["/nginx/error"].clone();
+        ckpt.save(&path).unwrap();
+
+        // 重启后窗口起点不变；只有命中增长的规则刷新最近命中时间
+        std::thread::sleep(Duration::from_millis(5));
+        let mut second = IngestCheckpointer::load(&path);
+        let ckpt = second.checkpoint_with(with_rules(&[("/nginx/access", 2)]));
+        assert_eq!(ckpt.window_start.as_deref(), Some(window.as_str()));
+        assert_eq!(ckpt.lifetime.rules["/nginx/access"], 7);
+        assert_eq!(ckpt.lifetime.rules["/nginx/error"], 1);
+        assert_eq!(ckpt.rule_last_seen["/nginx/error"], seen_error);
+        assert_eq!(ckpt.rule_last_seen["/nginx/access"], ckpt.updated_at);
+    }
+}
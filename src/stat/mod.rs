@@ -8,11 +8,14 @@ use wp_stat::ReportVariant;
 pub use reporting::ReportEngine;
 
 mod alert_system;
+pub mod ingest_checkpoint;
 mod metric_aggregat;
 pub mod metric_collect;
 pub mod metric_set;
+pub mod prometheus;
 pub mod reporting;
 pub mod runtime_metric;
+pub mod trace;
 //pub mod sink_stat;
 
 pub type MonSend = tokio::sync::mpsc::Sender<ReportVariant>;
@@ -0,0 +1,693 @@
+//! Prometheus 指标导出：以文本格式在 `/metrics` 暴露引擎运行期统计。
+//!
+//! - 计数器按「指标名 + 单个标签值」登记在进程内表中，热路径只持有 [`Counter`] 句柄做
+//!   原子累加；查表（加锁）仅发生在首次遇到新的 source/rule/sink 时。
+//! - 通道水位与进程级指标在抓取时现算，不占用数据路径。
+//! - 指标名视为对外契约，见下方 `*_TOTAL` / 水位常量；改名需同步文档与告警规则。
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode, header};
+use hyper_util::rt::TokioIo;
+use oml::language::LookupCounter;
+use once_cell::sync::Lazy;
+use orion_error::{ErrorOwe, ErrorWith};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use wp_cli_core::business::observability::IngestCounters;
+use wp_data_model::cache::CacheStats;
+use wp_data_model::limits::{self, LimitKind};
+use wp_error::run_error::RunResult;
+
+use crate::runtime::actor::command::{ActorCtrlCmd, CmdSubscriber};
+
+/// source 拉取并投递到解析通道的事件数（label: `src_key`）
+pub const SOURCE_RECEIVED_TOTAL: &str = "wparse_source_received_total";
+/// 解析成功（含带残留的部分成功）的事件数（label: `src_key`）
+pub const SOURCE_PARSED_TOTAL: &str = "wparse_source_parsed_total";
+/// 所有规则均未命中的事件数（label: `src_key`）
+pub const SOURCE_MISSED_TOTAL: &str = "wparse_source_missed_total";
+/// 超出 source 限速被丢弃的事件数，仅 `drop` 策略产生（label: `src_key`）
+pub const SOURCE_RATE_LIMITED_TOTAL: &str = "wparse_source_rate_limited_total";
+/// 规则命中数（label: `rule`，即 WPL 规则全名）
+pub const RULE_HITS_TOTAL: &str = "wparse_rule_hits_total";
+/// 写入主 sink 成功的记录数（label: `sink`）
+pub const SINK_SENT_TOTAL: &str = "wparse_sink_sent_total";
+/// 写入 sink 失败的记录数，按发送尝试计（label: `sink`）
+pub const SINK_FAILED_TOTAL: &str = "wparse_sink_failed_total";
+/// 主 sink 故障期间转写到救急文件的记录数（label: `sink`）
+pub const SINK_RESCUED_TOTAL: &str = "wparse_sink_rescued_total";
+/// 因路由 schema 冲突（`on_conflict = "drop"`）被丢弃的记录数（label: `sink`）
+pub const SINK_DROPPED_TOTAL: &str = "wparse_sink_dropped_total";
+/// sink 组转换线程处理的记录数（label: `worker`，形如 `<group>#<idx>`）
+pub const SINK_WORKER_RECORDS_TOTAL: &str = "wparse_sink_worker_records_total";
+/// 因体量限制被截断或丢弃的记录数（label: `kind`，即触发的限制）
+pub const RECORD_LIMIT_HITS_TOTAL: &str = "wparse_record_limit_hits_total";
+/// OML 查询命中缓存的次数（label: `model`）
+pub const OML_LOOKUP_HITS_TOTAL: &str = "wparse_oml_lookup_hits_total";
+/// OML 查询未命中缓存、实际访问知识库的次数（label: `model`）
+pub const OML_LOOKUP_MISSES_TOTAL: &str = "wparse_oml_lookup_misses_total";
+/// 通道当前积压的批次数（label: `channel`）
+pub const CHANNEL_DEPTH: &str = "wparse_channel_depth";
+/// 通道容量（label: `channel`）
+pub const CHANNEL_CAPACITY: &str = "wparse_channel_capacity";
+/// 进程常驻内存
+pub const PROCESS_RESIDENT_BYTES: &str = "wparse_process_resident_memory_bytes";
+/// 进程虚拟内存
+pub const PROCESS_VIRTUAL_BYTES: &str = "wparse_process_virtual_memory_bytes";
+/// 进程运行时长
+pub const PROCESS_UPTIME_SECONDS: &str = "wparse_process_uptime_seconds";
+
+/// 抓取路径
+pub const METRICS_PATH: &str = "/metrics";
+
+/// 单调计数器句柄；克隆共享同一计数
+#[derive(Clone, Default, Debug)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    pub fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// 单标签计数器族
+struct CounterFamily {
+    name: &'static str,
+    help: &'static str,
+    label: &'static str,
+    series: RwLock<BTreeMap<String, Counter>>,
+}
+
+impl CounterFamily {
+    const fn new(name: &'static str, help: &'static str, label: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            label,
+            series: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    fn counter(&self, value: &str) -> Counter {
+        if let Some(c) = self
+            .series
+            .read()
+            .expect("metrics family poisoned")
+            .get(value)
+        {
+            return c.clone();
+        }
+        self.series
+            .write()
+            .expect("metrics family poisoned")
+            .entry(value.to_string())
+            .or_default()
+            .clone()
+    }
+
+    fn values(&self) -> BTreeMap<String, u64> {
+        let series = self.series.read().expect("metrics family poisoned");
+        series.iter().map(|(k, c)| (k.clone(), c.get())).collect()
+    }
+
+    fn render(&self, out: &mut String) {
+        let series = self.series.read().expect("metrics family poisoned");
+        write_header(out, self.name, self.help, "counter");
+        for (value, c) in series.iter() {
+            write_sample(out, self.name, Some((self.label, value)), c.get());
+        }
+    }
+}
+
+static SOURCE_RECEIVED: CounterFamily = CounterFamily::new(
+    SOURCE_RECEIVED_TOTAL,
+    "Events received from source and handed to parse workers.",
+    "src_key",
+);
+static SOURCE_PARSED: CounterFamily = CounterFamily::new(
+    SOURCE_PARSED_TOTAL,
+    "Events parsed by a WPL rule.",
+    "src_key",
+);
+static SOURCE_MISSED: CounterFamily = CounterFamily::new(
+    SOURCE_MISSED_TOTAL,
+    "Events matched by no WPL rule.",
+    "src_key",
+);
+static SOURCE_RATE_LIMITED: CounterFamily = CounterFamily::new(
+    SOURCE_RATE_LIMITED_TOTAL,
+    "Events dropped by the source rate limit (policy = drop).",
+    "src_key",
+);
+static RULE_HITS: CounterFamily =
+    CounterFamily::new(RULE_HITS_TOTAL, "Events parsed per WPL rule.", "rule");
+static SINK_SENT: CounterFamily = CounterFamily::new(
+    SINK_SENT_TOTAL,
+    "Records written to the primary sink.",
+    "sink",
+);
+static SINK_FAILED: CounterFamily = CounterFamily::new(
+    SINK_FAILED_TOTAL,
+    "Records whose write attempt to the sink failed.",
+    "sink",
+);
+static SINK_RESCUED: CounterFamily = CounterFamily::new(
+    SINK_RESCUED_TOTAL,
+    "Records written to the rescue file while the sink was down.",
+    "sink",
+);
+static SINK_DROPPED: CounterFamily = CounterFamily::new(
+    SINK_DROPPED_TOTAL,
+    "Records dropped by the route schema (on_conflict = drop).",
+    "sink",
+);
+static SINK_WORKER_RECORDS: CounterFamily = CounterFamily::new(
+    SINK_WORKER_RECORDS_TOTAL,
+    "Records transformed per sink group worker thread.",
+    "worker",
+);
+
+/// 通道水位探针：返回 (积压, 容量)；通道已关闭时返回 None 并被移除
+type DepthProbe = Box<dyn Fn() -> Option<(usize, usize)> + Send + Sync>;
+
+static CHANNELS: Mutex<Vec<(String, DepthProbe)>> = Mutex::new(Vec::new());
+
+/// 已加载模型的查询命中计数；抓取时读取，同名模型后登记者覆盖
+static OML_MODELS: Mutex<Vec<(String, Arc<LookupCounter>)>> = Mutex::new(Vec::new());
+
+static STARTED_AT: Lazy<Instant> = Lazy::new(Instant::now);
+
+pub fn source_received(src_key: &str) -> Counter {
+    SOURCE_RECEIVED.counter(src_key)
+}
+
+pub fn source_parsed(src_key: &str) -> Counter {
+    SOURCE_PARSED.counter(src_key)
+}
+
+pub fn source_missed(src_key: &str) -> Counter {
+    SOURCE_MISSED.counter(src_key)
+}
+
+pub fn source_rate_limited(src_key: &str) -> Counter {
+    SOURCE_RATE_LIMITED.counter(src_key)
+}
+
+pub fn rule_hits(rule: &str) -> Counter {
+    RULE_HITS.counter(rule)
+}
+
+pub fn sink_worker_records(group: &str, worker: usize) -> Counter {
+    SINK_WORKER_RECORDS.counter(&format!("{}#{}", group, worker))
+}
+
+/// 本进程启动以来的 source/sink/规则命中计数快照（累计统计落盘使用）
+pub fn ingest_snapshot() -> IngestCounters {
+    let mut out = IngestCounters::default();
+    for (family, pick) in [
+        (&SOURCE_RECEIVED, 0usize),
+        (&SOURCE_PARSED, 1),
+        (&SOURCE_MISSED, 2),
+    ] {
+        for (key, v) in family.values() {
+            let e = out.sources.entry(key).or_default();
+            match pick {
+                0 => e.received = v,
+                1 => e.parsed = v,
+                _ => e.missed = v,
+            }
+        }
+    }
+    for (family, pick) in [
+        (&SINK_SENT, 0usize),
+        (&SINK_FAILED, 1),
+        (&SINK_RESCUED, 2),
+        (&SINK_DROPPED, 3),
+    ] {
+        for (key, v) in family.values() {
+            let e = out.sinks.entry(key).or_default();
+            match pick {
+                0 => e.sent = v,
+                1 => e.failed = v,
+                2 => e.rescued = v,
+                _ => e.dropped = v,
+            }
+        }
+    }
+    out.rules = RULE_HITS.values();
+    out
+}
+
+/// 单个 sink 的发送计数句柄
+#[derive(Clone, Debug)]
+pub struct SinkCounters {
+    pub sent: Counter,
+    pub failed: Counter,
+    pub rescued: Counter,
+    pub dropped: Counter,
+}
+
+impl SinkCounters {
+    pub fn new(sink: &str) -> Self {
+        Self {
+            sent: SINK_SENT.counter(sink),
+            failed: SINK_FAILED.counter(sink),
+            rescued: SINK_RESCUED.counter(sink),
+            dropped: SINK_DROPPED.counter(sink),
+        }
+    }
+}
+
+/// 解析线程本地的计数句柄缓存：同一 source/rule 只查一次全局表
+#[derive(Default, Clone)]
+pub struct ParseCounters {
+    parsed: HashMap<String, Counter>,
+    missed: HashMap<String, Counter>,
+    rules: HashMap<String, Counter>,
+}
+
+impl ParseCounters {
+    pub fn on_parsed(&mut self, src_key: &str, rule: &str) {
+        cached(&mut self.parsed, src_key, source_parsed).inc();
+        cached(&mut self.rules, rule, rule_hits).inc();
+    }
+
+    pub fn on_missed(&mut self, src_key: &str) {
+        cached(&mut self.missed, src_key, source_missed).inc();
+    }
+}
+
+fn cached<'a>(
+    cache: &'a mut HashMap<String, Counter>,
+    key: &str,
+    make: fn(&str) -> Counter,
+) -> &'a Counter {
+    if !cache.contains_key(key) {
+        cache.insert(key.to_string(), make(key));
+    }
+    &cache[key]
+}
+
+/// 登记需要暴露水位的通道；仅持有弱引用，不影响通道关闭。同名通道后登记者覆盖
+pub fn watch_channel<T: Send + 'static>(name: impl Into<String>, sender: &mpsc::Sender<T>) {
+    let weak = sender.downgrade();
+    let probe: DepthProbe = Box::new(move || {
+        let s = weak.upgrade()?;
+        Some((s.max_capacity() - s.capacity(), s.max_capacity()))
+    });
+    let name = name.into();
+    let mut channels = CHANNELS.lock().expect("metrics channels poisoned");
+    channels.retain(|(n, _)| *n != name);
+    channels.push((name, probe));
+}
+
+/// 登记模型的查询命中计数（模型加载时调用）
+pub fn watch_oml_model(name: &str, counter: Arc<LookupCounter>) {
+    let mut models = OML_MODELS.lock().expect("metrics models poisoned");
+    models.retain(|(n, _)| n != name);
+    models.push((name.to_string(), counter));
+}
+
+/// 按 Prometheus 文本格式（0.0.4）渲染全部指标
+pub fn render() -> String {
+    let mut out = String::new();
+    for family in [
+        &SOURCE_RECEIVED,
+        &SOURCE_PARSED,
+        &SOURCE_MISSED,
+        &SOURCE_RATE_LIMITED,
+        &RULE_HITS,
+        &SINK_SENT,
+        &SINK_FAILED,
+        &SINK_RESCUED,
+        &SINK_DROPPED,
+        &SINK_WORKER_RECORDS,
+    ] {
+        family.render(&mut out);
+    }
+    render_record_limits(&mut out);
+    render_oml_lookups(&mut out);
+    render_channels(&mut out);
+    render_process(&mut out);
+    out
+}
+
+fn render_record_limits(out: &mut String) {
+    write_header(
+        out,
+        RECORD_LIMIT_HITS_TOTAL,
+        "Records truncated or dropped by record size limits.",
+        "counter",
+    );
+    for kind in LimitKind::ALL {
+        write_sample(
+            out,
+            RECORD_LIMIT_HITS_TOTAL,
+            Some(("kind", kind.as_str())),
+            limits::hits(kind),
+        );
+    }
+}
+
+fn render_oml_lookups(out: &mut String) {
+    let mut models: Vec<(String, CacheStats)> = OML_MODELS
+        .lock()
+        .expect("metrics models poisoned")
+        .iter()
+        .map(|(name, counter)| (name.clone(), counter.snapshot()))
+        .collect();
+    models.sort_by(|a, b| a.0.cmp(&b.0));
+
+    write_header(
+        out,
+        OML_LOOKUP_HITS_TOTAL,
+        "OML lookups answered from cache.",
+        "counter",
+    );
+    for (name, stats) in &models {
+        write_sample(
+            out,
+            OML_LOOKUP_HITS_TOTAL,
+            Some(("model", name)),
+            stats.hits,
+        );
+    }
+    write_header(
+        out,
+        OML_LOOKUP_MISSES_TOTAL,
+        "OML lookups that queried the knowledge base.",
+        "counter",
+    );
+    for (name, stats) in &models {
+        write_sample(
+            out,
+            OML_LOOKUP_MISSES_TOTAL,
+            Some(("model", name)),
+            stats.misses,
+        );
+    }
+}
+
+fn render_channels(out: &mut String) {
+    let mut channels = CHANNELS.lock().expect("metrics channels poisoned");
+    let mut depths = Vec::with_capacity(channels.len());
+    channels.retain(|(name, probe)| match probe() {
+        Some((depth, cap)) => {
+            depths.push((name.clone(), depth, cap));
+            true
+        }
+        None => false,
+    });
+    drop(channels);
+    depths.sort();
+
+    write_header(
+        out,
+        CHANNEL_DEPTH,
+        "Batches queued in the channel.",
+        "gauge",
+    );
+    for (name, depth, _) in &depths {
+        write_sample(out, CHANNEL_DEPTH, Some(("channel", name)), *depth as u64);
+    }
+    write_header(
+        out,
+        CHANNEL_CAPACITY,
+        "Channel capacity in batches.",
+        "gauge",
+    );
+    for (name, _, cap) in &depths {
+        write_sample(out, CHANNEL_CAPACITY, Some(("channel", name)), *cap as u64);
+    }
+}
+
+fn render_process(out: &mut String) {
+    write_header(
+        out,
+        PROCESS_UPTIME_SECONDS,
+        "Seconds since the metrics registry was initialised.",
+        "gauge",
+    );
+    write_sample(
+        out,
+        PROCESS_UPTIME_SECONDS,
+        None,
+        STARTED_AT.elapsed().as_secs(),
+    );
+
+    let Ok(pid) = sysinfo::get_current_pid() else {
+        return;
+    };
+    let mut sys = System::new_with_specifics(
+        RefreshKind::nothing().with_processes(ProcessRefreshKind::nothing().with_memory()),
+    );
+    let _ = sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    if let Some(p) = sys.process(pid) {
+        write_header(
+            out,
+            PROCESS_RESIDENT_BYTES,
+            "Resident memory size in bytes.",
+            "gauge",
+        );
+        write_sample(out, PROCESS_RESIDENT_BYTES, None, p.memory());
+        write_header(
+            out,
+            PROCESS_VIRTUAL_BYTES,
+            "Virtual memory size in bytes.",
+            "gauge",
+        );
+        write_sample(out, PROCESS_VIRTUAL_BYTES, None, p.virtual_memory());
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn write_sample(out: &mut String, name: &str, label: Option<(&str, &str)>, value: u64) {
+    match label {
+        Some((key, val)) => {
+            let _ = writeln!(
+                out,
+                "{}{{{}=\"{}\"}} {}",
+                name,
+                key,
+                escape_label(val),
+                value
+            );
+        }
+        None => {
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+    }
+}
+
+fn escape_label(val: &str) -> String {
+    let mut escaped = String::with_capacity(val.len());
+    for ch in val.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+async fn handle(req: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let resp = if req.method() != Method::GET {
+        let mut resp = Response::new(Full::new(Bytes::new()));
+        *resp.status_mut() = StatusCode::METHOD_NOT_ALLOWED;
+        resp
+    } else if req.uri().path() == super::trace::TRACE_PATH {
+        match super::trace::render_json(req.uri().query()) {
+            Ok(Some(body)) => {
+                let mut resp = Response::new(Full::new(Bytes::from(body)));
+                resp.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    header::HeaderValue::from_static("application/json"),
+                );
+                resp
+            }
+            Ok(None) => {
+                let mut resp = Response::new(Full::new(Bytes::new()));
+                *resp.status_mut() = StatusCode::NOT_FOUND;
+                resp
+            }
+            Err(msg) => {
+                let mut resp = Response::new(Full::new(Bytes::from(msg)));
+                *resp.status_mut() = StatusCode::BAD_REQUEST;
+                resp
+            }
+        }
+    } else if req.uri().path() != METRICS_PATH {
+        let mut resp = Response::new(Full::new(Bytes::new()));
+        *resp.status_mut() = StatusCode::NOT_FOUND;
+        resp
+    } else {
+        let mut resp = Response::new(Full::new(Bytes::from(render())));
+        resp.headers_mut().insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("text/plain; version=0.0.4; charset=utf-8"),
+        );
+        resp
+    };
+    Ok(resp)
+}
+
+/// 启动指标监听；返回实际绑定地址（便于 `:0` 端口）与监听任务句柄，收到 Stop 命令后退出
+pub async fn spawn_metrics_server(
+    listen: &str,
+    mut cmd_sub: CmdSubscriber,
+) -> RunResult<(SocketAddr, JoinHandle<()>)> {
+    Lazy::force(&STARTED_AT);
+    let listener = TcpListener::bind(listen)
+        .await
+        .owe_sys()
+        .want("bind metrics listener")
+        .with(listen)?;
+    let addr = listener
+        .local_addr()
+        .owe_sys()
+        .want("metrics listener addr")?;
+    info_ctrl!(
+        "prometheus metrics listening on http://{}{}",
+        addr,
+        METRICS_PATH
+    );
+    let task = tokio::spawn(async move {
+        loop {
+            let accepted = tokio::select! {
+                cmd = cmd_sub.recv() => match cmd {
+                    Ok(ActorCtrlCmd::Stop(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                },
+                accepted = listener.accept() => accepted,
+            };
+            let (stream, peer) = match accepted {
+                Ok(pair) => pair,
+                Err(e) => {
+                    warn_ctrl!("metrics listener accept failed: {}", e);
+                    continue;
+                }
+            };
+            tokio::spawn(async move {
+                let conn = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service_fn(handle));
+                if let Err(e) = conn.await {
+                    debug_ctrl!("metrics connection from {} closed: {}", peer, e);
+                }
+            });
+        }
+        info_ctrl!("prometheus metrics listener stopped");
+    });
+    Ok((addr, task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::actor::TaskGroup;
+    use crate::runtime::actor::signal::ShutdownCmd;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    async fn scrape(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.expect("connect");
+        let req = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            path, addr
+        );
+        stream.write_all(req.as_bytes()).await.expect("write");
+        let mut resp = String::new();
+        stream.read_to_string(&mut resp).await.expect("read");
+        resp
+    }
+
+    #[tokio::test]
+    async fn scrape_exposes_key_series() {
+        source_received("prom_test_src").add(5);
+        let mut parse = ParseCounters::default();
+        parse.on_parsed("prom_test_src", "/prom/nginx");
+        parse.on_parsed("prom_test_src", "/prom/nginx");
+        parse.on_missed("prom_test_src");
+        let sink = SinkCounters::new("prom_grp/file");
+        sink.sent.add(2);
+        sink.failed.inc();
+        sink.rescued.inc();
+        sink.dropped.add(4);
+        let (tx, _rx) = mpsc::channel::<u8>(8);
+        tx.try_send(1).unwrap();
+        watch_channel("prom-parse-0", &tx);
+        let lookups = Arc::new(LookupCounter::default());
+        lookups.record(CacheStats { hits: 3, misses: 1 });
+        watch_oml_model("prom_model", lookups);
+
+        let mut group = TaskGroup::new("metrics", ShutdownCmd::Immediate);
+        let (addr, task) = spawn_metrics_server("127.0.0.1:0", group.subscribe())
+            .await
+            .expect("bind");
+        group.append(task);
+        let resp = scrape(addr, METRICS_PATH).await;
+        assert!(resp.starts_with("HTTP/1.1 200"), "{resp}");
+        assert!(resp.contains("text/plain; version=0.0.4"));
+        for series in [
+            "wparse_source_received_total{src_key=\"prom_test_src\"} 5",
+            "wparse_source_parsed_total{src_key=\"prom_test_src\"} 2",
+            "wparse_source_missed_total{src_key=\"prom_test_src\"} 1",
+            "wparse_rule_hits_total{rule=\"/prom/nginx\"} 2",
+            "wparse_sink_sent_total{sink=\"prom_grp/file\"} 2",
+            "wparse_sink_failed_total{sink=\"prom_grp/file\"} 1",
+            "wparse_sink_rescued_total{sink=\"prom_grp/file\"} 1",
+            "wparse_sink_dropped_total{sink=\"prom_grp/file\"} 4",
+            "wparse_channel_depth{channel=\"prom-parse-0\"} 1",
+            "wparse_channel_capacity{channel=\"prom-parse-0\"} 8",
+            "wparse_oml_lookup_hits_total{model=\"prom_model\"} 3",
+            "wparse_oml_lookup_misses_total{model=\"prom_model\"} 1",
+            "# TYPE wparse_record_limit_hits_total counter",
+            "# TYPE wparse_process_uptime_seconds gauge",
+        ] {
+            assert!(resp.contains(series), "missing `{series}` in:\n{resp}");
+        }
+
+        let resp = scrape(addr, "/other").await;
+        assert!(resp.starts_with("HTTP/1.1 404"), "{resp}");
+        group.wait_grace_down(None).await.expect("listener stops");
+    }
+
+    #[test]
+    fn closed_channel_is_dropped_and_labels_escaped() {
+        let (tx, rx) = mpsc::channel::<u8>(4);
+        watch_channel("prom-closed", &tx);
+        drop(tx);
+        drop(rx);
+        assert!(!render().contains("prom-closed"));
+
+        rule_hits("a\"b\\c").inc();
+        assert!(render().contains("wparse_rule_hits_total{rule=\"a\\\"b\\\\c\"} 1"));
+    }
+}
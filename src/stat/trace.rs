@@ -0,0 +1,261 @@
+//! 记录级追踪：以事件 ID 作为 `wp_trace_id`，对采样命中的记录在各阶段打点。
+//!
+//! - 事件 ID 在 source 拉取时分配，随 `SinkRecUnit` 贯穿解析、转换与下发，miss 输出中也会标注，
+//!   因此各阶段无需额外携带追踪字段。
+//! - 是否采样由 trace id 的哈希决定，同一记录在 source → WPL → OML → sink 各阶段结论一致；
+//!   未启用或未命中时打点只是一次原子读与一次哈希比较。
+//! - 打点写入进程内环形缓冲（按记录数淘汰最早的追踪），经指标服务的 `/debug/trace` 查询。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use serde_derive::Serialize;
+
+/// miss 通道输出中标注追踪 ID 的字段名
+pub const TRACE_ID_FIELD: &str = "wp_trace_id";
+/// 追踪查询路径（挂在指标服务上）
+pub const TRACE_PATH: &str = "/debug/trace";
+
+const PPM: u64 = 1_000_000;
+// 单条追踪最多保留的打点数，防止多 sink 扇出时无限增长
+const SPAN_MAX: usize = 64;
+const RECENT_DEFAULT: usize = 100;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SAMPLE_PPM: AtomicU64 = AtomicU64::new(0);
+static BUFFER: Lazy<Mutex<TraceBuffer>> = Lazy::new(|| Mutex::new(TraceBuffer::new(4096)));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStage {
+    /// source 拉取入 pending（tag: src_key）
+    Intake,
+    /// 进入 WPL 解析（tag: src_key）
+    ParseStart,
+    /// 解析命中（tag: WPL 规则名）
+    ParseEnd,
+    /// 所有规则均未命中，进入 miss 通道（tag: src_key）
+    Miss,
+    /// 进入 OML 转换（tag: 模型名）
+    TransformStart,
+    /// OML 转换结束（tag: 模型名）
+    TransformEnd,
+    /// 进入 sink 缓冲（tag: sink 名）
+    SinkEnqueue,
+    /// sink 确认写出（tag: sink 名）
+    SinkAck,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceSpan {
+    pub stage: TraceStage,
+    /// Unix 时间戳（微秒）
+    pub at_us: u64,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceRecord {
+    pub trace_id: u64,
+    pub spans: Vec<TraceSpan>,
+}
+
+struct TraceBuffer {
+    capacity: usize,
+    order: VecDeque<u64>,
+    records: HashMap<u64, TraceRecord>,
+}
+
+impl TraceBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            order: VecDeque::new(),
+            records: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, trace_id: u64, span: TraceSpan) {
+        if let Some(record) = self.records.get_mut(&trace_id) {
+            if record.spans.len() < SPAN_MAX {
+                record.spans.push(span);
+            }
+            return;
+        }
+        while self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.records.remove(&oldest);
+            }
+        }
+        self.order.push_back(trace_id);
+        self.records.insert(
+            trace_id,
+            TraceRecord {
+                trace_id,
+                spans: vec![span],
+            },
+        );
+    }
+}
+
+/// 启用/关闭追踪；`sample_rate` 为 None 表示关闭，取值会被限制在 `[0, 1]`
+pub fn configure(sample_rate: Option<f64>, capacity: usize) {
+    let mut buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    *buffer = TraceBuffer::new(capacity);
+    match sample_rate {
+        Some(rate) => {
+            let ppm = (rate.clamp(0.0, 1.0) * PPM as f64).round() as u64;
+            SAMPLE_PPM.store(ppm, Ordering::Relaxed);
+            ENABLED.store(true, Ordering::Relaxed);
+            info_ctrl!(
+                "record trace enabled: sample_rate={}, capacity={}",
+                rate,
+                capacity
+            );
+        }
+        None => {
+            ENABLED.store(false, Ordering::Relaxed);
+            SAMPLE_PPM.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 该 trace id 是否被采样
+#[inline]
+pub fn sampled(trace_id: u64) -> bool {
+    enabled() && mix(trace_id) % PPM < SAMPLE_PPM.load(Ordering::Relaxed)
+}
+
+/// 为采样命中的记录打点；未命中时直接返回
+#[inline]
+pub fn span(trace_id: u64, stage: TraceStage, tag: &str) {
+    if sampled(trace_id) {
+        record_span(trace_id, stage, tag);
+    }
+}
+
+fn record_span(trace_id: u64, stage: TraceStage, tag: &str) {
+    let at_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0);
+    let span = TraceSpan {
+        stage,
+        at_us,
+        tag: tag.to_string(),
+    };
+    BUFFER
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(trace_id, span);
+}
+
+pub fn lookup(trace_id: u64) -> Option<TraceRecord> {
+    BUFFER
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .records
+        .get(&trace_id)
+        .cloned()
+}
+
+/// 最近的追踪记录（新的在前）
+pub fn recent(limit: usize) -> Vec<TraceRecord> {
+    let buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    buffer
+        .order
+        .iter()
+        .rev()
+        .take(limit)
+        .filter_map(|id| buffer.records.get(id).cloned())
+        .collect()
+}
+
+/// `/debug/trace` 查询：`?id=<trace_id>` 返回单条（不存在为 `Ok(None)`），否则按 `?limit=<n>` 返回最近记录；
+/// id 不是合法的 trace id 时返回 `Err`（错误说明）
+pub fn render_json(query: Option<&str>) -> Result<Option<String>, String> {
+    let mut id = None;
+    let mut limit = RECENT_DEFAULT;
+    for pair in query.unwrap_or_default().split('&') {
+        match pair.split_once('=') {
+            Some(("id", v)) => match v.parse::<u64>() {
+                Ok(v) => id = Some(v),
+                Err(_) => return Err(format!("invalid trace id '{}'", v)),
+            },
+            Some(("limit", v)) => limit = v.parse().unwrap_or(RECENT_DEFAULT),
+            _ => {}
+        }
+    }
+    Ok(match id {
+        Some(id) => lookup(id).and_then(|rec| serde_json::to_string(&rec).ok()),
+        None => serde_json::to_string(&recent(limit)).ok(),
+    })
+}
+
+// splitmix64 终混：事件 ID 单调递增，需打散后再取模
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// 追踪配置为进程级全局状态，涉及 `configure` 的测试需串行
+#[cfg(test)]
+pub(crate) static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_recorded_only_for_sampled_ids() {
+        let _guard = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        configure(Some(0.5), 8);
+        let hit = (1u64..).find(|id| sampled(*id)).unwrap();
+        let miss = (1u64..).find(|id| !sampled(*id)).unwrap();
+        span(hit, TraceStage::Intake, "src");
+        span(hit, TraceStage::ParseEnd, "rule");
+        span(miss, TraceStage::Intake, "src");
+
+        let record = lookup(hit).expect("sampled record traced");
+        let stages: Vec<_> = record.spans.iter().map(|s| s.stage).collect();
+        assert_eq!(stages, vec![TraceStage::Intake, TraceStage::ParseEnd]);
+        assert!(record.spans[0].at_us <= record.spans[1].at_us);
+        assert!(lookup(miss).is_none());
+
+        let json = render_json(Some(&format!("id={}", hit))).unwrap().unwrap();
+        assert!(json.contains("\"parse_end\""));
+        assert_eq!(render_json(Some(&format!("id={}", miss))), Ok(None));
+        assert!(render_json(Some("id=abc")).is_err());
+        assert!(render_json(Some("id=-1")).is_err());
+
+        // 关闭后不再记录
+        configure(None, 8);
+        assert!(!sampled(hit));
+        span(hit, TraceStage::Intake, "src");
+        assert!(lookup(hit).is_none());
+    }
+
+    #[test]
+    fn ring_buffer_evicts_oldest() {
+        let _guard = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        configure(Some(1.0), 2);
+        for id in 1..=3 {
+            span(id, TraceStage::Intake, "src");
+        }
+        assert!(lookup(1).is_none());
+        let ids: Vec<u64> = recent(10).iter().map(|r| r.trace_id).collect();
+        assert_eq!(ids, vec![3, 2]);
+        configure(None, 2);
+    }
+}
@@ -65,6 +65,7 @@ async fn create_tcp_source(key: &str) -> (TcpSyslogSource, tokio::sync::broadcas
         "127.0.0.1:0".to_string(),
         4096,
         wp_engine::sources::tcp::FramingMode::Line,
+        wp_engine::sources::tcp::ConnLimits::default(),
         registry,
         rx,
     )
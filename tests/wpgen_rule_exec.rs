@@ -14,6 +14,7 @@ use orion_variate::EnvDict;
 use wp_conf::test_support::ForTest;
 use wp_engine::facade::config::{WPGEN_TOML, WarpConf, build_sink_target};
 use wp_engine::facade::generator::{GenGRA, RuleGRA, load_gen_confs, rule_gen_run};
+use wp_engine::runtime::generator::run_rule_direct_outputs;
 
 fn unique_tmp(prefix: &str) -> PathBuf {
     let now = std::time::SystemTime::now()
@@ -106,6 +107,7 @@ output = "stdout"
             stat_print: false,
             rescue: work.join("data/rescue").display().to_string(),
             speed_profile: None,
+            field_pools: None,
         },
     };
 
@@ -139,3 +141,95 @@ fn wpgen_rule_missing_rule_files_returns_error() {
     let msg = format!("{}", res.err().unwrap());
     assert!(msg.contains("gen rule conf file is empty"), "msg={}", msg);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn wpgen_rule_two_outputs_split_by_ratio() {
+    wp_engine::sinks::register_builtin_sinks();
+    let work = unique_tmp("wpgen_rule_outputs");
+    let cm = WarpConf::new(&work);
+    let out_dir = std::env::current_dir()
+        .unwrap()
+        .join(work.join("data/out_dat"));
+
+    let connectors = r#"
+[[connectors]]
+id = "file_raw_sink"
+type = "file"
+allow_override = ["base", "file"]
+[connectors.params]
+fmt = "raw"
+base = "./data/out_dat"
+file = "out.dat"
+"#;
+    write_file(
+        work.join("connectors/sink.d/file_raw_sink.toml"),
+        connectors,
+    );
+
+    // 90% to the engine-facing sink, 10% kept locally as ground truth
+    let wpgen_toml = format!(
+        r#"version = "1.0"
+
+[generator]
+mode = "rule"
+count = 1000
+speed = 0
+parallel = 1
+
+[[outputs]]
+name = "to_engine"
+connect = "file_raw_sink"
+params = {{ base = "{dir}", file = "engine.dat" }}
+ratio = 0.9
+
+[[outputs]]
+name = "ground_truth"
+connect = "file_raw_sink"
+params = {{ base = "{dir}", file = "truth.dat" }}
+ratio = 0.1
+
+[logging]
+level = "info"
+output = "stdout"
+"#,
+        dir = out_dir.display()
+    );
+    write_file(cm.config_path(WPGEN_TOML), &wpgen_toml);
+
+    let rule_root = work.join("models/wpl/smoke");
+    write_file(
+        rule_root.join("gen_rule.wpl"),
+        r#"package /smoke { rule r1 { (digit,ip,chars) } }"#,
+    );
+    write_file(rule_root.join("gen_field.toml"), "items = {}\n");
+
+    let rt = cm
+        .load_wpgen_config(WPGEN_TOML, &EnvDict::test_default())
+        .expect("load wpgen resolved");
+    assert_eq!(rt.outputs.len(), 2);
+    let gar = GenGRA {
+        total_line: Some(1000),
+        gen_speed: 0,
+        parallel: 1,
+        rescue: work.join("data/rescue").display().to_string(),
+        ..Default::default()
+    };
+    run_rule_direct_outputs(
+        rule_root.to_str().unwrap(),
+        &gar,
+        &rt.outputs,
+        0,
+        &EnvDict::test_default(),
+    )
+    .await
+    .expect("generator should succeed");
+
+    let lines = |name: &str| {
+        fs::read_to_string(out_dir.join(name))
+            .unwrap_or_default()
+            .lines()
+            .count()
+    };
+    assert_eq!(lines("engine.dat"), 900);
+    assert_eq!(lines("truth.dat"), 100);
+}